@@ -1,7 +1,19 @@
+mod activity;
+mod auth_state;
+mod build;
 mod client;
 mod codec;
+mod comments;
+mod detail_field;
 mod fixture;
 mod live;
+mod provider;
+mod pull_request;
+mod query;
+mod scheduling;
+mod scope;
+mod states;
+mod web;
 mod work_item;
 
 use std::path::PathBuf;
@@ -10,9 +22,24 @@ use anyhow::Result;
 
 use crate::config::Config;
 
+pub use activity::{ActivityEntry, FieldChange};
+pub use build::Build;
 pub use client::AzureDevOpsClient;
-pub use work_item::{FieldFormat, WorkItem};
+pub use comments::Comment;
+pub use detail_field::{DEFAULT_DETAIL_FIELDS, DetailField};
+pub use live::{is_transient_error, requests_sent};
 #[cfg(test)]
+pub use provider::MockWorkItemProvider;
+pub use provider::WorkItemProvider;
+pub use pull_request::{
+    CompletionOptions, MergeStrategy, NewPullRequest, PullRequest, RepositoryRef, Reviewer, Vote,
+    organization_url_from_remote,
+};
+pub use scheduling::WorkHours;
+pub use scope::PatScope;
+pub use states::StateTransition;
+pub use web::WebLinks;
+pub use work_item::{FieldFormat, WorkItem, urgency_color};
 pub use work_item::{RichTextField, WorkItemState, WorkItemType};
 
 pub fn work_item_client() -> Result<AzureDevOpsClient> {
@@ -20,5 +47,5 @@ pub fn work_item_client() -> Result<AzureDevOpsClient> {
         return AzureDevOpsClient::new_fixture(PathBuf::from(path));
     }
 
-    AzureDevOpsClient::new_live(&Config::load()?)
+    AzureDevOpsClient::new_live(&Config::load_with_overrides()?)
 }
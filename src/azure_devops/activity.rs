@@ -0,0 +1,134 @@
+//! Recent activity: work items assigned to the user or linked to their
+//! branches that changed lately, each with what its last change did.
+
+use super::work_item::WorkItem;
+
+/// Work items the activity query returns at most
+pub(super) const ACTIVITY_LIMIT: usize = 50;
+
+/// Fields whose changes the activity feed reports, with their labels
+const TRACKED_FIELDS: &[(&str, &str)] = &[
+    ("System.State", "State"),
+    ("System.AssignedTo", "Assigned To"),
+    ("System.Title", "Title"),
+    ("System.IterationPath", "Iteration"),
+    ("Microsoft.VSTS.Common.Priority", "Priority"),
+];
+
+/// A work item that changed recently.
+#[derive(Debug, Clone)]
+pub struct ActivityEntry {
+    /// The work item as of its last change
+    pub work_item: WorkItem,
+    /// What the last change did to the tracked fields; empty for a new work
+    /// item or a change to other fields
+    pub changes: Vec<FieldChange>,
+}
+
+/// One field set by a change, with its values before and after.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl ActivityEntry {
+    /// `work_item` with the tracked fields that differ from `previous`, its
+    /// revision before the last change.
+    pub(super) fn new(work_item: WorkItem, previous: Option<&WorkItem>) -> Self {
+        let changes = previous
+            .map(|previous| {
+                TRACKED_FIELDS
+                    .iter()
+                    .filter_map(|&(reference, field)| {
+                        let old = previous.fields.get(reference);
+                        let new = work_item.fields.get(reference);
+                        (old != new).then(|| FieldChange {
+                            field,
+                            old: old.cloned(),
+                            new: new.cloned(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { work_item, changes }
+    }
+
+    /// Whether the last change created the work item.
+    pub fn is_new(&self) -> bool {
+        self.work_item.revision() == Some(1)
+    }
+}
+
+/// WIQL for work items changed in the last `days` days that are assigned to
+/// the user or are one of `linked_ids`, most recently changed first.
+pub(super) fn activity_query(linked_ids: &[u32], days: u32) -> String {
+    let mut mine = "[System.AssignedTo] = @Me".to_string();
+    if !linked_ids.is_empty() {
+        let ids: Vec<String> = linked_ids.iter().map(u32::to_string).collect();
+        mine = format!("{mine} OR [System.Id] IN ({})", ids.join(", "));
+    }
+    format!(
+        "SELECT [System.Id] FROM WorkItems WHERE ({mine}) \
+         AND [System.ChangedDate] >= @Today - {days} \
+         ORDER BY [System.ChangedDate] DESC"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure_devops::{WorkItemState, WorkItemType};
+    use std::collections::BTreeMap;
+
+    fn revision(rev: u32, state: &str, assigned_to: &str) -> WorkItem {
+        WorkItem {
+            id: 5,
+            title: "Checkout".to_string(),
+            work_item_type: WorkItemType::Task,
+            state: state.parse().unwrap(),
+            assigned_to: Some(assigned_to.to_string()),
+            url: None,
+            tags: vec![],
+            rich_text_fields: vec![],
+            fields: BTreeMap::from([
+                ("System.Rev".to_string(), rev.to_string()),
+                ("System.State".to_string(), state.to_string()),
+                ("System.AssignedTo".to_string(), assigned_to.to_string()),
+                ("System.Title".to_string(), "Checkout".to_string()),
+            ]),
+        }
+    }
+
+    #[test]
+    fn entry_lists_the_tracked_fields_the_last_change_set() {
+        let previous = revision(3, "Active", "Ada Lovelace");
+        let entry = ActivityEntry::new(revision(4, "Resolved", "Ada Lovelace"), Some(&previous));
+        assert_eq!(
+            entry.changes,
+            [FieldChange {
+                field: "State",
+                old: Some("Active".to_string()),
+                new: Some("Resolved".to_string()),
+            }]
+        );
+        assert!(matches!(entry.work_item.state, WorkItemState::Resolved));
+
+        let created = ActivityEntry::new(revision(1, "New", "Ada Lovelace"), None);
+        assert!(created.is_new());
+        assert!(created.changes.is_empty());
+    }
+
+    #[test]
+    fn query_covers_assigned_and_linked_work_items() {
+        assert_eq!(
+            activity_query(&[12, 40], 7),
+            "SELECT [System.Id] FROM WorkItems WHERE ([System.AssignedTo] = @Me \
+             OR [System.Id] IN (12, 40)) AND [System.ChangedDate] >= @Today - 7 \
+             ORDER BY [System.ChangedDate] DESC"
+        );
+        assert!(!activity_query(&[], 3).contains("IN ("));
+    }
+}
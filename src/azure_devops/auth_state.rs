@@ -0,0 +1,158 @@
+//! Remembers when each PAT last authenticated, so a PAT that stops working
+//! is reported as likely expired instead of as a generic auth failure.
+//!
+//! Azure DevOps does not let a PAT read its own expiry date (the PAT
+//! lifecycle API requires an Entra ID token), so the last successful day is
+//! the best evidence available. Entries are keyed by organization and a hash
+//! of the PAT; the token itself is never written.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use directories::ProjectDirs;
+use sha2::{Digest, Sha256};
+
+use crate::timestamp::format_date;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// What this process last recorded per [`state_key`], so repeated successes
+/// do not re-read the state file.
+static RECORDED: Mutex<BTreeMap<String, i64>> = Mutex::new(BTreeMap::new());
+
+/// Where the state lives for the real client. Callers pass the path in so
+/// tests can point it elsewhere or turn recording off.
+pub(super) fn state_path() -> Option<PathBuf> {
+    let proj_dirs = ProjectDirs::from("", "", "cazdo")?;
+    Some(proj_dirs.cache_dir().join("auth-state.json"))
+}
+
+/// Unix timestamps of the last successful request, by [`state_key`].
+fn read_state(path: &Path) -> BTreeMap<String, i64> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// The hash must be stable across Rust releases, or an upgrade would forget
+/// every recorded PAT.
+fn state_key(organization_url: &str, pat: &str) -> String {
+    format!(
+        "{}#{:x}",
+        crate::redact::redact(organization_url),
+        Sha256::digest(pat.as_bytes())
+    )
+}
+
+/// When `pat` last authenticated against `organization_url`, if known.
+pub(super) fn last_success(path: Option<&Path>, organization_url: &str, pat: &str) -> Option<i64> {
+    read_state(path?)
+        .get(&state_key(organization_url, pat))
+        .copied()
+}
+
+/// Record a successful request. The file is written at most once a day per
+/// PAT; failures are ignored since this only improves an error message.
+pub(super) fn record_success(path: Option<&Path>, organization_url: &str, pat: &str) {
+    let Some(path) = path else {
+        return;
+    };
+    let now = Utc::now().timestamp();
+    let key = state_key(organization_url, pat);
+    let mut recorded = RECORDED.lock().unwrap_or_else(|error| error.into_inner());
+    if !needs_update(recorded.get(&key).copied(), now) {
+        return;
+    }
+    let mut state = read_state(path);
+    let previous = state.get(&key).copied();
+    if !needs_update(previous, now) {
+        recorded.insert(key, previous.unwrap_or(now));
+        return;
+    }
+    state.insert(key.clone(), now);
+    recorded.insert(key, now);
+
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(content) = serde_json::to_string_pretty(&state) {
+        let _ = fs::write(path, content);
+    }
+}
+
+fn needs_update(previous: Option<i64>, now: i64) -> bool {
+    previous.is_none_or(|previous| previous / SECONDS_PER_DAY != now / SECONDS_PER_DAY)
+}
+
+/// The error for an auth failure of a PAT that used to work.
+pub(super) fn expired_pat_error(organization_url: &str, last_success: i64) -> anyhow::Error {
+    let date = DateTime::<Utc>::from_timestamp(last_success, 0)
+        .map(format_date)
+        .unwrap_or_else(|| "an earlier run".to_string());
+    anyhow::anyhow!(
+        "PAT likely expired after {} (when it last worked) — regenerate at {}/_usersSettings/tokens and update CAZDO_PAT or [azure_devops].pat.",
+        date,
+        organization_url.trim_end_matches('/')
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_success_once_per_day() {
+        let day = 19_850 * SECONDS_PER_DAY;
+
+        assert!(needs_update(None, day));
+        assert!(!needs_update(Some(day + 60), day + 3_600));
+        assert!(needs_update(Some(day + 60), day + SECONDS_PER_DAY));
+    }
+
+    #[test]
+    fn keys_entries_by_organization_and_pat_without_storing_it() {
+        let key = state_key("https://dev.azure.com/org", "secret-pat");
+
+        assert!(key.starts_with("https://dev.azure.com/org#"));
+        assert!(!key.contains("secret-pat"));
+        assert_ne!(key, state_key("https://dev.azure.com/org", "new-pat"));
+    }
+
+    #[test]
+    fn pat_hash_is_stable_across_builds() {
+        assert_eq!(
+            state_key("https://dev.azure.com/org", "secret-pat"),
+            "https://dev.azure.com/org#aa49b134ef23fc374324364c1fc54cc12c401a483b3ca09fff3a07b7c7b30ff3"
+        );
+    }
+
+    #[test]
+    fn reads_back_a_recorded_success_from_the_given_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("auth-state.json");
+        let org = "https://dev.azure.com/state-path-test";
+
+        assert_eq!(last_success(Some(&path), org, "pat"), None);
+        record_success(Some(&path), org, "pat");
+
+        assert!(last_success(Some(&path), org, "pat").is_some());
+        assert_eq!(last_success(Some(&path), org, "other-pat"), None);
+        assert_eq!(last_success(None, org, "pat"), None);
+    }
+
+    #[test]
+    fn expired_message_names_last_working_day_and_token_page() {
+        let last_success = DateTime::parse_from_rfc3339("2024-05-14T09:30:00Z")
+            .unwrap()
+            .timestamp();
+
+        assert_eq!(
+            expired_pat_error("https://dev.azure.com/org/", last_success).to_string(),
+            "PAT likely expired after 2024-05-14 (when it last worked) — regenerate at https://dev.azure.com/org/_usersSettings/tokens and update CAZDO_PAT or [azure_devops].pat."
+        );
+    }
+}
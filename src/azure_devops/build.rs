@@ -0,0 +1,113 @@
+//! Azure Pipelines builds: the latest build of a branch, for status reports.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+/// A pipeline run, as returned by the builds API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Build {
+    pub id: u64,
+    pub pipeline: String,
+    pub definition_id: Option<u64>,
+    /// `notStarted`, `inProgress`, `completed`, ...
+    pub status: String,
+    /// `succeeded`, `partiallySucceeded`, `failed`, or `canceled` once completed.
+    pub result: Option<String>,
+    pub url: Option<String>,
+}
+
+impl Build {
+    fn decode(json: &Value) -> Result<Self> {
+        let id = json
+            .get("id")
+            .and_then(Value::as_u64)
+            .context("Missing 'id' in build response")?;
+        let str_at = |pointer: &str| json.pointer(pointer).and_then(Value::as_str);
+
+        Ok(Self {
+            id,
+            pipeline: str_at("/definition/name").unwrap_or_default().to_string(),
+            definition_id: json.pointer("/definition/id").and_then(Value::as_u64),
+            status: str_at("/status").unwrap_or_default().to_string(),
+            result: str_at("/result").map(str::to_string),
+            url: str_at("/_links/web/href").map(str::to_string),
+        })
+    }
+
+    /// The result of a completed build, otherwise its status.
+    pub fn outcome(&self) -> &str {
+        match (&self.status[..], &self.result) {
+            ("completed", Some(result)) => result,
+            (status, _) => status,
+        }
+    }
+}
+
+/// The newest build in a `{ "value": [...] }` list (sorted newest first) that
+/// ran against `repository`; the builds API filters by branch name only.
+pub(super) fn decode_latest(json: &Value, repository: &str) -> Result<Option<Build>> {
+    let builds = json
+        .get("value")
+        .and_then(Value::as_array)
+        .context("Missing 'value' in build list response")?;
+
+    builds
+        .iter()
+        .find(|build| {
+            build
+                .pointer("/repository/name")
+                .and_then(Value::as_str)
+                .is_none_or(|name| name.eq_ignore_ascii_case(repository))
+        })
+        .map(Build::decode)
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_newest_build_of_the_repository() {
+        let json = serde_json::json!({
+            "value": [
+                { "id": 3, "status": "inProgress", "repository": { "name": "other" } },
+                {
+                    "id": 2,
+                    "status": "completed",
+                    "result": "failed",
+                    "definition": { "id": 12, "name": "CI" },
+                    "repository": { "name": "Web" },
+                    "_links": { "web": { "href": "https://dev.azure.com/org/p/_build/results?buildId=2" } }
+                },
+                { "id": 1, "status": "completed", "result": "succeeded", "repository": { "name": "web" } }
+            ]
+        });
+
+        let build = decode_latest(&json, "web")
+            .expect("list should decode")
+            .expect("a build should match");
+
+        assert_eq!(build.id, 2);
+        assert_eq!(build.pipeline, "CI");
+        assert_eq!(build.definition_id, Some(12));
+        assert_eq!(build.outcome(), "failed");
+        assert!(build.url.is_some());
+    }
+
+    #[test]
+    fn reports_status_until_completed() {
+        let json = serde_json::json!({ "value": [{ "id": 4, "status": "inProgress" }] });
+
+        let build = decode_latest(&json, "web").unwrap().unwrap();
+
+        assert_eq!(build.outcome(), "inProgress");
+    }
+
+    #[test]
+    fn returns_none_without_builds() {
+        let json = serde_json::json!({ "count": 0, "value": [] });
+
+        assert_eq!(decode_latest(&json, "web").unwrap(), None);
+    }
+}
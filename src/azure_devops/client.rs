@@ -1,20 +1,36 @@
 use std::path::Path;
+use std::sync::Arc;
 
 use anyhow::Result;
 use serde_json::Value;
+use tokio::sync::Semaphore;
 
+use super::activity::ActivityEntry;
+use super::build::Build;
+use super::comments::Comment;
 use super::fixture::FixtureAzureDevOpsClient;
 use super::live::LiveAzureDevOpsClient;
+use super::pull_request::{
+    CompletionOptions, NewPullRequest, PullRequest, RepositoryRef, sort_linked,
+};
+use super::query::SavedQuery;
+use super::scheduling::WorkHours;
+use super::scope::PatScope;
+use super::states::StateTransition;
 use super::work_item::WorkItem;
-use crate::config::Config;
+use crate::config::{Config, DEFAULT_MAX_CONCURRENT_REQUESTS};
+use crate::output::verbose;
 
 #[derive(Clone)]
 pub struct AzureDevOpsClient {
-    provider: WorkItemProvider,
+    provider: ClientBackend,
+    /// Requests [`AzureDevOpsClient::get_work_items`] and
+    /// [`AzureDevOpsClient::latest_builds`] send at once
+    max_concurrent_requests: usize,
 }
 
 #[derive(Clone)]
-enum WorkItemProvider {
+enum ClientBackend {
     Live(LiveAzureDevOpsClient),
     Fixture(FixtureAzureDevOpsClient),
 }
@@ -22,41 +38,331 @@ enum WorkItemProvider {
 impl AzureDevOpsClient {
     pub fn new_live(config: &Config) -> Result<Self> {
         Ok(Self {
-            provider: WorkItemProvider::Live(LiveAzureDevOpsClient::new(config)?),
+            provider: ClientBackend::Live(LiveAzureDevOpsClient::new(config)?),
+            max_concurrent_requests: config.http.max_concurrent_requests(),
         })
     }
 
     pub fn new_fixture(path: impl AsRef<Path>) -> Result<Self> {
         Ok(Self {
-            provider: WorkItemProvider::Fixture(FixtureAzureDevOpsClient::from_path(
-                path.as_ref(),
-            )?),
+            provider: ClientBackend::Fixture(FixtureAzureDevOpsClient::from_path(path.as_ref())?),
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
         })
     }
 
+    /// The organization the client talks to; `None` for the demo fixture.
+    pub fn organization_url(&self) -> Option<&str> {
+        match &self.provider {
+            ClientBackend::Live(client) => Some(client.organization_url()),
+            ClientBackend::Fixture(_) => None,
+        }
+    }
+
     #[cfg(test)]
     pub fn uses_demo_fixture(&self) -> bool {
-        matches!(self.provider, WorkItemProvider::Fixture(_))
+        matches!(self.provider, ClientBackend::Fixture(_))
     }
 
     pub async fn get_work_item(&self, id: u32) -> Result<WorkItem> {
         match &self.provider {
-            WorkItemProvider::Live(client) => client.get_work_item(id).await,
-            WorkItemProvider::Fixture(client) => client.get_work_item(id),
+            ClientBackend::Live(client) => client.get_work_item(id).await,
+            ClientBackend::Fixture(client) => client.get_work_item(id),
+        }
+    }
+
+    /// Fetch several work items concurrently, at most
+    /// `[http].max_concurrent_requests` at a time, returning results in `ids`
+    /// order.
+    pub async fn get_work_items(&self, ids: &[u32]) -> Vec<Result<WorkItem>> {
+        self.fetch_all(
+            ids,
+            |client, id| async move { client.get_work_item(id).await },
+        )
+        .await
+    }
+
+    /// Fetch several work items with one `workitemsbatch` request per 200,
+    /// returning results in `ids` order. Fails as a whole only when a
+    /// request does; a missing work item fails on its own.
+    pub async fn get_work_items_batch(&self, ids: &[u32]) -> Result<Vec<Result<WorkItem>>> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.get_work_items_batch(ids).await,
+            ClientBackend::Fixture(client) => {
+                Ok(ids.iter().map(|&id| client.get_work_item(id)).collect())
+            }
+        }
+    }
+
+    /// Fetch the raw JSON of several work items concurrently, in `ids` order.
+    pub async fn get_work_items_json(&self, ids: &[u32]) -> Vec<Result<Value>> {
+        self.fetch_all(ids, |client, id| async move {
+            client.get_work_item_json(id).await
+        })
+        .await
+    }
+
+    /// Run `fetch` for each of `keys` concurrently, at most
+    /// `[http].max_concurrent_requests` at a time, returning results in
+    /// `keys` order.
+    async fn fetch_all<K, T, F, Fut>(&self, keys: &[K], fetch: F) -> Vec<Result<T>>
+    where
+        K: Clone,
+        T: Send + 'static,
+        F: Fn(Self, K) -> Fut,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+    {
+        let permits = Arc::new(Semaphore::new(self.max_concurrent_requests));
+        let handles: Vec<_> = keys
+            .iter()
+            .map(|key| {
+                let permits = Arc::clone(&permits);
+                let request = fetch(self.clone(), key.clone());
+                tokio::spawn(async move {
+                    let _permit = permits.acquire_owned().await;
+                    request.await
+                })
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(handles.len());
+        for handle in handles {
+            results.push(
+                handle
+                    .await
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("Fetch task panicked"))),
+            );
         }
+        results
     }
 
     pub async fn get_work_item_json(&self, id: u32) -> Result<Value> {
         match &self.provider {
-            WorkItemProvider::Live(client) => client.get_work_item_json(id).await,
-            WorkItemProvider::Fixture(client) => client.get_work_item_json(id),
+            ClientBackend::Live(client) => client.get_work_item_json(id).await,
+            ClientBackend::Fixture(client) => client.get_work_item_json(id),
+        }
+    }
+
+    /// Work items changed in the last `days` days that are assigned to the
+    /// user or are one of `linked_ids`, most recently changed first, each
+    /// with what its last change did. The work items are looked up like
+    /// [`AzureDevOpsClient::get_work_items`].
+    pub async fn get_recent_activity(
+        &self,
+        linked_ids: &[u32],
+        days: u32,
+    ) -> Result<Vec<ActivityEntry>> {
+        let ids = match &self.provider {
+            ClientBackend::Live(client) => client.recently_changed_ids(linked_ids, days).await?,
+            ClientBackend::Fixture(client) => client.recently_changed_ids()?,
+        };
+        self.fetch_all(&ids, |client, id| async move {
+            match &client.provider {
+                ClientBackend::Live(live) => live.activity_entry(id).await,
+                ClientBackend::Fixture(fixture) => fixture.activity_entry(id),
+            }
+        })
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    /// The newest comments on work item `id`, newest first.
+    pub async fn get_work_item_comments(&self, id: u32) -> Result<Vec<Comment>> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.get_work_item_comments(id).await,
+            ClientBackend::Fixture(client) => client.work_item_comments(id),
+        }
+    }
+
+    pub async fn add_comment(&self, id: u32, text: &str) -> Result<u64> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.add_comment(id, text).await,
+            ClientBackend::Fixture(client) => client.add_comment(id),
+        }
+    }
+
+    pub async fn update_work_hours(&self, id: u32, hours: WorkHours) -> Result<WorkHours> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.update_work_hours(id, hours).await,
+            ClientBackend::Fixture(client) => client.update_work_hours(id),
+        }
+    }
+
+    /// The states work item `id` can move to from its current one.
+    pub async fn get_state_transitions(&self, id: u32) -> Result<Vec<StateTransition>> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.state_transitions(id).await,
+            ClientBackend::Fixture(client) => client.state_transitions(id),
+        }
+    }
+
+    /// Move work item `id` to `state`, returning the updated work item.
+    pub async fn set_work_item_state(&self, id: u32, state: &str) -> Result<WorkItem> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.set_state(id, state).await,
+            ClientBackend::Fixture(client) => client.set_state(id),
+        }
+    }
+
+    /// Complete `pull_request` as it was shown and as `options` say,
+    /// returning it as updated.
+    pub async fn complete_pull_request(
+        &self,
+        pull_request: &PullRequest,
+        options: &CompletionOptions,
+    ) -> Result<PullRequest> {
+        match &self.provider {
+            ClientBackend::Live(client) => {
+                client.complete_pull_request(pull_request, options).await
+            }
+            ClientBackend::Fixture(client) => client.complete_pull_request(),
+        }
+    }
+
+    pub async fn create_pull_request(
+        &self,
+        repo: &RepositoryRef,
+        request: &NewPullRequest,
+    ) -> Result<PullRequest> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.create_pull_request(repo, request).await,
+            ClientBackend::Fixture(client) => client.create_pull_request(),
+        }
+    }
+
+    pub async fn list_active_pull_requests(
+        &self,
+        repo: &RepositoryRef,
+        mine: bool,
+    ) -> Result<Vec<PullRequest>> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.list_active_pull_requests(repo, mine).await,
+            ClientBackend::Fixture(client) => client.list_active_pull_requests(),
+        }
+    }
+
+    /// The pull requests linked to work item `id`, active ones first. They
+    /// are looked up like [`AzureDevOpsClient::get_work_items`]; one that
+    /// is deleted or not accessible is skipped rather than failing the rest.
+    pub async fn get_linked_pull_requests(&self, id: u32) -> Result<Vec<PullRequest>> {
+        let ids = match &self.provider {
+            ClientBackend::Live(client) => client.linked_pull_request_ids(id).await?,
+            ClientBackend::Fixture(client) => return client.linked_pull_requests(id),
+        };
+        let results = self
+            .fetch_all(&ids, |client, pr_id| async move {
+                client.get_pull_request(pr_id).await
+            })
+            .await;
+        let mut pull_requests = Vec::with_capacity(results.len());
+        for (pr_id, result) in ids.into_iter().zip(results) {
+            match result {
+                Ok(pull_request) => pull_requests.push(pull_request),
+                Err(error) => {
+                    tracing::warn!(work_item_id = id, pr_id, error = %format!("{error:#}"), "skipped linked pull request");
+                    verbose!("skipped PR !{pr_id} linked from #{id}: {error:#}");
+                }
+            }
+        }
+        sort_linked(&mut pull_requests);
+        Ok(pull_requests)
+    }
+
+    async fn get_pull_request(&self, id: u32) -> Result<PullRequest> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.get_pull_request(id).await,
+            ClientBackend::Fixture(client) => client.get_pull_request(),
+        }
+    }
+
+    /// The newest pipeline run for `branch` in `repo`, if any.
+    pub async fn latest_build(&self, repo: &RepositoryRef, branch: &str) -> Result<Option<Build>> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.latest_build(repo, branch).await,
+            ClientBackend::Fixture(client) => client.latest_build(),
+        }
+    }
+
+    /// The newest pipeline run for each of `branches` in `repo`, looked up
+    /// like [`AzureDevOpsClient::get_work_items`], in `branches` order.
+    pub async fn latest_builds(
+        &self,
+        repo: &RepositoryRef,
+        branches: &[String],
+    ) -> Vec<Result<Option<Build>>> {
+        self.fetch_all(branches, |client, branch| {
+            let repo = repo.clone();
+            async move { client.latest_build(&repo, &branch).await }
+        })
+        .await
+    }
+
+    /// IDs of the work items WIQL `query` lists, in its order. The fixture
+    /// lists all of its work items.
+    pub async fn query_wiql(&self, query: &str) -> Result<Vec<u32>> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.query_wiql(query).await,
+            ClientBackend::Fixture(client) => client.query_wiql(),
+        }
+    }
+
+    /// The child work items of work item `id`, by ID, looked up like
+    /// [`AzureDevOpsClient::get_work_items_batch`]. Children that fail to
+    /// load are left out.
+    pub async fn get_child_work_items(&self, id: u32) -> Result<Vec<WorkItem>> {
+        let ids = match &self.provider {
+            ClientBackend::Live(client) => client.child_ids(id).await?,
+            ClientBackend::Fixture(client) => client.child_ids(id)?,
+        };
+        Ok(self
+            .get_work_items_batch(&ids)
+            .await?
+            .into_iter()
+            .filter_map(|result| {
+                result
+                    .inspect_err(|error| tracing::debug!(error = %error, "child work item failed"))
+                    .ok()
+            })
+            .collect())
+    }
+
+    /// Run a saved query in `project`, identified by path or GUID.
+    pub async fn run_saved_query(
+        &self,
+        project: &str,
+        query: &str,
+    ) -> Result<(SavedQuery, Vec<u32>)> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.run_saved_query(project, query).await,
+            ClientBackend::Fixture(client) => client.run_saved_query(),
+        }
+    }
+
+    pub async fn verify_project(&self, project: &str, team: Option<&str>) -> Result<()> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.verify_project(project, team).await,
+            ClientBackend::Fixture(client) => client.verify_project(),
+        }
+    }
+
+    pub async fn has_scope(&self, scope: PatScope, project: Option<&str>) -> Result<Option<bool>> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.has_scope(scope, project).await,
+            ClientBackend::Fixture(client) => client.has_scope(),
+        }
+    }
+
+    pub async fn list_projects(&self) -> Result<Vec<String>> {
+        match &self.provider {
+            ClientBackend::Live(client) => client.list_projects().await,
+            ClientBackend::Fixture(client) => client.list_projects(),
         }
     }
 
     pub async fn verify_connection(&self) -> Result<()> {
         match &self.provider {
-            WorkItemProvider::Live(client) => client.verify_connection().await,
-            WorkItemProvider::Fixture(client) => client.verify_connection(),
+            ClientBackend::Live(client) => client.verify_connection().await,
+            ClientBackend::Fixture(client) => client.verify_connection(),
         }
     }
 }
@@ -6,6 +6,8 @@
 //! separator, the `_links.html.href` location, and the rich-text field mapping
 //! here means the schema is defined exactly once.
 
+use std::collections::HashMap;
+
 use anyhow::{Context, Result};
 use serde_json::Value;
 
@@ -25,6 +27,7 @@ const WORK_ITEM_TYPE: &str = "System.WorkItemType";
 const STATE: &str = "System.State";
 const ASSIGNED_TO: &str = "System.AssignedTo";
 const TAGS: &str = "System.Tags";
+const TEAM_PROJECT: &str = "System.TeamProject";
 
 const TAG_SPLIT: char = ';';
 
@@ -109,6 +112,16 @@ pub(super) fn decode(json: &Value, id: u32) -> Result<WorkItem> {
         }
     }
 
+    let plain_fields = fields
+        .as_object()
+        .map(|fields| {
+            fields
+                .iter()
+                .filter_map(|(name, value)| Some((name.clone(), field_text(value)?)))
+                .collect()
+        })
+        .unwrap_or_default();
+
     Ok(WorkItem::from_parts(WorkItemParts {
         id,
         title,
@@ -118,14 +131,99 @@ pub(super) fn decode(json: &Value, id: u32) -> Result<WorkItem> {
         url,
         tags,
         rich_text_fields,
+        fields: plain_fields,
     }))
 }
 
+/// Parse a `workitemsbatch` response into one result per ID of `ids`, in
+/// that order. Work items the response omits, deleted ones or those the user
+/// cannot read, are not found.
+pub(super) fn decode_batch(json: &Value, ids: &[u32]) -> Result<Vec<Result<WorkItem>>> {
+    let items = json
+        .get("value")
+        .and_then(Value::as_array)
+        .context("Missing 'value' in work item batch response")?;
+    let by_id: HashMap<u64, &Value> = items
+        .iter()
+        .filter_map(|item| Some((item.get("id")?.as_u64()?, item)))
+        .collect();
+    Ok(ids
+        .iter()
+        .map(|&id| match by_id.get(&u64::from(id)) {
+            Some(item) => decode(item, id),
+            None => Err(anyhow::anyhow!("Work Item #{id} not found")),
+        })
+        .collect())
+}
+
+/// A field value as display text: strings as-is, whole numbers without a
+/// fraction, and identities by display name. Empty values are skipped.
+fn field_text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(number) => match number.as_f64() {
+            Some(value) if value.fract() == 0.0 && value.abs() < 1e15 => {
+                format!("{}", value as i64)
+            }
+            _ => number.to_string(),
+        },
+        Value::Object(_) => value.get(DISPLAY_NAME)?.as_str()?.to_string(),
+        Value::Null | Value::Array(_) => return None,
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+/// The project a work item belongs to, needed for project-scoped endpoints
+/// such as comments.
+/// The type and state of a work item response, as named by its process.
+pub(super) fn type_and_state(json: &Value) -> Result<(&str, &str)> {
+    let field = |name: &str| {
+        json.get(FIELDS)
+            .and_then(|fields| fields.get(name))
+            .and_then(Value::as_str)
+            .with_context(|| format!("Missing '{name}' field"))
+    };
+    Ok((field(WORK_ITEM_TYPE)?, field(STATE)?))
+}
+
+pub(super) fn team_project(json: &Value) -> Result<&str> {
+    json.get(FIELDS)
+        .and_then(|fields| fields.get(TEAM_PROJECT))
+        .and_then(Value::as_str)
+        .context("Missing 'System.TeamProject' field")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn decode_batch_keeps_id_order_and_reports_omitted_items() {
+        let item = |id: u32, title: &str| {
+            json!({
+                "id": id,
+                "fields": {
+                    "System.Title": title,
+                    "System.WorkItemType": "Task",
+                    "System.State": "Active"
+                }
+            })
+        };
+        let json = json!({ "count": 2, "value": [item(9, "Signup"), null, item(4, "Login")] });
+
+        let results = decode_batch(&json, &[4, 7, 9]).expect("batch should parse");
+
+        assert_eq!(results[0].as_ref().unwrap().title, "Login");
+        assert_eq!(
+            results[1].as_ref().unwrap_err().to_string(),
+            "Work Item #7 not found"
+        );
+        assert_eq!(results[2].as_ref().unwrap().title, "Signup");
+        assert!(decode_batch(&json!({}), &[4]).is_err());
+    }
+
     #[test]
     fn decode_extracts_core_fields_and_rich_text() {
         let json = json!({
@@ -233,4 +331,44 @@ mod tests {
 
         assert_eq!(error.to_string(), "Missing 'System.Title' field");
     }
+
+    #[test]
+    fn team_project_reads_system_team_project() {
+        let json = json!({ "fields": { "System.TeamProject": "Fabrikam" } });
+
+        assert_eq!(team_project(&json).unwrap(), "Fabrikam");
+        assert!(team_project(&json!({ "fields": {} })).is_err());
+    }
+
+    #[test]
+    fn decode_keeps_plain_fields_as_display_text() {
+        let json = json!({
+            "fields": {
+                "System.Title": "Sized item",
+                "System.WorkItemType": "User Story",
+                "System.State": "Active",
+                "System.Rev": 12,
+                "System.IterationPath": "Web\\Sprint 3",
+                "Microsoft.VSTS.Scheduling.StoryPoints": 5.0,
+                "Microsoft.VSTS.Scheduling.RemainingWork": 2.5,
+                "System.CreatedBy": { "displayName": "Grace Hopper" },
+                "Custom.Blocked": false,
+                "Custom.Empty": "  "
+            }
+        });
+
+        let work_item = decode(&json, 7).expect("work item should parse");
+
+        let field = |name: &str| work_item.fields.get(name).map(String::as_str);
+        assert_eq!(field("System.IterationPath"), Some("Web\\Sprint 3"));
+        assert_eq!(field("Microsoft.VSTS.Scheduling.StoryPoints"), Some("5"));
+        assert_eq!(
+            field("Microsoft.VSTS.Scheduling.RemainingWork"),
+            Some("2.5")
+        );
+        assert_eq!(field("System.CreatedBy"), Some("Grace Hopper"));
+        assert_eq!(field("Custom.Blocked"), Some("false"));
+        assert_eq!(field("Custom.Empty"), None);
+        assert_eq!(work_item.revision(), Some(12));
+    }
 }
@@ -0,0 +1,93 @@
+//! Work item discussion comments, as the comments API lists them.
+
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use serde_json::Value;
+
+use super::work_item::FieldFormat;
+
+/// A comment in the discussion of a work item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    pub id: u64,
+    pub author: Option<String>,
+    /// When it was posted, as a Unix timestamp
+    pub created_at: Option<i64>,
+    pub text: String,
+    pub format: FieldFormat,
+}
+
+/// Decode a `{ "comments": [...] }` list response, in the order listed
+/// (newest first), leaving out deleted comments.
+pub(super) fn decode_comments(json: &Value) -> Result<Vec<Comment>> {
+    let comments = json
+        .get("comments")
+        .and_then(Value::as_array)
+        .context("Missing 'comments' in comments response")?;
+    Ok(comments
+        .iter()
+        .filter(|comment| comment.get("isDeleted").and_then(Value::as_bool) != Some(true))
+        .filter_map(|comment| {
+            Some(Comment {
+                id: comment.get("id")?.as_u64()?,
+                author: comment
+                    .get("createdBy")
+                    .and_then(|user| user.get("displayName"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                created_at: comment
+                    .get("createdDate")
+                    .and_then(Value::as_str)
+                    .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+                    .map(|date| date.timestamp()),
+                text: comment
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                format: match comment.get("format").and_then(Value::as_str) {
+                    Some(format) if format.eq_ignore_ascii_case("markdown") => {
+                        FieldFormat::Markdown
+                    }
+                    _ => FieldFormat::Html,
+                },
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn decodes_comments_without_deleted_ones() {
+        let json = json!({
+            "totalCount": 3,
+            "count": 3,
+            "comments": [
+                {
+                    "id": 12,
+                    "text": "Works on **staging**",
+                    "format": "markdown",
+                    "createdBy": { "displayName": "Grace Hopper" },
+                    "createdDate": "2026-10-15T09:30:00Z"
+                },
+                { "id": 11, "text": "<p>old</p>", "isDeleted": true },
+                { "id": 10, "text": "<p>Repro attached</p>" }
+            ]
+        });
+
+        let comments = decode_comments(&json).unwrap();
+
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].author.as_deref(), Some("Grace Hopper"));
+        assert_eq!(comments[0].created_at, Some(1_792_056_600));
+        assert_eq!(comments[0].format, FieldFormat::Markdown);
+        assert_eq!(comments[1].id, 10);
+        assert_eq!(comments[1].author, None);
+        assert_eq!(comments[1].format, FieldFormat::Html);
+        assert!(decode_comments(&json!({})).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! The metadata line of the TUI details pane, set with `[display].fields`.
+//!
+//! Entries are short names for common fields (`State`, `AssignedTo`,
+//! `Iteration`, `StoryPoints`, ...) or any field reference name, such as
+//! `Microsoft.VSTS.Common.Priority` or `Custom.Team`.
+
+use anyhow::{Result, bail};
+
+use super::work_item::DEADLINE_FIELDS;
+
+/// Fields shown when `[display].fields` is not set.
+pub const DEFAULT_DETAIL_FIELDS: &[&str] = &[
+    "State",
+    "Priority",
+    "Severity",
+    "TargetDate",
+    "DueDate",
+    "AssignedTo",
+    "Iteration",
+    "Area",
+    "Tags",
+];
+
+/// Short names, paired (`short name`, `reference name`, `label`).
+const KNOWN_FIELDS: &[(&str, &str, &str)] = &[
+    ("State", "System.State", "State"),
+    ("AssignedTo", "System.AssignedTo", "Assigned To"),
+    ("Tags", "System.Tags", "Tags"),
+    ("Iteration", "System.IterationPath", "Iteration"),
+    ("Area", "System.AreaPath", "Area"),
+    ("Reason", "System.Reason", "Reason"),
+    ("CreatedBy", "System.CreatedBy", "Created By"),
+    ("ChangedBy", "System.ChangedBy", "Changed By"),
+    ("CreatedDate", "System.CreatedDate", "Created"),
+    ("ChangedDate", "System.ChangedDate", "Changed"),
+    ("Priority", "Microsoft.VSTS.Common.Priority", "Priority"),
+    ("Severity", "Microsoft.VSTS.Common.Severity", "Severity"),
+    ("ValueArea", "Microsoft.VSTS.Common.ValueArea", "Value Area"),
+    (
+        "TargetDate",
+        "Microsoft.VSTS.Scheduling.TargetDate",
+        "Target",
+    ),
+    ("DueDate", "Microsoft.VSTS.Scheduling.DueDate", "Due"),
+    (
+        "StoryPoints",
+        "Microsoft.VSTS.Scheduling.StoryPoints",
+        "Story Points",
+    ),
+    ("Effort", "Microsoft.VSTS.Scheduling.Effort", "Effort"),
+    (
+        "OriginalEstimate",
+        "Microsoft.VSTS.Scheduling.OriginalEstimate",
+        "Original Estimate",
+    ),
+    (
+        "RemainingWork",
+        "Microsoft.VSTS.Scheduling.RemainingWork",
+        "Remaining Work",
+    ),
+    (
+        "CompletedWork",
+        "Microsoft.VSTS.Scheduling.CompletedWork",
+        "Completed Work",
+    ),
+];
+
+/// One entry of the details metadata line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetailField {
+    /// The state, with its icon and color
+    State,
+    /// The assignee's display name
+    AssignedTo,
+    /// The tags, comma-separated
+    Tags,
+    /// The iteration path, shortened to its last node on a narrow pane
+    Iteration,
+    /// The area path, shortened to its last node on a narrow pane
+    Area,
+    /// The priority, colored by rank
+    Priority,
+    /// The severity, colored by rank
+    Severity,
+    /// A target or due date, highlighted once it has passed on an open item
+    Deadline { reference: String, label: String },
+    /// Any other field, shown as `label: value`
+    Field { reference: String, label: String },
+}
+
+impl DetailField {
+    /// Parse a short name (case-insensitive) or a field reference name.
+    pub fn parse(name: &str) -> Result<Self> {
+        let name = name.trim();
+        let known = KNOWN_FIELDS.iter().find(|(short, reference, _)| {
+            short.eq_ignore_ascii_case(name) || reference.eq_ignore_ascii_case(name)
+        });
+        let (reference, label) = match known {
+            Some((_, reference, label)) => (reference.to_string(), label.to_string()),
+            None if is_reference_name(name) => {
+                let label = name.rsplit('.').next().unwrap_or(name);
+                (name.to_string(), label.to_string())
+            }
+            None => bail!(
+                "unknown field '{}' (use a reference name such as Custom.Team, or one of: {})",
+                name,
+                KNOWN_FIELDS
+                    .iter()
+                    .map(|(short, _, _)| *short)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        Ok(match reference.as_str() {
+            "System.State" => Self::State,
+            "System.AssignedTo" => Self::AssignedTo,
+            "System.Tags" => Self::Tags,
+            "System.IterationPath" => Self::Iteration,
+            "System.AreaPath" => Self::Area,
+            "Microsoft.VSTS.Common.Priority" => Self::Priority,
+            "Microsoft.VSTS.Common.Severity" => Self::Severity,
+            _ if DEADLINE_FIELDS.contains(&reference.as_str()) => {
+                Self::Deadline { reference, label }
+            }
+            _ => Self::Field { reference, label },
+        })
+    }
+
+    /// Parse `[display].fields`, or the defaults when it is not set.
+    pub fn parse_list(names: Option<&[String]>) -> Result<Vec<Self>> {
+        match names {
+            Some(names) => names.iter().map(|name| Self::parse(name)).collect(),
+            None => Ok(Self::defaults()),
+        }
+    }
+
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::State,
+            Self::Priority,
+            Self::Severity,
+            Self::Deadline {
+                reference: DEADLINE_FIELDS[0].to_string(),
+                label: "Target".to_string(),
+            },
+            Self::Deadline {
+                reference: DEADLINE_FIELDS[1].to_string(),
+                label: "Due".to_string(),
+            },
+            Self::AssignedTo,
+            Self::Iteration,
+            Self::Area,
+            Self::Tags,
+        ]
+    }
+}
+
+/// `Namespace.Field` with no empty or non-identifier segments.
+fn is_reference_name(name: &str) -> bool {
+    name.contains('.')
+        && name.split('.').all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_short_names_and_reference_names() {
+        assert_eq!(DetailField::parse("state").unwrap(), DetailField::State);
+        assert_eq!(
+            DetailField::parse("System.AssignedTo").unwrap(),
+            DetailField::AssignedTo
+        );
+        assert_eq!(
+            DetailField::parse("StoryPoints").unwrap(),
+            DetailField::Field {
+                reference: "Microsoft.VSTS.Scheduling.StoryPoints".to_string(),
+                label: "Story Points".to_string(),
+            }
+        );
+        assert_eq!(
+            DetailField::parse("DueDate").unwrap(),
+            DetailField::Deadline {
+                reference: "Microsoft.VSTS.Scheduling.DueDate".to_string(),
+                label: "Due".to_string(),
+            }
+        );
+        assert_eq!(
+            DetailField::parse("system.iterationpath").unwrap(),
+            DetailField::Iteration
+        );
+        assert_eq!(
+            DetailField::parse("Custom.ReleaseTrain").unwrap(),
+            DetailField::Field {
+                reference: "Custom.ReleaseTrain".to_string(),
+                label: "ReleaseTrain".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_short_names() {
+        let error = DetailField::parse("Points").expect_err("unknown");
+        assert!(error.to_string().starts_with("unknown field 'Points'"));
+        assert!(DetailField::parse("Custom..Team").is_err());
+
+        let names = DEFAULT_DETAIL_FIELDS
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            DetailField::parse_list(Some(&names)).unwrap(),
+            DetailField::defaults()
+        );
+    }
+}
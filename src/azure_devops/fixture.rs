@@ -4,6 +4,13 @@ use std::path::Path;
 use anyhow::{Context, Result};
 use serde_json::Value;
 
+use super::activity::ActivityEntry;
+use super::build::Build;
+use super::comments::Comment;
+use super::pull_request::PullRequest;
+use super::query::SavedQuery;
+use super::scheduling::WorkHours;
+use super::states::StateTransition;
 use super::work_item::WorkItem;
 
 /// In-memory stand-in for the live Azure DevOps API.
@@ -59,6 +66,110 @@ impl FixtureAzureDevOpsClient {
         Ok(())
     }
 
+    pub(super) fn verify_project(&self) -> Result<()> {
+        Ok(())
+    }
+
+    pub(super) fn has_scope(&self) -> Result<Option<bool>> {
+        Ok(Some(true))
+    }
+
+    pub(super) fn list_projects(&self) -> Result<Vec<String>> {
+        anyhow::bail!("Projects are not available with the demo work item fixture")
+    }
+
+    pub(super) fn create_pull_request(&self) -> Result<PullRequest> {
+        anyhow::bail!("Pull requests are not available with the demo work item fixture")
+    }
+
+    pub(super) fn list_active_pull_requests(&self) -> Result<Vec<PullRequest>> {
+        Ok(Vec::new())
+    }
+
+    pub(super) fn linked_pull_requests(&self, id: u32) -> Result<Vec<PullRequest>> {
+        self.lookup(id)?;
+        Ok(Vec::new())
+    }
+
+    pub(super) fn get_pull_request(&self) -> Result<PullRequest> {
+        anyhow::bail!("Pull requests are not available with the demo work item fixture")
+    }
+
+    pub(super) fn complete_pull_request(&self) -> Result<PullRequest> {
+        anyhow::bail!("Pull requests are not available with the demo work item fixture")
+    }
+
+    pub(super) fn latest_build(&self) -> Result<Option<Build>> {
+        Ok(None)
+    }
+
+    pub(super) fn run_saved_query(&self) -> Result<(SavedQuery, Vec<u32>)> {
+        anyhow::bail!("Saved queries are not available with the demo work item fixture")
+    }
+
+    pub(super) fn work_item_comments(&self, id: u32) -> Result<Vec<Comment>> {
+        self.lookup(id)?;
+        Ok(Vec::new())
+    }
+
+    pub(super) fn add_comment(&self, id: u32) -> Result<u64> {
+        self.lookup(id)?;
+        anyhow::bail!("Comments are not available with the demo work item fixture")
+    }
+
+    pub(super) fn update_work_hours(&self, id: u32) -> Result<WorkHours> {
+        self.lookup(id)?;
+        anyhow::bail!("Updating work hours is not available with the demo work item fixture")
+    }
+
+    pub(super) fn state_transitions(&self, id: u32) -> Result<Vec<StateTransition>> {
+        self.lookup(id)?;
+        anyhow::bail!("Changing state is not available with the demo work item fixture")
+    }
+
+    pub(super) fn set_state(&self, id: u32) -> Result<WorkItem> {
+        self.lookup(id)?;
+        anyhow::bail!("Changing state is not available with the demo work item fixture")
+    }
+
+    /// Every fixture work item, most recently changed first.
+    pub(super) fn recently_changed_ids(&self) -> Result<Vec<u32>> {
+        let mut work_items = self
+            .work_items
+            .keys()
+            .map(|&id| self.get_work_item(id))
+            .collect::<Result<Vec<_>>>()?;
+        work_items.sort_by_key(|work_item| std::cmp::Reverse(work_item.changed_at()));
+        Ok(work_items
+            .into_iter()
+            .map(|work_item| work_item.id)
+            .collect())
+    }
+
+    /// IDs of the fixture work items whose parent is `id`, by ID.
+    pub(super) fn child_ids(&self, id: u32) -> Result<Vec<u32>> {
+        self.lookup(id)?;
+        let mut ids = Vec::new();
+        for &child in self.work_items.keys() {
+            if self.get_work_item(child)?.parent_id() == Some(id) {
+                ids.push(child);
+            }
+        }
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    /// Every fixture work item, most recently changed first; the fixture
+    /// cannot run WIQL, so `query` is not looked at.
+    pub(super) fn query_wiql(&self) -> Result<Vec<u32>> {
+        self.recently_changed_ids()
+    }
+
+    /// Work item `id` without its changes; the fixture has no revisions.
+    pub(super) fn activity_entry(&self, id: u32) -> Result<ActivityEntry> {
+        Ok(ActivityEntry::new(self.get_work_item(id)?, None))
+    }
+
     fn lookup(&self, id: u32) -> Result<&Value> {
         self.work_items
             .get(&id)
@@ -301,4 +412,29 @@ mod tests {
             .await
             .expect("loaded fixture client should still verify");
     }
+
+    #[tokio::test]
+    async fn lists_the_children_of_a_demo_fixture_item() {
+        let temp_dir = TempDir::new().expect("temp dir should be created");
+        let fixture_path = write_fixture(
+            &temp_dir,
+            r#"[
+  { "id": 100, "fields": { "System.Title": "Demo feature", "System.WorkItemType": "Feature", "System.State": "Active" } },
+  { "id": 102, "fields": { "System.Title": "Second task", "System.WorkItemType": "Task", "System.State": "New", "System.Parent": 100 } },
+  { "id": 101, "fields": { "System.Title": "First task", "System.WorkItemType": "Task", "System.State": "Done", "System.Parent": 100 } }
+]"#,
+        );
+
+        let client = AzureDevOpsClient::new_fixture(&fixture_path)
+            .expect("fixture-backed client should initialize");
+
+        let children = client
+            .get_child_work_items(100)
+            .await
+            .expect("children should load");
+        let ids: Vec<u32> = children.iter().map(|child| child.id).collect();
+        assert_eq!(ids, [101, 102]);
+        assert!(client.get_child_work_items(101).await.unwrap().is_empty());
+        assert!(client.get_child_work_items(999).await.is_err());
+    }
 }
@@ -1,31 +1,72 @@
-use std::time::Duration;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use reqwest::Client;
 use serde_json::Value;
 
+use super::activity::{ACTIVITY_LIMIT, ActivityEntry, activity_query};
+use super::auth_state;
+use super::build::{Build, decode_latest};
+use super::comments::{Comment, decode_comments};
+use super::pull_request::{
+    CompletionOptions, NewPullRequest, PullRequest, RepositoryRef, branch_ref, completion_update,
+    decode_list, linked_pull_request_ids,
+};
+use super::query::{SavedQuery, decode_result_ids};
+use super::scheduling::WorkHours;
+use super::scope::{PatScope, scope_granted};
+use super::states::{StateTransition, decode_transitions, state_patch};
 use super::work_item::WorkItem;
 use crate::config::Config;
+use crate::http;
+use crate::output::verbose;
 
 const AZURE_DEVOPS_HTTP_TIMEOUT: Duration = Duration::from_secs(30);
-const AZURE_DEVOPS_API_VERSION: &str = "7.1";
+
+/// Work items a `workitemsbatch` request may ask for
+const WORK_ITEM_BATCH_LIMIT: usize = 200;
+
+/// Comments listed per work item, the most one request returns
+const COMMENT_LIMIT: usize = 200;
+
+/// Work items a WIQL query lists at most
+const WIQL_LIMIT: usize = 200;
+
+const NIL_REPOSITORY_ID: &str = "00000000-0000-0000-0000-000000000000";
+
+/// Requests sent to Azure DevOps by this process, failed ones included
+static REQUESTS_SENT: AtomicUsize = AtomicUsize::new(0);
+
+/// How many requests this process has sent to Azure DevOps.
+pub fn requests_sent() -> usize {
+    REQUESTS_SENT.load(Ordering::Relaxed)
+}
 
 #[derive(Clone)]
 pub(super) struct LiveAzureDevOpsClient {
     client: Client,
     base_url: String,
+    api_version: String,
     pat: String,
+    /// Where PAT successes are remembered; `None` records nothing.
+    auth_state_path: Option<PathBuf>,
 }
 
 impl LiveAzureDevOpsClient {
     pub(super) fn new(config: &Config) -> Result<Self> {
-        Self::new_with_timeout(config, AZURE_DEVOPS_HTTP_TIMEOUT)
+        Self::new_with_timeout(config, AZURE_DEVOPS_HTTP_TIMEOUT, auth_state::state_path())
     }
 
-    fn new_with_timeout(config: &Config, timeout: Duration) -> Result<Self> {
+    fn new_with_timeout(
+        config: &Config,
+        timeout: Duration,
+        auth_state_path: Option<PathBuf>,
+    ) -> Result<Self> {
         let pat = config.get_pat()?;
 
-        let client = Client::builder()
+        let client = http::client_builder(Some(&config.http))?
             .timeout(timeout)
             .build()
             .context("Failed to create HTTP client")?;
@@ -40,16 +81,50 @@ impl LiveAzureDevOpsClient {
         Ok(Self {
             client,
             base_url,
+            api_version: config.azure_devops.api_version().to_string(),
             pat,
+            auth_state_path,
         })
     }
 
+    pub(super) fn organization_url(&self) -> &str {
+        &self.base_url
+    }
+
     pub(super) async fn get_work_item(&self, id: u32) -> Result<WorkItem> {
         let json = self.request_work_item_json(id, false).await?;
 
         super::codec::decode(&json, id)
     }
 
+    /// Work items `ids` in `ids` order, [`WORK_ITEM_BATCH_LIMIT`] per
+    /// request. Missing work items fail on their own; a failed request fails
+    /// all of them.
+    pub(super) async fn get_work_items_batch(&self, ids: &[u32]) -> Result<Vec<Result<WorkItem>>> {
+        let mut url = self.organization_api_url(&["wit", "workitemsbatch"])?;
+        url.query_pairs_mut()
+            .append_pair("api-version", &self.api_version);
+
+        let mut work_items = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(WORK_ITEM_BATCH_LIMIT) {
+            let response = self
+                .send(
+                    self.client.post(url.clone()).json(&serde_json::json!({
+                        "ids": chunk,
+                        "$expand": "links",
+                        "errorPolicy": "omit",
+                    })),
+                    "Failed to send request to Azure DevOps",
+                )
+                .await?;
+            let json = self
+                .api_json(response, "Failed to fetch work items")
+                .await?;
+            work_items.extend(super::codec::decode_batch(&json, chunk)?);
+        }
+        Ok(work_items)
+    }
+
     pub(super) async fn get_work_item_json(&self, id: u32) -> Result<Value> {
         self.request_work_item_json(id, true).await
     }
@@ -58,16 +133,15 @@ impl LiveAzureDevOpsClient {
         let expand = if expand_all { "$expand=all&" } else { "" };
         let url = format!(
             "{}/_apis/wit/workitems/{}?{}api-version={}",
-            self.base_url, id, expand, AZURE_DEVOPS_API_VERSION
+            self.base_url, id, expand, self.api_version
         );
 
         let response = self
-            .client
-            .get(&url)
-            .basic_auth("", Some(&self.pat))
-            .send()
-            .await
-            .map_err(|err| request_error("Failed to send request to Azure DevOps", err))?;
+            .send(
+                self.client.get(&url),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
 
         let status = response.status();
         if !status.is_success() || status == reqwest::StatusCode::NON_AUTHORITATIVE_INFORMATION {
@@ -80,19 +154,135 @@ impl LiveAzureDevOpsClient {
             .map_err(|err| request_error("Failed to parse work item response", err))
     }
 
+    /// The comments endpoint of work item `id`, which is scoped to its
+    /// project, so this looks the work item up first.
+    async fn comments_url(&self, id: u32) -> Result<String> {
+        let work_item = self.request_work_item_json(id, false).await?;
+        let project = super::codec::team_project(&work_item)?;
+        Ok(format!(
+            "{}/{}/_apis/wit/workItems/{}/comments",
+            self.base_url, project, id
+        ))
+    }
+
+    /// The newest comments on a work item, newest first.
+    pub(super) async fn get_work_item_comments(&self, id: u32) -> Result<Vec<Comment>> {
+        let url = format!(
+            "{}?$top={}&api-version={}",
+            self.comments_url(id).await?,
+            COMMENT_LIMIT,
+            comments_api_version(&self.api_version)
+        );
+        let response = self
+            .send(
+                self.client.get(&url),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+        let json = self.api_json(response, "Failed to fetch comments").await?;
+        decode_comments(&json)
+    }
+
+    /// Add a comment to a work item and return the new comment's id.
+    pub(super) async fn add_comment(&self, id: u32, text: &str) -> Result<u64> {
+        let url = format!(
+            "{}?api-version={}",
+            self.comments_url(id).await?,
+            comments_api_version(&self.api_version)
+        );
+        let response = self
+            .send(
+                self.client
+                    .post(&url)
+                    .json(&serde_json::json!({ "text": comment_html(text) })),
+                "Failed to send comment to Azure DevOps",
+            )
+            .await?;
+
+        let json = self.api_json(response, "Failed to add comment").await?;
+        json.get("id")
+            .and_then(Value::as_u64)
+            .context("Missing 'id' in comment response")
+    }
+
+    /// Set a work item's remaining and/or completed hours and return the
+    /// hours it has afterwards.
+    pub(super) async fn update_work_hours(&self, id: u32, hours: WorkHours) -> Result<WorkHours> {
+        let url = format!(
+            "{}/_apis/wit/workitems/{}?api-version={}",
+            self.base_url, id, self.api_version
+        );
+        let response = self
+            .send(
+                self.client
+                    .patch(&url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json-patch+json")
+                    .body(hours.to_patch().to_string()),
+                "Failed to send work item update to Azure DevOps",
+            )
+            .await?;
+
+        let json = self
+            .api_json(response, "Failed to update work item hours")
+            .await?;
+        Ok(WorkHours::decode(&json))
+    }
+
+    /// The states work item `id` can move to from its current one, per the
+    /// process of its type.
+    pub(super) async fn state_transitions(&self, id: u32) -> Result<Vec<StateTransition>> {
+        let work_item = self.request_work_item_json(id, false).await?;
+        let project = super::codec::team_project(&work_item)?;
+        let (work_item_type, state) = super::codec::type_and_state(&work_item)?;
+
+        let mut url = self.project_api_url(project, &["wit", "workitemtypes", work_item_type])?;
+        url.query_pairs_mut()
+            .append_pair("api-version", &self.api_version);
+        let response = self
+            .send(
+                self.client.get(url),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+        let json = self
+            .api_json(response, "Failed to look up work item type")
+            .await?;
+        decode_transitions(&json, state)
+    }
+
+    /// Move work item `id` to `state` and return it as updated.
+    pub(super) async fn set_state(&self, id: u32, state: &str) -> Result<WorkItem> {
+        let url = format!(
+            "{}/_apis/wit/workitems/{}?api-version={}",
+            self.base_url, id, self.api_version
+        );
+        let response = self
+            .send(
+                self.client
+                    .patch(&url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json-patch+json")
+                    .body(state_patch(state).to_string()),
+                "Failed to send work item update to Azure DevOps",
+            )
+            .await?;
+
+        let json = self
+            .api_json(response, "Failed to change work item state")
+            .await?;
+        super::codec::decode(&json, id)
+    }
+
     pub(super) async fn verify_connection(&self) -> Result<()> {
         let url = format!("{}/_apis/connectionData", self.base_url);
 
         let response = self
-            .client
-            .get(&url)
-            .header(reqwest::header::ACCEPT, "application/json")
-            .basic_auth("", Some(&self.pat))
-            .send()
-            .await
-            .map_err(|err| {
-                request_error("Failed to send verification request to Azure DevOps", err)
-            })?;
+            .send(
+                self.client
+                    .get(&url)
+                    .header(reqwest::header::ACCEPT, "application/json"),
+                "Failed to send verification request to Azure DevOps",
+            )
+            .await?;
 
         let status = response.status();
         if status.is_success() {
@@ -133,6 +323,490 @@ impl LiveAzureDevOpsClient {
         Err(self.extract_verification_error(response).await)
     }
 
+    /// Check that `project` exists, and `team` in it, through the Projects
+    /// and Teams APIs.
+    pub(super) async fn verify_project(&self, project: &str, team: Option<&str>) -> Result<()> {
+        let mut checks = vec![(
+            vec!["projects", project],
+            format!("Project '{}' not found", project),
+        )];
+        if let Some(team) = team {
+            checks.push((
+                vec!["projects", project, "teams", team],
+                format!("Team '{}' not found in project '{}'", team, project),
+            ));
+        }
+
+        for (segments, context) in checks {
+            let mut url = self.organization_api_url(&segments)?;
+            url.query_pairs_mut()
+                .append_pair("api-version", &self.api_version);
+            let response = self
+                .send(
+                    self.client.get(url),
+                    "Failed to send request to Azure DevOps",
+                )
+                .await?;
+            self.api_json(response, &context).await?;
+        }
+        Ok(())
+    }
+
+    /// Whether the PAT grants `scope`, or `None` for Build (Read) without a
+    /// project to check it in. Each check requests something that does not
+    /// exist (work item 0, an all-zero repository ID) or a one-item list.
+    pub(super) async fn has_scope(
+        &self,
+        scope: PatScope,
+        project: Option<&str>,
+    ) -> Result<Option<bool>> {
+        let mut url = match scope {
+            PatScope::WorkItemsRead | PatScope::WorkItemsWrite => {
+                self.organization_api_url(&["wit", "workitems", "0"])?
+            }
+            PatScope::CodeRead => {
+                self.organization_api_url(&["git", "repositories", NIL_REPOSITORY_ID])?
+            }
+            PatScope::BuildRead => {
+                let Some(project) = project else {
+                    return Ok(None);
+                };
+                let mut url = self.project_api_url(project, &["build", "builds"])?;
+                url.query_pairs_mut().append_pair("$top", "1");
+                url
+            }
+        };
+        if scope == PatScope::WorkItemsWrite {
+            url.query_pairs_mut().append_pair("validateOnly", "true");
+        }
+        url.query_pairs_mut()
+            .append_pair("api-version", &self.api_version);
+        let request = match scope {
+            PatScope::WorkItemsWrite => self
+                .client
+                .patch(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json-patch+json")
+                .body("[]"),
+            _ => self.client.get(url),
+        };
+
+        let response = self
+            .execute(request, "Failed to send scope check to Azure DevOps")
+            .await?;
+        Ok(Some(scope_granted(response.status())))
+    }
+
+    /// Names of the projects in the organization, sorted.
+    pub(super) async fn list_projects(&self) -> Result<Vec<String>> {
+        let mut url = self.organization_api_url(&["projects"])?;
+        url.query_pairs_mut()
+            .append_pair("$top", "1000")
+            .append_pair("api-version", &self.api_version);
+        let response = self
+            .send(
+                self.client.get(url),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+        let json = self.api_json(response, "Failed to list projects").await?;
+
+        let mut names: Vec<String> = json["value"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|project| project["name"].as_str().map(str::to_string))
+            .collect();
+        names.sort_by_key(|name| name.to_lowercase());
+        Ok(names)
+    }
+
+    pub(super) async fn create_pull_request(
+        &self,
+        repo: &RepositoryRef,
+        request: &NewPullRequest,
+    ) -> Result<PullRequest> {
+        let target_ref = match &request.target_branch {
+            Some(branch) => branch_ref(branch),
+            None => self.default_branch_ref(repo).await?,
+        };
+
+        let url = format!(
+            "{}/pullrequests?api-version={}",
+            self.repository_url(repo),
+            self.api_version
+        );
+        let response = self
+            .send(
+                self.client.post(&url).json(&request.to_json(&target_ref)),
+                "Failed to send pull request to Azure DevOps",
+            )
+            .await?;
+
+        let json = self
+            .api_json(response, "Failed to create pull request")
+            .await?;
+        PullRequest::decode(&json)
+    }
+
+    pub(super) async fn list_active_pull_requests(
+        &self,
+        repo: &RepositoryRef,
+        mine: bool,
+    ) -> Result<Vec<PullRequest>> {
+        let creator = if mine {
+            format!(
+                "&searchCriteria.creatorId={}",
+                self.authenticated_user_id().await?
+            )
+        } else {
+            String::new()
+        };
+        let url = format!(
+            "{}/pullrequests?searchCriteria.status=active{}&api-version={}",
+            self.repository_url(repo),
+            creator,
+            self.api_version
+        );
+        let response = self
+            .send(
+                self.client.get(&url),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+
+        let json = self
+            .api_json(response, "Failed to list pull requests")
+            .await?;
+        decode_list(&json)
+    }
+
+    /// IDs of the pull requests linked from work item `id`, in link order.
+    pub(super) async fn linked_pull_request_ids(&self, id: u32) -> Result<Vec<u32>> {
+        let work_item = self.request_work_item_json(id, true).await?;
+        Ok(linked_pull_request_ids(&work_item))
+    }
+
+    /// Pull request `id`, in whichever repository it is.
+    pub(super) async fn get_pull_request(&self, id: u32) -> Result<PullRequest> {
+        let mut url = self.organization_api_url(&["git", "pullrequests", &id.to_string()])?;
+        url.query_pairs_mut()
+            .append_pair("api-version", &self.api_version);
+        let response = self
+            .send(
+                self.client.get(url),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+        let json = self
+            .api_json(response, "Failed to look up pull request")
+            .await?;
+        PullRequest::decode(&json)
+    }
+
+    /// Complete `pull_request` as it was shown and as `options` say,
+    /// returning it as updated. It is not fetched again first, so commits
+    /// pushed since it was loaded make the completion fail.
+    pub(super) async fn complete_pull_request(
+        &self,
+        pull_request: &PullRequest,
+        options: &CompletionOptions,
+    ) -> Result<PullRequest> {
+        let update = completion_update(pull_request, options)?;
+        let mut url = self.pull_request_api_url(pull_request)?;
+        url.query_pairs_mut()
+            .append_pair("api-version", &self.api_version);
+        let response = self
+            .send(
+                self.client.patch(url).json(&update),
+                "Failed to send pull request update to Azure DevOps",
+            )
+            .await?;
+        let json = self
+            .api_json(response, "Failed to complete pull request")
+            .await?;
+        PullRequest::decode(&json)
+    }
+
+    /// Look up a saved query by path (`Shared Queries/Team/Active Bugs`) or
+    /// GUID and run it, returning the query and its work item IDs.
+    pub(super) async fn run_saved_query(
+        &self,
+        project: &str,
+        query: &str,
+    ) -> Result<(SavedQuery, Vec<u32>)> {
+        let mut url = self.project_api_url(project, &["wit", "queries"])?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Invalid organization URL"))?
+            .extend(query.trim_matches('/').split('/'));
+        url.query_pairs_mut()
+            .append_pair("api-version", &self.api_version);
+        let response = self
+            .send(
+                self.client.get(url),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+        let json = self
+            .api_json(response, "Failed to look up saved query")
+            .await?;
+        let saved_query = SavedQuery::decode(&json)?;
+
+        let mut url = self.project_api_url(project, &["wit", "wiql", &saved_query.id])?;
+        url.query_pairs_mut()
+            .append_pair("api-version", &self.api_version);
+        let response = self
+            .send(
+                self.client.get(url),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+        let json = self.api_json(response, "Failed to run saved query").await?;
+
+        Ok((saved_query, decode_result_ids(&json)?))
+    }
+
+    /// IDs of the work items changed in the last `days` days that are
+    /// assigned to the user or are one of `linked_ids`, most recently changed
+    /// first.
+    pub(super) async fn recently_changed_ids(
+        &self,
+        linked_ids: &[u32],
+        days: u32,
+    ) -> Result<Vec<u32>> {
+        self.run_wiql(
+            &activity_query(linked_ids, days),
+            ACTIVITY_LIMIT,
+            "Failed to query recent activity",
+        )
+        .await
+    }
+
+    /// IDs of the work items WIQL `query` lists, up to [`WIQL_LIMIT`], in
+    /// its order.
+    pub(super) async fn query_wiql(&self, query: &str) -> Result<Vec<u32>> {
+        self.run_wiql(query, WIQL_LIMIT, "Failed to run WIQL query")
+            .await
+    }
+
+    /// IDs of the children of work item `id`, by ID.
+    pub(super) async fn child_ids(&self, id: u32) -> Result<Vec<u32>> {
+        self.run_wiql(
+            &children_query(id),
+            WIQL_LIMIT,
+            "Failed to query child work items",
+        )
+        .await
+    }
+
+    async fn run_wiql(&self, query: &str, top: usize, context: &str) -> Result<Vec<u32>> {
+        let mut url = self.organization_api_url(&["wit", "wiql"])?;
+        url.query_pairs_mut()
+            .append_pair("$top", &top.to_string())
+            .append_pair("api-version", &self.api_version);
+        let response = self
+            .send(
+                self.client
+                    .post(url)
+                    .json(&serde_json::json!({ "query": query })),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+        let json = self.api_json(response, context).await?;
+        decode_result_ids(&json)
+    }
+
+    /// Work item `id` with what its last change did, from the revision
+    /// before it.
+    pub(super) async fn activity_entry(&self, id: u32) -> Result<ActivityEntry> {
+        let work_item = self.get_work_item(id).await?;
+        let previous = match work_item.revision() {
+            Some(rev) if rev > 1 => {
+                let mut url = self.organization_api_url(&[
+                    "wit",
+                    "workItems",
+                    &id.to_string(),
+                    "revisions",
+                    &(rev - 1).to_string(),
+                ])?;
+                url.query_pairs_mut()
+                    .append_pair("api-version", &self.api_version);
+                let response = self
+                    .send(
+                        self.client.get(url),
+                        "Failed to send request to Azure DevOps",
+                    )
+                    .await?;
+                let json = self
+                    .api_json(response, "Failed to look up work item revision")
+                    .await?;
+                Some(super::codec::decode(&json, id)?)
+            }
+            _ => None,
+        };
+        Ok(ActivityEntry::new(work_item, previous.as_ref()))
+    }
+
+    /// The newest pipeline run for `branch` in `repo`, if any.
+    pub(super) async fn latest_build(
+        &self,
+        repo: &RepositoryRef,
+        branch: &str,
+    ) -> Result<Option<Build>> {
+        let mut url = self.project_api_url(&repo.project, &["build", "builds"])?;
+        url.query_pairs_mut()
+            .append_pair("branchName", &branch_ref(branch))
+            .append_pair("queryOrder", "queueTimeDescending")
+            .append_pair("$top", "20")
+            .append_pair("api-version", &self.api_version);
+        let response = self
+            .send(
+                self.client.get(url),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+
+        let json = self.api_json(response, "Failed to list builds").await?;
+        decode_latest(&json, &repo.repository)
+    }
+
+    /// `{organization}/_apis/{segments...}`, with each segment percent-encoded.
+    fn organization_api_url(&self, segments: &[&str]) -> Result<reqwest::Url> {
+        let mut url = reqwest::Url::parse(&self.base_url)
+            .with_context(|| format!("Invalid organization URL '{}'", self.base_url))?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Invalid organization URL '{}'", self.base_url))?
+            .pop_if_empty()
+            .push("_apis")
+            .extend(segments);
+        Ok(url)
+    }
+
+    /// `{organization}/{project}/_apis/git/repositories/{repository}/pullrequests/{id}`
+    /// for `pull_request`, from the configured organization URL.
+    fn pull_request_api_url(&self, pull_request: &PullRequest) -> Result<reqwest::Url> {
+        let (Some(project), Some(repository)) =
+            (&pull_request.project_id, &pull_request.repository_id)
+        else {
+            anyhow::bail!("PR !{} has no repository to update", pull_request.id);
+        };
+        self.project_api_url(
+            project,
+            &[
+                "git",
+                "repositories",
+                repository,
+                "pullrequests",
+                &pull_request.id.to_string(),
+            ],
+        )
+    }
+
+    /// `{organization}/{project}/_apis/{segments...}`, with each segment
+    /// percent-encoded.
+    fn project_api_url(&self, project: &str, segments: &[&str]) -> Result<reqwest::Url> {
+        let mut url = reqwest::Url::parse(&self.base_url)
+            .with_context(|| format!("Invalid organization URL '{}'", self.base_url))?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Invalid organization URL '{}'", self.base_url))?
+            .pop_if_empty()
+            .push(project)
+            .push("_apis")
+            .extend(segments);
+        Ok(url)
+    }
+
+    async fn authenticated_user_id(&self) -> Result<String> {
+        let url = format!("{}/_apis/connectionData", self.base_url);
+        let response = self
+            .send(
+                self.client
+                    .get(&url)
+                    .header(reqwest::header::ACCEPT, "application/json"),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+
+        let json = self
+            .api_json(response, "Failed to look up the authenticated user")
+            .await?;
+        json.get("authenticatedUser")
+            .and_then(|user| user.get("id"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .context("Response missing authenticated user details")
+    }
+
+    async fn default_branch_ref(&self, repo: &RepositoryRef) -> Result<String> {
+        let url = format!(
+            "{}?api-version={}",
+            self.repository_url(repo),
+            self.api_version
+        );
+        let response = self
+            .send(
+                self.client.get(&url),
+                "Failed to send request to Azure DevOps",
+            )
+            .await?;
+
+        let json = self
+            .api_json(response, "Failed to look up repository")
+            .await?;
+        json.get("defaultBranch")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .with_context(|| {
+                format!(
+                    "Repository '{}' has no default branch; pass --target",
+                    repo.repository
+                )
+            })
+    }
+
+    fn repository_url(&self, repo: &RepositoryRef) -> String {
+        format!(
+            "{}/{}/_apis/git/repositories/{}",
+            self.base_url, repo.project, repo.repository
+        )
+    }
+
+    /// Parse a successful API response, or turn a failure into an error
+    /// carrying the server's message.
+    async fn api_json(&self, response: reqwest::Response, context: &str) -> Result<Value> {
+        let status = response.status();
+
+        if status == reqwest::StatusCode::UNAUTHORIZED
+            || status == reqwest::StatusCode::FORBIDDEN
+            || status == reqwest::StatusCode::NON_AUTHORITATIVE_INFORMATION
+        {
+            anyhow::bail!(
+                "{}: authentication failed (status {}). Check that your PAT has the required scopes.",
+                context,
+                status
+            );
+        }
+
+        if !status.is_success() {
+            let message = response
+                .text()
+                .await
+                .ok()
+                .and_then(|body| serde_json::from_str::<Value>(&body).ok())
+                .and_then(|json| {
+                    json.get("message")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                })
+                .unwrap_or_else(|| format!("Azure DevOps API error ({})", status));
+            anyhow::bail!("{}: {}", context, message);
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|err| request_error("Failed to parse Azure DevOps response", err))
+    }
+
     async fn extract_api_error(&self, response: reqwest::Response, id: u32) -> anyhow::Error {
         let status = response.status();
 
@@ -213,20 +887,134 @@ impl LiveAzureDevOpsClient {
     }
 }
 
-fn request_error(context: &'static str, error: reqwest::Error) -> anyhow::Error {
+impl LiveAzureDevOpsClient {
+    /// Authenticate and send `request`, logging it under `--verbose`.
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        context: &'static str,
+    ) -> Result<reqwest::Response> {
+        let response = self.execute(request, context).await?;
+
+        // 203 is the sign-in page Azure DevOps serves for a rejected PAT.
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED
+            || status == reqwest::StatusCode::NON_AUTHORITATIVE_INFORMATION
+        {
+            if let Some(last_success) =
+                auth_state::last_success(self.auth_state_path.as_deref(), &self.base_url, &self.pat)
+            {
+                tracing::warn!(url = %response.url(), %status, last_success, "PAT rejected after previously working");
+                return Err(auth_state::expired_pat_error(&self.base_url, last_success));
+            }
+        } else if status.is_success() {
+            auth_state::record_success(self.auth_state_path.as_deref(), &self.base_url, &self.pat);
+        }
+
+        Ok(response)
+    }
+
+    /// Send an authenticated request and log it, without treating a rejected
+    /// PAT as expired.
+    async fn execute(
+        &self,
+        request: reqwest::RequestBuilder,
+        context: &'static str,
+    ) -> Result<reqwest::Response> {
+        let request = request
+            .basic_auth("", Some(&self.pat))
+            .build()
+            .map_err(|err| request_error(context, err))?;
+        let method = request.method().clone();
+        let url = request.url().clone();
+        let started = Instant::now();
+        REQUESTS_SENT.fetch_add(1, Ordering::Relaxed);
+
+        let response = match self.client.execute(request).await {
+            Ok(response) => response,
+            Err(err) => {
+                tracing::warn!(%method, %url, error = %err, "Azure DevOps request failed");
+                return Err(request_error(context, err));
+            }
+        };
+
+        tracing::debug!(
+            %method,
+            %url,
+            status = %response.status(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Azure DevOps request"
+        );
+        verbose!(
+            "{} {} -> {} in {} ms",
+            method,
+            url,
+            response.status(),
+            started.elapsed().as_millis()
+        );
+        Ok(response)
+    }
+}
+
+/// Comments are stored as HTML; escape the plain text and keep its line breaks.
+fn comment_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\n', "<br>")
+}
+
+/// WIQL listing the children of work item `id`.
+fn children_query(id: u32) -> String {
+    format!("SELECT [System.Id] FROM WorkItems WHERE [System.Parent] = {id} ORDER BY [System.Id]")
+}
+
+/// Wrap a reqwest error, dropping any credentials from the URL it carries.
+fn request_error(context: &'static str, mut error: reqwest::Error) -> anyhow::Error {
+    if let Some(url) = error.url_mut() {
+        let _ = url.set_username("");
+        let _ = url.set_password(None);
+    }
     if error.is_timeout() {
-        anyhow::anyhow!("{}: request timed out", context)
+        anyhow::Error::new(error).context(format!("{}: request timed out", context))
     } else {
         anyhow::Error::new(error).context(context)
     }
 }
 
+/// Whether `error` is a connection failure or timeout that another attempt
+/// may not hit, rather than an answer from the server.
+pub fn is_transient_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|error| error.is_timeout() || error.is_connect())
+    })
+}
+
+/// The comments API is still in preview; its resource version depends on the
+/// API version (`7.1-preview.4`, `6.0-preview.3`, `5.0-preview.2`).
+fn comments_api_version(api_version: &str) -> String {
+    let (major, minor) = api_version
+        .split_once('.')
+        .and_then(|(major, minor)| Some((major.parse::<u32>().ok()?, minor.parse::<u32>().ok()?)))
+        .unwrap_or((0, 0));
+    let resource = match (major, minor) {
+        (5, 0) => 2,
+        (major, minor) if (major, minor) >= (7, 1) => 4,
+        _ => 3,
+    };
+    format!("{api_version}-preview.{resource}")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tokio::io::AsyncWriteExt;
     use tokio::net::TcpListener;
     use tokio::task::JoinHandle;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     const TEST_HTTP_TIMEOUT: Duration = Duration::from_millis(10);
 
@@ -264,6 +1052,27 @@ mod tests {
         (url, handle)
     }
 
+    #[test]
+    fn comment_html_escapes_markup_and_keeps_line_breaks() {
+        assert_eq!(
+            comment_html("Fixed <b> & tested\nsee CI"),
+            "Fixed &lt;b&gt; &amp; tested<br>see CI"
+        );
+    }
+
+    #[test]
+    fn comments_preview_version_follows_api_version() {
+        assert_eq!(comments_api_version("7.1"), "7.1-preview.4");
+        assert_eq!(comments_api_version("7.0"), "7.0-preview.3");
+        assert_eq!(comments_api_version("6.0"), "6.0-preview.3");
+        assert_eq!(comments_api_version("5.0"), "5.0-preview.2");
+    }
+
+    /// A client that leaves the real auth state file alone.
+    fn test_client(config: &Config) -> Result<LiveAzureDevOpsClient> {
+        LiveAzureDevOpsClient::new_with_timeout(config, TEST_HTTP_TIMEOUT, None)
+    }
+
     fn test_config(base_url: String) -> Config {
         let mut config = Config::default();
         config.azure_devops.organization_url = base_url;
@@ -271,12 +1080,43 @@ mod tests {
         config
     }
 
+    #[test]
+    fn pull_request_api_url_uses_the_configured_organization() {
+        let client = test_client(&test_config("https://dev.azure.com/org/".to_string()))
+            .expect("client should build");
+        let json = serde_json::json!({
+            "pullRequestId": 22,
+            "url": "https://attacker.example/_apis/git/pullRequests/22",
+            "repository": { "id": "r1", "project": { "id": "p1" } }
+        });
+        let pull_request = PullRequest::decode(&json).unwrap();
+
+        assert_eq!(
+            client.pull_request_api_url(&pull_request).unwrap().as_str(),
+            "https://dev.azure.com/org/p1/_apis/git/repositories/r1/pullrequests/22"
+        );
+    }
+
+    #[test]
+    fn project_api_url_encodes_segments() {
+        let client = test_client(&test_config("https://dev.azure.com/org/".to_string()))
+            .expect("client should build");
+
+        let url = client
+            .project_api_url("My Project", &["wit", "queries", "Shared Queries", "A#1"])
+            .expect("url should build");
+
+        assert_eq!(
+            url.as_str(),
+            "https://dev.azure.com/org/My%20Project/_apis/wit/queries/Shared%20Queries/A%231"
+        );
+    }
+
     #[tokio::test]
     async fn work_item_request_times_out_when_server_stalls() {
         let (url, server) = start_stalling_server().await;
         let config = test_config(url);
-        let client = LiveAzureDevOpsClient::new_with_timeout(&config, TEST_HTTP_TIMEOUT)
-            .expect("client should initialize");
+        let client = test_client(&config).expect("client should initialize");
 
         let error = client
             .get_work_item_json(123)
@@ -288,14 +1128,16 @@ mod tests {
             error.to_string().contains("request timed out"),
             "unexpected error: {error:#}"
         );
+        assert!(is_transient_error(&error));
     }
 
     #[tokio::test]
     async fn work_item_request_times_out_when_server_stalls_during_body() {
         let (url, server) = start_body_stalling_server().await;
         let config = test_config(url);
-        let client = LiveAzureDevOpsClient::new_with_timeout(&config, Duration::from_millis(100))
-            .expect("client should initialize");
+        let client =
+            LiveAzureDevOpsClient::new_with_timeout(&config, Duration::from_millis(100), None)
+                .expect("client should initialize");
 
         let error = client
             .get_work_item_json(123)
@@ -313,8 +1155,7 @@ mod tests {
     async fn verification_request_times_out_when_server_stalls() {
         let (url, server) = start_stalling_server().await;
         let config = test_config(url);
-        let client = LiveAzureDevOpsClient::new_with_timeout(&config, TEST_HTTP_TIMEOUT)
-            .expect("client should initialize");
+        let client = test_client(&config).expect("client should initialize");
 
         let error = client
             .verify_connection()
@@ -327,4 +1168,195 @@ mod tests {
             "unexpected error: {error:#}"
         );
     }
+
+    async fn mock_client() -> (MockServer, LiveAzureDevOpsClient) {
+        let server = MockServer::start().await;
+        let client = test_client(&test_config(server.uri())).expect("client should build");
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn get_work_item_sends_pat_and_decodes_response() {
+        let (server, client) = mock_client().await;
+        Mock::given(method("GET"))
+            .and(path("/_apis/wit/workitems/42"))
+            .and(query_param("api-version", client.api_version.as_str()))
+            .and(header("authorization", "Basic OnRlc3QtcGF0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 42,
+                "rev": 3,
+                "fields": {
+                    "System.Title": "Login page",
+                    "System.WorkItemType": "Bug",
+                    "System.State": "Active",
+                    "System.Rev": 3,
+                    "System.AssignedTo": { "displayName": "Ada Lovelace" }
+                },
+                "_links": { "html": { "href": "https://dev.azure.com/org/_workitems/edit/42" } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let work_item = client.get_work_item(42).await.expect("work item");
+
+        assert_eq!(work_item.title, "Login page");
+        assert_eq!(work_item.assigned_to.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(work_item.revision(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn linked_pull_requests_skip_one_that_cannot_be_looked_up() {
+        let server = MockServer::start().await;
+        let client = crate::azure_devops::AzureDevOpsClient::new_live(&test_config(server.uri()))
+            .expect("client should build");
+        let link = |id: u32| {
+            serde_json::json!({
+                "rel": "ArtifactLink",
+                "url": format!("vstfs:///Git/PullRequestId/p1%2Fr1%2F{id}")
+            })
+        };
+        Mock::given(method("GET"))
+            .and(path("/_apis/wit/workitems/42"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 42,
+                "fields": {},
+                "relations": [link(5), link(6)]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_apis/git/pullrequests/5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "pullRequestId": 5,
+                "status": "active"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_apis/git/pullrequests/6"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "message": "The pull request with id 6 does not exist."
+            })))
+            .mount(&server)
+            .await;
+
+        let pull_requests = client
+            .get_linked_pull_requests(42)
+            .await
+            .expect("linked pull requests");
+
+        assert_eq!(
+            pull_requests.iter().map(|pr| pr.id).collect::<Vec<_>>(),
+            [5]
+        );
+    }
+
+    #[tokio::test]
+    async fn work_item_errors_use_the_server_message() {
+        let (server, client) = mock_client().await;
+        Mock::given(path("/_apis/wit/workitems/7"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "message": "TF401232: Work item 7 does not exist, or you do not have permissions to read it."
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(path("/_apis/wit/workitems/8"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(path("/_apis/wit/workitems/9"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("<html>oops</html>"))
+            .mount(&server)
+            .await;
+
+        let error = |id| {
+            let client = client.clone();
+            async move { client.get_work_item(id).await.unwrap_err().to_string() }
+        };
+        assert_eq!(
+            error(7).await,
+            "TF401232: Work item 7 does not exist, or you do not have permissions to read it."
+        );
+        assert_eq!(error(8).await, "Work Item #8 not found");
+        assert_eq!(
+            error(9).await,
+            "Azure DevOps API error (500 Internal Server Error)"
+        );
+        assert!(!is_transient_error(
+            &client.get_work_item(9).await.unwrap_err()
+        ));
+    }
+
+    #[tokio::test]
+    async fn status_203_is_reported_as_an_invalid_pat() {
+        let (server, client) = mock_client().await;
+        Mock::given(path("/_apis/wit/workitems/1"))
+            .respond_with(
+                ResponseTemplate::new(203).set_body_raw("<html>Sign in</html>", "text/html"),
+            )
+            .mount(&server)
+            .await;
+
+        let error = client.get_work_item(1).await.unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("Authentication failed (Status 203)"),
+            "unexpected error: {error:#}"
+        );
+    }
+
+    #[tokio::test]
+    async fn verify_connection_requires_json_with_an_authenticated_user() {
+        let cases = [
+            (
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "authenticatedUser": { "id": "user-1" } })),
+                None,
+            ),
+            (
+                ResponseTemplate::new(200)
+                    .set_body_raw("<html>Sign in</html>", "text/html; charset=utf-8"),
+                Some("Verification returned non-JSON response (text/html; charset=utf-8)"),
+            ),
+            (
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "instanceId": "x" })),
+                Some("Verification response missing authenticated user details"),
+            ),
+            (
+                ResponseTemplate::new(401),
+                Some("Authentication failed (status 401 Unauthorized)"),
+            ),
+            (
+                ResponseTemplate::new(404),
+                Some("Verification endpoint not found (404)"),
+            ),
+        ];
+
+        for (response, expected_error) in cases {
+            let (server, client) = mock_client().await;
+            Mock::given(method("GET"))
+                .and(path("/_apis/connectionData"))
+                .and(header("accept", "application/json"))
+                .respond_with(response)
+                .mount(&server)
+                .await;
+
+            let result = client.verify_connection().await;
+
+            match expected_error {
+                None => assert!(
+                    result.is_ok(),
+                    "unexpected error: {:#}",
+                    result.unwrap_err()
+                ),
+                Some(expected) => {
+                    let error = result.expect_err(expected).to_string();
+                    assert!(error.starts_with(expected), "unexpected error: {error}");
+                }
+            }
+        }
+    }
 }
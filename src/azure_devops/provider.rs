@@ -0,0 +1,399 @@
+//! Work item lookups as a trait, so the TUI's fetch, refresh, and error
+//! flows and the commands that list work items can be tested without network
+//! access. [`AzureDevOpsClient`] is the implementation used outside tests.
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::activity::ActivityEntry;
+use super::client::AzureDevOpsClient;
+use super::comments::Comment;
+use super::pull_request::{CompletionOptions, PullRequest, RepositoryRef};
+use super::states::StateTransition;
+use super::work_item::WorkItem;
+
+pub trait WorkItemProvider: Clone + Send + Sync + 'static {
+    fn get_work_item(&self, id: u32) -> impl Future<Output = Result<WorkItem>> + Send;
+
+    /// Fetch several work items concurrently, returning results in `ids` order.
+    fn get_work_items(&self, ids: &[u32]) -> impl Future<Output = Vec<Result<WorkItem>>> + Send;
+
+    /// Fetch several work items in as few requests as possible, returning
+    /// results in `ids` order; the outer error is a failed request.
+    fn get_work_items_batch(
+        &self,
+        ids: &[u32],
+    ) -> impl Future<Output = Result<Vec<Result<WorkItem>>>> + Send;
+
+    /// The pull requests linked to work item `id`, active ones first.
+    fn get_linked_pull_requests(
+        &self,
+        id: u32,
+    ) -> impl Future<Output = Result<Vec<PullRequest>>> + Send;
+
+    /// The active pull requests of `repo`, by anyone.
+    fn list_active_pull_requests(
+        &self,
+        repo: &RepositoryRef,
+    ) -> impl Future<Output = Result<Vec<PullRequest>>> + Send;
+
+    /// The newest comments on work item `id`, newest first.
+    fn get_work_item_comments(&self, id: u32) -> impl Future<Output = Result<Vec<Comment>>> + Send;
+
+    /// Work item `id` as the API returns it, with relations and links.
+    fn get_work_item_json(&self, id: u32) -> impl Future<Output = Result<Value>> + Send;
+
+    /// The states work item `id` can move to from its current one.
+    fn get_state_transitions(
+        &self,
+        id: u32,
+    ) -> impl Future<Output = Result<Vec<StateTransition>>> + Send;
+
+    /// Move work item `id` to `state`, returning the updated work item.
+    fn set_work_item_state(
+        &self,
+        id: u32,
+        state: &str,
+    ) -> impl Future<Output = Result<WorkItem>> + Send;
+
+    /// Complete `pull_request` as it was shown and as `options` say,
+    /// returning it as updated.
+    fn complete_pull_request(
+        &self,
+        pull_request: &PullRequest,
+        options: &CompletionOptions,
+    ) -> impl Future<Output = Result<PullRequest>> + Send;
+
+    /// Work items changed in the last `days` days that are assigned to the
+    /// user or are one of `linked_ids`, most recently changed first.
+    fn get_recent_activity(
+        &self,
+        linked_ids: &[u32],
+        days: u32,
+    ) -> impl Future<Output = Result<Vec<ActivityEntry>>> + Send;
+
+    /// IDs of the work items WIQL `query` lists, in its order.
+    fn query_wiql(&self, query: &str) -> impl Future<Output = Result<Vec<u32>>> + Send;
+
+    /// The child work items of work item `id`, by ID.
+    fn get_child_work_items(&self, id: u32) -> impl Future<Output = Result<Vec<WorkItem>>> + Send;
+}
+
+impl WorkItemProvider for AzureDevOpsClient {
+    async fn get_work_item(&self, id: u32) -> Result<WorkItem> {
+        AzureDevOpsClient::get_work_item(self, id).await
+    }
+
+    async fn get_work_items(&self, ids: &[u32]) -> Vec<Result<WorkItem>> {
+        AzureDevOpsClient::get_work_items(self, ids).await
+    }
+
+    async fn get_work_items_batch(&self, ids: &[u32]) -> Result<Vec<Result<WorkItem>>> {
+        AzureDevOpsClient::get_work_items_batch(self, ids).await
+    }
+
+    async fn get_linked_pull_requests(&self, id: u32) -> Result<Vec<PullRequest>> {
+        AzureDevOpsClient::get_linked_pull_requests(self, id).await
+    }
+
+    async fn list_active_pull_requests(&self, repo: &RepositoryRef) -> Result<Vec<PullRequest>> {
+        AzureDevOpsClient::list_active_pull_requests(self, repo, false).await
+    }
+
+    async fn get_work_item_comments(&self, id: u32) -> Result<Vec<Comment>> {
+        AzureDevOpsClient::get_work_item_comments(self, id).await
+    }
+
+    async fn get_work_item_json(&self, id: u32) -> Result<Value> {
+        AzureDevOpsClient::get_work_item_json(self, id).await
+    }
+
+    async fn get_state_transitions(&self, id: u32) -> Result<Vec<StateTransition>> {
+        AzureDevOpsClient::get_state_transitions(self, id).await
+    }
+
+    async fn set_work_item_state(&self, id: u32, state: &str) -> Result<WorkItem> {
+        AzureDevOpsClient::set_work_item_state(self, id, state).await
+    }
+
+    async fn complete_pull_request(
+        &self,
+        pull_request: &PullRequest,
+        options: &CompletionOptions,
+    ) -> Result<PullRequest> {
+        AzureDevOpsClient::complete_pull_request(self, pull_request, options).await
+    }
+
+    async fn get_recent_activity(
+        &self,
+        linked_ids: &[u32],
+        days: u32,
+    ) -> Result<Vec<ActivityEntry>> {
+        AzureDevOpsClient::get_recent_activity(self, linked_ids, days).await
+    }
+
+    async fn query_wiql(&self, query: &str) -> Result<Vec<u32>> {
+        AzureDevOpsClient::query_wiql(self, query).await
+    }
+
+    async fn get_child_work_items(&self, id: u32) -> Result<Vec<WorkItem>> {
+        AzureDevOpsClient::get_child_work_items(self, id).await
+    }
+}
+
+#[cfg(test)]
+pub use mock::MockWorkItemProvider;
+
+#[cfg(test)]
+mod mock {
+    use std::collections::{BTreeMap, HashMap, HashSet};
+    use std::sync::{Arc, Mutex};
+
+    use anyhow::{Result, anyhow};
+    use serde_json::Value;
+
+    use super::WorkItemProvider;
+    use crate::azure_devops::{
+        ActivityEntry, Comment, CompletionOptions, PullRequest, RepositoryRef, StateTransition,
+        WorkItem, WorkItemState, WorkItemType,
+    };
+
+    /// In-memory work items for tests. Unknown IDs fail like a missing work
+    /// item; every request is recorded. Clones share their items and log.
+    /// Work items have no linked pull requests, comments, or state
+    /// transitions unless given some, and the repository has none unless
+    /// [`MockWorkItemProvider::with_active_pull_requests`] lists some;
+    /// changing the state updates the stored work item. Recent
+    /// activity lists every work item assigned with
+    /// [`MockWorkItemProvider::with_assigned`] and those of the linked IDs,
+    /// without changes, and any WIQL query lists the assigned ones. Work
+    /// items have no children unless given some.
+    #[derive(Clone, Default)]
+    pub struct MockWorkItemProvider {
+        responses: Arc<Mutex<HashMap<u32, Result<WorkItem, String>>>>,
+        pull_requests: Arc<Mutex<HashMap<u32, Vec<PullRequest>>>>,
+        comments: Arc<Mutex<HashMap<u32, Vec<Comment>>>>,
+        children: Arc<Mutex<HashMap<u32, Vec<u32>>>>,
+        transitions: Arc<Mutex<HashMap<u32, Vec<StateTransition>>>>,
+        active_pull_requests: Arc<Mutex<Vec<PullRequest>>>,
+        assigned: Arc<Mutex<Vec<u32>>>,
+        requests: Arc<Mutex<Vec<u32>>>,
+    }
+
+    impl MockWorkItemProvider {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Answer requests for `id` with a task titled `title`.
+        pub fn with_work_item(self, id: u32, title: &str) -> Self {
+            self.set_work_item(id, title);
+            self
+        }
+
+        /// Answer requests for `id` with `error`.
+        pub fn with_error(self, id: u32, error: &str) -> Self {
+            self.responses
+                .lock()
+                .unwrap()
+                .insert(id, Err(error.to_string()));
+            self
+        }
+
+        /// Link `pull_requests` to work item `id`.
+        pub fn with_pull_requests(self, id: u32, pull_requests: Vec<PullRequest>) -> Self {
+            self.pull_requests.lock().unwrap().insert(id, pull_requests);
+            self
+        }
+
+        /// List `pull_requests` as the active ones of any repository.
+        pub fn with_active_pull_requests(self, pull_requests: Vec<PullRequest>) -> Self {
+            *self.active_pull_requests.lock().unwrap() = pull_requests;
+            self
+        }
+
+        /// Post `comments` on work item `id`, newest first.
+        pub fn with_comments(self, id: u32, comments: Vec<Comment>) -> Self {
+            self.comments.lock().unwrap().insert(id, comments);
+            self
+        }
+
+        /// Make `children` the child work items of work item `id`.
+        pub fn with_children(self, id: u32, children: Vec<u32>) -> Self {
+            self.children.lock().unwrap().insert(id, children);
+            self
+        }
+
+        /// Let work item `id` move to `transitions`.
+        pub fn with_transitions(self, id: u32, transitions: Vec<StateTransition>) -> Self {
+            self.transitions.lock().unwrap().insert(id, transitions);
+            self
+        }
+
+        /// Count work item `id` as assigned to the user.
+        pub fn with_assigned(self, id: u32) -> Self {
+            self.assigned.lock().unwrap().push(id);
+            self
+        }
+
+        /// Change the answer for `id`, e.g. between a fetch and a refresh.
+        pub fn set_work_item(&self, id: u32, title: &str) {
+            let work_item = WorkItem {
+                id,
+                title: title.to_string(),
+                work_item_type: WorkItemType::Task,
+                state: WorkItemState::Active,
+                assigned_to: None,
+                url: None,
+                tags: vec![],
+                rich_text_fields: vec![],
+                fields: BTreeMap::new(),
+            };
+            self.responses.lock().unwrap().insert(id, Ok(work_item));
+        }
+
+        /// The IDs requested so far, in order.
+        pub fn requests(&self) -> Vec<u32> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        fn respond(&self, id: u32) -> Result<WorkItem> {
+            self.requests.lock().unwrap().push(id);
+            match self.responses.lock().unwrap().get(&id) {
+                Some(Ok(work_item)) => Ok(work_item.clone()),
+                Some(Err(error)) => Err(anyhow!(error.clone())),
+                None => Err(anyhow!("Work item #{id} not found")),
+            }
+        }
+    }
+
+    impl WorkItemProvider for MockWorkItemProvider {
+        async fn get_work_item(&self, id: u32) -> Result<WorkItem> {
+            self.respond(id)
+        }
+
+        async fn get_work_items(&self, ids: &[u32]) -> Vec<Result<WorkItem>> {
+            ids.iter().map(|&id| self.respond(id)).collect()
+        }
+
+        /// Records each ID like single requests do, so tests see which
+        /// work items were asked for.
+        async fn get_work_items_batch(&self, ids: &[u32]) -> Result<Vec<Result<WorkItem>>> {
+            Ok(ids.iter().map(|&id| self.respond(id)).collect())
+        }
+
+        async fn get_linked_pull_requests(&self, id: u32) -> Result<Vec<PullRequest>> {
+            if let Some(Err(error)) = self.responses.lock().unwrap().get(&id) {
+                return Err(anyhow!(error.clone()));
+            }
+            Ok(self
+                .pull_requests
+                .lock()
+                .unwrap()
+                .get(&id)
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        async fn list_active_pull_requests(
+            &self,
+            _repo: &RepositoryRef,
+        ) -> Result<Vec<PullRequest>> {
+            Ok(self.active_pull_requests.lock().unwrap().clone())
+        }
+
+        async fn get_work_item_comments(&self, id: u32) -> Result<Vec<Comment>> {
+            if let Some(Err(error)) = self.responses.lock().unwrap().get(&id) {
+                return Err(anyhow!(error.clone()));
+            }
+            Ok(self
+                .comments
+                .lock()
+                .unwrap()
+                .get(&id)
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        /// The work item as serialized by cazdo, standing in for the API's
+        /// JSON.
+        async fn get_work_item_json(&self, id: u32) -> Result<Value> {
+            Ok(serde_json::to_value(self.respond(id)?)?)
+        }
+
+        async fn get_state_transitions(&self, id: u32) -> Result<Vec<StateTransition>> {
+            self.respond(id)?;
+            Ok(self
+                .transitions
+                .lock()
+                .unwrap()
+                .get(&id)
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        async fn get_recent_activity(
+            &self,
+            linked_ids: &[u32],
+            _days: u32,
+        ) -> Result<Vec<ActivityEntry>> {
+            let assigned = self.assigned.lock().unwrap().clone();
+            let mut seen = HashSet::new();
+            assigned
+                .into_iter()
+                .chain(linked_ids.iter().copied())
+                .filter(|&id| seen.insert(id))
+                .filter(|id| self.responses.lock().unwrap().contains_key(id))
+                .map(|id| Ok(ActivityEntry::new(self.respond(id)?, None)))
+                .collect()
+        }
+
+        async fn query_wiql(&self, _query: &str) -> Result<Vec<u32>> {
+            Ok(self.assigned.lock().unwrap().clone())
+        }
+
+        /// Children that fail to load are left out, as the client does.
+        async fn get_child_work_items(&self, id: u32) -> Result<Vec<WorkItem>> {
+            if let Some(Err(error)) = self.responses.lock().unwrap().get(&id) {
+                return Err(anyhow!(error.clone()));
+            }
+            let children = self
+                .children
+                .lock()
+                .unwrap()
+                .get(&id)
+                .cloned()
+                .unwrap_or_default();
+            Ok(children
+                .into_iter()
+                .filter_map(|id| self.respond(id).ok())
+                .collect())
+        }
+
+        async fn set_work_item_state(&self, id: u32, state: &str) -> Result<WorkItem> {
+            let mut work_item = self.respond(id)?;
+            work_item.state = state.parse().unwrap();
+            self.responses
+                .lock()
+                .unwrap()
+                .insert(id, Ok(work_item.clone()));
+            Ok(work_item)
+        }
+
+        async fn complete_pull_request(
+            &self,
+            pull_request: &PullRequest,
+            _options: &CompletionOptions,
+        ) -> Result<PullRequest> {
+            let id = pull_request.id;
+            let mut pull_requests = self.pull_requests.lock().unwrap();
+            let pull_request = pull_requests
+                .values_mut()
+                .flatten()
+                .find(|pull_request| pull_request.id == id)
+                .ok_or_else(|| anyhow!("Pull request {id} not found"))?;
+            pull_request.status = "completed".to_string();
+            Ok(pull_request.clone())
+        }
+    }
+}
@@ -0,0 +1,648 @@
+//! Azure Repos pull requests: locating the repository from the `origin` remote
+//! and the subset of the pull request JSON shape cazdo reads and writes.
+
+use anyhow::{Context, Result};
+use percent_encoding::percent_decode_str;
+use serde_json::Value;
+
+const REFS_HEADS: &str = "refs/heads/";
+/// Artifact URL prefix of a work item's link to a pull request, followed by
+/// `{projectId}%2F{repositoryId}%2F{pullRequestId}`
+const PULL_REQUEST_ARTIFACT: &str = "vstfs:///Git/PullRequestId/";
+
+/// An Azure Repos repository, identified by project and repository name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryRef {
+    pub project: String,
+    pub repository: String,
+}
+
+impl RepositoryRef {
+    /// Parse an Azure Repos clone URL.
+    ///
+    /// Supports HTTPS remotes (`…/{project}/_git/{repo}`, including
+    /// `visualstudio.com` and Azure DevOps Server collections) and SSH remotes
+    /// (`…:v3/{org}/{project}/{repo}`).
+    pub fn from_remote_url(url: &str) -> Result<Self> {
+        Self::parse(url.trim()).with_context(|| {
+            format!(
+                "Remote URL '{}' does not look like an Azure Repos repository",
+                url
+            )
+        })
+    }
+
+    fn parse(url: &str) -> Option<Self> {
+        let segments: Vec<&str> = url
+            .trim_end_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        if let Some(git) = segments.iter().position(|s| *s == "_git") {
+            let project = segments.get(git.checked_sub(1)?)?;
+            let repository = segments.get(git + 1)?;
+            return Some(Self::new(project, repository));
+        }
+
+        // SSH: git@ssh.dev.azure.com:v3/{org}/{project}/{repo}
+        let (_, path) = url.split_once(":v3/")?;
+        let mut parts = path.trim_end_matches('/').split('/');
+        let (_org, project, repository) = (parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self::new(project, repository))
+    }
+
+    /// Names are percent-decoded, since remotes spell `My Project` as
+    /// `My%20Project`.
+    fn new(project: &str, repository: &str) -> Self {
+        let decode = |name: &str| percent_decode_str(name).decode_utf8_lossy().into_owned();
+        Self {
+            project: decode(project),
+            repository: decode(repository.trim_end_matches(".git")),
+        }
+    }
+}
+
+/// The organization (or collection) URL of an Azure Repos clone URL, such as
+/// `https://dev.azure.com/org` for `https://org@dev.azure.com/org/Project/_git/repo`.
+pub fn organization_url_from_remote(url: &str) -> Option<String> {
+    let url = url.trim();
+
+    // SSH: git@ssh.dev.azure.com:v3/{org}/{project}/{repo}
+    if let Some((_, path)) = url.split_once(":v3/") {
+        let org = path.split('/').next().filter(|org| !org.is_empty())?;
+        return Some(format!("https://dev.azure.com/{org}"));
+    }
+
+    let mut parsed = reqwest::Url::parse(url).ok()?;
+    if !matches!(parsed.scheme(), "https" | "http") {
+        return None;
+    }
+    let segments: Vec<String> = parsed.path_segments()?.map(str::to_string).collect();
+    let git = segments.iter().position(|segment| segment == "_git")?;
+    let organization_path = segments.get(..git.checked_sub(1)?)?.join("/");
+    parsed.set_username("").ok()?;
+    parsed.set_password(None).ok()?;
+    parsed.set_path(&organization_path);
+    parsed.set_query(None);
+    parsed.set_fragment(None);
+    Some(parsed.as_str().trim_end_matches('/').to_string())
+}
+
+/// Input for creating a pull request.
+#[derive(Debug, Clone)]
+pub struct NewPullRequest {
+    pub source_branch: String,
+    /// Target branch; the repository's default branch when `None`.
+    pub target_branch: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub is_draft: bool,
+    pub work_item_ids: Vec<u32>,
+}
+
+impl NewPullRequest {
+    pub(super) fn to_json(&self, target_ref: &str) -> Value {
+        serde_json::json!({
+            "sourceRefName": branch_ref(&self.source_branch),
+            "targetRefName": target_ref,
+            "title": self.title,
+            "description": self.description,
+            "isDraft": self.is_draft,
+            "workItemRefs": self
+                .work_item_ids
+                .iter()
+                .map(|id| serde_json::json!({ "id": id.to_string() }))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// How completing a pull request brings its changes into the target branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// A merge commit, even when a fast-forward is possible
+    #[default]
+    NoFastForward,
+    Squash,
+    /// The source commits rebased onto the target and fast-forwarded
+    Rebase,
+    /// The source commits rebased onto the target, then a merge commit
+    RebaseMerge,
+}
+
+impl MergeStrategy {
+    pub const ALL: [MergeStrategy; 4] = [
+        MergeStrategy::NoFastForward,
+        MergeStrategy::Squash,
+        MergeStrategy::Rebase,
+        MergeStrategy::RebaseMerge,
+    ];
+
+    fn api_name(self) -> &'static str {
+        match self {
+            Self::NoFastForward => "noFastForward",
+            Self::Squash => "squash",
+            Self::Rebase => "rebase",
+            Self::RebaseMerge => "rebaseMerge",
+        }
+    }
+}
+
+/// How to complete a pull request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompletionOptions {
+    pub merge_strategy: MergeStrategy,
+    pub delete_source_branch: bool,
+    /// Move the linked work items to their completed state
+    pub complete_work_items: bool,
+}
+
+/// The update completing `pull_request` as it was shown. Azure Repos only
+/// completes a pull request whose latest source commit the update names, so
+/// a push since it was loaded makes the update fail.
+pub(super) fn completion_update(
+    pull_request: &PullRequest,
+    options: &CompletionOptions,
+) -> Result<Value> {
+    let commit_id = pull_request
+        .last_merge_source_commit
+        .as_deref()
+        .with_context(|| format!("PR !{} has no source commit to complete", pull_request.id))?;
+    let update = serde_json::json!({
+        "status": "completed",
+        "lastMergeSourceCommit": { "commitId": commit_id },
+        "completionOptions": {
+            "mergeStrategy": options.merge_strategy.api_name(),
+            "deleteSourceBranch": options.delete_source_branch,
+            "transitionWorkItems": options.complete_work_items,
+        },
+    });
+    Ok(update)
+}
+
+/// A reviewer's vote, from the Azure Repos numeric vote value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vote {
+    Approved,
+    ApprovedWithSuggestions,
+    Pending,
+    WaitingForAuthor,
+    Rejected,
+}
+
+impl Vote {
+    fn from_value(value: i64) -> Self {
+        match value {
+            10 => Self::Approved,
+            5 => Self::ApprovedWithSuggestions,
+            -5 => Self::WaitingForAuthor,
+            -10 => Self::Rejected,
+            _ => Self::Pending,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Approved => "approved",
+            Self::ApprovedWithSuggestions => "approved_with_suggestions",
+            Self::Pending => "pending",
+            Self::WaitingForAuthor => "waiting_for_author",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Reviewer {
+    pub name: String,
+    pub vote: Vote,
+    pub is_required: bool,
+}
+
+/// A pull request as returned by Azure Repos.
+#[derive(Debug, Clone)]
+pub struct PullRequest {
+    pub id: u32,
+    pub title: String,
+    pub status: String,
+    pub is_draft: bool,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub created_by: Option<String>,
+    pub reviewers: Vec<Reviewer>,
+    pub url: Option<String>,
+    /// IDs of the project and repository, for updating the pull request
+    pub project_id: Option<String>,
+    pub repository_id: Option<String>,
+    /// Latest source commit when the pull request was loaded; completing it
+    /// names this commit
+    pub last_merge_source_commit: Option<String>,
+}
+
+impl PullRequest {
+    pub(super) fn decode(json: &Value) -> Result<Self> {
+        let id = json
+            .get("pullRequestId")
+            .and_then(Value::as_u64)
+            .and_then(|id| u32::try_from(id).ok())
+            .context("Missing 'pullRequestId' in pull request response")?;
+        let str_field = |key: &str| json.get(key).and_then(Value::as_str).unwrap_or("");
+        let repository = json.get("repository");
+        let repository_str = |pointer: &str| {
+            repository
+                .and_then(|repo| repo.pointer(pointer))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+
+        let url = json
+            .get("repository")
+            .and_then(|repo| repo.get("webUrl"))
+            .and_then(Value::as_str)
+            .map(|web_url| format!("{}/pullrequest/{}", web_url.trim_end_matches('/'), id));
+
+        Ok(Self {
+            id,
+            title: str_field("title").to_string(),
+            status: str_field("status").to_string(),
+            is_draft: json
+                .get("isDraft")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            source_branch: short_branch(str_field("sourceRefName")).to_string(),
+            target_branch: short_branch(str_field("targetRefName")).to_string(),
+            created_by: json
+                .get("createdBy")
+                .and_then(|user| user.get("displayName"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            reviewers: json
+                .get("reviewers")
+                .and_then(Value::as_array)
+                .map(|reviewers| reviewers.iter().map(decode_reviewer).collect())
+                .unwrap_or_default(),
+            url,
+            project_id: repository_str("/project/id"),
+            repository_id: repository_str("/id"),
+            last_merge_source_commit: json
+                .pointer("/lastMergeSourceCommit/commitId")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        })
+    }
+
+    /// Compact vote counts, e.g. `2 approved, 1 waiting`; `no reviewers` when empty.
+    pub fn vote_summary(&self) -> String {
+        if self.reviewers.is_empty() {
+            return "no reviewers".to_string();
+        }
+
+        let count = |votes: &[Vote]| {
+            self.reviewers
+                .iter()
+                .filter(|reviewer| votes.contains(&reviewer.vote))
+                .count()
+        };
+        [
+            (
+                count(&[Vote::Approved, Vote::ApprovedWithSuggestions]),
+                "approved",
+            ),
+            (count(&[Vote::WaitingForAuthor]), "waiting"),
+            (count(&[Vote::Rejected]), "rejected"),
+            (count(&[Vote::Pending]), "pending"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+fn decode_reviewer(json: &Value) -> Reviewer {
+    Reviewer {
+        name: json
+            .get("displayName")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string(),
+        vote: Vote::from_value(json.get("vote").and_then(Value::as_i64).unwrap_or(0)),
+        is_required: json
+            .get("isRequired")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    }
+}
+
+/// Decode a `{ "value": [...] }` pull request list response.
+pub(super) fn decode_list(json: &Value) -> Result<Vec<PullRequest>> {
+    json.get("value")
+        .and_then(Value::as_array)
+        .context("Missing 'value' in pull request list response")?
+        .iter()
+        .map(PullRequest::decode)
+        .collect()
+}
+
+/// IDs of the pull requests linked from a work item fetched with
+/// `$expand=relations` (or `all`), in link order.
+pub(super) fn linked_pull_request_ids(work_item: &Value) -> Vec<u32> {
+    let Some(relations) = work_item.get("relations").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    let mut ids = Vec::new();
+    for relation in relations {
+        if relation.get("rel").and_then(Value::as_str) != Some("ArtifactLink") {
+            continue;
+        }
+        let Some(artifact) = relation
+            .get("url")
+            .and_then(Value::as_str)
+            .and_then(|url| url.strip_prefix(PULL_REQUEST_ARTIFACT))
+        else {
+            continue;
+        };
+        let artifact = percent_decode_str(artifact).decode_utf8_lossy();
+        if let Some(id) = artifact.rsplit('/').next().and_then(|id| id.parse().ok())
+            && !ids.contains(&id)
+        {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// Active pull requests first, then the newest.
+pub(super) fn sort_linked(pull_requests: &mut [PullRequest]) {
+    pull_requests.sort_by_key(|pr| (pr.status != "active", std::cmp::Reverse(pr.id)));
+}
+
+pub(super) fn branch_ref(branch: &str) -> String {
+    if branch.starts_with(REFS_HEADS) {
+        branch.to_string()
+    } else {
+        format!("{REFS_HEADS}{branch}")
+    }
+}
+
+fn short_branch(ref_name: &str) -> &str {
+    ref_name.strip_prefix(REFS_HEADS).unwrap_or(ref_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(project: &str, repository: &str) -> RepositoryRef {
+        RepositoryRef {
+            project: project.to_string(),
+            repository: repository.to_string(),
+        }
+    }
+
+    #[test]
+    fn completion_names_the_shown_source_commit_and_the_options() {
+        let pull_request = PullRequest::decode(&serde_json::json!({
+            "pullRequestId": 22,
+            "lastMergeSourceCommit": { "commitId": "3f2a1c9d" }
+        }))
+        .unwrap();
+        let options = CompletionOptions {
+            merge_strategy: MergeStrategy::Squash,
+            delete_source_branch: true,
+            complete_work_items: false,
+        };
+
+        let update = completion_update(&pull_request, &options).unwrap();
+
+        assert_eq!(
+            update,
+            serde_json::json!({
+                "status": "completed",
+                "lastMergeSourceCommit": { "commitId": "3f2a1c9d" },
+                "completionOptions": {
+                    "mergeStrategy": "squash",
+                    "deleteSourceBranch": true,
+                    "transitionWorkItems": false,
+                },
+            })
+        );
+        let unknown_commit =
+            PullRequest::decode(&serde_json::json!({ "pullRequestId": 22 })).unwrap();
+        assert!(completion_update(&unknown_commit, &options).is_err());
+    }
+
+    #[test]
+    fn parses_https_remote_urls() {
+        for url in [
+            "https://dev.azure.com/org/Project/_git/repo",
+            "https://org@dev.azure.com/org/Project/_git/repo",
+            "https://org.visualstudio.com/Project/_git/repo",
+            "https://org.visualstudio.com/DefaultCollection/Project/_git/repo/",
+            "https://tfs.example.com/tfs/Collection/Project/_git/repo.git",
+        ] {
+            assert_eq!(
+                RepositoryRef::from_remote_url(url).expect(url),
+                repo("Project", "repo"),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn suggests_organization_urls_from_remotes() {
+        for (url, organization) in [
+            (
+                "https://org@dev.azure.com/org/Project/_git/repo",
+                "https://dev.azure.com/org",
+            ),
+            (
+                "https://org.visualstudio.com/Project/_git/repo",
+                "https://org.visualstudio.com",
+            ),
+            (
+                "https://tfs.example.com/tfs/Collection/Project/_git/repo.git",
+                "https://tfs.example.com/tfs/Collection",
+            ),
+            (
+                "git@ssh.dev.azure.com:v3/org/Project/repo",
+                "https://dev.azure.com/org",
+            ),
+        ] {
+            assert_eq!(
+                organization_url_from_remote(url).as_deref(),
+                Some(organization),
+                "{url}"
+            );
+        }
+        assert_eq!(
+            organization_url_from_remote("git@github.com:owner/repo.git"),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_ssh_remote_urls() {
+        for url in [
+            "git@ssh.dev.azure.com:v3/org/Project/repo",
+            "org@vs-ssh.visualstudio.com:v3/org/Project/repo",
+        ] {
+            assert_eq!(
+                RepositoryRef::from_remote_url(url).expect(url),
+                repo("Project", "repo"),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn decodes_percent_encoded_names() {
+        assert_eq!(
+            RepositoryRef::from_remote_url("https://dev.azure.com/org/My%20Project/_git/web%20app")
+                .unwrap(),
+            repo("My Project", "web app")
+        );
+    }
+
+    #[test]
+    fn rejects_non_azure_remote_urls() {
+        assert!(RepositoryRef::from_remote_url("git@github.com:owner/repo.git").is_err());
+        assert!(RepositoryRef::from_remote_url("https://github.com/owner/repo").is_err());
+    }
+
+    #[test]
+    fn new_pull_request_json_links_work_items() {
+        let request = NewPullRequest {
+            source_branch: "feature/12-login".to_string(),
+            target_branch: None,
+            title: "Login".to_string(),
+            description: "Adds login".to_string(),
+            is_draft: true,
+            work_item_ids: vec![12],
+        };
+
+        let json = request.to_json("refs/heads/main");
+
+        assert_eq!(json["sourceRefName"], "refs/heads/feature/12-login");
+        assert_eq!(json["targetRefName"], "refs/heads/main");
+        assert_eq!(json["isDraft"], true);
+        assert_eq!(json["workItemRefs"], serde_json::json!([{ "id": "12" }]));
+    }
+
+    #[test]
+    fn decodes_pull_request_response() {
+        let json = serde_json::json!({
+            "pullRequestId": 42,
+            "title": "Login",
+            "status": "active",
+            "isDraft": false,
+            "sourceRefName": "refs/heads/feature/12-login",
+            "targetRefName": "refs/heads/main",
+            "createdBy": { "displayName": "Ada" },
+            "reviewers": [
+                { "displayName": "Grace", "vote": 10, "isRequired": true },
+                { "displayName": "Linus", "vote": -5 },
+                { "displayName": "Team", "vote": 0 }
+            ],
+            "repository": {
+                "id": "r1",
+                "project": { "id": "p1" },
+                "webUrl": "https://dev.azure.com/org/Project/_git/repo"
+            },
+            "lastMergeSourceCommit": { "commitId": "3f2a1c9d" }
+        });
+
+        let pr = PullRequest::decode(&json).expect("pull request should decode");
+
+        assert_eq!(pr.id, 42);
+        assert_eq!(pr.project_id.as_deref(), Some("p1"));
+        assert_eq!(pr.repository_id.as_deref(), Some("r1"));
+        assert_eq!(pr.last_merge_source_commit.as_deref(), Some("3f2a1c9d"));
+        assert_eq!(pr.source_branch, "feature/12-login");
+        assert_eq!(pr.target_branch, "main");
+        assert_eq!(pr.created_by.as_deref(), Some("Ada"));
+        assert_eq!(pr.reviewers.len(), 3);
+        assert!(pr.reviewers[0].is_required);
+        assert_eq!(pr.reviewers[1].vote, Vote::WaitingForAuthor);
+        assert_eq!(
+            pr.url.as_deref(),
+            Some("https://dev.azure.com/org/Project/_git/repo/pullrequest/42")
+        );
+    }
+
+    #[test]
+    fn vote_summary_counts_votes_by_outcome() {
+        let json = serde_json::json!({
+            "pullRequestId": 7,
+            "reviewers": [
+                { "displayName": "A", "vote": 10 },
+                { "displayName": "B", "vote": 5 },
+                { "displayName": "C", "vote": -10 },
+                { "displayName": "D", "vote": 0 }
+            ]
+        });
+
+        let pr = PullRequest::decode(&json).expect("pull request should decode");
+
+        assert_eq!(pr.vote_summary(), "2 approved, 1 rejected, 1 pending");
+    }
+
+    #[test]
+    fn vote_summary_reports_missing_reviewers() {
+        let pr = PullRequest::decode(&serde_json::json!({ "pullRequestId": 7 }))
+            .expect("pull request should decode");
+
+        assert_eq!(pr.vote_summary(), "no reviewers");
+    }
+
+    #[test]
+    fn decode_list_reads_value_array() {
+        let json = serde_json::json!({
+            "count": 2,
+            "value": [{ "pullRequestId": 1 }, { "pullRequestId": 2 }]
+        });
+
+        let prs = decode_list(&json).expect("list should decode");
+
+        assert_eq!(prs.iter().map(|pr| pr.id).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn reads_linked_pull_request_ids_from_relations() {
+        let json = serde_json::json!({
+            "relations": [
+                { "rel": "ArtifactLink", "url": "vstfs:///Git/PullRequestId/p1%2Fr1%2F12" },
+                { "rel": "System.LinkTypes.Hierarchy-Reverse", "url": "https://dev.azure.com/org/_apis/wit/workItems/3" },
+                { "rel": "ArtifactLink", "url": "vstfs:///Git/Ref/p1%2Fr1%2FGBmain" },
+                { "rel": "ArtifactLink", "url": "vstfs:///Git/PullRequestId/p1%2Fr2%2F40" },
+                { "rel": "ArtifactLink", "url": "vstfs:///Git/PullRequestId/p1%2Fr1%2F12" }
+            ]
+        });
+
+        assert_eq!(linked_pull_request_ids(&json), [12, 40]);
+        assert!(linked_pull_request_ids(&serde_json::json!({ "id": 1 })).is_empty());
+    }
+
+    #[test]
+    fn sort_linked_puts_active_pull_requests_first() {
+        let mut prs = [
+            (3, "completed"),
+            (5, "active"),
+            (9, "abandoned"),
+            (7, "active"),
+        ]
+        .map(|(id, status)| {
+            PullRequest::decode(&serde_json::json!({ "pullRequestId": id, "status": status }))
+                .unwrap()
+        });
+
+        sort_linked(&mut prs);
+
+        assert_eq!(prs.map(|pr| pr.id), [7, 5, 9, 3]);
+    }
+}
@@ -0,0 +1,116 @@
+//! Azure Boards saved queries: the query definition lookup and the work item
+//! IDs a query run returns.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+/// A saved query, as identified by `cazdo query --saved`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedQuery {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+}
+
+impl SavedQuery {
+    pub(super) fn decode(json: &Value) -> Result<Self> {
+        let str_field = |key: &str| json.get(key).and_then(Value::as_str);
+        let id = str_field("id").context("Missing 'id' in saved query response")?;
+
+        if json.get("isFolder").and_then(Value::as_bool) == Some(true) {
+            anyhow::bail!(
+                "'{}' is a query folder, not a query",
+                str_field("path").unwrap_or(id)
+            );
+        }
+
+        Ok(Self {
+            id: id.to_string(),
+            name: str_field("name").unwrap_or_default().to_string(),
+            path: str_field("path").unwrap_or_default().to_string(),
+        })
+    }
+}
+
+/// Work item IDs from a WIQL query result, in result order.
+///
+/// Flat queries list `workItems`; tree and one-hop queries list
+/// `workItemRelations`, where a work item can appear more than once.
+pub(super) fn decode_result_ids(json: &Value) -> Result<Vec<u32>> {
+    let id_of = |item: &Value| {
+        item.get("id")
+            .and_then(Value::as_u64)
+            .and_then(|id| u32::try_from(id).ok())
+    };
+
+    let ids: Vec<u32> = if let Some(items) = json.get("workItems").and_then(Value::as_array) {
+        items.iter().filter_map(id_of).collect()
+    } else if let Some(relations) = json.get("workItemRelations").and_then(Value::as_array) {
+        relations
+            .iter()
+            .filter_map(|relation| relation.get("target").and_then(id_of))
+            .collect()
+    } else {
+        anyhow::bail!("Missing 'workItems' in query result");
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    Ok(ids.into_iter().filter(|id| seen.insert(*id)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_saved_query() {
+        let json = serde_json::json!({
+            "id": "8a8c8212-15ca-41ed-97aa-1d6fbfbcd581",
+            "name": "Active Bugs",
+            "path": "Shared Queries/Team/Active Bugs",
+            "isFolder": false
+        });
+
+        let query = SavedQuery::decode(&json).expect("query should decode");
+
+        assert_eq!(query.name, "Active Bugs");
+        assert_eq!(query.path, "Shared Queries/Team/Active Bugs");
+    }
+
+    #[test]
+    fn rejects_query_folders() {
+        let json = serde_json::json!({
+            "id": "df60fdf6-3b3f-4f3f-9d8a-5b4b3f1c2a10",
+            "path": "Shared Queries/Team",
+            "isFolder": true
+        });
+
+        let error = SavedQuery::decode(&json).expect_err("folders cannot be run");
+
+        assert!(error.to_string().contains("is a query folder"));
+    }
+
+    #[test]
+    fn decodes_flat_query_result_ids() {
+        let json = serde_json::json!({
+            "queryType": "flat",
+            "workItems": [{ "id": 12 }, { "id": 7 }]
+        });
+
+        assert_eq!(decode_result_ids(&json).unwrap(), vec![12, 7]);
+    }
+
+    #[test]
+    fn decodes_tree_query_result_ids_once() {
+        let json = serde_json::json!({
+            "queryType": "tree",
+            "workItemRelations": [
+                { "target": { "id": 1 } },
+                { "source": { "id": 1 }, "target": { "id": 2 } },
+                { "source": { "id": 3 }, "target": { "id": 2 } }
+            ]
+        });
+
+        assert_eq!(decode_result_ids(&json).unwrap(), vec![1, 2]);
+    }
+}
@@ -0,0 +1,86 @@
+//! Task hours: the Remaining Work and Completed Work scheduling fields used
+//! for hour-based sprint burndown.
+
+use serde_json::Value;
+
+const REMAINING_WORK: &str = "Microsoft.VSTS.Scheduling.RemainingWork";
+const COMPLETED_WORK: &str = "Microsoft.VSTS.Scheduling.CompletedWork";
+
+/// Remaining and completed hours; `None` leaves a field unchanged in an
+/// update, or means it is unset on the work item.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WorkHours {
+    pub remaining: Option<f64>,
+    pub completed: Option<f64>,
+}
+
+impl WorkHours {
+    /// JSON Patch operations setting the given fields.
+    pub(super) fn to_patch(self) -> Value {
+        [
+            (REMAINING_WORK, self.remaining),
+            (COMPLETED_WORK, self.completed),
+        ]
+        .into_iter()
+        .filter_map(|(field, hours)| {
+            hours.map(|hours| {
+                serde_json::json!({
+                    "op": "add",
+                    "path": format!("/fields/{field}"),
+                    "value": hours,
+                })
+            })
+        })
+        .collect()
+    }
+
+    /// The hours of a work item response.
+    pub(super) fn decode(json: &Value) -> Self {
+        let field = |name: &str| json.get("fields")?.get(name)?.as_f64();
+        Self {
+            remaining: field(REMAINING_WORK),
+            completed: field(COMPLETED_WORK),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patch_sets_only_given_fields() {
+        let hours = WorkHours {
+            remaining: Some(4.0),
+            completed: None,
+        };
+
+        assert_eq!(
+            hours.to_patch(),
+            serde_json::json!([{
+                "op": "add",
+                "path": "/fields/Microsoft.VSTS.Scheduling.RemainingWork",
+                "value": 4.0,
+            }])
+        );
+    }
+
+    #[test]
+    fn decodes_hours_from_work_item() {
+        let json = serde_json::json!({
+            "id": 42,
+            "fields": {
+                "Microsoft.VSTS.Scheduling.RemainingWork": 1.5,
+                "Microsoft.VSTS.Scheduling.CompletedWork": 6
+            }
+        });
+
+        assert_eq!(
+            WorkHours::decode(&json),
+            WorkHours {
+                remaining: Some(1.5),
+                completed: Some(6.0),
+            }
+        );
+    }
+}
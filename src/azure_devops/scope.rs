@@ -0,0 +1,73 @@
+//! The PAT scopes cazdo uses and the features that need each one.
+//!
+//! A PAT cannot list its own scopes (the PAT lifecycle API requires an Entra
+//! ID token), so `cazdo config verify` checks each scope with a request that
+//! needs it but changes nothing.
+
+use reqwest::StatusCode;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatScope {
+    WorkItemsRead,
+    WorkItemsWrite,
+    CodeRead,
+    BuildRead,
+}
+
+impl PatScope {
+    pub const ALL: [PatScope; 4] = [
+        PatScope::WorkItemsRead,
+        PatScope::WorkItemsWrite,
+        PatScope::CodeRead,
+        PatScope::BuildRead,
+    ];
+
+    /// The scope's name on the Azure DevOps token page
+    pub fn label(self) -> &'static str {
+        match self {
+            PatScope::WorkItemsRead => "Work Items (Read)",
+            PatScope::WorkItemsWrite => "Work Items (Read & write)",
+            PatScope::CodeRead => "Code (Read)",
+            PatScope::BuildRead => "Build (Read)",
+        }
+    }
+
+    /// The commands that need the scope
+    pub fn features(self) -> &'static [&'static str] {
+        match self {
+            PatScope::WorkItemsRead => &["TUI", "wi", "commit-template", "query", "report"],
+            PatScope::WorkItemsWrite => &["wi comment", "wi state", "wi time", "TUI state change"],
+            PatScope::CodeRead => &["pr list", "report"],
+            PatScope::BuildRead => &["pipeline open", "report"],
+        }
+    }
+}
+
+/// Whether a scope check's response shows the scope is granted. The checks
+/// ask for resources that do not exist, so anything but an authorization
+/// failure (such as a 404) means the request got past the scope check.
+pub(super) fn scope_granted(status: StatusCode) -> bool {
+    !matches!(
+        status,
+        StatusCode::UNAUTHORIZED
+            | StatusCode::FORBIDDEN
+            | StatusCode::NON_AUTHORITATIVE_INFORMATION
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_authorization_failures_mean_a_missing_scope() {
+        assert!(scope_granted(StatusCode::OK));
+        assert!(scope_granted(StatusCode::NOT_FOUND));
+        assert!(scope_granted(StatusCode::BAD_REQUEST));
+        assert!(!scope_granted(StatusCode::UNAUTHORIZED));
+        assert!(!scope_granted(StatusCode::FORBIDDEN));
+        assert!(!scope_granted(StatusCode::NON_AUTHORITATIVE_INFORMATION));
+    }
+}
@@ -0,0 +1,105 @@
+//! Work item states and the transitions between them, as the process of a
+//! work item type defines them. Customized processes add, rename, and remove
+//! states, so the valid targets come from the server rather than a fixed list.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+const STATE: &str = "System.State";
+
+/// A state a work item can move to, with its category: `Proposed`,
+/// `InProgress`, `Resolved`, `Completed`, or `Removed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateTransition {
+    pub state: String,
+    pub category: String,
+}
+
+/// The states a work item in state `from` can move to, from a work item type
+/// response: the `transitions` out of `from`, in the order of `states`.
+pub(super) fn decode_transitions(json: &Value, from: &str) -> Result<Vec<StateTransition>> {
+    let transitions = json
+        .get("transitions")
+        .and_then(Value::as_object)
+        .context("Missing 'transitions' in work item type response")?;
+    let targets: Vec<&str> = transitions
+        .get(from)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|transition| transition.get("to").and_then(Value::as_str))
+        .filter(|&to| to != from)
+        .collect();
+
+    let states = json
+        .get("states")
+        .and_then(Value::as_array)
+        .context("Missing 'states' in work item type response")?;
+    Ok(states
+        .iter()
+        .filter_map(|state| {
+            let name = state.get("name").and_then(Value::as_str)?;
+            targets.contains(&name).then(|| StateTransition {
+                state: name.to_string(),
+                category: state
+                    .get("category")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+        })
+        .collect())
+}
+
+/// The JSON Patch operation moving a work item to `state`.
+pub(super) fn state_patch(state: &str) -> Value {
+    serde_json::json!([{
+        "op": "add",
+        "path": format!("/fields/{STATE}"),
+        "value": state,
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn decodes_transitions_out_of_the_current_state_in_state_order() {
+        let json = json!({
+            "name": "Bug",
+            "states": [
+                { "name": "New", "color": "b2b2b2", "category": "Proposed" },
+                { "name": "Triaged", "color": "ff9d00", "category": "Proposed" },
+                { "name": "Active", "color": "007acc", "category": "InProgress" },
+                { "name": "Closed", "color": "339933", "category": "Completed" }
+            ],
+            "transitions": {
+                "": [{ "to": "New", "actions": null }],
+                "New": [
+                    { "to": "Closed", "actions": null },
+                    { "to": "Active", "actions": null },
+                    { "to": "New", "actions": null }
+                ],
+                "Active": [{ "to": "Closed", "actions": null }]
+            }
+        });
+
+        assert_eq!(
+            decode_transitions(&json, "New").unwrap(),
+            [
+                StateTransition {
+                    state: "Active".to_string(),
+                    category: "InProgress".to_string(),
+                },
+                StateTransition {
+                    state: "Closed".to_string(),
+                    category: "Completed".to_string(),
+                },
+            ]
+        );
+        assert!(decode_transitions(&json, "Closed").unwrap().is_empty());
+        assert!(decode_transitions(&json!({ "states": [] }), "New").is_err());
+    }
+}
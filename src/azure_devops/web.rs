@@ -0,0 +1,117 @@
+//! Browser URLs for Azure DevOps pages that have no API object to read a
+//! `webUrl` from: boards, sprints, repository pages, and pipelines.
+
+use anyhow::{Context, Result};
+use reqwest::Url;
+
+use super::RepositoryRef;
+
+/// Builds web URLs under an organization (or Azure DevOps Server collection).
+pub struct WebLinks {
+    organization_url: Url,
+}
+
+impl WebLinks {
+    pub fn new(organization_url: &str) -> Result<Self> {
+        let organization_url = Url::parse(organization_url.trim())
+            .ok()
+            .filter(|url| !url.cannot_be_a_base())
+            .with_context(|| format!("Invalid organization URL '{}'", organization_url))?;
+        Ok(Self { organization_url })
+    }
+
+    /// The team's board, or the board of the last team used in `project`.
+    pub fn board(&self, project: &str, team: Option<&str>) -> Url {
+        match team {
+            Some(team) => self.url(&[project, "_boards", "board", "t", team]),
+            None => self.url(&[project, "_boards", "board"]),
+        }
+    }
+
+    /// The taskboard of the team's current sprint.
+    pub fn sprint(&self, project: &str, team: Option<&str>) -> Url {
+        match team {
+            Some(team) => self.url(&[project, "_sprints", "taskboard", team]),
+            None => self.url(&[project, "_sprints", "taskboard"]),
+        }
+    }
+
+    pub fn branches(&self, repository: &RepositoryRef) -> Url {
+        self.url(&[
+            &repository.project,
+            "_git",
+            &repository.repository,
+            "branches",
+        ])
+    }
+
+    /// A pipeline definition's runs, or every pipeline in `project`.
+    pub fn pipeline(&self, project: &str, definition_id: Option<u64>) -> Url {
+        let mut url = self.url(&[project, "_build"]);
+        if let Some(id) = definition_id {
+            url.query_pairs_mut()
+                .append_pair("definitionId", &id.to_string());
+        }
+        url
+    }
+
+    /// `{organization}/{segments...}`, with each segment percent-encoded.
+    fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.organization_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty().extend(segments);
+        }
+        url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn links() -> WebLinks {
+        WebLinks::new("https://dev.azure.com/org/").expect("organization URL should parse")
+    }
+
+    #[test]
+    fn builds_board_and_sprint_urls_with_encoded_team() {
+        assert_eq!(
+            links().board("My Project", None).as_str(),
+            "https://dev.azure.com/org/My%20Project/_boards/board"
+        );
+        assert_eq!(
+            links().board("Web", Some("Web Team")).as_str(),
+            "https://dev.azure.com/org/Web/_boards/board/t/Web%20Team"
+        );
+        assert_eq!(
+            links().sprint("Web", Some("Web Team")).as_str(),
+            "https://dev.azure.com/org/Web/_sprints/taskboard/Web%20Team"
+        );
+    }
+
+    #[test]
+    fn builds_repository_and_pipeline_urls() {
+        let repository = RepositoryRef {
+            project: "Web".to_string(),
+            repository: "frontend".to_string(),
+        };
+
+        assert_eq!(
+            links().branches(&repository).as_str(),
+            "https://dev.azure.com/org/Web/_git/frontend/branches"
+        );
+        assert_eq!(
+            links().pipeline("Web", Some(12)).as_str(),
+            "https://dev.azure.com/org/Web/_build?definitionId=12"
+        );
+        assert_eq!(
+            links().pipeline("Web", None).as_str(),
+            "https://dev.azure.com/org/Web/_build"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_organization_urls() {
+        assert!(WebLinks::new("dev.azure.com/org").is_err());
+    }
+}
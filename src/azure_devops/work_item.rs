@@ -1,8 +1,26 @@
+use chrono::{DateTime, Local, Utc};
 use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::str::FromStr;
 
+use crate::config::EstimateField;
+
+const STORY_POINTS: &str = "Microsoft.VSTS.Scheduling.StoryPoints";
+const EFFORT: &str = "Microsoft.VSTS.Scheduling.Effort";
+const ORIGINAL_ESTIMATE: &str = "Microsoft.VSTS.Scheduling.OriginalEstimate";
+const PRIORITY: &str = "Microsoft.VSTS.Common.Priority";
+const SEVERITY: &str = "Microsoft.VSTS.Common.Severity";
+
+/// Date fields a work item should be finished by
+pub const DEADLINE_FIELDS: &[&str] = &[
+    "Microsoft.VSTS.Scheduling.TargetDate",
+    "Microsoft.VSTS.Scheduling.DueDate",
+];
+
 /// Format of a rich text field's value, from Azure DevOps' `multilineFieldsFormat` map.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum FieldFormat {
     #[default]
     Html,
@@ -10,14 +28,14 @@ pub enum FieldFormat {
 }
 
 /// A rich text field from Azure DevOps.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RichTextField {
     pub name: String,
     pub value: String,
     pub format: FieldFormat,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct WorkItem {
     pub id: u32,
@@ -29,6 +47,8 @@ pub struct WorkItem {
     pub tags: Vec<String>,
     /// Dynamic rich text fields (Description, Acceptance Criteria, Repro Steps, etc.)
     pub rich_text_fields: Vec<RichTextField>,
+    /// Every plain field as display text, by reference name
+    pub fields: BTreeMap<String, String>,
 }
 
 pub(crate) struct WorkItemParts<'a> {
@@ -40,9 +60,31 @@ pub(crate) struct WorkItemParts<'a> {
     pub url: Option<String>,
     pub tags: Vec<String>,
     pub rich_text_fields: Vec<RichTextField>,
+    pub fields: BTreeMap<String, String>,
+}
+
+/// A work item's size, from the scheduling field its process uses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Estimate {
+    StoryPoints(f64),
+    Effort(f64),
+    /// Original estimate, in hours
+    Hours(f64),
+}
+
+impl fmt::Display for Estimate {
+    /// `5 pts`, `effort 8`, or `est 12h`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::StoryPoints(1.0) => write!(f, "1 pt"),
+            Self::StoryPoints(points) => write!(f, "{points} pts"),
+            Self::Effort(effort) => write!(f, "effort {effort}"),
+            Self::Hours(hours) => write!(f, "est {hours}h"),
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WorkItemType {
     Bug,
     ProductBacklogItem,
@@ -82,6 +124,19 @@ impl WorkItemType {
         }
     }
 
+    /// Single-width stand-in for [`icon`](Self::icon) when emoji are disabled.
+    pub fn ascii_icon(&self) -> &'static str {
+        match self {
+            Self::Bug => "[B]",
+            Self::ProductBacklogItem => "[P]",
+            Self::UserStory => "[S]",
+            Self::Task => "[T]",
+            Self::Feature => "[F]",
+            Self::Epic => "[E]",
+            Self::Other(_) => "[-]",
+        }
+    }
+
     pub fn display_name(&self) -> &str {
         match self {
             Self::Bug => "Bug",
@@ -95,7 +150,7 @@ impl WorkItemType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WorkItemState {
     New,
     Approved,
@@ -141,6 +196,20 @@ impl WorkItemState {
         }
     }
 
+    /// Single-width stand-in for [`icon`](Self::icon) when emoji are disabled.
+    pub fn ascii_icon(&self) -> &'static str {
+        match self {
+            Self::New => "[ ]",
+            Self::Approved => "[+]",
+            Self::Committed => "[>]",
+            Self::Active => "[*]",
+            Self::Resolved => "[~]",
+            Self::Closed | Self::Done => "[x]",
+            Self::Removed => "[-]",
+            Self::Other(_) => "[?]",
+        }
+    }
+
     pub fn display_name(&self) -> &str {
         match self {
             Self::New => "New",
@@ -155,6 +224,11 @@ impl WorkItemState {
         }
     }
 
+    /// Whether work on the item has ended: closed, done, or removed.
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Closed | Self::Done | Self::Removed)
+    }
+
     pub fn color(&self) -> Color {
         match self {
             Self::New | Self::Approved => Color::Gray,
@@ -168,6 +242,17 @@ impl WorkItemState {
     }
 }
 
+/// Color of a priority or severity rank, from red for 1, the most urgent,
+/// to gray for 4 and beyond.
+pub fn urgency_color(rank: u8) -> Color {
+    match rank {
+        0 | 1 => Color::Red,
+        2 => Color::Yellow,
+        3 => Color::Blue,
+        _ => Color::Gray,
+    }
+}
+
 impl WorkItem {
     pub(crate) fn from_parts(parts: WorkItemParts<'_>) -> Self {
         Self {
@@ -179,6 +264,110 @@ impl WorkItem {
             url: parts.url,
             tags: parts.tags,
             rich_text_fields: parts.rich_text_fields,
+            fields: parts.fields,
         }
     }
+
+    /// The area path (`System.AreaPath`), e.g. `Web\Checkout`.
+    pub fn area_path(&self) -> Option<&str> {
+        self.fields.get("System.AreaPath").map(String::as_str)
+    }
+
+    /// The iteration path (`System.IterationPath`), e.g. `Web\Sprint 3`.
+    pub fn iteration_path(&self) -> Option<&str> {
+        self.fields.get("System.IterationPath").map(String::as_str)
+    }
+
+    /// The ID of the parent work item (`System.Parent`), if it has one.
+    pub fn parent_id(&self) -> Option<u32> {
+        self.fields.get("System.Parent")?.parse().ok()
+    }
+
+    /// The priority (`Microsoft.VSTS.Common.Priority`), 1 being the highest.
+    pub fn priority(&self) -> Option<u8> {
+        self.fields.get(PRIORITY)?.trim().parse().ok()
+    }
+
+    /// The severity (`Microsoft.VSTS.Common.Severity`), e.g. `2 - High`.
+    pub fn severity(&self) -> Option<&str> {
+        self.fields.get(SEVERITY).map(String::as_str)
+    }
+
+    /// The rank a severity starts with: 2 for `2 - High`.
+    pub fn severity_rank(&self) -> Option<u8> {
+        let severity = self.severity()?.trim_start();
+        let digits = severity
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(severity.len());
+        severity[..digits].parse().ok()
+    }
+
+    /// The size of the work item from the scheduling field `field`; with
+    /// [`EstimateField::Auto`], the first set of story points, effort, and
+    /// original estimate.
+    pub fn estimate(&self, field: EstimateField) -> Option<Estimate> {
+        let value = |reference: &str| self.fields.get(reference)?.parse::<f64>().ok();
+        match field {
+            EstimateField::Auto => self
+                .estimate(EstimateField::StoryPoints)
+                .or_else(|| self.estimate(EstimateField::Effort))
+                .or_else(|| self.estimate(EstimateField::OriginalEstimate)),
+            EstimateField::StoryPoints => value(STORY_POINTS).map(Estimate::StoryPoints),
+            EstimateField::Effort => value(EFFORT).map(Estimate::Effort),
+            EstimateField::OriginalEstimate => value(ORIGINAL_ESTIMATE).map(Estimate::Hours),
+            EstimateField::Off => None,
+        }
+    }
+
+    /// When the work item was created (`System.CreatedDate`), as a Unix
+    /// timestamp.
+    pub fn created_at(&self) -> Option<i64> {
+        self.timestamp("System.CreatedDate")
+    }
+
+    /// Who created the work item (`System.CreatedBy`).
+    pub fn created_by(&self) -> Option<&str> {
+        self.fields.get("System.CreatedBy").map(String::as_str)
+    }
+
+    /// When the work item was last changed (`System.ChangedDate`), as a Unix
+    /// timestamp.
+    pub fn changed_at(&self) -> Option<i64> {
+        self.timestamp("System.ChangedDate")
+    }
+
+    /// Who last changed the work item (`System.ChangedBy`).
+    pub fn changed_by(&self) -> Option<&str> {
+        self.fields.get("System.ChangedBy").map(String::as_str)
+    }
+
+    /// Whether the work item is still open after the day of its target or
+    /// due date.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        DEADLINE_FIELDS
+            .iter()
+            .any(|reference| self.is_past_deadline(reference, now))
+    }
+
+    /// Whether the work item is still open after the day of date field
+    /// `reference`, in local time.
+    pub fn is_past_deadline(&self, reference: &str, now: DateTime<Utc>) -> bool {
+        let Some(deadline) = self.timestamp(reference) else {
+            return false;
+        };
+        let day = |timestamp: DateTime<Utc>| timestamp.with_timezone(&Local).date_naive();
+        !self.state.is_closed()
+            && DateTime::from_timestamp(deadline, 0)
+                .is_some_and(|deadline| day(deadline) < day(now))
+    }
+
+    fn timestamp(&self, reference: &str) -> Option<i64> {
+        let value = self.fields.get(reference)?;
+        Some(DateTime::parse_from_rfc3339(value).ok()?.timestamp())
+    }
+
+    /// The revision number (`System.Rev`), which grows with every change.
+    pub fn revision(&self) -> Option<u32> {
+        self.fields.get("System.Rev")?.parse().ok()
+    }
 }
@@ -1,4 +1,11 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+mod alias;
+
+pub use alias::expand_aliases;
 
 #[derive(Parser)]
 #[command(name = "cazdo")]
@@ -11,6 +18,34 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+    /// Print machine-readable JSON instead of human-readable text
+    #[arg(long, global = true)]
+    pub json: bool,
+    /// Print only essential values, without progress messages or decoration
+    #[arg(long, short, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+    /// Print request URLs, timing, and git details to stderr
+    #[arg(long, short, global = true)]
+    pub verbose: bool,
+    /// Print plain text without ANSI styling (implied when stdout is not a
+    /// terminal or `NO_COLOR` is set)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+    /// Azure DevOps organization URL, instead of the configured one
+    #[arg(long, global = true, env = "CAZDO_ORG", value_name = "URL")]
+    pub org: Option<String>,
+    /// Azure DevOps project, instead of the one detected from the `origin` remote
+    #[arg(long, global = true, env = "CAZDO_PROJECT")]
+    pub project: Option<String>,
+    /// Append a diagnostic log (requests, git operations, TUI actions) to this file
+    #[arg(long, global = true, env = "CAZDO_LOG", value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+    /// Start the TUI in screen-reader-friendly plain mode (see `[display].plain`)
+    #[arg(long)]
+    pub plain_tui: bool,
+    /// Write a JSON summary of the TUI session to this file on quit
+    #[arg(long, env = "CAZDO_SESSION_SUMMARY", value_name = "PATH")]
+    pub session_summary: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -20,29 +55,350 @@ pub enum Commands {
         #[command(subcommand)]
         action: ConfigAction,
     },
-    /// Show a bounded work item preview in the console
+    /// Show a bounded work item preview in the console (`--format json` prints the full work item)
+    #[command(args_conflicts_with_subcommands = true)]
     Wi {
-        /// Work item ID (if omitted, uses the current branch)
-        id: Option<u32>,
+        #[command(subcommand)]
+        action: Option<WiAction>,
+        /// Work item ID, or `-` to read IDs from stdin (if omitted, uses the current branch)
+        id: Option<WorkItemIds>,
         /// Show a longer, still bounded description preview
         #[arg(long, conflicts_with = "json")]
         long: bool,
-        /// Print the full Azure DevOps work item JSON
-        #[arg(long, conflicts_with = "long")]
-        json: bool,
+        /// Output format: box by default, plain when stdout is not a terminal (`--json` is
+        /// shorthand for `--format json`)
+        #[arg(long, value_enum)]
+        format: Option<WorkItemFormat>,
+    },
+    /// Inspect local branches and their work items
+    Branch {
+        #[command(subcommand)]
+        action: BranchAction,
+    },
+    /// Open the team board or current sprint in the browser
+    Board {
+        #[command(subcommand)]
+        action: BoardAction,
+    },
+    /// Open the current repository's pipelines in the browser
+    Pipeline {
+        #[command(subcommand)]
+        action: PipelineAction,
+    },
+    /// Print a commit message skeleton for the current branch's work item, for
+    /// `git commit -t <(cazdo commit-template)`
+    CommitTemplate {
+        /// Work item ID (if omitted, uses the current branch)
+        id: Option<u32>,
+    },
+    /// Manage the TUI's work item cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Manage git hooks that link commits to work items
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+    /// Work with pull requests for the current repository
+    Pr {
+        #[command(subcommand)]
+        action: PrAction,
+    },
+    /// Run a saved Azure DevOps query and list its work items
+    Query {
+        /// Saved query path (e.g. `Shared Queries/Team/Active Bugs`) or GUID
+        #[arg(long, value_name = "QUERY")]
+        saved: String,
+        /// Output format (`--json` is shorthand for `--format json`)
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+    },
+    /// Print a Markdown status report of local branches, work items, PRs, and builds
+    Report {
+        /// Only include branches with commits since a date (`2024-05-01`) or age (`7d`, `2w`)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+    },
+    /// List branches deleted in TUI sessions, or restore those of one session
+    Restore {
+        /// Session number from the list (1 is the latest); lists sessions if omitted
+        session: Option<usize>,
+        /// Only restore these branches of the session
+        #[arg(requires = "session")]
+        branches: Vec<String>,
+    },
+    /// Check and preview TUI theme files
+    Theme {
+        #[command(subcommand)]
+        action: ThemeAction,
+    },
+    /// Print the cazdo version
+    Version {
+        /// Check GitHub for a newer release
+        #[arg(long)]
+        check: bool,
     },
     /// Update cazdo to the latest release
     Update,
 }
 
+/// A single work item ID, or `-` for newline-separated IDs on stdin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkItemIds {
+    Id(u32),
+    Stdin,
+}
+
+impl FromStr for WorkItemIds {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "-" {
+            return Ok(Self::Stdin);
+        }
+
+        s.parse()
+            .map(Self::Id)
+            .map_err(|_| format!("'{s}' is not a work item ID or '-'"))
+    }
+}
+
+#[derive(Subcommand)]
+pub enum WiAction {
+    /// Open the work item in the browser
+    Open {
+        /// Work item ID (if omitted, uses the current branch)
+        id: Option<u32>,
+    },
+    /// Add a comment to a work item
+    Comment {
+        /// Work item ID
+        id: u32,
+        /// Comment text (read from stdin when omitted)
+        text: Option<String>,
+    },
+    /// Move a work item, or each ID on stdin, to another state
+    State {
+        /// Work item ID, or `-` to read IDs from stdin
+        id: WorkItemIds,
+        /// State to move to, e.g. Resolved
+        state: String,
+    },
+    /// Set a task's remaining and/or completed work in hours
+    Time {
+        /// Work item ID (if omitted, uses the current branch)
+        id: Option<u32>,
+        /// Remaining work in hours
+        #[arg(long, value_name = "HOURS", value_parser = parse_hours, required_unless_present = "completed")]
+        remaining: Option<f64>,
+        /// Completed work in hours
+        #[arg(long, value_name = "HOURS", value_parser = parse_hours)]
+        completed: Option<f64>,
+    },
+    /// List the work items a WIQL query returns (default: `[query].wiql`, or
+    /// open work items assigned to you)
+    List {
+        /// WIQL query to run instead of the configured one
+        #[arg(long, value_name = "WIQL")]
+        query: Option<String>,
+        /// Output format (`--json` is shorthand for `--format json`)
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+    },
+}
+
+fn parse_hours(text: &str) -> Result<f64, String> {
+    match text.parse::<f64>() {
+        Ok(hours) if hours.is_finite() && hours >= 0.0 => Ok(hours),
+        _ => Err(format!("'{text}' is not a non-negative number of hours")),
+    }
+}
+
+#[derive(Subcommand)]
+pub enum BranchAction {
+    /// List local branches with work item, remote status, and last commit age
+    List {
+        /// Output format (`--json` is shorthand for `--format json`)
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+    },
+    /// Show the current branch's work item and remote status
+    Status {
+        /// Show every local branch instead of only the current one
+        #[arg(long)]
+        all: bool,
+        /// Stable tab-separated records for scripts and shell prompts
+        #[arg(long, conflicts_with = "json")]
+        porcelain: bool,
+    },
+    /// Delete local branches whose upstream branch is gone
+    PruneGone {
+        /// Show which branches would be deleted without deleting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List local branches whose upstream is gone and delete them after asking once
+    Clean {
+        /// Only delete branches merged into the default branch
+        #[arg(long)]
+        merged: bool,
+        /// Show which branches would be deleted without deleting them
+        #[arg(long)]
+        dry_run: bool,
+        /// Delete without asking
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Open the repository's branches page in the browser
+    Open,
+}
+
+#[derive(Subcommand)]
+pub enum BoardAction {
+    /// Open the team board in the browser
+    Open {
+        /// Team name (defaults to `azure_devops.team`, else the last team used in Azure DevOps)
+        #[arg(long)]
+        team: Option<String>,
+        /// Open the current sprint's taskboard instead of the board
+        #[arg(long)]
+        sprint: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheAction {
+    /// Delete the cached work items, so the TUI fetches them all again
+    Clear,
+}
+
+#[derive(Subcommand)]
+pub enum HooksAction {
+    /// Install a prepare-commit-msg hook that appends AB#<id> from the branch name
+    Install {
+        /// Remove the hook instead, restoring any hook it replaced
+        #[arg(long, conflicts_with = "force")]
+        uninstall: bool,
+        /// Replace an existing hook that cazdo did not install, keeping it as a backup
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ThemeAction {
+    /// Render a sample screen and every style of a theme file
+    Preview {
+        /// Theme file to preview
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PipelineAction {
+    /// Open the pipeline that last built a branch in the browser
+    Open {
+        /// Branch whose latest build picks the pipeline (defaults to the current branch)
+        #[arg(long)]
+        branch: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PrAction {
+    /// Create a pull request for the current branch from its work item
+    Create {
+        /// Create the pull request as a draft
+        #[arg(long)]
+        draft: bool,
+        /// Target branch (defaults to the repository's default branch)
+        #[arg(long)]
+        target: Option<String>,
+    },
+    /// List active pull requests for the repository
+    List {
+        /// Only list pull requests you created
+        #[arg(long)]
+        mine: bool,
+        /// Output format (`--json` is shorthand for `--format json`)
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum WorkItemFormat {
+    /// Bounded preview in a box
+    Box,
+    /// `key: value` lines for scripts
+    Plain,
+    /// All fields as a Markdown document
+    Markdown,
+    /// Full work item JSON from Azure DevOps
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    Table,
+    Json,
+    Tsv,
+}
+
 #[derive(Subcommand)]
 pub enum ConfigAction {
-    /// Initialize config with default values (overwrites existing)
+    /// Set up cazdo step by step: organization, PAT, project, protected branches
     Init,
     /// Show current configuration
     Show,
     /// Verify Azure DevOps organization URL and PAT access
-    Verify,
+    Verify {
+        /// Check the config file, URL, patterns, and PAT without contacting Azure DevOps
+        #[arg(long)]
+        offline: bool,
+    },
+    /// Print one config value (e.g. `branches.protected`)
+    Get {
+        /// Dotted config key
+        key: String,
+    },
+    /// Set one config value, creating the config file if needed
+    Set {
+        /// Dotted config key
+        key: String,
+        /// New value; lists are comma-separated
+        value: String,
+    },
+    /// Print the config as TOML without the PAT, to share team settings
+    Export,
+    /// Merge settings from a TOML file (e.g. one from `config export`) into the config
+    Import {
+        /// TOML file with a whole config or only some sections
+        file: PathBuf,
+    },
+    /// Manage protected branch patterns
+    Protect {
+        #[command(subcommand)]
+        action: ProtectAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProtectAction {
+    /// Protect branches matching patterns (`releases/*`, `re:<regex>`)
+    Add {
+        #[arg(required = true)]
+        patterns: Vec<String>,
+    },
+    /// Stop protecting branches matching patterns
+    Remove {
+        #[arg(required = true)]
+        patterns: Vec<String>,
+    },
+    /// List protected patterns and the local branches they match
+    List,
 }
 
 #[cfg(test)]
@@ -54,10 +410,15 @@ mod tests {
         let cli = Cli::parse_from(["cazdo", "wi"]);
 
         match cli.command {
-            Some(Commands::Wi { id, long, json }) => {
+            Some(Commands::Wi {
+                action: None,
+                id,
+                long,
+                ..
+            }) => {
                 assert_eq!(id, None);
                 assert!(!long);
-                assert!(!json);
+                assert!(!cli.json);
             }
             _ => panic!("expected wi command without id"),
         }
@@ -68,10 +429,15 @@ mod tests {
         let cli = Cli::parse_from(["cazdo", "wi", "120"]);
 
         match cli.command {
-            Some(Commands::Wi { id, long, json }) => {
-                assert_eq!(id, Some(120));
+            Some(Commands::Wi {
+                action: None,
+                id,
+                long,
+                ..
+            }) => {
+                assert_eq!(id, Some(WorkItemIds::Id(120)));
                 assert!(!long);
-                assert!(!json);
+                assert!(!cli.json);
             }
             _ => panic!("expected wi command with id"),
         }
@@ -82,10 +448,15 @@ mod tests {
         let cli = Cli::parse_from(["cazdo", "wi", "--long"]);
 
         match cli.command {
-            Some(Commands::Wi { id, long, json }) => {
+            Some(Commands::Wi {
+                action: None,
+                id,
+                long,
+                ..
+            }) => {
                 assert_eq!(id, None);
                 assert!(long);
-                assert!(!json);
+                assert!(!cli.json);
             }
             _ => panic!("expected wi command with long flag"),
         }
@@ -96,10 +467,15 @@ mod tests {
         let cli = Cli::parse_from(["cazdo", "wi", "120", "--long"]);
 
         match cli.command {
-            Some(Commands::Wi { id, long, json }) => {
-                assert_eq!(id, Some(120));
+            Some(Commands::Wi {
+                action: None,
+                id,
+                long,
+                ..
+            }) => {
+                assert_eq!(id, Some(WorkItemIds::Id(120)));
                 assert!(long);
-                assert!(!json);
+                assert!(!cli.json);
             }
             _ => panic!("expected wi command with id and long flag"),
         }
@@ -110,10 +486,15 @@ mod tests {
         let cli = Cli::parse_from(["cazdo", "wi", "--long", "120"]);
 
         match cli.command {
-            Some(Commands::Wi { id, long, json }) => {
-                assert_eq!(id, Some(120));
+            Some(Commands::Wi {
+                action: None,
+                id,
+                long,
+                ..
+            }) => {
+                assert_eq!(id, Some(WorkItemIds::Id(120)));
                 assert!(long);
-                assert!(!json);
+                assert!(!cli.json);
             }
             _ => panic!("expected wi command with long flag before id"),
         }
@@ -124,10 +505,15 @@ mod tests {
         let cli = Cli::parse_from(["cazdo", "wi", "--json"]);
 
         match cli.command {
-            Some(Commands::Wi { id, long, json }) => {
+            Some(Commands::Wi {
+                action: None,
+                id,
+                long,
+                ..
+            }) => {
                 assert_eq!(id, None);
                 assert!(!long);
-                assert!(json);
+                assert!(cli.json);
             }
             _ => panic!("expected wi command with json flag"),
         }
@@ -138,15 +524,49 @@ mod tests {
         let cli = Cli::parse_from(["cazdo", "wi", "120", "--json"]);
 
         match cli.command {
-            Some(Commands::Wi { id, long, json }) => {
-                assert_eq!(id, Some(120));
+            Some(Commands::Wi {
+                action: None,
+                id,
+                long,
+                ..
+            }) => {
+                assert_eq!(id, Some(WorkItemIds::Id(120)));
                 assert!(!long);
-                assert!(json);
+                assert!(cli.json);
             }
             _ => panic!("expected wi command with id and json flag"),
         }
     }
 
+    #[test]
+    fn parses_wi_dash_as_stdin_ids() {
+        let cli = Cli::parse_from(["cazdo", "wi", "-", "--format", "plain"]);
+
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Wi {
+                action: None,
+                id: Some(WorkItemIds::Stdin),
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["cazdo", "wi", "abc"]).is_err());
+    }
+
+    #[test]
+    fn parses_wi_format() {
+        let cli = Cli::parse_from(["cazdo", "wi", "120", "--format", "markdown"]);
+
+        match cli.command {
+            Some(Commands::Wi {
+                action: None,
+                format,
+                ..
+            }) => assert_eq!(format, Some(WorkItemFormat::Markdown)),
+            _ => panic!("expected wi command with format"),
+        }
+    }
+
     #[test]
     fn rejects_wi_with_long_and_json_flags() {
         let error = match Cli::try_parse_from(["cazdo", "wi", "120", "--long", "--json"]) {
@@ -157,10 +577,430 @@ mod tests {
         assert_eq!(error.kind(), clap::error::ErrorKind::ArgumentConflict);
     }
 
+    #[test]
+    fn parses_wi_open_without_id() {
+        let cli = Cli::parse_from(["cazdo", "wi", "open"]);
+
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Wi {
+                action: Some(WiAction::Open { id: None }),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_wi_open_with_id() {
+        let cli = Cli::parse_from(["cazdo", "wi", "open", "120"]);
+
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Wi {
+                action: Some(WiAction::Open { id: Some(120) }),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_wi_comment_with_and_without_text() {
+        let cli = Cli::parse_from(["cazdo", "wi", "comment", "120", "Deployed to staging"]);
+        match cli.command {
+            Some(Commands::Wi {
+                action: Some(WiAction::Comment { id, text }),
+                ..
+            }) => {
+                assert_eq!(id, 120);
+                assert_eq!(text.as_deref(), Some("Deployed to staging"));
+            }
+            _ => panic!("expected wi comment command"),
+        }
+
+        let cli = Cli::parse_from(["cazdo", "wi", "comment", "120"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Wi {
+                action: Some(WiAction::Comment {
+                    id: 120,
+                    text: None
+                }),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_wi_state_with_an_id_or_stdin() {
+        let cli = Cli::parse_from(["cazdo", "wi", "state", "-", "Resolved"]);
+        match cli.command {
+            Some(Commands::Wi {
+                action: Some(WiAction::State { id, state }),
+                ..
+            }) => {
+                assert_eq!(id, WorkItemIds::Stdin);
+                assert_eq!(state, "Resolved");
+            }
+            _ => panic!("expected wi state command"),
+        }
+
+        assert!(matches!(
+            Cli::parse_from(["cazdo", "wi", "state", "120", "Active"]).command,
+            Some(Commands::Wi {
+                action: Some(WiAction::State {
+                    id: WorkItemIds::Id(120),
+                    ..
+                }),
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["cazdo", "wi", "state", "Resolved"]).is_err());
+    }
+
+    #[test]
+    fn rejects_wi_id_before_open() {
+        assert!(Cli::try_parse_from(["cazdo", "wi", "120", "open"]).is_err());
+    }
+
+    #[test]
+    fn parses_pr_create_with_draft_and_target() {
+        let cli = Cli::parse_from(["cazdo", "pr", "create", "--draft", "--target", "develop"]);
+
+        match cli.command {
+            Some(Commands::Pr {
+                action: PrAction::Create { draft, target },
+            }) => {
+                assert!(draft);
+                assert_eq!(target.as_deref(), Some("develop"));
+            }
+            _ => panic!("expected pr create command"),
+        }
+    }
+
+    #[test]
+    fn parses_pr_list_mine_as_json() {
+        let cli = Cli::parse_from(["cazdo", "pr", "list", "--mine", "--format", "json"]);
+
+        match cli.command {
+            Some(Commands::Pr {
+                action: PrAction::List { mine, format },
+            }) => {
+                assert!(mine);
+                assert_eq!(format, ListFormat::Json);
+            }
+            _ => panic!("expected pr list command"),
+        }
+    }
+
+    #[test]
+    fn parses_config_set() {
+        let cli = Cli::parse_from([
+            "cazdo",
+            "config",
+            "set",
+            "branches.protected",
+            "main,release/*",
+        ]);
+
+        match cli.command {
+            Some(Commands::Config {
+                action: ConfigAction::Set { key, value },
+            }) => {
+                assert_eq!(key, "branches.protected");
+                assert_eq!(value, "main,release/*");
+            }
+            _ => panic!("expected config set command"),
+        }
+    }
+
+    #[test]
+    fn parses_version_check() {
+        let cli = Cli::parse_from(["cazdo", "version", "--check"]);
+
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Version { check: true })
+        ));
+    }
+
     #[test]
     fn parses_update() {
         let cli = Cli::parse_from(["cazdo", "update"]);
 
         assert!(matches!(cli.command, Some(Commands::Update)));
+        assert!(!cli.json);
+    }
+
+    #[test]
+    fn parses_branch_list_with_default_table_format() {
+        let cli = Cli::parse_from(["cazdo", "branch", "list"]);
+
+        match cli.command {
+            Some(Commands::Branch {
+                action: BranchAction::List { format },
+            }) => assert_eq!(format, ListFormat::Table),
+            _ => panic!("expected branch list command"),
+        }
+    }
+
+    #[test]
+    fn parses_branch_list_with_tsv_format() {
+        let cli = Cli::parse_from(["cazdo", "branch", "list", "--format", "tsv"]);
+
+        match cli.command {
+            Some(Commands::Branch {
+                action: BranchAction::List { format },
+            }) => assert_eq!(format, ListFormat::Tsv),
+            _ => panic!("expected branch list command with tsv format"),
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_branch_list_format() {
+        assert!(Cli::try_parse_from(["cazdo", "branch", "list", "--format", "csv"]).is_err());
+    }
+
+    #[test]
+    fn parses_branch_status_porcelain() {
+        let cli = Cli::parse_from(["cazdo", "branch", "status", "--porcelain"]);
+
+        match cli.command {
+            Some(Commands::Branch {
+                action: BranchAction::Status { all, porcelain },
+            }) => assert!(porcelain && !all),
+            _ => panic!("expected branch status command"),
+        }
+        assert!(
+            Cli::try_parse_from(["cazdo", "branch", "status", "--porcelain", "--json"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parses_branch_prune_gone_dry_run() {
+        let cli = Cli::parse_from(["cazdo", "branch", "prune-gone", "--dry-run"]);
+
+        match cli.command {
+            Some(Commands::Branch {
+                action: BranchAction::PruneGone { dry_run },
+            }) => assert!(dry_run),
+            _ => panic!("expected branch prune-gone command"),
+        }
+    }
+
+    #[test]
+    fn parses_branch_clean_merged_yes() {
+        let cli = Cli::parse_from(["cazdo", "branch", "clean", "--merged", "-y"]);
+
+        match cli.command {
+            Some(Commands::Branch {
+                action:
+                    BranchAction::Clean {
+                        merged,
+                        dry_run,
+                        yes,
+                    },
+            }) => assert!(merged && !dry_run && yes),
+            _ => panic!("expected branch clean command"),
+        }
+    }
+
+    #[test]
+    fn parses_board_open_sprint_for_team() {
+        let cli = Cli::parse_from(["cazdo", "board", "open", "--sprint", "--team", "Web Team"]);
+
+        match cli.command {
+            Some(Commands::Board {
+                action: BoardAction::Open { team, sprint },
+            }) => {
+                assert_eq!(team.as_deref(), Some("Web Team"));
+                assert!(sprint);
+            }
+            _ => panic!("expected board open command"),
+        }
+    }
+
+    #[test]
+    fn parses_wi_time_and_requires_a_field() {
+        let cli = Cli::parse_from([
+            "cazdo",
+            "wi",
+            "time",
+            "42",
+            "--remaining",
+            "4",
+            "--completed",
+            "2.5",
+        ]);
+
+        match cli.command {
+            Some(Commands::Wi {
+                action:
+                    Some(WiAction::Time {
+                        id,
+                        remaining,
+                        completed,
+                    }),
+                ..
+            }) => {
+                assert_eq!(id, Some(42));
+                assert_eq!(remaining, Some(4.0));
+                assert_eq!(completed, Some(2.5));
+            }
+            _ => panic!("expected wi time command"),
+        }
+        assert!(Cli::try_parse_from(["cazdo", "wi", "time", "42"]).is_err());
+        assert!(Cli::try_parse_from(["cazdo", "wi", "time", "--remaining", "-1"]).is_err());
+    }
+
+    #[test]
+    fn parses_commit_template_with_optional_id() {
+        let cli = Cli::parse_from(["cazdo", "commit-template", "42"]);
+
+        assert!(matches!(
+            cli.command,
+            Some(Commands::CommitTemplate { id: Some(42) })
+        ));
+    }
+
+    #[test]
+    fn hooks_install_rejects_uninstall_with_force() {
+        assert!(
+            Cli::try_parse_from(["cazdo", "hooks", "install", "--uninstall", "--force"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parses_saved_query_with_project() {
+        let cli = Cli::parse_from([
+            "cazdo",
+            "query",
+            "--saved",
+            "Shared Queries/Team/Active Bugs",
+            "--project",
+            "Web",
+        ]);
+
+        match cli.command {
+            Some(Commands::Query { saved, format }) => {
+                assert_eq!(saved, "Shared Queries/Team/Active Bugs");
+                assert_eq!(cli.project.as_deref(), Some("Web"));
+                assert_eq!(format, ListFormat::Table);
+            }
+            _ => panic!("expected query command"),
+        }
+    }
+
+    #[test]
+    fn parses_wi_list_query() {
+        let cli = Cli::parse_from([
+            "cazdo",
+            "wi",
+            "list",
+            "--query",
+            "SELECT [System.Id] FROM WorkItems",
+            "--format",
+            "tsv",
+        ]);
+
+        match cli.command {
+            Some(Commands::Wi {
+                action: Some(WiAction::List { query, format }),
+                ..
+            }) => {
+                assert_eq!(query.as_deref(), Some("SELECT [System.Id] FROM WorkItems"));
+                assert_eq!(format, ListFormat::Tsv);
+            }
+            _ => panic!("expected wi list command"),
+        }
+    }
+
+    #[test]
+    fn parses_report_since() {
+        let cli = Cli::parse_from(["cazdo", "report", "--since", "7d"]);
+
+        match cli.command {
+            Some(Commands::Report { since }) => assert_eq!(since.as_deref(), Some("7d")),
+            _ => panic!("expected report command"),
+        }
+    }
+
+    #[test]
+    fn requires_saved_query() {
+        assert!(Cli::try_parse_from(["cazdo", "query"]).is_err());
+    }
+
+    #[test]
+    fn parses_global_org_and_project_after_subcommand() {
+        let cli = Cli::parse_from([
+            "cazdo",
+            "pr",
+            "list",
+            "--org",
+            "https://dev.azure.com/other",
+            "--project",
+            "Web",
+        ]);
+
+        assert_eq!(cli.org.as_deref(), Some("https://dev.azure.com/other"));
+        assert_eq!(cli.project.as_deref(), Some("Web"));
+    }
+
+    #[test]
+    fn parses_global_no_color_flag() {
+        let cli = Cli::parse_from(["cazdo", "wi", "120", "--no-color"]);
+
+        assert!(cli.no_color);
+    }
+
+    #[test]
+    fn parses_global_verbosity_flags() {
+        let cli = Cli::parse_from(["cazdo", "branch", "list", "-q"]);
+        assert!(cli.quiet);
+        assert!(!cli.verbose);
+
+        let cli = Cli::parse_from(["cazdo", "--verbose", "wi", "120"]);
+        assert!(cli.verbose);
+    }
+
+    #[test]
+    fn rejects_quiet_with_verbose() {
+        let error = match Cli::try_parse_from(["cazdo", "--quiet", "--verbose", "update"]) {
+            Ok(_) => panic!("quiet and verbose should conflict"),
+            Err(error) => error,
+        };
+
+        assert_eq!(error.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn parses_global_json_flag_before_subcommand() {
+        let cli = Cli::parse_from(["cazdo", "--json", "config", "verify"]);
+
+        assert!(cli.json);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Config {
+                action: ConfigAction::Verify { offline: false }
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_config_verify_offline() {
+        let cli = Cli::parse_from(["cazdo", "config", "verify", "--offline"]);
+
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Config {
+                action: ConfigAction::Verify { offline: true }
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_global_json_flag_after_subcommand() {
+        let cli = Cli::parse_from(["cazdo", "config", "show", "--json"]);
+
+        assert!(cli.json);
     }
 }
@@ -0,0 +1,246 @@
+//! User-defined command aliases from the `[alias]` config section.
+//!
+//! Like git aliases, an alias replaces the subcommand word with its expansion
+//! before clap sees the arguments, so `bl = "branch list"` makes
+//! `cazdo bl --format tsv` run `cazdo branch list --format tsv`. Built-in
+//! commands always win over aliases of the same name.
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+
+use anyhow::{Result, bail};
+use clap::CommandFactory;
+
+use super::Cli;
+use crate::i18n::{Text, tf};
+
+/// Options before the subcommand that take a value, so the word after them
+/// is not the subcommand.
+const GLOBAL_OPTIONS_WITH_VALUE: &[&str] =
+    &["--log-file", "--org", "--project", "--session-summary"];
+
+/// Expand an alias in the subcommand position of `args` (including the
+/// program name), following aliases that expand to other aliases.
+pub fn expand_aliases(
+    mut args: Vec<OsString>,
+    aliases: &BTreeMap<String, String>,
+) -> Result<Vec<OsString>> {
+    if aliases.is_empty() {
+        return Ok(args);
+    }
+
+    let Some(position) = subcommand_position(&args) else {
+        return Ok(args);
+    };
+    let builtins = builtin_commands();
+    let mut seen = Vec::new();
+
+    loop {
+        let Some(name) = args[position].to_str().map(str::to_string) else {
+            return Ok(args);
+        };
+        if builtins.contains(&name) {
+            return Ok(args);
+        }
+        let Some(expansion) = aliases.get(&name) else {
+            return Ok(args);
+        };
+        if seen.contains(&name) {
+            seen.push(name);
+            bail!("{}", tf(Text::AliasLoop, &[("chain", &seen.join(" -> "))]));
+        }
+
+        let words = split_words(expansion).map_err(|error| {
+            anyhow::anyhow!(
+                "{}",
+                tf(Text::InvalidAlias, &[("name", &name), ("error", &error)])
+            )
+        })?;
+        if words.is_empty() {
+            bail!("{}", tf(Text::EmptyAlias, &[("name", &name)]));
+        }
+
+        seen.push(name);
+        args.splice(position..=position, words.into_iter().map(OsString::from));
+    }
+}
+
+/// Index of the first argument after the program name that is not a global
+/// flag or a global option's value.
+fn subcommand_position(args: &[OsString]) -> Option<usize> {
+    let mut index = 1;
+    while let Some(arg) = args.get(index) {
+        let arg = arg.to_str()?;
+        if arg == "--" {
+            return None;
+        }
+        if !arg.starts_with('-') {
+            return Some(index);
+        }
+        index += if GLOBAL_OPTIONS_WITH_VALUE.contains(&arg) {
+            2
+        } else {
+            1
+        };
+    }
+    None
+}
+
+fn builtin_commands() -> Vec<String> {
+    let command = Cli::command();
+    let mut names = vec!["help".to_string()];
+    for subcommand in command.get_subcommands() {
+        names.push(subcommand.get_name().to_string());
+        names.extend(subcommand.get_all_aliases().map(str::to_string));
+    }
+    names
+}
+
+/// Split an alias value into words on whitespace, honoring single and double
+/// quotes.
+fn split_words(text: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+
+    for c in text.chars() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("unterminated quote".to_string());
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(words: &[&str]) -> Vec<OsString> {
+        words.iter().map(OsString::from).collect()
+    }
+
+    fn aliases(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn expands_alias_and_keeps_trailing_arguments() {
+        let expanded = expand_aliases(
+            args(&["cazdo", "--no-color", "bl", "--format", "tsv"]),
+            &aliases(&[("bl", "branch list")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            expanded,
+            args(&["cazdo", "--no-color", "branch", "list", "--format", "tsv"])
+        );
+    }
+
+    #[test]
+    fn skips_global_option_values() {
+        let expanded = expand_aliases(
+            args(&["cazdo", "--log-file", "bl", "bl"]),
+            &aliases(&[("bl", "branch list")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            expanded,
+            args(&["cazdo", "--log-file", "bl", "branch", "list"])
+        );
+    }
+
+    #[test]
+    fn skips_the_session_summary_path() {
+        let expanded = expand_aliases(
+            args(&["cazdo", "--session-summary", "bl", "bl"]),
+            &aliases(&[("bl", "branch list")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            expanded,
+            args(&["cazdo", "--session-summary", "bl", "branch", "list"])
+        );
+    }
+
+    #[test]
+    fn every_option_with_a_value_is_skipped() {
+        let command = Cli::command();
+        for arg in command.get_arguments() {
+            if let Some(long) = arg.get_long()
+                && arg.get_action().takes_values()
+            {
+                let option = format!("--{long}");
+                assert!(
+                    GLOBAL_OPTIONS_WITH_VALUE.contains(&option.as_str()),
+                    "{option} takes a value but is not in GLOBAL_OPTIONS_WITH_VALUE"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn builtin_commands_win_over_aliases() {
+        let original = args(&["cazdo", "wi", "120"]);
+
+        let expanded = expand_aliases(original.clone(), &aliases(&[("wi", "pr list")])).unwrap();
+
+        assert_eq!(expanded, original);
+    }
+
+    #[test]
+    fn follows_nested_aliases_and_rejects_loops() {
+        let nested = aliases(&[("gone", "branch prune-gone"), ("dry", "gone --dry-run")]);
+        assert_eq!(
+            expand_aliases(args(&["cazdo", "dry"]), &nested).unwrap(),
+            args(&["cazdo", "branch", "prune-gone", "--dry-run"])
+        );
+
+        let looping = aliases(&[("a", "b"), ("b", "a")]);
+        let error = expand_aliases(args(&["cazdo", "a"]), &looping).expect_err("loop");
+        assert_eq!(error.to_string(), "Alias loop: a -> b -> a");
+    }
+
+    #[test]
+    fn splits_quoted_words() {
+        assert_eq!(
+            split_words(r#"query --saved "Shared Queries/My Bugs" --format 'tsv'"#).unwrap(),
+            vec![
+                "query",
+                "--saved",
+                "Shared Queries/My Bugs",
+                "--format",
+                "tsv"
+            ]
+        );
+        assert!(split_words("query --saved \"open").is_err());
+    }
+}
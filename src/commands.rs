@@ -1,51 +1,176 @@
-use crate::azure_devops::{AzureDevOpsClient, work_item_client};
-use crate::config::{Config, PatSource};
-use crate::git::{GitRepo, RepoBranch, extract_work_item_number};
+use crate::azure_devops::{
+    AzureDevOpsClient, DetailField, PatScope, RepositoryRef, WorkHours, WorkItem, WorkItemProvider,
+    work_item_client,
+};
+use crate::cli::{WorkItemFormat, WorkItemIds};
+use crate::config::{
+    BranchConfig, Config, ConfigKey, DEFAULT_CACHE_MAX_AGE_DAYS, DEFAULT_CACHED_WORK_ITEMS,
+    DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_WIQL, PatSource, project_override,
+};
+use crate::config::{export_config, find_project_config, import_config, validate_organization_url};
+use crate::git::{GitRepo, RepoBranch, origin_remote_url_in_dir};
+use crate::http;
+use crate::i18n::{Text, t, tf};
+use crate::output::{color_enabled, is_quiet, terminal_width, verbose};
 use crate::pattern::is_protected;
-use crate::tui::render_html;
-use crate::tui::{App, BranchInfo, run_app};
+use crate::release;
+use crate::tui::{App, BranchInfo, Keymap, run_app};
+use crate::tui::{ascii_fallback, emoji_enabled, open_url, render_html, unsupported_modifiers};
 use anyhow::{Context, Result, bail};
 use axoupdater::{AxoUpdater, AxoupdateError, Version};
-use crossterm::style::Stylize;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
-
-pub async fn interactive() -> Result<()> {
+use table::render_table;
+use wi_format::{box_content_width, format_box, format_markdown, format_plain};
+
+mod branch;
+mod cache;
+mod commit_template;
+mod hooks;
+mod init;
+mod open;
+mod pr;
+mod protect;
+mod query;
+mod report;
+mod restore;
+mod table;
+mod theme;
+mod wi_format;
+
+pub use branch::{branch_clean, branch_list, branch_prune_gone, branch_status};
+pub use cache::cache_clear;
+pub use commit_template::commit_template;
+pub use hooks::hooks_install;
+pub use init::config_init;
+pub use open::{board_open, branch_open, pipeline_open};
+pub use pr::{pr_create, pr_list};
+pub use protect::{config_protect_add, config_protect_list, config_protect_remove};
+pub use query::{list_work_items, query_saved};
+pub use report::report;
+pub use restore::restore;
+pub use theme::theme_preview;
+
+/// Run the TUI, in plain mode if `plain` or `[display].plain` is set.
+pub async fn interactive(plain: bool, session_summary: Option<&Path>) -> Result<()> {
     let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
     let branches = repo.list_branches().context("Failed to list branches")?;
 
-    // Load protected patterns from config (with fallback to defaults)
-    let protected_patterns = Config::load()
-        .map(|c| c.branches.protected_patterns())
-        .unwrap_or_else(|_| {
-            crate::config::DEFAULT_PROTECTED_PATTERNS
-                .iter()
-                .map(|s| s.to_string())
-                .collect()
-        });
+    // Load protected patterns and display settings from config (defaults
+    // only when there is none; a broken one must not unprotect branches)
+    let config = Config::load_optional_for_current_dir()?;
+    let protected_patterns = protected_patterns(config.as_ref());
+    let branch_config = branch_config(config.as_ref());
+    let emoji_mode = config.as_ref().map(|c| c.display.emoji).unwrap_or_default();
+    let plain = plain || config.as_ref().is_some_and(|c| c.display.plain);
+    let update_hint = config
+        .as_ref()
+        .filter(|c| c.updates.check)
+        .and_then(|_| update_hint());
+    if let Some(config) = &config {
+        theme::use_config_theme(config)?;
+    }
+    let keymap = match &config {
+        Some(config) => Keymap::from_config(&config.keys)?,
+        None => Keymap::default(),
+    };
+    let work_item_capacity = config
+        .as_ref()
+        .map(|config| config.cache.work_items())
+        .unwrap_or(DEFAULT_CACHED_WORK_ITEMS);
+    let cache_max_age_days = config
+        .as_ref()
+        .map(|config| config.cache.max_age_days())
+        .unwrap_or(DEFAULT_CACHE_MAX_AGE_DAYS);
+    let max_concurrent_requests = config
+        .as_ref()
+        .map(|config| config.http.max_concurrent_requests())
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS);
+    let estimate_field = config
+        .as_ref()
+        .map(|config| config.display.estimate)
+        .unwrap_or_default();
+    let work_item_query = config
+        .as_ref()
+        .map(|config| config.query.wiql())
+        .unwrap_or(DEFAULT_WIQL)
+        .to_string();
+    let detail_fields = DetailField::parse_list(
+        config
+            .as_ref()
+            .and_then(|config| config.display.fields.as_deref()),
+    )?;
 
     let branch_infos: Vec<BranchInfo> = branches
         .into_iter()
-        .map(|branch| branch_info(branch, &protected_patterns))
+        .map(|branch| branch_info(branch, &branch_config, &protected_patterns))
         .collect();
 
     if branch_infos.is_empty() {
-        bail!("No branches found in repository");
+        bail!("{}", t(Text::NoBranches));
     }
 
-    let app = App::new(branch_infos, protected_patterns);
-    run_app(app, repo).await?;
+    let app = App::new(branch_infos, protected_patterns)
+        .with_emoji(emoji_enabled(emoji_mode) && !plain)
+        .with_plain(plain)
+        .with_unsupported_modifiers(unsupported_modifiers())
+        .with_update_hint(update_hint)
+        .with_keymap(keymap)
+        .with_detail_fields(detail_fields)
+        .with_estimate_field(estimate_field)
+        .with_work_item_capacity(work_item_capacity)
+        .with_cache_max_age_days(cache_max_age_days)
+        .with_max_concurrent_requests(max_concurrent_requests)
+        .with_repository(current_repository(&repo).map_err(|error| format!("{error:#}")))
+        .with_work_item_query(work_item_query)
+        .with_fetch_on_start(branch_config.fetch_on_start);
+    run_app(app, repo, session_summary).await?;
 
     Ok(())
 }
 
-fn branch_info(branch: RepoBranch, protected_patterns: &[String]) -> BranchInfo {
+/// The newer release found by the weekly check, refreshing the check in the
+/// background when it is due. The refresh only affects the next startup.
+fn update_hint() -> Option<String> {
+    if release::check_is_due() {
+        tokio::spawn(async {
+            if let Err(error) = release::refresh_cache().await {
+                tracing::debug!("update check failed: {error:#}");
+            }
+        });
+    }
+    release::cached_newer_version().map(|version| version.to_string())
+}
+
+fn protected_patterns(config: Option<&Config>) -> Vec<String> {
+    config
+        .map(|c| c.branches.protected_patterns())
+        .unwrap_or_else(|| {
+            crate::config::DEFAULT_PROTECTED_PATTERNS
+                .iter()
+                .map(|s| s.to_string())
+                .collect()
+        })
+}
+
+/// `[branches]` from `config`, or the defaults without one.
+fn branch_config(config: Option<&Config>) -> BranchConfig {
+    config
+        .map(|config| config.branches.clone())
+        .unwrap_or_default()
+}
+
+fn branch_info(
+    branch: RepoBranch,
+    branch_config: &BranchConfig,
+    protected_patterns: &[String],
+) -> BranchInfo {
     let is_current = branch.is_current;
     let is_protected_branch = is_protected(&branch.branch_name, protected_patterns);
     let wi_id = if is_protected_branch {
         None
     } else {
-        extract_work_item_number(&branch.branch_name)
+        branch_config.work_item_number(&branch.branch_name)
     };
 
     BranchInfo {
@@ -58,10 +183,11 @@ fn branch_info(branch: RepoBranch, protected_patterns: &[String]) -> BranchInfo
         is_current,
         is_protected: is_protected_branch,
         is_stale: false,
+        origin_upstream: branch.origin_upstream,
     }
 }
 
-pub fn config_show() -> Result<()> {
+pub fn config_show(json: bool) -> Result<()> {
     let config_path = Config::config_path()?;
 
     if !config_path.exists() {
@@ -71,20 +197,70 @@ pub fn config_show() -> Result<()> {
         );
     }
 
-    println!("# {}", config_path.display());
-    println!();
-
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
-    print!("{}", Config::redact_for_display(&content));
+    let redacted = Config::redact_for_display(&content);
+    let project = match find_project_config(Path::new(".")) {
+        Some(path) => {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            Some((path, content))
+        }
+        None => None,
+    };
+
+    let config = Config::load_for_current_dir()?;
+    let pat_source = config.pat_source();
 
-    let config = Config::load()?;
-    let pat_status = match config.pat_source() {
+    if json {
+        let redacted_config: toml::Value =
+            toml::from_str(&redacted).context("Failed to parse redacted config")?;
+        let project_config = match &project {
+            Some((path, content)) => Some(serde_json::json!({
+                "path": path,
+                "config": toml::from_str::<toml::Value>(content)
+                    .with_context(|| format!("Failed to parse {}", path.display()))?,
+            })),
+            None => None,
+        };
+        print_json(&serde_json::json!({
+            "path": config_path,
+            "config": redacted_config,
+            "project": project_config,
+            "pat_source": pat_source,
+        }))?;
+        return Ok(());
+    }
+
+    if is_quiet() {
+        print!("{}", redacted);
+        if let Some((_, content)) = &project {
+            print!("{}", content);
+        }
+        return Ok(());
+    }
+
+    println!("# {}", config_path.display());
+    println!();
+    print!("{}", redacted);
+    if let Some((path, content)) = &project {
+        println!();
+        println!(
+            "# {} (overrides the above in this repository)",
+            path.display()
+        );
+        println!();
+        print!("{}", content);
+    }
+
+    let pat_status = match pat_source {
         PatSource::Env => "env (CAZDO_PAT)",
         PatSource::Config => "config ([azure_devops].pat)",
+        PatSource::Keyring => "system keyring",
         PatSource::Missing => "missing",
         PatSource::InvalidEnvWhitespace => "invalid: CAZDO_PAT is whitespace-only",
         PatSource::InvalidConfigWhitespace => "invalid: [azure_devops].pat is whitespace-only",
+        PatSource::Untrusted => "none: the repository's organization is not trusted",
     };
 
     println!();
@@ -92,59 +268,189 @@ pub fn config_show() -> Result<()> {
     Ok(())
 }
 
-pub fn config_init() -> Result<()> {
-    use std::io::{self, Write};
+pub fn config_get(key: &str, json: bool) -> Result<()> {
+    let key: ConfigKey = key.parse()?;
+    let value = Config::load()?.get(key);
 
+    if json {
+        return print_json(&serde_json::json!({
+            "key": key.name(),
+            "value": value,
+        }));
+    }
+
+    println!("{}", value);
+    Ok(())
+}
+
+pub fn config_set(key: &str, value: &str) -> Result<()> {
+    let key: ConfigKey = key.parse()?;
     let config_path = Config::config_path()?;
+    let mut config = if config_path.exists() {
+        Config::load()?
+    } else {
+        Config::default()
+    };
 
-    if config_path.exists() {
-        print!(
-            "Config already exists at {}. Overwrite? [y/N] ",
-            config_path.display()
-        );
-        io::stdout().flush()?;
+    config.set(key, value)?;
+    config.save()?;
+    verbose!("wrote {}", config_path.display());
 
-        let mut response = String::new();
-        io::stdin().read_line(&mut response)?;
-        let response = response.trim().to_lowercase();
+    if is_quiet() {
+        return Ok(());
+    }
+    println!(
+        "{}",
+        tf(
+            Text::ConfigSet,
+            &[("key", key.name()), ("value", &config.get(key).to_string())]
+        )
+    );
+    Ok(())
+}
 
-        if response != "y" && response != "yes" {
-            println!("Aborted.");
-            return Ok(());
-        }
+pub fn config_export(json: bool) -> Result<()> {
+    let exported = export_config(&Config::load()?)?;
+    if json {
+        return print_json(&serde_json::json!({ "config": exported }));
     }
 
-    let config = Config::default();
-    config.save()?;
+    print!(
+        "{}",
+        toml::to_string_pretty(&exported).context("Failed to serialize config")?
+    );
+    Ok(())
+}
 
-    println!("Configuration initialized with defaults!");
-    println!();
-    println!("Config location: {}", config_path.display());
-    println!();
-    println!("Edit the config file to set:");
-    println!("  - Azure DevOps organization URL");
-    println!("  - Protected branch patterns");
-    if std::env::var("CAZDO_PAT").is_err() {
-        println!();
-        println!("Don't forget to set your PAT:");
-        println!("  export CAZDO_PAT=\"your-personal-access-token\"");
+pub fn config_import(file: &Path, json: bool) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let config_path = Config::config_path()?;
+    let current = if config_path.exists() {
+        Config::load()?
+    } else {
+        Config::default()
+    };
+
+    let import = import_config(&current, &content)
+        .with_context(|| format!("Failed to import {}", file.display()))?;
+    if !import.keys.is_empty() {
+        import.config.save()?;
+        verbose!("wrote {}", config_path.display());
     }
 
+    if json {
+        return print_json(&serde_json::json!({
+            "file": file,
+            "keys": import.keys,
+            "skipped": import.skipped,
+        }));
+    }
+    if is_quiet() {
+        return Ok(());
+    }
+    for key in &import.skipped {
+        println!("{}", tf(Text::ImportSkipped, &[("key", key)]));
+    }
+    let file = file.display().to_string();
+    if import.keys.is_empty() {
+        println!("{}", tf(Text::ImportEmpty, &[("file", &file)]));
+        return Ok(());
+    }
+    println!("{}", tf(Text::Imported, &[("file", &file)]));
+    for key in &import.keys {
+        println!("  {key}");
+    }
     Ok(())
 }
 
-pub async fn config_verify() -> Result<()> {
-    let config = Config::load()?;
+/// Check the configuration, then (unless `offline`) that Azure DevOps accepts
+/// the organization URL, PAT, project, and team.
+pub async fn config_verify(json: bool, offline: bool) -> Result<()> {
+    let progress = !json && !is_quiet();
+    let config = verify_offline(progress)?;
     let org_url = config.azure_devops.organization_url.trim();
+    let pat_source = config.pat_source();
+    let project = project_override().or(config.azure_devops.project.as_deref());
+    let team = config.azure_devops.team.as_deref();
 
-    println!("Checking Azure DevOps configuration...");
-    println!("  organization_url: {}", org_url);
+    let scopes = if offline {
+        Vec::new()
+    } else {
+        verify_online(&config, project, team, progress).await?
+    };
+    if progress && !scopes.is_empty() {
+        print_scopes(&scopes);
+    }
+    if scopes.contains(&(PatScope::WorkItemsRead, Some(false))) {
+        bail!(
+            "The PAT does not grant {}, which cazdo needs to show work items. Create a PAT with that scope at {}/_usersSettings/tokens.",
+            PatScope::WorkItemsRead.label(),
+            org_url.trim_end_matches('/')
+        );
+    }
 
-    let pat_source = config.pat_source();
-    match pat_source {
+    if json {
+        return print_json(&serde_json::json!({
+            "organization_url": org_url,
+            "pat_source": pat_source,
+            "project": project,
+            "team": project.and(team),
+            "offline": offline,
+            "scopes": (!offline).then(|| scopes
+                .iter()
+                .map(|(scope, granted)| serde_json::json!({
+                    "scope": scope,
+                    "granted": granted,
+                    "features": scope.features(),
+                }))
+                .collect::<Vec<_>>()),
+            "verified": true,
+        }));
+    }
+
+    if !is_quiet() {
+        let missing = scopes.iter().any(|(_, granted)| *granted == Some(false));
+        match project {
+            _ if offline => println!("{}", t(Text::VerifyOffline)),
+            _ if missing => println!("{}", t(Text::VerifyMissingScopes)),
+            Some(_) => println!("{}", t(Text::VerifyProject)),
+            None => println!("{}", t(Text::VerifyBasic)),
+        }
+    }
+    Ok(())
+}
+
+/// Checks that need no network: the config file exists and parses (which
+/// validates its patterns), the organization URL is well-formed, and a PAT
+/// is available.
+fn verify_offline(progress: bool) -> Result<Config> {
+    if progress {
+        println!("{}", t(Text::VerifyChecking));
+        println!("  config file: {}", Config::config_path()?.display());
+    }
+    let config = Config::load_with_overrides()?;
+    let org_url = config.azure_devops.organization_url.trim();
+
+    if progress {
+        println!("  organization_url: {}", org_url);
+    }
+    validate_organization_url(org_url).context("Invalid [azure_devops].organization_url")?;
+
+    if progress {
+        println!(
+            "  protected patterns: {}",
+            config.branches.protected_patterns().join(", ")
+        );
+        if !config.branches.rules.is_empty() {
+            println!("  branch rules: {}", config.branches.rules.len());
+        }
+    }
+
+    match config.pat_source() {
         PatSource::Missing => {
             bail!(
-                "PAT is missing. Cannot verify organization URL/auth without a PAT.\nSet CAZDO_PAT or [azure_devops].pat, then run `cazdo config verify` again."
+                "PAT is missing. Cannot verify organization URL/auth without a PAT.\nSet CAZDO_PAT or [azure_devops].pat, or store one with `cazdo config init`, then run `cazdo config verify` again."
             );
         }
         PatSource::InvalidEnvWhitespace => {
@@ -153,22 +459,137 @@ pub async fn config_verify() -> Result<()> {
         PatSource::InvalidConfigWhitespace => {
             bail!("[azure_devops].pat is whitespace-only. Set a valid token or remove the field.");
         }
-        PatSource::Env => println!("  PAT source: env (CAZDO_PAT)"),
-        PatSource::Config => println!("  PAT source: config ([azure_devops].pat)"),
+        PatSource::Untrusted => {
+            config.get_pat()?;
+        }
+        PatSource::Env if progress => println!("  PAT source: env (CAZDO_PAT)"),
+        PatSource::Config if progress => println!("  PAT source: config ([azure_devops].pat)"),
+        PatSource::Keyring if progress => println!("  PAT source: system keyring"),
+        PatSource::Env | PatSource::Config | PatSource::Keyring => {}
     }
+    Ok(config)
+}
 
-    let client = AzureDevOpsClient::new_live(&config)?;
+/// Check the URL, PAT, project, and team against Azure DevOps, then which
+/// scopes the PAT grants (`None` when a scope could not be checked).
+async fn verify_online(
+    config: &Config,
+    project: Option<&str>,
+    team: Option<&str>,
+    progress: bool,
+) -> Result<Vec<(PatScope, Option<bool>)>> {
+    let client = AzureDevOpsClient::new_live(config)?;
     client.verify_connection().await?;
 
-    println!("Verification successful: URL and PAT are working.");
+    match project {
+        Some(project) => {
+            if progress {
+                println!("  project: {}", project);
+                if let Some(team) = team {
+                    println!("  team: {}", team);
+                }
+            }
+            client.verify_project(project, team).await?;
+        }
+        None if team.is_some() && progress => {
+            println!("  team: not checked without azure_devops.project or --project")
+        }
+        None => {}
+    }
+
+    let mut scopes = Vec::new();
+    for scope in PatScope::ALL {
+        scopes.push((scope, client.has_scope(scope, project).await?));
+    }
+    Ok(scopes)
+}
+
+fn print_scopes(scopes: &[(PatScope, Option<bool>)]) {
+    let rows: Vec<[String; 3]> = scopes
+        .iter()
+        .map(|(scope, granted)| {
+            let status = match granted {
+                Some(true) => "granted",
+                Some(false) => "missing",
+                None => "not checked (needs a project)",
+            };
+            [
+                scope.label().to_string(),
+                status.to_string(),
+                scope.features().join(", "),
+            ]
+        })
+        .collect();
+    println!();
+    print!(
+        "{}",
+        render_table(["PAT SCOPE", "STATUS", "USED BY"], &rows)
+    );
+    println!();
+}
+
+pub async fn version(check: bool, json: bool) -> Result<()> {
+    let current = env!("CARGO_PKG_VERSION");
+    if !check {
+        if json {
+            return print_json(&serde_json::json!({ "version": current }));
+        }
+        if is_quiet() {
+            println!("{current}");
+        } else {
+            println!("cazdo {current}");
+        }
+        return Ok(());
+    }
+
+    let latest = release::latest_release().await?;
+    let update_available = latest.is_newer();
+
+    if json {
+        return print_json(&serde_json::json!({
+            "version": current,
+            "latest_version": latest.version.to_string(),
+            "update_available": update_available,
+            "url": latest.url,
+        }));
+    }
+
+    if is_quiet() {
+        if update_available {
+            println!("{}", latest.version);
+        }
+        return Ok(());
+    }
+
+    if !update_available {
+        println!("{}", tf(Text::LatestVersion, &[("version", current)]));
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        tf(
+            Text::UpdateAvailable,
+            &[
+                ("latest", &latest.version.to_string()),
+                ("current", current)
+            ]
+        )
+    );
+    println!("{}", t(Text::UpdateHowTo));
+    if let Some(url) = latest.url {
+        println!("{}", tf(Text::ReleaseNotes, &[("url", &url)]));
+    }
+
     Ok(())
 }
 
-pub async fn update() -> Result<()> {
+pub async fn update(json: bool) -> Result<()> {
     let current_exe = std::env::current_exe().context("Failed to locate the cazdo executable")?;
     let mut updater = AxoUpdater::new_for("cazdo");
+    let config = Config::load().ok();
     updater.set_client(
-        reqwest::Client::builder()
+        http::client_builder(config.as_ref().map(|config| &config.http))?
             .timeout(Duration::from_secs(30))
             .build()
             .context("Failed to configure the update client")?,
@@ -195,21 +616,11 @@ pub async fn update() -> Result<()> {
     // The executable version is authoritative if a binary was replaced without its receipt.
     updater.set_current_version(Version::parse(env!("CARGO_PKG_VERSION"))?)?;
 
-    println!("Checking for updates...");
-    match updater.run().await? {
-        Some(result) => println!(
-            "Updated cazdo from {} to {}.",
-            result
-                .old_version
-                .map(|version| version.to_string())
-                .unwrap_or_else(|| "an unknown version".to_string()),
-            result.new_version
-        ),
-        None => println!(
-            "cazdo {} is already the latest version.",
-            env!("CARGO_PKG_VERSION")
-        ),
+    if !json && !is_quiet() {
+        println!("{}", t(Text::CheckingForUpdates));
     }
+    verbose!("install receipt prefix: {}", updater.install_prefix_root()?);
+    let result = updater.run().await?;
 
     if let Err(error) = remove_legacy_updater(&current_exe) {
         eprintln!(
@@ -218,6 +629,59 @@ pub async fn update() -> Result<()> {
         );
     }
 
+    if json {
+        return print_json(&match result {
+            Some(result) => serde_json::json!({
+                "updated": true,
+                "old_version": result.old_version.map(|version| version.to_string()),
+                "new_version": result.new_version.to_string(),
+            }),
+            None => serde_json::json!({
+                "updated": false,
+                "version": env!("CARGO_PKG_VERSION"),
+            }),
+        });
+    }
+
+    if is_quiet() {
+        match result {
+            Some(result) => println!("{}", result.new_version),
+            None => println!("{}", env!("CARGO_PKG_VERSION")),
+        }
+        return Ok(());
+    }
+
+    match result {
+        Some(result) => println!(
+            "{}",
+            tf(
+                Text::UpdatedFrom,
+                &[
+                    (
+                        "old",
+                        &result
+                            .old_version
+                            .map(|version| version.to_string())
+                            .unwrap_or_else(|| t(Text::UnknownVersion).to_string()),
+                    ),
+                    ("new", &result.new_version.to_string()),
+                ]
+            )
+        ),
+        None => println!(
+            "{}",
+            tf(
+                Text::AlreadyLatest,
+                &[("version", env!("CARGO_PKG_VERSION"))]
+            )
+        ),
+    }
+
+    Ok(())
+}
+
+fn print_json(value: &serde_json::Value) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
     Ok(())
 }
 
@@ -246,20 +710,23 @@ fn remove_legacy_updater(current_exe: &Path) -> std::io::Result<()> {
 const WI_PREVIEW_CHAR_LIMIT: usize = 320;
 const WI_LONG_PREVIEW_CHAR_LIMIT: usize = 600;
 
-pub enum WorkItemOutput {
-    Preview,
-    Long,
-    Json,
-}
-
-fn current_branch_work_item_id(branch_name: Option<&str>) -> Result<u32> {
+fn current_branch_work_item_id(
+    branch_name: Option<&str>,
+    branch_config: &BranchConfig,
+) -> Result<u32> {
     let Some(branch_name) = branch_name else {
         bail!(
             "No local branch is currently checked out. Pass a work item id explicitly or check out a branch."
         );
     };
+    if !branch_config.links_work_item(branch_name) {
+        bail!(
+            "Branch '{}' is set to have no work item by [[branches.rules]]. Pass a work item id explicitly.",
+            branch_name
+        );
+    }
 
-    match extract_work_item_number(branch_name) {
+    match branch_config.work_item_number(branch_name) {
         Some(id) => Ok(id),
         None => {
             bail!(
@@ -270,63 +737,340 @@ fn current_branch_work_item_id(branch_name: Option<&str>) -> Result<u32> {
     }
 }
 
-pub async fn show_work_item(id: Option<u32>, output: WorkItemOutput) -> Result<()> {
-    let wi_id = resolve_work_item_id(id)?;
+pub async fn show_work_item(
+    target: Option<WorkItemIds>,
+    format: WorkItemFormat,
+    long: bool,
+) -> Result<()> {
+    let (ids, batch) = match target {
+        Some(WorkItemIds::Id(id)) => (vec![id], false),
+        Some(WorkItemIds::Stdin) => (
+            parse_work_item_ids(&read_stdin(Text::StdinWorkItemIds)?)?,
+            true,
+        ),
+        None => (vec![resolve_work_item_id(None)?], false),
+    };
 
     let client = work_item_client()?;
 
-    if matches!(output, WorkItemOutput::Json) {
-        let json = client.get_work_item_json(wi_id).await?;
-        println!("{}", serde_json::to_string_pretty(&json)?);
-        return Ok(());
+    if format == WorkItemFormat::Json {
+        if !batch {
+            return print_json(&client.get_work_item_json(ids[0]).await?);
+        }
+        let (items, failures) = split_failures(&ids, client.get_work_items_json(&ids).await);
+        print_json(&serde_json::Value::Array(items))?;
+        return report_failures(failures, ids.len());
     }
 
-    let wi = client.get_work_item(wi_id).await?;
+    let emoji_mode = Config::load().map(|c| c.display.emoji).unwrap_or_default();
+    let ascii = |text: String| {
+        if emoji_enabled(emoji_mode) {
+            text
+        } else {
+            ascii_fallback(&text).into_owned()
+        }
+    };
 
-    let wi_label = format!("#{}", wi.id);
-    let linked_wi = wi
-        .url
-        .as_deref()
-        .map(|url| terminal_link(&wi_label, url))
-        .unwrap_or(wi_label);
+    let results = client.get_work_items_batch(&ids).await?;
+    let (work_items, failures) = if batch {
+        split_failures(&ids, results)
+    } else {
+        (results.into_iter().collect::<Result<Vec<_>>>()?, 0)
+    };
 
-    let state = wi.state.display_name();
+    for (index, wi) in work_items.iter().enumerate() {
+        let separator = match format {
+            WorkItemFormat::Markdown => "\n---\n\n",
+            WorkItemFormat::Plain => "\n",
+            _ => "",
+        };
+        if index > 0 {
+            print!("{separator}");
+        }
 
-    println!(
-        "{}  {} - {}",
-        linked_wi,
-        wi.work_item_type.display_name(),
-        state
-    );
-    println!("{} {}", "Title:".bold(), wi.title);
+        if format == WorkItemFormat::Markdown {
+            print!("{}", ascii(format_markdown(wi)));
+            continue;
+        }
 
-    if let Some(assigned_to) = wi.assigned_to.as_deref() {
-        println!("{} {}", "Assigned:".bold(), assigned_to);
+        let description = wi
+            .rich_text_fields
+            .iter()
+            .find(|field| field.name == "Description")
+            .map(|field| compact_text_preview(&field.value, wi_preview_char_limit(long)))
+            .unwrap_or_else(|| "(none)".to_string());
+        let description = ascii(description);
+
+        match format {
+            WorkItemFormat::Plain => print!("{}", format_plain(wi, &description)),
+            _ => print!(
+                "{}",
+                format_box(
+                    wi,
+                    &description,
+                    color_enabled(),
+                    box_content_width(terminal_width())
+                )
+            ),
+        }
     }
 
-    let description_html = wi
-        .rich_text_fields
-        .iter()
-        .find(|field| field.name == "Description")
-        .map(|field| field.value.as_str());
+    report_failures(failures, ids.len())
+}
+
+/// Separate successful fetches from failures, reporting each failure on stderr.
+fn split_failures<T>(ids: &[u32], results: Vec<Result<T>>) -> (Vec<T>, usize) {
+    let mut items = Vec::new();
+    let mut failures = 0;
+    for (id, result) in ids.iter().zip(results) {
+        match result {
+            Ok(item) => items.push(item),
+            Err(error) => {
+                eprintln!("#{}: {:#}", id, error);
+                failures += 1;
+            }
+        }
+    }
+    (items, failures)
+}
 
-    let long = matches!(output, WorkItemOutput::Long);
-    let description = description_html
-        .map(|html| compact_text_preview(html, wi_preview_char_limit(long)))
-        .unwrap_or_else(|| "(none)".to_string());
+fn report_failures(failures: usize, total: usize) -> Result<()> {
+    if failures > 0 {
+        bail!(
+            "{}",
+            tf(
+                Text::LoadFailures,
+                &[
+                    ("failures", &failures.to_string()),
+                    ("count", &total.to_string())
+                ]
+            )
+        );
+    }
+    Ok(())
+}
 
-    println!("{} {}", "Description:".bold(), description);
+/// Parse work item IDs separated by newlines, whitespace, or commas; a leading
+/// `#` is allowed.
+fn parse_work_item_ids(text: &str) -> Result<Vec<u32>> {
+    let mut ids = Vec::new();
+    for (line_number, line) in text.lines().enumerate() {
+        for token in line.split(|c: char| c.is_whitespace() || c == ',') {
+            if token.is_empty() {
+                continue;
+            }
+            let id = token.trim_start_matches('#').parse().with_context(|| {
+                format!(
+                    "Invalid work item ID '{}' on line {}",
+                    token,
+                    line_number + 1
+                )
+            })?;
+            ids.push(id);
+        }
+    }
 
+    if ids.is_empty() {
+        bail!("{}", t(Text::NoStdinIds));
+    }
+    Ok(ids)
+}
+
+pub async fn open_work_item(id: Option<u32>) -> Result<()> {
+    let wi_id = resolve_work_item_id(id)?;
+    let wi = work_item_client()?.get_work_item(wi_id).await?;
+
+    let Some(url) = wi.url else {
+        bail!("{}", tf(Text::NoWebUrl, &[("id", &wi.id.to_string())]));
+    };
+
+    open_url(&url).context("Could not open work item in browser")?;
+    if is_quiet() {
+        println!("{}", url);
+    } else {
+        println!(
+            "{}",
+            tf(Text::Opened, &[("id", &wi.id.to_string()), ("url", &url)])
+        );
+    }
     Ok(())
 }
 
+pub async fn comment_work_item(id: u32, text: Option<String>, json: bool) -> Result<()> {
+    let text = match text {
+        Some(text) => text,
+        None => read_stdin(Text::StdinComment)?,
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        bail!("{}", t(Text::EmptyComment));
+    }
+
+    let comment_id = work_item_client()?.add_comment(id, text).await?;
+
+    if json {
+        return print_json(&serde_json::json!({
+            "work_item_id": id,
+            "comment_id": comment_id,
+        }));
+    }
+
+    if !is_quiet() {
+        println!("{}", tf(Text::CommentAdded, &[("id", &id.to_string())]));
+    }
+    Ok(())
+}
+
+/// Move work item `target`, or each ID on stdin, to `state`. With IDs from
+/// stdin, each failure is reported on stderr and the others still move.
+pub async fn set_work_item_state(target: WorkItemIds, state: &str, json: bool) -> Result<()> {
+    let (ids, batch) = match target {
+        WorkItemIds::Id(id) => (vec![id], false),
+        WorkItemIds::Stdin => (
+            parse_work_item_ids(&read_stdin(Text::StdinWorkItemIds)?)?,
+            true,
+        ),
+    };
+
+    let results = move_work_items(&work_item_client()?, &ids, state).await;
+    let (work_items, failures) = if batch {
+        split_failures(&ids, results)
+    } else {
+        (results.into_iter().collect::<Result<Vec<_>>>()?, 0)
+    };
+
+    if json {
+        print_json(&serde_json::Value::Array(
+            work_items
+                .iter()
+                .map(|wi| {
+                    serde_json::json!({
+                        "work_item_id": wi.id,
+                        "state": wi.state.display_name(),
+                    })
+                })
+                .collect(),
+        ))?;
+    } else if !is_quiet() {
+        for wi in &work_items {
+            println!(
+                "{}",
+                tf(
+                    Text::StateChanged,
+                    &[
+                        ("id", &wi.id.to_string()),
+                        ("state", wi.state.display_name())
+                    ]
+                )
+            );
+        }
+    }
+
+    if failures > 0 {
+        bail!(
+            "{}",
+            tf(
+                Text::StateChangeFailures,
+                &[
+                    ("failures", &failures.to_string()),
+                    ("count", &ids.len().to_string())
+                ]
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Move each of `ids` to `state` in turn, returning the results in `ids`
+/// order.
+async fn move_work_items(
+    provider: &impl WorkItemProvider,
+    ids: &[u32],
+    state: &str,
+) -> Vec<Result<WorkItem>> {
+    let mut results = Vec::with_capacity(ids.len());
+    for &id in ids {
+        results.push(provider.set_work_item_state(id, state).await);
+    }
+    results
+}
+
+pub async fn work_item_time(
+    id: Option<u32>,
+    remaining: Option<f64>,
+    completed: Option<f64>,
+    json: bool,
+) -> Result<()> {
+    let id = resolve_work_item_id(id)?;
+    let hours = work_item_client()?
+        .update_work_hours(
+            id,
+            WorkHours {
+                remaining,
+                completed,
+            },
+        )
+        .await?;
+
+    if json {
+        return print_json(&serde_json::json!({
+            "work_item_id": id,
+            "remaining_work": hours.remaining,
+            "completed_work": hours.completed,
+        }));
+    }
+
+    if !is_quiet() {
+        let show = |hours: Option<f64>| match hours {
+            Some(hours) => format!("{hours}h"),
+            None => t(Text::Unset).to_string(),
+        };
+        println!(
+            "{}",
+            tf(
+                Text::HoursUpdated,
+                &[
+                    ("id", &id.to_string()),
+                    ("remaining", &show(hours.remaining)),
+                    ("completed", &show(hours.completed)),
+                ]
+            )
+        );
+    }
+    Ok(())
+}
+
+fn read_stdin(what: Text) -> Result<String> {
+    use std::io::{IsTerminal, Read};
+
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        bail!("{}", tf(Text::StdinIsTerminal, &[("what", t(what))]));
+    }
+
+    let mut text = String::new();
+    stdin
+        .read_to_string(&mut text)
+        .with_context(|| tf(Text::ReadStdinFailed, &[("what", t(what))]))?;
+    Ok(text)
+}
+
 fn resolve_work_item_id(id: Option<u32>) -> Result<u32> {
     match id {
         Some(id) => Ok(id),
         None => {
             let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+            let branch_name = repo.current_local_branch_name()?;
+            verbose!(
+                "git: {} on branch {}",
+                repo.repo_dir()?.display(),
+                branch_name.as_deref().unwrap_or("(detached HEAD)")
+            );
 
-            current_branch_work_item_id(repo.current_local_branch_name()?.as_deref())
+            current_branch_work_item_id(
+                branch_name.as_deref(),
+                &branch_config(Config::load_for_current_dir().ok().as_ref()),
+            )
         }
     }
 }
@@ -365,6 +1109,43 @@ fn compact_text_preview(html: &str, max_chars: usize) -> String {
     truncated
 }
 
+/// The Azure Repos repository behind the `origin` remote, in the `--project`
+/// project when given.
+/// `--project`, else `[azure_devops].project`, else the project behind the
+/// `origin` remote.
+fn current_project() -> Result<String> {
+    if let Some(project) = project_override() {
+        return Ok(project.to_string());
+    }
+    if let Some(project) = Config::load_for_current_dir()
+        .ok()
+        .and_then(|c| c.azure_devops.project)
+    {
+        verbose!("config: project '{}'", project);
+        return Ok(project);
+    }
+    let repo = GitRepo::open_current_dir()
+        .context("Failed to open git repository; pass --project or set azure_devops.project")?;
+    Ok(current_repository(&repo)
+        .context("Failed to determine the project; pass --project or set azure_devops.project")?
+        .project)
+}
+
+fn current_repository(repo: &GitRepo) -> Result<RepositoryRef> {
+    let remote_url = origin_remote_url_in_dir(&repo.repo_dir()?)?;
+    let mut repository = RepositoryRef::from_remote_url(&remote_url)?;
+    if let Some(project) = project_override() {
+        repository.project = project.to_string();
+    }
+    verbose!(
+        "git: origin {} -> project '{}', repository '{}'",
+        remote_url,
+        repository.project,
+        repository.repository
+    );
+    Ok(repository)
+}
+
 fn terminal_link(label: &str, url: &str) -> String {
     format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, label)
 }
@@ -374,9 +1155,38 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[tokio::test]
+    async fn moves_each_work_item_and_keeps_going_past_failures() {
+        let provider = crate::azure_devops::MockWorkItemProvider::new()
+            .with_work_item(1, "First")
+            .with_error(2, "Work Item #2 not found")
+            .with_work_item(3, "Third");
+
+        let results = move_work_items(&provider, &[1, 2, 3], "Resolved").await;
+
+        let states: Vec<Option<String>> = results
+            .iter()
+            .map(|result| {
+                result
+                    .as_ref()
+                    .ok()
+                    .map(|wi| wi.state.display_name().to_string())
+            })
+            .collect();
+        assert_eq!(
+            states,
+            [
+                Some("Resolved".to_string()),
+                None,
+                Some("Resolved".to_string())
+            ]
+        );
+    }
+
     #[test]
     fn current_branch_work_item_id_requires_local_branch() {
-        let error = current_branch_work_item_id(None).expect_err("detached head should error");
+        let error = current_branch_work_item_id(None, &BranchConfig::default())
+            .expect_err("detached head should error");
 
         assert_eq!(
             error.to_string(),
@@ -387,7 +1197,7 @@ mod tests {
     #[test]
     fn current_branch_work_item_id_extracts_work_item_from_branch_name() {
         assert_eq!(
-            current_branch_work_item_id(Some("feature/12345-login"))
+            current_branch_work_item_id(Some("feature/12345-login"), &BranchConfig::default())
                 .expect("branch name should yield work item id"),
             12345
         );
@@ -395,7 +1205,7 @@ mod tests {
 
     #[test]
     fn current_branch_work_item_id_reports_missing_work_item_number() {
-        let error = current_branch_work_item_id(Some("main"))
+        let error = current_branch_work_item_id(Some("main"), &BranchConfig::default())
             .expect_err("branch without work item should error");
 
         assert_eq!(
@@ -404,6 +1214,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn current_branch_work_item_id_honors_branch_rules() {
+        let mut branch_config = BranchConfig::default();
+        branch_config.rules.push(crate::config::BranchRule {
+            pattern: "spike/*".to_string(),
+            work_item: Some(false),
+            pr_target: None,
+        });
+
+        let error = current_branch_work_item_id(Some("spike/123-grpc"), &branch_config)
+            .expect_err("rule disables work item");
+
+        assert_eq!(
+            error.to_string(),
+            "Branch 'spike/123-grpc' is set to have no work item by [[branches.rules]]. Pass a work item id explicitly."
+        );
+    }
+
+    #[test]
+    fn parse_work_item_ids_accepts_lines_commas_and_hashes() {
+        assert_eq!(
+            parse_work_item_ids("101\n#102, 103\n\n  104\n").unwrap(),
+            vec![101, 102, 103, 104]
+        );
+    }
+
+    #[test]
+    fn parse_work_item_ids_reports_invalid_token_with_line() {
+        let error = parse_work_item_ids("101\nabc\n").expect_err("abc is not an id");
+
+        assert_eq!(error.to_string(), "Invalid work item ID 'abc' on line 2");
+    }
+
+    #[test]
+    fn parse_work_item_ids_requires_at_least_one_id() {
+        assert!(parse_work_item_ids("\n \n").is_err());
+    }
+
     #[test]
     fn compact_text_preview_keeps_short_text() {
         let preview = compact_text_preview("<p>Hello <b>world</b></p>", 50);
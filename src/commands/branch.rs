@@ -0,0 +1,768 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{Context, Result, bail};
+
+use super::table::{render_table, render_tsv, truncate};
+use super::{branch_config, branch_info, print_json, protected_patterns};
+use crate::azure_devops::{WorkItemProvider, work_item_client};
+use crate::cli::ListFormat;
+use crate::config::Config;
+use crate::git::{BranchScope, DeleteResult, GitRepo, RemoteStatus};
+use crate::i18n::{Text, t, tf};
+use crate::output::{is_quiet, verbose};
+use crate::pattern::is_protected;
+use crate::timestamp::format_timestamp;
+
+const TITLE_COLUMN_LIMIT: usize = 50;
+
+/// A local branch with its work item, as listed by `cazdo branch list`.
+pub(super) struct BranchRow {
+    pub(super) branch: String,
+    pub(super) current: bool,
+    pub(super) protected: bool,
+    pub(super) work_item_id: Option<u32>,
+    pub(super) title: Option<String>,
+    pub(super) state: Option<String>,
+    pub(super) assigned_to: Option<String>,
+    pub(super) url: Option<String>,
+    pub(super) remote_status: RemoteStatus,
+    pub(super) last_commit_author: Option<String>,
+    pub(super) last_commit_time: Option<i64>,
+    pub(super) error: Option<String>,
+}
+
+pub async fn branch_list(format: ListFormat) -> Result<()> {
+    let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+    let rows = local_branch_rows(&repo, false).await?;
+
+    match format {
+        ListFormat::Table => print!("{}", format_table(&rows)),
+        ListFormat::Tsv => print!("{}", format_tsv(&rows)),
+        ListFormat::Json => print_json(&rows_json(&rows))?,
+    }
+
+    Ok(())
+}
+
+/// Local branches (or only the current one) with their remote status, last
+/// commit, and work item.
+pub(super) async fn local_branch_rows(
+    repo: &GitRepo,
+    current_only: bool,
+) -> Result<Vec<BranchRow>> {
+    let branches = repo.list_branches().context("Failed to list branches")?;
+    let config = Config::load_for_current_dir().ok();
+    let protected_patterns = protected_patterns(config.as_ref());
+    let branch_config = branch_config(config.as_ref());
+
+    let mut rows = Vec::new();
+    for branch in branches
+        .into_iter()
+        .filter(|branch| branch.scope == BranchScope::Local)
+        .filter(|branch| branch.is_current || !current_only)
+    {
+        let info = branch_info(branch, &branch_config, &protected_patterns);
+        let status = repo
+            .get_branch_status(info.scope, &info.branch_name, None)
+            .with_context(|| format!("Failed to read status of '{}'", info.branch_name))?;
+
+        rows.push(BranchRow {
+            branch: info.branch_name,
+            current: info.is_current,
+            protected: info.is_protected,
+            work_item_id: info.work_item_id,
+            title: None,
+            state: None,
+            assigned_to: None,
+            url: None,
+            remote_status: status.remote_status,
+            last_commit_author: status.last_commit_author,
+            last_commit_time: status.last_commit_time,
+            error: None,
+        });
+    }
+
+    verbose!(
+        "git: {} local branches in {}",
+        rows.len(),
+        repo.repo_dir()?.display()
+    );
+
+    if rows.iter().any(|row| row.work_item_id.is_some()) {
+        attach_work_items(&mut rows, &work_item_client()?).await;
+    }
+
+    Ok(rows)
+}
+
+/// Fill in the work item columns of `rows`, or the error for a work item that
+/// could not be fetched. The work items are fetched together, each once.
+async fn attach_work_items(rows: &mut [BranchRow], provider: &impl WorkItemProvider) {
+    let mut ids: Vec<u32> = rows.iter().filter_map(|row| row.work_item_id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let results = match provider.get_work_items_batch(&ids).await {
+        Ok(results) => results,
+        Err(error) => {
+            let message = format!("{error:#}");
+            ids.iter()
+                .map(|_| Err(anyhow::anyhow!(message.clone())))
+                .collect()
+        }
+    };
+    let work_items: HashMap<u32, _> = ids.iter().copied().zip(results).collect();
+
+    for row in rows {
+        match row.work_item_id.and_then(|id| work_items.get(&id)) {
+            Some(Ok(wi)) => {
+                row.title = Some(wi.title.clone());
+                row.state = Some(wi.state.display_name().to_string());
+                row.assigned_to = wi.assigned_to.clone();
+                row.url = wi.url.clone();
+            }
+            Some(Err(error)) => row.error = Some(format!("{error:#}")),
+            None => {}
+        }
+    }
+}
+
+/// Print the status of the current branch, or of every local branch with
+/// `all`.
+///
+/// `--porcelain` prints one tab-separated record per branch, with no header:
+/// branch, work item ID, work item state, remote status, ahead, behind, and
+/// last commit time (Unix seconds). Empty fields stay empty. This format is
+/// stable across versions; new fields are only ever appended.
+pub async fn branch_status(all: bool, porcelain: bool, json: bool) -> Result<()> {
+    let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+    let rows = local_branch_rows(&repo, !all).await?;
+
+    if json {
+        return print_json(&rows_json(&rows));
+    }
+    if porcelain {
+        print!("{}", format_porcelain(&rows));
+        return Ok(());
+    }
+    if rows.is_empty() {
+        if !is_quiet() {
+            println!("{}", t(Text::NoCurrentBranch));
+        }
+        return Ok(());
+    }
+
+    print!("{}", format_table(&rows));
+    Ok(())
+}
+
+fn format_porcelain(rows: &[BranchRow]) -> String {
+    rows.iter()
+        .map(|row| {
+            let (status, ahead, behind) = remote_status_parts(&row.remote_status);
+            let fields = [
+                row.branch.clone(),
+                row.work_item_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+                row.state.clone().unwrap_or_default(),
+                status.to_string(),
+                ahead.to_string(),
+                behind.to_string(),
+                row.last_commit_time
+                    .map(|time| time.to_string())
+                    .unwrap_or_default(),
+            ];
+            format!("{}\n", fields.join("\t"))
+        })
+        .collect()
+}
+
+/// What `cazdo branch prune-gone` or `cazdo branch clean` did, or would
+/// do, with a gone branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PruneOutcome {
+    Deleted,
+    /// Why the branch qualifies, e.g. `upstream gone`
+    WouldDelete(&'static str),
+    Skipped(&'static str),
+    Failed(String),
+}
+
+impl PruneOutcome {
+    fn label(&self) -> String {
+        match self {
+            PruneOutcome::Deleted => "deleted".to_string(),
+            PruneOutcome::WouldDelete(reason) => format!("would delete: {reason}"),
+            PruneOutcome::Skipped(reason) => format!("skipped: {reason}"),
+            PruneOutcome::Failed(error) => format!("failed: {error}"),
+        }
+    }
+}
+
+/// A local branch whose upstream is gone.
+struct GoneBranch {
+    branch: String,
+    commit_sha: Option<String>,
+    outcome: PruneOutcome,
+}
+
+/// Delete local branches whose upstream branch no longer exists.
+///
+/// Protected branches and the current branch are skipped. The deleted
+/// branches are printed with their commit SHA so they can be restored with
+/// `git branch <name> <sha>`.
+pub fn branch_prune_gone(dry_run: bool, json: bool) -> Result<()> {
+    let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+    let protected_patterns = protected_patterns(Config::load_optional_for_current_dir()?.as_ref());
+
+    let mut gone = gone_branches(&repo, &protected_patterns, None)?;
+    if !dry_run {
+        delete_gone_branches(&repo, &mut gone, &protected_patterns);
+    }
+    report_gone_branches(&gone, json)
+}
+
+/// Delete local branches whose upstream branch is gone, after listing them
+/// and asking once.
+///
+/// Like [`branch_prune_gone`], protected branches and the current branch
+/// are kept; with `merged`, so are branches not merged into the default
+/// branch. `yes` skips the question, which is required when stdin is not a
+/// terminal or the output is JSON.
+pub fn branch_clean(merged: bool, dry_run: bool, yes: bool, json: bool) -> Result<()> {
+    let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+    let protected_patterns = protected_patterns(Config::load_optional_for_current_dir()?.as_ref());
+    let merged = if merged {
+        Some(
+            repo.merged_branches()
+                .context("Failed to list merged branches")?,
+        )
+    } else {
+        None
+    };
+
+    let mut gone = gone_branches(&repo, &protected_patterns, merged.as_ref())?;
+    let count = gone
+        .iter()
+        .filter(|branch| matches!(branch.outcome, PruneOutcome::WouldDelete(_)))
+        .count();
+    if dry_run || count == 0 {
+        return report_gone_branches(&gone, json);
+    }
+
+    if !yes {
+        if json || !io::stdin().is_terminal() {
+            bail!(
+                "{}",
+                tf(Text::PruneNeedsYes, &[("count", &count.to_string())])
+            );
+        }
+        print!("{}", format_prune_table(&gone));
+        println!();
+        if !confirm_delete(count)? {
+            println!("{}", t(Text::Aborted));
+            return Ok(());
+        }
+        println!();
+    }
+
+    delete_gone_branches(&repo, &mut gone, &protected_patterns);
+    report_gone_branches(&gone, json)
+}
+
+/// Ask whether to delete `count` branches; anything but yes declines.
+fn confirm_delete(count: usize) -> Result<bool> {
+    let question = if count == 1 {
+        Text::ConfirmDeleteOne
+    } else {
+        Text::ConfirmDeleteMany
+    };
+    print!("{} [y/N] ", tf(question, &[("count", &count.to_string())]));
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// The local branches whose upstream is gone, each either skipped with the
+/// reason or to be deleted.
+fn gone_branches(
+    repo: &GitRepo,
+    protected_patterns: &[String],
+    merged: Option<&HashSet<String>>,
+) -> Result<Vec<GoneBranch>> {
+    let branches = repo.list_branches().context("Failed to list branches")?;
+
+    let mut gone = Vec::new();
+    for branch in branches
+        .into_iter()
+        .filter(|branch| branch.scope == BranchScope::Local)
+    {
+        let status = repo
+            .get_branch_status(branch.scope, &branch.branch_name, None)
+            .with_context(|| format!("Failed to read status of '{}'", branch.branch_name))?;
+        if !matches!(status.remote_status, RemoteStatus::Gone) {
+            continue;
+        }
+
+        let outcome = match skip_reason(
+            &branch.branch_name,
+            branch.is_current,
+            protected_patterns,
+            merged,
+        ) {
+            Some(reason) => PruneOutcome::Skipped(reason),
+            None if merged.is_some() => PruneOutcome::WouldDelete("upstream gone, merged"),
+            None => PruneOutcome::WouldDelete("upstream gone"),
+        };
+
+        gone.push(GoneBranch {
+            branch: branch.branch_name,
+            commit_sha: status.last_commit_sha,
+            outcome,
+        });
+    }
+
+    verbose!("git: {} local branches with a gone upstream", gone.len());
+    Ok(gone)
+}
+
+/// Delete the gone branches not skipped, recording each result.
+fn delete_gone_branches(repo: &GitRepo, gone: &mut [GoneBranch], protected_patterns: &[String]) {
+    for branch in gone
+        .iter_mut()
+        .filter(|branch| matches!(branch.outcome, PruneOutcome::WouldDelete(_)))
+    {
+        branch.outcome = match repo.delete_branch(
+            BranchScope::Local,
+            &branch.branch,
+            None,
+            false,
+            protected_patterns,
+        ) {
+            Ok(result) => {
+                if let DeleteResult::Local { commit_sha, .. } = result {
+                    branch.commit_sha = Some(commit_sha);
+                }
+                PruneOutcome::Deleted
+            }
+            Err(error) => PruneOutcome::Failed(format!("{error:#}")),
+        };
+    }
+}
+
+/// Print the gone branches with their outcome, failing if any deletion did.
+fn report_gone_branches(gone: &[GoneBranch], json: bool) -> Result<()> {
+    if json {
+        print_json(&gone_json(gone))?;
+    } else if gone.is_empty() {
+        if !is_quiet() {
+            println!("{}", t(Text::NoGoneBranches));
+        }
+    } else {
+        print!("{}", format_prune_table(gone));
+        if !is_quiet()
+            && gone
+                .iter()
+                .any(|branch| branch.outcome == PruneOutcome::Deleted)
+        {
+            println!();
+            println!("{}", t(Text::RestoreDeletedHint));
+        }
+    }
+
+    let failures = gone
+        .iter()
+        .filter(|branch| matches!(branch.outcome, PruneOutcome::Failed(_)))
+        .count();
+    if failures > 0 {
+        bail!(
+            "{}",
+            tf(
+                Text::DeleteFailures,
+                &[
+                    ("failures", &failures.to_string()),
+                    ("count", &gone.len().to_string()),
+                ]
+            )
+        );
+    }
+
+    Ok(())
+}
+
+fn skip_reason(
+    branch: &str,
+    is_current: bool,
+    protected_patterns: &[String],
+    merged: Option<&HashSet<String>>,
+) -> Option<&'static str> {
+    if is_protected(branch, protected_patterns) {
+        Some("protected")
+    } else if is_current {
+        Some("current branch")
+    } else if merged.is_some_and(|merged| !merged.contains(branch)) {
+        Some("not merged")
+    } else {
+        None
+    }
+}
+
+const PRUNE_HEADERS: [&str; 3] = ["BRANCH", "COMMIT", "RESULT"];
+
+fn format_prune_table(gone: &[GoneBranch]) -> String {
+    let cells: Vec<[String; 3]> = gone
+        .iter()
+        .map(|branch| {
+            [
+                branch.branch.clone(),
+                branch.commit_sha.clone().unwrap_or_default(),
+                branch.outcome.label(),
+            ]
+        })
+        .collect();
+
+    render_table(PRUNE_HEADERS, &cells)
+}
+
+fn gone_json(gone: &[GoneBranch]) -> serde_json::Value {
+    gone.iter()
+        .map(|branch| {
+            let (result, reason) = match &branch.outcome {
+                PruneOutcome::Deleted => ("deleted", None),
+                PruneOutcome::WouldDelete(reason) => ("would_delete", Some(reason.to_string())),
+                PruneOutcome::Skipped(reason) => ("skipped", Some(reason.to_string())),
+                PruneOutcome::Failed(error) => ("failed", Some(error.clone())),
+            };
+            serde_json::json!({
+                "branch": branch.branch,
+                "commit_sha": branch.commit_sha,
+                "result": result,
+                "reason": reason,
+            })
+        })
+        .collect()
+}
+
+const HEADERS: [&str; 6] = ["BRANCH", "WI", "TITLE", "STATE", "REMOTE", "LAST COMMIT"];
+
+fn row_cells(row: &BranchRow, title_limit: Option<usize>) -> [String; 6] {
+    let title = match (&row.title, &row.error) {
+        (Some(title), _) => title.clone(),
+        (None, Some(_)) => "(failed to load)".to_string(),
+        (None, None) => String::new(),
+    };
+    let title = match title_limit {
+        Some(limit) => truncate(&title, limit),
+        None => title,
+    };
+    let branch = if row.current {
+        format!("* {}", row.branch)
+    } else {
+        row.branch.clone()
+    };
+
+    [
+        branch,
+        row.work_item_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        title,
+        row.state.clone().unwrap_or_default(),
+        remote_status_label(&row.remote_status),
+        row.last_commit_time.map(commit_age).unwrap_or_default(),
+    ]
+}
+
+fn format_table(rows: &[BranchRow]) -> String {
+    let cells: Vec<[String; 6]> = rows
+        .iter()
+        .map(|row| row_cells(row, Some(TITLE_COLUMN_LIMIT)))
+        .collect();
+
+    render_table(HEADERS, &cells)
+}
+
+fn format_tsv(rows: &[BranchRow]) -> String {
+    let cells: Vec<[String; 6]> = rows
+        .iter()
+        .map(|row| {
+            let mut cells = row_cells(row, None);
+            // The current-branch marker is a table affordance; TSV keeps raw names.
+            cells[0] = row.branch.clone();
+            cells
+        })
+        .collect();
+
+    render_tsv(HEADERS, &cells)
+}
+
+fn rows_json(rows: &[BranchRow]) -> serde_json::Value {
+    rows.iter()
+        .map(|row| {
+            serde_json::json!({
+                "branch": row.branch,
+                "current": row.current,
+                "work_item_id": row.work_item_id,
+                "title": row.title,
+                "state": row.state,
+                "remote_status": remote_status_json(&row.remote_status),
+                "last_commit_author": row.last_commit_author,
+                "last_commit_time": row.last_commit_time,
+                "error": row.error,
+            })
+        })
+        .collect()
+}
+
+fn remote_status_label(status: &RemoteStatus) -> String {
+    match status {
+        RemoteStatus::LocalOnly => "local only".to_string(),
+        RemoteStatus::RemoteTracking => "remote".to_string(),
+        RemoteStatus::UpToDate => "up to date".to_string(),
+        RemoteStatus::Ahead(n) => format!("↑{n}"),
+        RemoteStatus::Behind(n) => format!("↓{n}"),
+        RemoteStatus::Diverged { ahead, behind } => format!("↑{ahead} ↓{behind}"),
+        RemoteStatus::Gone => "remote gone".to_string(),
+    }
+}
+
+fn remote_status_json(status: &RemoteStatus) -> serde_json::Value {
+    let (name, ahead, behind) = remote_status_parts(status);
+
+    serde_json::json!({ "status": name, "ahead": ahead, "behind": behind })
+}
+
+/// Machine-readable remote status name with ahead and behind counts.
+fn remote_status_parts(status: &RemoteStatus) -> (&'static str, usize, usize) {
+    match *status {
+        RemoteStatus::LocalOnly => ("local_only", 0, 0),
+        RemoteStatus::RemoteTracking => ("remote_tracking", 0, 0),
+        RemoteStatus::UpToDate => ("up_to_date", 0, 0),
+        RemoteStatus::Ahead(n) => ("ahead", n, 0),
+        RemoteStatus::Behind(n) => ("behind", 0, n),
+        RemoteStatus::Diverged { ahead, behind } => ("diverged", ahead, behind),
+        RemoteStatus::Gone => ("gone", 0, 0),
+    }
+}
+
+fn commit_age(timestamp: i64) -> String {
+    format_timestamp(timestamp).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure_devops::MockWorkItemProvider;
+
+    fn row(branch: &str, work_item_id: Option<u32>, title: Option<&str>) -> BranchRow {
+        BranchRow {
+            branch: branch.to_string(),
+            current: false,
+            protected: false,
+            work_item_id,
+            title: title.map(str::to_string),
+            state: title.map(|_| "Active".to_string()),
+            assigned_to: None,
+            url: None,
+            remote_status: RemoteStatus::UpToDate,
+            last_commit_author: None,
+            last_commit_time: None,
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn attaches_each_work_item_once_and_keeps_errors_per_row() {
+        let provider = MockWorkItemProvider::new()
+            .with_work_item(12, "Login page")
+            .with_error(13, "TF401232: Work item 13 does not exist");
+        let mut rows = vec![
+            row("feature/12-login", Some(12), None),
+            row("bugfix/12-login", Some(12), None),
+            row("feature/13-gone", Some(13), None),
+            row("spike", None, None),
+        ];
+
+        attach_work_items(&mut rows, &provider).await;
+
+        assert_eq!(provider.requests(), [12, 13]);
+        assert_eq!(rows[0].title.as_deref(), Some("Login page"));
+        assert_eq!(rows[1].state.as_deref(), Some("Active"));
+        assert_eq!(
+            rows[2].error.as_deref(),
+            Some("TF401232: Work item 13 does not exist")
+        );
+        assert!(rows[3].title.is_none() && rows[3].error.is_none());
+    }
+
+    #[test]
+    fn table_aligns_columns_and_marks_current_branch() {
+        let mut current = row("feature/12-login", Some(12), Some("Login page"));
+        current.current = true;
+        current.remote_status = RemoteStatus::Ahead(2);
+
+        let table = format_table(&[current, row("main", None, None)]);
+
+        assert_eq!(
+            table,
+            "BRANCH              WI  TITLE       STATE   REMOTE      LAST COMMIT\n\
+             * feature/12-login  12  Login page  Active  ↑2\n\
+             main                                        up to date\n"
+        );
+    }
+
+    #[test]
+    fn table_truncates_long_titles() {
+        let title = "x".repeat(80);
+        let table = format_table(&[row("feature/1", Some(1), Some(&title))]);
+
+        assert!(table.contains(&format!("{}...", "x".repeat(TITLE_COLUMN_LIMIT - 3))));
+        assert!(!table.contains(&title));
+    }
+
+    #[test]
+    fn tsv_keeps_full_titles_and_escapes_separators() {
+        let mut failed = row("feature/2", Some(2), None);
+        failed.error = Some("not found".to_string());
+
+        let tsv = format_tsv(&[row("feature/1", Some(1), Some("a\tb")), failed]);
+
+        assert_eq!(
+            tsv,
+            "BRANCH\tWI\tTITLE\tSTATE\tREMOTE\tLAST COMMIT\n\
+             feature/1\t1\ta b\tActive\tup to date\t\n\
+             feature/2\t2\t(failed to load)\t\tup to date\t\n"
+        );
+    }
+
+    #[test]
+    fn json_reports_structured_remote_status() {
+        let mut diverged = row("feature/3", Some(3), Some("Sync"));
+        diverged.remote_status = RemoteStatus::Diverged {
+            ahead: 1,
+            behind: 4,
+        };
+        diverged.last_commit_time = Some(1_700_000_000);
+
+        let json = rows_json(&[diverged]);
+
+        assert_eq!(json[0]["branch"], "feature/3");
+        assert_eq!(json[0]["work_item_id"], 3);
+        assert_eq!(
+            json[0]["remote_status"],
+            serde_json::json!({ "status": "diverged", "ahead": 1, "behind": 4 })
+        );
+        assert_eq!(json[0]["last_commit_time"], 1_700_000_000);
+        assert!(json[0]["error"].is_null());
+    }
+
+    #[test]
+    fn porcelain_prints_stable_tab_separated_records() {
+        let mut tracked = row("feature/12-login", Some(12), Some("Login page"));
+        tracked.remote_status = RemoteStatus::Diverged {
+            ahead: 2,
+            behind: 1,
+        };
+        tracked.last_commit_time = Some(1_700_000_000);
+        let mut local = row("spike", None, None);
+        local.remote_status = RemoteStatus::LocalOnly;
+
+        assert_eq!(
+            format_porcelain(&[tracked, local]),
+            "feature/12-login\t12\tActive\tdiverged\t2\t1\t1700000000\n\
+             spike\t\t\tlocal_only\t0\t0\t\n"
+        );
+    }
+
+    fn gone(branch: &str, outcome: PruneOutcome) -> GoneBranch {
+        GoneBranch {
+            branch: branch.to_string(),
+            commit_sha: Some("0123456789abcdef".to_string()),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn skip_reason_keeps_protected_and_current_branches() {
+        let patterns = vec!["main".to_string(), "release/*".to_string()];
+
+        assert_eq!(
+            skip_reason("release/1.0", false, &patterns, None),
+            Some("protected")
+        );
+        assert_eq!(
+            skip_reason("feature/7", true, &patterns, None),
+            Some("current branch")
+        );
+        assert_eq!(skip_reason("feature/7", false, &patterns, None), None);
+    }
+
+    #[test]
+    fn skip_reason_keeps_unmerged_branches_when_cleaning_merged_ones() {
+        let patterns = vec!["main".to_string()];
+        let merged = HashSet::from(["feature/7".to_string(), "main".to_string()]);
+
+        assert_eq!(
+            skip_reason("feature/7", false, &patterns, Some(&merged)),
+            None
+        );
+        assert_eq!(
+            skip_reason("feature/8", false, &patterns, Some(&merged)),
+            Some("not merged")
+        );
+        assert_eq!(
+            skip_reason("main", false, &patterns, Some(&merged)),
+            Some("protected")
+        );
+    }
+
+    #[test]
+    fn prune_table_lists_commit_for_restore() {
+        let table = format_prune_table(&[
+            gone("feature/7", PruneOutcome::Deleted),
+            gone("main", PruneOutcome::Skipped("protected")),
+        ]);
+
+        assert_eq!(
+            table,
+            "BRANCH     COMMIT            RESULT\n\
+             feature/7  0123456789abcdef  deleted\n\
+             main       0123456789abcdef  skipped: protected\n"
+        );
+    }
+
+    #[test]
+    fn dry_run_table_says_why_each_branch_qualifies() {
+        let table = format_prune_table(&[
+            gone(
+                "feature/7",
+                PruneOutcome::WouldDelete("upstream gone, merged"),
+            ),
+            gone("feature/8", PruneOutcome::Skipped("not merged")),
+        ]);
+
+        assert_eq!(
+            table,
+            "BRANCH     COMMIT            RESULT\n\
+             feature/7  0123456789abcdef  would delete: upstream gone, merged\n\
+             feature/8  0123456789abcdef  skipped: not merged\n"
+        );
+    }
+
+    #[test]
+    fn prune_json_reports_result_and_reason() {
+        let json = gone_json(&[
+            gone("feature/7", PruneOutcome::WouldDelete("upstream gone")),
+            gone("feature/8", PruneOutcome::Failed("locked".to_string())),
+        ]);
+
+        assert_eq!(json[0]["result"], "would_delete");
+        assert_eq!(json[0]["reason"], "upstream gone");
+        assert_eq!(json[1]["commit_sha"], "0123456789abcdef");
+        assert_eq!(json[1]["reason"], "locked");
+    }
+}
@@ -0,0 +1,32 @@
+//! `cazdo cache clear`: delete the TUI's work item cache.
+
+use anyhow::Result;
+
+use super::print_json;
+use crate::i18n::{Text, t, tf};
+use crate::output::is_quiet;
+use crate::tui::clear_work_item_cache;
+
+pub fn cache_clear(json: bool) -> Result<()> {
+    let cleared = clear_work_item_cache()?;
+
+    if json {
+        return print_json(&serde_json::json!({
+            "cleared": cleared.is_some(),
+            "path": cleared.as_ref().map(|path| path.display().to_string()),
+        }));
+    }
+
+    if is_quiet() {
+        return Ok(());
+    }
+
+    match cleared {
+        Some(path) => println!(
+            "{}",
+            tf(Text::CacheCleared, &[("path", &path.display().to_string())])
+        ),
+        None => println!("{}", t(Text::CacheAlreadyEmpty)),
+    }
+    Ok(())
+}
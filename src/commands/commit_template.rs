@@ -0,0 +1,96 @@
+//! `cazdo commit-template`: a commit message skeleton for the branch's work
+//! item, for `git commit -t <(cazdo commit-template)`.
+
+use anyhow::{Context, Result};
+
+use super::{branch_config, current_branch_work_item_id, print_json};
+use crate::azure_devops::{WorkItem, work_item_client};
+use crate::config::{Config, DEFAULT_COMMIT_TEMPLATE};
+use crate::git::GitRepo;
+use crate::template;
+
+pub async fn commit_template(id: Option<u32>, json: bool) -> Result<()> {
+    let repo = GitRepo::open_current_dir().ok();
+    let branch = match &repo {
+        Some(repo) => repo.current_local_branch_name()?,
+        None => None,
+    };
+    let config = Config::load_for_current_dir().ok();
+    let id = match id {
+        Some(id) => id,
+        None => current_branch_work_item_id(branch.as_deref(), &branch_config(config.as_ref()))?,
+    };
+
+    let template = config
+        .as_ref()
+        .map(|config| config.commit.template())
+        .unwrap_or(DEFAULT_COMMIT_TEMPLATE);
+
+    let wi = work_item_client()?.get_work_item(id).await?;
+    let message = render_commit_template(template, &wi, branch.as_deref().unwrap_or(""))
+        .context("Invalid [commit].template in config")?;
+
+    if json {
+        return print_json(&serde_json::json!({ "id": wi.id, "message": message }));
+    }
+    print!("{message}");
+    if !message.ends_with('\n') {
+        println!();
+    }
+    Ok(())
+}
+
+fn render_commit_template(template: &str, wi: &WorkItem, branch: &str) -> Result<String> {
+    let id = wi.id.to_string();
+    let values = [
+        ("id", id.as_str()),
+        ("title", wi.title.as_str()),
+        ("type", wi.work_item_type.display_name()),
+        ("state", wi.state.display_name()),
+        ("branch", branch),
+    ];
+    template::render(template, &values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure_devops::{WorkItemState, WorkItemType};
+    use std::collections::BTreeMap;
+
+    fn work_item() -> WorkItem {
+        WorkItem {
+            id: 42,
+            title: "Fix login".to_string(),
+            work_item_type: WorkItemType::Bug,
+            state: WorkItemState::Active,
+            assigned_to: None,
+            url: None,
+            tags: Vec::new(),
+            rich_text_fields: Vec::new(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn default_template_uses_title_and_work_item_link() {
+        assert_eq!(
+            render_commit_template(DEFAULT_COMMIT_TEMPLATE, &work_item(), "feature/42-login")
+                .unwrap(),
+            "Fix login\n\nAB#42\n"
+        );
+    }
+
+    #[test]
+    fn custom_template_fills_every_placeholder() {
+        assert_eq!(
+            render_commit_template(
+                "[{type}/{state}] {title} ({branch}) #{id}",
+                &work_item(),
+                "feature/42-login"
+            )
+            .unwrap(),
+            "[Bug/Active] Fix login (feature/42-login) #42"
+        );
+    }
+}
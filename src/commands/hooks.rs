@@ -0,0 +1,304 @@
+//! `cazdo hooks install`: a `prepare-commit-msg` hook that links commits to
+//! the branch's work item by appending `AB#<id>` to the message.
+//!
+//! The hook is a self-contained shell script, so commits made from IDEs and
+//! GUI clients work even when cazdo is not on their `PATH`. It extracts the
+//! first number from the branch name, like the rest of cazdo, but does not
+//! read `[branches]` settings such as `work_item_min_digits`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use super::print_json;
+use crate::git::{GitRepo, hooks_dir_in_dir};
+use crate::i18n::{Text, t, tf};
+use crate::output::is_quiet;
+
+const HOOK_NAME: &str = "prepare-commit-msg";
+const BACKUP_SUFFIX: &str = ".cazdo-backup";
+/// Identifies hooks written by cazdo, which may be replaced or removed.
+const MARKER: &str = "# Installed by cazdo hooks install";
+
+const HOOK_SCRIPT: &str = r#"#!/bin/sh
+# Installed by cazdo hooks install: appends AB#<id> from the branch name so
+# Azure DevOps links the commit to the work item.
+# Remove with: cazdo hooks install --uninstall
+
+# Leave merges, squashes, and amended or reused messages alone.
+case "$2" in
+  merge|squash|commit) exit 0 ;;
+esac
+
+branch=$(git symbolic-ref --short -q HEAD) || exit 0
+id=$(printf '%s\n' "$branch" | sed -n 's/^[^0-9]*0*\([0-9][0-9]*\).*/\1/p')
+[ -n "$id" ] || exit 0
+grep -Eq "AB#$id([^0-9]|\$)" "$1" && exit 0
+
+# Add the link above git's comment lines, which also keeps it above the
+# scissors line of `git commit --verbose`.
+awk -v ref="AB#$id" '
+  !done && /^#/ { print ""; print ref; print ""; done = 1 }
+  { print }
+  END { if (!done) { print ""; print ref } }
+' "$1" > "$1.cazdo" && mv "$1.cazdo" "$1"
+"#;
+
+#[derive(Debug, PartialEq, Eq)]
+enum HookChange {
+    Installed { backup: Option<PathBuf> },
+    Uninstalled { restored: bool },
+    NotInstalled,
+}
+
+pub fn hooks_install(uninstall: bool, force: bool, json: bool) -> Result<()> {
+    let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+    let hooks_dir = hooks_dir_in_dir(&repo.repo_dir()?)?;
+    let path = hooks_dir.join(HOOK_NAME);
+
+    let change = if uninstall {
+        uninstall_hook(&path)?
+    } else {
+        install_hook(&path, force)?
+    };
+
+    if json {
+        return print_json(&serde_json::json!({
+            "hook": path.display().to_string(),
+            "installed": matches!(change, HookChange::Installed { .. }),
+            "backup": match &change {
+                HookChange::Installed { backup } => {
+                    backup.as_ref().map(|backup| backup.display().to_string())
+                }
+                _ => None,
+            },
+        }));
+    }
+
+    if is_quiet() {
+        if let HookChange::Installed { .. } = change {
+            println!("{}", path.display());
+        }
+        return Ok(());
+    }
+
+    let path = path.display().to_string();
+    match change {
+        HookChange::Installed { backup } => {
+            println!(
+                "{}",
+                tf(Text::HookInstalled, &[("hook", HOOK_NAME), ("path", &path)])
+            );
+            if let Some(backup) = backup {
+                println!(
+                    "{}",
+                    tf(
+                        Text::HookBackedUp,
+                        &[("path", &backup.display().to_string())]
+                    )
+                );
+            }
+            println!("{}", t(Text::HookEffect));
+        }
+        HookChange::Uninstalled { restored } => {
+            println!(
+                "{}",
+                tf(Text::HookRemoved, &[("hook", HOOK_NAME), ("path", &path)])
+            );
+            if restored {
+                println!("{}", t(Text::HookRestored));
+            }
+        }
+        HookChange::NotInstalled => {
+            println!("{}", tf(Text::HookNotInstalled, &[("hook", HOOK_NAME)]))
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the hook, refusing to replace a hook cazdo did not write unless
+/// `force` is set, in which case that hook is kept as a backup.
+fn install_hook(path: &Path, force: bool) -> Result<HookChange> {
+    let mut backup = None;
+    if let Some(existing) = read_hook(path)?
+        && !existing.contains(MARKER)
+    {
+        if !force {
+            bail!(
+                "{}",
+                tf(
+                    Text::HookExists,
+                    &[
+                        ("hook", HOOK_NAME),
+                        ("path", &path.display().to_string()),
+                        ("backup", &format!("{HOOK_NAME}{BACKUP_SUFFIX}")),
+                    ]
+                )
+            );
+        }
+        let backup_path = backup_path(path);
+        fs::rename(path, &backup_path)
+            .with_context(|| format!("Failed to back up {}", path.display()))?;
+        backup = Some(backup_path);
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create hooks directory: {}", parent.display()))?;
+    }
+    fs::write(path, HOOK_SCRIPT)
+        .with_context(|| format!("Failed to write hook: {}", path.display()))?;
+    make_executable(path)?;
+
+    Ok(HookChange::Installed { backup })
+}
+
+/// Remove a hook cazdo wrote, restoring the hook it replaced, if any.
+fn uninstall_hook(path: &Path) -> Result<HookChange> {
+    let Some(existing) = read_hook(path)? else {
+        return Ok(HookChange::NotInstalled);
+    };
+    if !existing.contains(MARKER) {
+        bail!(
+            "{}",
+            tf(
+                Text::HookForeign,
+                &[("hook", HOOK_NAME), ("path", &path.display().to_string())]
+            )
+        );
+    }
+
+    fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    let backup_path = backup_path(path);
+    let restored = backup_path.exists();
+    if restored {
+        fs::rename(&backup_path, path)
+            .with_context(|| format!("Failed to restore {}", backup_path.display()))?;
+    }
+
+    Ok(HookChange::Uninstalled { restored })
+}
+
+fn read_hook(path: &Path) -> Result<Option<String>> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(BACKUP_SUFFIX);
+    PathBuf::from(name)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    struct TestDir {
+        path: PathBuf,
+    }
+
+    impl TestDir {
+        fn new() -> Self {
+            let unique = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos();
+            let path = std::env::temp_dir()
+                .join(format!("cazdo-hooks-test-{}-{unique}", std::process::id()));
+            fs::create_dir_all(&path).unwrap();
+            Self { path }
+        }
+
+        fn hook(&self) -> PathBuf {
+            self.path.join("hooks").join(HOOK_NAME)
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    #[test]
+    fn installs_and_reinstalls_own_hook() {
+        let dir = TestDir::new();
+
+        assert_eq!(
+            install_hook(&dir.hook(), false).unwrap(),
+            HookChange::Installed { backup: None }
+        );
+        assert_eq!(
+            install_hook(&dir.hook(), false).unwrap(),
+            HookChange::Installed { backup: None }
+        );
+        assert!(fs::read_to_string(dir.hook()).unwrap().contains(MARKER));
+    }
+
+    #[test]
+    fn refuses_to_replace_existing_hook_without_force() {
+        let dir = TestDir::new();
+        fs::create_dir_all(dir.hook().parent().unwrap()).unwrap();
+        fs::write(dir.hook(), "#!/bin/sh\necho mine\n").unwrap();
+
+        let error = install_hook(&dir.hook(), false).expect_err("existing hook");
+
+        assert!(error.to_string().contains("already exists"));
+        assert_eq!(
+            fs::read_to_string(dir.hook()).unwrap(),
+            "#!/bin/sh\necho mine\n"
+        );
+        assert!(matches!(
+            uninstall_hook(&dir.hook()),
+            Err(error) if error.to_string().contains("not installed by cazdo")
+        ));
+    }
+
+    #[test]
+    fn force_keeps_existing_hook_and_uninstall_restores_it() {
+        let dir = TestDir::new();
+        fs::create_dir_all(dir.hook().parent().unwrap()).unwrap();
+        fs::write(dir.hook(), "#!/bin/sh\necho mine\n").unwrap();
+
+        let change = install_hook(&dir.hook(), true).unwrap();
+        assert_eq!(
+            change,
+            HookChange::Installed {
+                backup: Some(backup_path(&dir.hook()))
+            }
+        );
+
+        assert_eq!(
+            uninstall_hook(&dir.hook()).unwrap(),
+            HookChange::Uninstalled { restored: true }
+        );
+        assert_eq!(
+            fs::read_to_string(dir.hook()).unwrap(),
+            "#!/bin/sh\necho mine\n"
+        );
+        assert_eq!(
+            uninstall_hook(&TestDir::new().hook()).unwrap(),
+            HookChange::NotInstalled
+        );
+    }
+}
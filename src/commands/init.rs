@@ -0,0 +1,402 @@
+//! `cazdo config init`: a guided setup that asks for the organization URL,
+//! PAT, project, and protected branches, saves the config, and verifies it.
+//!
+//! When stdin is not a terminal the defaults are written without prompting.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{Context, Result, bail};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+
+use super::config_verify;
+use crate::azure_devops::{AzureDevOpsClient, RepositoryRef, organization_url_from_remote};
+use crate::config::{Config, DEFAULT_PROTECTED_PATTERNS, store_pat, validate_organization_url};
+use crate::git::{GitRepo, origin_remote_url_in_dir};
+use crate::i18n::{Text, t, tf};
+use crate::output::{is_quiet, verbose};
+use crate::pattern::validate_pattern;
+
+/// Branches protected by name when they exist.
+const PROTECTED_NAMES: &[&str] = &["main", "master", "develop", "development", "trunk"];
+
+/// Branch families protected with a `prefix/*` pattern when any branch uses
+/// the prefix.
+const PROTECTED_PREFIXES: &[&str] = &["release/", "releases/", "hotfix/", "support/"];
+
+pub async fn config_init() -> Result<()> {
+    let config_path = Config::config_path()?;
+
+    if config_path.exists()
+        && !confirm(
+            &tf(
+                Text::InitOverwrite,
+                &[("path", &config_path.display().to_string())],
+            ),
+            false,
+        )?
+    {
+        println!("{}", t(Text::Aborted));
+        return Ok(());
+    }
+
+    if !io::stdin().is_terminal() {
+        return write_defaults();
+    }
+
+    let remote_url = std::env::current_dir()
+        .ok()
+        .and_then(|dir| origin_remote_url_in_dir(&dir).ok());
+    let mut config = Config::default();
+
+    println!("{}", t(Text::InitIntro));
+    println!();
+
+    config.azure_devops.organization_url = prompt_organization_url(remote_url.as_deref())?;
+    let pat = prompt_pat(&mut config)?;
+
+    let remote_project = remote_url
+        .as_deref()
+        .and_then(|url| RepositoryRef::from_remote_url(url).ok())
+        .map(|repo| repo.project);
+    config.azure_devops.project = prompt_project(&config, pat.as_deref(), remote_project).await?;
+
+    config.branches.protected =
+        prompt_protected_patterns(&suggest_protected_patterns(&repository_branch_names()))?;
+
+    config.save()?;
+    println!();
+    println!(
+        "{}",
+        tf(
+            Text::InitSaved,
+            &[("path", &config_path.display().to_string())]
+        )
+    );
+
+    if pat.is_none() {
+        println!("{}", t(Text::InitSetPatLater));
+        return Ok(());
+    }
+    println!();
+    config_verify(false, false)
+        .await
+        .context(t(Text::InitVerifyFailed))
+}
+
+fn write_defaults() -> Result<()> {
+    let config_path = Config::config_path()?;
+    Config::default().save()?;
+
+    if is_quiet() {
+        return Ok(());
+    }
+    println!("{}", t(Text::InitDefaults));
+    println!();
+    println!(
+        "{}",
+        tf(
+            Text::InitConfigLocation,
+            &[("path", &config_path.display().to_string())]
+        )
+    );
+    println!();
+    println!("{}", t(Text::InitEditToSet));
+    println!("  - {}", t(Text::InitEditOrganization));
+    println!("  - {}", t(Text::InitEditPatterns));
+    if std::env::var("CAZDO_PAT").is_err() {
+        println!();
+        println!("{}", t(Text::InitRemindPat));
+        println!("  export CAZDO_PAT=\"{}\"", t(Text::InitPatPlaceholder));
+    }
+    Ok(())
+}
+
+fn prompt_organization_url(remote_url: Option<&str>) -> Result<String> {
+    let suggestion = remote_url.and_then(organization_url_from_remote);
+    loop {
+        let url = prompt(t(Text::InitOrganizationPrompt), suggestion.as_deref())?;
+        match validate_organization_url(&url) {
+            Ok(()) => return Ok(url.trim_end_matches('/').to_string()),
+            Err(error) => println!(
+                "  {}",
+                tf(Text::InitInvalidUrl, &[("error", &error.to_string())])
+            ),
+        }
+    }
+}
+
+/// Ask for a PAT and store it in the keyring, or in the config file when the
+/// keyring is unavailable. Returns the PAT the rest of the setup can use.
+fn prompt_pat(config: &mut Config) -> Result<Option<String>> {
+    if let Ok(pat) = std::env::var("CAZDO_PAT")
+        && !pat.trim().is_empty()
+    {
+        println!("{}", t(Text::InitPatFromEnv));
+        return Ok(Some(pat.trim().to_string()));
+    }
+
+    println!(
+        "{}",
+        tf(
+            Text::InitCreatePat,
+            &[("url", &config.azure_devops.organization_url)]
+        )
+    );
+    print!("{}: ", t(Text::InitPatPrompt));
+    io::stdout().flush()?;
+    let pat = read_hidden()?.trim().to_string();
+    if pat.is_empty() {
+        println!("  {}", t(Text::InitPatSkipped));
+        return Ok(None);
+    }
+
+    match store_pat(&config.azure_devops.organization_url, &pat) {
+        Ok(()) => println!("  {}", t(Text::InitPatStored)),
+        Err(error) => {
+            verbose!("{error:#}");
+            println!("  {error}.");
+            if confirm(t(Text::InitPatInConfig), false)? {
+                config.azure_devops.pat = Some(pat.clone());
+            } else {
+                println!("  {}", t(Text::InitPatInProfile));
+            }
+        }
+    }
+    Ok(Some(pat))
+}
+
+async fn prompt_project(
+    config: &Config,
+    pat: Option<&str>,
+    remote_project: Option<String>,
+) -> Result<Option<String>> {
+    let projects = match pat {
+        Some(pat) => match list_projects(config, pat).await {
+            Ok(projects) => projects,
+            Err(error) => {
+                println!(
+                    "{}",
+                    tf(
+                        Text::InitProjectsFailed,
+                        &[("error", &format!("{error:#}"))]
+                    )
+                );
+                Vec::new()
+            }
+        },
+        None => Vec::new(),
+    };
+
+    if projects.is_empty() {
+        let project = prompt(t(Text::InitProjectPrompt), remote_project.as_deref())?;
+        return Ok(Some(project).filter(|project| !project.is_empty() && project != "-"));
+    }
+
+    println!("{}", t(Text::InitProjects));
+    for (index, project) in projects.iter().enumerate() {
+        println!("  {:>2}. {project}", index + 1);
+    }
+    let default = remote_project
+        .as_deref()
+        .and_then(|remote| {
+            projects
+                .iter()
+                .find(|project| project.eq_ignore_ascii_case(remote))
+        })
+        .or_else(|| (projects.len() == 1).then(|| &projects[0]));
+    loop {
+        let answer = prompt(
+            t(Text::InitProjectChoicePrompt),
+            default.map(String::as_str),
+        )?;
+        if answer.is_empty() || answer == "-" {
+            return Ok(None);
+        }
+        match select_project(&answer, &projects) {
+            Some(project) => return Ok(Some(project.to_string())),
+            None => println!("  {}", tf(Text::InitUnknownProject, &[("answer", &answer)])),
+        }
+    }
+}
+
+async fn list_projects(config: &Config, pat: &str) -> Result<Vec<String>> {
+    let mut client_config = Config::default();
+    client_config.azure_devops.organization_url = config.azure_devops.organization_url.clone();
+    client_config.azure_devops.pat = Some(pat.to_string());
+    AzureDevOpsClient::new_live(&client_config)?
+        .list_projects()
+        .await
+}
+
+/// A project picked by its 1-based number in `projects` or by name.
+fn select_project<'a>(answer: &str, projects: &'a [String]) -> Option<&'a str> {
+    if let Ok(number) = answer.parse::<usize>() {
+        return projects.get(number.checked_sub(1)?).map(String::as_str);
+    }
+    projects
+        .iter()
+        .find(|project| project.eq_ignore_ascii_case(answer))
+        .map(String::as_str)
+}
+
+fn prompt_protected_patterns(suggested: &[String]) -> Result<Vec<String>> {
+    loop {
+        let answer = prompt(t(Text::InitPatternsPrompt), Some(&suggested.join(", ")))?;
+        let patterns: Vec<String> = answer
+            .split(',')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .map(str::to_string)
+            .collect();
+        match patterns
+            .iter()
+            .try_for_each(|pattern| validate_pattern(pattern))
+        {
+            Ok(()) if !patterns.is_empty() => return Ok(patterns),
+            Ok(()) => println!("  {}", t(Text::InitNoPatterns)),
+            Err(error) => println!("  {error:#}"),
+        }
+    }
+}
+
+fn repository_branch_names() -> Vec<String> {
+    GitRepo::open_current_dir()
+        .and_then(|repo| repo.list_branches())
+        .map(|branches| {
+            branches
+                .into_iter()
+                .map(|branch| branch.branch_name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Protected patterns for the branches of a repository: long-lived branches
+/// by name and release-style families by prefix, or the defaults when none
+/// of them exist.
+fn suggest_protected_patterns(branches: &[String]) -> Vec<String> {
+    let mut patterns: Vec<String> = PROTECTED_NAMES
+        .iter()
+        .filter(|name| branches.iter().any(|branch| branch == *name))
+        .map(|name| name.to_string())
+        .collect();
+    patterns.extend(
+        PROTECTED_PREFIXES
+            .iter()
+            .filter(|prefix| branches.iter().any(|branch| branch.starts_with(*prefix)))
+            .map(|prefix| format!("{prefix}*")),
+    );
+    if patterns.is_empty() {
+        return DEFAULT_PROTECTED_PATTERNS
+            .iter()
+            .map(|pattern| pattern.to_string())
+            .collect();
+    }
+    patterns
+}
+
+/// Ask a question, returning the trimmed answer or `default` for an empty one.
+fn prompt(question: &str, default: Option<&str>) -> Result<String> {
+    match default {
+        Some(default) if !default.is_empty() => print!("{question} [{default}]: "),
+        _ => print!("{question}: "),
+    }
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer)? == 0 {
+        bail!("{}", t(Text::Aborted));
+    }
+    let answer = answer.trim();
+    Ok(match default {
+        Some(default) if answer.is_empty() => default.to_string(),
+        _ => answer.to_string(),
+    })
+}
+
+fn confirm(question: &str, default: bool) -> Result<bool> {
+    print!("{question} {} ", if default { "[Y/n]" } else { "[y/N]" });
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(match answer.trim().to_lowercase().as_str() {
+        "" => default,
+        answer => answer == "y" || answer == "yes",
+    })
+}
+
+/// Read a line without echoing it.
+fn read_hidden() -> Result<String> {
+    terminal::enable_raw_mode().context("Failed to read from the terminal")?;
+    let input = read_hidden_raw();
+    terminal::disable_raw_mode().context("Failed to restore the terminal")?;
+    println!();
+    input
+}
+
+fn read_hidden_raw() -> Result<String> {
+    let mut input = String::new();
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(input),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char('c' | 'd') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                bail!("{}", t(Text::Aborted))
+            }
+            KeyCode::Esc => bail!("{}", t(Text::Aborted)),
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn suggests_patterns_for_existing_branches() {
+        let branches = names(&[
+            "main",
+            "develop",
+            "feature/42-login",
+            "release/1.0",
+            "release/1.1",
+            "hotfix/1.0.1",
+        ]);
+
+        assert_eq!(
+            suggest_protected_patterns(&branches),
+            names(&["main", "develop", "release/*", "hotfix/*"])
+        );
+        assert_eq!(
+            suggest_protected_patterns(&names(&["feature/1"])),
+            names(DEFAULT_PROTECTED_PATTERNS)
+        );
+    }
+
+    #[test]
+    fn selects_projects_by_number_or_name() {
+        let projects = names(&["Platform", "Web"]);
+
+        assert_eq!(select_project("2", &projects), Some("Web"));
+        assert_eq!(select_project("platform", &projects), Some("Platform"));
+        assert_eq!(select_project("0", &projects), None);
+        assert_eq!(select_project("3", &projects), None);
+        assert_eq!(select_project("Mobile", &projects), None);
+    }
+}
@@ -0,0 +1,84 @@
+//! Openers for Azure DevOps pages around the current repository: the team
+//! board, the current sprint, the branches page, and the branch's pipeline.
+
+use anyhow::{Context, Result};
+
+use super::{current_project, current_repository};
+use crate::azure_devops::{WebLinks, work_item_client};
+use crate::config::Config;
+use crate::git::GitRepo;
+use crate::i18n::{Text, t, tf};
+use crate::output::{is_quiet, verbose};
+use crate::tui::open_url;
+
+/// Open the board or sprint of `team`, else of `[azure_devops].team`.
+pub fn board_open(team: Option<String>, sprint: bool) -> Result<()> {
+    let config = Config::load_with_overrides()?;
+    let links = WebLinks::new(&config.azure_devops.organization_url)?;
+    let project = current_project()?;
+    let team = team.or(config.azure_devops.team);
+    let team = team.as_deref();
+
+    if sprint {
+        open_page(&links.sprint(&project, team), Text::PageSprint)
+    } else {
+        open_page(&links.board(&project, team), Text::PageBoard)
+    }
+}
+
+pub fn branch_open() -> Result<()> {
+    let links = web_links()?;
+    let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+    let repository = current_repository(&repo)?;
+
+    open_page(&links.branches(&repository), Text::PageBranches)
+}
+
+/// Open the pipeline that last built `branch` (the current branch by
+/// default), or the project's pipeline list if the branch was never built.
+pub async fn pipeline_open(branch: Option<String>) -> Result<()> {
+    let links = web_links()?;
+    let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+    let repository = current_repository(&repo)?;
+    let branch = match branch {
+        Some(branch) => branch,
+        None => repo
+            .current_local_branch_name()?
+            .context(t(Text::PipelineNeedsBranch))?,
+    };
+
+    let build = work_item_client()?
+        .latest_build(&repository, &branch)
+        .await?;
+    let definition_id = build.and_then(|build| build.definition_id);
+    if definition_id.is_none() && !is_quiet() {
+        println!("{}", tf(Text::NoBuilds, &[("branch", &branch)]));
+    }
+    verbose!("pipeline: '{branch}' -> definition {definition_id:?}");
+
+    open_page(
+        &links.pipeline(&repository.project, definition_id),
+        Text::PagePipeline,
+    )
+}
+
+fn web_links() -> Result<WebLinks> {
+    let config = Config::load_with_overrides()?;
+    WebLinks::new(&config.azure_devops.organization_url)
+}
+
+fn open_page(url: &reqwest::Url, page: Text) -> Result<()> {
+    open_url(url.as_str()).with_context(|| tf(Text::OpenPageFailed, &[("page", t(page))]))?;
+    if is_quiet() {
+        println!("{url}");
+    } else {
+        println!(
+            "{}",
+            tf(
+                Text::OpenedPage,
+                &[("page", t(page)), ("url", url.as_str())]
+            )
+        );
+    }
+    Ok(())
+}
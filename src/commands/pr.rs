@@ -0,0 +1,265 @@
+use anyhow::{Context, Result, bail};
+
+use super::table::{render_table, render_tsv, truncate};
+use super::{
+    branch_config, current_branch_work_item_id, current_repository, print_json, protected_patterns,
+    terminal_link,
+};
+use crate::azure_devops::{NewPullRequest, PullRequest, work_item_client};
+use crate::cli::ListFormat;
+use crate::config::Config;
+use crate::git::GitRepo;
+use crate::i18n::{Text, t, tf};
+use crate::output::{color_enabled, is_quiet};
+use crate::pattern::is_protected;
+use crate::tui::render_html;
+
+/// Azure Repos rejects pull request descriptions longer than this.
+const PR_DESCRIPTION_CHAR_LIMIT: usize = 4000;
+const PR_DESCRIPTION_WIDTH: usize = 100;
+const TITLE_COLUMN_LIMIT: usize = 50;
+const LIST_HEADERS: [&str; 5] = ["ID", "TITLE", "SOURCE", "REVIEWS", "STATUS"];
+
+pub async fn pr_create(draft: bool, target: Option<String>, json: bool) -> Result<()> {
+    let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+    let Some(branch) = repo.current_local_branch_name()? else {
+        bail!("{}", t(Text::PullRequestNeedsBranch));
+    };
+
+    let config = Config::load_for_current_dir().ok();
+    let protected_patterns = protected_patterns(config.as_ref());
+    if is_protected(&branch, &protected_patterns) {
+        bail!(
+            "{}",
+            tf(Text::PullRequestFromProtected, &[("branch", &branch)])
+        );
+    }
+
+    let branch_config = branch_config(config.as_ref());
+    let wi_id = current_branch_work_item_id(Some(&branch), &branch_config)?;
+    let target = target.or_else(|| branch_config.pr_target(&branch).map(str::to_string));
+    let repository = current_repository(&repo)?;
+
+    let client = work_item_client()?;
+    let wi = client.get_work_item(wi_id).await?;
+    let description = wi
+        .rich_text_fields
+        .iter()
+        .find(|field| field.name == "Description")
+        .map(|field| description_text(&field.value))
+        .unwrap_or_default();
+
+    let request = NewPullRequest {
+        source_branch: branch,
+        target_branch: target,
+        title: wi.title,
+        description,
+        is_draft: draft,
+        work_item_ids: vec![wi_id],
+    };
+    let pr = client.create_pull_request(&repository, &request).await?;
+
+    if json {
+        return print_json(&serde_json::json!({
+            "id": pr.id,
+            "title": pr.title,
+            "is_draft": pr.is_draft,
+            "source_branch": pr.source_branch,
+            "target_branch": pr.target_branch,
+            "work_item_id": wi_id,
+            "url": pr.url,
+        }));
+    }
+
+    if is_quiet() {
+        println!("{}", pr.url.unwrap_or_else(|| pr.id.to_string()));
+        return Ok(());
+    }
+
+    let created = if pr.is_draft {
+        Text::CreatedDraftPullRequest
+    } else {
+        Text::CreatedPullRequest
+    };
+    println!(
+        "{}",
+        tf(
+            created,
+            &[
+                ("id", &pr.id.to_string()),
+                ("source", &pr.source_branch),
+                ("target", &pr.target_branch),
+            ]
+        )
+    );
+    match pr.url {
+        Some(url) if color_enabled() => println!("{}", terminal_link(&url, &url)),
+        Some(url) => println!("{url}"),
+        None => {}
+    }
+
+    Ok(())
+}
+
+pub async fn pr_list(mine: bool, format: ListFormat) -> Result<()> {
+    let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+    let repository = current_repository(&repo)?;
+
+    let prs = work_item_client()?
+        .list_active_pull_requests(&repository, mine)
+        .await?;
+
+    match format {
+        ListFormat::Table if prs.is_empty() && is_quiet() => {}
+        ListFormat::Table if prs.is_empty() => println!("{}", t(Text::NoActivePullRequests)),
+        ListFormat::Table => print!("{}", render_table(LIST_HEADERS, &list_cells(&prs, true))),
+        ListFormat::Tsv => print!("{}", render_tsv(LIST_HEADERS, &list_cells(&prs, false))),
+        ListFormat::Json => print_json(&prs_json(&prs))?,
+    }
+
+    Ok(())
+}
+
+fn pr_status(pr: &PullRequest) -> &str {
+    if pr.is_draft { "draft" } else { &pr.status }
+}
+
+fn list_cells(prs: &[PullRequest], truncate_titles: bool) -> Vec<[String; 5]> {
+    prs.iter()
+        .map(|pr| {
+            let title = if truncate_titles {
+                truncate(&pr.title, TITLE_COLUMN_LIMIT)
+            } else {
+                pr.title.clone()
+            };
+            [
+                pr.id.to_string(),
+                title,
+                pr.source_branch.clone(),
+                pr.vote_summary(),
+                pr_status(pr).to_string(),
+            ]
+        })
+        .collect()
+}
+
+fn prs_json(prs: &[PullRequest]) -> serde_json::Value {
+    prs.iter()
+        .map(|pr| {
+            serde_json::json!({
+                "id": pr.id,
+                "title": pr.title,
+                "status": pr.status,
+                "is_draft": pr.is_draft,
+                "source_branch": pr.source_branch,
+                "target_branch": pr.target_branch,
+                "created_by": pr.created_by,
+                "reviewers": pr.reviewers.iter().map(|reviewer| serde_json::json!({
+                    "name": reviewer.name,
+                    "vote": reviewer.vote.as_str(),
+                    "is_required": reviewer.is_required,
+                })).collect::<Vec<_>>(),
+                "url": pr.url,
+            })
+        })
+        .collect()
+}
+
+/// Plain-text pull request description from a work item's HTML description.
+fn description_text(html: &str) -> String {
+    let text = render_html(html, PR_DESCRIPTION_WIDTH)
+        .into_iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let text = text.trim();
+
+    if text.chars().count() <= PR_DESCRIPTION_CHAR_LIMIT {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text
+        .chars()
+        .take(PR_DESCRIPTION_CHAR_LIMIT.saturating_sub(3))
+        .collect();
+    truncated.push_str("...");
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure_devops::{Reviewer, Vote};
+
+    fn pull_request(id: u32, title: &str, is_draft: bool) -> PullRequest {
+        PullRequest {
+            id,
+            title: title.to_string(),
+            status: "active".to_string(),
+            is_draft,
+            source_branch: format!("feature/{id}"),
+            target_branch: "main".to_string(),
+            created_by: Some("Ada".to_string()),
+            project_id: None,
+            repository_id: None,
+            last_merge_source_commit: None,
+            reviewers: vec![Reviewer {
+                name: "Grace".to_string(),
+                vote: Vote::Approved,
+                is_required: true,
+            }],
+            url: None,
+        }
+    }
+
+    #[test]
+    fn list_table_shows_votes_and_draft_status() {
+        let prs = [
+            pull_request(7, "Login page", false),
+            pull_request(12, "Logout", true),
+        ];
+
+        assert_eq!(
+            render_table(LIST_HEADERS, &list_cells(&prs, true)),
+            "ID  TITLE       SOURCE      REVIEWS     STATUS\n\
+             7   Login page  feature/7   1 approved  active\n\
+             12  Logout      feature/12  1 approved  draft\n"
+        );
+    }
+
+    #[test]
+    fn list_json_includes_reviewer_votes() {
+        let json = prs_json(&[pull_request(7, "Login page", false)]);
+
+        assert_eq!(json[0]["id"], 7);
+        assert_eq!(
+            json[0]["reviewers"],
+            serde_json::json!([{ "name": "Grace", "vote": "approved", "is_required": true }])
+        );
+    }
+
+    #[test]
+    fn description_text_keeps_line_structure() {
+        assert_eq!(
+            description_text("<p>First</p><ul><li>one</li><li>two</li></ul>"),
+            "First\n\n  • one\n  • two"
+        );
+    }
+
+    #[test]
+    fn description_text_respects_azure_length_limit() {
+        let html = format!("<p>{}</p>", "word ".repeat(2000));
+
+        let text = description_text(&html);
+
+        assert_eq!(text.chars().count(), PR_DESCRIPTION_CHAR_LIMIT);
+        assert!(text.ends_with("..."));
+    }
+}
@@ -0,0 +1,198 @@
+//! `cazdo config protect`: edit `[branches].protected` and show which local
+//! branches each pattern protects.
+
+use std::path::Path;
+
+use anyhow::{Result, bail};
+
+use super::print_json;
+use super::table::render_table;
+use crate::config::{Config, DEFAULT_PROTECTED_PATTERNS, find_project_config};
+use crate::git::{BranchScope, GitRepo};
+use crate::i18n::{Text, t, tf};
+use crate::output::{is_quiet, verbose};
+use crate::pattern::matches_pattern;
+
+pub fn config_protect_add(patterns: &[String], json: bool) -> Result<()> {
+    let mut config = load_or_default()?;
+    let mut added = Vec::new();
+    for pattern in patterns {
+        if config.branches.add_protected(pattern)? {
+            added.push(pattern.trim().to_string());
+        } else if !is_quiet() && !json {
+            println!(
+                "{}",
+                tf(Text::AlreadyProtected, &[("pattern", pattern.trim())])
+            );
+        }
+    }
+    save(&config, !added.is_empty())?;
+
+    print_patterns(&added, json, t(Text::ProtectedAdded))
+}
+
+pub fn config_protect_remove(patterns: &[String], json: bool) -> Result<()> {
+    let mut config = load_or_default()?;
+    for pattern in patterns {
+        if !config.branches.remove_protected(pattern) {
+            bail!(
+                "{}",
+                tf(
+                    Text::NotProtected,
+                    &[
+                        ("pattern", pattern.trim()),
+                        ("patterns", &config.branches.protected_patterns().join(", ")),
+                    ]
+                )
+            );
+        }
+    }
+    if config.branches.protected.is_empty() {
+        bail!(
+            "{}",
+            tf(
+                Text::KeepOneProtected,
+                &[("patterns", &DEFAULT_PROTECTED_PATTERNS.join(", "))]
+            )
+        );
+    }
+    save(&config, true)?;
+
+    let remaining = config.branches.protected_patterns();
+    print_patterns(&remaining, json, t(Text::ProtectedPatterns))
+}
+
+/// List the patterns in effect here, which a repository's `.cazdo.toml` may
+/// set in place of the global ones that `add` and `remove` edit.
+pub fn config_protect_list(json: bool) -> Result<()> {
+    let config = Config::load_optional_for_current_dir()?.unwrap_or_default();
+    let patterns = config.branches.protected_patterns();
+    let global_patterns = load_or_default()?.branches.protected_patterns();
+    let project_file = find_project_config(Path::new(".")).filter(|_| patterns != global_patterns);
+    let heading = match project_file {
+        Some(file) => tf(
+            Text::ProtectedFromProject,
+            &[("file", &file.display().to_string())],
+        ),
+        None if patterns == DEFAULT_PROTECTED_PATTERNS => t(Text::ProtectedDefaults).to_string(),
+        None => t(Text::ProtectedPatterns).to_string(),
+    };
+    print_patterns(&patterns, json, &heading)
+}
+
+fn load_or_default() -> Result<Config> {
+    if Config::config_path()?.exists() {
+        Config::load()
+    } else {
+        Ok(Config::default())
+    }
+}
+
+fn save(config: &Config, changed: bool) -> Result<()> {
+    if changed {
+        config.save()?;
+        verbose!("wrote {}", Config::config_path()?.display());
+    }
+    Ok(())
+}
+
+/// Print `patterns` with the local branches each one matches. Outside a
+/// repository only the patterns are printed.
+fn print_patterns(patterns: &[String], json: bool, heading: &str) -> Result<()> {
+    let branches = local_branch_names();
+    let matches: Vec<Vec<String>> = patterns
+        .iter()
+        .map(|pattern| matching_branches(pattern, branches.as_deref().unwrap_or_default()))
+        .collect();
+
+    if json {
+        return print_json(&serde_json::json!({
+            "patterns": patterns
+                .iter()
+                .zip(&matches)
+                .map(|(pattern, matches)| serde_json::json!({
+                    "pattern": pattern,
+                    "local_branches": branches.as_ref().map(|_| matches),
+                }))
+                .collect::<Vec<_>>(),
+        }));
+    }
+
+    if is_quiet() {
+        for pattern in patterns {
+            println!("{pattern}");
+        }
+        return Ok(());
+    }
+    if patterns.is_empty() {
+        return Ok(());
+    }
+
+    println!("{heading}");
+    println!();
+    if branches.is_none() {
+        for pattern in patterns {
+            println!("  {pattern}");
+        }
+        return Ok(());
+    }
+    let rows: Vec<[String; 2]> = patterns
+        .iter()
+        .zip(matches)
+        .map(|(pattern, matches)| {
+            let matches = if matches.is_empty() {
+                "-".to_string()
+            } else {
+                matches.join(", ")
+            };
+            [pattern.clone(), matches]
+        })
+        .collect();
+    print!("{}", render_table(["PATTERN", "LOCAL BRANCHES"], &rows));
+    Ok(())
+}
+
+fn local_branch_names() -> Option<Vec<String>> {
+    let repo = GitRepo::open_current_dir().ok()?;
+    let branches = repo.list_branches().ok()?;
+    Some(
+        branches
+            .into_iter()
+            .filter(|branch| branch.scope == BranchScope::Local)
+            .map(|branch| branch.branch_name)
+            .collect(),
+    )
+}
+
+fn matching_branches(pattern: &str, branches: &[String]) -> Vec<String> {
+    branches
+        .iter()
+        .filter(|branch| matches_pattern(branch, pattern))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_local_branches_per_pattern() {
+        let branches = vec![
+            "main".to_string(),
+            "releases/1.0".to_string(),
+            "releases/2.0".to_string(),
+            "feature/42".to_string(),
+        ];
+
+        assert_eq!(
+            matching_branches("releases/*", &branches),
+            vec!["releases/1.0", "releases/2.0"]
+        );
+        assert_eq!(
+            matching_branches(r"re:feature/\d+", &branches),
+            vec!["feature/42"]
+        );
+        assert!(matching_branches("master", &branches).is_empty());
+    }
+}
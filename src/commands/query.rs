@@ -0,0 +1,166 @@
+use anyhow::Result;
+
+use super::table::{render_table, render_tsv, truncate};
+use super::{current_project, print_json};
+use crate::azure_devops::{WorkItem, work_item_client};
+use crate::cli::ListFormat;
+use crate::config::Config;
+use crate::output::{is_quiet, verbose};
+
+const TITLE_COLUMN_LIMIT: usize = 60;
+const HEADERS: [&str; 5] = ["ID", "TYPE", "STATE", "ASSIGNED", "TITLE"];
+
+/// Run a saved query and list its work items.
+///
+/// The project is `--project`, `[azure_devops].project`, or else the one
+/// behind the `origin` remote.
+pub async fn query_saved(query: &str, format: ListFormat) -> Result<()> {
+    let project = current_project()?;
+    let client = work_item_client()?;
+    let (saved_query, ids) = client.run_saved_query(&project, query).await?;
+    verbose!(
+        "query: '{}' ({}) returned {} work items",
+        saved_query.path,
+        saved_query.id,
+        ids.len()
+    );
+
+    let rows: Vec<(u32, Result<WorkItem>)> = ids
+        .iter()
+        .copied()
+        .zip(client.get_work_items_batch(&ids).await?)
+        .collect();
+
+    print_rows(
+        &rows,
+        format,
+        &format!("No work items match '{}'.", saved_query.name),
+    )
+}
+
+/// Run WIQL `query`, or else `[query].wiql`, and list its work items.
+pub async fn list_work_items(query: Option<&str>, format: ListFormat) -> Result<()> {
+    let config = Config::load_for_current_dir()?;
+    let query = query.unwrap_or(config.query.wiql());
+    let client = work_item_client()?;
+    let ids = client.query_wiql(query).await?;
+    verbose!("query: {query} returned {} work items", ids.len());
+
+    let rows: Vec<(u32, Result<WorkItem>)> = ids
+        .iter()
+        .copied()
+        .zip(client.get_work_items_batch(&ids).await?)
+        .collect();
+
+    print_rows(&rows, format, "No work items match the query.")
+}
+
+fn print_rows(rows: &[(u32, Result<WorkItem>)], format: ListFormat, empty: &str) -> Result<()> {
+    match format {
+        ListFormat::Table if rows.is_empty() && is_quiet() => {}
+        ListFormat::Table if rows.is_empty() => println!("{empty}"),
+        ListFormat::Table => print!("{}", render_table(HEADERS, &cells(rows, true))),
+        ListFormat::Tsv => print!("{}", render_tsv(HEADERS, &cells(rows, false))),
+        ListFormat::Json => print_json(&rows_json(rows))?,
+    }
+
+    Ok(())
+}
+
+fn cells(rows: &[(u32, Result<WorkItem>)], truncate_titles: bool) -> Vec<[String; 5]> {
+    rows.iter()
+        .map(|(id, result)| match result {
+            Ok(wi) => {
+                let title = if truncate_titles {
+                    truncate(&wi.title, TITLE_COLUMN_LIMIT)
+                } else {
+                    wi.title.clone()
+                };
+                [
+                    id.to_string(),
+                    wi.work_item_type.display_name().to_string(),
+                    wi.state.display_name().to_string(),
+                    wi.assigned_to.clone().unwrap_or_default(),
+                    title,
+                ]
+            }
+            Err(_) => [
+                id.to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                "(failed to load)".to_string(),
+            ],
+        })
+        .collect()
+}
+
+fn rows_json(rows: &[(u32, Result<WorkItem>)]) -> serde_json::Value {
+    rows.iter()
+        .map(|(id, result)| match result {
+            Ok(wi) => serde_json::json!({
+                "id": id,
+                "type": wi.work_item_type.display_name(),
+                "state": wi.state.display_name(),
+                "assigned_to": wi.assigned_to,
+                "title": wi.title,
+                "url": wi.url,
+                "error": null,
+            }),
+            Err(error) => serde_json::json!({
+                "id": id,
+                "error": format!("{error:#}"),
+            }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure_devops::{WorkItemState, WorkItemType};
+    use std::collections::BTreeMap;
+
+    fn work_item(id: u32, title: &str) -> WorkItem {
+        WorkItem {
+            id,
+            title: title.to_string(),
+            work_item_type: WorkItemType::Bug,
+            state: WorkItemState::Active,
+            assigned_to: Some("Ada".to_string()),
+            url: None,
+            tags: Vec::new(),
+            rich_text_fields: Vec::new(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn table_lists_work_items_and_failures() {
+        let rows = vec![
+            (12, Ok(work_item(12, "Login fails"))),
+            (7, Err(anyhow::anyhow!("Work Item #7 not found"))),
+        ];
+
+        assert_eq!(
+            render_table(HEADERS, &cells(&rows, true)),
+            "ID  TYPE  STATE   ASSIGNED  TITLE\n\
+             12  Bug   Active  Ada       Login fails\n\
+             7                           (failed to load)\n"
+        );
+    }
+
+    #[test]
+    fn json_reports_fields_and_errors() {
+        let rows = vec![
+            (12, Ok(work_item(12, "Login fails"))),
+            (7, Err(anyhow::anyhow!("Work Item #7 not found"))),
+        ];
+
+        let json = rows_json(&rows);
+
+        assert_eq!(json[0]["type"], "Bug");
+        assert!(json[0]["error"].is_null());
+        assert_eq!(json[1]["error"], "Work Item #7 not found");
+    }
+}
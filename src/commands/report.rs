@@ -0,0 +1,314 @@
+//! `cazdo report`: a Markdown status report of local branches, their work
+//! items, pull requests, and latest builds, grouped by work item state.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use super::branch::{BranchRow, local_branch_rows};
+use super::current_repository;
+use crate::azure_devops::{Build, PullRequest, work_item_client};
+use crate::git::GitRepo;
+use crate::output::verbose;
+use crate::timestamp::format_date;
+
+const NO_WORK_ITEM: &str = "No work item";
+const UNKNOWN_STATE: &str = "Unknown";
+
+/// One branch in the report.
+struct ReportEntry {
+    row: BranchRow,
+    pull_request: Option<PullRequest>,
+    build: Option<Build>,
+}
+
+pub async fn report(since: Option<String>) -> Result<()> {
+    let now = Utc::now();
+    let since = since
+        .as_deref()
+        .map(|text| parse_since(text, now).map(|time| (text, time)))
+        .transpose()?;
+
+    let repo = GitRepo::open_current_dir().context("Failed to open git repository")?;
+    let mut rows = local_branch_rows(&repo, false).await?;
+    rows.retain(|row| !row.protected);
+    if let Some((_, since)) = since {
+        rows.retain(|row| {
+            row.last_commit_time
+                .is_some_and(|time| time >= since.timestamp())
+        });
+    }
+
+    let client = work_item_client()?;
+    let mut entries: Vec<ReportEntry> = rows
+        .into_iter()
+        .map(|row| ReportEntry {
+            row,
+            pull_request: None,
+            build: None,
+        })
+        .collect();
+
+    match current_repository(&repo) {
+        Ok(repository) => {
+            match client.list_active_pull_requests(&repository, false).await {
+                Ok(prs) => {
+                    for entry in &mut entries {
+                        entry.pull_request = prs
+                            .iter()
+                            .find(|pr| pr.source_branch == entry.row.branch)
+                            .cloned();
+                    }
+                }
+                Err(error) => verbose!("report: pull requests unavailable: {error:#}"),
+            }
+
+            let branches: Vec<String> = entries
+                .iter()
+                .map(|entry| entry.row.branch.clone())
+                .collect();
+            let builds = client.latest_builds(&repository, &branches).await;
+            for (entry, build) in entries.iter_mut().zip(builds) {
+                match build {
+                    Ok(build) => entry.build = build,
+                    Err(error) => {
+                        verbose!(
+                            "report: build of '{}' unavailable: {error:#}",
+                            entry.row.branch
+                        )
+                    }
+                }
+            }
+        }
+        Err(error) => verbose!("report: pull requests and builds unavailable: {error:#}"),
+    }
+
+    let since_label = since.map(|(text, _)| text);
+    print!(
+        "{}",
+        format_report(&entries, &format_date(now), since_label)
+    );
+
+    Ok(())
+}
+
+/// Parse `--since` as a date (`2024-05-01`) or an age in days or weeks
+/// (`7d`, `2w`), relative to `now`.
+fn parse_since(text: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+
+    let (count, unit) = text.split_at(text.len().saturating_sub(1));
+    let age = match (count.parse::<i64>(), unit) {
+        (Ok(count), "d") => Duration::days(count),
+        (Ok(count), "w") => Duration::weeks(count),
+        _ => bail!(
+            "Invalid --since value '{}'. Use a date (2024-05-01) or an age like 7d or 2w.",
+            text
+        ),
+    };
+    Ok(now - age)
+}
+
+fn format_report(entries: &[ReportEntry], generated: &str, since: Option<&str>) -> String {
+    let mut groups: BTreeMap<(u8, &str), Vec<&ReportEntry>> = BTreeMap::new();
+    for entry in entries {
+        let key = match (&entry.row.state, entry.row.work_item_id) {
+            (Some(state), _) => (0, state.as_str()),
+            (None, Some(_)) => (1, UNKNOWN_STATE),
+            (None, None) => (2, NO_WORK_ITEM),
+        };
+        groups.entry(key).or_default().push(entry);
+    }
+
+    let mut out = String::from("# Branch status report\n\n");
+    out.push_str(&format!("Generated {generated}"));
+    if let Some(since) = since {
+        out.push_str(&format!(", branches with commits since {since}"));
+    }
+    out.push_str(".\n");
+
+    if entries.is_empty() {
+        out.push_str("\nNo branches to report.\n");
+        return out;
+    }
+
+    for ((_, state), entries) in groups {
+        out.push_str(&format!("\n## {} ({})\n\n", state, entries.len()));
+        out.push_str("| Branch | Work item | Assigned to | Pull request | Build |\n");
+        out.push_str("| --- | --- | --- | --- | --- |\n");
+        for entry in entries {
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} |\n",
+                entry.row.branch,
+                work_item_cell(&entry.row),
+                escape_cell(entry.row.assigned_to.as_deref().unwrap_or("")),
+                entry.pull_request.as_ref().map(pr_cell).unwrap_or_default(),
+                entry.build.as_ref().map(build_cell).unwrap_or_default(),
+            ));
+        }
+    }
+
+    out
+}
+
+fn work_item_cell(row: &BranchRow) -> String {
+    let Some(id) = row.work_item_id else {
+        return String::new();
+    };
+    let label = match &row.url {
+        Some(url) => format!("[#{id}]({url})"),
+        None => format!("#{id}"),
+    };
+    match (&row.title, &row.error) {
+        (Some(title), _) => format!("{} {}", label, escape_cell(title)),
+        (None, Some(_)) => format!("{label} (failed to load)"),
+        (None, None) => label,
+    }
+}
+
+fn pr_cell(pr: &PullRequest) -> String {
+    let label = match &pr.url {
+        Some(url) => format!("[!{}]({})", pr.id, url),
+        None => format!("!{}", pr.id),
+    };
+    if pr.is_draft {
+        format!("{label} draft")
+    } else {
+        format!("{} {}", label, pr.vote_summary())
+    }
+}
+
+fn build_cell(build: &Build) -> String {
+    let text = if build.pipeline.is_empty() {
+        build.outcome().to_string()
+    } else {
+        format!("{}: {}", escape_cell(&build.pipeline), build.outcome())
+    };
+    match &build.url {
+        Some(url) => format!("[{text}]({url})"),
+        None => text,
+    }
+}
+
+/// Keep a value on one table row: pipes are escaped and newlines flattened.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::RemoteStatus;
+
+    fn row(branch: &str, work_item_id: Option<u32>, state: Option<&str>) -> BranchRow {
+        BranchRow {
+            branch: branch.to_string(),
+            current: false,
+            protected: false,
+            work_item_id,
+            title: state.map(|_| "Login | logout".to_string()),
+            state: state.map(str::to_string),
+            assigned_to: state.map(|_| "Ada".to_string()),
+            url: None,
+            remote_status: RemoteStatus::UpToDate,
+            last_commit_author: None,
+            last_commit_time: None,
+            error: None,
+        }
+    }
+
+    fn entry(row: BranchRow) -> ReportEntry {
+        ReportEntry {
+            row,
+            pull_request: None,
+            build: None,
+        }
+    }
+
+    #[test]
+    fn parses_since_dates_and_ages() {
+        let now = DateTime::parse_from_rfc3339("2024-05-15T12:00:00Z")
+            .unwrap()
+            .to_utc();
+
+        assert_eq!(
+            parse_since("2024-05-01", now).unwrap().to_rfc3339(),
+            "2024-05-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_since("2w", now).unwrap().to_rfc3339(),
+            "2024-05-01T12:00:00+00:00"
+        );
+        assert!(parse_since("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn groups_branches_by_state_with_branches_without_work_items_last() {
+        let mut failed = row("feature/9-x", Some(9), None);
+        failed.error = Some("not found".to_string());
+        let entries = [
+            entry(row("spike", None, None)),
+            entry(row("feature/2-b", Some(2), Some("Resolved"))),
+            entry(failed),
+            entry(row("feature/1-a", Some(1), Some("Active"))),
+        ];
+
+        let report = format_report(&entries, "2024-05-15", Some("7d"));
+        let headings: Vec<&str> = report
+            .lines()
+            .filter(|line| line.starts_with("## "))
+            .collect();
+
+        assert!(report.contains("Generated 2024-05-15, branches with commits since 7d."));
+        assert_eq!(
+            headings,
+            [
+                "## Active (1)",
+                "## Resolved (1)",
+                "## Unknown (1)",
+                "## No work item (1)"
+            ]
+        );
+        assert!(report.contains("| `feature/1-a` | #1 Login \\| logout | Ada |  |  |"));
+        assert!(report.contains("| `feature/9-x` | #9 (failed to load) |  |  |  |"));
+    }
+
+    #[test]
+    fn reports_pull_request_and_build_status() {
+        let mut entry = entry(row("feature/1-a", Some(1), Some("Active")));
+        entry.pull_request = Some(PullRequest {
+            id: 42,
+            title: "Login".to_string(),
+            status: "active".to_string(),
+            is_draft: true,
+            source_branch: "feature/1-a".to_string(),
+            target_branch: "main".to_string(),
+            created_by: None,
+            project_id: None,
+            repository_id: None,
+            last_merge_source_commit: None,
+            reviewers: Vec::new(),
+            url: Some("https://example.com/pr/42".to_string()),
+        });
+        entry.build = Some(Build {
+            id: 7,
+            pipeline: "CI".to_string(),
+            definition_id: None,
+            status: "completed".to_string(),
+            result: Some("succeeded".to_string()),
+            url: None,
+        });
+
+        let report = format_report(&[entry], "2024-05-15", None);
+
+        assert!(report.contains("| [!42](https://example.com/pr/42) draft | CI: succeeded |"));
+    }
+
+    #[test]
+    fn reports_when_no_branches_match() {
+        assert!(format_report(&[], "2024-05-15", None).ends_with("\nNo branches to report.\n"));
+    }
+}
@@ -0,0 +1,162 @@
+//! `cazdo restore`: list the branches deleted in TUI sessions and recreate
+//! those of one session.
+
+use anyhow::{Context, Result, bail};
+
+use super::print_json;
+use crate::git::{GitRepo, short_sha};
+use crate::i18n::{Text, t, tf};
+use crate::output::is_quiet;
+use crate::restore::{DeletedBranch, Session, sessions};
+use crate::timestamp::format_timestamp;
+
+/// List the recorded sessions, newest first, or restore the branches of
+/// `session` (numbered from 1 as listed), only `branches` if any are given.
+pub fn restore(session: Option<usize>, branches: &[String], json: bool) -> Result<()> {
+    let sessions = sessions()?;
+    let Some(number) = session else {
+        return list_sessions(&sessions, json);
+    };
+    let session = number
+        .checked_sub(1)
+        .and_then(|index| sessions.get(index))
+        .with_context(|| {
+            tf(
+                Text::NoSession,
+                &[
+                    ("number", &number.to_string()),
+                    ("count", &sessions.len().to_string()),
+                ],
+            )
+        })?;
+    replay(session, branches, json)
+}
+
+fn list_sessions(sessions: &[Session], json: bool) -> Result<()> {
+    if json {
+        return print_json(
+            &sessions
+                .iter()
+                .enumerate()
+                .map(|(index, session)| {
+                    serde_json::json!({
+                        "session": index + 1,
+                        "deleted_at": session.deleted_at.to_rfc3339(),
+                        "repo": session.repo_dir,
+                        "script": session.path,
+                        "branches": session.branches.iter().map(|branch| serde_json::json!({
+                            "branch": branch.name,
+                            "commit_sha": branch.commit_sha,
+                        })).collect::<Vec<_>>(),
+                    })
+                })
+                .collect(),
+        );
+    }
+    if sessions.is_empty() {
+        if !is_quiet() {
+            println!("{}", t(Text::NoDeletedBranches));
+        }
+        return Ok(());
+    }
+    for (index, session) in sessions.iter().enumerate() {
+        println!(
+            "{}  {}  {}",
+            index + 1,
+            format_timestamp(session.deleted_at.timestamp()).unwrap_or_default(),
+            session.repo_dir.display()
+        );
+        for branch in &session.branches {
+            println!("   {}  {}", branch.name, short_sha(&branch.commit_sha));
+        }
+    }
+    if !is_quiet() {
+        println!();
+        println!("{}", t(Text::RestoreSessionHint));
+    }
+    Ok(())
+}
+
+fn replay(session: &Session, names: &[String], json: bool) -> Result<()> {
+    let branches: Vec<&DeletedBranch> = if names.is_empty() {
+        session.branches.iter().collect()
+    } else {
+        names
+            .iter()
+            .map(|name| {
+                session
+                    .branches
+                    .iter()
+                    .find(|branch| &branch.name == name)
+                    .with_context(|| tf(Text::NotDeletedInSession, &[("branch", name)]))
+            })
+            .collect::<Result<_>>()?
+    };
+    let repo = GitRepo::open_dir(&session.repo_dir).with_context(|| {
+        format!(
+            "Failed to open git repository {}",
+            session.repo_dir.display()
+        )
+    })?;
+
+    let results: Vec<(&DeletedBranch, Result<()>)> = branches
+        .into_iter()
+        .map(|branch| {
+            let result = repo.restore_branch(&branch.name, &branch.commit_sha);
+            (branch, result)
+        })
+        .collect();
+
+    if json {
+        print_json(
+            &results
+                .iter()
+                .map(|(branch, result)| {
+                    serde_json::json!({
+                        "branch": branch.name,
+                        "commit_sha": branch.commit_sha,
+                        "result": if result.is_ok() { "restored" } else { "failed" },
+                        "reason": result.as_ref().err().map(|error| format!("{error:#}")),
+                    })
+                })
+                .collect(),
+        )?;
+    } else {
+        for (branch, result) in &results {
+            match result {
+                Ok(()) => println!(
+                    "{}",
+                    tf(
+                        Text::Restored,
+                        &[
+                            ("branch", &branch.name),
+                            ("sha", short_sha(&branch.commit_sha)),
+                        ]
+                    )
+                ),
+                Err(error) => println!(
+                    "{}",
+                    tf(
+                        Text::RestoreFailed,
+                        &[("branch", &branch.name), ("error", &format!("{error:#}"))]
+                    )
+                ),
+            }
+        }
+    }
+
+    let failures = results.iter().filter(|(_, result)| result.is_err()).count();
+    if failures > 0 {
+        bail!(
+            "{}",
+            tf(
+                Text::RestoreFailures,
+                &[
+                    ("failures", &failures.to_string()),
+                    ("count", &results.len().to_string()),
+                ]
+            )
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Plain-text table and TSV rendering shared by the listing commands.
+
+/// Render left-aligned columns separated by two spaces, with a header row.
+pub(super) fn render_table<const N: usize>(headers: [&str; N], rows: &[[String; N]]) -> String {
+    let mut widths = headers.map(|header| header.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let headers = headers.map(str::to_string);
+    std::iter::once(&headers)
+        .chain(rows)
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+/// Render tab-separated values with a header row; tabs and newlines inside
+/// cells become spaces.
+pub(super) fn render_tsv<const N: usize>(headers: [&str; N], rows: &[[String; N]]) -> String {
+    std::iter::once(headers.map(str::to_string))
+        .chain(
+            rows.iter()
+                .map(|row| row.clone().map(|cell| cell.replace(['\t', '\n'], " "))),
+        )
+        .map(|cells| format!("{}\n", cells.join("\t")))
+        .collect()
+}
+
+/// Shorten `text` to `max_chars`, ending with `...` when cut.
+pub(super) fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    truncated.push_str("...");
+    truncated
+}
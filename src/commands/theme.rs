@@ -0,0 +1,31 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::output::{color_enabled, terminal_width};
+use crate::tui::render_preview;
+use crate::tui::theme::{load_theme_file, set_theme};
+
+const PREVIEW_WIDTH: u16 = 100;
+const PREVIEW_HEIGHT: u16 = 24;
+
+/// Print the TUI drawn with the theme at `file`, to try a theme before
+/// setting `[display].theme`.
+pub fn theme_preview(file: &Path) -> Result<()> {
+    set_theme(load_theme_file(file)?);
+    let width = terminal_width().unwrap_or(PREVIEW_WIDTH);
+    print!(
+        "{}",
+        render_preview(width, PREVIEW_HEIGHT, color_enabled())?
+    );
+    Ok(())
+}
+
+/// Load `[display].theme`, if set, for the TUI.
+pub(super) fn use_config_theme(config: &Config) -> Result<()> {
+    if let Some(path) = &config.display.theme {
+        set_theme(load_theme_file(&Config::resolve_path(path))?);
+    }
+    Ok(())
+}
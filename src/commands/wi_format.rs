@@ -0,0 +1,268 @@
+//! Output formats for `cazdo wi`: a boxed preview for people, `key: value`
+//! lines for pipes, and a Markdown export of every rich text field.
+
+use crossterm::style::Stylize;
+
+use super::terminal_link;
+use crate::azure_devops::{FieldFormat, WorkItem};
+use crate::tui::{html_to_markdown, wrap_text};
+
+/// Text width inside the box, excluding borders and padding, when the
+/// terminal width is unknown.
+const DEFAULT_BOX_CONTENT_WIDTH: usize = 76;
+/// Bounds for the text width on narrow and very wide terminals.
+const MIN_BOX_CONTENT_WIDTH: usize = 30;
+const MAX_BOX_CONTENT_WIDTH: usize = 116;
+/// Columns taken by the borders and padding on both sides.
+const BOX_FRAME_WIDTH: usize = 4;
+
+/// Text width for a box that fills a terminal of `terminal_width` columns.
+pub(super) fn box_content_width(terminal_width: Option<u16>) -> usize {
+    match terminal_width {
+        Some(columns) => usize::from(columns)
+            .saturating_sub(BOX_FRAME_WIDTH)
+            .clamp(MIN_BOX_CONTENT_WIDTH, MAX_BOX_CONTENT_WIDTH),
+        None => DEFAULT_BOX_CONTENT_WIDTH,
+    }
+}
+
+/// One boxed row: the plain text used for width, and its styled rendering.
+struct Row {
+    plain: String,
+    styled: String,
+}
+
+/// The preview inside a rounded box whose values wrap at `width` columns,
+/// with bold labels and a linked ID when `color` is set.
+pub(super) fn format_box(wi: &WorkItem, description: &str, color: bool, width: usize) -> String {
+    let wi_label = format!("#{}", wi.id);
+    let header_rest = format!(
+        "  {} - {}",
+        wi.work_item_type.display_name(),
+        wi.state.display_name()
+    );
+    let mut rows = vec![Row {
+        plain: format!("{wi_label}{header_rest}"),
+        styled: format!(
+            "{}{}",
+            wi.url
+                .as_deref()
+                .filter(|_| color)
+                .map(|url| terminal_link(&wi_label, url))
+                .unwrap_or_else(|| wi_label.clone()),
+            header_rest
+        ),
+    }];
+
+    push_labeled(&mut rows, "Title:", &wi.title, color, width);
+    if let Some(assigned_to) = wi.assigned_to.as_deref() {
+        push_labeled(&mut rows, "Assigned:", assigned_to, color, width);
+    }
+    push_labeled(&mut rows, "Description:", description, color, width);
+
+    let width = rows
+        .iter()
+        .map(|row| row.plain.chars().count())
+        .max()
+        .unwrap_or(0);
+    let border = "─".repeat(width + 2);
+
+    let mut out = format!("╭{border}╮\n");
+    for row in rows {
+        let padding = " ".repeat(width - row.plain.chars().count());
+        out.push_str(&format!("│ {}{} │\n", row.styled, padding));
+    }
+    out.push_str(&format!("╰{border}╯\n"));
+    out
+}
+
+/// Wrap `value` after `label`, indenting continuation lines under it.
+fn push_labeled(rows: &mut Vec<Row>, label: &str, value: &str, color: bool, width: usize) {
+    let indent = " ".repeat(label.chars().count() + 1);
+    let wrapped = wrap_text(value, width.saturating_sub(indent.len()));
+
+    for (i, line) in wrapped.into_iter().enumerate() {
+        let (plain_prefix, styled_prefix) = if i == 0 && color {
+            (format!("{label} "), format!("{} ", label.bold()))
+        } else if i == 0 {
+            (format!("{label} "), format!("{label} "))
+        } else {
+            (indent.clone(), indent.clone())
+        };
+        rows.push(Row {
+            plain: format!("{plain_prefix}{line}"),
+            styled: format!("{styled_prefix}{line}"),
+        });
+    }
+}
+
+/// Pipe-friendly `key: value` lines; every key is always present.
+pub(super) fn format_plain(wi: &WorkItem, description: &str) -> String {
+    [
+        ("id", wi.id.to_string()),
+        ("type", wi.work_item_type.display_name().to_string()),
+        ("state", wi.state.display_name().to_string()),
+        ("title", wi.title.clone()),
+        ("assigned_to", wi.assigned_to.clone().unwrap_or_default()),
+        ("tags", wi.tags.join("; ")),
+        ("url", wi.url.clone().unwrap_or_default()),
+        ("description", description.to_string()),
+    ]
+    .into_iter()
+    .map(|(key, value)| format!("{key}: {}\n", value.replace('\n', " ")))
+    .collect()
+}
+
+/// A Markdown document with the work item's metadata and all rich text fields.
+pub(super) fn format_markdown(wi: &WorkItem) -> String {
+    let mut out = format!("# #{} {}\n\n", wi.id, wi.title);
+
+    let mut meta = vec![
+        format!("**Type:** {}", wi.work_item_type.display_name()),
+        format!("**State:** {}", wi.state.display_name()),
+    ];
+    if let Some(assigned_to) = wi.assigned_to.as_deref() {
+        meta.push(format!("**Assigned:** {assigned_to}"));
+    }
+    if !wi.tags.is_empty() {
+        meta.push(format!("**Tags:** {}", wi.tags.join(", ")));
+    }
+    out.push_str(&meta.join("  \n"));
+    out.push('\n');
+
+    if let Some(url) = wi.url.as_deref() {
+        out.push_str(&format!("\n[Open in Azure DevOps]({url})\n"));
+    }
+
+    for field in &wi.rich_text_fields {
+        let body = match field.format {
+            FieldFormat::Html => html_to_markdown(&field.value),
+            FieldFormat::Markdown => field.value.trim().to_string(),
+        };
+        out.push_str(&format!("\n## {}\n\n{}\n", field.name, body));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure_devops::{RichTextField, WorkItemState, WorkItemType};
+    use std::collections::BTreeMap;
+
+    fn work_item() -> WorkItem {
+        WorkItem {
+            id: 42,
+            title: "Fix login".to_string(),
+            work_item_type: WorkItemType::Bug,
+            state: WorkItemState::Active,
+            assigned_to: Some("Ada".to_string()),
+            url: None,
+            tags: vec!["auth".to_string(), "web".to_string()],
+            rich_text_fields: vec![
+                RichTextField {
+                    name: "Description".to_string(),
+                    value: "<p>Broken on <b>mobile</b></p>".to_string(),
+                    format: FieldFormat::Html,
+                },
+                RichTextField {
+                    name: "Acceptance Criteria".to_string(),
+                    value: "- works\n".to_string(),
+                    format: FieldFormat::Markdown,
+                },
+            ],
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn box_pads_rows_to_the_widest_line() {
+        let boxed = format_box(
+            &work_item(),
+            "Broken on mobile",
+            true,
+            DEFAULT_BOX_CONTENT_WIDTH,
+        );
+        let lines: Vec<&str> = boxed.lines().collect();
+
+        assert!(lines[0].starts_with('╭') && lines[0].ends_with('╮'));
+        assert!(lines.last().unwrap().starts_with('╰'));
+        assert!(lines[1].contains("#42  Bug - Active"));
+        assert!(
+            lines
+                .iter()
+                .all(|line| line.ends_with('│') || line.ends_with('╮') || line.ends_with('╯'))
+        );
+    }
+
+    #[test]
+    fn box_wraps_long_values_under_their_label() {
+        let description = "word ".repeat(40);
+        let boxed = format_box(
+            &work_item(),
+            description.trim(),
+            true,
+            DEFAULT_BOX_CONTENT_WIDTH,
+        );
+
+        let description_rows = boxed
+            .lines()
+            .skip_while(|line| !line.contains("Description:"))
+            .take_while(|line| !line.starts_with('╰'))
+            .count();
+        assert!(description_rows > 1);
+    }
+
+    #[test]
+    fn box_width_follows_terminal_within_bounds() {
+        assert_eq!(box_content_width(None), DEFAULT_BOX_CONTENT_WIDTH);
+        assert_eq!(box_content_width(Some(100)), 96);
+        assert_eq!(box_content_width(Some(20)), MIN_BOX_CONTENT_WIDTH);
+        assert_eq!(box_content_width(Some(300)), MAX_BOX_CONTENT_WIDTH);
+    }
+
+    #[test]
+    fn box_wraps_long_titles_to_fit_narrow_terminals() {
+        let mut wi = work_item();
+        wi.title = "Fix login redirect loop on expired sessions".to_string();
+        let width = box_content_width(Some(40));
+
+        let boxed = format_box(&wi, "Broken", false, width);
+
+        assert!(boxed.contains("│ Title: Fix login redirect loop on"));
+        assert!(
+            boxed
+                .lines()
+                .skip(2)
+                .all(|line| line.chars().count() <= width + BOX_FRAME_WIDTH)
+        );
+    }
+
+    #[test]
+    fn box_without_color_has_no_escape_sequences() {
+        let mut wi = work_item();
+        wi.url = Some("https://example.com/wi/42".to_string());
+
+        let boxed = format_box(&wi, "Broken on mobile", false, DEFAULT_BOX_CONTENT_WIDTH);
+
+        assert!(!boxed.contains('\x1b'));
+        assert!(boxed.contains("│ Title: Fix login"));
+    }
+
+    #[test]
+    fn plain_prints_every_key_on_its_own_line() {
+        assert_eq!(
+            format_plain(&work_item(), "Broken on mobile"),
+            "id: 42\ntype: Bug\nstate: Active\ntitle: Fix login\nassigned_to: Ada\ntags: auth; web\nurl: \ndescription: Broken on mobile\n"
+        );
+    }
+
+    #[test]
+    fn markdown_converts_html_fields_and_keeps_markdown_fields() {
+        assert_eq!(
+            format_markdown(&work_item()),
+            "# #42 Fix login\n\n**Type:** Bug  \n**State:** Active  \n**Assigned:** Ada  \n**Tags:** auth, web\n\n## Description\n\nBroken on **mobile**\n\n## Acceptance Criteria\n\n- works\n"
+        );
+    }
+}
@@ -1,4 +1,23 @@
+mod keyring;
+mod keys;
+mod overrides;
+mod project;
 mod settings;
+mod share;
 
-pub use settings::PatSource;
-pub use settings::{Config, DEFAULT_PROTECTED_PATTERNS};
+pub use keyring::store_pat;
+pub use keys::ConfigKey;
+pub use overrides::{Overrides, project_override, set_overrides};
+pub use project::find_project_config;
+pub use share::{export_config, import_config};
+
+#[cfg(test)]
+pub use settings::BranchRule;
+pub use settings::{
+    BranchConfig, DisplayConfig, EmojiMode, EstimateField, HttpConfig, KeyBinding, Language,
+    PatSource, ProxyMode, TimeZoneMode, TimestampStyle, validate_organization_url,
+};
+pub use settings::{
+    Config, DEFAULT_CACHE_MAX_AGE_DAYS, DEFAULT_CACHED_WORK_ITEMS, DEFAULT_COMMIT_TEMPLATE,
+    DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_PROTECTED_PATTERNS, DEFAULT_WIQL,
+};
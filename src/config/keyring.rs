@@ -0,0 +1,35 @@
+//! PATs kept in the system keyring (macOS Keychain, Windows Credential
+//! Manager, Secret Service on Linux), one entry per organization URL.
+//!
+//! The keyring is the last place a PAT is looked up, after `CAZDO_PAT` and
+//! `[azure_devops].pat`; `cazdo config init` stores the PAT there.
+
+use anyhow::{Context, Result};
+
+use crate::output::verbose;
+
+const SERVICE: &str = "cazdo";
+
+fn entry(organization_url: &str) -> ::keyring::Result<::keyring::Entry> {
+    ::keyring::Entry::new(SERVICE, organization_url.trim().trim_end_matches('/'))
+}
+
+/// The PAT stored for `organization_url`, if any. A keyring that is missing
+/// or locked counts as having no PAT.
+pub fn read_pat(organization_url: &str) -> Option<String> {
+    match entry(organization_url).and_then(|entry| entry.get_password()) {
+        Ok(pat) => Some(pat),
+        Err(::keyring::Error::NoEntry) => None,
+        Err(error) => {
+            verbose!("keyring: {error}");
+            None
+        }
+    }
+}
+
+/// Store `pat` for `organization_url`, replacing any earlier PAT.
+pub fn store_pat(organization_url: &str, pat: &str) -> Result<()> {
+    entry(organization_url)
+        .and_then(|entry| entry.set_password(pat))
+        .context("Failed to store the PAT in the system keyring")
+}
@@ -0,0 +1,631 @@
+//! Dotted config keys for `cazdo config get` / `cazdo config set`.
+//!
+//! Each key maps to one field of [`Config`] and knows how to parse and
+//! validate a command-line value for it, so scripted setup gets the same
+//! checks a hand-edited file would fail on at load time.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+
+use super::settings::{
+    COMMIT_TEMPLATE_PLACEHOLDERS, Config, EmojiMode, EstimateField, Language, ProxyMode,
+    TimeZoneMode, TimestampStyle, validate_api_version, validate_organization_url,
+};
+use crate::azure_devops::{DEFAULT_DETAIL_FIELDS, DetailField};
+use crate::pattern;
+use crate::template;
+use crate::timestamp::{DEFAULT_TIME_FORMAT, validate_time_format};
+
+const REDACTED: &str = "***redacted***";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigKey {
+    OrganizationUrl,
+    Pat,
+    Project,
+    Team,
+    ApiVersion,
+    TrustedOrganizations,
+    ProtectedBranches,
+    WorkItemMinDigits,
+    WorkItemIgnore,
+    WorkItemRegex,
+    FetchOnStart,
+    Emoji,
+    Theme,
+    DetailFields,
+    Estimate,
+    Timestamps,
+    TimeFormat,
+    TimeZone,
+    Lang,
+    Plain,
+    UpdateCheck,
+    CommitTemplate,
+    Proxy,
+    CaBundle,
+    MaxConcurrentRequests,
+    CachedWorkItems,
+    CacheMaxAgeDays,
+    Wiql,
+}
+
+impl ConfigKey {
+    pub const ALL: [ConfigKey; 28] = [
+        ConfigKey::OrganizationUrl,
+        ConfigKey::Pat,
+        ConfigKey::Project,
+        ConfigKey::Team,
+        ConfigKey::ApiVersion,
+        ConfigKey::TrustedOrganizations,
+        ConfigKey::ProtectedBranches,
+        ConfigKey::WorkItemMinDigits,
+        ConfigKey::WorkItemIgnore,
+        ConfigKey::WorkItemRegex,
+        ConfigKey::FetchOnStart,
+        ConfigKey::Emoji,
+        ConfigKey::Theme,
+        ConfigKey::DetailFields,
+        ConfigKey::Estimate,
+        ConfigKey::Timestamps,
+        ConfigKey::TimeFormat,
+        ConfigKey::TimeZone,
+        ConfigKey::Lang,
+        ConfigKey::Plain,
+        ConfigKey::UpdateCheck,
+        ConfigKey::CommitTemplate,
+        ConfigKey::Proxy,
+        ConfigKey::CaBundle,
+        ConfigKey::MaxConcurrentRequests,
+        ConfigKey::CachedWorkItems,
+        ConfigKey::CacheMaxAgeDays,
+        ConfigKey::Wiql,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ConfigKey::OrganizationUrl => "azure_devops.organization_url",
+            ConfigKey::Pat => "azure_devops.pat",
+            ConfigKey::Project => "azure_devops.project",
+            ConfigKey::Team => "azure_devops.team",
+            ConfigKey::ApiVersion => "azure_devops.api_version",
+            ConfigKey::TrustedOrganizations => "azure_devops.trusted_organizations",
+            ConfigKey::ProtectedBranches => "branches.protected",
+            ConfigKey::WorkItemMinDigits => "branches.work_item_min_digits",
+            ConfigKey::WorkItemIgnore => "branches.work_item_ignore",
+            ConfigKey::WorkItemRegex => "branches.work_item_regex",
+            ConfigKey::FetchOnStart => "branches.fetch_on_start",
+            ConfigKey::Emoji => "display.emoji",
+            ConfigKey::Theme => "display.theme",
+            ConfigKey::DetailFields => "display.fields",
+            ConfigKey::Estimate => "display.estimate",
+            ConfigKey::Timestamps => "display.timestamps",
+            ConfigKey::TimeFormat => "display.time_format",
+            ConfigKey::TimeZone => "display.timezone",
+            ConfigKey::Lang => "display.lang",
+            ConfigKey::Plain => "display.plain",
+            ConfigKey::UpdateCheck => "updates.check",
+            ConfigKey::CommitTemplate => "commit.template",
+            ConfigKey::Proxy => "http.proxy",
+            ConfigKey::CaBundle => "http.ca_bundle",
+            ConfigKey::MaxConcurrentRequests => "http.max_concurrent_requests",
+            ConfigKey::CachedWorkItems => "cache.work_items",
+            ConfigKey::CacheMaxAgeDays => "cache.max_age_days",
+            ConfigKey::Wiql => "query.wiql",
+        }
+    }
+}
+
+impl FromStr for ConfigKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match Self::ALL.into_iter().find(|key| key.name() == s) {
+            Some(key) => Ok(key),
+            None => {
+                let known: Vec<&str> = Self::ALL.iter().map(|key| key.name()).collect();
+                bail!(
+                    "Unknown config key '{}'. Known keys: {}",
+                    s,
+                    known.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// A config value as shown by `cazdo config get`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum ConfigValue {
+    Text(String),
+    List(Vec<String>),
+    Unset,
+}
+
+impl fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigValue::Text(value) => write!(f, "{value}"),
+            ConfigValue::List(values) => write!(f, "{}", values.join(",")),
+            ConfigValue::Unset => Ok(()),
+        }
+    }
+}
+
+impl Config {
+    /// Read the value of `key`. The PAT is redacted.
+    pub fn get(&self, key: ConfigKey) -> ConfigValue {
+        match key {
+            ConfigKey::OrganizationUrl => {
+                ConfigValue::Text(self.azure_devops.organization_url.clone())
+            }
+            ConfigKey::Pat => match self.azure_devops.pat {
+                Some(_) => ConfigValue::Text(REDACTED.to_string()),
+                None => ConfigValue::Unset,
+            },
+            ConfigKey::Project => optional_text(&self.azure_devops.project),
+            ConfigKey::Team => optional_text(&self.azure_devops.team),
+            ConfigKey::ApiVersion => ConfigValue::Text(self.azure_devops.api_version().to_string()),
+            ConfigKey::TrustedOrganizations => {
+                ConfigValue::List(self.azure_devops.trusted_organizations.clone())
+            }
+            ConfigKey::ProtectedBranches => ConfigValue::List(self.branches.protected_patterns()),
+            ConfigKey::WorkItemMinDigits => {
+                ConfigValue::Text(self.branches.work_item_min_digits().to_string())
+            }
+            ConfigKey::WorkItemIgnore => ConfigValue::List(self.branches.work_item_ignore.clone()),
+            ConfigKey::WorkItemRegex => optional_text(&self.branches.work_item_regex),
+            ConfigKey::FetchOnStart => ConfigValue::Text(self.branches.fetch_on_start.to_string()),
+            ConfigKey::Emoji => ConfigValue::Text(
+                match self.display.emoji {
+                    EmojiMode::Auto => "auto",
+                    EmojiMode::Always => "always",
+                    EmojiMode::Never => "never",
+                }
+                .to_string(),
+            ),
+            ConfigKey::Theme => optional_text(&self.display.theme),
+            ConfigKey::DetailFields => ConfigValue::List(match &self.display.fields {
+                Some(fields) => fields.clone(),
+                None => DEFAULT_DETAIL_FIELDS
+                    .iter()
+                    .map(|field| field.to_string())
+                    .collect(),
+            }),
+            ConfigKey::Estimate => ConfigValue::Text(
+                match self.display.estimate {
+                    EstimateField::Auto => "auto",
+                    EstimateField::StoryPoints => "story_points",
+                    EstimateField::Effort => "effort",
+                    EstimateField::OriginalEstimate => "original_estimate",
+                    EstimateField::Off => "off",
+                }
+                .to_string(),
+            ),
+            ConfigKey::Timestamps => ConfigValue::Text(
+                match self.display.timestamps {
+                    TimestampStyle::Relative => "relative",
+                    TimestampStyle::Absolute => "absolute",
+                }
+                .to_string(),
+            ),
+            ConfigKey::TimeFormat => ConfigValue::Text(
+                self.display
+                    .time_format
+                    .as_deref()
+                    .unwrap_or(DEFAULT_TIME_FORMAT)
+                    .to_string(),
+            ),
+            ConfigKey::TimeZone => ConfigValue::Text(
+                match self.display.timezone {
+                    TimeZoneMode::Local => "local",
+                    TimeZoneMode::Utc => "utc",
+                }
+                .to_string(),
+            ),
+            ConfigKey::Lang => ConfigValue::Text(
+                match self.display.lang {
+                    Language::En => "en",
+                    Language::De => "de",
+                }
+                .to_string(),
+            ),
+            ConfigKey::Plain => ConfigValue::Text(self.display.plain.to_string()),
+            ConfigKey::UpdateCheck => ConfigValue::Text(self.updates.check.to_string()),
+            ConfigKey::CommitTemplate => ConfigValue::Text(self.commit.template().to_string()),
+            ConfigKey::Proxy => ConfigValue::Text(
+                match self.http.proxy {
+                    ProxyMode::Auto => "auto",
+                    ProxyMode::Off => "off",
+                }
+                .to_string(),
+            ),
+            ConfigKey::CaBundle => optional_text(&self.http.ca_bundle),
+            ConfigKey::MaxConcurrentRequests => {
+                ConfigValue::Text(self.http.max_concurrent_requests().to_string())
+            }
+            ConfigKey::CachedWorkItems => ConfigValue::Text(self.cache.work_items().to_string()),
+            ConfigKey::CacheMaxAgeDays => ConfigValue::Text(self.cache.max_age_days().to_string()),
+            ConfigKey::Wiql => ConfigValue::Text(self.query.wiql().to_string()),
+        }
+    }
+
+    /// Parse `value` for `key` and store it.
+    ///
+    /// Lists are comma-separated; an empty value resets `branches.protected`,
+    /// `branches.work_item_min_digits`, `display.fields`, `display.time_format`, `commit.template`,
+    /// `cache.work_items`, `cache.max_age_days`, `http.max_concurrent_requests`, and
+    /// `azure_devops.api_version` to the defaults and unsets optional values such as `azure_devops.pat`, `display.theme`, and `http.ca_bundle`.
+    pub fn set(&mut self, key: ConfigKey, value: &str) -> Result<()> {
+        let value = value.trim();
+
+        match key {
+            ConfigKey::OrganizationUrl => {
+                if !(value.starts_with("https://") || value.starts_with("http://")) {
+                    bail!(
+                        "{} must be an http(s) URL, e.g. https://dev.azure.com/your-organization",
+                        key.name()
+                    );
+                }
+                self.azure_devops.organization_url = value.to_string();
+            }
+            ConfigKey::Pat => {
+                self.azure_devops.pat = (!value.is_empty()).then(|| value.to_string());
+            }
+            ConfigKey::Project => {
+                self.azure_devops.project = (!value.is_empty()).then(|| value.to_string());
+            }
+            ConfigKey::Team => {
+                self.azure_devops.team = (!value.is_empty()).then(|| value.to_string());
+            }
+            ConfigKey::ApiVersion => {
+                if !value.is_empty() {
+                    validate_api_version(value)
+                        .with_context(|| format!("Invalid {}", key.name()))?;
+                }
+                self.azure_devops.api_version = (!value.is_empty()).then(|| value.to_string());
+            }
+            ConfigKey::TrustedOrganizations => {
+                let urls: Vec<String> = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|url| !url.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                for url in &urls {
+                    validate_organization_url(url)
+                        .with_context(|| format!("Invalid {}", key.name()))?;
+                }
+                self.azure_devops.trusted_organizations = urls;
+            }
+            ConfigKey::ProtectedBranches => {
+                let patterns: Vec<String> = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|pattern| !pattern.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                for pattern in &patterns {
+                    pattern::validate_pattern(pattern)
+                        .with_context(|| format!("Invalid {}", key.name()))?;
+                }
+                self.branches.protected = patterns;
+            }
+            ConfigKey::WorkItemMinDigits => {
+                self.branches.work_item_min_digits = positive_count(key, value)?;
+            }
+            ConfigKey::WorkItemIgnore => {
+                let patterns: Vec<String> = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|pattern| !pattern.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                for pattern in &patterns {
+                    pattern::validate_pattern(pattern)
+                        .with_context(|| format!("Invalid {}", key.name()))?;
+                }
+                self.branches.work_item_ignore = patterns;
+            }
+            ConfigKey::Emoji => {
+                self.display.emoji = match value {
+                    "auto" => EmojiMode::Auto,
+                    "always" => EmojiMode::Always,
+                    "never" => EmojiMode::Never,
+                    _ => bail!("{} must be one of: auto, always, never", key.name()),
+                };
+            }
+            ConfigKey::Theme => {
+                self.display.theme = (!value.is_empty()).then(|| value.to_string());
+            }
+            ConfigKey::DetailFields => {
+                let fields: Vec<String> = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|field| !field.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                DetailField::parse_list(Some(&fields))
+                    .with_context(|| format!("Invalid {}", key.name()))?;
+                self.display.fields = (!fields.is_empty()).then_some(fields);
+            }
+            ConfigKey::Estimate => {
+                self.display.estimate = match value {
+                    "auto" => EstimateField::Auto,
+                    "story_points" => EstimateField::StoryPoints,
+                    "effort" => EstimateField::Effort,
+                    "original_estimate" => EstimateField::OriginalEstimate,
+                    "off" => EstimateField::Off,
+                    _ => bail!(
+                        "{} must be auto, story_points, effort, original_estimate, or off",
+                        key.name()
+                    ),
+                };
+            }
+            ConfigKey::Timestamps => {
+                self.display.timestamps = match value {
+                    "relative" => TimestampStyle::Relative,
+                    "absolute" => TimestampStyle::Absolute,
+                    _ => bail!("{} must be relative or absolute", key.name()),
+                };
+            }
+            ConfigKey::TimeFormat => {
+                if !value.is_empty() {
+                    validate_time_format(value)
+                        .with_context(|| format!("Invalid {}", key.name()))?;
+                }
+                self.display.time_format = (!value.is_empty()).then(|| value.to_string());
+            }
+            ConfigKey::TimeZone => {
+                self.display.timezone = match value {
+                    "local" => TimeZoneMode::Local,
+                    "utc" => TimeZoneMode::Utc,
+                    _ => bail!("{} must be local or utc", key.name()),
+                };
+            }
+            ConfigKey::Lang => {
+                self.display.lang = match value {
+                    "en" => Language::En,
+                    "de" => Language::De,
+                    _ => bail!("{} must be en or de", key.name()),
+                };
+            }
+            ConfigKey::WorkItemRegex => {
+                if !value.is_empty() {
+                    pattern::validate_capture_regex(value)
+                        .with_context(|| format!("Invalid {}", key.name()))?;
+                }
+                self.branches.work_item_regex = (!value.is_empty()).then(|| value.to_string());
+            }
+            ConfigKey::FetchOnStart => {
+                self.branches.fetch_on_start = match value {
+                    "true" => true,
+                    "false" => false,
+                    _ => bail!("{} must be true or false", key.name()),
+                };
+            }
+            ConfigKey::Plain => {
+                self.display.plain = match value {
+                    "true" => true,
+                    "false" => false,
+                    _ => bail!("{} must be true or false", key.name()),
+                };
+            }
+            ConfigKey::UpdateCheck => {
+                self.updates.check = match value {
+                    "true" => true,
+                    "false" => false,
+                    _ => bail!("{} must be true or false", key.name()),
+                };
+            }
+            ConfigKey::CommitTemplate => {
+                if !value.is_empty() {
+                    let placeholders: Vec<(&str, &str)> = COMMIT_TEMPLATE_PLACEHOLDERS
+                        .iter()
+                        .map(|name| (*name, ""))
+                        .collect();
+                    template::render(value, &placeholders)
+                        .with_context(|| format!("Invalid {}", key.name()))?;
+                }
+                self.commit.template = (!value.is_empty()).then(|| value.to_string());
+            }
+            ConfigKey::Proxy => {
+                self.http.proxy = match value {
+                    "auto" => ProxyMode::Auto,
+                    "off" => ProxyMode::Off,
+                    _ => bail!("{} must be auto or off", key.name()),
+                };
+            }
+            ConfigKey::CaBundle => {
+                self.http.ca_bundle = (!value.is_empty()).then(|| value.to_string());
+            }
+            ConfigKey::MaxConcurrentRequests => {
+                self.http.max_concurrent_requests = positive_count(key, value)?;
+            }
+            ConfigKey::CachedWorkItems => {
+                self.cache.work_items = positive_count(key, value)?;
+            }
+            ConfigKey::CacheMaxAgeDays => {
+                self.cache.max_age_days = positive_count(key, value)?;
+            }
+            ConfigKey::Wiql => {
+                let value = value.trim();
+                if !value.is_empty() && !value.to_ascii_uppercase().starts_with("SELECT ") {
+                    bail!("{} must be a WIQL SELECT query", key.name());
+                }
+                self.query.wiql = (!value.is_empty()).then(|| value.to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn optional_text(value: &Option<String>) -> ConfigValue {
+    match value {
+        Some(value) => ConfigValue::Text(value.clone()),
+        None => ConfigValue::Unset,
+    }
+}
+
+/// A count above zero for `key`, or `None` for an empty value.
+fn positive_count(key: ConfigKey, value: &str) -> Result<Option<usize>> {
+    match value {
+        "" => Ok(None),
+        _ => match value.parse::<usize>() {
+            Ok(count) if count > 0 => Ok(Some(count)),
+            _ => bail!("{} must be a positive number", key.name()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_keys_and_lists_them_on_error() {
+        assert_eq!(
+            "branches.protected".parse::<ConfigKey>().unwrap(),
+            ConfigKey::ProtectedBranches
+        );
+
+        let error = "branches.protect"
+            .parse::<ConfigKey>()
+            .expect_err("typo should be rejected");
+        assert!(
+            error
+                .to_string()
+                .contains("Known keys: azure_devops.organization_url")
+        );
+    }
+
+    #[test]
+    fn set_protected_branches_splits_and_trims_list() {
+        let mut config = Config::default();
+
+        config
+            .set(ConfigKey::ProtectedBranches, "main, release/*,,")
+            .unwrap();
+
+        assert_eq!(config.branches.protected, vec!["main", "release/*"]);
+        assert_eq!(
+            config.get(ConfigKey::ProtectedBranches).to_string(),
+            "main,release/*"
+        );
+    }
+
+    #[test]
+    fn set_validates_values() {
+        let mut config = Config::default();
+
+        assert!(
+            config
+                .set(ConfigKey::ProtectedBranches, "main, re:release/(")
+                .is_err()
+        );
+
+        assert!(
+            config
+                .set(ConfigKey::OrganizationUrl, "dev.azure.com/org")
+                .is_err()
+        );
+        assert!(config.set(ConfigKey::Emoji, "sometimes").is_err());
+
+        assert!(config.set(ConfigKey::UpdateCheck, "yes").is_err());
+        assert!(config.set(ConfigKey::FetchOnStart, "yes").is_err());
+        assert!(
+            config
+                .set(ConfigKey::WorkItemRegex, r"feature/\d+")
+                .is_err()
+        );
+        assert!(config.set(ConfigKey::Proxy, "none").is_err());
+
+        config.set(ConfigKey::Emoji, "never").unwrap();
+        assert_eq!(config.display.emoji, EmojiMode::Never);
+        config.set(ConfigKey::UpdateCheck, "true").unwrap();
+        assert!(config.updates.check);
+        config.set(ConfigKey::Proxy, "off").unwrap();
+        assert_eq!(config.http.proxy, ProxyMode::Off);
+
+        assert!(config.set(ConfigKey::Plain, "on").is_err());
+        config.set(ConfigKey::Plain, "true").unwrap();
+        assert!(config.display.plain);
+
+        assert!(config.set(ConfigKey::Lang, "fr").is_err());
+        config.set(ConfigKey::Lang, "de").unwrap();
+        assert_eq!(config.display.lang, Language::De);
+
+        assert!(config.set(ConfigKey::Estimate, "points").is_err());
+        config.set(ConfigKey::Estimate, "story_points").unwrap();
+        assert_eq!(config.display.estimate, EstimateField::StoryPoints);
+        assert_eq!(
+            config.get(ConfigKey::Estimate),
+            ConfigValue::Text("story_points".to_string())
+        );
+
+        assert!(config.set(ConfigKey::WorkItemMinDigits, "0").is_err());
+        config.set(ConfigKey::WorkItemMinDigits, "4").unwrap();
+        assert_eq!(config.branches.work_item_min_digits(), 4);
+        assert!(config.set(ConfigKey::WorkItemIgnore, "v*, re:(").is_err());
+        config.set(ConfigKey::WorkItemIgnore, "v*, ie*").unwrap();
+        assert_eq!(config.branches.work_item_ignore, ["v*", "ie*"]);
+
+        assert!(config.set(ConfigKey::CachedWorkItems, "0").is_err());
+        assert!(config.set(ConfigKey::CachedWorkItems, "many").is_err());
+        config.set(ConfigKey::CachedWorkItems, "200").unwrap();
+        assert_eq!(config.cache.work_items(), 200);
+        config.set(ConfigKey::CachedWorkItems, "").unwrap();
+        assert_eq!(
+            config.get(ConfigKey::CachedWorkItems).to_string(),
+            crate::config::DEFAULT_CACHED_WORK_ITEMS.to_string()
+        );
+
+        assert!(config.set(ConfigKey::MaxConcurrentRequests, "0").is_err());
+        config.set(ConfigKey::MaxConcurrentRequests, "1").unwrap();
+        assert_eq!(config.http.max_concurrent_requests(), 1);
+        config.set(ConfigKey::MaxConcurrentRequests, "").unwrap();
+        assert_eq!(config.http.max_concurrent_requests, None);
+
+        assert!(config.set(ConfigKey::CommitTemplate, "{titel}").is_err());
+        config
+            .set(ConfigKey::CommitTemplate, "#{id} {title}")
+            .unwrap();
+        assert_eq!(config.commit.template(), "#{id} {title}");
+        config.set(ConfigKey::CommitTemplate, "").unwrap();
+        assert_eq!(config.commit.template, None);
+    }
+
+    #[test]
+    fn get_redacts_pat_and_empty_set_unsets_it() {
+        let mut config = Config::default();
+
+        config.set(ConfigKey::Pat, "secret").unwrap();
+        assert_eq!(
+            config.get(ConfigKey::Pat),
+            ConfigValue::Text(REDACTED.to_string())
+        );
+
+        config.set(ConfigKey::Pat, "").unwrap();
+        assert_eq!(config.get(ConfigKey::Pat), ConfigValue::Unset);
+    }
+
+    #[test]
+    fn set_and_unset_project_and_team() {
+        let mut config = Config::default();
+
+        config.set(ConfigKey::Project, "Web").unwrap();
+        config.set(ConfigKey::Team, " Web Team ").unwrap();
+        assert_eq!(
+            config.get(ConfigKey::Team),
+            ConfigValue::Text("Web Team".to_string())
+        );
+
+        config.set(ConfigKey::Project, "").unwrap();
+        assert_eq!(config.get(ConfigKey::Project), ConfigValue::Unset);
+    }
+}
@@ -0,0 +1,99 @@
+//! Per-invocation overrides from the global `--org` and `--project` flags.
+//!
+//! They take precedence over the config file and over the project detected
+//! from the `origin` remote, without ever being written back to the file.
+
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+
+use super::keys::ConfigKey;
+use super::settings::Config;
+
+#[derive(Debug, Default)]
+pub struct Overrides {
+    pub organization_url: Option<String>,
+    pub project: Option<String>,
+}
+
+static OVERRIDES: OnceLock<Overrides> = OnceLock::new();
+
+pub fn set_overrides(overrides: Overrides) {
+    let _ = OVERRIDES.set(overrides);
+}
+
+fn overrides() -> Option<&'static Overrides> {
+    OVERRIDES.get()
+}
+
+/// The `--project` override, if given.
+pub fn project_override() -> Option<&'static str> {
+    overrides().and_then(|overrides| overrides.project.as_deref())
+}
+
+impl Config {
+    /// Load the config used to talk to Azure DevOps, with the repository's
+    /// `.cazdo.toml` and `--org` applied.
+    ///
+    /// With `--org`, a missing config file is fine: the PAT can still come
+    /// from `CAZDO_PAT`.
+    pub fn load_with_overrides() -> Result<Self> {
+        let Some(url) = overrides().and_then(|overrides| overrides.organization_url.as_deref())
+        else {
+            return Self::load_for_current_dir();
+        };
+
+        let (mut config, global) = if Self::config_path()?.exists() {
+            (Self::load_for_current_dir()?, Self::load()?)
+        } else {
+            (Self::default(), Self::default())
+        };
+        apply_organization_override(&mut config, &global, url)?;
+        Ok(config)
+    }
+}
+
+/// Point `config` at `url`. The URL comes from the user rather than from the
+/// repository, so it is trusted and gets the PAT of the global config, which
+/// a `.cazdo.toml` naming another organization removed.
+fn apply_organization_override(config: &mut Config, global: &Config, url: &str) -> Result<()> {
+    config
+        .set(ConfigKey::OrganizationUrl, url)
+        .with_context(|| format!("Invalid --org value '{}'", url))?;
+    config.azure_devops.untrusted = false;
+    config.azure_devops.pat = global.azure_devops.pat.clone();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::parse_config;
+
+    #[test]
+    fn org_override_restores_the_pat_a_project_config_removed() {
+        let content = "[azure_devops]\norganization_url = \"https://dev.azure.com/org\"\npat = \"global-pat\"\n";
+        let global = parse_config(content).unwrap();
+        let mut config = parse_config(content).unwrap();
+        config.azure_devops.organization_url = "https://attacker.example/org".into();
+        config.azure_devops.pat = None;
+        config.azure_devops.untrusted = true;
+
+        apply_organization_override(&mut config, &global, "https://dev.azure.com/org").unwrap();
+
+        assert_eq!(
+            config.azure_devops.organization_url,
+            "https://dev.azure.com/org"
+        );
+        assert!(!config.azure_devops.untrusted);
+        assert_eq!(config.azure_devops.pat.as_deref(), Some("global-pat"));
+    }
+
+    #[test]
+    fn an_invalid_org_override_is_an_error() {
+        let mut config = Config::default();
+        assert!(
+            apply_organization_override(&mut config, &Config::default(), "dev.azure.com").is_err()
+        );
+    }
+}
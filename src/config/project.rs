@@ -0,0 +1,273 @@
+//! `.cazdo.toml` in a repository's root: team settings checked in with the
+//! code and applied over the global config for commands run in that
+//! repository.
+//!
+//! Only the shared sections can be set there, never the PAT or the trusted
+//! organizations. A cloned repository may point cazdo at any host, so one
+//! that names another organization gets no PAT from any source unless that
+//! organization is listed in the global `[azure_devops].trusted_organizations`;
+//! a trusted one gets `CAZDO_PAT` or its keyring entry, never the PAT from
+//! the global config file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use toml::{Table, Value};
+
+use super::settings::{Config, toml_error, validate_organization_url};
+use super::share::import_config;
+use crate::output::verbose;
+
+/// Name of the repository config file, next to `.git`
+pub const PROJECT_CONFIG_FILE: &str = ".cazdo.toml";
+
+/// Sections a repository config may set
+const PROJECT_SECTIONS: &[&str] = &["azure_devops", "branches", "commit", "query"];
+
+impl Config {
+    /// Load the config for the repository containing `path`: the global
+    /// config with the repository's `.cazdo.toml` applied, if it has one.
+    ///
+    /// Without a global config file, a `.cazdo.toml` that sets the
+    /// organization URL is enough, though no PAT is sent to it since nothing
+    /// is trusted.
+    pub fn load_for_repo(path: &Path) -> Result<Self> {
+        let Some(project_path) = find_project_config(path) else {
+            return Self::load();
+        };
+        verbose!("config: applying {}", project_path.display());
+        let content = fs::read_to_string(&project_path)
+            .with_context(|| format!("Failed to read {}", project_path.display()))?;
+
+        let base = if Self::config_path()?.exists() {
+            Self::load()?
+        } else if sets_organization_url(&content) {
+            Self::default()
+        } else {
+            return Self::load();
+        };
+        apply_project_config(&base, &content)
+            .with_context(|| format!("Invalid {}", project_path.display()))
+    }
+
+    /// [`Config::load_for_repo`] for the repository in the current directory.
+    pub fn load_for_current_dir() -> Result<Self> {
+        Self::load_for_repo(Path::new("."))
+    }
+
+    /// [`Config::load_for_repo`] for commands that also work unconfigured:
+    /// `None` when there is neither a global config nor a `.cazdo.toml`,
+    /// and the defaults in place of a missing global config otherwise. A
+    /// config file that cannot be read or parsed is still an error, so its
+    /// protection rules are never silently replaced by the defaults.
+    pub fn load_optional_for_repo(path: &Path) -> Result<Option<Self>> {
+        let global_exists = Self::config_path()?.exists();
+        let Some(project_path) = find_project_config(path) else {
+            return if global_exists {
+                Self::load().map(Some)
+            } else {
+                Ok(None)
+            };
+        };
+        verbose!("config: applying {}", project_path.display());
+        let content = fs::read_to_string(&project_path)
+            .with_context(|| format!("Failed to read {}", project_path.display()))?;
+
+        let base = if global_exists {
+            Self::load()?
+        } else {
+            Self::default()
+        };
+        apply_project_config(&base, &content)
+            .with_context(|| format!("Invalid {}", project_path.display()))
+            .map(Some)
+    }
+
+    /// [`Config::load_optional_for_repo`] for the current directory.
+    pub fn load_optional_for_current_dir() -> Result<Option<Self>> {
+        Self::load_optional_for_repo(Path::new("."))
+    }
+}
+
+/// The `.cazdo.toml` in the root of the repository containing `path`.
+pub fn find_project_config(path: &Path) -> Option<PathBuf> {
+    let path = path.canonicalize().ok()?;
+    let root = path.ancestors().find(|dir| dir.join(".git").exists())?;
+    let file = root.join(PROJECT_CONFIG_FILE);
+    file.is_file().then_some(file)
+}
+
+fn sets_organization_url(content: &str) -> bool {
+    toml::from_str::<Table>(content).is_ok_and(|table| {
+        table
+            .get("azure_devops")
+            .and_then(|section| section.get("organization_url"))
+            .is_some()
+    })
+}
+
+/// Merge the repository config in `content` over `base`, key by key as
+/// `cazdo config import` does.
+fn apply_project_config(base: &Config, content: &str) -> Result<Config> {
+    let table: Table = toml::from_str(content).map_err(|error| toml_error(content, &error))?;
+    for section in table.keys() {
+        if !PROJECT_SECTIONS.contains(&section.as_str()) {
+            bail!(
+                "[{section}] cannot be set per repository; only [azure_devops], [branches], [commit], and [query] can"
+            );
+        }
+    }
+    let organization_url = match table.get("azure_devops") {
+        Some(Value::Table(section)) => {
+            if section.contains_key("pat") {
+                bail!("the PAT cannot be set per repository");
+            }
+            if section.contains_key("trusted_organizations") {
+                bail!("trusted organizations cannot be set per repository");
+            }
+            section.get("organization_url").and_then(Value::as_str)
+        }
+        _ => None,
+    };
+    if let Some(url) = organization_url {
+        validate_organization_url(url).context("Invalid [azure_devops].organization_url")?;
+    }
+
+    let mut config = import_config(base, content)?.config;
+    if let Some(url) = organization_url
+        && !same_url(url, &base.azure_devops.organization_url)
+    {
+        config.azure_devops.pat = None;
+        config.azure_devops.untrusted = !base
+            .azure_devops
+            .trusted_organizations
+            .iter()
+            .any(|trusted| same_url(url, trusted));
+    }
+    Ok(config)
+}
+
+fn same_url(a: &str, b: &str) -> bool {
+    a.trim().trim_end_matches('/') == b.trim().trim_end_matches('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::{PatSource, parse_config};
+    use tempfile::TempDir;
+
+    fn global_config() -> Config {
+        parse_config(
+            "[azure_devops]\norganization_url = \"https://dev.azure.com/org\"\npat = \"global-pat\"\n\n[branches]\nprotected = [\"main\"]\n\n[display]\nlang = \"de\"\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn project_config_overrides_shared_settings() {
+        let config = apply_project_config(
+            &global_config(),
+            "[azure_devops]\nproject = \"Web\"\n\n[branches]\nprotected = [\"main\", \"release/*\"]\nwork_item_min_digits = 4\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.azure_devops.project.as_deref(), Some("Web"));
+        assert_eq!(config.azure_devops.pat.as_deref(), Some("global-pat"));
+        assert_eq!(config.branches.protected, ["main", "release/*"]);
+        assert_eq!(config.branches.work_item_min_digits(), 4);
+        assert_eq!(config.display.lang, crate::config::Language::De);
+    }
+
+    #[test]
+    fn another_organization_does_not_get_the_global_pat() {
+        let config = apply_project_config(
+            &global_config(),
+            "[azure_devops]\norganization_url = \"https://dev.azure.com/other\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.azure_devops.organization_url,
+            "https://dev.azure.com/other"
+        );
+        assert_eq!(config.azure_devops.pat, None);
+        assert!(config.azure_devops.untrusted);
+
+        let config = apply_project_config(
+            &global_config(),
+            "[azure_devops]\norganization_url = \"https://dev.azure.com/org/\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.azure_devops.pat.as_deref(), Some("global-pat"));
+        assert!(!config.azure_devops.untrusted);
+    }
+
+    #[test]
+    fn an_untrusted_organization_gets_no_pat_from_the_environment() {
+        let other = "[azure_devops]\norganization_url = \"https://attacker.example/org\"\n";
+        let config = apply_project_config(&global_config(), other).unwrap();
+        assert_eq!(
+            config.resolve_pat_source(Some("env-pat".to_string())),
+            PatSource::Untrusted
+        );
+
+        let mut trusting = global_config();
+        trusting.azure_devops.trusted_organizations = vec!["https://attacker.example/org/".into()];
+        let config = apply_project_config(&trusting, other).unwrap();
+        assert_eq!(
+            config.resolve_pat_source(Some("env-pat".to_string())),
+            PatSource::Env
+        );
+        assert_eq!(config.resolve_pat_source(None), PatSource::Missing);
+    }
+
+    #[test]
+    fn project_config_rejects_personal_settings_and_secrets() {
+        for content in [
+            "[azure_devops]\npat = \"repo-pat\"\n",
+            "[azure_devops]\ntrusted_organizations = [\"https://attacker.example\"]\n",
+            "[http]\nca_bundle = \"certs.pem\"\n",
+            "[azure_devops]\norganization_url = \"dev.azure.com/org\"\n",
+            "[branches]\nprotected = [\"re:release/(\"]\n",
+        ] {
+            assert!(
+                apply_project_config(&global_config(), content).is_err(),
+                "{content}"
+            );
+        }
+    }
+
+    #[test]
+    fn an_invalid_project_config_is_an_error_not_the_defaults() {
+        let temp = TempDir::new().expect("temp dir should be created");
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        fs::write(
+            temp.path().join(PROJECT_CONFIG_FILE),
+            "[branches]\nprotected = [\"release/*\"\n",
+        )
+        .unwrap();
+
+        assert!(Config::load_optional_for_repo(temp.path()).is_err());
+    }
+
+    #[test]
+    fn finds_the_file_in_the_repository_root() {
+        let temp = TempDir::new().expect("temp dir should be created");
+        let nested = temp.path().join("src").join("app");
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        assert_eq!(find_project_config(&nested), None);
+
+        fs::write(temp.path().join(PROJECT_CONFIG_FILE), "[branches]\n").unwrap();
+        assert_eq!(
+            find_project_config(&nested),
+            Some(
+                temp.path()
+                    .canonicalize()
+                    .unwrap()
+                    .join(PROJECT_CONFIG_FILE)
+            )
+        );
+    }
+}
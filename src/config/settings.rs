@@ -1,6 +1,7 @@
 use anyhow::{Context, Result, bail};
-use directories::ProjectDirs;
+use directories::{BaseDirs, ProjectDirs};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 #[cfg(unix)]
 use std::io::Write;
@@ -8,16 +9,51 @@ use std::io::Write;
 use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
 use std::path::{Path, PathBuf};
 
+use super::keyring;
+use crate::azure_devops::DetailField;
+use crate::git::extract_work_item_number;
+use crate::output::verbose;
+use crate::pattern;
+use crate::timestamp;
+
 /// Default protected branch patterns (main/master)
 pub const DEFAULT_PROTECTED_PATTERNS: &[&str] = &["main", "master"];
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Default `cazdo commit-template` output: the title as subject, then the link
+pub const DEFAULT_COMMIT_TEMPLATE: &str = "{title}\n\nAB#{id}\n";
+
+/// Work items the TUI keeps in memory and in its cache by default
+pub const DEFAULT_CACHED_WORK_ITEMS: usize = 500;
+
+/// Days a cached work item is kept without being fetched again by default
+pub const DEFAULT_CACHE_MAX_AGE_DAYS: usize = 30;
+
+/// Work item requests in flight at once unless `[http].max_concurrent_requests` is set
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Azure DevOps REST API version used unless `[azure_devops].api_version` is set
+pub const DEFAULT_API_VERSION: &str = "7.0";
+
+/// WIQL `cazdo wi list` and the TUI's work item list run unless
+/// `[query].wiql` is set: open work items assigned to the user
+pub const DEFAULT_WIQL: &str = "SELECT [System.Id] FROM WorkItems \
+     WHERE [System.AssignedTo] = @Me \
+     AND [System.State] NOT IN ('Closed', 'Done', 'Removed') \
+     ORDER BY [System.ChangedDate] DESC";
+
+/// Placeholders available in `[commit].template`
+pub const COMMIT_TEMPLATE_PLACEHOLDERS: &[&str] = &["id", "title", "type", "state", "branch"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PatSource {
     Env,
     Config,
+    Keyring,
     Missing,
     InvalidEnvWhitespace,
     InvalidConfigWhitespace,
+    Untrusted,
 }
 
 enum PatResolution {
@@ -25,6 +61,48 @@ enum PatResolution {
     Missing,
     InvalidEnvWhitespace,
     InvalidConfigWhitespace,
+    Untrusted(String),
+}
+
+impl PatResolution {
+    fn into_pat(self) -> Result<String> {
+        match self {
+            Self::Valid { token, .. } => Ok(token),
+            Self::InvalidEnvWhitespace => {
+                bail!(
+                    "CAZDO_PAT is set but empty/whitespace. Set a valid token or unset CAZDO_PAT to use config value."
+                )
+            }
+            Self::InvalidConfigWhitespace => {
+                bail!(
+                    "Config value [azure_devops].pat is empty/whitespace. Set a valid token or remove the field."
+                )
+            }
+            Self::Missing => bail!(
+                "Azure DevOps PAT not found.\n\n\
+                You can set it in three ways (checked in order):\n\
+                1. Environment variable: export CAZDO_PAT=\"your-token\"\n\
+                2. Config file: Add 'pat = \"your-token\"' under [azure_devops] section in config.toml\n\
+                3. System keyring: run `cazdo config init` to store it there\n\n\
+                The PAT needs 'Work Items (Read)' permission."
+            ),
+            Self::Untrusted(url) => bail!(
+                "This repository's .cazdo.toml points cazdo at {url}, which is not trusted, so no PAT is sent there.\n\
+                If you trust it, add it to [azure_devops].trusted_organizations in the config file, e.g. with:\n\
+                cazdo config set azure_devops.trusted_organizations {url}"
+            ),
+        }
+    }
+
+    fn source(&self) -> PatSource {
+        match self {
+            Self::Valid { source, .. } => *source,
+            Self::Missing => PatSource::Missing,
+            Self::InvalidEnvWhitespace => PatSource::InvalidEnvWhitespace,
+            Self::InvalidConfigWhitespace => PatSource::InvalidConfigWhitespace,
+            Self::Untrusted(_) => PatSource::Untrusted,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -32,6 +110,24 @@ pub struct Config {
     pub azure_devops: AzureDevOpsConfig,
     #[serde(default)]
     pub branches: BranchConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub updates: UpdatesConfig,
+    #[serde(default)]
+    pub commit: CommitConfig,
+    #[serde(default)]
+    pub http: HttpConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub query: QueryConfig,
+    /// Command aliases, e.g. `bl = "branch list"`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub alias: BTreeMap<String, String>,
+    /// TUI key bindings by action, e.g. `open = ["o", "ctrl-o"]`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, KeyBinding>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +135,32 @@ pub struct AzureDevOpsConfig {
     pub organization_url: String,
     #[serde(default)]
     pub pat: Option<String>,
+    /// Project for boards, sprints, and queries, instead of the `origin` remote's
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Team whose board and sprint `cazdo board open` shows
+    #[serde(default)]
+    pub team: Option<String>,
+    /// REST API version sent with every request, e.g. "6.0" for an older
+    /// Azure DevOps Server
+    /// Default: "7.0"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+    /// Organization URLs a repository's `.cazdo.toml` may switch to; a PAT
+    /// is sent to no other organization a repository names
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_organizations: Vec<String>,
+    /// Set when a repository's `.cazdo.toml` names an organization that is
+    /// not trusted; no PAT is looked up for it
+    #[serde(skip)]
+    pub untrusted: bool,
+}
+
+impl AzureDevOpsConfig {
+    /// Get the API version, falling back to the default if not configured
+    pub fn api_version(&self) -> &str {
+        self.api_version.as_deref().unwrap_or(DEFAULT_API_VERSION)
+    }
 }
 
 impl Default for AzureDevOpsConfig {
@@ -46,6 +168,11 @@ impl Default for AzureDevOpsConfig {
         Self {
             organization_url: "https://dev.azure.com/your-organization".to_string(),
             pat: None,
+            project: None,
+            team: None,
+            api_version: None,
+            trusted_organizations: Vec::new(),
+            untrusted: false,
         }
     }
 }
@@ -59,7 +186,20 @@ impl Default for Config {
                     .iter()
                     .map(|s| s.to_string())
                     .collect(),
+                rules: Vec::new(),
+                work_item_min_digits: None,
+                work_item_ignore: Vec::new(),
+                work_item_regex: None,
+                fetch_on_start: false,
             },
+            display: DisplayConfig::default(),
+            updates: UpdatesConfig::default(),
+            commit: CommitConfig::default(),
+            http: HttpConfig::default(),
+            cache: CacheConfig::default(),
+            query: QueryConfig::default(),
+            alias: BTreeMap::new(),
+            keys: BTreeMap::new(),
         }
     }
 }
@@ -71,6 +211,44 @@ pub struct BranchConfig {
     /// Default: ["main", "master"]
     #[serde(default)]
     pub protected: Vec<String>,
+    /// Per-branch behavior, checked in order, e.g. a different pull request
+    /// target for `hotfix/*`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<BranchRule>,
+    /// Fewest digits of a work item number in a branch name, so `fix-ie11`
+    /// is not read as work item 11
+    /// Default: 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_item_min_digits: Option<usize>,
+    /// Patterns for the parts of a branch name whose number is never a work
+    /// item, such as `v*` for `v2-migration`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub work_item_ignore: Vec<String>,
+    /// Regular expression whose first capture group is the work item
+    /// number, such as `^(?:feature|bug)/(\d+)`; replaces the search for the
+    /// first number, `work_item_min_digits`, and `work_item_ignore`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_item_regex: Option<String>,
+    /// Whether the TUI runs `git fetch --prune origin` when it starts, so
+    /// branches deleted on origin show as gone
+    /// Default: false
+    #[serde(default)]
+    pub fetch_on_start: bool,
+}
+
+/// Behavior for branches matching `pattern`, from `[[branches.rules]]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BranchRule {
+    /// Branch pattern, with the same syntax as `protected`
+    pub pattern: String,
+    /// Whether the branch name links to a work item
+    /// Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_item: Option<bool>,
+    /// Target branch for `cazdo pr create` without `--target`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_target: Option<String>,
 }
 
 impl BranchConfig {
@@ -85,6 +263,299 @@ impl BranchConfig {
             self.protected.clone()
         }
     }
+
+    /// Add `pattern` after validating it, keeping the default patterns when
+    /// none were configured. Returns `false` if it was already protected.
+    pub fn add_protected(&mut self, pattern: &str) -> Result<bool> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            bail!("Protected branch pattern must not be empty");
+        }
+        pattern::validate_pattern(pattern)?;
+
+        let mut patterns = self.protected_patterns();
+        if patterns.iter().any(|known| known == pattern) {
+            return Ok(false);
+        }
+        patterns.push(pattern.to_string());
+        self.protected = patterns;
+        Ok(true)
+    }
+
+    /// Remove `pattern`. Returns `false` if it was not protected.
+    pub fn remove_protected(&mut self, pattern: &str) -> bool {
+        let pattern = pattern.trim();
+        let mut patterns = self.protected_patterns();
+        let before = patterns.len();
+        patterns.retain(|known| known != pattern);
+        if patterns.len() == before {
+            return false;
+        }
+        self.protected = patterns;
+        true
+    }
+
+    /// Whether a work item number is read from `branch_name`. The first rule
+    /// matching the branch that sets `work_item` decides.
+    pub fn links_work_item(&self, branch_name: &str) -> bool {
+        self.matching_rules(branch_name)
+            .find_map(|rule| rule.work_item)
+            .unwrap_or(true)
+    }
+
+    pub fn work_item_min_digits(&self) -> usize {
+        self.work_item_min_digits.unwrap_or(1).max(1)
+    }
+
+    /// The work item number in `branch_name`: what `work_item_regex`
+    /// captures, or else the first number with at least
+    /// `work_item_min_digits` digits whose part of the name no
+    /// `work_item_ignore` pattern matches. `None` when a rule turns work
+    /// items off for the branch.
+    pub fn work_item_number(&self, branch_name: &str) -> Option<u32> {
+        if !self.links_work_item(branch_name) {
+            return None;
+        }
+        if let Some(source) = &self.work_item_regex {
+            return pattern::first_capture(branch_name, source)?.parse().ok();
+        }
+        let min_digits = self.work_item_min_digits();
+        extract_work_item_number(branch_name, |digits, segment| {
+            digits.len() >= min_digits
+                && !self
+                    .work_item_ignore
+                    .iter()
+                    .any(|pattern| pattern::matches_pattern(segment, pattern))
+        })
+    }
+
+    /// The pull request target for `branch_name`, from the first matching
+    /// rule that sets `pr_target`.
+    pub fn pr_target(&self, branch_name: &str) -> Option<&str> {
+        self.matching_rules(branch_name)
+            .find_map(|rule| rule.pr_target.as_deref())
+    }
+
+    fn matching_rules(&self, branch_name: &str) -> impl Iterator<Item = &BranchRule> {
+        self.rules
+            .iter()
+            .filter(move |rule| pattern::matches_pattern(branch_name, &rule.pattern))
+    }
+}
+
+/// Display-related configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DisplayConfig {
+    /// Whether icons and rendered fields use emoji or ASCII fallbacks
+    /// Default: auto (ASCII on terminals known to mis-render emoji)
+    #[serde(default)]
+    pub emoji: EmojiMode,
+    /// Theme file for the TUI; `~/` and paths relative to the config
+    /// directory are resolved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Metadata fields in the TUI details header, in order: short names such
+    /// as "Iteration" or field reference names such as "Custom.Team"
+    /// Default: ["State", "Priority", "Severity", "AssignedTo", "Iteration",
+    /// "Area", "Tags"]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
+    /// Scheduling field shown as a badge next to the state: auto (the first
+    /// set of story points, effort, and original estimate), story_points,
+    /// effort, original_estimate, or off
+    /// Default: auto
+    #[serde(default)]
+    pub estimate: EstimateField,
+    /// Whether times are relative ("3 days ago") or absolute
+    /// Default: relative
+    #[serde(default)]
+    pub timestamps: TimestampStyle,
+    /// strftime format for absolute times
+    /// Default: "%Y-%m-%d %H:%M"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<String>,
+    /// Time zone for absolute times and dates: local or utc
+    /// Default: local
+    #[serde(default)]
+    pub timezone: TimeZoneMode,
+    /// Language of the TUI and command messages: en or de
+    /// Default: en
+    #[serde(default)]
+    pub lang: Language,
+    /// Screen-reader-friendly TUI: no borders, scrollbars, or emoji, and
+    /// text labels for what is otherwise shown by color or symbols
+    /// Default: false
+    #[serde(default)]
+    pub plain: bool,
+}
+
+/// Commit message configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CommitConfig {
+    /// Template for `cazdo commit-template`, with `{id}`, `{title}`, `{type}`,
+    /// `{state}`, and `{branch}` placeholders
+    /// Default: "{title}\n\nAB#{id}\n"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+impl CommitConfig {
+    /// Get the commit template, falling back to the default if not configured
+    pub fn template(&self) -> &str {
+        self.template.as_deref().unwrap_or(DEFAULT_COMMIT_TEMPLATE)
+    }
+}
+
+/// Release check configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UpdatesConfig {
+    /// Whether the TUI checks GitHub for a newer release once a week
+    /// Default: false
+    #[serde(default)]
+    pub check: bool,
+}
+
+/// HTTP client configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HttpConfig {
+    /// Whether requests use the proxy from `HTTPS_PROXY`/`HTTP_PROXY`,
+    /// honoring `NO_PROXY`
+    /// Default: auto
+    #[serde(default)]
+    pub proxy: ProxyMode,
+
+    /// PEM file of extra root certificates, for servers behind an internal
+    /// certificate authority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<String>,
+
+    /// Work item requests sent at once when prefetching in the TUI or
+    /// fetching several for a command, for servers that throttle bursts
+    /// Default: 4
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<usize>,
+}
+
+impl HttpConfig {
+    pub fn max_concurrent_requests(&self) -> usize {
+        self.max_concurrent_requests
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS)
+            .max(1)
+    }
+}
+
+/// TUI work item cache configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CacheConfig {
+    /// Work items kept in memory and in the cache directory; the least
+    /// recently viewed are dropped first
+    /// Default: 500
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_items: Option<usize>,
+    /// Days a cached work item is kept without being fetched again
+    /// Default: 30
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<usize>,
+}
+
+impl CacheConfig {
+    pub fn work_items(&self) -> usize {
+        self.work_items.unwrap_or(DEFAULT_CACHED_WORK_ITEMS)
+    }
+
+    pub fn max_age_days(&self) -> usize {
+        self.max_age_days
+            .unwrap_or(DEFAULT_CACHE_MAX_AGE_DAYS)
+            .max(1)
+    }
+}
+
+/// Work item list configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct QueryConfig {
+    /// WIQL query listed by `cazdo wi list` and the TUI's work item list
+    /// Default: open work items assigned to you, most recently changed first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wiql: Option<String>,
+}
+
+impl QueryConfig {
+    pub fn wiql(&self) -> &str {
+        self.wiql.as_deref().unwrap_or(DEFAULT_WIQL)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyMode {
+    #[default]
+    Auto,
+    Off,
+}
+
+/// Key chords for one TUI action: a single chord or a list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyBinding {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyBinding {
+    pub fn chords(&self) -> &[String] {
+        match self {
+            KeyBinding::One(chord) => std::slice::from_ref(chord),
+            KeyBinding::Many(chords) => chords,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EmojiMode {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// The scheduling field a process sizes work items with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateField {
+    #[default]
+    Auto,
+    /// Agile
+    StoryPoints,
+    /// Scrum and Basic
+    Effort,
+    /// Hours, as in CMMI
+    OriginalEstimate,
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampStyle {
+    #[default]
+    Relative,
+    Absolute,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeZoneMode {
+    #[default]
+    Local,
+    Utc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    De,
 }
 
 impl Config {
@@ -95,6 +566,25 @@ impl Config {
         Ok(proj_dirs.config_dir().join("config.toml"))
     }
 
+    /// Expand `~/` to the home directory and resolve relative paths against
+    /// the config directory, so a path such as `theme = "themes/dark.toml"`
+    /// sits next to `config.toml`.
+    pub fn resolve_path(path: &str) -> PathBuf {
+        if let Some(rest) = path.strip_prefix("~/")
+            && let Some(dirs) = BaseDirs::new()
+        {
+            return dirs.home_dir().join(rest);
+        }
+        let path = PathBuf::from(path);
+        if path.is_relative()
+            && let Ok(config_path) = Self::config_path()
+            && let Some(config_dir) = config_path.parent()
+        {
+            return config_dir.join(path);
+        }
+        path
+    }
+
     pub fn load() -> Result<Self> {
         let config_path = Self::config_path()?;
 
@@ -105,13 +595,12 @@ impl Config {
             );
         }
 
+        verbose!("config: loading {}", config_path.display());
         let content = fs::read_to_string(&config_path)
             .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
 
-        let config: Config = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {}", config_path.display()))?;
-
-        Ok(config)
+        parse_config(&content)
+            .with_context(|| format!("Failed to parse config file: {}", config_path.display()))
     }
 
     pub fn save(&self) -> Result<()> {
@@ -164,49 +653,44 @@ impl Config {
     }
 
     pub fn get_pat(&self) -> Result<String> {
-        // Read from actual environment or use fallback logic
-        self.resolve_pat(std::env::var("CAZDO_PAT").ok())
+        let pat = self.pat_resolution().into_pat()?;
+        crate::redact::register_secret(&pat);
+        Ok(pat)
     }
 
     pub fn pat_source(&self) -> PatSource {
-        self.resolve_pat_source(std::env::var("CAZDO_PAT").ok())
+        self.pat_resolution().source()
+    }
+
+    /// `CAZDO_PAT`, then `[azure_devops].pat`, then the system keyring.
+    fn pat_resolution(&self) -> PatResolution {
+        match self.resolve_pat_resolution(std::env::var("CAZDO_PAT").ok()) {
+            PatResolution::Missing => keyring::read_pat(&self.azure_devops.organization_url)
+                .map(|pat| pat.trim().to_string())
+                .filter(|pat| !pat.is_empty())
+                .map_or(PatResolution::Missing, |token| PatResolution::Valid {
+                    source: PatSource::Keyring,
+                    token,
+                }),
+            resolution => resolution,
+        }
     }
 
     /// Helper for tests to abstract env::var("CAZDO_PAT")
+    #[cfg(test)]
     fn resolve_pat(&self, env_pat: Option<String>) -> Result<String> {
-        match self.resolve_pat_resolution(env_pat) {
-            PatResolution::Valid { token, .. } => Ok(token),
-            PatResolution::InvalidEnvWhitespace => {
-                bail!(
-                    "CAZDO_PAT is set but empty/whitespace. Set a valid token or unset CAZDO_PAT to use config value."
-                )
-            }
-            PatResolution::InvalidConfigWhitespace => {
-                bail!(
-                    "Config value [azure_devops].pat is empty/whitespace. Set a valid token or remove the field."
-                )
-            }
-            PatResolution::Missing => anyhow::bail!(
-                "Azure DevOps PAT not found.\n\n\
-                You can set it in two ways (checked in order):\n\
-                1. Environment variable: export CAZDO_PAT=\"your-token\"\n\
-                2. Config file: Add 'pat = \"your-token\"' under [azure_devops] section in config.toml\n\n\
-                The PAT needs 'Work Items (Read)' permission."
-            ),
-        }
+        self.resolve_pat_resolution(env_pat).into_pat()
     }
 
-    /// Helper for status display and tests.
-    fn resolve_pat_source(&self, env_pat: Option<String>) -> PatSource {
-        match self.resolve_pat_resolution(env_pat) {
-            PatResolution::Valid { source, .. } => source,
-            PatResolution::Missing => PatSource::Missing,
-            PatResolution::InvalidEnvWhitespace => PatSource::InvalidEnvWhitespace,
-            PatResolution::InvalidConfigWhitespace => PatSource::InvalidConfigWhitespace,
-        }
+    #[cfg(test)]
+    pub(super) fn resolve_pat_source(&self, env_pat: Option<String>) -> PatSource {
+        self.resolve_pat_resolution(env_pat).source()
     }
 
     fn resolve_pat_resolution(&self, env_pat: Option<String>) -> PatResolution {
+        if self.azure_devops.untrusted {
+            return PatResolution::Untrusted(self.azure_devops.organization_url.clone());
+        }
         if let Some(pat) = env_pat {
             let trimmed = pat.trim();
             if trimmed.is_empty() {
@@ -233,6 +717,83 @@ impl Config {
     }
 }
 
+/// Parse config text. Errors name the line instead of quoting it, since the
+/// line may hold the PAT.
+pub(super) fn parse_config(content: &str) -> Result<Config> {
+    let config: Config = toml::from_str(content).map_err(|error| toml_error(content, &error))?;
+
+    for pattern in &config.branches.protected {
+        pattern::validate_pattern(pattern).context("Invalid [branches].protected")?;
+    }
+    for pattern in &config.branches.work_item_ignore {
+        pattern::validate_pattern(pattern).context("Invalid [branches].work_item_ignore")?;
+    }
+    if let Some(source) = &config.branches.work_item_regex {
+        pattern::validate_capture_regex(source).context("Invalid [branches].work_item_regex")?;
+    }
+    for rule in &config.branches.rules {
+        pattern::validate_pattern(&rule.pattern).context("Invalid [[branches.rules]] pattern")?;
+        if rule
+            .pr_target
+            .as_deref()
+            .is_some_and(|target| target.trim().is_empty())
+        {
+            bail!(
+                "Invalid [[branches.rules]] for '{}': pr_target must not be empty",
+                rule.pattern
+            );
+        }
+    }
+    if let Some(version) = &config.azure_devops.api_version {
+        validate_api_version(version).context("Invalid [azure_devops].api_version")?;
+    }
+    DetailField::parse_list(config.display.fields.as_deref())
+        .context("Invalid [display].fields")?;
+    if let Some(format) = &config.display.time_format {
+        timestamp::validate_time_format(format).context("Invalid [display].time_format")?;
+    }
+
+    Ok(config)
+}
+
+/// A TOML parse error with its line number, without quoting the offending
+/// line (which may hold the PAT).
+pub(super) fn toml_error(content: &str, error: &toml::de::Error) -> anyhow::Error {
+    let message = error.message().trim_end();
+    match error.span() {
+        Some(span) => {
+            let line = content[..span.start].matches('\n').count() + 1;
+            anyhow::anyhow!("line {}: {}", line, message)
+        }
+        None => anyhow::anyhow!("{}", message),
+    }
+}
+
+/// Check that `version` is a `major.minor` REST API version the client can
+/// use, 5.0 or later.
+pub(super) fn validate_api_version(version: &str) -> Result<()> {
+    let parsed = version
+        .split_once('.')
+        .and_then(|(major, minor)| Some((major.parse::<u32>().ok()?, minor.parse::<u32>().ok()?)));
+    match parsed {
+        Some((major, _)) if major >= 5 => Ok(()),
+        Some(_) => bail!("'{version}' is not supported; cazdo needs API version 5.0 or later"),
+        None => bail!("'{version}' is not a major.minor version such as 7.0 or 6.0"),
+    }
+}
+
+/// Check that `url` is an absolute http(s) URL, such as an organization or
+/// collection URL.
+pub fn validate_organization_url(url: &str) -> Result<()> {
+    if url.trim().is_empty() {
+        bail!("the organization URL is empty");
+    }
+    match reqwest::Url::parse(url.trim()) {
+        Ok(parsed) if matches!(parsed.scheme(), "https" | "http") && parsed.has_host() => Ok(()),
+        _ => bail!("'{url}' is not an http(s) URL such as https://dev.azure.com/your-organization"),
+    }
+}
+
 fn section_name(line: &str) -> Option<&str> {
     let trimmed = line.trim();
     if !trimmed.starts_with('[') {
@@ -340,14 +901,195 @@ mod tests {
         fs::metadata(path).unwrap().permissions().mode() & 0o777
     }
 
+    #[test]
+    fn parse_errors_do_not_quote_the_pat() {
+        let error = parse_config(
+            "[azure_devops]\norganization_url = \"https://dev.azure.com/org\"\npat = \"secret-token\n",
+        )
+        .expect_err("unterminated string");
+
+        let message = error.to_string();
+        assert!(message.starts_with("line 3: "), "{message}");
+        assert!(!message.contains("secret-token"), "{message}");
+    }
+
+    #[test]
+    fn add_and_remove_protected_keep_defaults_and_dedup() {
+        let mut branches = BranchConfig::default();
+
+        assert!(branches.add_protected(" releases/* ").unwrap());
+        assert!(!branches.add_protected("main").unwrap());
+        assert!(!branches.add_protected("releases/*").unwrap());
+        assert_eq!(branches.protected, vec!["main", "master", "releases/*"]);
+
+        assert!(branches.add_protected("re:release/(").is_err());
+        assert!(branches.add_protected("  ").is_err());
+
+        assert!(branches.remove_protected("master"));
+        assert!(!branches.remove_protected("develop"));
+        assert_eq!(branches.protected, vec!["main", "releases/*"]);
+    }
+
+    #[test]
+    fn branch_rules_apply_first_matching_setting() {
+        let config = parse_config(
+            r#"
+[azure_devops]
+organization_url = "https://dev.azure.com/org"
+
+[[branches.rules]]
+pattern = "hotfix/*"
+pr_target = "release/current"
+
+[[branches.rules]]
+pattern = "spike/*"
+work_item = false
+
+[[branches.rules]]
+pattern = "*"
+pr_target = "develop"
+"#,
+        )
+        .unwrap();
+        let branches = &config.branches;
+
+        assert_eq!(
+            branches.pr_target("hotfix/42-crash"),
+            Some("release/current")
+        );
+        assert_eq!(branches.pr_target("feature/7-login"), Some("develop"));
+        assert!(!branches.links_work_item("spike/123-try-grpc"));
+        assert!(branches.links_work_item("hotfix/42-crash"));
+
+        let error = parse_config(
+            "[azure_devops]\norganization_url = \"https://dev.azure.com/org\"\n[[branches.rules]]\npattern = \"x\"\nbase = \"main\"\n",
+        )
+        .expect_err("unknown rule field");
+        assert!(format!("{error:#}").contains("unknown field `base`"));
+    }
+
+    #[test]
+    fn work_item_number_skips_short_and_ignored_numbers() {
+        let config = parse_config(
+            r#"
+[azure_devops]
+organization_url = "https://dev.azure.com/org"
+
+[branches]
+work_item_min_digits = 3
+work_item_ignore = ["v*", "re:20\\d\\d"]
+
+[[branches.rules]]
+pattern = "spike/*"
+work_item = false
+"#,
+        )
+        .unwrap();
+        let branches = &config.branches;
+
+        assert_eq!(branches.work_item_number("fix-ie11"), None);
+        assert_eq!(branches.work_item_number("fix-ie11-4521"), Some(4521));
+        assert_eq!(branches.work_item_number("v200-migration-1234"), Some(1234));
+        assert_eq!(branches.work_item_number("2024-cleanup-310"), Some(310));
+        assert_eq!(branches.work_item_number("spike/4521-grpc"), None);
+        assert_eq!(
+            BranchConfig::default().work_item_number("fix-ie11"),
+            Some(11)
+        );
+
+        let error = parse_config(
+            "[azure_devops]\norganization_url = \"https://dev.azure.com/org\"\n[branches]\nwork_item_ignore = [\"re:(\"]\n",
+        )
+        .expect_err("invalid regex");
+        assert!(format!("{error:#}").starts_with("Invalid [branches].work_item_ignore"));
+    }
+
+    #[test]
+    fn work_item_regex_replaces_the_first_number() {
+        let config = parse_config(
+            r#"
+[azure_devops]
+organization_url = "https://dev.azure.com/org"
+
+[branches]
+work_item_regex = '^(?:feature|bugfix)/(\d+)'
+work_item_min_digits = 5
+
+[[branches.rules]]
+pattern = "feature/spike-*"
+work_item = false
+"#,
+        )
+        .unwrap();
+        let branches = &config.branches;
+
+        assert_eq!(branches.work_item_number("feature/123-login"), Some(123));
+        assert_eq!(branches.work_item_number("release/2024-sprint"), None);
+        assert_eq!(branches.work_item_number("feature/spike-12"), None);
+
+        let error = parse_config(
+            "[azure_devops]\norganization_url = \"https://dev.azure.com/org\"\n[branches]\nwork_item_regex = '\\d+'\n",
+        )
+        .expect_err("no capture group");
+        assert!(format!("{error:#}").starts_with("Invalid [branches].work_item_regex"));
+    }
+
+    #[test]
+    fn invalid_regex_protected_pattern_fails_to_parse() {
+        let error = parse_config(
+            "[azure_devops]\norganization_url = \"https://dev.azure.com/org\"\n[branches]\nprotected = [\"main\", \"re:release/(\"]\n",
+        )
+        .expect_err("invalid regex");
+
+        assert!(
+            format!("{error:#}")
+                .starts_with("Invalid [branches].protected: Invalid regex pattern 're:release/('")
+        );
+    }
+
+    #[test]
+    fn api_version_defaults_and_is_validated() {
+        let config =
+            parse_config("[azure_devops]\norganization_url = \"https://dev.azure.com/org\"\n")
+                .unwrap();
+        assert_eq!(config.azure_devops.api_version(), DEFAULT_API_VERSION);
+
+        let config = parse_config(
+            "[azure_devops]\norganization_url = \"https://server/tfs/Coll\"\napi_version = \"6.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.azure_devops.api_version(), "6.0");
+
+        let error = parse_config(
+            "[azure_devops]\norganization_url = \"https://server/tfs/Coll\"\napi_version = \"4.1\"\n",
+        )
+        .expect_err("too old");
+        assert_eq!(
+            format!("{error:#}"),
+            "Invalid [azure_devops].api_version: '4.1' is not supported; cazdo needs API version 5.0 or later"
+        );
+        assert!(validate_api_version("7").is_err());
+        assert!(validate_api_version("7.1-preview").is_err());
+    }
+
+    #[test]
+    fn validates_organization_urls() {
+        assert!(validate_organization_url("https://dev.azure.com/org").is_ok());
+        assert!(validate_organization_url("http://tfs:8080/tfs/DefaultCollection").is_ok());
+        assert!(validate_organization_url(" ").is_err());
+        assert!(validate_organization_url("dev.azure.com/org").is_err());
+        assert!(validate_organization_url("ftp://dev.azure.com/org").is_err());
+    }
+
     #[test]
     fn test_get_pat_precedence() {
         let config = Config {
             azure_devops: AzureDevOpsConfig {
                 organization_url: "https://dev.azure.com/test".to_string(),
                 pat: Some("config-pat".to_string()),
+                ..AzureDevOpsConfig::default()
             },
-            branches: BranchConfig::default(),
+            ..Config::default()
         };
 
         // Case 1: Env var set (should override config)
@@ -369,8 +1111,9 @@ mod tests {
             azure_devops: AzureDevOpsConfig {
                 organization_url: "https://dev.azure.com/test".to_string(),
                 pat: Some("config-pat".to_string()),
+                ..AzureDevOpsConfig::default()
             },
-            branches: BranchConfig::default(),
+            ..Config::default()
         };
 
         // Whitespace env is treated as invalid (no fallback)
@@ -384,8 +1127,9 @@ mod tests {
             azure_devops: AzureDevOpsConfig {
                 organization_url: "https://dev.azure.com/test".to_string(),
                 pat: Some("   ".to_string()),
+                ..AzureDevOpsConfig::default()
             },
-            branches: BranchConfig::default(),
+            ..Config::default()
         };
         assert!(config_whitespace.resolve_pat(None).is_err());
     }
@@ -396,8 +1140,9 @@ mod tests {
             azure_devops: AzureDevOpsConfig {
                 organization_url: "https://dev.azure.com/test".to_string(),
                 pat: Some("config-pat".to_string()),
+                ..AzureDevOpsConfig::default()
             },
-            branches: BranchConfig::default(),
+            ..Config::default()
         };
 
         assert_eq!(
@@ -414,8 +1159,9 @@ mod tests {
             azure_devops: AzureDevOpsConfig {
                 organization_url: "https://dev.azure.com/test".to_string(),
                 pat: None,
+                ..AzureDevOpsConfig::default()
             },
-            branches: BranchConfig::default(),
+            ..Config::default()
         };
         assert_eq!(no_pat_config.resolve_pat_source(None), PatSource::Missing);
 
@@ -423,8 +1169,9 @@ mod tests {
             azure_devops: AzureDevOpsConfig {
                 organization_url: "https://dev.azure.com/test".to_string(),
                 pat: Some("   ".to_string()),
+                ..AzureDevOpsConfig::default()
             },
-            branches: BranchConfig::default(),
+            ..Config::default()
         };
         assert_eq!(
             whitespace_config.resolve_pat_source(None),
@@ -438,8 +1185,9 @@ mod tests {
             azure_devops: AzureDevOpsConfig {
                 organization_url: "https://dev.azure.com/test".to_string(),
                 pat: None,
+                ..AzureDevOpsConfig::default()
             },
-            branches: BranchConfig::default(),
+            ..Config::default()
         };
 
         let pat = config.resolve_pat(Some("env-pat".to_string())).unwrap();
@@ -452,8 +1200,9 @@ mod tests {
             azure_devops: AzureDevOpsConfig {
                 organization_url: "https://dev.azure.com/test".to_string(),
                 pat: None,
+                ..AzureDevOpsConfig::default()
             },
-            branches: BranchConfig::default(),
+            ..Config::default()
         };
 
         assert!(config.resolve_pat(None).is_err());
@@ -3,8 +3,9 @@ mod fixture;
 mod repo;
 
 #[cfg(test)]
-pub use fixture::FixtureGitRepo;
+pub use fixture::{FIXTURE_COMMIT_SHA, FixtureGitRepo};
 pub use repo::{
-    BranchOrder, BranchScope, BranchStatus, DeleteResult, GitRepo, RemoteStatus, RepoBranch,
-    compare_branch_order, extract_work_item_number, list_origin_remote_heads_in_dir, short_sha,
+    BranchOrder, BranchScope, BranchStatus, DeleteResult, FetchProgress, GitRepo, RemoteDeletion,
+    RemoteStatus, RepoBranch, compare_branch_order, extract_work_item_number, fetch_origin_in_dir,
+    hooks_dir_in_dir, list_origin_remote_heads_in_dir, origin_remote_url_in_dir, short_sha,
 };
@@ -1,14 +1,28 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 use anyhow::{Result, anyhow, bail};
 
-use super::repo::{BranchScope, BranchStatus, DeleteResult, GitBackend, RepoBranch};
+use super::repo::{
+    BranchScope, BranchStatus, DeleteResult, GitBackend, RemoteDeletion, RemoteStatus, RepoBranch,
+    compare_branch_order,
+};
 
-/// In-memory `GitRepo` backend for tests: returns preset checkout/delete/prune
-/// outcomes. Ops it isn't configured for (branch listing, status, freshness) are
-/// unsupported and error.
+const ORIGIN: &str = "origin";
+
+/// Commit every fixture branch points at
+pub const FIXTURE_COMMIT_SHA: &str = "abcdef1234567890abcdef1234567890abcdef12";
+
+/// In-memory `GitRepo` backend for tests. Branches added with
+/// [`FixtureGitRepo::with_local_branch`] and [`FixtureGitRepo::with_remote_branch`]
+/// behave like a small repository: checkout moves the current branch
+/// (creating a local branch for a remote one), delete and prune remove
+/// branches. A preset result replaces that behavior for its operation.
 #[derive(Default)]
 pub struct FixtureGitRepo {
+    branches: RefCell<Vec<RepoBranch>>,
+    merged: HashSet<String>,
     checkout_result: Option<Result<(), String>>,
     delete_result: Option<Result<DeleteResult, String>>,
     prune_result: Option<Result<(), String>>,
@@ -19,6 +33,43 @@ impl FixtureGitRepo {
         Self::default()
     }
 
+    pub fn with_local_branch(self, name: &str, is_current: bool) -> Self {
+        self.branches.borrow_mut().push(local(name, is_current));
+        self
+    }
+
+    pub fn with_remote_branch(self, name: &str) -> Self {
+        self.branches.borrow_mut().push(RepoBranch {
+            key: format!("refs/remotes/{ORIGIN}/{name}"),
+            display_name: format!("{ORIGIN}/{name}"),
+            branch_name: name.to_string(),
+            remote_name: Some(ORIGIN.to_string()),
+            scope: BranchScope::Remote,
+            is_current: false,
+            origin_upstream: None,
+        });
+        self
+    }
+
+    /// Have local branch `name` track `upstream` on origin.
+    pub fn with_upstream(self, name: &str, upstream: &str) -> Self {
+        if let Some(branch) = self
+            .branches
+            .borrow_mut()
+            .iter_mut()
+            .find(|branch| branch.scope == BranchScope::Local && branch.branch_name == name)
+        {
+            branch.origin_upstream = Some(upstream.to_string());
+        }
+        self
+    }
+
+    /// Report local branch `name` as merged into the default branch.
+    pub fn with_merged_branch(mut self, name: &str) -> Self {
+        self.merged.insert(name.to_string());
+        self
+    }
+
     pub fn with_checkout_result(mut self, result: Result<(), String>) -> Self {
         self.checkout_result = Some(result);
         self
@@ -33,54 +84,166 @@ impl FixtureGitRepo {
         self.prune_result = Some(result);
         self
     }
+
+    fn position(&self, scope: BranchScope, branch_name: &str) -> Result<usize> {
+        self.branches
+            .borrow()
+            .iter()
+            .position(|branch| branch.scope == scope && branch.branch_name == branch_name)
+            .ok_or_else(|| anyhow!("fixture git repo: no branch '{branch_name}'"))
+    }
 }
 
 impl GitBackend for FixtureGitRepo {
     fn list_branches(&self) -> Result<Vec<RepoBranch>> {
-        bail!("fixture git repo: list_branches unsupported")
+        let mut branches = self.branches.borrow().clone();
+        branches.sort_by(compare_branch_order);
+        Ok(branches)
     }
 
     fn get_branch_status(
         &self,
-        _scope: BranchScope,
-        _branch_name: &str,
+        scope: BranchScope,
+        branch_name: &str,
         _remote_name: Option<&str>,
     ) -> Result<BranchStatus> {
-        bail!("fixture git repo: get_branch_status unsupported")
+        self.position(scope, branch_name)?;
+        Ok(BranchStatus {
+            remote_status: match scope {
+                BranchScope::Local => RemoteStatus::LocalOnly,
+                BranchScope::Remote => RemoteStatus::RemoteTracking,
+            },
+            last_commit_sha: Some(FIXTURE_COMMIT_SHA.to_string()),
+            last_commit_author: None,
+            last_commit_time: None,
+        })
     }
 
     fn checkout_branch(
         &self,
-        _scope: BranchScope,
-        _branch_name: &str,
+        scope: BranchScope,
+        branch_name: &str,
         _remote_name: Option<&str>,
     ) -> Result<()> {
-        preset("checkout_branch", &self.checkout_result)
+        if self.checkout_result.is_some() {
+            return preset("checkout_branch", &self.checkout_result);
+        }
+
+        self.position(scope, branch_name)?;
+        if self.position(BranchScope::Local, branch_name).is_err() {
+            self.branches.borrow_mut().push(local(branch_name, false));
+        }
+        for branch in self.branches.borrow_mut().iter_mut() {
+            branch.is_current =
+                branch.scope == BranchScope::Local && branch.branch_name == branch_name;
+        }
+        Ok(())
     }
 
     fn delete_branch(
         &self,
-        _scope: BranchScope,
-        _branch_name: &str,
+        scope: BranchScope,
+        branch_name: &str,
         _remote_name: Option<&str>,
+        with_remote: bool,
     ) -> Result<DeleteResult> {
-        match &self.delete_result {
-            Some(Ok(result)) => Ok(result.clone()),
-            Some(Err(message)) => Err(anyhow!(message.clone())),
-            None => bail!("fixture git repo: no delete result configured"),
+        if let Some(result) = &self.delete_result {
+            return match result {
+                Ok(result) => Ok(result.clone()),
+                Err(message) => Err(anyhow!(message.clone())),
+            };
+        }
+
+        let index = self.position(scope, branch_name)?;
+        match scope {
+            BranchScope::Local => {
+                let branch = self.branches.borrow()[index].clone();
+                if branch.is_current {
+                    bail!("Cannot delete the currently checked out branch '{branch_name}'");
+                }
+                let upstream = match (with_remote, branch.origin_upstream) {
+                    (false, _) => None,
+                    (true, Some(upstream)) => Some(upstream),
+                    (true, None) => bail!("'{branch_name}' does not track a branch on {ORIGIN}"),
+                };
+                self.branches.borrow_mut().remove(index);
+                // Unlike deleting a remote branch alone, the push also drops
+                // the remote-tracking ref
+                let remote = upstream.map(|upstream| RemoteDeletion {
+                    remote_ref: format!("{ORIGIN}/{upstream}"),
+                    result: self
+                        .position(BranchScope::Remote, &upstream)
+                        .map(|index| {
+                            self.branches.borrow_mut().remove(index);
+                        })
+                        .map_err(|error| error.to_string()),
+                });
+                Ok(DeleteResult::Local {
+                    commit_sha: FIXTURE_COMMIT_SHA.to_string(),
+                    remote,
+                })
+            }
+            // Like a push that deletes the branch on origin: the
+            // remote-tracking ref stays until it is pruned.
+            BranchScope::Remote => Ok(DeleteResult::Remote),
         }
     }
 
-    fn prune_remote_tracking_branch(&self, _branch_name: &str) -> Result<()> {
-        preset("prune_remote_tracking_branch", &self.prune_result)
+    fn prune_remote_tracking_branch(&self, branch_name: &str) -> Result<()> {
+        if self.prune_result.is_some() {
+            return preset("prune_remote_tracking_branch", &self.prune_result);
+        }
+
+        let index = self.position(BranchScope::Remote, branch_name)?;
+        self.branches.borrow_mut().remove(index);
+        Ok(())
+    }
+
+    fn restore_branch(&self, branch_name: &str, _commit_sha: &str) -> Result<()> {
+        if self.position(BranchScope::Local, branch_name).is_ok() {
+            bail!("Branch '{branch_name}' already exists");
+        }
+        self.branches.borrow_mut().push(local(branch_name, false));
+        Ok(())
+    }
+
+    fn merged_branches(&self) -> Result<HashSet<String>> {
+        Ok(self
+            .merged
+            .iter()
+            .filter(|name| self.position(BranchScope::Local, name).is_ok())
+            .cloned()
+            .collect())
     }
 
     fn repo_dir(&self) -> Result<PathBuf> {
         bail!("fixture git repo: repo_dir unsupported")
     }
 
+    fn origin_upstream(&self, branch_name: &str) -> Result<Option<String>> {
+        let index = self.position(BranchScope::Local, branch_name)?;
+        Ok(self.branches.borrow()[index].origin_upstream.clone())
+    }
+
     fn current_local_branch_name(&self) -> Result<Option<String>> {
-        Ok(None)
+        Ok(self
+            .branches
+            .borrow()
+            .iter()
+            .find(|branch| branch.is_current)
+            .map(|branch| branch.branch_name.clone()))
+    }
+}
+
+fn local(name: &str, is_current: bool) -> RepoBranch {
+    RepoBranch {
+        key: format!("refs/heads/{name}"),
+        display_name: name.to_string(),
+        branch_name: name.to_string(),
+        remote_name: None,
+        scope: BranchScope::Local,
+        is_current,
+        origin_upstream: None,
     }
 }
 
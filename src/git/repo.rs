@@ -1,22 +1,44 @@
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 
 use anyhow::{Context, Result};
 use git2::{BranchType, Repository};
+use tokio::io::AsyncReadExt;
 
+use crate::i18n::{Text, tf};
 use crate::pattern::is_protected;
 
 const ORIGIN_REMOTE: &str = "origin";
 
-/// Extract the first number from a branch name (work item number)
-pub fn extract_work_item_number(branch_name: &str) -> Option<u32> {
-    let start = branch_name.find(|c: char| c.is_ascii_digit())?;
-    let num_str: String = branch_name[start..]
-        .chars()
-        .take_while(|c| c.is_ascii_digit())
-        .collect();
-    num_str.parse().ok()
+/// Extract the first number from a branch name (work item number) that
+/// `accept` takes. It is given the digits and the part of the name they are
+/// in, between `/`, `-`, `_`, and `.`: `("11", "ie11")` for `fix-ie11`.
+pub fn extract_work_item_number(
+    branch_name: &str,
+    mut accept: impl FnMut(&str, &str) -> bool,
+) -> Option<u32> {
+    let is_separator = |c: char| matches!(c, '/' | '-' | '_' | '.');
+    let mut rest = 0;
+    while let Some(offset) = branch_name[rest..].find(|c: char| c.is_ascii_digit()) {
+        let start = rest + offset;
+        let end = branch_name[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(branch_name.len(), |len| start + len);
+        let segment_start = branch_name[..start]
+            .rfind(is_separator)
+            .map_or(0, |index| index + 1);
+        let segment_end = branch_name[end..]
+            .find(is_separator)
+            .map_or(branch_name.len(), |len| end + len);
+
+        let digits = &branch_name[start..end];
+        if accept(digits, &branch_name[segment_start..segment_end]) {
+            return digits.parse().ok();
+        }
+        rest = end;
+    }
+    None
 }
 
 /// Safely get the short SHA (first 7 characters)
@@ -44,6 +66,10 @@ pub struct RepoBranch {
     pub remote_name: Option<String>,
     pub scope: BranchScope,
     pub is_current: bool,
+    /// For a local branch, the branch on origin it tracks: `feature/1` for
+    /// an upstream of `origin/feature/1`. `None` without an upstream or with
+    /// one on another remote.
+    pub origin_upstream: Option<String>,
 }
 
 /// Remote tracking status for a branch
@@ -69,16 +95,30 @@ pub enum RemoteStatus {
 #[derive(Debug, Clone)]
 pub struct BranchStatus {
     pub remote_status: RemoteStatus,
+    pub last_commit_sha: Option<String>,
     pub last_commit_author: Option<String>,
     pub last_commit_time: Option<i64>, // Unix timestamp
 }
 
 #[derive(Debug, Clone)]
 pub enum DeleteResult {
-    Local { commit_sha: String },
+    Local {
+        commit_sha: String,
+        /// The branch on origin, if it was asked to go too
+        remote: Option<RemoteDeletion>,
+    },
     Remote,
 }
 
+/// Deleting `origin/<branch>` along with the local branch, which stays
+/// deleted when this fails.
+#[derive(Debug, Clone)]
+pub struct RemoteDeletion {
+    /// `origin/<branch>`
+    pub remote_ref: String,
+    pub result: Result<(), String>,
+}
+
 /// Branch fields needed to order branch lists: locals first, the current
 /// branch first within locals, then by display name.
 pub trait BranchOrder {
@@ -135,8 +175,13 @@ pub(crate) trait GitBackend {
         scope: BranchScope,
         branch_name: &str,
         remote_name: Option<&str>,
+        with_remote: bool,
     ) -> Result<DeleteResult>;
     fn prune_remote_tracking_branch(&self, branch_name: &str) -> Result<()>;
+    fn restore_branch(&self, branch_name: &str, commit_sha: &str) -> Result<()>;
+    fn merged_branches(&self) -> Result<HashSet<String>>;
+    /// The branch on origin that local branch `branch_name` tracks, if any.
+    fn origin_upstream(&self, branch_name: &str) -> Result<Option<String>>;
     fn repo_dir(&self) -> Result<PathBuf>;
     fn current_local_branch_name(&self) -> Result<Option<String>>;
 }
@@ -148,6 +193,13 @@ impl GitRepo {
         })
     }
 
+    /// Open the repository containing `dir`, e.g. on a worker thread.
+    pub fn open_dir(dir: &Path) -> Result<Self> {
+        Ok(Self {
+            backend: Box::new(LiveGitRepo::open_dir(dir)?),
+        })
+    }
+
     #[cfg(test)]
     pub fn fixture(fixture: super::fixture::FixtureGitRepo) -> Self {
         Self {
@@ -175,25 +227,76 @@ impl GitRepo {
         branch_name: &str,
         remote_name: Option<&str>,
     ) -> Result<()> {
+        tracing::info!(?scope, branch_name, ?remote_name, "git checkout");
         self.backend
             .checkout_branch(scope, branch_name, remote_name)
+            .inspect_err(|error| tracing::warn!(branch_name, error = %error, "git checkout failed"))
     }
 
+    /// Delete a branch; a local one `with_remote` also has the branch it
+    /// tracks on origin deleted there afterwards, which the result reports.
+    /// Without such an upstream, nothing is deleted.
     pub fn delete_branch(
         &self,
         scope: BranchScope,
         branch_name: &str,
         remote_name: Option<&str>,
+        with_remote: bool,
         protected_patterns: &[String],
     ) -> Result<DeleteResult> {
         if is_protected(branch_name, protected_patterns) {
-            anyhow::bail!("Cannot delete protected branch '{}'", branch_name);
+            anyhow::bail!(
+                "{}",
+                tf(Text::CannotDeleteProtected, &[("branch", branch_name)])
+            );
         }
-        self.backend.delete_branch(scope, branch_name, remote_name)
+        // The push deletes the tracked branch, which may have another name
+        if with_remote
+            && scope == BranchScope::Local
+            && let Some(upstream) = self.backend.origin_upstream(branch_name)?
+            && is_protected(&upstream, protected_patterns)
+        {
+            anyhow::bail!(
+                "{}",
+                tf(
+                    Text::CannotDeleteProtectedOnOrigin,
+                    &[("branch", &upstream)]
+                )
+            );
+        }
+        tracing::info!(
+            ?scope,
+            branch_name,
+            ?remote_name,
+            with_remote,
+            "git delete branch"
+        );
+        self.backend
+            .delete_branch(scope, branch_name, remote_name, with_remote)
+            .inspect_err(|error| tracing::warn!(branch_name, error = %error, "git delete failed"))
     }
 
     pub fn prune_remote_tracking_branch(&self, branch_name: &str) -> Result<()> {
-        self.backend.prune_remote_tracking_branch(branch_name)
+        tracing::info!(branch_name, "git prune remote-tracking branch");
+        self.backend
+            .prune_remote_tracking_branch(branch_name)
+            .inspect_err(|error| tracing::warn!(branch_name, error = %error, "git prune failed"))
+    }
+
+    /// Create local branch `branch_name` at `commit_sha` again after it was
+    /// deleted. Fails if the branch exists or the commit is gone.
+    pub fn restore_branch(&self, branch_name: &str, commit_sha: &str) -> Result<()> {
+        tracing::info!(branch_name, commit_sha, "git restore branch");
+        self.backend
+            .restore_branch(branch_name, commit_sha)
+            .inspect_err(|error| tracing::warn!(branch_name, error = %error, "git restore failed"))
+    }
+
+    /// Names of the local branches whose commits are all on origin's default
+    /// branch, which is left out. Fails when origin's default branch is
+    /// unknown.
+    pub fn merged_branches(&self) -> Result<HashSet<String>> {
+        self.backend.merged_branches()
     }
 
     pub fn repo_dir(&self) -> Result<PathBuf> {
@@ -217,7 +320,12 @@ enum ExistingLocalBranchAction {
 impl LiveGitRepo {
     /// Open the git repository in the current directory
     pub fn open_current_dir() -> Result<Self> {
-        let repo = Repository::discover(".")
+        Self::open_dir(Path::new("."))
+    }
+
+    /// Open the git repository containing `dir`
+    pub fn open_dir(dir: &Path) -> Result<Self> {
+        let repo = Repository::discover(dir)
             .context("Not a git repository (or any of the parent directories)")?;
         Ok(Self { repo })
     }
@@ -244,6 +352,7 @@ impl GitBackend for LiveGitRepo {
                     remote_name: None,
                     scope: BranchScope::Local,
                     is_current: current.as_ref().is_some_and(|c| c == name),
+                    origin_upstream: origin_upstream(&branch),
                 });
             }
         }
@@ -269,6 +378,7 @@ impl GitBackend for LiveGitRepo {
                 remote_name: Some(ORIGIN_REMOTE.to_string()),
                 scope: BranchScope::Remote,
                 is_current: false,
+                origin_upstream: None,
             });
         }
 
@@ -311,9 +421,29 @@ impl GitBackend for LiveGitRepo {
         scope: BranchScope,
         branch_name: &str,
         remote_name: Option<&str>,
+        with_remote: bool,
     ) -> Result<DeleteResult> {
         match scope {
-            BranchScope::Local => self.delete_local_branch(branch_name),
+            BranchScope::Local => {
+                // Only the branch the local one tracks is deleted on origin,
+                // never another one that merely has the same name
+                let upstream = if with_remote {
+                    let branch = self
+                        .repo
+                        .find_branch(branch_name, BranchType::Local)
+                        .with_context(|| format!("Local branch '{}' not found", branch_name))?;
+                    Some(origin_upstream(&branch).with_context(|| {
+                        format!("'{branch_name}' does not track a branch on {ORIGIN_REMOTE}")
+                    })?)
+                } else {
+                    None
+                };
+                let commit_sha = self.delete_local_branch(branch_name)?;
+                Ok(DeleteResult::Local {
+                    commit_sha,
+                    remote: upstream.map(|upstream| self.delete_origin_branch(&upstream)),
+                })
+            }
             BranchScope::Remote => self.delete_remote_branch(branch_name, remote_name),
         }
     }
@@ -342,10 +472,71 @@ impl GitBackend for LiveGitRepo {
         Ok(())
     }
 
+    fn restore_branch(&self, branch_name: &str, commit_sha: &str) -> Result<()> {
+        if self
+            .repo
+            .find_branch(branch_name, BranchType::Local)
+            .is_ok()
+        {
+            anyhow::bail!("Branch '{branch_name}' already exists");
+        }
+        let commit = git2::Oid::from_str(commit_sha)
+            .and_then(|oid| self.repo.find_commit(oid))
+            .with_context(|| {
+                format!(
+                    "Commit {} of '{branch_name}' no longer exists",
+                    short_sha(commit_sha)
+                )
+            })?;
+        self.repo
+            .branch(branch_name, &commit, false)
+            .with_context(|| format!("Failed to create branch '{branch_name}'"))?;
+        Ok(())
+    }
+
+    fn merged_branches(&self) -> Result<HashSet<String>> {
+        let Some((default_branch, base)) = self.origin_default_branch() else {
+            anyhow::bail!(
+                "Cannot tell which branch is {ORIGIN_REMOTE}'s default branch. Run `git remote set-head {ORIGIN_REMOTE} -a` and try again."
+            );
+        };
+        let mut merged = HashSet::new();
+        let local_iter = self
+            .repo
+            .branches(Some(BranchType::Local))
+            .context("Failed to list local branches")?;
+        for branch_result in local_iter {
+            let (branch, _) = branch_result.context("Failed to read local branch")?;
+            let (Some(name), Some(tip)) = (branch.name().ok().flatten(), branch.get().target())
+            else {
+                continue;
+            };
+            if name == default_branch {
+                continue;
+            }
+            let is_merged = tip == base
+                || self.repo.graph_descendant_of(base, tip).with_context(|| {
+                    format!("Failed to compare '{name}' with '{default_branch}'")
+                })?;
+            if is_merged {
+                merged.insert(name.to_string());
+            }
+        }
+        Ok(merged)
+    }
+
     fn repo_dir(&self) -> Result<PathBuf> {
         Ok(self.command_dir()?.to_path_buf())
     }
 
+    fn origin_upstream(&self, branch_name: &str) -> Result<Option<String>> {
+        let branch = self
+            .repo
+            .find_branch(branch_name, BranchType::Local)
+            .with_context(|| format!("Local branch '{}' not found", branch_name))?;
+        Ok(origin_upstream(&branch))
+    }
+
     fn current_local_branch_name(&self) -> Result<Option<String>> {
         current_local_branch_name(&self.repo)
     }
@@ -359,17 +550,42 @@ impl LiveGitRepo {
             .context("Failed to determine repository working directory")
     }
 
+    /// The branch `origin/HEAD` points at, else `main` or `master` on origin,
+    /// with its commit.
+    fn origin_default_branch(&self) -> Option<(String, git2::Oid)> {
+        let head = format!("refs/remotes/{ORIGIN_REMOTE}/HEAD");
+        let target = self
+            .repo
+            .find_reference(&head)
+            .ok()
+            .and_then(|reference| reference.symbolic_target().map(str::to_string));
+        let candidates = target
+            .into_iter()
+            .chain(["main", "master"].map(|name| format!("refs/remotes/{ORIGIN_REMOTE}/{name}")));
+        for ref_name in candidates {
+            if let Ok(reference) = self.repo.find_reference(&ref_name)
+                && let Some(oid) = reference.target()
+            {
+                let prefix = format!("refs/remotes/{ORIGIN_REMOTE}/");
+                let name = ref_name.strip_prefix(&prefix).unwrap_or(&ref_name);
+                return Some((name.to_string(), oid));
+            }
+        }
+        None
+    }
+
     fn get_local_branch_status(&self, branch_name: &str) -> Result<BranchStatus> {
         let branch = self
             .repo
             .find_branch(branch_name, BranchType::Local)
             .with_context(|| format!("Branch '{}' not found", branch_name))?;
 
-        let (last_commit_author, last_commit_time) = last_commit_details(&branch);
+        let (last_commit_sha, last_commit_author, last_commit_time) = last_commit_details(&branch);
         let remote_status = self.get_remote_status(&branch);
 
         Ok(BranchStatus {
             remote_status,
+            last_commit_sha,
             last_commit_author,
             last_commit_time,
         })
@@ -386,9 +602,13 @@ impl LiveGitRepo {
             .find_branch(&format!("{remote_name}/{branch_name}"), BranchType::Remote)
             .with_context(|| format!("Remote branch '{remote_name}/{branch_name}' not found"))?;
 
-        let (last_commit_author, last_commit_time) = last_commit_details(&branch);
+        let (last_commit_sha, last_commit_author, last_commit_time) = last_commit_details(&branch);
 
-        Ok(remote_branch_status(last_commit_author, last_commit_time))
+        Ok(remote_branch_status(
+            last_commit_sha,
+            last_commit_author,
+            last_commit_time,
+        ))
     }
 
     fn get_remote_status(&self, branch: &git2::Branch) -> RemoteStatus {
@@ -614,7 +834,8 @@ impl LiveGitRepo {
         Ok(None)
     }
 
-    fn delete_local_branch(&self, branch_name: &str) -> Result<DeleteResult> {
+    /// Delete local branch `branch_name`, returning the commit it pointed at.
+    fn delete_local_branch(&self, branch_name: &str) -> Result<String> {
         if self.current_local_branch_name()?.as_deref() == Some(branch_name) {
             anyhow::bail!("Cannot delete the current branch");
         }
@@ -635,7 +856,22 @@ impl LiveGitRepo {
             .delete()
             .with_context(|| format!("Failed to delete branch '{}'", branch_name))?;
 
-        Ok(DeleteResult::Local { commit_sha })
+        Ok(commit_sha)
+    }
+
+    /// Delete `branch_name` on origin after its local branch, dropping the
+    /// remote-tracking ref if the push left it.
+    fn delete_origin_branch(&self, branch_name: &str) -> RemoteDeletion {
+        let remote_ref = format!("{ORIGIN_REMOTE}/{branch_name}");
+        let result = self
+            .push_delete(ORIGIN_REMOTE, branch_name)
+            .map(|()| {
+                if let Ok(mut tracking) = self.repo.find_branch(&remote_ref, BranchType::Remote) {
+                    let _ = tracking.delete();
+                }
+            })
+            .map_err(|error| format!("{error:#}"));
+        RemoteDeletion { remote_ref, result }
     }
 
     fn delete_remote_branch(
@@ -657,6 +893,12 @@ impl LiveGitRepo {
             );
         }
 
+        self.push_delete(remote_name, branch_name)?;
+        Ok(DeleteResult::Remote)
+    }
+
+    /// Push the deletion of `branch_name` to `remote_name`.
+    fn push_delete(&self, remote_name: &str, branch_name: &str) -> Result<()> {
         let output = Command::new("git")
             .args(["push", remote_name, "--delete", branch_name])
             .current_dir(self.command_dir()?)
@@ -670,13 +912,14 @@ impl LiveGitRepo {
             let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
             let message = if !stderr.is_empty() { stderr } else { stdout };
             anyhow::bail!(
-                "Failed to delete remote branch '{}': {}",
-                remote_ref_name,
+                "Failed to delete remote branch '{}/{}': {}",
+                remote_name,
+                branch_name,
                 message
             );
         }
 
-        Ok(DeleteResult::Remote)
+        Ok(())
     }
 
     fn current_branch_tracks_remote(&self, remote_ref_name: &str) -> Result<bool> {
@@ -710,6 +953,39 @@ fn current_local_branch_name(repo: &Repository) -> Result<Option<String>> {
     Ok(Some(branch_name))
 }
 
+/// URL of the `origin` remote of the repository containing `dir`.
+pub fn origin_remote_url_in_dir(dir: &Path) -> Result<String> {
+    let repo = Repository::discover(dir)
+        .context("Not a git repository (or any of the parent directories)")?;
+    let remote = repo
+        .find_remote(ORIGIN_REMOTE)
+        .context("No 'origin' remote configured")?;
+
+    remote
+        .url()
+        .map(str::to_string)
+        .context("The 'origin' remote URL is not valid UTF-8")
+}
+
+/// Hooks directory of the repository containing `dir`, as git resolves it:
+/// `core.hooksPath` if set, otherwise the main repository's `hooks` (shared
+/// by worktrees).
+pub fn hooks_dir_in_dir(dir: &Path) -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "hooks"])
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git rev-parse --git-path hooks")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        anyhow::bail!("Failed to locate the hooks directory: {}", stderr);
+    }
+
+    // Relative paths are relative to `dir`.
+    Ok(dir.join(String::from_utf8_lossy(&output.stdout).trim()))
+}
+
 pub fn list_origin_remote_heads_in_dir(dir: &Path) -> Result<HashSet<String>> {
     let output = Command::new("git")
         .args(["ls-remote", "--heads", ORIGIN_REMOTE])
@@ -729,17 +1005,157 @@ pub fn list_origin_remote_heads_in_dir(dir: &Path) -> Result<HashSet<String>> {
     )))
 }
 
-fn last_commit_details(branch: &git2::Branch) -> (Option<String>, Option<i64>) {
+/// A progress line of `git fetch --progress`, such as
+/// `Receiving objects:  45% (9/20)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchProgress {
+    pub phase: String,
+    pub percent: u8,
+}
+
+/// The ssh command git would use in `dir`, told never to prompt: ssh asks
+/// for a passphrase or to accept a host key on the terminal itself, which
+/// `GIT_TERMINAL_PROMPT` does not stop. `None` leaves git's choice alone:
+/// a `GIT_SSH` program such as plink, or a wrapper that may not take `-o`.
+fn batch_ssh_command(dir: &Path) -> Option<String> {
+    // Git's order: GIT_SSH_COMMAND, core.sshCommand, GIT_SSH, then ssh
+    let non_empty = |command: String| (!command.trim().is_empty()).then_some(command);
+    let command = std::env::var("GIT_SSH_COMMAND")
+        .ok()
+        .and_then(non_empty)
+        .or_else(|| {
+            Repository::discover(dir)
+                .and_then(|repo| repo.config())
+                .and_then(|config| config.get_string("core.sshCommand"))
+                .ok()
+                .and_then(non_empty)
+        });
+    match command {
+        Some(command) => with_batch_mode(&command),
+        None if std::env::var_os("GIT_SSH").is_some_and(|program| !program.is_empty()) => None,
+        None => with_batch_mode("ssh"),
+    }
+}
+
+/// `command` with `-o BatchMode=yes`, if its program is OpenSSH's `ssh`.
+fn with_batch_mode(command: &str) -> Option<String> {
+    let command = command.trim();
+    let program = match command.chars().next()? {
+        quote @ ('"' | '\'') => command[1..].split(quote).next()?,
+        _ => command.split_whitespace().next()?,
+    };
+    let name = Path::new(program).file_stem()?.to_str()?;
+    name.eq_ignore_ascii_case("ssh")
+        .then(|| format!("{command} -o BatchMode=yes"))
+}
+
+/// Run `git fetch origin` in `dir`, with `--prune` when `prune` is set to
+/// drop the remote-tracking refs of branches deleted on origin, calling
+/// `on_progress` as git reports progress. Credentials come from git's
+/// helpers and agents; git is not allowed to prompt for them. The fetch is
+/// killed if the future is dropped.
+pub async fn fetch_origin_in_dir(
+    dir: &Path,
+    prune: bool,
+    mut on_progress: impl FnMut(FetchProgress),
+) -> Result<()> {
+    let mut command = tokio::process::Command::new("git");
+    command.args(["fetch", "--progress"]);
+    if prune {
+        command.arg("--prune");
+    }
+    if let Some(ssh_command) = batch_ssh_command(dir) {
+        command.env("GIT_SSH_COMMAND", ssh_command);
+    }
+    let mut child = command
+        .arg(ORIGIN_REMOTE)
+        .current_dir(dir)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .context("Failed to run git fetch origin")?;
+    let mut stderr = child
+        .stderr
+        .take()
+        .context("Failed to read git fetch output")?;
+
+    // Progress lines are redrawn in place, ending in `\r` rather than `\n`.
+    let mut messages = Vec::new();
+    let mut line = Vec::new();
+    let mut buffer = [0; 4096];
+    loop {
+        let read = stderr
+            .read(&mut buffer)
+            .await
+            .context("Failed to read git fetch output")?;
+        for &byte in &buffer[..read] {
+            if byte != b'\r' && byte != b'\n' {
+                line.push(byte);
+                continue;
+            }
+            let text = String::from_utf8_lossy(&line).trim().to_string();
+            line.clear();
+            match parse_fetch_progress(&text) {
+                Some(progress) => on_progress(progress),
+                None if !text.is_empty() => messages.push(text),
+                None => {}
+            }
+        }
+        if read == 0 {
+            break;
+        }
+    }
+
+    let status = child
+        .wait()
+        .await
+        .context("Failed to run git fetch origin")?;
+    if !status.success() {
+        anyhow::bail!("Failed to fetch origin: {}", fetch_error_message(&messages));
+    }
+    Ok(())
+}
+
+fn parse_fetch_progress(line: &str) -> Option<FetchProgress> {
+    let line = line.strip_prefix("remote:").unwrap_or(line).trim_start();
+    let (phase, rest) = line.split_once(':')?;
+    let (percent, _) = rest.trim_start().split_once('%')?;
+    Some(FetchProgress {
+        phase: phase.to_string(),
+        percent: percent.parse().ok()?,
+    })
+}
+
+/// The line of git's output that explains a failed fetch: the first
+/// `fatal:` or `error:` line, otherwise the last line.
+fn fetch_error_message(messages: &[String]) -> &str {
+    messages
+        .iter()
+        .find(|line| line.starts_with("fatal:") || line.starts_with("error:"))
+        .or(messages.last())
+        .map_or("git exited with an error", String::as_str)
+}
+
+fn last_commit_details(branch: &git2::Branch) -> (Option<String>, Option<String>, Option<i64>) {
     if let Ok(reference) = branch.get().resolve()
         && let Ok(commit) = reference.peel_to_commit()
     {
         let author = commit.author();
         let name = author.name().map(|s| s.to_string());
         let time = commit.time().seconds();
-        return (name, Some(time));
+        return (Some(commit.id().to_string()), name, Some(time));
     }
 
-    (None, None)
+    (None, None, None)
+}
+
+/// The branch on origin that local `branch` tracks, if any.
+fn origin_upstream(branch: &git2::Branch) -> Option<String> {
+    let upstream = branch.upstream().ok()?;
+    origin_branch_name(upstream.name().ok()??).map(str::to_string)
 }
 
 fn origin_branch_name(name: &str) -> Option<&str> {
@@ -769,11 +1185,13 @@ fn parse_ls_remote_heads(output: &str) -> HashSet<String> {
 }
 
 fn remote_branch_status(
+    last_commit_sha: Option<String>,
     last_commit_author: Option<String>,
     last_commit_time: Option<i64>,
 ) -> BranchStatus {
     BranchStatus {
         remote_status: RemoteStatus::RemoteTracking,
+        last_commit_sha,
         last_commit_author,
         last_commit_time,
     }
@@ -833,16 +1251,45 @@ mod tests {
 
     #[test]
     fn test_extract_work_item_number() {
-        assert_eq!(extract_work_item_number("feature/12345-login"), Some(12345));
-        assert_eq!(extract_work_item_number("bugfix-42-fix-crash"), Some(42));
-        assert_eq!(extract_work_item_number("12345-some-feature"), Some(12345));
+        assert_eq!(
+            extract_work_item_number("feature/12345-login", |_, _| true),
+            Some(12345)
+        );
+        assert_eq!(
+            extract_work_item_number("bugfix-42-fix-crash", |_, _| true),
+            Some(42)
+        );
+        assert_eq!(
+            extract_work_item_number("12345-some-feature", |_, _| true),
+            Some(12345)
+        );
 
-        assert_eq!(extract_work_item_number("main"), None);
-        assert_eq!(extract_work_item_number("develop"), None);
-        assert_eq!(extract_work_item_number("no-numbers-here"), None);
+        assert_eq!(extract_work_item_number("main", |_, _| true), None);
+        assert_eq!(extract_work_item_number("develop", |_, _| true), None);
+        assert_eq!(
+            extract_work_item_number("no-numbers-here", |_, _| true),
+            None
+        );
 
-        assert_eq!(extract_work_item_number(""), None);
-        assert_eq!(extract_work_item_number("v2.1.0"), Some(2));
+        assert_eq!(extract_work_item_number("", |_, _| true), None);
+        assert_eq!(extract_work_item_number("v2.1.0", |_, _| true), Some(2));
+    }
+
+    #[test]
+    fn test_extract_work_item_number_skips_rejected_numbers() {
+        let four_digits = |digits: &str, _: &str| digits.len() >= 4;
+        assert_eq!(
+            extract_work_item_number("fix-ie11-12345", four_digits),
+            Some(12345)
+        );
+        assert_eq!(extract_work_item_number("fix-ie11", four_digits), None);
+
+        let mut segments = Vec::new();
+        extract_work_item_number("feature/v2-migration_x3.4", |_, segment| {
+            segments.push(segment.to_string());
+            false
+        });
+        assert_eq!(segments, ["v2", "x3", "4"]);
     }
 
     #[test]
@@ -887,6 +1334,46 @@ mod tests {
         assert!(branches.is_empty());
     }
 
+    #[test]
+    fn test_parse_fetch_progress_reads_phase_and_percent() {
+        assert_eq!(
+            parse_fetch_progress("Receiving objects:  45% (9/20), 1.20 MiB | 2.00 MiB/s"),
+            Some(FetchProgress {
+                phase: "Receiving objects".to_string(),
+                percent: 45,
+            })
+        );
+        assert_eq!(
+            parse_fetch_progress("remote: Compressing objects: 100% (3/3), done."),
+            Some(FetchProgress {
+                phase: "Compressing objects".to_string(),
+                percent: 100,
+            })
+        );
+        assert_eq!(parse_fetch_progress("From github.com:org/repo"), None);
+        assert_eq!(
+            parse_fetch_progress("   abc123..def456  main       -> origin/main"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fetch_error_message_prefers_fatal_line() {
+        let messages = [
+            "fatal: Could not read from remote repository.".to_string(),
+            "Please make sure you have the correct access rights".to_string(),
+        ];
+        assert_eq!(
+            fetch_error_message(&messages),
+            "fatal: Could not read from remote repository."
+        );
+        assert_eq!(
+            fetch_error_message(&["ssh: connect to host example.com".to_string()]),
+            "ssh: connect to host example.com"
+        );
+        assert_eq!(fetch_error_message(&[]), "git exited with an error");
+    }
+
     #[test]
     fn test_preserve_upstream_error_when_cleanup_succeeds() {
         let result = handle_upstream_setup_result(
@@ -919,7 +1406,7 @@ mod tests {
 
     #[test]
     fn test_remote_branch_status_uses_remote_tracking_variant() {
-        let status = remote_branch_status(Some("Alice".to_string()), Some(123));
+        let status = remote_branch_status(None, Some("Alice".to_string()), Some(123));
 
         assert!(matches!(status.remote_status, RemoteStatus::RemoteTracking));
         assert_eq!(status.last_commit_author.as_deref(), Some("Alice"));
@@ -994,6 +1481,40 @@ mod tests {
         assert!(!result.expect("detached head should not error"));
     }
 
+    #[test]
+    fn test_with_batch_mode_keeps_the_configured_ssh_command() {
+        assert_eq!(
+            with_batch_mode("ssh").as_deref(),
+            Some("ssh -o BatchMode=yes")
+        );
+        assert_eq!(
+            with_batch_mode("ssh -i ~/.ssh/work").as_deref(),
+            Some("ssh -i ~/.ssh/work -o BatchMode=yes")
+        );
+        assert_eq!(
+            with_batch_mode("\"C:/Program Files/OpenSSH/ssh.exe\" -v").as_deref(),
+            Some("\"C:/Program Files/OpenSSH/ssh.exe\" -v -o BatchMode=yes")
+        );
+    }
+
+    #[test]
+    fn test_with_batch_mode_leaves_other_ssh_programs_alone() {
+        assert_eq!(with_batch_mode("plink -batch"), None);
+        assert_eq!(with_batch_mode("/usr/local/bin/ssh-wrapper"), None);
+        assert_eq!(with_batch_mode(""), None);
+    }
+
+    #[test]
+    fn test_merged_branches_fails_without_an_origin_default_branch() {
+        let (repo, repo_path, _) = init_test_repo("merged-no-origin-head");
+
+        let result = repo.merged_branches();
+
+        let _ = fs::remove_dir_all(repo_path);
+        let error = result.expect_err("unknown default branch should fail");
+        assert!(error.to_string().contains("git remote set-head origin -a"));
+    }
+
     #[test]
     fn test_current_local_branch_name_returns_none_in_detached_head() {
         let (repo, repo_path, oid) = init_test_repo("detached-head-local-name");
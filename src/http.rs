@@ -0,0 +1,89 @@
+//! HTTP client setup shared by the Azure DevOps and GitHub clients.
+//!
+//! reqwest already routes requests through the proxy named by `HTTPS_PROXY`,
+//! `HTTP_PROXY`, or `ALL_PROXY` and skips hosts in `NO_PROXY`; `[http].proxy`
+//! only decides whether it may. `[http].ca_bundle` adds root certificates
+//! on top of the built-in ones.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use reqwest::{Certificate, ClientBuilder};
+
+use crate::config::{Config, HttpConfig, ProxyMode};
+use crate::output::verbose;
+
+const PROXY_VARIABLES: &[&str] = &[
+    "HTTPS_PROXY",
+    "https_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+];
+
+/// A client builder with `[http]` applied, or the defaults without a config.
+pub fn client_builder(http: Option<&HttpConfig>) -> Result<ClientBuilder> {
+    let mut builder = reqwest::Client::builder();
+    match http.map(|http| http.proxy).unwrap_or_default() {
+        ProxyMode::Auto => {
+            if let Some((name, value)) = environment_proxy() {
+                verbose!("http: using proxy {value} from {name}");
+            }
+        }
+        ProxyMode::Off => {
+            verbose!("http: proxy disabled by [http].proxy");
+            builder = builder.no_proxy();
+        }
+    }
+    if let Some(path) = http.and_then(|http| http.ca_bundle.as_deref()) {
+        let path = Config::resolve_path(path);
+        let certs = load_ca_bundle(&path)?;
+        verbose!(
+            "http: trusting {} certificate(s) from {}",
+            certs.len(),
+            path.display()
+        );
+        builder = builder.tls_certs_merge(certs);
+    }
+    Ok(builder)
+}
+
+/// Read the PEM certificates in `path` for `[http].ca_bundle`.
+fn load_ca_bundle(path: &Path) -> Result<Vec<Certificate>> {
+    let pem = fs::read(path)
+        .with_context(|| format!("Failed to read [http].ca_bundle: {}", path.display()))?;
+    parse_ca_bundle(&pem).with_context(|| format!("Invalid [http].ca_bundle: {}", path.display()))
+}
+
+fn parse_ca_bundle(pem: &[u8]) -> Result<Vec<Certificate>> {
+    let certs = Certificate::from_pem_bundle(pem)?;
+    if certs.is_empty() {
+        bail!("no PEM certificates found (expected -----BEGIN CERTIFICATE----- blocks)");
+    }
+    Ok(certs)
+}
+
+/// The first proxy environment variable that is set, if any.
+fn environment_proxy() -> Option<(&'static str, String)> {
+    PROXY_VARIABLES.iter().find_map(|name| {
+        std::env::var(name)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| (*name, value))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_ca_bundle_without_certificates() {
+        let error = parse_ca_bundle(b"not a certificate\n").expect_err("no certificates");
+        assert!(error.to_string().starts_with("no PEM certificates found"));
+
+        assert!(parse_ca_bundle(b"").is_err());
+    }
+}
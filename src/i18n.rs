@@ -0,0 +1,453 @@
+//! User-facing text in the language set by `[display].lang`.
+//!
+//! Every translated string is a [`Text`] in the catalog below, with one
+//! column per [`Language`]. Strings with values use `{placeholder}`
+//! templates so each language can place them where its grammar wants.
+//! Set once at startup like the time display; until then, and in tests, text
+//! is English.
+//!
+//! Field names, work item states, and errors from git and Azure DevOps come
+//! from outside cazdo and are shown as received.
+
+use std::sync::OnceLock;
+
+use crate::config::Language;
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+/// Use `language` for the rest of the run.
+pub fn set_language(language: Language) {
+    let _ = LANGUAGE.set(language);
+}
+
+fn language() -> Language {
+    LANGUAGE.get().copied().unwrap_or_default()
+}
+
+macro_rules! catalog {
+    ($($name:ident => $en:literal, $de:literal;)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Text {
+            $($name,)*
+        }
+
+        impl Text {
+            #[cfg(test)]
+            const ALL: &[Text] = &[$(Text::$name,)*];
+
+            fn translate(self, language: Language) -> &'static str {
+                match (self, language) {
+                    $(
+                        (Text::$name, Language::En) => $en,
+                        (Text::$name, Language::De) => $de,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+catalog! {
+    // Footer
+    FooterType => "type", "tippen";
+    FooterFilter => "filter", "filtern";
+    FooterDelete => "delete", "löschen";
+    FooterClear => "clear", "leeren";
+    FooterApply => "apply", "anwenden";
+    FooterCancel => "cancel", "abbrechen";
+    FooterNavigate => "navigate", "navigieren";
+    FooterToggleRemote => "toggle remote", "zu remote";
+    FooterToggleLocal => "toggle local", "zu lokal";
+    FooterOpen => "open", "öffnen";
+    FooterScroll => "scroll", "scrollen";
+    FooterRefresh => "refresh", "aktualisieren";
+    FooterProtected => "protected", "geschützte";
+    FooterClearFilter => "clear filter \"{filter}\"", "Filter \"{filter}\" leeren";
+    FooterQuit => "quit", "beenden";
+    FooterTopBottom => "top/bottom", "Anfang/Ende";
+    FooterClose => "close", "schließen";
+    FooterSelect => "select", "auswählen";
+    FooterChange => "change", "ändern";
+    FooterComplete => "complete", "abschließen";
+    FooterBranches => "branches", "Branches";
+    FooterFetchProgress => "fetching origin: {phase} {percent}%", "hole origin: {phase} {percent}%";
+    FooterFetching => "fetching origin...", "hole origin...";
+    FooterUpdateAvailable => "cazdo {version} available", "cazdo {version} verfügbar";
+
+    // Popups
+    DeleteTitle => "Delete Branch", "Branch löschen";
+    PruneTitle => "Prune Stale Branch", "Veralteten Branch bereinigen";
+    ErrorTitle => "Error", "Fehler";
+    ConfirmDelete => "Are you sure you want to delete branch {branch}?", "Branch {branch} wirklich löschen?";
+    ConfirmDeleteRemote => "Are you sure you want to delete remote branch {branch}?", "Remote-Branch {branch} wirklich löschen?";
+    ConfirmDeleteMarked => "Delete these {count} branches?", "Diese {count} Branches löschen?";
+    DeleteMarkedTitle => "Delete Marked Branches", "Markierte Branches löschen";
+    DeleteResultsTitle => "Deleted {deleted} of {count} Branches", "{deleted} von {count} Branches gelöscht";
+    ConfirmPrune => "Prune stale tracking ref for {branch}?", "Veraltete Tracking-Ref für {branch} bereinigen?";
+    PruneReason => "(branch no longer exists on origin)", "(Branch existiert nicht mehr auf origin)";
+    HintConfirm => "Press {keys} to confirm.", "Zum Bestätigen {keys} drücken.";
+    HintCancel => "Press {keys} to cancel.", "Zum Abbrechen {keys} drücken.";
+    HintDeleteLocalOnly => "Press {keys} to delete it locally only.", "Zum Löschen nur lokal {keys} drücken.";
+    HintDeleteWithRemote => "Press {keys} to delete it locally and {remote}.", "Zum Löschen lokal und von {remote} {keys} drücken.";
+    HintDismiss => "Press {keys} to Dismiss.", "Zum Schließen {keys} drücken.";
+    KeysOr => " or ", " oder ";
+    InspectorTitle => "Work Item #{id} JSON", "Arbeitselement #{id} als JSON";
+    InspectorLoading => "Loading work item JSON...", "Lade JSON des Arbeitselements...";
+    InspectorError => "Could not load work item JSON: {error}", "JSON des Arbeitselements konnte nicht geladen werden: {error}";
+    StateMenuTitle => "Change State of #{id}", "Status von #{id} ändern";
+    StateMenuLoading => "Loading states...", "Lade Status...";
+    StateMenuError => "Could not load states: {error}", "Status konnten nicht geladen werden: {error}";
+    StateMenuEmpty => "The process allows no other state from here.", "Der Prozess erlaubt von hier keinen anderen Status.";
+    CategoryProposed => "Proposed", "Vorgeschlagen";
+    CategoryInProgress => "In Progress", "In Bearbeitung";
+    CategoryResolved => "Resolved", "Gelöst";
+    CategoryCompleted => "Completed", "Abgeschlossen";
+    CategoryRemoved => "Removed", "Entfernt";
+    ActivityTitle => "Activity (last {days} days)", "Aktivität (letzte {days} Tage)";
+    ActivityRefreshing => "refreshing...", "aktualisiere...";
+    ActivityLoading => "Loading recent activity...", "Lade letzte Aktivität...";
+    ActivityError => "Could not load recent activity: {error}", "Letzte Aktivität konnte nicht geladen werden: {error}";
+    ActivityEmpty => "No work item assigned to you or linked to a local branch changed in the last {days} days.", "Kein dir zugewiesenes oder mit einem lokalen Branch verknüpftes Arbeitselement wurde in den letzten {days} Tagen geändert.";
+    ActivityCreated => "created", "erstellt";
+    ActivityOtherFields => "other fields changed", "andere Felder geändert";
+    ActivityNone => "(none)", "(keiner)";
+    PullRequestListHeading => "Pull Requests", "Pull Requests";
+    PullRequestListTitle => "Pull Requests in {repository}", "Pull Requests in {repository}";
+    PullRequestListError => "Could not list pull requests: {error}", "Pull Requests konnten nicht aufgelistet werden: {error}";
+    PullRequestListEmpty => "No active pull requests in this repository.", "Keine aktiven Pull Requests in diesem Repository.";
+    PullRequestListNoRepository => "No Azure Repos repository", "Kein Azure-Repos-Repository";
+    WorkItemListTitle => "My Work Items", "Meine Arbeitselemente";
+    WorkItemListLoading => "Running work item query...", "Führe Abfrage der Arbeitselemente aus...";
+    WorkItemListError => "Could not run work item query: {error}", "Abfrage der Arbeitselemente fehlgeschlagen: {error}";
+    WorkItemListEmpty => "The query returned no work items.", "Die Abfrage lieferte keine Arbeitselemente.";
+    PullRequestNoReviewers => "no reviewers", "keine Reviewer";
+    ReviewerRequired => "required", "erforderlich";
+    VoteApproved => "approved", "genehmigt";
+    VoteApprovedWithSuggestions => "approved with suggestions", "mit Vorschlägen genehmigt";
+    VotePending => "no vote", "keine Stimme";
+    VoteWaitingForAuthor => "waiting for author", "wartet auf Autor";
+    VoteRejected => "rejected", "abgelehnt";
+    StatsTitle => "Branch Statistics ({count} local)", "Branch-Statistik ({count} lokal)";
+    StatsByState => "Work item state", "Status des Arbeitselements";
+    StatsByAge => "Last commit", "Letzter Commit";
+    StatsByRemote => "Remote status", "Remote-Status";
+    StatsCleanup => "Cleanup opportunity", "Aufräumpotenzial";
+    StatsNoWorkItem => "no work item", "kein Arbeitselement";
+    StatsAgeWeek => "under a week", "unter einer Woche";
+    StatsAgeMonth => "1-4 weeks", "1-4 Wochen";
+    StatsAgeQuarter => "1-3 months", "1-3 Monate";
+    StatsAgeOlder => "older", "älter";
+    StatsAhead => "ahead", "voraus";
+    StatsBehind => "behind", "zurück";
+    StatsDiverged => "diverged", "auseinandergelaufen";
+    StatsMerged => "merged", "zusammengeführt";
+    StatsCleanupTotal => "total", "gesamt";
+    StatsCheckingMerged => "checking merged branches...", "prüfe zusammengeführte Branches...";
+    StatsMergedFailed => "Could not check merged branches: {error}", "Zusammengeführte Branches konnten nicht geprüft werden: {error}";
+
+    // Branch list and branch info
+    BranchesTitle => "Branches ({view})", "Branches ({view})";
+    ViewLocal => "Local", "Lokal";
+    ViewRemote => "Remote", "Remote";
+    SortedByPriority => ", by priority", ", nach Priorität";
+    LocalBranches => "local branches", "lokalen Branches";
+    RemoteBranches => "remote branches", "Remote-Branches";
+    NoBranchesMatch => "No {branches} match \"{filter}\".", "Keine {branches} passen zu \"{filter}\".";
+    NoBranchesShown => "No {branches} shown. Press p to show protected branches.", "Keine {branches} angezeigt. p drücken, um geschützte Branches zu zeigen.";
+    NoBranchesFound => "No {branches} found.", "Keine {branches} gefunden.";
+    BranchInfoTitle => "Branch Info", "Branch-Infos";
+    OriginLabel => "Origin: ", "Origin: ";
+    RemoteLabel => "Remote: ", "Remote: ";
+    SourceLabel => "Source: ", "Quelle: ";
+    StaleLabel => "Stale: ", "Veraltet: ";
+    LastCommitLabel => "Last commit: ", "Letzter Commit: ";
+    CheckingOrigin => "Checking origin...", "Prüfe origin...";
+    StaleOnOrigin => "Missing on origin; cached remote-tracking ref may be stale", "Fehlt auf origin; die zwischengespeicherte Tracking-Ref ist evtl. veraltet";
+    Loading => "Loading...", "Lädt...";
+    Unknown => "unknown", "unbekannt";
+    LocalOnly => "local only", "nur lokal";
+    UpToDate => "up to date", "aktuell";
+    RemoteGone => "remote gone", "Remote entfernt";
+    Ahead => "ahead {count}", "{count} voraus";
+    Behind => "behind {count}", "{count} zurück";
+
+    // Plain mode labels
+    SelectedLabel => "SELECTED", "AUSGEWÄHLT";
+    CurrentLabel => "CURRENT", "AKTUELL";
+    ProtectedLabel => "PROTECTED", "GESCHÜTZT";
+    MarkedLabel => "MARKED", "MARKIERT";
+    BranchesMarked => "{count} marked", "{count} markiert";
+    StaleBadge => "STALE", "VERALTET";
+    ToastMoved => "#{id} moved to {state}", "#{id} nach {state} verschoben";
+    ToastReassigned => "#{id} reassigned to {name}", "#{id} {name} zugewiesen";
+    ToastUnassigned => "#{id} unassigned", "Zuweisung von #{id} aufgehoben";
+    ToastChanged => "#{id} changed", "#{id} geändert";
+    ToastJump => "jump", "springen";
+    NoBranchForWorkItem => "No branch for #{id} in either view", "Kein Branch für #{id} in beiden Ansichten";
+    OverdueLabel => "OVERDUE", "ÜBERFÄLLIG";
+    ErrorLabel => "ERROR:", "FEHLER:";
+
+    // Work item details
+    DetailsTitle => "Work Item Details", "Details des Arbeitselements";
+    NoWorkItemLinked => "No work item linked to this branch", "Kein Arbeitselement mit diesem Branch verknüpft";
+    Connecting => "Connecting to Azure DevOps...", "Verbinde mit Azure DevOps...";
+    LoadingWorkItem => "Loading work item...", "Lade Arbeitselement...";
+    NotConfigured => "Azure DevOps is not configured", "Azure DevOps ist nicht konfiguriert";
+    RunConfigInit => "Run `cazdo config init` to set up the organization and PAT.", "`cazdo config init` ausführen, um Organisation und PAT einzurichten.";
+    NotConnected => "Not connected to Azure DevOps", "Nicht mit Azure DevOps verbunden";
+    Error => "Error: {error}", "Fehler: {error}";
+    ParentLabel => "Parent: ", "Übergeordnet: ";
+    ChildLabel => "Child: ", "Untergeordnet: ";
+    PriorityLabel => "Priority: ", "Priorität: ";
+    SeverityLabel => "Severity: ", "Schweregrad: ";
+    HierarchyHeading => "Hierarchy:", "Hierarchie:";
+    HierarchyCollapsed => "Hierarchy ({key} to step through):", "Hierarchie ({key} zum Durchgehen):";
+    HierarchyKeys => "Hierarchy (j/k select, enter show, backspace back, esc close):", "Hierarchie (j/k auswählen, Enter anzeigen, Rücktaste zurück, Esc schließen):";
+    LoadingChildren => "Loading child work items...", "Lade untergeordnete Arbeitselemente...";
+    ChildrenError => "Could not load child work items: {error}", "Untergeordnete Arbeitselemente konnten nicht geladen werden: {error}";
+    LoadingLower => "loading...", "lädt...";
+    Created => "created {time}", "erstellt {time}";
+    Updated => "updated {time}", "aktualisiert {time}";
+    By => "by {name}", "von {name}";
+    PullRequestsHeading => "Pull Requests:", "Pull Requests:";
+    LoadingPullRequests => "Loading pull requests...", "Lade Pull Requests...";
+    PullRequestsError => "Could not load pull requests: {error}", "Pull Requests konnten nicht geladen werden: {error}";
+    CommentsTitle => "Comments", "Kommentare";
+    CommentsHeading => "Comments ({count})", "Kommentare ({count})";
+    CommentsCollapsed => "Comments ({key} to show)", "Kommentare ({key} zum Anzeigen)";
+    LoadingComments => "Loading comments...", "Lade Kommentare...";
+    CommentsError => "Could not load comments: {error}", "Kommentare konnten nicht geladen werden: {error}";
+    NoComments => "No comments", "Keine Kommentare";
+    PullRequestDraft => "draft", "Entwurf";
+    PullRequestActive => "active", "aktiv";
+    PullRequestCompleted => "completed", "abgeschlossen";
+
+    // Status messages
+    Deleted => "Deleted {branch} (was {sha})", "{branch} gelöscht (war {sha})";
+    DeletedRemote => "Deleted remote branch '{branch}'", "Remote-Branch '{branch}' gelöscht";
+    DeletedWithRemote => "Deleted {branch} and {remote} (was {sha})", "{branch} und {remote} gelöscht (war {sha})";
+    DeletedRemoteFailed => "Deleted {branch} (was {sha}), but not {remote}: {error}", "{branch} gelöscht (war {sha}), aber nicht {remote}: {error}";
+    DeletedRemotePruneFailed => "Deleted remote branch '{branch}', but could not prune tracking ref: {error}", "Remote-Branch '{branch}' gelöscht, aber die Tracking-Ref konnte nicht bereinigt werden: {error}";
+    Restored => "Restored {branch} at {sha}", "{branch} bei {sha} wiederhergestellt";
+    RestoreFailed => "Could not restore {branch}: {error}", "{branch} konnte nicht wiederhergestellt werden: {error}";
+    NothingToUndo => "No deleted branch to restore", "Kein gelöschter Branch zum Wiederherstellen";
+    Pruned => "Pruned stale tracking ref '{branch}'", "Veraltete Tracking-Ref '{branch}' bereinigt";
+    Switched => "Switched to branch '{branch}'", "Zu Branch '{branch}' gewechselt";
+    StaleCheckout => "'{branch}' is stale (no longer on origin). Prune it first with 'd'.", "'{branch}' ist veraltet (nicht mehr auf origin). Zuerst mit 'd' bereinigen.";
+    NoBranchSelected => "No branch selected", "Kein Branch ausgewählt";
+    CannotDeleteCurrent => "Cannot delete the current branch", "Der aktuelle Branch kann nicht gelöscht werden";
+    CannotDeleteProtected => "Cannot delete protected branch '{branch}'", "Geschützter Branch '{branch}' kann nicht gelöscht werden";
+    OpenWorkItemFailed => "Could not open work item in browser: {error}", "Arbeitselement konnte nicht im Browser geöffnet werden: {error}";
+    OpenPullRequestFailed => "Could not open pull request in browser: {error}", "Pull Request konnte nicht im Browser geöffnet werden: {error}";
+    NoActivePullRequest => "No active pull request for this work item", "Kein aktiver Pull Request für dieses Arbeitselement";
+    CompletionTitle => "Complete PR !{id}", "PR !{id} abschließen";
+    MergeStrategyLabel => "Merge type", "Zusammenführungsart";
+    MergeNoFastForward => "Merge (no fast-forward)", "Merge (kein Fast-Forward)";
+    MergeSquash => "Squash commit", "Squash-Commit";
+    MergeRebase => "Rebase and fast-forward", "Rebase und Fast-Forward";
+    MergeRebaseMerge => "Semi-linear merge", "Semilinearer Merge";
+    DeleteSourceBranch => "Delete source branch", "Quellbranch löschen";
+    CompleteWorkItems => "Complete linked work items", "Verknüpfte Arbeitselemente abschließen";
+    CompletedPullRequest => "Completed PR !{id}", "PR !{id} abgeschlossen";
+    PullRequestCompletionFailed => "Could not complete PR !{id}: {error}", "PR !{id} konnte nicht abgeschlossen werden: {error}";
+    NoPullRequest => "No pull request linked to this work item", "Kein Pull Request mit diesem Arbeitselement verknüpft";
+    Fetched => "Fetched origin", "origin geholt";
+    FetchFailed => "Could not fetch origin: {error}", "origin konnte nicht geholt werden: {error}";
+    OriginCheckFailed => "Could not verify origin branches", "Branches auf origin konnten nicht geprüft werden";
+    FetchedPruned => "Fetched origin, {count} branches gone", "origin geholt, {count} Branches entfernt";
+    StateChanged => "#{id} is now {state}", "#{id} ist jetzt {state}";
+    StateChangeFailed => "Could not change the state of #{id}: {error}", "Status von #{id} konnte nicht geändert werden: {error}";
+    BranchInfoFailed => "Could not load branch info for '{branch}': {error}", "Branch-Infos für '{branch}' konnten nicht geladen werden: {error}";
+    DeletedThisSession => "Deleted branches this session:", "In dieser Sitzung gelöschte Branches:";
+    DeletedAlongWith => "{branch} and {remote}", "{branch} und {remote}";
+    Restore => "restore: {hint}", "wiederherstellen: {hint}";
+    RestoreScriptSaved => "Saved a restore script to {path}; `cazdo restore` lists and replays it.", "Wiederherstellungsskript unter {path} gespeichert; `cazdo restore` listet und spielt es ab.";
+    RestoreScriptFailed => "Could not save the restore script: {error}", "Wiederherstellungsskript konnte nicht gespeichert werden: {error}";
+    SessionSummary => "Session {start}-{end} ({duration}):", "Sitzung {start}-{end} ({duration}):";
+    SessionCheckedOut => "Checked out:", "Ausgecheckt:";
+    SessionViewed => "Work items viewed:", "Angesehene Arbeitselemente:";
+    SessionStateChanges => "State changes:", "Statusänderungen:";
+    SessionRequests => "API requests: {count}", "API-Anfragen: {count}";
+    SessionSummarySaved => "Saved the session summary to {path}", "Sitzungszusammenfassung unter {path} gespeichert";
+    SessionSummaryFailed => "Could not save the session summary: {error}", "Sitzungszusammenfassung konnte nicht gespeichert werden: {error}";
+
+    // Commands
+    ConfigSet => "Set {key} = {value}", "{key} = {value} gesetzt";
+    ImportSkipped => "Skipped {key}: PATs are never imported.", "{key} übersprungen: PATs werden nie importiert.";
+    ImportEmpty => "No settings to import from {file}.", "Keine Einstellungen zum Importieren in {file}.";
+    Imported => "Imported from {file}:", "Importiert aus {file}:";
+    VerifyChecking => "Checking Azure DevOps configuration...", "Prüfe die Azure-DevOps-Konfiguration...";
+    VerifyOffline => "Offline checks passed: config file, URL, patterns, and PAT are set up. Run without --offline to check them against Azure DevOps.", "Offline-Prüfung bestanden: Konfigurationsdatei, URL, Muster und PAT sind eingerichtet. Ohne --offline ausführen, um sie gegen Azure DevOps zu prüfen.";
+    VerifyMissingScopes => "Verification successful, but the features listed with missing scopes will fail until the PAT grants them.", "Prüfung erfolgreich, aber die Funktionen mit fehlenden Berechtigungen schlagen fehl, bis der PAT sie gewährt.";
+    VerifyProject => "Verification successful: URL, PAT, project, and team are working.", "Prüfung erfolgreich: URL, PAT, Projekt und Team funktionieren.";
+    VerifyBasic => "Verification successful: URL and PAT are working.", "Prüfung erfolgreich: URL und PAT funktionieren.";
+    LatestVersion => "cazdo {version} is the latest version.", "cazdo {version} ist die neueste Version.";
+    UpdateAvailable => "cazdo {latest} is available (you have {current}).", "cazdo {latest} ist verfügbar (installiert: {current}).";
+    UpdateHowTo => "Run `cazdo update` if you installed cazdo with the shell or PowerShell installer, or update it through your package manager.", "`cazdo update` ausführen, wenn cazdo mit dem Shell- oder PowerShell-Installer installiert wurde, sonst über den Paketmanager aktualisieren.";
+    ReleaseNotes => "Release notes: {url}", "Versionshinweise: {url}";
+    CheckingForUpdates => "Checking for updates...", "Suche nach Updates...";
+    UpdatedFrom => "Updated cazdo from {old} to {new}.", "cazdo von {old} auf {new} aktualisiert.";
+    UnknownVersion => "an unknown version", "einer unbekannten Version";
+    AlreadyLatest => "cazdo {version} is already the latest version.", "cazdo {version} ist bereits die neueste Version.";
+    Opened => "Opened #{id} in the browser: {url}", "#{id} im Browser geöffnet: {url}";
+    CommentAdded => "Added comment to #{id}.", "Kommentar zu #{id} hinzugefügt.";
+    HoursUpdated => "Updated #{id}: remaining {remaining}, completed {completed}.", "#{id} aktualisiert: verbleibend {remaining}, erledigt {completed}.";
+    Unset => "unset", "nicht gesetzt";
+    Aborted => "Aborted.", "Abgebrochen.";
+    CacheCleared => "Deleted the work item cache at {path}.", "Cache der Arbeitselemente unter {path} gelöscht.";
+    CacheAlreadyEmpty => "The work item cache is already empty.", "Der Cache der Arbeitselemente ist bereits leer.";
+    PageBoard => "board", "Board";
+    PageSprint => "current sprint", "Aktueller Sprint";
+    PageBranches => "branches page", "Branch-Seite";
+    PagePipeline => "pipeline", "Pipeline";
+    OpenedPage => "Opened the {page} in the browser: {url}", "{page} im Browser geöffnet: {url}";
+    OpenPageFailed => "Could not open the {page} in browser", "{page} konnte nicht im Browser geöffnet werden";
+    NoBuilds => "No builds of '{branch}' found; opening all pipelines.", "Keine Builds von '{branch}' gefunden; öffne alle Pipelines.";
+    PipelineNeedsBranch => "No local branch is currently checked out. Pass --branch to pick the pipeline's branch.", "Derzeit ist kein lokaler Branch ausgecheckt. Mit --branch den Branch der Pipeline wählen.";
+    PullRequestNeedsBranch => "No local branch is currently checked out. Check out the branch to open a pull request for.", "Derzeit ist kein lokaler Branch ausgecheckt. Den Branch auschecken, für den ein Pull Request geöffnet werden soll.";
+    PullRequestFromProtected => "Cannot create a pull request from protected branch '{branch}'.", "Aus dem geschützten Branch '{branch}' kann kein Pull Request erstellt werden.";
+    CreatedPullRequest => "Created pull request !{id}: {source} -> {target}", "Pull Request !{id} erstellt: {source} -> {target}";
+    CreatedDraftPullRequest => "Created pull request !{id} (draft): {source} -> {target}", "Pull Request !{id} (Entwurf) erstellt: {source} -> {target}";
+    NoActivePullRequests => "No active pull requests.", "Keine aktiven Pull Requests.";
+    NoCurrentBranch => "No local branch is checked out.", "Kein lokaler Branch ausgecheckt.";
+    NoGoneBranches => "No local branches with a gone upstream.", "Keine lokalen Branches mit entferntem Upstream.";
+    PruneNeedsYes => "Pass --yes to delete {count} branches without asking.", "--yes angeben, um {count} Branches ohne Rückfrage zu löschen.";
+    ConfirmDeleteOne => "Delete {count} branch?", "{count} Branch löschen?";
+    ConfirmDeleteMany => "Delete {count} branches?", "{count} Branches löschen?";
+    RestoreDeletedHint => "Restore a deleted branch with: git branch <BRANCH> <COMMIT>", "Gelöschten Branch wiederherstellen mit: git branch <BRANCH> <COMMIT>";
+    DeleteFailures => "Failed to delete {failures} of {count} branches.", "{failures} von {count} Branches konnten nicht gelöscht werden.";
+    NoSession => "No session {number}; `cazdo restore` lists the {count} recorded", "Keine Sitzung {number}; `cazdo restore` listet die {count} aufgezeichneten";
+    NoDeletedBranches => "No deleted branches recorded.", "Keine gelöschten Branches aufgezeichnet.";
+    RestoreSessionHint => "Restore a session with: cazdo restore <SESSION> [BRANCH...]", "Sitzung wiederherstellen mit: cazdo restore <SESSION> [BRANCH...]";
+    NotDeletedInSession => "The session did not delete a branch '{branch}'", "Die Sitzung hat keinen Branch '{branch}' gelöscht";
+    RestoreFailures => "Failed to restore {failures} of {count} branches.", "{failures} von {count} Branches konnten nicht wiederhergestellt werden.";
+    AlreadyProtected => "'{pattern}' is already protected.", "'{pattern}' ist bereits geschützt.";
+    NotProtected => "'{pattern}' is not a protected pattern. Protected: {patterns}", "'{pattern}' ist kein geschütztes Muster. Geschützt: {patterns}";
+    KeepOneProtected => "Removing every pattern would restore the defaults ({patterns}); add another pattern first.", "Ohne Muster gälten wieder die Standardwerte ({patterns}); zuerst ein anderes Muster hinzufügen.";
+    ProtectedAdded => "Added protected patterns:", "Hinzugefügte geschützte Muster:";
+    ProtectedPatterns => "Protected patterns:", "Geschützte Muster:";
+    ProtectedDefaults => "Protected patterns (defaults):", "Geschützte Muster (Standardwerte):";
+    ProtectedFromProject => "Protected patterns (from {file}):", "Geschützte Muster (aus {file}):";
+    HookInstalled => "Installed the {hook} hook at {path}.", "{hook}-Hook unter {path} installiert.";
+    HookBackedUp => "The previous hook was moved to {path}.", "Der bisherige Hook wurde nach {path} verschoben.";
+    HookEffect => "Commit messages on work item branches now end with AB#<id>.", "Commit-Nachrichten auf Branches mit Arbeitselement enden jetzt mit AB#<id>.";
+    HookRemoved => "Removed the {hook} hook at {path}.", "{hook}-Hook unter {path} entfernt.";
+    HookRestored => "Restored the previous hook from before cazdo was installed.", "Der Hook von vor der Installation von cazdo wurde wiederhergestellt.";
+    HookNotInstalled => "No cazdo {hook} hook is installed.", "Kein {hook}-Hook von cazdo installiert.";
+    HookExists => "A {hook} hook already exists at {path}.\n\nRun 'cazdo hooks install --force' to replace it (it is kept as {backup}), or add the AB#<id> step to it yourself.", "Unter {path} gibt es bereits einen {hook}-Hook.\n\n'cazdo hooks install --force' ausführen, um ihn zu ersetzen (er bleibt als {backup} erhalten), oder den Schritt für AB#<id> selbst ergänzen.";
+    HookForeign => "The {hook} hook at {path} was not installed by cazdo; leaving it in place.", "Der {hook}-Hook unter {path} wurde nicht von cazdo installiert und bleibt unverändert.";
+
+    // Setup (`cazdo config init`)
+    InitOverwrite => "Config already exists at {path}. Overwrite?", "Unter {path} gibt es bereits eine Konfiguration. Überschreiben?";
+    InitIntro => "Set up cazdo. Press Enter to accept the value in brackets.", "cazdo einrichten. Enter übernimmt den Wert in Klammern.";
+    InitSaved => "Saved {path}", "{path} gespeichert";
+    InitSetPatLater => "Set a PAT, then run `cazdo config verify` to check the configuration.", "Einen PAT setzen und dann mit `cazdo config verify` die Konfiguration prüfen.";
+    InitVerifyFailed => "The configuration was saved, but verification failed", "Die Konfiguration wurde gespeichert, aber die Prüfung ist fehlgeschlagen";
+    InitDefaults => "Configuration initialized with defaults!", "Konfiguration mit Standardwerten angelegt!";
+    InitConfigLocation => "Config location: {path}", "Ort der Konfiguration: {path}";
+    InitEditToSet => "Edit the config file to set:", "In der Konfigurationsdatei festlegen:";
+    InitEditOrganization => "Azure DevOps organization URL", "URL der Azure-DevOps-Organisation";
+    InitEditPatterns => "Protected branch patterns", "Muster geschützter Branches";
+    InitRemindPat => "Don't forget to set your PAT:", "Nicht vergessen, den PAT zu setzen:";
+    InitPatPlaceholder => "your-personal-access-token", "dein-personal-access-token";
+    InitOrganizationPrompt => "Organization URL (e.g. https://dev.azure.com/your-organization)", "Organisations-URL (z. B. https://dev.azure.com/deine-organisation)";
+    InitInvalidUrl => "Invalid URL: {error}", "Ungültige URL: {error}";
+    InitPatFromEnv => "Using the PAT from CAZDO_PAT.", "Verwende den PAT aus CAZDO_PAT.";
+    InitCreatePat => "Create a PAT at {url}/_usersSettings/tokens with Work Items (Read & Write), Code (Read), and Build (Read).", "Einen PAT unter {url}/_usersSettings/tokens mit Work Items (Read & Write), Code (Read) und Build (Read) erstellen.";
+    InitPatPrompt => "Personal access token (hidden, empty to skip)", "Personal Access Token (verborgen, leer zum Überspringen)";
+    InitPatSkipped => "Skipped. Set CAZDO_PAT or [azure_devops].pat later.", "Übersprungen. CAZDO_PAT oder [azure_devops].pat später setzen.";
+    InitPatStored => "Stored in the system keyring.", "Im Schlüsselbund des Systems gespeichert.";
+    InitPatInConfig => "Save the PAT in config.toml instead?", "Den PAT stattdessen in config.toml speichern?";
+    InitPatInProfile => "Set CAZDO_PAT=\"<your PAT>\" in your shell profile.", "CAZDO_PAT=\"<dein PAT>\" im Shell-Profil setzen.";
+    InitProjectsFailed => "Could not list projects: {error}", "Projekte konnten nicht aufgelistet werden: {error}";
+    InitProjectPrompt => "Project (- to skip)", "Projekt (- zum Überspringen)";
+    InitProjects => "Projects:", "Projekte:";
+    InitProjectChoicePrompt => "Project (number or name, - to skip)", "Projekt (Nummer oder Name, - zum Überspringen)";
+    InitUnknownProject => "'{answer}' is not one of the listed projects.", "'{answer}' ist keines der aufgelisteten Projekte.";
+    InitPatternsPrompt => "Protected branch patterns, comma-separated", "Muster geschützter Branches, durch Kommas getrennt";
+    InitNoPatterns => "Enter at least one pattern.", "Mindestens ein Muster eingeben.";
+
+    // Errors
+    NoBranches => "No branches found in repository", "Keine Branches im Repository gefunden";
+    CannotDeleteProtectedOnOrigin => "Cannot delete protected branch '{branch}' on origin", "Geschützter Branch '{branch}' auf origin kann nicht gelöscht werden";
+    LoadFailures => "Failed to load {failures} of {count} work items.", "{failures} von {count} Arbeitselementen konnten nicht geladen werden.";
+    StateChangeFailures => "Failed to change the state of {failures} of {count} work items.", "Der Status von {failures} von {count} Arbeitselementen konnte nicht geändert werden.";
+    NoStdinIds => "No work item IDs found on stdin.", "Keine Arbeitselement-IDs auf stdin gefunden.";
+    NoWebUrl => "Work item #{id} has no web URL to open.", "Arbeitselement #{id} hat keine Web-URL zum Öffnen.";
+    EmptyComment => "Comment text is empty.", "Der Kommentartext ist leer.";
+    StdinIsTerminal => "Expected {what} on stdin, but stdin is a terminal.", "Auf stdin erwartet: {what}, aber stdin ist ein Terminal.";
+    ReadStdinFailed => "Failed to read {what} from stdin", "Lesen von {what} auf stdin fehlgeschlagen";
+    StdinWorkItemIds => "work item IDs", "Arbeitselement-IDs";
+    StdinComment => "the comment text", "Kommentartext";
+    AliasLoop => "Alias loop: {chain}", "Alias-Schleife: {chain}";
+    InvalidAlias => "Invalid alias '{name}': {error}", "Ungültiger Alias '{name}': {error}";
+    EmptyAlias => "Alias '{name}' is empty", "Alias '{name}' ist leer";
+    TemplateUnclosed => "Unclosed '{{' in template; write '{{{{' for a literal brace", "Nicht geschlossenes '{{' in der Vorlage; '{{{{' für eine wörtliche Klammer schreiben";
+    TemplateUnknown => "Unknown placeholder '{{{name}}}' in template. Known placeholders: {known}", "Unbekannter Platzhalter '{{{name}}}' in der Vorlage. Bekannte Platzhalter: {known}";
+    TemplateUnmatched => "Unmatched '}}' in template; write '}}}}' for a literal brace", "Nicht geöffnetes '}}' in der Vorlage; '}}}}' für eine wörtliche Klammer schreiben";
+}
+
+/// `text` in the configured language.
+pub fn t(text: Text) -> &'static str {
+    text.translate(language())
+}
+
+/// `text` in the configured language with its placeholders filled in.
+pub fn tf(text: Text, values: &[(&str, &str)]) -> String {
+    fill(text.translate(language()), values)
+}
+
+/// Fill in a catalog template. The catalog test checks every placeholder, so
+/// a template that does not render is shown unfilled rather than failing.
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    crate::template::render(template, values).unwrap_or_else(|_| template.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn every_translation_has_the_english_placeholders() {
+        for &text in Text::ALL {
+            let english = text.translate(Language::En);
+            let names = placeholders(english);
+            let values: Vec<(&str, &str)> = names.iter().map(|&name| (name, "x")).collect();
+            for language in [Language::En, Language::De] {
+                let translated = text.translate(language);
+                assert!(!translated.is_empty(), "{text:?} is empty in {language:?}");
+                assert_eq!(placeholders(translated), names, "{text:?} in {language:?}");
+                assert!(
+                    crate::template::render(translated, &values).is_ok(),
+                    "{text:?} does not render in {language:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn text_defaults_to_english() {
+        assert_eq!(t(Text::FooterQuit), "quit");
+        assert_eq!(
+            tf(Text::Switched, &[("branch", "feature/1")]),
+            "Switched to branch 'feature/1'"
+        );
+        assert_eq!(
+            fill(
+                Text::Switched.translate(Language::De),
+                &[("branch", "feature/1")]
+            ),
+            "Zu Branch 'feature/1' gewechselt"
+        );
+    }
+}
@@ -0,0 +1,35 @@
+//! Optional diagnostic log file (`--log-file` / `CAZDO_LOG`).
+//!
+//! Without a log file no subscriber is installed and the `tracing` calls across
+//! the crate are no-ops. Requests are logged by method, URL, and status only; the
+//! PAT travels in a header that is never recorded, and every line is redacted in
+//! case a token reaches a message some other way.
+
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use tracing::level_filters::LevelFilter;
+
+use crate::redact::RedactingWriter;
+
+/// Append diagnostics at debug level and above to the file at `path`.
+pub fn init(path: &Path) -> Result<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file: {}", path.display()))?;
+
+    tracing_subscriber::fmt()
+        .with_writer(Mutex::new(RedactingWriter(file)))
+        .with_ansi(false)
+        .with_max_level(LevelFilter::DEBUG)
+        .try_init()
+        .map_err(|error| anyhow::anyhow!(error))
+        .context("Failed to initialize logging")?;
+
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "cazdo started");
+    Ok(())
+}
@@ -4,38 +4,204 @@ mod cli;
 mod commands;
 mod config;
 mod git;
+mod http;
+mod i18n;
+mod logging;
+mod output;
 mod pattern;
+mod redact;
+mod release;
+mod restore;
+mod template;
+mod timestamp;
 mod tui;
 
+use std::io::IsTerminal;
+use std::process::ExitCode;
+
 use anyhow::Result;
 use clap::Parser;
-use cli::{Cli, Commands, ConfigAction};
-use commands::WorkItemOutput;
+use cli::{
+    BoardAction, BranchAction, CacheAction, Cli, Commands, ConfigAction, HooksAction, ListFormat,
+    PipelineAction, PrAction, ProtectAction, ThemeAction, WiAction, WorkItemFormat,
+};
+use output::Verbosity;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+async fn main() -> ExitCode {
+    redact::register_env_secrets();
+
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!(
+                "{}",
+                i18n::tf(
+                    i18n::Text::Error,
+                    &[("error", &redact::redact(&format!("{error:?}")))]
+                )
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run() -> Result<()> {
+    let config = config::Config::load().ok();
+    let aliases = config
+        .as_ref()
+        .map(|config| config.alias.clone())
+        .unwrap_or_default();
+    let cli = Cli::parse_from(cli::expand_aliases(
+        std::env::args_os().collect(),
+        &aliases,
+    )?);
+    if let Some(config) = &config {
+        timestamp::set_time_display(&config.display);
+        i18n::set_language(config.display.lang);
+    }
+
+    if let Some(path) = &cli.log_file {
+        logging::init(path)?;
+    }
+
+    let json = cli.json;
+    output::set_verbosity(if cli.quiet {
+        Verbosity::Quiet
+    } else if cli.verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    });
+    config::set_overrides(config::Overrides {
+        organization_url: cli.org.clone(),
+        project: cli.project.clone(),
+    });
+    output::set_color(
+        !cli.no_color
+            && !output::no_color_env(std::env::var_os("NO_COLOR").as_deref())
+            && std::io::stdout().is_terminal(),
+    );
 
     match cli.command {
         Some(Commands::Config { action }) => match action {
-            ConfigAction::Init => commands::config_init()?,
-            ConfigAction::Show => commands::config_show()?,
-            ConfigAction::Verify => commands::config_verify().await?,
-        },
-        Some(Commands::Wi { id, long, json }) => {
-            let output = if json {
-                WorkItemOutput::Json
-            } else if long {
-                WorkItemOutput::Long
-            } else {
-                WorkItemOutput::Preview
+            ConfigAction::Init => commands::config_init().await?,
+            ConfigAction::Show => commands::config_show(json)?,
+            ConfigAction::Verify { offline } => commands::config_verify(json, offline).await?,
+            ConfigAction::Get { key } => commands::config_get(&key, json)?,
+            ConfigAction::Set { key, value } => commands::config_set(&key, &value)?,
+            ConfigAction::Export => commands::config_export(json)?,
+            ConfigAction::Import { file } => commands::config_import(&file, json)?,
+            ConfigAction::Protect { action } => match action {
+                ProtectAction::Add { patterns } => commands::config_protect_add(&patterns, json)?,
+                ProtectAction::Remove { patterns } => {
+                    commands::config_protect_remove(&patterns, json)?
+                }
+                ProtectAction::List => commands::config_protect_list(json)?,
+            },
+        },
+        Some(Commands::Wi {
+            action: Some(WiAction::Open { id }),
+            ..
+        }) => commands::open_work_item(id).await?,
+        Some(Commands::Wi {
+            action: Some(WiAction::Comment { id, text }),
+            ..
+        }) => commands::comment_work_item(id, text, json).await?,
+        Some(Commands::Wi {
+            action: Some(WiAction::State { id, state }),
+            ..
+        }) => commands::set_work_item_state(id, &state, json).await?,
+        Some(Commands::Wi {
+            action:
+                Some(WiAction::Time {
+                    id,
+                    remaining,
+                    completed,
+                }),
+            ..
+        }) => commands::work_item_time(id, remaining, completed, json).await?,
+        Some(Commands::Wi {
+            action: Some(WiAction::List { query, format }),
+            ..
+        }) => {
+            let format = if json { ListFormat::Json } else { format };
+            commands::list_work_items(query.as_deref(), format).await?;
+        }
+        Some(Commands::Wi {
+            action: None,
+            id,
+            long,
+            format,
+        }) => {
+            let format = match format {
+                _ if json => WorkItemFormat::Json,
+                Some(format) => format,
+                None if output::color_enabled() => WorkItemFormat::Box,
+                None => WorkItemFormat::Plain,
             };
-            commands::show_work_item(id, output).await?;
+            commands::show_work_item(id, format, long).await?;
+        }
+        Some(Commands::Branch { action }) => match action {
+            BranchAction::List { format } => {
+                let format = if json { ListFormat::Json } else { format };
+                commands::branch_list(format).await?;
+            }
+            BranchAction::Status { all, porcelain } => {
+                commands::branch_status(all, porcelain, json).await?
+            }
+            BranchAction::PruneGone { dry_run } => commands::branch_prune_gone(dry_run, json)?,
+            BranchAction::Clean {
+                merged,
+                dry_run,
+                yes,
+            } => commands::branch_clean(merged, dry_run, yes, json)?,
+            BranchAction::Open => commands::branch_open()?,
+        },
+        Some(Commands::Board { action }) => match action {
+            BoardAction::Open { team, sprint } => commands::board_open(team, sprint)?,
+        },
+        Some(Commands::CommitTemplate { id }) => commands::commit_template(id, json).await?,
+        Some(Commands::Cache { action }) => match action {
+            CacheAction::Clear => commands::cache_clear(json)?,
+        },
+        Some(Commands::Hooks { action }) => match action {
+            HooksAction::Install { uninstall, force } => {
+                commands::hooks_install(uninstall, force, json)?
+            }
+        },
+        Some(Commands::Pipeline { action }) => match action {
+            PipelineAction::Open { branch } => commands::pipeline_open(branch).await?,
+        },
+        Some(Commands::Pr { action }) => match action {
+            PrAction::Create { draft, target } => commands::pr_create(draft, target, json).await?,
+            PrAction::List { mine, format } => {
+                let format = if json { ListFormat::Json } else { format };
+                commands::pr_list(mine, format).await?;
+            }
+        },
+        Some(Commands::Query { saved, format }) => {
+            let format = if json { ListFormat::Json } else { format };
+            commands::query_saved(&saved, format).await?;
+        }
+        Some(Commands::Report { since }) => commands::report(since).await?,
+        Some(Commands::Theme { action }) => match action {
+            ThemeAction::Preview { file } => commands::theme_preview(&file)?,
+        },
+        Some(Commands::Version { check }) => commands::version(check, json).await?,
+        Some(Commands::Restore { session, branches }) => {
+            commands::restore(session, &branches, json)?
         }
-        Some(Commands::Update) => commands::update().await?,
+        Some(Commands::Update) => commands::update(json).await?,
         None => {
+            if json {
+                anyhow::bail!("--json is not supported by the interactive TUI");
+            }
+            if cli.verbose {
+                anyhow::bail!("--verbose is not supported by the interactive TUI");
+            }
             // Default: launch interactive TUI
-            commands::interactive().await?;
+            commands::interactive(cli.plain_tui, cli.session_summary.as_deref()).await?;
         }
     }
 
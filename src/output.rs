@@ -0,0 +1,83 @@
+//! Process-wide output settings, set once from the global `--quiet`,
+//! `--verbose`, and `--no-color` flags.
+//!
+//! `--quiet` drops progress messages and decorations so commands print only
+//! their essential values. `--verbose` adds diagnostics (request URLs, timing,
+//! git details) on stderr, keeping stdout parseable. Without color, commands
+//! print plain text with no ANSI styling or terminal hyperlinks.
+
+use std::ffi::OsStr;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+}
+
+static VERBOSITY: OnceLock<Verbosity> = OnceLock::new();
+static COLOR: OnceLock<bool> = OnceLock::new();
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    let _ = VERBOSITY.set(verbosity);
+}
+
+pub fn verbosity() -> Verbosity {
+    VERBOSITY.get().copied().unwrap_or_default()
+}
+
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+pub fn set_color(enabled: bool) {
+    let _ = COLOR.set(enabled);
+}
+
+/// Whether stdout may carry ANSI styling: off with `--no-color` or when
+/// stdout is not a terminal.
+pub fn color_enabled() -> bool {
+    COLOR.get().copied().unwrap_or(false)
+}
+
+/// Whether a `NO_COLOR` value turns color off: any non-empty value does,
+/// as <https://no-color.org> asks.
+pub fn no_color_env(value: Option<&OsStr>) -> bool {
+    value.is_some_and(|value| !value.is_empty())
+}
+
+/// Width of the terminal stdout is attached to, if any.
+pub fn terminal_width() -> Option<u16> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    crossterm::terminal::size().ok().map(|(columns, _)| columns)
+}
+
+/// Print a diagnostic line to stderr when `--verbose` is set, with secrets
+/// redacted.
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if $crate::output::verbosity() == $crate::output::Verbosity::Verbose {
+            eprintln!("[verbose] {}", $crate::redact::redact(&format!($($arg)*)));
+        }
+    };
+}
+
+pub(crate) use verbose;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn any_non_empty_no_color_value_turns_color_off() {
+        assert!(no_color_env(Some(OsStr::new("1"))));
+        assert!(no_color_env(Some(OsStr::new("true"))));
+        assert!(!no_color_env(Some(OsStr::new(""))));
+        assert!(!no_color_env(None));
+    }
+}
@@ -3,15 +3,81 @@
 //! Supports:
 //! - `*` matches any sequence of characters (including empty)
 //! - Literal characters match themselves
+//! - `re:` followed by a regular expression that must match the whole name
 //!
 //! Examples:
 //! - `main` matches only "main"
 //! - `releases/*` matches "releases/v1.0", "releases/v12.x"
 //! - `feature-*-test` matches "feature-123-test", "feature-abc-test"
+//! - `re:release/\d+\.\d+` matches "release/1.2" but not "release/1.2-rc"
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+/// Prefix of patterns that are regular expressions rather than wildcards
+pub const REGEX_PREFIX: &str = "re:";
+
+/// Compiled `re:` patterns; `None` for invalid ones, which match nothing
+static REGEXES: LazyLock<Mutex<HashMap<String, Option<Regex>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Compiled [`first_capture`] expressions, unanchored; `None` for invalid ones
+static CAPTURES: LazyLock<Mutex<HashMap<String, Option<Regex>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
 
 /// Check if a branch name matches a single pattern
 pub fn matches_pattern(text: &str, pattern: &str) -> bool {
-    matches_pattern_impl(text.as_bytes(), pattern.as_bytes())
+    match pattern.strip_prefix(REGEX_PREFIX) {
+        Some(source) => cached_regex(source).is_some_and(|regex| regex.is_match(text)),
+        None => matches_pattern_impl(text.as_bytes(), pattern.as_bytes()),
+    }
+}
+
+/// Check that a `re:` pattern compiles; wildcard patterns are always valid
+pub fn validate_pattern(pattern: &str) -> Result<()> {
+    if let Some(source) = pattern.strip_prefix(REGEX_PREFIX) {
+        compile_regex(source).with_context(|| format!("Invalid regex pattern '{pattern}'"))?;
+    }
+    Ok(())
+}
+
+/// Check that `source` compiles and has a capture group for
+/// [`first_capture`]
+pub fn validate_capture_regex(source: &str) -> Result<()> {
+    let regex = Regex::new(source).with_context(|| format!("Invalid regex '{source}'"))?;
+    if regex.captures_len() < 2 {
+        anyhow::bail!("Regex '{source}' has no capture group");
+    }
+    Ok(())
+}
+
+/// The text of the first capture group of regular expression `source` at
+/// its first match in `text`. An invalid `source` matches nothing.
+pub fn first_capture<'a>(text: &'a str, source: &str) -> Option<&'a str> {
+    let regex = {
+        let mut regexes = CAPTURES.lock().unwrap_or_else(|error| error.into_inner());
+        regexes
+            .entry(source.to_string())
+            .or_insert_with(|| Regex::new(source).ok())
+            .clone()?
+    };
+    Some(regex.captures(text)?.get(1)?.as_str())
+}
+
+/// Anchor `source` so it matches whole branch names, like wildcard patterns
+fn compile_regex(source: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{source})$"))
+}
+
+fn cached_regex(source: &str) -> Option<Regex> {
+    let mut regexes = REGEXES.lock().unwrap_or_else(|error| error.into_inner());
+    regexes
+        .entry(source.to_string())
+        .or_insert_with(|| compile_regex(source).ok())
+        .clone()
 }
 
 fn matches_pattern_impl(text: &[u8], pattern: &[u8]) -> bool {
@@ -111,6 +177,44 @@ mod tests {
         assert!(!is_protected("develop", &patterns));
     }
 
+    #[test]
+    fn test_regex_patterns_match_whole_name() {
+        assert!(matches_pattern("release/1.2", r"re:release/\d+\.\d+"));
+        assert!(!matches_pattern("release/1.2-rc", r"re:release/\d+\.\d+"));
+        assert!(!matches_pattern("old-release/1.2", r"re:release/\d+\.\d+"));
+        assert!(matches_pattern("hotfix/JIRA-12", "re:(hotfix|support)/.*"));
+        assert!(matches_pattern("main", "re:main|master"));
+
+        let patterns = vec!["main".to_string(), r"re:release/\d+".to_string()];
+        assert!(is_protected("release/7", &patterns));
+        assert!(!is_protected("release/x", &patterns));
+    }
+
+    #[test]
+    fn test_invalid_regex_is_reported_and_matches_nothing() {
+        let error = validate_pattern("re:release/(").expect_err("unclosed group");
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid regex pattern 're:release/('")
+        );
+        assert!(!matches_pattern("release/(", "re:release/("));
+
+        assert!(validate_pattern("releases/*").is_ok());
+    }
+
+    #[test]
+    fn test_first_capture() {
+        let source = r"^(?:feature|bug)/(\d+)";
+        assert_eq!(first_capture("feature/123-login", source), Some("123"));
+        assert_eq!(first_capture("release/2024-sprint", source), None);
+        assert_eq!(first_capture("feature/1", "("), None);
+
+        assert!(validate_capture_regex(source).is_ok());
+        assert!(validate_capture_regex(r"feature/\d+").is_err());
+        assert!(validate_capture_regex("(").is_err());
+    }
+
     #[test]
     fn test_star_only() {
         assert!(matches_pattern("anything", "*"));
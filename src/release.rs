@@ -0,0 +1,190 @@
+//! Checking GitHub for a newer cazdo release.
+//!
+//! `cazdo version --check` asks GitHub directly. The TUI hint instead reads a
+//! small cache file that is refreshed in the background at most once a week,
+//! so startup never waits on the network.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use axoupdater::Version;
+use chrono::{DateTime, Utc};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::http;
+use crate::output::verbose;
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/FelixDamrau/cazdo/releases/latest";
+/// How often the TUI hint refreshes its cached check: once a week.
+const CHECK_INTERVAL_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// A published cazdo release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    pub version: Version,
+    pub url: Option<String>,
+}
+
+impl Release {
+    /// Whether this release is newer than the running executable.
+    pub fn is_newer(&self) -> bool {
+        current_version().is_ok_and(|current| self.version > current)
+    }
+}
+
+pub fn current_version() -> Result<Version> {
+    Ok(Version::parse(env!("CARGO_PKG_VERSION"))?)
+}
+
+/// Fetch the latest release from GitHub.
+pub async fn latest_release() -> Result<Release> {
+    let config = Config::load().ok();
+    let client = http::client_builder(config.as_ref().map(|config| &config.http))?
+        .timeout(Duration::from_secs(10))
+        .user_agent(concat!("cazdo/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .context("Failed to configure the release check client")?;
+
+    let mut request = client
+        .get(LATEST_RELEASE_URL)
+        .header("Accept", "application/vnd.github+json");
+    if let Ok(token) = std::env::var("CAZDO_GITHUB_TOKEN") {
+        request = request.bearer_auth(token);
+    }
+
+    verbose!("release: GET {LATEST_RELEASE_URL}");
+    let response = request
+        .send()
+        .await
+        .context("Failed to reach GitHub to check for releases")?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("GitHub release check failed with status {}", status);
+    }
+
+    let json: Value = response
+        .json()
+        .await
+        .context("Failed to read the GitHub release response")?;
+    decode_release(&json)
+}
+
+fn decode_release(json: &Value) -> Result<Release> {
+    let tag = json
+        .get("tag_name")
+        .and_then(Value::as_str)
+        .context("Missing 'tag_name' in GitHub release response")?;
+    let version = Version::parse(tag.trim_start_matches('v'))
+        .with_context(|| format!("Release tag '{}' is not a version", tag))?;
+
+    Ok(Release {
+        version,
+        url: json
+            .get("html_url")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+/// The last weekly check, stored in the cache directory.
+#[derive(Debug, Serialize, Deserialize)]
+struct CheckCache {
+    /// Unix timestamp of the check.
+    checked_at: i64,
+    latest_version: String,
+}
+
+impl CheckCache {
+    fn is_due(&self, now: DateTime<Utc>) -> bool {
+        now.timestamp() - self.checked_at >= CHECK_INTERVAL_SECS
+    }
+
+    /// The cached version, if it is newer than `current`.
+    fn newer_than(&self, current: &Version) -> Option<Version> {
+        Version::parse(&self.latest_version)
+            .ok()
+            .filter(|latest| latest > current)
+    }
+}
+
+fn cache_path() -> Result<PathBuf> {
+    let proj_dirs =
+        ProjectDirs::from("", "", "cazdo").context("Failed to determine cache directory")?;
+    Ok(proj_dirs.cache_dir().join("update-check.json"))
+}
+
+fn read_cache() -> Option<CheckCache> {
+    let content = fs::read_to_string(cache_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// The newer version found by the last weekly check, if any.
+pub fn cached_newer_version() -> Option<Version> {
+    read_cache()?.newer_than(&current_version().ok()?)
+}
+
+/// Whether the weekly check has not run in the last seven days.
+pub fn check_is_due() -> bool {
+    read_cache().is_none_or(|cache| cache.is_due(Utc::now()))
+}
+
+/// Fetch the latest release and record it for the next startup.
+pub async fn refresh_cache() -> Result<()> {
+    let release = latest_release().await?;
+    let cache = CheckCache {
+        checked_at: Utc::now().timestamp(),
+        latest_version: release.version.to_string(),
+    };
+
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create cache directory: {}", parent.display()))?;
+    }
+    fs::write(&path, serde_json::to_string(&cache)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_tag_without_leading_v() {
+        let json = serde_json::json!({
+            "tag_name": "v1.4.0",
+            "html_url": "https://github.com/FelixDamrau/cazdo/releases/tag/v1.4.0"
+        });
+
+        let release = decode_release(&json).unwrap();
+
+        assert_eq!(release.version, Version::new(1, 4, 0));
+        assert!(release.url.is_some());
+        assert!(decode_release(&serde_json::json!({ "tag_name": "nightly" })).is_err());
+    }
+
+    #[test]
+    fn cache_reports_only_newer_versions_and_expires_after_a_week() {
+        let checked_at = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let cache = CheckCache {
+            checked_at: checked_at.timestamp(),
+            latest_version: "1.4.0".to_string(),
+        };
+
+        assert_eq!(
+            cache.newer_than(&Version::new(1, 3, 2)),
+            Some(Version::new(1, 4, 0))
+        );
+        assert_eq!(cache.newer_than(&Version::new(1, 4, 0)), None);
+        assert!(!cache.is_due(checked_at + chrono::Duration::days(6)));
+        assert!(cache.is_due(checked_at + chrono::Duration::days(7)));
+    }
+}
@@ -0,0 +1,181 @@
+//! Branches deleted in TUI sessions, kept so they can be restored later.
+//!
+//! Each session that deletes local branches writes one timestamped file to
+//! the data directory. The file is a shell script that recreates the
+//! branches, and its header comments record the repository and each branch
+//! with its commit, which `cazdo restore` reads to list and replay sessions.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use directories::ProjectDirs;
+
+const FILE_NAME_FORMAT: &str = "%Y%m%d-%H%M%S";
+const REPO_PREFIX: &str = "# repo: ";
+const BRANCH_PREFIX: &str = "# branch: ";
+
+/// A local branch deleted in a session, with the commit it pointed at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedBranch {
+    pub name: String,
+    pub commit_sha: String,
+}
+
+/// The branches one session deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub path: PathBuf,
+    pub deleted_at: DateTime<Local>,
+    pub repo_dir: PathBuf,
+    pub branches: Vec<DeletedBranch>,
+}
+
+fn sessions_dir() -> Result<PathBuf> {
+    let proj_dirs =
+        ProjectDirs::from("", "", "cazdo").context("Failed to determine data directory")?;
+    Ok(proj_dirs.data_dir().join("deleted-branches"))
+}
+
+/// Write the branches deleted in `repo_dir` this session, returning the file.
+pub fn write_session(repo_dir: &Path, branches: &[DeletedBranch]) -> Result<PathBuf> {
+    let dir = sessions_dir()?;
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let deleted_at = Local::now();
+    let path = dir.join(format!("{}.sh", deleted_at.format(FILE_NAME_FORMAT)));
+    fs::write(&path, restore_script(repo_dir, branches, deleted_at))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    make_executable(&path)?;
+    Ok(path)
+}
+
+/// The recorded sessions, newest first. Files that do not parse are skipped.
+pub fn sessions() -> Result<Vec<Session>> {
+    let dir = sessions_dir()?;
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => {
+            return Err(error).with_context(|| format!("Failed to read {}", dir.display()));
+        }
+    };
+    let mut sessions: Vec<Session> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let content = fs::read_to_string(&path).ok()?;
+            parse_session(path, &content)
+        })
+        .collect();
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.deleted_at));
+    Ok(sessions)
+}
+
+/// A script recreating `branches` in `repo_dir`, with header comments that
+/// [`parse_session`] reads back.
+fn restore_script(
+    repo_dir: &Path,
+    branches: &[DeletedBranch],
+    deleted_at: DateTime<Local>,
+) -> String {
+    let mut script = format!(
+        "#!/bin/sh\n# Branches deleted by cazdo on {}\n{REPO_PREFIX}{}\n",
+        deleted_at.format("%Y-%m-%d %H:%M:%S"),
+        repo_dir.display()
+    );
+    for branch in branches {
+        script.push_str(&format!(
+            "{BRANCH_PREFIX}{} {}\n",
+            branch.name, branch.commit_sha
+        ));
+    }
+    script.push_str(&format!(
+        "\nset -e\ncd {}\n",
+        shell_quote(&repo_dir.to_string_lossy())
+    ));
+    for branch in branches {
+        script.push_str(&format!(
+            "git branch {} {}\n",
+            shell_quote(&branch.name),
+            branch.commit_sha
+        ));
+    }
+    script
+}
+
+/// The session recorded in `content`, timed by its file name.
+fn parse_session(path: PathBuf, content: &str) -> Option<Session> {
+    let stem = path.file_stem()?.to_str()?;
+    let deleted_at = NaiveDateTime::parse_from_str(stem, FILE_NAME_FORMAT).ok()?;
+    let deleted_at = Local.from_local_datetime(&deleted_at).earliest()?;
+    let repo_dir = content
+        .lines()
+        .find_map(|line| line.strip_prefix(REPO_PREFIX))?
+        .into();
+    // Git does not allow whitespace in branch names
+    let branches = content
+        .lines()
+        .filter_map(|line| line.strip_prefix(BRANCH_PREFIX))
+        .filter_map(|entry| {
+            let (name, commit_sha) = entry.split_once(' ')?;
+            Some(DeletedBranch {
+                name: name.to_string(),
+                commit_sha: commit_sha.trim().to_string(),
+            })
+        })
+        .collect();
+    Some(Session {
+        path,
+        deleted_at,
+        repo_dir,
+        branches,
+    })
+}
+
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_restores_the_branches_and_reads_back_as_a_session() {
+        let branches = vec![
+            DeletedBranch {
+                name: "feature/1234-login".to_string(),
+                commit_sha: "3f2a1c9d".to_string(),
+            },
+            DeletedBranch {
+                name: "bugfix/it's".to_string(),
+                commit_sha: "9e8d7c6b".to_string(),
+            },
+        ];
+        let deleted_at = Local.with_ymd_and_hms(2026, 10, 15, 14, 3, 22).unwrap();
+        let script = restore_script(Path::new("/work/shop"), &branches, deleted_at);
+
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("cd '/work/shop'\ngit branch 'feature/1234-login' 3f2a1c9d\n"));
+        assert!(script.contains(r"git branch 'bugfix/it'\''s' 9e8d7c6b"));
+
+        let session = parse_session(PathBuf::from("20261015-140322.sh"), &script).unwrap();
+        assert_eq!(session.deleted_at, deleted_at);
+        assert_eq!(session.repo_dir, Path::new("/work/shop"));
+        assert_eq!(session.branches, branches);
+        assert!(parse_session(PathBuf::from("notes.sh"), &script).is_none());
+    }
+}
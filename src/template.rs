@@ -0,0 +1,80 @@
+//! `{placeholder}` templates for text cazdo generates from a work item, such
+//! as the commit message skeleton.
+//!
+//! `{{` and `}}` produce literal braces. Unknown placeholders are errors, so a
+//! typo in the config is reported instead of silently printed.
+
+use anyhow::{Result, bail};
+
+use crate::i18n::{Text, t, tf};
+
+/// Replace each `{name}` in `template` with its value from `values`.
+pub fn render(template: &str, values: &[(&str, &str)]) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                out.push('}');
+            }
+            '{' => {
+                let rest = chars.as_str();
+                let Some(end) = rest.find('}') else {
+                    bail!("{}", t(Text::TemplateUnclosed));
+                };
+                let name = &rest[..end];
+                let Some((_, value)) = values.iter().find(|(key, _)| *key == name) else {
+                    let known: Vec<String> =
+                        values.iter().map(|(key, _)| format!("{{{key}}}")).collect();
+                    bail!(
+                        "{}",
+                        tf(
+                            Text::TemplateUnknown,
+                            &[("name", name), ("known", &known.join(", "))]
+                        )
+                    );
+                };
+                out.push_str(value);
+                chars = rest[end + 1..].chars();
+            }
+            '}' => bail!("{}", t(Text::TemplateUnmatched)),
+            c => out.push(c),
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_placeholders_and_escaped_braces() {
+        let rendered = render(
+            "{title}\n\nAB#{id} {{done}}",
+            &[("id", "42"), ("title", "Fix login")],
+        )
+        .unwrap();
+
+        assert_eq!(rendered, "Fix login\n\nAB#42 {done}");
+    }
+
+    #[test]
+    fn rejects_unknown_placeholders_and_stray_braces() {
+        let error = render("{titel}", &[("id", "42"), ("title", "x")]).expect_err("typo");
+        assert_eq!(
+            error.to_string(),
+            "Unknown placeholder '{titel}' in template. Known placeholders: {id}, {title}"
+        );
+
+        assert!(render("{id", &[("id", "42")]).is_err());
+        assert!(render("id}", &[("id", "42")]).is_err());
+    }
+}
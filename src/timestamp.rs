@@ -0,0 +1,125 @@
+//! How times are shown, from `[display]`: relative ("3 days ago") or
+//! absolute with a strftime format, in local time or UTC.
+//!
+//! Set once at startup like the output settings; until then, and in tests,
+//! times are relative.
+
+use std::sync::OnceLock;
+
+use anyhow::{Result, anyhow};
+use chrono::format::StrftimeItems;
+use chrono::{DateTime, Local, Utc};
+use chrono_humanize::HumanTime;
+
+use crate::config::{DisplayConfig, TimeZoneMode, TimestampStyle};
+
+/// Format for absolute timestamps unless `[display].time_format` is set
+pub const DEFAULT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+const CLOCK_FORMAT: &str = "%H:%M";
+
+#[derive(Debug, Clone, Default)]
+struct TimeDisplay {
+    style: TimestampStyle,
+    format: Option<String>,
+    timezone: TimeZoneMode,
+}
+
+static TIME_DISPLAY: OnceLock<TimeDisplay> = OnceLock::new();
+
+/// Use the timestamp settings of `display` for the rest of the run.
+pub fn set_time_display(display: &DisplayConfig) {
+    let _ = TIME_DISPLAY.set(TimeDisplay {
+        style: display.timestamps,
+        format: display.time_format.clone(),
+        timezone: display.timezone,
+    });
+}
+
+fn current() -> TimeDisplay {
+    TIME_DISPLAY.get().cloned().unwrap_or_default()
+}
+
+/// A Unix timestamp as configured, or `None` if it is out of range.
+pub fn format_timestamp(timestamp: i64) -> Option<String> {
+    let time = DateTime::<Utc>::from_timestamp(timestamp, 0)?;
+    Some(format_time(time, &current()))
+}
+
+/// The calendar date of `time` in the configured time zone, for dates that
+/// stay absolute such as a report's generation date.
+pub fn format_date(time: DateTime<Utc>) -> String {
+    format_in_zone(time, DATE_FORMAT, current().timezone)
+}
+
+/// The time of day of `time` in the configured time zone, for events of
+/// one session such as those of the TUI's session summary.
+pub fn format_clock(time: DateTime<Utc>) -> String {
+    format_in_zone(time, CLOCK_FORMAT, current().timezone)
+}
+
+fn format_time(time: DateTime<Utc>, display: &TimeDisplay) -> String {
+    match display.style {
+        TimestampStyle::Relative => HumanTime::from(time).to_string(),
+        TimestampStyle::Absolute => format_in_zone(
+            time,
+            display.format.as_deref().unwrap_or(DEFAULT_TIME_FORMAT),
+            display.timezone,
+        ),
+    }
+}
+
+fn format_in_zone(time: DateTime<Utc>, format: &str, timezone: TimeZoneMode) -> String {
+    match timezone {
+        TimeZoneMode::Local => time.with_timezone(&Local).format(format).to_string(),
+        TimeZoneMode::Utc => time.format(format).to_string(),
+    }
+}
+
+/// Check that `format` is a strftime format chrono can render.
+pub fn validate_time_format(format: &str) -> Result<()> {
+    StrftimeItems::new(format)
+        .parse()
+        .map(|_| ())
+        .map_err(|_| anyhow!("'{format}' is not a valid strftime format (e.g. %Y-%m-%d %H:%M)"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(format: Option<&str>) -> TimeDisplay {
+        TimeDisplay {
+            style: TimestampStyle::Absolute,
+            format: format.map(str::to_string),
+            timezone: TimeZoneMode::Utc,
+        }
+    }
+
+    #[test]
+    fn absolute_times_use_format_and_zone() {
+        let time = Utc.with_ymd_and_hms(2024, 5, 15, 9, 30, 0).unwrap();
+
+        assert_eq!(format_time(time, &utc(None)), "2024-05-15 09:30");
+        assert_eq!(
+            format_time(time, &utc(Some("%d.%m.%Y %H:%M:%S %Z"))),
+            "15.05.2024 09:30:00 UTC"
+        );
+    }
+
+    #[test]
+    fn relative_times_are_humanized() {
+        let time = Utc::now() - chrono::Duration::days(3);
+
+        assert_eq!(format_time(time, &TimeDisplay::default()), "3 days ago");
+    }
+
+    #[test]
+    fn rejects_invalid_formats() {
+        assert!(validate_time_format("%Y-%m-%d").is_ok());
+        assert!(validate_time_format("%Q").is_err());
+    }
+}
@@ -1,13 +1,28 @@
 mod actions;
 mod app;
 mod background;
+mod cache;
+mod compat;
+mod emoji;
 mod event;
+mod fetch;
 mod html_render;
 mod input;
+mod keymap;
 mod markdown_render;
+mod preview;
+mod session;
+mod shutdown;
 pub mod theme;
 mod ui;
 
+pub use actions::open_url;
 pub use app::{App, BranchInfo};
+pub use cache::clear as clear_work_item_cache;
+pub use compat::unsupported_modifiers;
+pub use emoji::{ascii_fallback, emoji_enabled};
 pub use event::run_app;
-pub use html_render::render_html;
+pub use html_render::{html_to_markdown, render_html};
+pub use keymap::Keymap;
+pub use preview::render_preview;
+pub use ui::wrap_text;
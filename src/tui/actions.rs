@@ -1,61 +1,188 @@
 use anyhow::Result;
 
-use super::app::{App, BranchInfo, BranchView, Msg, WorkItemStatus};
+use super::app::{
+    App, BranchInfo, BranchView, DeleteOutcome, Msg, PullRequestsStatus, WorkItemStatus,
+};
 use super::theme::timing;
-use crate::git::{BranchScope, DeleteResult, GitRepo, short_sha};
+use crate::git::{BranchScope, DeleteResult, GitRepo, RemoteDeletion, short_sha};
+use crate::i18n::{Text, t, tf};
 
 pub(super) fn open_current_work_item(app: &mut App) {
     open_current_work_item_with(app, open_url);
 }
 
-pub(super) fn execute_delete_branch(app: &mut App, git_repo: &GitRepo, branch: &BranchInfo) {
+pub(super) fn open_work_item(app: &mut App, wi_id: u32) {
+    open_work_item_with(app, wi_id, open_url);
+}
+
+pub(super) fn open_current_pull_request(app: &mut App) {
+    open_current_pull_request_with(app, open_url);
+}
+
+pub(super) fn open_listed_pull_request(app: &mut App) {
+    open_listed_pull_request_with(app, open_url);
+}
+
+/// Create the local branch deleted last this session again at the commit it
+/// pointed at. A remote branch deleted along with it stays deleted.
+pub(super) fn execute_undo_delete(app: &mut App, git_repo: &GitRepo) {
+    let Some((branch, commit_sha)) = app.last_undoable_deletion() else {
+        app.set_status_message(
+            t(Text::NothingToUndo).to_string(),
+            false,
+            timing::STATUS_DURATION_SECS,
+        );
+        return;
+    };
+    let name = branch.branch_name.clone();
+    let commit_sha = commit_sha.to_string();
+    match git_repo.restore_branch(&name, &commit_sha) {
+        Ok(()) => {
+            app.update(Msg::BranchRestored);
+            app.set_status_message(
+                tf(
+                    Text::Restored,
+                    &[("branch", &name), ("sha", short_sha(&commit_sha))],
+                ),
+                false,
+                timing::STATUS_DURATION_SECS,
+            );
+        }
+        Err(error) => app.set_status_message(
+            tf(
+                Text::RestoreFailed,
+                &[("branch", &name), ("error", &format!("{error:#}"))],
+            ),
+            true,
+            timing::STATUS_DURATION_SECS,
+        ),
+    }
+}
+
+/// Delete `branch`, and for a local branch `with_remote`, the branch it
+/// tracks on origin after it.
+pub(super) fn execute_delete_branch(
+    app: &mut App,
+    git_repo: &GitRepo,
+    branch: &BranchInfo,
+    with_remote: bool,
+) {
+    let (message, is_error) = delete_branch(app, git_repo, branch, with_remote);
+    app.set_status_message(message, is_error, timing::STATUS_DURATION_SECS);
+}
+
+/// Delete the marked branches one at a time, pruning stale remote ones, and
+/// show how each went. Branches that cannot be deleted are reported, not
+/// skipped silently.
+pub(super) fn execute_delete_marked(app: &mut App, git_repo: &GitRepo) {
+    let branches: Vec<BranchInfo> = app.marked_branches().into_iter().cloned().collect();
+    let outcomes = branches
+        .iter()
+        .map(|branch| {
+            let (message, is_error) = match App::can_delete(branch) {
+                Err(error) => (error, true),
+                Ok(()) if branch.is_stale => prune_branch(app, git_repo, branch),
+                Ok(()) => delete_branch(app, git_repo, branch, false),
+            };
+            DeleteOutcome {
+                branch: branch.display_name.clone(),
+                message,
+                is_error,
+            }
+        })
+        .collect();
+    app.update(Msg::ShowDeleteResults(outcomes));
+}
+
+/// Delete `branch`, returning the status message and whether it is an error.
+fn delete_branch(
+    app: &mut App,
+    git_repo: &GitRepo,
+    branch: &BranchInfo,
+    with_remote: bool,
+) -> (String, bool) {
     match git_repo.delete_branch(
         branch.scope,
         &branch.branch_name,
         branch.remote_name.as_deref(),
+        with_remote,
         app.protected_patterns(),
     ) {
-        Ok(DeleteResult::Local { commit_sha }) => {
+        Ok(DeleteResult::Local { commit_sha, remote }) => {
             let restore_hint = format!("git checkout -b {} {}", branch.branch_name, commit_sha);
+            let sha = short_sha(&commit_sha);
+            let (message, is_error) = match &remote {
+                None => (
+                    tf(
+                        Text::Deleted,
+                        &[("branch", &branch.display_name), ("sha", sha)],
+                    ),
+                    false,
+                ),
+                Some(RemoteDeletion {
+                    remote_ref,
+                    result: Ok(()),
+                }) => (
+                    tf(
+                        Text::DeletedWithRemote,
+                        &[
+                            ("branch", &branch.display_name),
+                            ("remote", remote_ref),
+                            ("sha", sha),
+                        ],
+                    ),
+                    false,
+                ),
+                Some(RemoteDeletion {
+                    remote_ref,
+                    result: Err(error),
+                }) => (
+                    tf(
+                        Text::DeletedRemoteFailed,
+                        &[
+                            ("branch", &branch.display_name),
+                            ("remote", remote_ref),
+                            ("sha", sha),
+                            ("error", error),
+                        ],
+                    ),
+                    true,
+                ),
+            };
             app.update(Msg::BranchDeleted {
                 key: branch.key.clone(),
                 name: branch.display_name.clone(),
                 restore_hint: Some(restore_hint),
+                commit_sha: Some(commit_sha.clone()),
+                remote: remote
+                    .filter(|remote| remote.result.is_ok())
+                    .map(|remote| remote.remote_ref),
             });
-            app.set_status_message(
-                format!(
-                    "Deleted {} (was {})",
-                    branch.display_name,
-                    short_sha(&commit_sha)
-                ),
-                false,
-                timing::STATUS_DURATION_SECS,
-            );
+            (message, is_error)
         }
-        Ok(DeleteResult::Remote) => {
-            apply_remote_delete_result(
-                app,
-                branch,
-                git_repo.prune_remote_tracking_branch(&branch.branch_name),
-            );
-        }
-        Err(error) => app.set_status_message(error.to_string(), true, timing::STATUS_DURATION_SECS),
+        Ok(DeleteResult::Remote) => record_remote_delete(
+            app,
+            branch,
+            git_repo.prune_remote_tracking_branch(&branch.branch_name),
+        ),
+        Err(error) => (error.to_string(), true),
     }
 }
 
 pub(super) fn execute_prune_branch(app: &mut App, git_repo: &GitRepo, branch: &BranchInfo) {
+    let (message, is_error) = prune_branch(app, git_repo, branch);
+    app.set_status_message(message, is_error, timing::STATUS_DURATION_SECS);
+}
+
+fn prune_branch(app: &mut App, git_repo: &GitRepo, branch: &BranchInfo) -> (String, bool) {
     match git_repo.prune_remote_tracking_branch(&branch.branch_name) {
         Ok(()) => {
             app.update(Msg::BranchPruned {
                 key: branch.key.clone(),
             });
-            app.set_status_message(
-                format!("Pruned stale tracking ref '{}'", branch.display_name),
-                false,
-                timing::STATUS_DURATION_SECS,
-            );
+            (tf(Text::Pruned, &[("branch", &branch.display_name)]), false)
         }
-        Err(error) => app.set_status_message(error.to_string(), true, timing::STATUS_DURATION_SECS),
+        Err(error) => (error.to_string(), true),
     }
 }
 
@@ -78,7 +205,7 @@ pub(super) fn execute_checkout_branch(app: &mut App, git_repo: &GitRepo, branch:
                 app.focus_local_branch(&branch.branch_name);
             }
             app.set_status_message(
-                format!("Switched to branch '{}'", branch.branch_name),
+                tf(Text::Switched, &[("branch", &branch.branch_name)]),
                 false,
                 timing::STATUS_DURATION_SECS,
             );
@@ -89,10 +216,7 @@ pub(super) fn execute_checkout_branch(app: &mut App, git_repo: &GitRepo, branch:
 
 pub(super) fn stale_remote_checkout_error_message(branch: &BranchInfo) -> Option<String> {
     if branch.scope == BranchScope::Remote && branch.is_stale {
-        Some(format!(
-            "'{}' is stale (no longer on origin). Prune it first with 'd'.",
-            branch.display_name
-        ))
+        Some(tf(Text::StaleCheckout, &[("branch", &branch.display_name)]))
     } else {
         None
     }
@@ -102,20 +226,97 @@ fn open_current_work_item_with<F>(app: &mut App, open: F)
 where
     F: FnOnce(&str) -> Result<()>,
 {
-    if let Some(wi_id) = app.selected_work_item_id()
-        && let WorkItemStatus::Loaded(wi) = app.get_work_item_status(wi_id)
+    if let Some(wi_id) = app.selected_work_item_id() {
+        open_work_item_with(app, wi_id, open);
+    }
+}
+
+/// Open work item `wi_id` in the browser if it is loaded.
+fn open_work_item_with<F>(app: &mut App, wi_id: u32, open: F)
+where
+    F: FnOnce(&str) -> Result<()>,
+{
+    if let WorkItemStatus::Loaded(wi) = app.get_work_item_status(wi_id)
         && let Some(ref url) = wi.url
-        && let Err(error) = open(url)
     {
+        tracing::info!(wi_id, url, "open work item in browser");
+        if let Err(error) = open(url) {
+            app.set_status_message(
+                tf(Text::OpenWorkItemFailed, &[("error", &error.to_string())]),
+                true,
+                timing::STATUS_DURATION_SECS,
+            );
+        }
+    }
+}
+
+/// Open the first pull request listed for the selected work item: the
+/// newest active one, if any.
+fn open_current_pull_request_with<F>(app: &mut App, open: F)
+where
+    F: FnOnce(&str) -> Result<()>,
+{
+    let Some(wi_id) = app.selected_work_item_id() else {
+        return;
+    };
+    let url = match app.get_pull_requests(wi_id) {
+        Some(PullRequestsStatus::Loaded(pull_requests)) => pull_requests
+            .iter()
+            .find_map(|pr| Some((pr.id, pr.url.clone()?))),
+        _ => return,
+    };
+    let Some((pr_id, url)) = url else {
         app.set_status_message(
-            format!("Could not open work item in browser: {}", error),
+            t(Text::NoPullRequest).to_string(),
+            false,
+            timing::STATUS_DURATION_SECS,
+        );
+        return;
+    };
+
+    tracing::info!(wi_id, pr_id, url, "open pull request in browser");
+    if let Err(error) = open(&url) {
+        app.set_status_message(
+            tf(
+                Text::OpenPullRequestFailed,
+                &[("error", &error.to_string())],
+            ),
             true,
             timing::STATUS_DURATION_SECS,
         );
     }
 }
 
-fn apply_remote_delete_result(app: &mut App, branch: &BranchInfo, prune_result: Result<()>) {
+/// Open the pull request highlighted in the pull request tab.
+fn open_listed_pull_request_with<F>(app: &mut App, open: F)
+where
+    F: FnOnce(&str) -> Result<()>,
+{
+    let Some((pr_id, url)) = app
+        .selected_listed_pull_request()
+        .and_then(|pr| Some((pr.id, pr.url.clone()?)))
+    else {
+        return;
+    };
+
+    tracing::info!(pr_id, url, "open pull request in browser");
+    if let Err(error) = open(&url) {
+        app.set_status_message(
+            tf(
+                Text::OpenPullRequestFailed,
+                &[("error", &error.to_string())],
+            ),
+            true,
+            timing::STATUS_DURATION_SECS,
+        );
+    }
+}
+
+fn record_remote_delete(
+    app: &mut App,
+    branch: &BranchInfo,
+    prune_result: Result<()>,
+) -> (String, bool) {
     let (message, is_error) = remote_delete_status_message(&branch.display_name, prune_result);
 
     if is_error {
@@ -128,26 +329,28 @@ fn apply_remote_delete_result(app: &mut App, branch: &BranchInfo, prune_result:
             key: branch.key.clone(),
             name: branch.display_name.clone(),
             restore_hint: None,
+            commit_sha: None,
+            remote: None,
         });
     }
-
-    app.set_status_message(message, is_error, timing::STATUS_DURATION_SECS);
+    (message, is_error)
 }
 
 fn remote_delete_status_message(display_name: &str, prune_result: Result<()>) -> (String, bool) {
     match prune_result {
-        Ok(()) => (format!("Deleted remote branch '{}'", display_name), false),
+        Ok(()) => (tf(Text::DeletedRemote, &[("branch", display_name)]), false),
         Err(error) => (
-            format!(
-                "Deleted remote branch '{}', but could not prune tracking ref: {}",
-                display_name, error
+            tf(
+                Text::DeletedRemotePruneFailed,
+                &[("branch", display_name), ("error", &error.to_string())],
             ),
             true,
         ),
     }
 }
 
-fn open_url(url: &str) -> Result<()> {
+/// Open `url` in the platform's default browser.
+pub fn open_url(url: &str) -> Result<()> {
     #[cfg(target_os = "windows")]
     {
         std::process::Command::new("cmd")
@@ -168,9 +371,10 @@ fn open_url(url: &str) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::azure_devops::{WorkItem, WorkItemState, WorkItemType};
-    use crate::git::FixtureGitRepo;
+    use crate::azure_devops::{PullRequest, WorkItem, WorkItemState, WorkItemType};
+    use crate::git::{FIXTURE_COMMIT_SHA, FixtureGitRepo, RepoBranch};
     use crate::tui::app::{AppMode, Msg};
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_remote_delete_status_message_reports_prune_failure() {
@@ -200,7 +404,7 @@ mod tests {
         let mut app = App::new(vec![branch.clone()], vec![]);
         app.update(Msg::ToggleView);
 
-        apply_remote_delete_result(
+        let (message, is_error) = record_remote_delete(
             &mut app,
             &branch,
             Err(anyhow::anyhow!("could not prune tracking ref")),
@@ -210,12 +414,8 @@ mod tests {
             .branch_by_key("refs/remotes/origin/feature/1")
             .expect("branch should remain visible");
         assert!(branch.is_stale);
-
-        let status = app
-            .get_status_message()
-            .expect("status message should be set");
-        assert!(status.is_error);
-        assert!(status.text.contains("could not prune tracking ref"));
+        assert!(is_error);
+        assert!(message.contains("could not prune tracking ref"));
     }
 
     #[test]
@@ -224,7 +424,7 @@ mod tests {
         let mut app = App::new(vec![branch.clone()], vec![]);
         app.update(Msg::ToggleView);
 
-        apply_remote_delete_result(
+        record_remote_delete(
             &mut app,
             &branch,
             Err(anyhow::anyhow!("could not prune tracking ref")),
@@ -262,6 +462,7 @@ mod tests {
             is_current: true,
             is_protected: false,
             is_stale: false,
+            origin_upstream: None,
         };
         let mut app = App::new(vec![local_branch], vec![]);
         app.update(Msg::ToggleView);
@@ -286,6 +487,7 @@ mod tests {
                     is_current: true,
                     is_protected: false,
                     is_stale: false,
+                    origin_upstream: None,
                 },
                 BranchInfo {
                     key: "refs/heads/feature/4".to_string(),
@@ -297,6 +499,7 @@ mod tests {
                     is_current: false,
                     is_protected: false,
                     is_stale: false,
+                    origin_upstream: None,
                 },
             ],
             vec![],
@@ -326,6 +529,7 @@ mod tests {
                 is_current: false,
                 is_protected: false,
                 is_stale: false,
+                origin_upstream: None,
             }],
             vec![],
         );
@@ -340,6 +544,7 @@ mod tests {
                 url: Some("https://example.test/items/42".to_string()),
                 tags: vec![],
                 rich_text_fields: vec![],
+                fields: BTreeMap::new(),
             },
         );
 
@@ -355,6 +560,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_open_current_pull_request_opens_the_first_linked_one() {
+        let mut app = App::new(
+            vec![BranchInfo {
+                key: "refs/heads/feature/1".to_string(),
+                display_name: "feature/1".to_string(),
+                branch_name: "feature/1".to_string(),
+                remote_name: None,
+                scope: BranchScope::Local,
+                work_item_id: Some(42),
+                is_current: false,
+                is_protected: false,
+                is_stale: false,
+                origin_upstream: None,
+            }],
+            vec![],
+        );
+        app.set_work_item_loaded(
+            42,
+            WorkItem {
+                id: 42,
+                title: "Open me".to_string(),
+                work_item_type: WorkItemType::Task,
+                state: WorkItemState::Active,
+                assigned_to: None,
+                url: None,
+                tags: vec![],
+                rich_text_fields: vec![],
+                fields: BTreeMap::new(),
+            },
+        );
+        app.set_pull_requests(42, Ok(vec![]));
+
+        open_current_pull_request_with(&mut app, |_| panic!("nothing to open"));
+        let status = app.get_status_message().expect("status message");
+        assert_eq!(status.text, "No pull request linked to this work item");
+
+        let pull_request = |id: u32| PullRequest {
+            id,
+            title: "Login".to_string(),
+            status: "active".to_string(),
+            is_draft: false,
+            source_branch: "feature/1".to_string(),
+            target_branch: "main".to_string(),
+            created_by: None,
+            project_id: None,
+            repository_id: None,
+            last_merge_source_commit: None,
+            reviewers: vec![],
+            url: Some(format!("https://example.test/pullrequest/{id}")),
+        };
+        app.set_pull_requests(42, Ok(vec![pull_request(7), pull_request(3)]));
+        let mut opened = None;
+        open_current_pull_request_with(&mut app, |url| {
+            opened = Some(url.to_string());
+            Ok(())
+        });
+        assert_eq!(
+            opened.as_deref(),
+            Some("https://example.test/pullrequest/7")
+        );
+    }
+
     #[test]
     fn test_stale_remote_checkout_error_message_reports_prune_hint() {
         let branch = remote_branch(true);
@@ -390,10 +658,11 @@ mod tests {
         let git_repo = GitRepo::fixture(FixtureGitRepo::new().with_delete_result(Ok(
             DeleteResult::Local {
                 commit_sha: "abcdef1234567".to_string(),
+                remote: None,
             },
         )));
 
-        execute_delete_branch(&mut app, &git_repo, &branch);
+        execute_delete_branch(&mut app, &git_repo, &branch, false);
 
         assert!(app.branch_by_key("refs/heads/feature/1").is_none());
         assert_eq!(app.deleted_branches().len(), 1);
@@ -406,13 +675,72 @@ mod tests {
         assert_eq!(status.text, "Deleted feature/1 (was abcdef1)");
     }
 
+    #[test]
+    fn test_execute_delete_marked_reports_each_branch() {
+        let names = ["feature/1", "feature/2", "feature/3"];
+        let mut app = App::new(names.map(local_branch).to_vec(), vec![]);
+        let git_repo = GitRepo::fixture(
+            FixtureGitRepo::new()
+                .with_local_branch("feature/1", false)
+                .with_local_branch("feature/3", false),
+        );
+        app.update(Msg::ToggleMark);
+        app.update(Msg::NextBranch);
+        app.update(Msg::NextBranch);
+        app.update(Msg::MarkRange);
+        assert_eq!(app.marked_branches().len(), 3);
+
+        execute_delete_marked(&mut app, &git_repo);
+
+        let outcomes = app.delete_outcomes().expect("results popup");
+        let failed: Vec<&str> = outcomes
+            .iter()
+            .filter(|outcome| outcome.is_error)
+            .map(|outcome| outcome.branch.as_str())
+            .collect();
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(failed, ["feature/2"]);
+        assert!(app.branch_by_key("refs/heads/feature/1").is_none());
+        assert!(app.branch_by_key("refs/heads/feature/2").is_some());
+        assert!(!app.has_marked_branches());
+        assert_eq!(app.deleted_branches().len(), 2);
+    }
+
+    #[test]
+    fn test_execute_undo_delete_lists_the_last_deleted_branch_again() {
+        let branch = local_branch("feature/1");
+        let mut app = App::new(vec![branch.clone(), local_branch("feature/2")], vec![]);
+        let deleting_repo = GitRepo::fixture(FixtureGitRepo::new().with_delete_result(Ok(
+            DeleteResult::Local {
+                commit_sha: "abcdef1234567".to_string(),
+                remote: None,
+            },
+        )));
+        execute_delete_branch(&mut app, &deleting_repo, &branch, false);
+        assert!(app.branch_by_key("refs/heads/feature/1").is_none());
+
+        let git_repo = GitRepo::fixture(FixtureGitRepo::new());
+        execute_undo_delete(&mut app, &git_repo);
+
+        assert_eq!(app.selected_branch().unwrap().branch_name, "feature/1");
+        assert!(app.deleted_branches().is_empty());
+        let status = app.get_status_message().expect("status message");
+        assert_eq!(status.text, "Restored feature/1 at abcdef1");
+
+        execute_undo_delete(&mut app, &git_repo);
+        assert_eq!(
+            app.get_status_message().unwrap().text,
+            "No deleted branch to restore"
+        );
+    }
+
     #[test]
     fn test_execute_delete_branch_rejects_protected_branch_via_fixture() {
         let branch = local_branch("main");
         let mut app = App::new(vec![branch.clone()], vec!["main".to_string()]);
         let git_repo = GitRepo::fixture(FixtureGitRepo::new());
 
-        execute_delete_branch(&mut app, &git_repo, &branch);
+        execute_delete_branch(&mut app, &git_repo, &branch, false);
 
         assert!(app.branch_by_key("refs/heads/main").is_some());
         let status = app.get_status_message().expect("status message");
@@ -430,7 +758,7 @@ mod tests {
                 .with_prune_result(Ok(())),
         );
 
-        execute_delete_branch(&mut app, &git_repo, &branch);
+        execute_delete_branch(&mut app, &git_repo, &branch, false);
 
         assert!(app.branch_by_key("refs/remotes/origin/feature/1").is_none());
         assert_eq!(app.deleted_branches().len(), 1);
@@ -451,7 +779,7 @@ mod tests {
                 .with_prune_result(Err("could not prune tracking ref".to_string())),
         );
 
-        execute_delete_branch(&mut app, &git_repo, &branch);
+        execute_delete_branch(&mut app, &git_repo, &branch, false);
 
         let branch = app
             .branch_by_key("refs/remotes/origin/feature/1")
@@ -510,6 +838,192 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_checkout_and_delete_keep_app_and_repo_in_sync() {
+        let git_repo = GitRepo::fixture(
+            FixtureGitRepo::new()
+                .with_local_branch("main", true)
+                .with_local_branch("feature/1", false)
+                .with_remote_branch("feature/2"),
+        );
+        let mut app = App::new(branch_infos(&git_repo), vec![]);
+        let remote = app
+            .branch_by_key("refs/remotes/origin/feature/2")
+            .cloned()
+            .expect("remote branch");
+
+        execute_checkout_branch(&mut app, &git_repo, &remote);
+        assert_eq!(local_snapshot(&app), repo_snapshot(&git_repo));
+        assert_eq!(
+            git_repo.current_local_branch_name().unwrap().as_deref(),
+            Some("feature/2")
+        );
+
+        execute_delete_branch(&mut app, &git_repo, &local_branch("feature/1"), false);
+        assert_eq!(local_snapshot(&app), repo_snapshot(&git_repo));
+        assert_eq!(
+            app.deleted_branches()[0].restore_hint,
+            Some(format!("git checkout -b feature/1 {FIXTURE_COMMIT_SHA}"))
+        );
+
+        let current = app
+            .branch_by_key("refs/heads/feature/2")
+            .cloned()
+            .expect("checked out branch");
+        execute_delete_branch(&mut app, &git_repo, &current, false);
+        assert!(app.get_status_message().expect("status").is_error);
+        assert_eq!(local_snapshot(&app), repo_snapshot(&git_repo));
+        assert_eq!(
+            repo_snapshot(&git_repo),
+            [("feature/2".to_string(), true), ("main".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn test_delete_with_remote_removes_both_branches_and_records_the_remote() {
+        let git_repo = GitRepo::fixture(
+            FixtureGitRepo::new()
+                .with_local_branch("main", true)
+                .with_local_branch("feature/1", false)
+                .with_remote_branch("feature/1")
+                .with_upstream("feature/1", "feature/1"),
+        );
+        let mut app = App::new(branch_infos(&git_repo), vec![]);
+        app.update(Msg::NextBranch);
+        app.enter_confirm_mode();
+        assert_eq!(
+            app.confirm_delete_remote()
+                .map(|remote| remote.key.as_str()),
+            Some("refs/remotes/origin/feature/1")
+        );
+        app.cancel_mode();
+
+        execute_delete_branch(&mut app, &git_repo, &local_branch("feature/1"), true);
+
+        assert!(app.branch_by_key("refs/remotes/origin/feature/1").is_none());
+        assert_eq!(branch_infos(&git_repo).len(), 1);
+        assert_eq!(
+            app.deleted_branches()[0].remote.as_deref(),
+            Some("origin/feature/1")
+        );
+        assert_eq!(
+            app.get_status_message().expect("status").text,
+            format!(
+                "Deleted feature/1 and origin/feature/1 (was {})",
+                short_sha(FIXTURE_COMMIT_SHA)
+            )
+        );
+    }
+
+    #[test]
+    fn test_same_named_remote_is_not_deleted_without_an_upstream() {
+        let git_repo = GitRepo::fixture(
+            FixtureGitRepo::new()
+                .with_local_branch("main", true)
+                .with_local_branch("feature/1", false)
+                .with_remote_branch("feature/1"),
+        );
+        let mut app = App::new(branch_infos(&git_repo), vec![]);
+        app.update(Msg::NextBranch);
+        app.enter_confirm_mode();
+        assert!(app.confirm_delete_remote().is_none());
+        app.cancel_mode();
+
+        execute_delete_branch(&mut app, &git_repo, &local_branch("feature/1"), true);
+
+        assert!(app.get_status_message().expect("status").is_error);
+        assert_eq!(branch_infos(&git_repo).len(), 3);
+        assert!(app.branch_by_key("refs/heads/feature/1").is_some());
+        assert!(app.branch_by_key("refs/remotes/origin/feature/1").is_some());
+    }
+
+    #[test]
+    fn test_protected_upstream_is_not_deleted_with_a_differently_named_branch() {
+        let git_repo = GitRepo::fixture(
+            FixtureGitRepo::new()
+                .with_local_branch("develop", true)
+                .with_local_branch("mywork", false)
+                .with_remote_branch("main")
+                .with_upstream("mywork", "main"),
+        );
+        let branches = branch_infos(&git_repo)
+            .into_iter()
+            .map(|branch| BranchInfo {
+                is_protected: branch.branch_name == "main",
+                ..branch
+            })
+            .collect();
+        let mut app = App::new(branches, vec!["main".to_string()]);
+        app.update(Msg::NextBranch);
+        app.enter_confirm_mode();
+        assert_eq!(app.confirm_delete_branch().unwrap().branch_name, "mywork");
+        assert!(app.confirm_delete_remote().is_none());
+        app.cancel_mode();
+
+        execute_delete_branch(&mut app, &git_repo, &local_branch("mywork"), true);
+
+        let status = app.get_status_message().expect("status");
+        assert!(status.is_error);
+        assert!(status.text.contains("'main' on origin"));
+        assert!(app.branch_by_key("refs/heads/mywork").is_some());
+        assert!(app.branch_by_key("refs/remotes/origin/main").is_some());
+    }
+
+    #[test]
+    fn test_delete_with_remote_keeps_the_local_deletion_when_the_push_fails() {
+        let git_repo = GitRepo::fixture(
+            FixtureGitRepo::new()
+                .with_local_branch("main", true)
+                .with_local_branch("feature/1", false)
+                .with_upstream("feature/1", "feature/1"),
+        );
+        let mut app = App::new(branch_infos(&git_repo), vec![]);
+
+        execute_delete_branch(&mut app, &git_repo, &local_branch("feature/1"), true);
+
+        assert!(app.branch_by_key("refs/heads/feature/1").is_none());
+        assert_eq!(app.deleted_branches()[0].remote, None);
+        let status = app.get_status_message().expect("status");
+        assert!(status.is_error);
+        assert!(status.text.contains("but not origin/feature/1"));
+    }
+
+    fn branch_infos(git_repo: &GitRepo) -> Vec<BranchInfo> {
+        git_repo
+            .list_branches()
+            .unwrap()
+            .into_iter()
+            .map(|branch: RepoBranch| BranchInfo {
+                key: branch.key,
+                display_name: branch.display_name,
+                branch_name: branch.branch_name,
+                remote_name: branch.remote_name,
+                scope: branch.scope,
+                work_item_id: None,
+                is_current: branch.is_current,
+                is_protected: false,
+                is_stale: false,
+                origin_upstream: branch.origin_upstream,
+            })
+            .collect()
+    }
+
+    /// Local branches as `(name, is_current)`, in list order.
+    fn local_snapshot(app: &App) -> Vec<(String, bool)> {
+        app.visible_branches()
+            .into_iter()
+            .map(|branch| (branch.branch_name.clone(), branch.is_current))
+            .collect()
+    }
+
+    fn repo_snapshot(git_repo: &GitRepo) -> Vec<(String, bool)> {
+        branch_infos(git_repo)
+            .into_iter()
+            .filter(|branch| branch.scope == BranchScope::Local)
+            .map(|branch| (branch.branch_name, branch.is_current))
+            .collect()
+    }
+
     fn local_branch(name: &str) -> BranchInfo {
         BranchInfo {
             key: format!("refs/heads/{name}"),
@@ -521,6 +1035,7 @@ mod tests {
             is_current: false,
             is_protected: false,
             is_stale: false,
+            origin_upstream: None,
         }
     }
 
@@ -535,6 +1050,7 @@ mod tests {
             is_current: false,
             is_protected: false,
             is_stale,
+            origin_upstream: None,
         }
     }
 }
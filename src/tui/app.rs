@@ -1,13 +1,42 @@
-use crate::azure_devops::WorkItem;
-use crate::git::{BranchOrder, BranchScope, BranchStatus, compare_branch_order};
-use std::collections::{HashMap, HashSet};
+use crate::azure_devops::{
+    ActivityEntry, Comment, CompletionOptions, DetailField, MergeStrategy, PullRequest,
+    RepositoryRef, StateTransition, WorkItem,
+};
+use crate::config::{
+    DEFAULT_CACHE_MAX_AGE_DAYS, DEFAULT_CACHED_WORK_ITEMS, DEFAULT_MAX_CONCURRENT_REQUESTS,
+    DEFAULT_WIQL, EstimateField,
+};
+use crate::git::{BranchOrder, BranchScope, BranchStatus, FetchProgress, compare_branch_order};
+use crate::i18n::{Text, t, tf};
+use crate::redact::redact;
+use crate::tui::keymap::Keymap;
+use crate::tui::session::{SessionLog, StateChange};
+use chrono::Utc;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::Modifier;
+use ratatui::text::Line;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
 use std::time::Instant;
 
+mod activity;
 mod branch_filter;
+mod comments;
+mod completion;
 mod filtering;
+mod hierarchy;
+mod inspect;
 mod load_state;
+mod marking;
+mod pull_request_list;
 mod selection;
+mod session;
+mod state_menu;
+mod stats;
 mod status;
+mod toasts;
+mod work_item_list;
 
 use branch_filter::BranchFilter;
 use selection::OnMiss;
@@ -24,6 +53,8 @@ pub struct BranchInfo {
     pub is_current: bool,
     pub is_protected: bool,
     pub is_stale: bool,
+    /// For a local branch, the branch on origin it tracks
+    pub origin_upstream: Option<String>,
 }
 
 impl BranchOrder for BranchInfo {
@@ -54,8 +85,8 @@ impl BranchView {
 
     pub fn label(self) -> &'static str {
         match self {
-            Self::Local => "Local",
-            Self::Remote => "Remote",
+            Self::Local => t(Text::ViewLocal),
+            Self::Remote => t(Text::ViewRemote),
         }
     }
 }
@@ -64,8 +95,176 @@ impl BranchView {
 #[derive(Debug, Clone)]
 pub enum AppMode {
     Normal,
-    ConfirmDelete { branch_key: String },
+    ConfirmDelete {
+        branch_key: String,
+    },
+    /// Confirm deleting the marked branches
+    ConfirmDeleteMarked,
+    /// How deleting each marked branch went
+    DeleteResults(Vec<DeleteOutcome>),
     ErrorPopup(String),
+    /// The raw API JSON of a work item
+    Inspect(JsonInspector),
+    /// The states a work item can move to
+    ChangeState(StateMenu),
+    /// Recently changed work items, in place of the panels
+    Activity,
+    /// Active pull requests of the repository, in place of the panels
+    PullRequests,
+    /// Work items of the `[query].wiql` query, in place of the branch list
+    WorkItemList,
+    /// How to complete a pull request
+    CompletePullRequest(Box<CompletionForm>),
+    /// Counts of the local branches
+    BranchStats(MergedBranches),
+    /// Stepping through the parent and children of work items in the
+    /// details panel
+    Hierarchy(HierarchyView),
+}
+
+/// The work items stepped into from the hierarchy section of the details
+/// panel, which shows the last one in place of the selected work item.
+#[derive(Debug, Clone)]
+pub struct HierarchyView {
+    /// The selected work item, then each one stepped into from the one
+    /// before; going back drops the last
+    pub trail: Vec<u32>,
+    /// Index of the highlighted related work item: the parent, if any, then
+    /// the children
+    pub selected: usize,
+}
+
+/// The raw JSON of a work item as the API returns it, for finding the
+/// reference name of a field the details panel does not show.
+#[derive(Debug, Clone)]
+pub struct JsonInspector {
+    pub work_item_id: u32,
+    pub content: InspectorContent,
+    /// Index of the first line shown
+    pub scroll: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum InspectorContent {
+    Loading,
+    /// Pretty-printed JSON, by line
+    Loaded(Vec<String>),
+    Error(String),
+}
+
+/// The states a work item's process lets it move to from its current one,
+/// to pick the new state from.
+#[derive(Debug, Clone)]
+pub struct StateMenu {
+    pub work_item_id: u32,
+    pub content: StateMenuContent,
+    /// Index of the highlighted transition
+    pub selected: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum StateMenuContent {
+    Loading,
+    Loaded(Vec<StateTransition>),
+    Error(String),
+}
+
+/// The options for completing a pull request, picked before it is sent.
+#[derive(Debug, Clone)]
+pub struct CompletionForm {
+    pub work_item_id: u32,
+    /// The pull request as shown; its source commit is the one completed
+    pub pull_request: PullRequest,
+    pub options: CompletionOptions,
+    /// Index of the highlighted row: the merge strategy, deleting the source
+    /// branch, or completing the linked work items
+    pub selected: usize,
+}
+
+/// Local branches merged into origin's default branch, checked when the
+/// statistics popup opens.
+#[derive(Debug, Clone)]
+pub enum MergedBranches {
+    Checking,
+    Checked(HashSet<String>),
+    Failed(String),
+}
+
+/// Counts of the local branches for the statistics popup, as rows of a label
+/// and a count.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BranchStats {
+    pub local_branches: usize,
+    /// By state of the linked work item, most common first
+    pub by_state: Vec<(String, usize)>,
+    /// By age of the last commit, newest first
+    pub by_age: Vec<(String, usize)>,
+    pub by_remote: Vec<(String, usize)>,
+    pub cleanup: Cleanup,
+}
+
+/// Local branches that could be deleted, leaving out protected branches and
+/// the current one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cleanup {
+    /// Merged into origin's default branch; `None` until checked
+    pub merged: Option<usize>,
+    /// Upstream branch gone
+    pub gone: usize,
+    /// Merged or gone, each branch counted once
+    pub total: usize,
+}
+
+/// Recently changed work items for the activity tab. The entries are kept
+/// while the tab is closed and replaced by each poll.
+#[derive(Debug, Clone, Default)]
+pub struct ActivityFeed {
+    pub entries: Vec<ActivityEntry>,
+    /// Index of the highlighted entry
+    pub selected: usize,
+    pub loading: bool,
+    /// Why the last poll failed
+    pub error: Option<String>,
+    /// When the last poll started
+    polled_at: Option<Instant>,
+}
+
+/// Active pull requests of the repository for the pull request tab. The
+/// entries are kept while the tab is closed and listed again each time it
+/// opens.
+#[derive(Debug, Clone, Default)]
+pub struct PullRequestList {
+    pub entries: Vec<PullRequest>,
+    /// Index of the highlighted entry
+    pub selected: usize,
+    pub loading: bool,
+    /// Why the last listing failed
+    pub error: Option<String>,
+    /// Whether the entries were listed since the tab opened
+    listed: bool,
+}
+
+/// Work items of the `[query].wiql` query for the work item list, with
+/// the highlighted one shown in the details panel. Queried again each time
+/// the list opens.
+#[derive(Debug, Clone, Default)]
+pub struct WorkItemList {
+    pub entries: Vec<WorkItem>,
+    /// Index of the highlighted entry
+    pub selected: usize,
+    pub loading: bool,
+    /// Why the last query failed
+    pub error: Option<String>,
+    /// Whether the query ran since the list opened
+    listed: bool,
+}
+
+/// A change to a loaded work item, noticed while the TUI is open
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub work_item_id: u32,
+    pub text: String,
+    pub expires_at: Instant,
 }
 
 /// Deleted branch info for summary on exit
@@ -73,6 +272,22 @@ pub enum AppMode {
 pub struct DeletedBranch {
     pub name: String,
     pub restore_hint: Option<String>,
+    /// Commit of a deleted local branch, for the restore script
+    pub commit_sha: Option<String>,
+    /// The origin branch the local branch tracked, if it was deleted along
+    /// with it
+    pub remote: Option<String>,
+    /// The deleted local branch as it was listed, for undoing the deletion
+    pub branch: Option<BranchInfo>,
+}
+
+/// How deleting one of the marked branches went, with the status line the
+/// branch would have gotten on its own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteOutcome {
+    pub branch: String,
+    pub message: String,
+    pub is_error: bool,
 }
 
 /// Status message with expiration
@@ -92,6 +307,49 @@ pub enum WorkItemStatus {
     Error(String),
 }
 
+/// Fetch status of the pull requests linked to a work item
+#[derive(Debug, Clone)]
+pub enum PullRequestsStatus {
+    Loading,
+    Loaded(Vec<PullRequest>),
+    Error(String),
+}
+
+/// Fetch status of the comments on a work item
+#[derive(Debug, Clone)]
+pub enum CommentsStatus {
+    Loading,
+    Loaded(Vec<Comment>),
+    Error(String),
+}
+
+/// Fetch status of the children of a work item
+#[derive(Debug, Clone)]
+pub enum ChildrenStatus {
+    Loading,
+    Loaded(Vec<WorkItem>),
+    Error(String),
+}
+
+/// Whether the Azure DevOps client, set up in the background, is available
+#[derive(Debug, Clone, Default)]
+pub enum ClientState {
+    #[default]
+    Connecting,
+    Ready,
+    /// Not configured, or the config could not be loaded
+    Unavailable(String),
+}
+
+/// A `git fetch origin` started from the TUI
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum GitFetchState {
+    #[default]
+    Idle,
+    /// Running, with the latest progress git reported
+    Running(Option<FetchProgress>),
+}
+
 #[derive(Debug, Clone, Default)]
 pub enum RemoteFreshness {
     #[default]
@@ -106,10 +364,44 @@ pub enum RemoteFreshness {
 /// Produced by the renderer and applied through [`Msg::SetDetailsMetrics`] so the
 /// heights are stored on `App` outside the render pass, keeping rendering
 /// read-only.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct DetailsMetrics {
     pub content_height: u16,
     pub visible_height: u16,
+    /// Rich text fields the renderer had to render, to reuse on later frames
+    pub rendered_fields: Option<RenderedFields>,
+    /// Where the parent line was drawn, if it is in view
+    pub parent_link: Option<ParentLink>,
+    /// Lines the JSON inspector shows at once, while it is open
+    pub inspector_height: u16,
+}
+
+/// The parent work item line of the details panel, opened when clicked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParentLink {
+    pub work_item_id: u32,
+    /// Screen cells of the line
+    pub area: Rect,
+}
+
+/// The rich text fields of a work item as rendered for the details panel.
+/// Rendering a description of a few hundred KB takes longer than a frame,
+/// so the lines are kept until the work item or the panel width changes.
+#[derive(Clone)]
+pub struct RenderedFields {
+    pub work_item_id: u32,
+    pub width: u16,
+    pub lines: Arc<[Line<'static>]>,
+}
+
+impl fmt::Debug for RenderedFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderedFields")
+            .field("work_item_id", &self.work_item_id)
+            .field("width", &self.width)
+            .field("lines", &self.lines.len())
+            .finish()
+    }
 }
 
 /// Message handled by the TUI update loop.
@@ -123,6 +415,7 @@ pub enum Msg {
     PreviousBranch,
     ToggleView,
     ToggleShowProtected,
+    ToggleSortByPriority,
     ScrollDown(u16),
     ScrollUp(u16),
     StartFilter,
@@ -136,8 +429,10 @@ pub enum Msg {
     ShowErrorPopup(String),
     SetStatus(StatusMessage),
     ClearStatus,
+    SetClientState(ClientState),
     SetRemoteFreshness(RemoteFreshness),
     SetRemoteFreshnessChecked(HashSet<String>),
+    SetGitFetch(GitFetchState),
     SetWorkItemLoading(u32),
     SetWorkItemLoaded {
         id: u32,
@@ -147,6 +442,86 @@ pub enum Msg {
         id: u32,
         error: String,
     },
+    WorkItemRevalidationFailed(u32),
+    SetPullRequests {
+        work_item_id: u32,
+        status: PullRequestsStatus,
+    },
+    /// Show or hide the comments section of the details panel
+    ToggleComments,
+    SetComments {
+        work_item_id: u32,
+        status: CommentsStatus,
+    },
+    SetChildren {
+        work_item_id: u32,
+        status: ChildrenStatus,
+    },
+    OpenHierarchy,
+    HierarchyNext,
+    HierarchyPrevious,
+    /// Show the highlighted related work item, keeping the one shown to go
+    /// back to
+    EnterRelatedWorkItem,
+    /// Show the work item stepped in from, or close the hierarchy at the
+    /// first
+    HierarchyBack,
+    OpenInspector(u32),
+    SetInspectorJson {
+        work_item_id: u32,
+        result: Result<String, String>,
+    },
+    InspectorScrollDown(usize),
+    InspectorScrollUp(usize),
+    OpenStateMenu(u32),
+    SetStateTransitions {
+        work_item_id: u32,
+        result: Result<Vec<StateTransition>, String>,
+    },
+    StateMenuNext,
+    StateMenuPrevious,
+    OpenActivity,
+    /// Poll again on the next loop, e.g. on `r`
+    RefreshActivity,
+    SetActivityLoading(Instant),
+    SetActivity(Result<Vec<ActivityEntry>, String>),
+    ActivityNext,
+    ActivityPrevious,
+    OpenPullRequestList,
+    /// List again on the next loop, e.g. on `r`
+    RefreshPullRequestList,
+    SetPullRequestListLoading,
+    SetPullRequestList(Result<Vec<PullRequest>, String>),
+    PullRequestListNext,
+    PullRequestListPrevious,
+    OpenWorkItemList,
+    /// Query again on the next loop, e.g. on `r`
+    RefreshWorkItemList,
+    SetWorkItemListLoading,
+    SetWorkItemList(Result<Vec<WorkItem>, String>),
+    WorkItemListNext,
+    WorkItemListPrevious,
+    OpenCompletionForm,
+    CompletionFormNext,
+    CompletionFormPrevious,
+    /// Step the highlighted merge strategy by the delta, or flip the
+    /// highlighted toggle
+    ChangeCompletionOption(isize),
+    StartChangeCheck(Instant),
+    /// Work items fetched again to look for changes
+    ChangesChecked(Vec<WorkItem>),
+    ClearExpiredToasts,
+    /// Select the branch of the newest toast's work item and dismiss it
+    JumpToToast,
+    OpenBranchStats,
+    SetMergedBranches(Result<HashSet<String>, String>),
+    /// The selected work item was viewed, for the session summary
+    RecordView(u32),
+    /// A work item is about to move to a new state, for the session summary
+    RecordStateChange {
+        work_item_id: u32,
+        to: String,
+    },
     SetBranchStatus {
         key: String,
         status: BranchStatus,
@@ -161,6 +536,9 @@ pub enum Msg {
         key: String,
         name: String,
         restore_hint: Option<String>,
+        commit_sha: Option<String>,
+        /// The remote branch deleted along with a local one, by display name
+        remote: Option<String>,
     },
     BranchDeletePruneFailed {
         key: String,
@@ -169,6 +547,14 @@ pub enum Msg {
     BranchPruned {
         key: String,
     },
+    /// The local branch of the last undoable deletion was created again
+    BranchRestored,
+    ToggleMark,
+    /// Mark the branches from the last marked one to the selected one
+    MarkRange,
+    ClearMarks,
+    EnterDeleteMarkedConfirmMode,
+    ShowDeleteResults(Vec<DeleteOutcome>),
     SortBranches,
     SetCurrentBranch(String),
 }
@@ -179,6 +565,13 @@ pub struct App {
     branches: Vec<BranchInfo>,
     deleted_branches: Vec<DeletedBranch>,
     protected_patterns: Vec<String>, // immutable config
+    emoji: bool,                     // immutable config
+    plain: bool,                     // immutable config
+    unsupported_modifiers: Modifier, // terminal capability, immutable
+    update_hint: Option<String>,     // newer release version, immutable
+    keymap: Keymap,                  // immutable config
+    detail_fields: Vec<DetailField>, // immutable config
+    estimate_field: EstimateField,   // immutable config
 
     // Selection & scroll (selection.rs)
     active_view: BranchView,
@@ -187,15 +580,66 @@ pub struct App {
     scroll_offset: u16,
     content_height: u16,
     visible_height: u16,
+    rendered_fields: Option<RenderedFields>,
+    parent_link: Option<ParentLink>,
+    inspector_height: u16,
     show_protected: bool,
+    sort_by_priority: bool,
 
     // Filtering (filtering.rs)
     filter: BranchFilter,
 
+    // Marked branches (marking.rs)
+    /// Keys of the branches marked for deleting in one go
+    marked: HashSet<String>,
+    /// The branch last marked or unmarked, where a range starts
+    mark_anchor: Option<String>,
+
     // Async load state (load_state.rs)
     work_items: HashMap<u32, WorkItemStatus>,
+    /// Keys of `work_items`, least recently used first
+    recent_work_items: VecDeque<u32>,
+    work_item_capacity: usize,
+    /// Work item requests in flight at once
+    max_concurrent_requests: usize,
+    cache_max_age_days: usize,
+    fetch_on_start: bool,
+    cached_work_items: HashSet<u32>,
+    failed_revalidations: HashSet<u32>,
+    /// Linked pull requests, by work item ID; only kept for stored work items
+    pull_requests: HashMap<u32, PullRequestsStatus>,
+    /// Comments, by work item ID; only kept for stored work items
+    comments: HashMap<u32, CommentsStatus>,
+    /// Whether the details panel shows the comments section, fetching the
+    /// selected work item's comments
+    comments_expanded: bool,
+    /// Child work items, by work item ID; only kept for stored work items
+    children: HashMap<u32, ChildrenStatus>,
     branch_statuses: HashMap<String, Result<BranchStatus, String>>,
     remote_freshness: RemoteFreshness,
+    git_fetch: GitFetchState,
+    client_state: ClientState,
+
+    // Activity tab (activity.rs)
+    activity: ActivityFeed,
+
+    // Pull request tab (pull_request_list.rs)
+    /// The repository of the `origin` remote, or why it is not one
+    repository: Result<RepositoryRef, String>,
+    pull_request_list: PullRequestList,
+
+    // Work item list (work_item_list.rs)
+    work_item_query: String,
+    work_item_list: WorkItemList,
+
+    // Change toasts (toasts.rs)
+    /// Oldest first
+    toasts: Vec<Toast>,
+    changes_checked_at: Instant,
+    checking_changes: bool,
+
+    // Session summary (session.rs)
+    session: SessionLog,
 
     // Mode & status (status.rs)
     mode: AppMode,
@@ -203,6 +647,7 @@ pub struct App {
 
     // Lifecycle
     should_quit: bool,
+    needs_redraw: bool,
 }
 
 impl App {
@@ -212,6 +657,13 @@ impl App {
             branches,
             deleted_branches: Vec::new(),
             protected_patterns,
+            emoji: true,
+            plain: false,
+            unsupported_modifiers: Modifier::empty(),
+            update_hint: None,
+            keymap: Keymap::default(),
+            detail_fields: DetailField::defaults(),
+            estimate_field: EstimateField::Auto,
 
             // Selection & scroll
             active_view: BranchView::Local,
@@ -220,15 +672,55 @@ impl App {
             scroll_offset: 0,
             content_height: 0,
             visible_height: 0,
+            rendered_fields: None,
+            parent_link: None,
+            inspector_height: 0,
             show_protected: false,
+            sort_by_priority: false,
 
             // Filtering
             filter: BranchFilter::default(),
 
+            // Marked branches
+            marked: HashSet::new(),
+            mark_anchor: None,
+
             // Async load state
             work_items: HashMap::new(),
+            recent_work_items: VecDeque::new(),
+            work_item_capacity: DEFAULT_CACHED_WORK_ITEMS,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            cache_max_age_days: DEFAULT_CACHE_MAX_AGE_DAYS,
+            fetch_on_start: false,
+            cached_work_items: HashSet::new(),
+            failed_revalidations: HashSet::new(),
+            pull_requests: HashMap::new(),
+            comments: HashMap::new(),
+            comments_expanded: false,
+            children: HashMap::new(),
             branch_statuses: HashMap::new(),
             remote_freshness: RemoteFreshness::NotChecked,
+            git_fetch: GitFetchState::Idle,
+            client_state: ClientState::Connecting,
+
+            // Activity tab
+            activity: ActivityFeed::default(),
+
+            // Pull request tab
+            repository: Err(t(Text::PullRequestListNoRepository).to_string()),
+            pull_request_list: PullRequestList::default(),
+
+            // Work item list
+            work_item_query: DEFAULT_WIQL.to_string(),
+            work_item_list: WorkItemList::default(),
+
+            // Change toasts
+            toasts: Vec::new(),
+            changes_checked_at: Instant::now(),
+            checking_changes: false,
+
+            // Session summary
+            session: SessionLog::new(Utc::now()),
 
             // Mode & status
             mode: AppMode::Normal,
@@ -236,15 +728,124 @@ impl App {
 
             // Lifecycle
             should_quit: false,
+            needs_redraw: true,
         }
     }
 
+    /// Render for screen readers: no box drawing, and text labels for what
+    /// is otherwise shown by color or symbols.
+    pub fn with_plain(mut self, plain: bool) -> Self {
+        self.plain = plain;
+        self
+    }
+
+    /// Use ASCII fallbacks instead of emoji when `emoji` is false.
+    pub fn with_emoji(mut self, emoji: bool) -> Self {
+        self.emoji = emoji;
+        self
+    }
+
+    /// Drop text modifiers the terminal cannot draw, such as dim and
+    /// strikethrough on the legacy Windows console.
+    pub fn with_unsupported_modifiers(mut self, modifiers: Modifier) -> Self {
+        self.unsupported_modifiers = modifiers;
+        self
+    }
+
+    /// Mention a newer cazdo release in the footer.
+    pub fn with_update_hint(mut self, version: Option<String>) -> Self {
+        self.update_hint = version;
+        self
+    }
+
+    /// Use `[keys]` bindings instead of the defaults.
+    pub fn with_keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Show `[display].fields` in the details header instead of the defaults.
+    pub fn with_detail_fields(mut self, fields: Vec<DetailField>) -> Self {
+        self.detail_fields = fields;
+        self
+    }
+
+    /// Show `[display].estimate` as the estimate badge next to the state.
+    pub fn with_estimate_field(mut self, field: EstimateField) -> Self {
+        self.estimate_field = field;
+        self
+    }
+
+    /// List the pull requests of `repository` in the pull request tab, or
+    /// show why there is none.
+    pub fn with_repository(mut self, repository: Result<RepositoryRef, String>) -> Self {
+        self.repository = repository;
+        self
+    }
+
+    /// Run `git fetch --prune origin` once the TUI starts
+    /// (`[branches].fetch_on_start`).
+    pub fn with_fetch_on_start(mut self, fetch_on_start: bool) -> Self {
+        self.fetch_on_start = fetch_on_start;
+        self
+    }
+
+    pub fn fetch_on_start(&self) -> bool {
+        self.fetch_on_start
+    }
+
+    /// List the work items of `query` in the work item list.
+    pub fn with_work_item_query(mut self, query: String) -> Self {
+        self.work_item_query = query;
+        self
+    }
+
+    /// Keep at most `capacity` work items (`[cache].work_items`), dropping
+    /// the least recently viewed.
+    pub fn with_work_item_capacity(mut self, capacity: usize) -> Self {
+        self.work_item_capacity = capacity.max(1);
+        self
+    }
+
+    pub fn work_item_capacity(&self) -> usize {
+        self.work_item_capacity
+    }
+
+    /// Drop cached work items not fetched for `days` days
+    /// (`[cache].max_age_days`).
+    pub fn with_cache_max_age_days(mut self, days: usize) -> Self {
+        self.cache_max_age_days = days.max(1);
+        self
+    }
+
+    pub fn cache_max_age_days(&self) -> usize {
+        self.cache_max_age_days
+    }
+
+    /// Send at most `limit` work item requests at once
+    /// (`[http].max_concurrent_requests`).
+    pub fn with_max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.max_concurrent_requests = limit.max(1);
+        self
+    }
+
+    pub fn max_concurrent_requests(&self) -> usize {
+        self.max_concurrent_requests
+    }
+
     pub fn update(&mut self, msg: Msg) {
+        tracing::debug!(?msg, "tui update");
+        // Metrics come from the last draw; they only need another draw when
+        // they change (see `apply_details_metrics`).
+        if !matches!(msg, Msg::SetDetailsMetrics(_)) {
+            self.needs_redraw = true;
+        }
         match msg {
             Msg::NextBranch => self.next(),
             Msg::PreviousBranch => self.previous(),
             Msg::ToggleView => self.toggle_view(),
             Msg::ToggleShowProtected => self.toggle_show_protected(),
+            Msg::ToggleSortByPriority => self.toggle_sort_by_priority(),
             Msg::ScrollDown(amount) => self.scroll_down(amount),
             Msg::ScrollUp(amount) => self.scroll_up(amount),
             Msg::StartFilter => self.enter_filter_input(),
@@ -253,18 +854,127 @@ impl App {
             Msg::ClearFilter => self.clear_branch_filter(),
             Msg::CancelFilter => self.cancel_filter_input(),
             Msg::Quit => self.should_quit = true,
-            Msg::EnterNormalMode => self.mode = AppMode::Normal,
+            Msg::EnterNormalMode => {
+                // The details go back to the selected branch's work item
+                if self.is_work_item_list_open() || self.hierarchy().is_some() {
+                    self.scroll_offset = 0;
+                }
+                self.mode = AppMode::Normal;
+            }
             Msg::EnterDeleteConfirmMode => self.apply_enter_confirm_mode(),
-            Msg::ShowErrorPopup(message) => self.mode = AppMode::ErrorPopup(message),
-            Msg::SetStatus(message) => self.status_message = Some(message),
+            Msg::ShowErrorPopup(message) => {
+                self.mode = AppMode::ErrorPopup(redact(&message).into_owned())
+            }
+            Msg::SetStatus(mut message) => {
+                message.text = redact(&message.text).into_owned();
+                self.status_message = Some(message)
+            }
             Msg::ClearStatus => self.status_message = None,
+            Msg::SetClientState(client_state) => {
+                if let ClientState::Unavailable(error) = &client_state {
+                    tracing::warn!(error = %error, "Azure DevOps client unavailable");
+                }
+                self.client_state = client_state
+            }
             Msg::SetRemoteFreshness(remote_freshness) => self.remote_freshness = remote_freshness,
             Msg::SetRemoteFreshnessChecked(live_branches) => {
                 self.apply_remote_freshness_checked(live_branches)
             }
+            Msg::SetGitFetch(git_fetch) => self.git_fetch = git_fetch,
             Msg::SetWorkItemLoading(id) => self.apply_work_item_loading(id),
             Msg::SetWorkItemLoaded { id, work_item } => self.apply_work_item_loaded(id, work_item),
-            Msg::SetWorkItemError { id, error } => self.apply_work_item_error(id, error),
+            Msg::SetWorkItemError { id, error } => {
+                self.apply_work_item_error(id, redact(&error).into_owned())
+            }
+            Msg::WorkItemRevalidationFailed(id) => {
+                self.failed_revalidations.insert(id);
+            }
+            Msg::SetPullRequests {
+                work_item_id,
+                status,
+            } => self.apply_pull_requests(work_item_id, status),
+            Msg::ToggleComments => self.comments_expanded = !self.comments_expanded,
+            Msg::SetComments {
+                work_item_id,
+                status,
+            } => self.apply_comments(work_item_id, status),
+            Msg::SetChildren {
+                work_item_id,
+                status,
+            } => self.apply_children(work_item_id, status),
+            Msg::OpenHierarchy => self.apply_open_hierarchy(),
+            Msg::HierarchyNext => self.move_hierarchy_selection(1),
+            Msg::HierarchyPrevious => self.move_hierarchy_selection(-1),
+            Msg::EnterRelatedWorkItem => self.apply_enter_related_work_item(),
+            Msg::HierarchyBack => self.apply_hierarchy_back(),
+            Msg::OpenInspector(work_item_id) => self.apply_open_inspector(work_item_id),
+            Msg::SetInspectorJson {
+                work_item_id,
+                result,
+            } => self.apply_inspector_json(work_item_id, result),
+            Msg::InspectorScrollDown(amount) => self.scroll_inspector_down(amount),
+            Msg::InspectorScrollUp(amount) => self.scroll_inspector_up(amount),
+            Msg::OpenStateMenu(work_item_id) => self.apply_open_state_menu(work_item_id),
+            Msg::SetStateTransitions {
+                work_item_id,
+                result,
+            } => self.apply_state_transitions(work_item_id, result),
+            Msg::StateMenuNext => self.move_state_selection(1),
+            Msg::StateMenuPrevious => self.move_state_selection(-1),
+            Msg::OpenActivity => self.mode = AppMode::Activity,
+            Msg::RefreshActivity => self.activity.polled_at = None,
+            Msg::SetActivityLoading(now) => {
+                self.activity.loading = true;
+                self.activity.polled_at = Some(now);
+            }
+            Msg::SetActivity(result) => self.apply_activity(result),
+            Msg::ActivityNext => self.move_activity_selection(1),
+            Msg::ActivityPrevious => self.move_activity_selection(-1),
+            Msg::OpenPullRequestList => {
+                self.mode = AppMode::PullRequests;
+                self.pull_request_list.listed = false;
+            }
+            Msg::RefreshPullRequestList => self.pull_request_list.listed = false,
+            Msg::SetPullRequestListLoading => {
+                self.pull_request_list.loading = true;
+                self.pull_request_list.listed = true;
+            }
+            Msg::SetPullRequestList(result) => self.apply_pull_request_list(result),
+            Msg::PullRequestListNext => self.move_pull_request_selection(1),
+            Msg::PullRequestListPrevious => self.move_pull_request_selection(-1),
+            Msg::OpenWorkItemList => {
+                self.mode = AppMode::WorkItemList;
+                self.work_item_list.listed = false;
+                self.scroll_offset = 0;
+            }
+            Msg::RefreshWorkItemList => self.work_item_list.listed = false,
+            Msg::SetWorkItemListLoading => {
+                self.work_item_list.loading = true;
+                self.work_item_list.listed = true;
+            }
+            Msg::SetWorkItemList(result) => self.apply_work_item_list(result),
+            Msg::WorkItemListNext => self.move_work_item_list_selection(1),
+            Msg::WorkItemListPrevious => self.move_work_item_list_selection(-1),
+            Msg::OpenCompletionForm => self.apply_open_completion_form(),
+            Msg::CompletionFormNext => self.move_completion_selection(1),
+            Msg::CompletionFormPrevious => self.move_completion_selection(-1),
+            Msg::ChangeCompletionOption(delta) => self.change_completion_option(delta),
+            Msg::StartChangeCheck(now) => {
+                self.changes_checked_at = now;
+                self.checking_changes = true;
+            }
+            Msg::ChangesChecked(work_items) => self.apply_changes_checked(work_items),
+            Msg::ClearExpiredToasts => {
+                let now = Instant::now();
+                self.toasts.retain(|toast| toast.expires_at > now);
+            }
+            Msg::JumpToToast => self.apply_jump_to_toast(),
+            Msg::OpenBranchStats => self.mode = AppMode::BranchStats(MergedBranches::Checking),
+            Msg::SetMergedBranches(result) => self.apply_merged_branches(result),
+            Msg::RecordView(work_item_id) => self.apply_record_view(work_item_id),
+            Msg::RecordStateChange { work_item_id, to } => {
+                self.apply_record_state_change(work_item_id, to)
+            }
             Msg::SetBranchStatus { key, status } => self.apply_branch_status(key, status),
             Msg::SetBranchStatusError { key, error } => self.apply_branch_status_error(key, error),
             Msg::SetBackgroundError(error) => self.apply_background_error(error),
@@ -273,21 +983,64 @@ impl App {
                 key,
                 name,
                 restore_hint,
+                commit_sha,
+                remote,
             } => {
-                self.record_deleted_branch(name, restore_hint);
+                let branch = commit_sha
+                    .as_ref()
+                    .and_then(|_| self.branches.iter().find(|branch| branch.key == key))
+                    .cloned();
                 self.remove_branch(&key);
+                if let Some(remote_key) = remote
+                    .as_deref()
+                    .and_then(|remote| self.remote_branch_named(remote))
+                    .map(|branch| branch.key.clone())
+                {
+                    self.remove_branch(&remote_key);
+                }
+                self.record_deleted_branch(name, restore_hint, commit_sha, remote, branch);
+            }
+            Msg::BranchRestored => self.apply_branch_restored(),
+            Msg::ToggleMark => self.toggle_mark(),
+            Msg::MarkRange => self.mark_range(),
+            Msg::ClearMarks => self.clear_marks(),
+            Msg::EnterDeleteMarkedConfirmMode => {
+                if !self.marked.is_empty() {
+                    self.mode = AppMode::ConfirmDeleteMarked;
+                }
+            }
+            Msg::ShowDeleteResults(outcomes) => {
+                self.clear_marks();
+                self.mode = AppMode::DeleteResults(outcomes);
             }
             Msg::BranchDeletePruneFailed { key, name } => {
-                self.record_deleted_branch(name, None);
+                self.record_deleted_branch(name, None, None, None, None);
                 self.mark_branch_stale(&key);
             }
             Msg::BranchPruned { key } => self.remove_branch(&key),
             Msg::SortBranches => self.sort_branches(),
-            Msg::SetCurrentBranch(name) => self.update_current_branch(&name),
+            Msg::SetCurrentBranch(name) => {
+                self.update_current_branch(&name);
+                self.session.record_checkout(name, Utc::now());
+            }
+        }
+        if let Some(id) = self.selected_work_item_id() {
+            self.touch_work_item(id);
         }
     }
 
+    /// The work item of the selected branch, the highlighted one while the
+    /// work item list is open, or the one last stepped into in the
+    /// hierarchy.
     pub fn selected_work_item_id(&self) -> Option<u32> {
+        if let Some(view) = self.hierarchy() {
+            return view.trail.last().copied();
+        }
+        if self.is_work_item_list_open() {
+            return self
+                .selected_listed_work_item()
+                .map(|work_item| work_item.id);
+        }
         self.selected_branch().and_then(|b| b.work_item_id)
     }
 
@@ -316,25 +1069,146 @@ impl App {
         &self.protected_patterns
     }
 
+    pub fn emoji(&self) -> bool {
+        self.emoji
+    }
+
+    pub fn plain(&self) -> bool {
+        self.plain
+    }
+
+    pub fn unsupported_modifiers(&self) -> Modifier {
+        self.unsupported_modifiers
+    }
+
+    pub fn update_hint(&self) -> Option<&str> {
+        self.update_hint.as_deref()
+    }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    pub fn detail_fields(&self) -> &[DetailField] {
+        &self.detail_fields
+    }
+
+    pub fn estimate_field(&self) -> EstimateField {
+        self.estimate_field
+    }
+
     pub fn deleted_branches(&self) -> &[DeletedBranch] {
         &self.deleted_branches
     }
 
+    /// Ask for a draw on the next loop iteration, e.g. after a resize.
+    pub fn request_redraw(&mut self) {
+        self.needs_redraw = true;
+    }
+
+    /// Whether the state changed since the last call, clearing the flag.
+    pub fn take_needs_redraw(&mut self) -> bool {
+        std::mem::take(&mut self.needs_redraw)
+    }
+
     pub fn should_quit(&self) -> bool {
         self.should_quit
     }
 
     fn apply_details_metrics(&mut self, metrics: DetailsMetrics) {
+        if (self.content_height, self.visible_height)
+            != (metrics.content_height, metrics.visible_height)
+        {
+            self.needs_redraw = true;
+        }
         self.content_height = metrics.content_height;
         self.visible_height = metrics.visible_height;
+        if let Some(rendered) = metrics.rendered_fields {
+            self.rendered_fields = Some(rendered);
+        }
+        self.parent_link = metrics.parent_link;
+        self.inspector_height = metrics.inspector_height;
     }
 
-    fn record_deleted_branch(&mut self, name: String, restore_hint: Option<String>) {
+    /// The parent work item whose line in the details panel covers the
+    /// screen cell at `column`, `row`.
+    pub fn parent_link_at(&self, column: u16, row: u16) -> Option<u32> {
+        self.parent_link
+            .filter(|link| link.area.contains(Position::new(column, row)))
+            .map(|link| link.work_item_id)
+    }
+
+    /// The parent of the selected work item, once that is loaded.
+    pub fn selected_parent_id(&self) -> Option<u32> {
+        match self.get_work_item_status(self.selected_work_item_id()?) {
+            WorkItemStatus::Loaded(work_item) => work_item.parent_id(),
+            _ => None,
+        }
+    }
+
+    /// The rich text field lines of `work_item_id` last rendered at `width`.
+    pub fn rendered_fields(&self, work_item_id: u32, width: u16) -> Option<&Arc<[Line<'static>]>> {
+        self.rendered_fields
+            .as_ref()
+            .filter(|rendered| rendered.work_item_id == work_item_id && rendered.width == width)
+            .map(|rendered| &rendered.lines)
+    }
+
+    fn record_deleted_branch(
+        &mut self,
+        name: String,
+        restore_hint: Option<String>,
+        commit_sha: Option<String>,
+        remote: Option<String>,
+        branch: Option<BranchInfo>,
+    ) {
+        self.deleted_branches.push(DeletedBranch {
+            name,
+            restore_hint,
+            commit_sha,
+            remote,
+            branch,
+        });
+    }
+
+    /// The local branch deleted last this session that has not been
+    /// restored yet, with the commit it pointed at.
+    pub fn last_undoable_deletion(&self) -> Option<(&BranchInfo, &str)> {
         self.deleted_branches
-            .push(DeletedBranch { name, restore_hint });
+            .iter()
+            .rev()
+            .find_map(|deleted| Some((deleted.branch.as_ref()?, deleted.commit_sha.as_deref()?)))
+    }
+
+    /// List the branch of the last undoable deletion again and select it.
+    /// It no longer counts as deleted, so the exit summary and restore
+    /// script leave it out.
+    fn apply_branch_restored(&mut self) {
+        let Some(index) = self
+            .deleted_branches
+            .iter()
+            .rposition(|deleted| deleted.branch.is_some() && deleted.commit_sha.is_some())
+        else {
+            return;
+        };
+        let Some(branch) = self.deleted_branches.remove(index).branch else {
+            return;
+        };
+        self.branch_statuses.remove(&branch.key);
+        let branch_name = branch.branch_name.clone();
+        self.branches.push(branch);
+        self.sort_branches();
+        self.focus_local_branch(&branch_name);
+    }
+
+    fn remote_branch_named(&self, display_name: &str) -> Option<&BranchInfo> {
+        self.branches
+            .iter()
+            .find(|branch| branch.scope.is_remote() && branch.display_name == display_name)
     }
 
     fn remove_branch(&mut self, key: &str) {
+        self.marked.remove(key);
         if let Some(pos) = self.branches.iter().position(|b| b.key == key) {
             self.branches.remove(pos);
             self.clamp_selected_index();
@@ -367,6 +1241,7 @@ impl App {
             .any(|b| b.scope == BranchScope::Local && b.branch_name == branch.branch_name);
 
         if !local_exists {
+            self.needs_redraw = true;
             let mut new_branch = branch.clone();
             new_branch.scope = BranchScope::Local;
             new_branch.key = format!("refs/heads/{}", branch.branch_name);
@@ -381,17 +1256,20 @@ impl App {
 
     pub fn can_delete_selected(&self) -> Result<(), String> {
         let Some(branch) = self.selected_branch() else {
-            return Err("No branch selected".to_string());
+            return Err(t(Text::NoBranchSelected).to_string());
         };
+        Self::can_delete(branch)
+    }
 
+    pub fn can_delete(branch: &BranchInfo) -> Result<(), String> {
         if branch.is_current {
-            return Err("Cannot delete the current branch".to_string());
+            return Err(t(Text::CannotDeleteCurrent).to_string());
         }
 
         if branch.is_protected {
-            return Err(format!(
-                "Cannot delete protected branch '{}'",
-                branch.display_name
+            return Err(tf(
+                Text::CannotDeleteProtected,
+                &[("branch", &branch.display_name)],
             ));
         }
 
@@ -414,6 +1292,24 @@ impl App {
             .is_some_and(|branch| branch.is_stale)
     }
 
+    /// The branch on origin that the local branch awaiting delete
+    /// confirmation tracks, which can be deleted along with it, if it is
+    /// listed, not stale, and not protected. Only origin's branches are
+    /// listed.
+    pub fn confirm_delete_remote(&self) -> Option<&BranchInfo> {
+        let upstream = self
+            .confirm_delete_branch()
+            .filter(|branch| branch.scope == BranchScope::Local)?
+            .origin_upstream
+            .as_deref()?;
+        self.branches.iter().find(|remote| {
+            remote.scope.is_remote()
+                && remote.branch_name == upstream
+                && !remote.is_stale
+                && !remote.is_protected
+        })
+    }
+
     fn sort_branches(&mut self) {
         self.branches.sort_by(compare_branch_order);
     }
@@ -422,6 +1318,7 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::azure_devops::{WorkItemState, WorkItemType};
     use crate::git::RemoteStatus;
 
     fn branch(
@@ -443,6 +1340,7 @@ mod tests {
             is_current,
             is_protected,
             is_stale: false,
+            origin_upstream: None,
         }
     }
 
@@ -628,6 +1526,7 @@ mod tests {
         app.update(Msg::SetDetailsMetrics(DetailsMetrics {
             content_height: 50,
             visible_height: 20,
+            ..DetailsMetrics::default()
         }));
         assert_eq!(app.content_height, 50);
         assert_eq!(app.visible_height, 20);
@@ -1493,6 +2392,7 @@ mod tests {
             "refs/heads/feature/1".to_string(),
             BranchStatus {
                 remote_status: RemoteStatus::UpToDate,
+                last_commit_sha: None,
                 last_commit_author: None,
                 last_commit_time: None,
             },
@@ -1501,6 +2401,95 @@ mod tests {
         assert!(!app.needs_branch_status("refs/heads/feature/1"));
     }
 
+    #[test]
+    fn test_branch_stats_count_local_branches_and_cleanup() {
+        let now = 1_800_000_000;
+        let day: i64 = 24 * 60 * 60;
+        let mut branches = create_test_branches();
+        branches.push(branch(
+            "refs/heads/feature/9",
+            "feature/9",
+            "feature/9",
+            BranchScope::Local,
+            false,
+            false,
+            Some(9),
+        ));
+        let mut app = App::new(branches, vec![]);
+        let status = |remote_status, days_ago| BranchStatus {
+            remote_status,
+            last_commit_sha: None,
+            last_commit_author: None,
+            last_commit_time: Some(now - days_ago * day),
+        };
+        app.set_branch_status(
+            "refs/heads/feature/123".to_string(),
+            status(RemoteStatus::Gone, 40),
+        );
+        app.set_branch_status(
+            "refs/heads/feature/9".to_string(),
+            status(RemoteStatus::LocalOnly, 2),
+        );
+        app.set_work_item_loaded(
+            123,
+            WorkItem {
+                id: 123,
+                title: "Checkout".to_string(),
+                work_item_type: WorkItemType::Task,
+                state: WorkItemState::Active,
+                assigned_to: None,
+                url: None,
+                tags: vec![],
+                rich_text_fields: vec![],
+                fields: Default::default(),
+            },
+        );
+        assert!(app.branch_stats(now).is_none());
+
+        app.update(Msg::OpenBranchStats);
+        let stats = app.branch_stats(now).unwrap();
+        let rows = |rows: &[(&str, usize)]| -> Vec<(String, usize)> {
+            rows.iter()
+                .map(|(label, count)| (label.to_string(), *count))
+                .collect()
+        };
+        assert_eq!(stats.local_branches, 3);
+        assert_eq!(
+            stats.by_state,
+            rows(&[("Active", 1), ("no work item", 1), ("unknown", 1)])
+        );
+        assert_eq!(
+            stats.by_age,
+            rows(&[("under a week", 1), ("1-3 months", 1), ("unknown", 1)])
+        );
+        assert_eq!(
+            stats.by_remote,
+            rows(&[("local only", 1), ("remote gone", 1), ("unknown", 1)])
+        );
+        assert_eq!(
+            stats.cleanup,
+            Cleanup {
+                merged: None,
+                gone: 1,
+                total: 1
+            }
+        );
+
+        // The current branch is left out of the cleanup even when merged
+        app.set_merged_branches(Ok(HashSet::from([
+            "feature/9".to_string(),
+            "main".to_string(),
+        ])));
+        assert_eq!(
+            app.branch_stats(now).unwrap().cleanup,
+            Cleanup {
+                merged: Some(1),
+                gone: 1,
+                total: 2
+            }
+        );
+    }
+
     #[test]
     fn test_branch_deleted_msg_removes_branch_and_records_history() {
         let mut app = App::new(
@@ -1520,6 +2509,8 @@ mod tests {
             key: "refs/heads/feature/1".to_string(),
             name: "feature/1".to_string(),
             restore_hint: Some("git checkout -b feature/1 abc1234".to_string()),
+            commit_sha: Some("abc1234".to_string()),
+            remote: None,
         });
 
         assert!(app.branch_by_key("refs/heads/feature/1").is_none());
@@ -1618,4 +2609,193 @@ mod tests {
             "feature/1"
         );
     }
+
+    #[test]
+    fn test_work_items_beyond_capacity_evict_least_recently_viewed() {
+        let branches = (1..=4)
+            .map(|id| {
+                let name = format!("feature/{id}");
+                branch(
+                    &format!("refs/heads/{name}"),
+                    &name,
+                    &name,
+                    BranchScope::Local,
+                    false,
+                    false,
+                    Some(id),
+                )
+            })
+            .collect();
+        let mut app = App::new(branches, vec![]).with_work_item_capacity(2);
+        let work_item = |id| WorkItem {
+            id,
+            title: format!("Item {id}"),
+            work_item_type: WorkItemType::Task,
+            state: WorkItemState::Active,
+            assigned_to: None,
+            url: None,
+            tags: vec![],
+            rich_text_fields: vec![],
+            fields: Default::default(),
+        };
+
+        app.set_work_item_loaded(1, work_item(1));
+        app.set_work_item_loaded(2, work_item(2));
+        assert!(app.unfetched_work_item_ids().is_empty());
+
+        for _ in 0..3 {
+            app.update(Msg::NextBranch);
+        }
+        assert_eq!(app.unfetched_work_item_ids(), [4, 3]);
+        app.set_work_item_loaded(4, work_item(4));
+
+        assert!(matches!(
+            app.get_work_item_status(1),
+            WorkItemStatus::NotFetched
+        ));
+        assert!(matches!(
+            app.get_work_item_status(2),
+            WorkItemStatus::Loaded(_)
+        ));
+        assert!(matches!(
+            app.get_work_item_status(4),
+            WorkItemStatus::Loaded(_)
+        ));
+    }
+
+    #[test]
+    fn test_parent_of_the_selected_work_item_is_fetched_next() {
+        let mut app = App::new(create_test_branches(), vec![]);
+        app.update(Msg::NextBranch);
+        assert_eq!(app.unfetched_work_item_ids(), [123]);
+
+        let mut work_item = WorkItem {
+            id: 123,
+            title: "Child".to_string(),
+            work_item_type: WorkItemType::Task,
+            state: WorkItemState::Active,
+            assigned_to: None,
+            url: None,
+            tags: vec![],
+            rich_text_fields: vec![],
+            fields: Default::default(),
+        };
+        work_item
+            .fields
+            .insert("System.Parent".to_string(), "99".to_string());
+        app.set_work_item_loaded(123, work_item);
+
+        assert_eq!(app.selected_parent_id(), Some(99));
+        assert_eq!(app.unfetched_work_item_ids(), [99]);
+    }
+
+    #[test]
+    fn test_sort_and_filter_by_priority_keep_the_selection() {
+        let branches = [(1, None), (2, Some(3)), (3, Some(1)), (4, Some(2))]
+            .map(|(n, id)| {
+                let name = format!("feature/{n}");
+                branch(
+                    &format!("refs/heads/{name}"),
+                    &name,
+                    &name,
+                    BranchScope::Local,
+                    false,
+                    false,
+                    id,
+                )
+            })
+            .to_vec();
+        let mut app = App::new(branches, vec![]);
+        let with_priority = |id, priority: &str| WorkItem {
+            id,
+            title: format!("Item {id}"),
+            work_item_type: WorkItemType::Bug,
+            state: WorkItemState::Active,
+            assigned_to: None,
+            url: None,
+            tags: vec![],
+            rich_text_fields: vec![],
+            fields: [(
+                "Microsoft.VSTS.Common.Priority".to_string(),
+                priority.to_string(),
+            )]
+            .into(),
+        };
+        let names = |app: &App| {
+            app.visible_branches()
+                .iter()
+                .map(|branch| branch.branch_name.clone())
+                .collect::<Vec<_>>()
+        };
+        app.set_work_item_loaded(3, with_priority(3, "3"));
+        app.set_work_item_loaded(1, with_priority(1, "1"));
+
+        app.update(Msg::ToggleSortByPriority);
+        assert_eq!(
+            names(&app),
+            ["feature/3", "feature/2", "feature/1", "feature/4"]
+        );
+
+        assert_eq!(app.selected_branch().unwrap().branch_name, "feature/1");
+        app.set_work_item_loaded(2, with_priority(2, "2"));
+        assert_eq!(
+            names(&app),
+            ["feature/3", "feature/4", "feature/2", "feature/1"]
+        );
+        assert_eq!(app.selected_branch().unwrap().branch_name, "feature/1");
+
+        app.apply_branch_filter("p:1-2".to_string());
+        assert_eq!(names(&app), ["feature/3", "feature/4"]);
+        app.apply_branch_filter("p:3 feature".to_string());
+        assert_eq!(names(&app), ["feature/2"]);
+
+        app.apply_branch_filter(String::new());
+        app.update(Msg::ToggleSortByPriority);
+        assert_eq!(
+            names(&app),
+            ["feature/1", "feature/2", "feature/3", "feature/4"]
+        );
+    }
+
+    #[test]
+    fn test_redraw_only_after_state_changes() {
+        let mut app = App::new(
+            vec![
+                branch(
+                    "refs/heads/feature/1",
+                    "feature/1",
+                    "feature/1",
+                    BranchScope::Local,
+                    false,
+                    false,
+                    None,
+                ),
+                branch(
+                    "refs/heads/feature/2",
+                    "feature/2",
+                    "feature/2",
+                    BranchScope::Local,
+                    false,
+                    false,
+                    None,
+                ),
+            ],
+            vec![],
+        );
+        let metrics = DetailsMetrics {
+            content_height: 10,
+            visible_height: 5,
+            ..DetailsMetrics::default()
+        };
+
+        assert!(app.take_needs_redraw());
+        app.update(Msg::SetDetailsMetrics(metrics.clone()));
+        assert!(app.take_needs_redraw());
+        app.update(Msg::SetDetailsMetrics(metrics));
+        assert!(!app.take_needs_redraw());
+
+        app.update(Msg::NextBranch);
+        assert!(app.take_needs_redraw());
+        assert!(!app.take_needs_redraw());
+    }
 }
@@ -0,0 +1,90 @@
+use super::*;
+use crate::tui::theme::timing::ACTIVITY_POLL_INTERVAL;
+
+impl App {
+    pub fn activity(&self) -> &ActivityFeed {
+        &self.activity
+    }
+
+    pub fn is_activity_open(&self) -> bool {
+        matches!(self.mode, AppMode::Activity)
+    }
+
+    /// The work item highlighted in the activity tab.
+    pub fn selected_activity_work_item(&self) -> Option<u32> {
+        self.activity
+            .entries
+            .get(self.activity.selected)
+            .map(|entry| entry.work_item.id)
+    }
+
+    /// Work items of local branches, whose changes the activity tab shows
+    /// besides those of work items assigned to the user.
+    pub fn activity_linked_ids(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .branches
+            .iter()
+            .filter(|branch| branch.scope == BranchScope::Local)
+            .filter_map(|branch| branch.work_item_id)
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    /// When the open activity tab polls next; `None` while it is closed or
+    /// a poll is in flight.
+    pub fn activity_polls_at(&self) -> Option<Instant> {
+        if !self.is_activity_open() || self.activity.loading {
+            return None;
+        }
+        Some(match self.activity.polled_at {
+            Some(polled_at) => polled_at + ACTIVITY_POLL_INTERVAL,
+            None => Instant::now(),
+        })
+    }
+
+    /// Whether the open activity tab should poll as of `now`.
+    pub fn activity_poll_due(&self, now: Instant) -> bool {
+        self.is_activity_open()
+            && !self.activity.loading
+            && self
+                .activity
+                .polled_at
+                .is_none_or(|polled_at| polled_at + ACTIVITY_POLL_INTERVAL <= now)
+    }
+
+    pub fn set_activity(&mut self, result: Result<Vec<ActivityEntry>, String>) {
+        self.update(Msg::SetActivity(
+            result.map_err(|error| redact(&error).into_owned()),
+        ));
+    }
+
+    /// Replace the entries, keeping the highlight on the same work item when
+    /// it is still listed. A failed poll keeps the entries of the last one.
+    pub(super) fn apply_activity(&mut self, result: Result<Vec<ActivityEntry>, String>) {
+        self.activity.loading = false;
+        match result {
+            Ok(entries) => {
+                let selected = self.selected_activity_work_item();
+                self.activity.selected = entries
+                    .iter()
+                    .position(|entry| Some(entry.work_item.id) == selected)
+                    .unwrap_or(0);
+                self.activity.entries = entries;
+                self.activity.error = None;
+            }
+            Err(error) => self.activity.error = Some(error),
+        }
+    }
+
+    /// Move the highlight by `delta`, stopping at either end.
+    pub(super) fn move_activity_selection(&mut self, delta: isize) {
+        let last = self.activity.entries.len().saturating_sub(1);
+        self.activity.selected = self
+            .activity
+            .selected
+            .saturating_add_signed(delta)
+            .min(last);
+    }
+}
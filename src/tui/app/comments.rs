@@ -0,0 +1,56 @@
+use super::*;
+
+impl App {
+    pub fn comments_expanded(&self) -> bool {
+        self.comments_expanded
+    }
+
+    pub fn get_comments(&self, work_item_id: u32) -> Option<&CommentsStatus> {
+        self.comments.get(&work_item_id)
+    }
+
+    /// The selected work item, while the comments section is shown, if it
+    /// is loaded and its comments have not been fetched.
+    pub fn work_item_needing_comments(&self) -> Option<u32> {
+        if !self.comments_expanded {
+            return None;
+        }
+        let id = self.selected_work_item_id()?;
+        (matches!(self.get_work_item_status(id), WorkItemStatus::Loaded(_))
+            && !self.comments.contains_key(&id))
+        .then_some(id)
+    }
+
+    pub fn set_comments_loading(&mut self, work_item_id: u32) {
+        self.update(Msg::SetComments {
+            work_item_id,
+            status: CommentsStatus::Loading,
+        });
+    }
+
+    pub fn set_comments(&mut self, work_item_id: u32, result: Result<Vec<Comment>, String>) {
+        let status = match result {
+            Ok(comments) => CommentsStatus::Loaded(comments),
+            Err(error) => CommentsStatus::Error(redact(&error).into_owned()),
+        };
+        self.update(Msg::SetComments {
+            work_item_id,
+            status,
+        });
+    }
+
+    /// Store the comments of a work item that is still stored; those of one
+    /// evicted or refreshed meanwhile are dropped.
+    pub(super) fn apply_comments(&mut self, work_item_id: u32, status: CommentsStatus) {
+        if self.work_items.contains_key(&work_item_id) {
+            self.comments.insert(work_item_id, status);
+        }
+    }
+
+    /// Forget the comments of a work item, e.g. when their fetch is
+    /// cancelled, so they are fetched again when it is next selected.
+    pub fn reset_comments(&mut self, work_item_id: u32) {
+        self.needs_redraw = true;
+        self.comments.remove(&work_item_id);
+    }
+}
@@ -0,0 +1,82 @@
+use super::*;
+
+/// Rows of the completion form: the merge strategy and the two toggles.
+const COMPLETION_ROWS: usize = 3;
+
+impl App {
+    /// The active pull request of the selected work item, preferring the
+    /// one from the selected branch.
+    pub fn selected_pull_request(&self) -> Option<&PullRequest> {
+        let work_item_id = self.selected_work_item_id()?;
+        let Some(PullRequestsStatus::Loaded(pull_requests)) = self.get_pull_requests(work_item_id)
+        else {
+            return None;
+        };
+        let branch_name = self
+            .selected_branch()
+            .map(|branch| branch.branch_name.as_str());
+        let mut active = pull_requests.iter().filter(|pr| pr.status == "active");
+        active
+            .clone()
+            .find(|pr| Some(pr.source_branch.as_str()) == branch_name)
+            .or_else(|| active.next())
+    }
+
+    /// The completion form, while it is open.
+    pub fn completion_form(&self) -> Option<&CompletionForm> {
+        match &self.mode {
+            AppMode::CompletePullRequest(form) => Some(form),
+            _ => None,
+        }
+    }
+
+    pub(super) fn apply_open_completion_form(&mut self) {
+        let Some(work_item_id) = self.selected_work_item_id() else {
+            return;
+        };
+        let Some(pull_request) = self.selected_pull_request() else {
+            self.apply_background_error(t(Text::NoActivePullRequest).to_string());
+            return;
+        };
+        self.mode = AppMode::CompletePullRequest(Box::new(CompletionForm {
+            work_item_id,
+            pull_request: pull_request.clone(),
+            options: CompletionOptions {
+                merge_strategy: MergeStrategy::default(),
+                delete_source_branch: false,
+                complete_work_items: true,
+            },
+            selected: 0,
+        }));
+    }
+
+    /// Move the highlight by `delta`, wrapping around at either end.
+    pub(super) fn move_completion_selection(&mut self, delta: isize) {
+        if let AppMode::CompletePullRequest(form) = &mut self.mode {
+            form.selected =
+                (form.selected as isize + delta).rem_euclid(COMPLETION_ROWS as isize) as usize;
+        }
+    }
+
+    /// Step the highlighted merge strategy by `delta`, or flip the
+    /// highlighted toggle.
+    pub(super) fn change_completion_option(&mut self, delta: isize) {
+        let AppMode::CompletePullRequest(form) = &mut self.mode else {
+            return;
+        };
+        let options = &mut form.options;
+        match form.selected {
+            0 => {
+                let strategies = MergeStrategy::ALL;
+                let index = strategies
+                    .iter()
+                    .position(|&strategy| strategy == options.merge_strategy)
+                    .unwrap_or(0);
+                let next = (index as isize + delta).rem_euclid(strategies.len() as isize);
+                options.merge_strategy = strategies[next as usize];
+            }
+            1 => options.delete_source_branch = !options.delete_source_branch,
+            _ => options.complete_work_items = !options.complete_work_items,
+        }
+    }
+}
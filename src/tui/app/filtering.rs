@@ -68,7 +68,12 @@ impl App {
         filter
             .split_whitespace()
             .map(|token| token.to_ascii_lowercase())
-            .all(|token| branch_name.contains(&token))
+            .all(|token| match priority_range(&token) {
+                Some((low, high)) => self
+                    .branch_priority(branch)
+                    .is_some_and(|priority| (low..=high).contains(&priority)),
+                None => branch_name.contains(&token),
+            })
     }
 
     /// After a filter change: keep the selected branch, else fall back to first
@@ -78,3 +83,11 @@ impl App {
         self.select_by_key_or(selected_key.as_deref(), OnMiss::First);
     }
 }
+
+/// The priorities a `p:2` or `p:1-2` filter term matches, as an inclusive
+/// range.
+fn priority_range(token: &str) -> Option<(u8, u8)> {
+    let range = token.strip_prefix("p:")?;
+    let (low, high) = range.split_once('-').unwrap_or((range, range));
+    Some((low.parse().ok()?, high.parse().ok()?))
+}
@@ -0,0 +1,159 @@
+use super::*;
+
+impl App {
+    pub fn get_children(&self, work_item_id: u32) -> Option<&ChildrenStatus> {
+        self.children.get(&work_item_id)
+    }
+
+    /// The selected work item, if it is loaded and its children have not
+    /// been fetched.
+    pub fn work_item_needing_children(&self) -> Option<u32> {
+        let id = self.selected_work_item_id()?;
+        (matches!(self.get_work_item_status(id), WorkItemStatus::Loaded(_))
+            && !self.children.contains_key(&id))
+        .then_some(id)
+    }
+
+    pub fn set_children_loading(&mut self, work_item_id: u32) {
+        self.update(Msg::SetChildren {
+            work_item_id,
+            status: ChildrenStatus::Loading,
+        });
+    }
+
+    pub fn set_children(&mut self, work_item_id: u32, result: Result<Vec<WorkItem>, String>) {
+        let status = match result {
+            Ok(children) => ChildrenStatus::Loaded(children),
+            Err(error) => ChildrenStatus::Error(redact(&error).into_owned()),
+        };
+        self.update(Msg::SetChildren {
+            work_item_id,
+            status,
+        });
+    }
+
+    /// Store the children of a work item that is still stored; those of one
+    /// evicted or refreshed meanwhile are dropped.
+    pub(super) fn apply_children(&mut self, work_item_id: u32, status: ChildrenStatus) {
+        if self.work_items.contains_key(&work_item_id) {
+            self.children.insert(work_item_id, status);
+        }
+    }
+
+    /// Forget the children of a work item, e.g. when their fetch is
+    /// cancelled, so they are fetched again when it is next selected.
+    pub fn reset_children(&mut self, work_item_id: u32) {
+        self.needs_redraw = true;
+        self.children.remove(&work_item_id);
+    }
+
+    /// The work items stepped into, while the hierarchy is open.
+    pub fn hierarchy(&self) -> Option<&HierarchyView> {
+        match &self.mode {
+            AppMode::Hierarchy(view) => Some(view),
+            _ => None,
+        }
+    }
+
+    /// The parent of work item `id`, once it is loaded, then its children
+    /// as fetched.
+    pub fn related_work_items(&self, id: u32) -> Vec<u32> {
+        let parent = match self.get_work_item_status(id) {
+            WorkItemStatus::Loaded(work_item) => work_item.parent_id(),
+            _ => None,
+        };
+        let children = match self.children.get(&id) {
+            Some(ChildrenStatus::Loaded(children)) => children.as_slice(),
+            _ => &[],
+        };
+        parent
+            .into_iter()
+            .chain(children.iter().map(|child| child.id))
+            .collect()
+    }
+
+    /// The related work item highlighted in the hierarchy.
+    pub fn selected_related_work_item(&self) -> Option<u32> {
+        let view = self.hierarchy()?;
+        self.related_work_items(*view.trail.last()?)
+            .get(view.selected)
+            .copied()
+    }
+
+    /// Step through the hierarchy of the selected work item, if there is one.
+    pub(super) fn apply_open_hierarchy(&mut self) {
+        if let Some(id) = self.selected_work_item_id() {
+            self.mode = AppMode::Hierarchy(HierarchyView {
+                trail: vec![id],
+                selected: 0,
+            });
+        }
+    }
+
+    /// Move the highlight by `delta`, stopping at either end.
+    pub(super) fn move_hierarchy_selection(&mut self, delta: isize) {
+        let Some(id) = self.selected_work_item_id() else {
+            return;
+        };
+        let last = self.related_work_items(id).len().saturating_sub(1);
+        if let AppMode::Hierarchy(view) = &mut self.mode {
+            view.selected = view.selected.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Show the highlighted related work item. A child is shown as its
+    /// parent's fetch returned it, unless it is already loaded; a parent is
+    /// fetched like the selected work item.
+    pub(super) fn apply_enter_related_work_item(&mut self) {
+        let (Some(shown), Some(related)) = (
+            self.selected_work_item_id(),
+            self.selected_related_work_item(),
+        ) else {
+            return;
+        };
+        let child = match self.children.get(&shown) {
+            Some(ChildrenStatus::Loaded(children)) => {
+                children.iter().find(|child| child.id == related).cloned()
+            }
+            _ => None,
+        };
+        if let Some(child) = child
+            && matches!(
+                self.get_work_item_status(related),
+                WorkItemStatus::NotFetched
+            )
+        {
+            self.store_work_item(related, WorkItemStatus::Loaded(child));
+        }
+        if let AppMode::Hierarchy(view) = &mut self.mode {
+            view.trail.push(related);
+            view.selected = 0;
+        }
+        self.scroll_offset = 0;
+    }
+
+    /// Show the work item stepped in from again, highlighting the one left,
+    /// or close the hierarchy when back at the selected work item.
+    pub(super) fn apply_hierarchy_back(&mut self) {
+        let Some(view) = self.hierarchy() else {
+            return;
+        };
+        if view.trail.len() <= 1 {
+            self.mode = AppMode::Normal;
+            self.scroll_offset = 0;
+            return;
+        }
+        let mut trail = view.trail.clone();
+        let left = trail.pop();
+        let shown = trail.last().copied();
+        let selected = shown
+            .and_then(|shown| {
+                self.related_work_items(shown)
+                    .iter()
+                    .position(|&id| Some(id) == left)
+            })
+            .unwrap_or(0);
+        self.mode = AppMode::Hierarchy(HierarchyView { trail, selected });
+        self.scroll_offset = 0;
+    }
+}
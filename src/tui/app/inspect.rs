@@ -0,0 +1,64 @@
+use super::*;
+
+impl App {
+    /// The JSON inspector, while it is open.
+    pub fn inspector(&self) -> Option<&JsonInspector> {
+        match &self.mode {
+            AppMode::Inspect(inspector) => Some(inspector),
+            _ => None,
+        }
+    }
+
+    /// Lines the JSON inspector shows at once, as last rendered.
+    pub fn inspector_height(&self) -> u16 {
+        self.inspector_height
+    }
+
+    pub fn set_inspector_json(&mut self, work_item_id: u32, result: Result<String, String>) {
+        self.update(Msg::SetInspectorJson {
+            work_item_id,
+            result: result.map_err(|error| redact(&error).into_owned()),
+        });
+    }
+
+    pub(super) fn apply_open_inspector(&mut self, work_item_id: u32) {
+        self.mode = AppMode::Inspect(JsonInspector {
+            work_item_id,
+            content: InspectorContent::Loading,
+            scroll: 0,
+        });
+    }
+
+    /// Show the JSON of `work_item_id` if the inspector is still open on it.
+    pub(super) fn apply_inspector_json(
+        &mut self,
+        work_item_id: u32,
+        result: Result<String, String>,
+    ) {
+        if let AppMode::Inspect(inspector) = &mut self.mode
+            && inspector.work_item_id == work_item_id
+        {
+            inspector.content = match result {
+                Ok(json) => InspectorContent::Loaded(json.lines().map(str::to_string).collect()),
+                Err(error) => InspectorContent::Error(error),
+            };
+        }
+    }
+
+    /// Scroll down, stopping once the last line is in view.
+    pub(super) fn scroll_inspector_down(&mut self, amount: usize) {
+        let height = usize::from(self.inspector_height);
+        if let AppMode::Inspect(inspector) = &mut self.mode
+            && let InspectorContent::Loaded(lines) = &inspector.content
+        {
+            let max_scroll = lines.len().saturating_sub(height);
+            inspector.scroll = inspector.scroll.saturating_add(amount).min(max_scroll);
+        }
+    }
+
+    pub(super) fn scroll_inspector_up(&mut self, amount: usize) {
+        if let AppMode::Inspect(inspector) = &mut self.mode {
+            inspector.scroll = inspector.scroll.saturating_sub(amount);
+        }
+    }
+}
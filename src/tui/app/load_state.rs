@@ -31,6 +31,29 @@ impl App {
         matches!(self.remote_freshness, RemoteFreshness::Checking)
     }
 
+    /// Mark a `git fetch` as started, unless one is already running.
+    pub fn start_git_fetch(&mut self) -> bool {
+        if self.git_fetch != GitFetchState::Idle {
+            return false;
+        }
+        self.update(Msg::SetGitFetch(GitFetchState::Running(None)));
+        true
+    }
+
+    pub fn set_git_fetch_progress(&mut self, progress: FetchProgress) {
+        if self.git_fetch != GitFetchState::Idle {
+            self.update(Msg::SetGitFetch(GitFetchState::Running(Some(progress))));
+        }
+    }
+
+    pub fn finish_git_fetch(&mut self) {
+        self.update(Msg::SetGitFetch(GitFetchState::Idle));
+    }
+
+    pub fn git_fetch(&self) -> &GitFetchState {
+        &self.git_fetch
+    }
+
     pub fn remote_freshness_error(&self) -> Option<&str> {
         match &self.remote_freshness {
             RemoteFreshness::Error(error) => Some(error.as_str()),
@@ -38,6 +61,34 @@ impl App {
         }
     }
 
+    /// Start fetching work items, showing `cached_work_items` from an earlier
+    /// session until they are fetched again.
+    pub fn set_client_ready(&mut self, cached_work_items: Vec<WorkItem>) {
+        self.add_cached_work_items(cached_work_items);
+        self.update(Msg::SetClientState(ClientState::Ready));
+    }
+
+    /// Explain in the details panel why work items cannot be fetched.
+    pub fn set_client_unavailable(&mut self, error: String) {
+        self.update(Msg::SetClientState(ClientState::Unavailable(
+            redact(&error).into_owned(),
+        )));
+    }
+
+    pub fn client_state(&self) -> &ClientState {
+        &self.client_state
+    }
+
+    fn add_cached_work_items(&mut self, work_items: Vec<WorkItem>) {
+        for work_item in work_items {
+            if self.work_items.contains_key(&work_item.id) {
+                continue;
+            }
+            self.cached_work_items.insert(work_item.id);
+            self.store_work_item(work_item.id, WorkItemStatus::Loaded(work_item));
+        }
+    }
+
     pub fn get_work_item_status(&self, id: u32) -> &WorkItemStatus {
         self.work_items
             .get(&id)
@@ -49,7 +100,7 @@ impl App {
     }
 
     pub(super) fn apply_work_item_loading(&mut self, id: u32) {
-        self.work_items.insert(id, WorkItemStatus::Loading);
+        self.store_work_item(id, WorkItemStatus::Loading);
     }
 
     pub fn set_work_item_loaded(&mut self, id: u32, work_item: WorkItem) {
@@ -57,8 +108,17 @@ impl App {
     }
 
     pub(super) fn apply_work_item_loaded(&mut self, id: u32, work_item: WorkItem) {
-        self.work_items
-            .insert(id, WorkItemStatus::Loaded(work_item));
+        self.cached_work_items.remove(&id);
+        self.failed_revalidations.remove(&id);
+        if self
+            .rendered_fields
+            .as_ref()
+            .is_some_and(|rendered| rendered.work_item_id == id)
+        {
+            self.rendered_fields = None;
+        }
+        self.update_listed_work_item(&work_item);
+        self.store_work_item(id, WorkItemStatus::Loaded(work_item));
     }
 
     pub fn set_work_item_error(&mut self, id: u32, error: String) {
@@ -66,11 +126,226 @@ impl App {
     }
 
     pub(super) fn apply_work_item_error(&mut self, id: u32, error: String) {
-        self.work_items.insert(id, WorkItemStatus::Error(error));
+        self.store_work_item(id, WorkItemStatus::Error(error));
     }
 
-    pub fn reset_work_item(&mut self, id: u32) {
+    /// Keep showing the cached copy of a work item that could not be fetched
+    /// again.
+    pub fn set_work_item_revalidation_failed(&mut self, id: u32) {
+        self.update(Msg::WorkItemRevalidationFailed(id));
+    }
+
+    /// Whether the work item shown is a cached copy not fetched this session.
+    pub fn is_work_item_stale(&self, id: u32) -> bool {
+        self.cached_work_items.contains(&id)
+    }
+
+    /// Work item IDs of the visible branches that have not been fetched yet,
+    /// nearest the selection first (the selected branch's, if any, leads).
+    pub fn unfetched_work_item_ids(&self) -> Vec<u32> {
+        self.visible_work_item_ids(|id| {
+            matches!(self.get_work_item_status(id), WorkItemStatus::NotFetched)
+        })
+    }
+
+    /// Work item IDs of the visible branches that are not fetched yet or
+    /// still loading, nearest the selection first.
+    pub fn unloaded_work_item_ids(&self) -> Vec<u32> {
+        self.visible_work_item_ids(|id| {
+            matches!(
+                self.get_work_item_status(id),
+                WorkItemStatus::NotFetched | WorkItemStatus::Loading
+            )
+        })
+    }
+
+    /// Work item IDs of the visible branches showing cached copies that have
+    /// not failed to revalidate, nearest the selection first.
+    pub fn stale_work_item_ids(&self) -> Vec<u32> {
+        self.visible_work_item_ids(|id| {
+            self.is_work_item_stale(id) && !self.failed_revalidations.contains(&id)
+        })
+    }
+
+    /// Work item IDs of the local branches that are not fetched yet or show
+    /// cached copies, up to the capacity, for prefetching in one request.
+    pub fn local_work_item_ids_to_prefetch(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = Vec::new();
+        for id in self
+            .branches
+            .iter()
+            .filter(|branch| branch.scope == BranchScope::Local)
+            .filter_map(|branch| branch.work_item_id)
+        {
+            if ids.len() == self.work_item_capacity {
+                break;
+            }
+            let unfetched = matches!(self.get_work_item_status(id), WorkItemStatus::NotFetched);
+            if (unfetched || self.is_work_item_stale(id)) && !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+
+    /// Work items fetched this session, for the cache, least recently
+    /// viewed first.
+    pub fn fresh_work_items(&self) -> Vec<WorkItem> {
+        self.recent_work_items
+            .iter()
+            .filter(|id| !self.is_work_item_stale(**id))
+            .filter_map(|id| match self.work_items.get(id) {
+                Some(WorkItemStatus::Loaded(work_item)) => Some(work_item.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The `include`d work items among the visible ones nearest the
+    /// selection, up to the capacity, so fetching ahead never loads more
+    /// than can be kept.
+    fn visible_work_item_ids(&self, include: impl Fn(u32) -> bool) -> Vec<u32> {
+        self.nearby_work_item_ids()
+            .into_iter()
+            .filter(|id| include(*id))
+            .collect()
+    }
+
+    /// Work item IDs of the visible branches, nearest the selection first,
+    /// up to the capacity. The parent of the selected work item follows it,
+    /// as the details panel shows its title. A work item stepped into in the
+    /// hierarchy, which no branch may link to, comes first.
+    fn nearby_work_item_ids(&self) -> Vec<u32> {
+        let selected = self.selected_index();
+        let mut branches: Vec<(usize, u32)> = self
+            .visible_branches()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, branch)| Some((index.abs_diff(selected), branch.work_item_id?)))
+            .collect();
+        branches.sort_by_key(|(distance, _)| *distance);
+
+        let parent = self.selected_parent_id();
+        let stepped_into = self.hierarchy().and_then(|view| view.trail.last().copied());
+        let focused = stepped_into
+            .into_iter()
+            .chain(parent.filter(|_| stepped_into.is_some()));
+        let branch_ids = branches
+            .into_iter()
+            .flat_map(|(distance, id)| std::iter::once(id).chain(parent.filter(|_| distance == 0)));
+        let mut ids = Vec::new();
+        for id in focused.chain(branch_ids) {
+            if ids.len() == self.work_item_capacity {
+                break;
+            }
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+
+    /// Store `status` as the most recently used work item, then drop the
+    /// least recently used beyond the capacity.
+    pub(super) fn store_work_item(&mut self, id: u32, status: WorkItemStatus) {
+        self.keeping_selection(|app| {
+            app.work_items.insert(id, status);
+            app.touch_work_item(id);
+            app.evict_work_items();
+        });
+    }
+
+    /// Mark a stored work item as the most recently used.
+    pub(super) fn touch_work_item(&mut self, id: u32) {
+        if self.recent_work_items.back() == Some(&id) || !self.work_items.contains_key(&id) {
+            return;
+        }
+        self.recent_work_items.retain(|&recent| recent != id);
+        self.recent_work_items.push_back(id);
+    }
+
+    /// Drop the least recently used work items until the capacity is met.
+    /// Those near the selection stay, as they would only be fetched again,
+    /// and so do those still loading.
+    fn evict_work_items(&mut self) {
+        if self.work_items.len() <= self.work_item_capacity {
+            return;
+        }
+        let nearby: HashSet<u32> = self.nearby_work_item_ids().into_iter().collect();
+        while self.work_items.len() > self.work_item_capacity {
+            let Some(&id) = self.recent_work_items.iter().find(|id| {
+                !nearby.contains(id)
+                    && !matches!(self.work_items.get(id), Some(WorkItemStatus::Loading))
+            }) else {
+                break;
+            };
+            self.forget_work_item(id);
+        }
+    }
+
+    fn forget_work_item(&mut self, id: u32) {
         self.work_items.remove(&id);
+        self.pull_requests.remove(&id);
+        self.comments.remove(&id);
+        self.children.remove(&id);
+        self.recent_work_items.retain(|&recent| recent != id);
+        self.cached_work_items.remove(&id);
+        self.failed_revalidations.remove(&id);
+    }
+
+    pub fn reset_work_item(&mut self, id: u32) {
+        self.needs_redraw = true;
+        self.keeping_selection(|app| app.forget_work_item(id));
+    }
+
+    /// The selected work item, if it is loaded and its linked pull requests
+    /// have not been fetched.
+    pub fn work_item_needing_pull_requests(&self) -> Option<u32> {
+        let id = self.selected_work_item_id()?;
+        (matches!(self.get_work_item_status(id), WorkItemStatus::Loaded(_))
+            && !self.pull_requests.contains_key(&id))
+        .then_some(id)
+    }
+
+    pub fn get_pull_requests(&self, work_item_id: u32) -> Option<&PullRequestsStatus> {
+        self.pull_requests.get(&work_item_id)
+    }
+
+    pub fn set_pull_requests_loading(&mut self, work_item_id: u32) {
+        self.update(Msg::SetPullRequests {
+            work_item_id,
+            status: PullRequestsStatus::Loading,
+        });
+    }
+
+    pub fn set_pull_requests(
+        &mut self,
+        work_item_id: u32,
+        result: Result<Vec<PullRequest>, String>,
+    ) {
+        let status = match result {
+            Ok(pull_requests) => PullRequestsStatus::Loaded(pull_requests),
+            Err(error) => PullRequestsStatus::Error(redact(&error).into_owned()),
+        };
+        self.update(Msg::SetPullRequests {
+            work_item_id,
+            status,
+        });
+    }
+
+    /// Store the pull requests of a work item that is still stored; those
+    /// of one evicted or refreshed meanwhile are dropped.
+    pub(super) fn apply_pull_requests(&mut self, work_item_id: u32, status: PullRequestsStatus) {
+        if self.work_items.contains_key(&work_item_id) {
+            self.pull_requests.insert(work_item_id, status);
+        }
+    }
+
+    /// Forget the linked pull requests of a work item, e.g. when their fetch
+    /// is cancelled, so they are fetched again when it is next selected.
+    pub fn reset_pull_requests(&mut self, work_item_id: u32) {
+        self.needs_redraw = true;
+        self.pull_requests.remove(&work_item_id);
     }
 
     pub fn current_branch_has_work_item(&self) -> bool {
@@ -108,6 +383,21 @@ impl App {
         self.branch_statuses.insert(key, Err(error));
     }
 
+    /// Every branch, in list order, e.g. to load their statuses again after
+    /// a fetch.
+    pub fn branches(&self) -> &[BranchInfo] {
+        &self.branches
+    }
+
+    /// Branches, in list order, whose status has not been loaded.
+    pub fn branches_needing_status(&self) -> Vec<BranchInfo> {
+        self.branches
+            .iter()
+            .filter(|branch| self.needs_branch_status(&branch.key))
+            .cloned()
+            .collect()
+    }
+
     pub fn needs_branch_status(&self, key: &str) -> bool {
         !matches!(self.branch_statuses.get(key), Some(Ok(_)))
     }
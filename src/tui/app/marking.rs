@@ -0,0 +1,66 @@
+use super::*;
+
+impl App {
+    pub fn is_marked(&self, branch: &BranchInfo) -> bool {
+        self.marked.contains(&branch.key)
+    }
+
+    pub fn has_marked_branches(&self) -> bool {
+        !self.marked.is_empty()
+    }
+
+    /// The marked branches in list order, local ones first.
+    pub fn marked_branches(&self) -> Vec<&BranchInfo> {
+        self.branches
+            .iter()
+            .filter(|branch| self.marked.contains(&branch.key))
+            .collect()
+    }
+
+    /// The outcomes shown after deleting the marked branches.
+    pub fn delete_outcomes(&self) -> Option<&[DeleteOutcome]> {
+        match &self.mode {
+            AppMode::DeleteResults(outcomes) => Some(outcomes),
+            _ => None,
+        }
+    }
+
+    pub(super) fn toggle_mark(&mut self) {
+        let Some(key) = self.selected_branch().map(|branch| branch.key.clone()) else {
+            return;
+        };
+        if !self.marked.remove(&key) {
+            self.marked.insert(key.clone());
+        }
+        self.mark_anchor = Some(key);
+    }
+
+    /// Mark the visible branches between the anchor and the selected branch,
+    /// both included, leaving out those that cannot be deleted. Without a
+    /// visible anchor, only the selected branch is marked.
+    pub(super) fn mark_range(&mut self) {
+        let visible = self.visible_branches();
+        let selected = self.selected_index();
+        let Some(branch) = visible.get(selected) else {
+            return;
+        };
+        let anchor = self
+            .mark_anchor
+            .as_deref()
+            .and_then(|anchor| visible.iter().position(|branch| branch.key == anchor))
+            .unwrap_or(selected);
+        let key = branch.key.clone();
+        let keys: Vec<String> = visible[anchor.min(selected)..=anchor.max(selected)]
+            .iter()
+            .filter(|branch| Self::can_delete(branch).is_ok())
+            .map(|branch| branch.key.clone())
+            .collect();
+        self.marked.extend(keys);
+        self.mark_anchor = Some(key);
+    }
+
+    pub(super) fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.mark_anchor = None;
+    }
+}
@@ -0,0 +1,67 @@
+use super::*;
+
+impl App {
+    pub fn pull_request_list(&self) -> &PullRequestList {
+        &self.pull_request_list
+    }
+
+    pub fn is_pull_request_list_open(&self) -> bool {
+        matches!(self.mode, AppMode::PullRequests)
+    }
+
+    /// The repository whose pull requests the tab lists, or why there is
+    /// none.
+    pub fn repository(&self) -> Result<&RepositoryRef, &str> {
+        self.repository.as_ref().map_err(String::as_str)
+    }
+
+    /// The pull request highlighted in the pull request tab.
+    pub fn selected_listed_pull_request(&self) -> Option<&PullRequest> {
+        self.pull_request_list
+            .entries
+            .get(self.pull_request_list.selected)
+    }
+
+    /// Whether the open pull request tab should list the pull requests.
+    pub fn pull_request_list_due(&self) -> bool {
+        self.is_pull_request_list_open()
+            && !self.pull_request_list.loading
+            && !self.pull_request_list.listed
+    }
+
+    pub fn set_pull_request_list(&mut self, result: Result<Vec<PullRequest>, String>) {
+        self.update(Msg::SetPullRequestList(
+            result.map_err(|error| redact(&error).into_owned()),
+        ));
+    }
+
+    /// Replace the entries, keeping the highlight on the same pull request
+    /// when it is still listed. A failed listing keeps the entries of the
+    /// last one.
+    pub(super) fn apply_pull_request_list(&mut self, result: Result<Vec<PullRequest>, String>) {
+        self.pull_request_list.loading = false;
+        self.pull_request_list.listed = true;
+        match result {
+            Ok(entries) => {
+                let selected = self.selected_listed_pull_request().map(|pr| pr.id);
+                self.pull_request_list.selected = entries
+                    .iter()
+                    .position(|pr| Some(pr.id) == selected)
+                    .unwrap_or(0);
+                self.pull_request_list.entries = entries;
+                self.pull_request_list.error = None;
+            }
+            Err(error) => self.pull_request_list.error = Some(error),
+        }
+    }
+
+    /// Move the highlight by `delta`, stopping at either end.
+    pub(super) fn move_pull_request_selection(&mut self, delta: isize) {
+        let last = self.pull_request_list.entries.len().saturating_sub(1);
+        self.pull_request_list.selected = self
+            .pull_request_list
+            .selected
+            .saturating_add_signed(delta)
+            .min(last);
+    }
+}
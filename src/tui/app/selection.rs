@@ -1,4 +1,5 @@
 use super::*;
+use chrono::Utc;
 
 impl App {
     pub fn selected_branch(&self) -> Option<&BranchInfo> {
@@ -7,12 +8,41 @@ impl App {
     }
 
     pub fn visible_branches(&self) -> Vec<&BranchInfo> {
-        self.branches
+        let mut visible: Vec<&BranchInfo> = self
+            .branches
             .iter()
             .filter(|branch| self.matches_active_view(branch))
             .filter(|branch| self.show_protected || branch.is_current || !branch.is_protected)
             .filter(|branch| self.branch_matches_filter(branch, self.effective_branch_filter()))
-            .collect()
+            .collect();
+        if self.sort_by_priority {
+            visible.sort_by_key(|branch| self.branch_priority(branch).unwrap_or(u8::MAX));
+        }
+        visible
+    }
+
+    pub fn is_sorted_by_priority(&self) -> bool {
+        self.sort_by_priority
+    }
+
+    /// The priority of the branch's work item, once it is loaded.
+    pub fn branch_priority(&self, branch: &BranchInfo) -> Option<u8> {
+        match self.get_work_item_status(branch.work_item_id?) {
+            WorkItemStatus::Loaded(work_item) => work_item.priority(),
+            _ => None,
+        }
+    }
+
+    /// Whether the branch's work item is loaded and past its target or due
+    /// date.
+    pub fn branch_is_overdue(&self, branch: &BranchInfo) -> bool {
+        let Some(id) = branch.work_item_id else {
+            return false;
+        };
+        match self.get_work_item_status(id) {
+            WorkItemStatus::Loaded(work_item) => work_item.is_overdue(Utc::now()),
+            _ => false,
+        }
     }
 
     pub fn visible_count(&self) -> usize {
@@ -41,6 +71,7 @@ impl App {
     /// resetting scroll. Falls back to a clamped local selection when the
     /// branch is not currently visible.
     pub fn focus_local_branch(&mut self, branch_name: &str) {
+        self.needs_redraw = true;
         self.active_view = BranchView::Local;
         self.scroll_offset = 0;
 
@@ -94,6 +125,26 @@ impl App {
         self.select_by_key_or(selected_key.as_deref(), OnMiss::Clamp);
     }
 
+    /// Sort by work item priority, highest first and branches without one
+    /// last, or back to the usual order.
+    pub(super) fn toggle_sort_by_priority(&mut self) {
+        let selected_key = self.selected_branch().map(|branch| branch.key.clone());
+        self.sort_by_priority = !self.sort_by_priority;
+        self.select_by_key_or(selected_key.as_deref(), OnMiss::Clamp);
+    }
+
+    /// Apply `change`, keeping the selected branch selected while sorting by
+    /// priority moves it as work items load.
+    pub(super) fn keeping_selection(&mut self, change: impl FnOnce(&mut Self)) {
+        if !self.sort_by_priority {
+            change(self);
+            return;
+        }
+        let selected_key = self.selected_branch().map(|branch| branch.key.clone());
+        change(self);
+        self.select_by_key_or(selected_key.as_deref(), OnMiss::Clamp);
+    }
+
     pub(super) fn toggle_view(&mut self) {
         self.active_view = self.active_view.toggle();
         if self.active_view == BranchView::Remote
@@ -0,0 +1,38 @@
+use super::*;
+
+impl App {
+    pub fn session_log(&self) -> &SessionLog {
+        &self.session
+    }
+
+    /// Record the selected work item as viewed once it has loaded.
+    pub fn record_viewed_work_item(&mut self) {
+        if let Some(id) = self.selected_work_item_id()
+            && !self.session.has_viewed(id)
+            && matches!(self.get_work_item_status(id), WorkItemStatus::Loaded(_))
+        {
+            self.update(Msg::RecordView(id));
+        }
+    }
+
+    pub(super) fn apply_record_view(&mut self, work_item_id: u32) {
+        if let WorkItemStatus::Loaded(work_item) = self.get_work_item_status(work_item_id) {
+            let title = work_item.title.clone();
+            self.session.record_view(work_item_id, title, Utc::now());
+        }
+    }
+
+    /// Record the move to `to` with the state the work item is loaded in.
+    pub(super) fn apply_record_state_change(&mut self, work_item_id: u32, to: String) {
+        let from = match self.get_work_item_status(work_item_id) {
+            WorkItemStatus::Loaded(work_item) => Some(work_item.state.display_name().to_string()),
+            _ => None,
+        };
+        self.session.record_state_change(StateChange {
+            work_item_id,
+            from,
+            to,
+            at: Utc::now(),
+        });
+    }
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+impl App {
+    /// The state menu, while it is open.
+    pub fn state_menu(&self) -> Option<&StateMenu> {
+        match &self.mode {
+            AppMode::ChangeState(menu) => Some(menu),
+            _ => None,
+        }
+    }
+
+    /// The work item and the state highlighted in the state menu, if its
+    /// transitions have loaded.
+    pub fn selected_state_transition(&self) -> Option<(u32, &StateTransition)> {
+        let menu = self.state_menu()?;
+        match &menu.content {
+            StateMenuContent::Loaded(transitions) => transitions
+                .get(menu.selected)
+                .map(|transition| (menu.work_item_id, transition)),
+            _ => None,
+        }
+    }
+
+    pub fn set_state_transitions(
+        &mut self,
+        work_item_id: u32,
+        result: Result<Vec<StateTransition>, String>,
+    ) {
+        self.update(Msg::SetStateTransitions {
+            work_item_id,
+            result: result.map_err(|error| redact(&error).into_owned()),
+        });
+    }
+
+    pub(super) fn apply_open_state_menu(&mut self, work_item_id: u32) {
+        self.mode = AppMode::ChangeState(StateMenu {
+            work_item_id,
+            content: StateMenuContent::Loading,
+            selected: 0,
+        });
+    }
+
+    /// Show the transitions of `work_item_id` if the menu is still open on it.
+    pub(super) fn apply_state_transitions(
+        &mut self,
+        work_item_id: u32,
+        result: Result<Vec<StateTransition>, String>,
+    ) {
+        if let AppMode::ChangeState(menu) = &mut self.mode
+            && menu.work_item_id == work_item_id
+        {
+            menu.content = match result {
+                Ok(transitions) => StateMenuContent::Loaded(transitions),
+                Err(error) => StateMenuContent::Error(error),
+            };
+            menu.selected = 0;
+        }
+    }
+
+    /// Move the highlight by `delta`, wrapping around at either end.
+    pub(super) fn move_state_selection(&mut self, delta: isize) {
+        if let AppMode::ChangeState(menu) = &mut self.mode
+            && let StateMenuContent::Loaded(transitions) = &menu.content
+            && !transitions.is_empty()
+        {
+            let len = transitions.len() as isize;
+            menu.selected = (menu.selected as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+}
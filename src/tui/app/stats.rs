@@ -0,0 +1,163 @@
+use super::*;
+use crate::git::RemoteStatus;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Upper bounds in days of the last commit age buckets, with their labels;
+/// older branches fall in the last bucket.
+const AGE_BUCKETS: &[(i64, Text)] = &[
+    (7, Text::StatsAgeWeek),
+    (30, Text::StatsAgeMonth),
+    (90, Text::StatsAgeQuarter),
+];
+
+impl App {
+    /// The merged branch check of the statistics popup, while it is open.
+    pub fn merged_branches(&self) -> Option<&MergedBranches> {
+        match &self.mode {
+            AppMode::BranchStats(merged) => Some(merged),
+            _ => None,
+        }
+    }
+
+    pub fn set_merged_branches(&mut self, result: Result<HashSet<String>, String>) {
+        self.update(Msg::SetMergedBranches(result));
+    }
+
+    pub(super) fn apply_merged_branches(&mut self, result: Result<HashSet<String>, String>) {
+        if let AppMode::BranchStats(merged) = &mut self.mode {
+            *merged = match result {
+                Ok(names) => MergedBranches::Checked(names),
+                Err(error) => MergedBranches::Failed(error),
+            };
+        }
+    }
+
+    /// Counts of the local branches as of `now`, a Unix timestamp, while the
+    /// statistics popup is open. Branches whose status or work item has not
+    /// loaded yet are counted as unknown.
+    pub fn branch_stats(&self, now: i64) -> Option<BranchStats> {
+        let merged = match self.merged_branches()? {
+            MergedBranches::Checked(names) => Some(names),
+            _ => None,
+        };
+        let local: Vec<&BranchInfo> = self
+            .branches
+            .iter()
+            .filter(|branch| branch.scope == BranchScope::Local)
+            .collect();
+
+        let mut by_state = Counts::default();
+        let mut by_age = Counts::default();
+        let mut by_remote = Counts::default();
+        let mut cleanup = Cleanup {
+            merged: merged.map(|_| 0),
+            ..Cleanup::default()
+        };
+        for branch in &local {
+            by_state.add(
+                match branch.work_item_id.map(|id| self.get_work_item_status(id)) {
+                    None => t(Text::StatsNoWorkItem).to_string(),
+                    Some(WorkItemStatus::Loaded(work_item)) => {
+                        work_item.state.display_name().to_string()
+                    }
+                    Some(_) => t(Text::Unknown).to_string(),
+                },
+            );
+
+            let status = self.get_branch_status(&branch.key);
+            by_age.add(age_label(
+                status.and_then(|status| status.last_commit_time),
+                now,
+            ));
+            by_remote.add(remote_label(status.map(|status| &status.remote_status)));
+
+            if branch.is_protected || branch.is_current {
+                continue;
+            }
+            let is_gone =
+                status.is_some_and(|status| matches!(status.remote_status, RemoteStatus::Gone));
+            let is_merged = merged.is_some_and(|names| names.contains(&branch.branch_name));
+            cleanup.gone += usize::from(is_gone);
+            if let Some(count) = &mut cleanup.merged {
+                *count += usize::from(is_merged);
+            }
+            cleanup.total += usize::from(is_gone || is_merged);
+        }
+
+        by_state
+            .0
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        by_age.sort_by_labels(
+            AGE_BUCKETS
+                .iter()
+                .map(|&(_, text)| text)
+                .chain([Text::StatsAgeOlder, Text::Unknown]),
+        );
+        by_remote.sort_by_labels([
+            Text::UpToDate,
+            Text::StatsAhead,
+            Text::StatsBehind,
+            Text::StatsDiverged,
+            Text::LocalOnly,
+            Text::RemoteGone,
+            Text::Unknown,
+        ]);
+        Some(BranchStats {
+            local_branches: local.len(),
+            by_state: by_state.0,
+            by_age: by_age.0,
+            by_remote: by_remote.0,
+            cleanup,
+        })
+    }
+}
+
+/// Row counts by label, in the order labels are first seen.
+#[derive(Default)]
+struct Counts(Vec<(String, usize)>);
+
+impl Counts {
+    fn add(&mut self, label: String) {
+        match self.0.iter_mut().find(|(seen, _)| *seen == label) {
+            Some((_, count)) => *count += 1,
+            None => self.0.push((label, 1)),
+        }
+    }
+
+    /// Order the rows like `labels`.
+    fn sort_by_labels(&mut self, labels: impl IntoIterator<Item = Text>) {
+        let order: Vec<&str> = labels.into_iter().map(t).collect();
+        self.0.sort_by_key(|(label, _)| {
+            order
+                .iter()
+                .position(|known| known == label)
+                .unwrap_or(order.len())
+        });
+    }
+}
+
+fn age_label(last_commit_time: Option<i64>, now: i64) -> String {
+    let Some(time) = last_commit_time else {
+        return t(Text::Unknown).to_string();
+    };
+    let days = (now - time) / SECONDS_PER_DAY;
+    let text = AGE_BUCKETS
+        .iter()
+        .find(|&&(max_days, _)| days < max_days)
+        .map_or(Text::StatsAgeOlder, |&(_, text)| text);
+    t(text).to_string()
+}
+
+fn remote_label(remote_status: Option<&RemoteStatus>) -> String {
+    let text = match remote_status {
+        Some(RemoteStatus::UpToDate) => Text::UpToDate,
+        Some(RemoteStatus::Ahead(_)) => Text::StatsAhead,
+        Some(RemoteStatus::Behind(_)) => Text::StatsBehind,
+        Some(RemoteStatus::Diverged { .. }) => Text::StatsDiverged,
+        Some(RemoteStatus::LocalOnly) => Text::LocalOnly,
+        Some(RemoteStatus::Gone) => Text::RemoteGone,
+        Some(RemoteStatus::RemoteTracking) | None => Text::Unknown,
+    };
+    t(text).to_string()
+}
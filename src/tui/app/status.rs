@@ -52,6 +52,13 @@ impl App {
             .filter(|message| message.expires_at > Instant::now())
     }
 
+    /// When the status message shown, if any, is due to be cleared.
+    pub fn status_expires_at(&self) -> Option<Instant> {
+        self.status_message
+            .as_ref()
+            .map(|message| message.expires_at)
+    }
+
     pub fn clear_expired_status(&mut self) {
         if let Some(ref message) = self.status_message
             && message.expires_at <= Instant::now()
@@ -0,0 +1,117 @@
+use super::*;
+use crate::tui::theme::timing::{CHANGE_CHECK_INTERVAL, MAX_TOASTS, TOAST_DURATION};
+
+impl App {
+    /// The toasts showing, newest first.
+    pub fn toasts(&self) -> impl Iterator<Item = &Toast> {
+        self.toasts.iter().rev().take(MAX_TOASTS)
+    }
+
+    /// Drop the toasts that have expired.
+    pub fn clear_expired_toasts(&mut self) {
+        let now = Instant::now();
+        if self.toasts.iter().any(|toast| toast.expires_at <= now) {
+            self.update(Msg::ClearExpiredToasts);
+        }
+    }
+
+    /// When the first toast showing expires.
+    pub fn toasts_expire_at(&self) -> Option<Instant> {
+        self.toasts.iter().map(|toast| toast.expires_at).min()
+    }
+
+    /// Work items fetched this session, whose changes raise toasts.
+    pub fn work_items_to_check(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .work_items
+            .iter()
+            .filter(|(id, status)| {
+                matches!(status, WorkItemStatus::Loaded(_)) && !self.is_work_item_stale(**id)
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// When loaded work items are checked for changes next; `None` without a
+    /// client or while a check is in flight.
+    pub fn change_check_at(&self) -> Option<Instant> {
+        (matches!(self.client_state, ClientState::Ready) && !self.checking_changes)
+            .then(|| self.changes_checked_at + CHANGE_CHECK_INTERVAL)
+    }
+
+    /// Whether loaded work items should be checked for changes as of `now`.
+    pub fn change_check_due(&self, now: Instant) -> bool {
+        self.change_check_at().is_some_and(|at| at <= now)
+    }
+
+    /// Store the work items fetched again, raising a toast for each that
+    /// changed since it loaded.
+    pub(super) fn apply_changes_checked(&mut self, work_items: Vec<WorkItem>) {
+        self.checking_changes = false;
+        let expires_at = Instant::now() + TOAST_DURATION;
+        for work_item in work_items {
+            let WorkItemStatus::Loaded(previous) = self.get_work_item_status(work_item.id) else {
+                continue;
+            };
+            if work_item.revision() <= previous.revision() {
+                continue;
+            }
+            self.toasts.push(Toast {
+                work_item_id: work_item.id,
+                text: toast_text(previous, &work_item),
+                expires_at,
+            });
+            self.keeping_selection(|app| app.apply_work_item_loaded(work_item.id, work_item));
+        }
+    }
+
+    /// Select the branch of the newest toast's work item, in the other view
+    /// if the active one has none, and dismiss the toast.
+    pub(super) fn apply_jump_to_toast(&mut self) {
+        let Some(toast) = self.toasts.pop() else {
+            return;
+        };
+        let id = toast.work_item_id;
+        let key_in_view = |app: &Self| {
+            app.visible_branches()
+                .iter()
+                .find(|branch| branch.work_item_id == Some(id))
+                .map(|branch| branch.key.clone())
+        };
+        let key = key_in_view(self).or_else(|| {
+            self.toggle_view();
+            let key = key_in_view(self);
+            if key.is_none() {
+                self.toggle_view();
+            }
+            key
+        });
+        match key {
+            Some(key) => self.select_by_key_or(Some(&key), OnMiss::Clamp),
+            None => self
+                .apply_background_error(tf(Text::NoBranchForWorkItem, &[("id", &id.to_string())])),
+        }
+    }
+}
+
+/// `#1234 moved to Resolved by Alice`, or what else changed.
+fn toast_text(previous: &WorkItem, work_item: &WorkItem) -> String {
+    let id = work_item.id.to_string();
+    let state = work_item.state.display_name();
+    let mut text = if state != previous.state.display_name() {
+        tf(Text::ToastMoved, &[("id", &id), ("state", state)])
+    } else if work_item.assigned_to != previous.assigned_to {
+        match &work_item.assigned_to {
+            Some(name) => tf(Text::ToastReassigned, &[("id", &id), ("name", name)]),
+            None => tf(Text::ToastUnassigned, &[("id", &id)]),
+        }
+    } else {
+        tf(Text::ToastChanged, &[("id", &id)])
+    };
+    if let Some(name) = work_item.changed_by() {
+        text = format!("{text} {}", tf(Text::By, &[("name", name)]));
+    }
+    text
+}
@@ -0,0 +1,99 @@
+use super::*;
+
+impl App {
+    pub fn work_item_list(&self) -> &WorkItemList {
+        &self.work_item_list
+    }
+
+    pub fn is_work_item_list_open(&self) -> bool {
+        matches!(self.mode, AppMode::WorkItemList)
+    }
+
+    /// The WIQL query whose work items the list shows.
+    pub fn work_item_query(&self) -> &str {
+        &self.work_item_query
+    }
+
+    /// The work item highlighted in the work item list.
+    pub fn selected_listed_work_item(&self) -> Option<&WorkItem> {
+        self.work_item_list
+            .entries
+            .get(self.work_item_list.selected)
+    }
+
+    /// Whether the open work item list should run the query.
+    pub fn work_item_list_due(&self) -> bool {
+        self.is_work_item_list_open() && !self.work_item_list.loading && !self.work_item_list.listed
+    }
+
+    pub fn set_work_item_list(&mut self, result: Result<Vec<WorkItem>, String>) {
+        self.update(Msg::SetWorkItemList(
+            result.map_err(|error| redact(&error).into_owned()),
+        ));
+    }
+
+    /// Replace the entries, keeping the highlight on the same work item when
+    /// it is still listed. A failed query keeps the entries of the last one.
+    pub(super) fn apply_work_item_list(&mut self, result: Result<Vec<WorkItem>, String>) {
+        self.work_item_list.loading = false;
+        self.work_item_list.listed = true;
+        match result {
+            Ok(entries) => {
+                let selected = self
+                    .selected_listed_work_item()
+                    .map(|work_item| work_item.id);
+                self.work_item_list.selected = entries
+                    .iter()
+                    .position(|work_item| Some(work_item.id) == selected)
+                    .unwrap_or(0);
+                self.work_item_list.entries = entries;
+                self.work_item_list.error = None;
+                self.store_listed_work_item();
+            }
+            Err(error) => self.work_item_list.error = Some(error),
+        }
+    }
+
+    /// Move the highlight by `delta`, stopping at either end.
+    pub(super) fn move_work_item_list_selection(&mut self, delta: isize) {
+        let last = self.work_item_list.entries.len().saturating_sub(1);
+        let selected = self
+            .work_item_list
+            .selected
+            .saturating_add_signed(delta)
+            .min(last);
+        if selected != self.work_item_list.selected {
+            self.work_item_list.selected = selected;
+            self.scroll_offset = 0;
+            self.store_listed_work_item();
+        }
+    }
+
+    /// Replace the listed copy of `work_item`, e.g. after its state was
+    /// changed, so the list does not show it as the query last returned it.
+    pub(super) fn update_listed_work_item(&mut self, work_item: &WorkItem) {
+        if let Some(entry) = self
+            .work_item_list
+            .entries
+            .iter_mut()
+            .find(|entry| entry.id == work_item.id)
+        {
+            *entry = work_item.clone();
+        }
+    }
+
+    /// Show the highlighted work item as the query returned it, instead of
+    /// fetching it again, unless it is already loaded.
+    fn store_listed_work_item(&mut self) {
+        let Some(work_item) = self.selected_listed_work_item() else {
+            return;
+        };
+        if matches!(
+            self.get_work_item_status(work_item.id),
+            WorkItemStatus::NotFetched
+        ) {
+            let work_item = work_item.clone();
+            self.store_work_item(work_item.id, WorkItemStatus::Loaded(work_item));
+        }
+    }
+}
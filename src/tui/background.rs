@@ -1,52 +1,379 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
 
-use super::app::{App, Msg, WorkItemStatus};
+use super::app::{App, BranchInfo, Msg};
+use super::fetch::{FetchKind, FetchManager, RetryPolicy};
 use super::theme::timing;
-use crate::azure_devops::{AzureDevOpsClient, WorkItem};
-use crate::git::{GitRepo, list_origin_remote_heads_in_dir};
+use crate::azure_devops::{
+    ActivityEntry, Comment, CompletionOptions, PullRequest, StateTransition, WorkItem,
+    WorkItemProvider,
+};
+use crate::git::{
+    BranchStatus, FetchProgress, GitRepo, fetch_origin_in_dir, list_origin_remote_heads_in_dir,
+};
+use crate::i18n::{Text, t, tf};
 
 const REMOTE_FRESHNESS_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Threads computing branch statuses at startup
+const BRANCH_STATUS_WORKERS: usize = 4;
+
 pub(super) enum FetchResult {
-    Success { id: u32, work_item: WorkItem },
-    Error { id: u32, error: String },
-    RemoteFreshnessSuccess { live_branches: HashSet<String> },
-    RemoteFreshnessError { error: String },
+    Success {
+        id: u32,
+        work_item: WorkItem,
+    },
+    Error {
+        id: u32,
+        error: String,
+    },
+    RevalidationError {
+        id: u32,
+        error: String,
+    },
+    /// The local branches' work items, by ID
+    WorkItemBatchSuccess {
+        results: Vec<(u32, Result<WorkItem, String>)>,
+    },
+    WorkItemBatchError {
+        error: String,
+    },
+    PullRequests {
+        work_item_id: u32,
+        result: Result<Vec<PullRequest>, String>,
+    },
+    Comments {
+        work_item_id: u32,
+        result: Result<Vec<Comment>, String>,
+    },
+    Children {
+        work_item_id: u32,
+        result: Result<Vec<WorkItem>, String>,
+    },
+    BranchStatus {
+        key: String,
+        result: Result<BranchStatus, String>,
+    },
+    /// The status of the selected branch, loaded on demand; unlike a
+    /// prefetched one, its error is reported in the status bar.
+    SelectedBranchStatus {
+        key: String,
+        display_name: String,
+        result: Result<BranchStatus, String>,
+    },
+    RemoteFreshnessSuccess {
+        live_branches: HashSet<String>,
+    },
+    RemoteFreshnessError {
+        error: String,
+    },
+    GitFetchProgress(FetchProgress),
+    /// The keys of the remote branches a pruning fetch removed
+    GitFetchFinished(Result<Vec<String>, String>),
+    WorkItemJson {
+        work_item_id: u32,
+        result: Result<String, String>,
+    },
+    StateTransitions {
+        work_item_id: u32,
+        result: Result<Vec<StateTransition>, String>,
+    },
+    StateChanged {
+        work_item_id: u32,
+        result: Result<WorkItem, String>,
+    },
+    PullRequestCompleted {
+        work_item_id: u32,
+        pull_request_id: u32,
+        result: Result<PullRequest, String>,
+    },
+    Activity(Result<Vec<ActivityEntry>, String>),
+    /// Active pull requests of the repository, for the pull request tab
+    PullRequestList(Result<Vec<PullRequest>, String>),
+    /// Work items of the WIQL query, for the work item list
+    WorkItemList(Result<Vec<WorkItem>, String>),
+    /// Loaded work items fetched again; those that failed are left out
+    ChangesChecked(Vec<WorkItem>),
 }
 
 pub(super) fn process_fetch_results(
     rx: &mut mpsc::UnboundedReceiver<FetchResult>,
     app: &mut App,
-    pending_fetches: &mut HashSet<u32>,
+    fetches: &mut Fetches,
 ) {
     while let Ok(result) = rx.try_recv() {
-        match result {
-            FetchResult::Success { id, work_item } => {
-                app.set_work_item_loaded(id, work_item);
-                pending_fetches.remove(&id);
+        apply_fetch_result(app, result, fetches);
+    }
+}
+
+pub(super) fn apply_fetch_result(app: &mut App, result: FetchResult, fetches: &mut Fetches) {
+    match result {
+        FetchResult::Success { id, work_item } => {
+            app.set_work_item_loaded(id, work_item);
+            fetches.work_items.finish(&id);
+        }
+        FetchResult::Error { id, error } => {
+            tracing::warn!(id, error = %error, "work item fetch failed");
+            app.set_work_item_error(id, error);
+            fetches.work_items.finish(&id);
+        }
+        FetchResult::RevalidationError { id, error } => {
+            tracing::warn!(id, error = %error, "cached work item revalidation failed");
+            app.set_work_item_revalidation_failed(id);
+            fetches.work_items.finish(&id);
+        }
+        FetchResult::WorkItemBatchSuccess { results } => {
+            for (id, result) in results {
+                // Refreshed meanwhile and fetched on its own
+                let Some(kind) = fetches.batch.remove(&id) else {
+                    continue;
+                };
+                match (result, kind) {
+                    (Ok(work_item), _) => app.set_work_item_loaded(id, work_item),
+                    (Err(error), FetchKind::Load) => {
+                        tracing::warn!(id, error = %error, "work item fetch failed");
+                        app.set_work_item_error(id, error);
+                    }
+                    (Err(error), FetchKind::Revalidate) => {
+                        tracing::warn!(id, error = %error, "cached work item revalidation failed");
+                        app.set_work_item_revalidation_failed(id);
+                    }
+                }
+            }
+        }
+        FetchResult::WorkItemBatchError { error } => {
+            // Fetching one at a time as branches are selected still works
+            tracing::warn!(error = %error, "work item batch fetch failed");
+            for (id, kind) in fetches.batch.drain() {
+                if kind == FetchKind::Load {
+                    app.reset_work_item(id);
+                }
+            }
+        }
+        FetchResult::PullRequests {
+            work_item_id,
+            result,
+        } => {
+            if let Err(error) = &result {
+                tracing::warn!(work_item_id, error = %error, "pull request fetch failed");
+            }
+            app.set_pull_requests(work_item_id, result);
+            fetches.pull_requests.finish(&work_item_id);
+        }
+        FetchResult::Comments {
+            work_item_id,
+            result,
+        } => {
+            if let Err(error) = &result {
+                tracing::warn!(work_item_id, error = %error, "comment fetch failed");
+            }
+            app.set_comments(work_item_id, result);
+            fetches.comments.finish(&work_item_id);
+        }
+        FetchResult::Children {
+            work_item_id,
+            result,
+        } => {
+            if let Err(error) = &result {
+                tracing::warn!(work_item_id, error = %error, "child work item fetch failed");
+            }
+            app.set_children(work_item_id, result);
+            fetches.children.finish(&work_item_id);
+        }
+        FetchResult::BranchStatus { key, result } => match result {
+            Ok(status) => app.set_branch_status(key, status),
+            // The selected branch's error is reported when it is loaded
+            // again on demand; keep a status that loaded meanwhile.
+            Err(error) if app.get_branch_status(&key).is_none() => {
+                app.set_branch_status_error(key, error)
+            }
+            Err(_) => {}
+        },
+        FetchResult::SelectedBranchStatus {
+            key,
+            display_name,
+            result,
+        } => {
+            fetches.branch_statuses.remove(&key);
+            apply_branch_status_result(app, &key, &display_name, result);
+        }
+        FetchResult::RemoteFreshnessSuccess { live_branches } => {
+            app.set_remote_freshness(live_branches);
+        }
+        FetchResult::RemoteFreshnessError { error } => {
+            tracing::warn!(error = %error, "origin branch check failed");
+            app.set_remote_freshness_error(error);
+            app.update(Msg::SetBackgroundError(
+                t(Text::OriginCheckFailed).to_string(),
+            ));
+        }
+        FetchResult::WorkItemJson {
+            work_item_id,
+            result,
+        } => app.set_inspector_json(work_item_id, result),
+        FetchResult::StateTransitions {
+            work_item_id,
+            result,
+        } => app.set_state_transitions(work_item_id, result),
+        FetchResult::ChangesChecked(work_items) => app.update(Msg::ChangesChecked(work_items)),
+        FetchResult::Activity(result) => {
+            if let Err(error) = &result {
+                tracing::warn!(error = %error, "activity poll failed");
+            }
+            app.set_activity(result);
+        }
+        FetchResult::PullRequestList(result) => {
+            if let Err(error) = &result {
+                tracing::warn!(error = %error, "pull request listing failed");
+            }
+            app.set_pull_request_list(result);
+        }
+        FetchResult::WorkItemList(result) => {
+            if let Err(error) = &result {
+                tracing::warn!(error = %error, "work item query failed");
+            }
+            app.set_work_item_list(result);
+        }
+        FetchResult::StateChanged {
+            work_item_id,
+            result,
+        } => match result {
+            Ok(work_item) => {
+                let state = work_item.state.display_name().to_string();
+                app.update(Msg::RecordStateChange {
+                    work_item_id,
+                    to: state.clone(),
+                });
+                app.set_work_item_loaded(work_item_id, work_item);
+                app.set_status_message(
+                    tf(
+                        Text::StateChanged,
+                        &[("id", &work_item_id.to_string()), ("state", &state)],
+                    ),
+                    false,
+                    timing::STATUS_DURATION_SECS,
+                );
             }
-            FetchResult::Error { id, error } => {
-                app.set_work_item_error(id, error);
-                pending_fetches.remove(&id);
+            Err(error) => {
+                tracing::warn!(work_item_id, error = %error, "state change failed");
+                app.update(Msg::ShowErrorPopup(tf(
+                    Text::StateChangeFailed,
+                    &[("id", &work_item_id.to_string()), ("error", &error)],
+                )));
             }
-            FetchResult::RemoteFreshnessSuccess { live_branches } => {
-                app.set_remote_freshness(live_branches);
+        },
+        FetchResult::PullRequestCompleted {
+            work_item_id,
+            pull_request_id,
+            result,
+        } => match result {
+            Ok(_) => {
+                // Completing can move the work item too, so both load again
+                app.reset_pull_requests(work_item_id);
+                app.reset_work_item(work_item_id);
+                app.set_status_message(
+                    tf(
+                        Text::CompletedPullRequest,
+                        &[("id", &pull_request_id.to_string())],
+                    ),
+                    false,
+                    timing::STATUS_DURATION_SECS,
+                );
             }
-            FetchResult::RemoteFreshnessError { error } => {
-                app.set_remote_freshness_error(error);
-                app.update(Msg::SetBackgroundError(
-                    "Could not verify origin branches".to_string(),
-                ));
+            Err(error) => {
+                tracing::warn!(pull_request_id, error = %error, "pull request completion failed");
+                app.update(Msg::ShowErrorPopup(tf(
+                    Text::PullRequestCompletionFailed,
+                    &[("id", &pull_request_id.to_string()), ("error", &error)],
+                )));
+            }
+        },
+        FetchResult::GitFetchProgress(progress) => app.set_git_fetch_progress(progress),
+        FetchResult::GitFetchFinished(result) => {
+            app.finish_git_fetch();
+            match result {
+                Ok(pruned) if pruned.is_empty() => app.set_status_message(
+                    t(Text::Fetched).to_string(),
+                    false,
+                    timing::STATUS_DURATION_SECS,
+                ),
+                Ok(pruned) => {
+                    let count = pruned.len();
+                    for key in pruned {
+                        app.update(Msg::BranchPruned { key });
+                    }
+                    app.set_status_message(
+                        tf(Text::FetchedPruned, &[("count", &count.to_string())]),
+                        false,
+                        timing::STATUS_DURATION_SECS,
+                    );
+                }
+                Err(error) => {
+                    tracing::warn!(error = %error, "git fetch failed");
+                    app.update(Msg::SetBackgroundError(error));
+                }
             }
         }
     }
 }
 
+/// Fetch origin without blocking the TUI, showing git's progress in the
+/// footer. Once the fetch is done, every branch's ahead/behind status is
+/// loaded again, so local branches whose upstream was pruned show as gone.
+/// A pruning fetch also drops the remote branches it removed; branches new
+/// on origin are not added.
+pub(super) fn start_git_fetch(
+    app: &mut App,
+    git_repo: &GitRepo,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+    prune: bool,
+) {
+    let repo_dir = match git_repo.repo_dir() {
+        Ok(repo_dir) => repo_dir,
+        Err(error) => {
+            app.update(Msg::SetBackgroundError(tf(
+                Text::FetchFailed,
+                &[("error", &error.to_string())],
+            )));
+            return;
+        }
+    };
+    if !app.start_git_fetch() {
+        return;
+    }
+
+    let branches = app.branches().to_vec();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let progress_tx = tx.clone();
+        let result = fetch_origin_in_dir(&repo_dir, prune, |progress| {
+            let _ = progress_tx.send(FetchResult::GitFetchProgress(progress));
+        })
+        .await;
+        let result = match result {
+            Ok(()) => {
+                let tx = tx.clone();
+                let reloaded =
+                    tokio::task::spawn_blocking(move || match GitRepo::open_dir(&repo_dir) {
+                        Ok(git_repo) => reload_branch_statuses(&git_repo, branches, &tx),
+                        Err(error) => {
+                            tracing::warn!(error = %error, "branch status reload skipped");
+                            Vec::new()
+                        }
+                    });
+                Ok(reloaded.await.unwrap_or_default())
+            }
+            Err(error) => Err(error.to_string()),
+        };
+        let _ = tx.send(FetchResult::GitFetchFinished(result));
+    });
+}
+
 pub(super) fn trigger_remote_freshness_check(
     app: &mut App,
     git_repo: &GitRepo,
@@ -72,54 +399,705 @@ pub(super) fn trigger_remote_freshness_check(
     });
 }
 
+/// Holds work item fetches back while the selection is moving, so scrolling
+/// through many branches only fetches for the one it stops on.
+pub(super) struct SelectionDebounce {
+    selected_key: Option<String>,
+    changed_at: Instant,
+}
+
+impl SelectionDebounce {
+    /// A debounce that lets the initial selection fetch right away.
+    pub(super) fn new(app: &App) -> Self {
+        let now = Instant::now();
+        Self {
+            selected_key: app.selected_branch().map(|branch| branch.key.clone()),
+            changed_at: now.checked_sub(timing::FETCH_DEBOUNCE).unwrap_or(now),
+        }
+    }
+
+    /// Whether the selection has rested for [`timing::FETCH_DEBOUNCE`] as of
+    /// `now`; a different selection than last time restarts the wait.
+    pub(super) fn is_settled(&mut self, app: &App, now: Instant) -> bool {
+        let selected_key = app.selected_branch().map(|branch| &branch.key);
+        if selected_key != self.selected_key.as_ref() {
+            self.selected_key = selected_key.cloned();
+            self.changed_at = now;
+        }
+        now.duration_since(self.changed_at) >= timing::FETCH_DEBOUNCE
+    }
+
+    /// When the current selection will have rested long enough.
+    pub(super) fn settles_at(&self) -> Instant {
+        self.changed_at + timing::FETCH_DEBOUNCE
+    }
+}
+
+/// Requests in flight, by what they fetch.
+pub(super) struct Fetches {
+    /// By work item ID, prefetching up to the limit at a time. The selected
+    /// branch's work item is fetched right away even when this many are
+    /// pending.
+    pub(super) work_items: FetchManager<u32>,
+    /// By work item ID; only the selected work item's are fetched.
+    pub(super) pull_requests: FetchManager<u32>,
+    /// By work item ID; only the selected work item's are fetched, while
+    /// the comments section is shown.
+    pub(super) comments: FetchManager<u32>,
+    /// By work item ID; only the selected work item's are fetched.
+    pub(super) children: FetchManager<u32>,
+    /// Work items of the request prefetching the local branches' until each
+    /// arrives, by ID
+    pub(super) batch: HashMap<u32, FetchKind>,
+    batch_handle: Option<AbortHandle>,
+    /// Keys of the branches whose status is being loaded on demand
+    pub(super) branch_statuses: HashSet<String>,
+}
+
+impl Fetches {
+    /// Prefetch up to `limit` work items at a time.
+    pub(super) fn new(limit: usize) -> Self {
+        Self {
+            work_items: FetchManager::new(limit, RetryPolicy::NETWORK),
+            pull_requests: FetchManager::new(1, RetryPolicy::NETWORK),
+            comments: FetchManager::new(1, RetryPolicy::NETWORK),
+            children: FetchManager::new(1, RetryPolicy::NETWORK),
+            batch: HashMap::new(),
+            batch_handle: None,
+            branch_statuses: HashSet::new(),
+        }
+    }
+
+    /// Abort every request, e.g. on quit.
+    pub(super) fn cancel_all(&mut self) {
+        self.work_items.cancel_all();
+        self.pull_requests.cancel_all();
+        self.comments.cancel_all();
+        self.children.cancel_all();
+        if let Some(handle) = self.batch_handle.take() {
+            handle.abort();
+        }
+        self.batch.clear();
+    }
+}
+
+/// Abort the fetches of `id` and its pull requests, comments, and children,
+/// if any. A work item that was loading goes back to not fetched; a cached
+/// one keeps showing the cached copy.
+pub(super) fn cancel_work_item_fetch(app: &mut App, fetches: &mut Fetches, id: u32) {
+    if fetches.work_items.cancel(&id) == Some(FetchKind::Load)
+        || fetches.batch.remove(&id) == Some(FetchKind::Load)
+    {
+        app.reset_work_item(id);
+    }
+    if fetches.pull_requests.cancel(&id).is_some() {
+        app.reset_pull_requests(id);
+    }
+    if fetches.comments.cancel(&id).is_some() {
+        app.reset_comments(id);
+    }
+    if fetches.children.cancel(&id).is_some() {
+        app.reset_children(id);
+    }
+}
+
+/// Abort fetches the selection has moved away from: work items that would
+/// no longer be started, per [`wanted_work_items`], and pull requests,
+/// comments, and children of any work item but the selected one.
+pub(super) fn cancel_superseded_fetches(app: &mut App, fetches: &mut Fetches) {
+    let wanted = wanted_work_items(app, fetches.work_items.limit());
+    for (id, kind) in fetches.work_items.cancel_unless(|id| wanted.contains(id)) {
+        tracing::debug!(id, "work item fetch cancelled");
+        if kind == FetchKind::Load {
+            app.reset_work_item(id);
+        }
+    }
+
+    let selected = app.selected_work_item_id();
+    for (id, _) in fetches
+        .pull_requests
+        .cancel_unless(|id| Some(*id) == selected)
+    {
+        tracing::debug!(id, "pull request fetch cancelled");
+        app.reset_pull_requests(id);
+    }
+    for (id, _) in fetches.comments.cancel_unless(|id| Some(*id) == selected) {
+        tracing::debug!(id, "comment fetch cancelled");
+        app.reset_comments(id);
+    }
+    for (id, _) in fetches.children.cancel_unless(|id| Some(*id) == selected) {
+        tracing::debug!(id, "child work item fetch cancelled");
+        app.reset_children(id);
+    }
+}
+
+/// Fetch the pull requests linked to the selected work item once it has
+/// loaded. They are not prefetched for other branches: each work item's
+/// costs a request per pull request.
+pub(super) fn trigger_pull_request_fetch(
+    app: &mut App,
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+    fetches: &mut Fetches,
+) {
+    let Some(wi_id) = app.work_item_needing_pull_requests() else {
+        return;
+    };
+    app.set_pull_requests_loading(wi_id);
+
+    let client = client.clone();
+    let tx = tx.clone();
+    let retry = fetches.pull_requests.retry_policy();
+    fetches
+        .pull_requests
+        .spawn(wi_id, FetchKind::Load, async move {
+            let result = retry
+                .run(|| client.get_linked_pull_requests(wi_id))
+                .await
+                .map_err(|error| error.to_string());
+            let _ = tx.send(FetchResult::PullRequests {
+                work_item_id: wi_id,
+                result,
+            });
+        });
+}
+
+/// Fetch the comments on the selected work item once it has loaded, only
+/// while the comments section is shown.
+pub(super) fn trigger_comment_fetch(
+    app: &mut App,
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+    fetches: &mut Fetches,
+) {
+    let Some(wi_id) = app.work_item_needing_comments() else {
+        return;
+    };
+    app.set_comments_loading(wi_id);
+
+    let client = client.clone();
+    let tx = tx.clone();
+    let retry = fetches.comments.retry_policy();
+    fetches.comments.spawn(wi_id, FetchKind::Load, async move {
+        let result = retry
+            .run(|| client.get_work_item_comments(wi_id))
+            .await
+            .map_err(|error| error.to_string());
+        let _ = tx.send(FetchResult::Comments {
+            work_item_id: wi_id,
+            result,
+        });
+    });
+}
+
+/// Fetch the children of the selected work item once it has loaded, for the
+/// hierarchy section of the details panel.
+pub(super) fn trigger_children_fetch(
+    app: &mut App,
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+    fetches: &mut Fetches,
+) {
+    let Some(wi_id) = app.work_item_needing_children() else {
+        return;
+    };
+    app.set_children_loading(wi_id);
+
+    let client = client.clone();
+    let tx = tx.clone();
+    let retry = fetches.children.retry_policy();
+    fetches.children.spawn(wi_id, FetchKind::Load, async move {
+        let result = retry
+            .run(|| client.get_child_work_items(wi_id))
+            .await
+            .map_err(|error| format!("{error:#}"));
+        let _ = tx.send(FetchResult::Children {
+            work_item_id: wi_id,
+            result,
+        });
+    });
+}
+
+/// Fetch the raw JSON of work item `wi_id` for the inspector, pretty-printed.
+/// It is not cancelled when the inspector closes; the result is dropped.
+pub(super) fn fetch_work_item_json(
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+    wi_id: u32,
+) {
+    let client = client.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = match client.get_work_item_json(wi_id).await {
+            Ok(json) => serde_json::to_string_pretty(&json).map_err(|error| error.to_string()),
+            Err(error) => Err(format!("{error:#}")),
+        };
+        let _ = tx.send(FetchResult::WorkItemJson {
+            work_item_id: wi_id,
+            result,
+        });
+    });
+}
+
+/// Poll the recently changed work items while the activity tab is open and
+/// the last poll is [`timing::ACTIVITY_POLL_INTERVAL`] old.
+pub(super) fn trigger_activity_poll(
+    app: &mut App,
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+) {
+    let now = Instant::now();
+    if !app.activity_poll_due(now) {
+        return;
+    }
+    app.update(Msg::SetActivityLoading(now));
+
+    let linked_ids = app.activity_linked_ids();
+    let client = client.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = client
+            .get_recent_activity(&linked_ids, timing::ACTIVITY_DAYS)
+            .await
+            .map_err(|error| format!("{error:#}"));
+        let _ = tx.send(FetchResult::Activity(result));
+    });
+}
+
+/// List the active pull requests of the repository when the pull request tab
+/// opens or `r` asks for it.
+pub(super) fn trigger_pull_request_list_fetch(
+    app: &mut App,
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+) {
+    if !app.pull_request_list_due() {
+        return;
+    }
+    let repository = match app.repository() {
+        Ok(repository) => repository.clone(),
+        Err(error) => {
+            let error = error.to_string();
+            app.set_pull_request_list(Err(error));
+            return;
+        }
+    };
+    app.update(Msg::SetPullRequestListLoading);
+
+    let client = client.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = client
+            .list_active_pull_requests(&repository)
+            .await
+            .map_err(|error| format!("{error:#}"));
+        let _ = tx.send(FetchResult::PullRequestList(result));
+    });
+}
+
+/// Run the work item query once the list opens, then fetch the work items
+/// it returned in one batch. Those that fail to load are left out.
+pub(super) fn trigger_work_item_list_fetch(
+    app: &mut App,
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+) {
+    if !app.work_item_list_due() {
+        return;
+    }
+    app.update(Msg::SetWorkItemListLoading);
+
+    let query = app.work_item_query().to_string();
+    let client = client.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = async {
+            let ids = client.query_wiql(&query).await?;
+            let work_items = client.get_work_items_batch(&ids).await?;
+            Ok::<_, anyhow::Error>(
+                work_items
+                    .into_iter()
+                    .filter_map(|result| {
+                        result
+                            .inspect_err(
+                                |error| tracing::debug!(error = %error, "listed work item failed"),
+                            )
+                            .ok()
+                    })
+                    .collect(),
+            )
+        }
+        .await
+        .map_err(|error| format!("{error:#}"));
+        let _ = tx.send(FetchResult::WorkItemList(result));
+    });
+}
+
+/// Fetch the loaded work items again every [`timing::CHANGE_CHECK_INTERVAL`]
+/// so changes made by others raise toasts.
+pub(super) fn trigger_change_check(
+    app: &mut App,
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+) {
+    let now = Instant::now();
+    if !app.change_check_due(now) {
+        return;
+    }
+    app.update(Msg::StartChangeCheck(now));
+
+    let ids = app.work_items_to_check();
+    let client = client.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let work_items = client
+            .get_work_items(&ids)
+            .await
+            .into_iter()
+            .filter_map(|result| {
+                result
+                    .inspect_err(|error| tracing::debug!(error = %error, "change check failed"))
+                    .ok()
+            })
+            .collect();
+        let _ = tx.send(FetchResult::ChangesChecked(work_items));
+    });
+}
+
+/// Fetch the states work item `wi_id` can move to, for the state menu. Like
+/// the inspector's JSON, the result is dropped if the menu has closed.
+pub(super) fn fetch_state_transitions(
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+    wi_id: u32,
+) {
+    let client = client.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = client
+            .get_state_transitions(wi_id)
+            .await
+            .map_err(|error| format!("{error:#}"));
+        let _ = tx.send(FetchResult::StateTransitions {
+            work_item_id: wi_id,
+            result,
+        });
+    });
+}
+
+/// Move work item `wi_id` to `state`. The updated work item replaces the one
+/// shown; a failure is shown in a popup.
+pub(super) fn set_work_item_state(
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+    wi_id: u32,
+    state: String,
+) {
+    let client = client.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = client
+            .set_work_item_state(wi_id, &state)
+            .await
+            .map_err(|error| format!("{error:#}"));
+        let _ = tx.send(FetchResult::StateChanged {
+            work_item_id: wi_id,
+            result,
+        });
+    });
+}
+
+/// Complete `pull_request` of work item `wi_id` as it was shown.
+pub(super) fn complete_pull_request(
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+    wi_id: u32,
+    pull_request: PullRequest,
+    options: CompletionOptions,
+) {
+    let client = client.clone();
+    let tx = tx.clone();
+    let pull_request_id = pull_request.id;
+    tokio::spawn(async move {
+        let result = client
+            .complete_pull_request(&pull_request, &options)
+            .await
+            .map_err(|error| format!("{error:#}"));
+        let _ = tx.send(FetchResult::PullRequestCompleted {
+            work_item_id: wi_id,
+            pull_request_id,
+            result,
+        });
+    });
+}
+
+/// Fetch the work items of all local branches in one batch request once the
+/// client is ready, instead of one request per branch as they are selected.
+/// Cached copies are fetched again with the rest.
+pub(super) fn trigger_work_item_prefetch(
+    app: &mut App,
+    client: &impl WorkItemProvider,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+    fetches: &mut Fetches,
+) {
+    let ids = app.local_work_item_ids_to_prefetch();
+    if ids.is_empty() {
+        return;
+    }
+    for &id in &ids {
+        let kind = if app.is_work_item_stale(id) {
+            FetchKind::Revalidate
+        } else {
+            app.set_work_item_loading(id);
+            FetchKind::Load
+        };
+        fetches.batch.insert(id, kind);
+    }
+
+    let client = client.clone();
+    let tx = tx.clone();
+    let retry = fetches.work_items.retry_policy();
+    let handle = tokio::spawn(async move {
+        let result = match retry.run(|| client.get_work_items_batch(&ids)).await {
+            Ok(results) => FetchResult::WorkItemBatchSuccess {
+                results: ids
+                    .iter()
+                    .copied()
+                    .zip(
+                        results
+                            .into_iter()
+                            .map(|result| result.map_err(|error| error.to_string())),
+                    )
+                    .collect(),
+            },
+            Err(error) => FetchResult::WorkItemBatchError {
+                error: format!("{error:#}"),
+            },
+        };
+        let _ = tx.send(result);
+    });
+    fetches.batch_handle = Some(handle.abort_handle());
+}
+
+/// Fetch the selected branch's work item right away, and prefetch those of
+/// the other visible branches a few at a time so scrolling finds them loaded.
+/// Cached work items are fetched again with whatever capacity is left; they
+/// stay on screen until the fresh copy arrives.
 pub(super) fn trigger_work_item_fetch(
     app: &mut App,
-    client: &AzureDevOpsClient,
+    client: &impl WorkItemProvider,
     tx: &mpsc::UnboundedSender<FetchResult>,
-    pending_fetches: &mut HashSet<u32>,
+    fetches: &mut Fetches,
 ) {
-    if let Some(wi_id) = app.selected_work_item_id() {
-        let status = app.get_work_item_status(wi_id);
-        if matches!(status, WorkItemStatus::NotFetched) && !pending_fetches.contains(&wi_id) {
+    let batched = fetches.batch.keys().copied();
+    let fetches = &mut fetches.work_items;
+    let pending = fetches.keys().chain(batched).collect();
+    for (wi_id, kind) in work_items_to_fetch(app, &pending, fetches.limit()) {
+        if kind == FetchKind::Load {
             app.set_work_item_loading(wi_id);
-            pending_fetches.insert(wi_id);
+        }
 
-            let client = client.clone();
-            let tx = tx.clone();
+        let client = client.clone();
+        let tx = tx.clone();
+        let retry = fetches.retry_policy();
 
-            tokio::spawn(async move {
-                let result = match client.get_work_item(wi_id).await {
-                    Ok(work_item) => FetchResult::Success {
+        fetches.spawn(wi_id, kind, async move {
+            let result = match retry.run(|| client.get_work_item(wi_id)).await {
+                Ok(work_item) => FetchResult::Success {
+                    id: wi_id,
+                    work_item,
+                },
+                Err(error) => match kind {
+                    FetchKind::Load => FetchResult::Error {
                         id: wi_id,
-                        work_item,
+                        error: error.to_string(),
                     },
-                    Err(error) => FetchResult::Error {
+                    FetchKind::Revalidate => FetchResult::RevalidationError {
                         id: wi_id,
                         error: error.to_string(),
                     },
-                };
-                let _ = tx.send(result);
-            });
+                },
+            };
+            let _ = tx.send(result);
+        });
+    }
+}
+
+/// Visible work items to load, then cached ones to revalidate, nearest the
+/// selection first within each group. `loads` are the IDs to load.
+fn fetch_candidates(app: &App, loads: Vec<u32>) -> impl Iterator<Item = (u32, FetchKind)> {
+    loads
+        .into_iter()
+        .map(|wi_id| (wi_id, FetchKind::Load))
+        .chain(
+            app.stale_work_item_ids()
+                .into_iter()
+                .map(|wi_id| (wi_id, FetchKind::Revalidate)),
+        )
+}
+
+/// The selected work item if it still needs fetching, then unfetched visible
+/// ones nearest the selection while fewer than `limit` fetches are pending,
+/// then cached ones to revalidate.
+fn work_items_to_fetch(
+    app: &App,
+    pending_fetches: &HashSet<u32>,
+    limit: usize,
+) -> Vec<(u32, FetchKind)> {
+    let selected = app.selected_work_item_id();
+    let mut in_flight = pending_fetches.len();
+    let mut ids = Vec::new();
+    for (wi_id, kind) in fetch_candidates(app, app.unfetched_work_item_ids()) {
+        if pending_fetches.contains(&wi_id) {
+            continue;
+        }
+        let urgent = kind == FetchKind::Load && Some(wi_id) == selected;
+        if !urgent && in_flight >= limit {
+            break;
+        }
+        ids.push((wi_id, kind));
+        in_flight += 1;
+    }
+    ids
+}
+
+/// The work items [`work_items_to_fetch`] would keep in flight for the
+/// current selection: the selected one and the first `limit` candidates,
+/// counting those already loading.
+fn wanted_work_items(app: &App, limit: usize) -> HashSet<u32> {
+    let mut wanted: HashSet<u32> = fetch_candidates(app, app.unloaded_work_item_ids())
+        .take(limit)
+        .map(|(wi_id, _)| wi_id)
+        .collect();
+    wanted.extend(app.selected_work_item_id());
+    wanted
+}
+
+/// Load the status of every branch on [`BRANCH_STATUS_WORKERS`] blocking
+/// threads, each with its own handle to the repository, so the Branch Info
+/// panel does not wait for git when the selection moves.
+pub(super) fn trigger_branch_status_prefetch(
+    app: &App,
+    git_repo: &GitRepo,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+) {
+    let branches = app.branches_needing_status();
+    if branches.is_empty() {
+        return;
+    }
+    let repo_dir = match git_repo.repo_dir() {
+        Ok(repo_dir) => repo_dir,
+        Err(error) => {
+            tracing::warn!(error = %error, "branch status prefetch skipped");
+            return;
         }
+    };
+
+    let workers = BRANCH_STATUS_WORKERS.min(branches.len());
+    let mut batches = vec![Vec::new(); workers];
+    for (index, branch) in branches.into_iter().enumerate() {
+        batches[index % workers].push(branch);
+    }
+    for batch in batches {
+        let repo_dir = repo_dir.clone();
+        let tx = tx.clone();
+        tokio::task::spawn_blocking(move || match GitRepo::open_dir(&repo_dir) {
+            Ok(git_repo) => load_branch_statuses(&git_repo, &batch, &tx),
+            Err(error) => {
+                tracing::warn!(error = %error, "branch status worker failed to open repo")
+            }
+        });
     }
 }
 
-pub(super) fn fetch_branch_status_if_needed(app: &mut App, git_repo: &GitRepo) {
-    if let Some(branch) = app.selected_branch() {
-        let branch_key = branch.key.clone();
-        let branch_display_name = branch.display_name.clone();
+/// Load the status of `branches` again after a fetch, leaving out the
+/// remote branches whose remote-tracking ref is gone, which are returned.
+fn reload_branch_statuses(
+    git_repo: &GitRepo,
+    branches: Vec<BranchInfo>,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+) -> Vec<String> {
+    let listed: HashSet<String> = match git_repo.list_branches() {
+        Ok(listed) => listed.into_iter().map(|branch| branch.key).collect(),
+        Err(error) => {
+            tracing::warn!(error = %error, "branch list reload failed");
+            branches.iter().map(|branch| branch.key.clone()).collect()
+        }
+    };
+    let (kept, pruned): (Vec<_>, Vec<_>) = branches
+        .into_iter()
+        .partition(|branch| !branch.scope.is_remote() || listed.contains(&branch.key));
+    load_branch_statuses(git_repo, &kept, tx);
+    pruned.into_iter().map(|branch| branch.key).collect()
+}
 
-        if app.needs_branch_status(&branch_key) {
-            let result = git_repo.get_branch_status(
+fn load_branch_statuses(
+    git_repo: &GitRepo,
+    branches: &[BranchInfo],
+    tx: &mpsc::UnboundedSender<FetchResult>,
+) {
+    for branch in branches {
+        let result = git_repo
+            .get_branch_status(
                 branch.scope,
                 &branch.branch_name,
                 branch.remote_name.as_deref(),
-            );
-            apply_branch_status_result(app, &branch_key, &branch_display_name, result);
+            )
+            .map_err(|error| error.to_string());
+        let sent = tx.send(FetchResult::BranchStatus {
+            key: branch.key.clone(),
+            result,
+        });
+        if sent.is_err() {
+            return;
         }
     }
 }
 
+/// Load the selected branch's status on a blocking thread unless it is
+/// loaded or already loading, so walking a large repository's history does
+/// not hold up drawing.
+pub(super) fn fetch_branch_status_if_needed(
+    app: &mut App,
+    git_repo: &GitRepo,
+    tx: &mpsc::UnboundedSender<FetchResult>,
+    fetches: &mut Fetches,
+) {
+    let Some(branch) = app.selected_branch().cloned() else {
+        return;
+    };
+    if !app.needs_branch_status(&branch.key) || fetches.branch_statuses.contains(&branch.key) {
+        return;
+    }
+    let repo_dir = match git_repo.repo_dir() {
+        Ok(repo_dir) => repo_dir,
+        Err(error) => {
+            let error = error.to_string();
+            apply_branch_status_result(app, &branch.key, &branch.display_name, Err(error));
+            return;
+        }
+    };
+
+    fetches.branch_statuses.insert(branch.key.clone());
+    let tx = tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let result = GitRepo::open_dir(&repo_dir)
+            .and_then(|git_repo| {
+                git_repo.get_branch_status(
+                    branch.scope,
+                    &branch.branch_name,
+                    branch.remote_name.as_deref(),
+                )
+            })
+            .map_err(|error| error.to_string());
+        let _ = tx.send(FetchResult::SelectedBranchStatus {
+            key: branch.key,
+            display_name: branch.display_name,
+            result,
+        });
+    });
+}
+
 async fn fetch_remote_freshness(repo_dir: PathBuf) -> FetchResult {
     let task = tokio::task::spawn_blocking(move || list_origin_remote_heads_in_dir(&repo_dir));
 
@@ -153,21 +1131,20 @@ fn apply_branch_status_result(
     app: &mut App,
     branch_key: &str,
     branch_display_name: &str,
-    result: Result<crate::git::BranchStatus>,
+    result: Result<BranchStatus, String>,
 ) {
     match result {
         Ok(status) => app.set_branch_status(branch_key.to_string(), status),
-        Err(error) => {
-            let error_text = error.to_string();
+        Err(error_text) => {
             let should_show_status = app.get_branch_status_error(branch_key) != Some(&error_text);
 
             app.set_branch_status_error(branch_key.to_string(), error_text.clone());
 
             if should_show_status {
                 app.set_status_message(
-                    format!(
-                        "Could not load branch info for '{}': {}",
-                        branch_display_name, error_text
+                    tf(
+                        Text::BranchInfoFailed,
+                        &[("branch", branch_display_name), ("error", &error_text)],
                     ),
                     true,
                     timing::STATUS_DURATION_SECS,
@@ -180,9 +1157,17 @@ fn apply_branch_status_result(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::azure_devops::{WorkItem, WorkItemState, WorkItemType};
+    use crate::azure_devops::{
+        Comment, FieldFormat, MergeStrategy, MockWorkItemProvider, RepositoryRef, WorkItem,
+        WorkItemState, WorkItemType,
+    };
+    use crate::config::DEFAULT_MAX_CONCURRENT_REQUESTS;
     use crate::git::BranchScope;
-    use crate::tui::app::BranchInfo;
+    use crate::git::{FixtureGitRepo, RemoteStatus};
+    use crate::tui::app::{
+        AppMode, ChildrenStatus, CommentsStatus, PullRequestsStatus, WorkItemStatus,
+    };
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_apply_branch_status_result_caches_error_and_sets_status_message() {
@@ -192,7 +1177,7 @@ mod tests {
             &mut app,
             "refs/remotes/origin/feature/1",
             "origin/feature/1",
-            Err(anyhow::anyhow!("git lookup failed")),
+            Err("git lookup failed".to_string()),
         );
 
         assert_eq!(
@@ -215,7 +1200,7 @@ mod tests {
             &mut app,
             "refs/remotes/origin/feature/1",
             "origin/feature/1",
-            Err(anyhow::anyhow!("git lookup failed")),
+            Err("git lookup failed".to_string()),
         );
 
         app.set_status_message(
@@ -228,7 +1213,7 @@ mod tests {
             &mut app,
             "refs/remotes/origin/feature/1",
             "origin/feature/1",
-            Err(anyhow::anyhow!("git lookup failed")),
+            Err("git lookup failed".to_string()),
         );
 
         let status = app
@@ -246,7 +1231,7 @@ mod tests {
             &mut app,
             "refs/remotes/origin/feature/1",
             "origin/feature/1",
-            Err(anyhow::anyhow!("git lookup failed")),
+            Err("git lookup failed".to_string()),
         );
 
         app.set_status_message(
@@ -259,7 +1244,7 @@ mod tests {
             &mut app,
             "refs/remotes/origin/feature/1",
             "origin/feature/1",
-            Err(anyhow::anyhow!("repo locked")),
+            Err("repo locked".to_string()),
         );
 
         let status = app
@@ -270,10 +1255,55 @@ mod tests {
     }
 
     #[test]
-    fn test_process_fetch_results_loads_work_item_and_clears_pending_fetch() {
+    fn test_pruning_fetch_drops_remote_branches_gone_from_the_repo() {
+        let git_repo = GitRepo::fixture(FixtureGitRepo::new().with_local_branch("feature/2", true));
+        let branches = vec![local_branch("feature/2", Some(2)), remote_branch(false)];
+        let mut app = App::new(branches.clone(), vec![]);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        let pruned = reload_branch_statuses(&git_repo, branches, &tx);
+        assert_eq!(pruned, vec!["refs/remotes/origin/feature/1".to_string()]);
+        tx.send(FetchResult::GitFetchFinished(Ok(pruned)))
+            .expect("send should succeed");
+        process_fetch_results(&mut rx, &mut app, &mut fetches);
+
+        assert!(app.get_branch_status("refs/heads/feature/2").is_some());
+        assert!(app.branch_by_key("refs/remotes/origin/feature/1").is_none());
+        let status = app.get_status_message().expect("status message");
+        assert!(status.text.contains("1 branches gone"));
+    }
+
+    #[test]
+    fn test_selected_branch_status_result_finishes_the_load_and_reports_errors() {
+        let mut app = App::new(vec![remote_branch(false)], vec![]);
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+        let key = "refs/remotes/origin/feature/1".to_string();
+        fetches.branch_statuses.insert(key.clone());
+
+        apply_fetch_result(
+            &mut app,
+            FetchResult::SelectedBranchStatus {
+                key: key.clone(),
+                display_name: "origin/feature/1".to_string(),
+                result: Err("git lookup failed".to_string()),
+            },
+            &mut fetches,
+        );
+
+        assert!(fetches.branch_statuses.is_empty());
+        assert_eq!(app.get_branch_status_error(&key), Some("git lookup failed"));
+        let status = app
+            .get_status_message()
+            .expect("status message should be set");
+        assert!(status.is_error);
+    }
+
+    #[tokio::test]
+    async fn test_process_fetch_results_loads_work_item_and_clears_pending_fetch() {
         let mut app = App::new(vec![remote_branch(false)], vec![]);
         let (tx, mut rx) = mpsc::unbounded_channel();
-        let mut pending_fetches = HashSet::from([42]);
+        let mut fetches = pending(&[(42, FetchKind::Load)]);
 
         tx.send(FetchResult::Success {
             id: 42,
@@ -286,13 +1316,14 @@ mod tests {
                 url: None,
                 tags: vec![],
                 rich_text_fields: vec![],
+                fields: BTreeMap::new(),
             },
         })
         .expect("send should succeed");
 
-        process_fetch_results(&mut rx, &mut app, &mut pending_fetches);
+        process_fetch_results(&mut rx, &mut app, &mut fetches);
 
-        assert!(pending_fetches.is_empty());
+        assert!(fetches.work_items.keys().next().is_none());
         match app.get_work_item_status(42) {
             WorkItemStatus::Loaded(work_item) => assert_eq!(work_item.title, "Loaded item"),
             _ => panic!("expected loaded work item"),
@@ -303,14 +1334,14 @@ mod tests {
     fn test_process_fetch_results_sets_remote_freshness_error_and_status() {
         let mut app = App::new(vec![remote_branch(false)], vec![]);
         let (tx, mut rx) = mpsc::unbounded_channel();
-        let mut pending_fetches = HashSet::new();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
 
         tx.send(FetchResult::RemoteFreshnessError {
             error: "origin unreachable".to_string(),
         })
         .expect("send should succeed");
 
-        process_fetch_results(&mut rx, &mut app, &mut pending_fetches);
+        process_fetch_results(&mut rx, &mut app, &mut fetches);
 
         assert_eq!(app.remote_freshness_error(), Some("origin unreachable"));
         let status = app
@@ -320,6 +1351,758 @@ mod tests {
         assert_eq!(status.text, "Could not verify origin branches");
     }
 
+    #[test]
+    fn test_background_branch_statuses_fill_in_without_hiding_loaded_ones() {
+        let mut app = App::new(
+            vec![
+                local_branch("feature/1", None),
+                local_branch("feature/2", None),
+            ],
+            vec![],
+        );
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        app.set_branch_status("refs/heads/feature/2".to_string(), branch_status());
+        tx.send(FetchResult::BranchStatus {
+            key: "refs/heads/feature/1".to_string(),
+            result: Ok(branch_status()),
+        })
+        .expect("send should succeed");
+        tx.send(FetchResult::BranchStatus {
+            key: "refs/heads/feature/2".to_string(),
+            result: Err("lock held".to_string()),
+        })
+        .expect("send should succeed");
+        process_fetch_results(&mut rx, &mut app, &mut fetches);
+
+        assert!(app.branches_needing_status().is_empty());
+        assert!(app.get_status_message().is_none());
+    }
+
+    #[test]
+    fn test_load_branch_statuses_sends_a_result_per_branch() {
+        let git_repo = GitRepo::fixture(FixtureGitRepo::new());
+        let branches = [
+            local_branch("feature/1", None),
+            local_branch("feature/2", None),
+        ];
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        load_branch_statuses(&git_repo, &branches, &tx);
+
+        let mut keys = Vec::new();
+        while let Ok(FetchResult::BranchStatus { key, result }) = rx.try_recv() {
+            assert!(result.is_err());
+            keys.push(key);
+        }
+        assert_eq!(keys, ["refs/heads/feature/1", "refs/heads/feature/2"]);
+    }
+
+    #[test]
+    fn test_selection_debounce_waits_for_the_selection_to_rest() {
+        let branches = (1..=3)
+            .map(|id| local_branch(&format!("feature/{id}"), Some(id)))
+            .collect();
+        let mut app = App::new(branches, vec![]);
+        let mut debounce = SelectionDebounce::new(&app);
+        let start = Instant::now();
+
+        assert!(debounce.is_settled(&app, start));
+
+        app.update(Msg::NextBranch);
+        assert!(!debounce.is_settled(&app, start));
+        app.update(Msg::NextBranch);
+        let later = start + timing::FETCH_DEBOUNCE / 2;
+        assert!(!debounce.is_settled(&app, later));
+        assert!(!debounce.is_settled(&app, later + timing::FETCH_DEBOUNCE / 2));
+        assert!(debounce.is_settled(&app, later + timing::FETCH_DEBOUNCE));
+    }
+
+    #[test]
+    fn test_work_items_to_fetch_starts_with_selection_and_bounds_prefetch() {
+        let branches = (1..=8)
+            .map(|id| local_branch(&format!("feature/{id}"), Some(id)))
+            .collect();
+        let mut app = App::new(branches, vec![]);
+        app.update(Msg::NextBranch);
+        app.update(Msg::NextBranch);
+
+        let ids = work_items_to_fetch(&app, &HashSet::new(), DEFAULT_MAX_CONCURRENT_REQUESTS);
+        assert_eq!(ids.len(), DEFAULT_MAX_CONCURRENT_REQUESTS);
+        assert_eq!(ids[0], (3, FetchKind::Load));
+        assert!(ids[1..].iter().all(|(id, _)| id.abs_diff(3) <= 2));
+
+        let pending = (4..=7).collect::<HashSet<u32>>();
+        assert_eq!(
+            work_items_to_fetch(&app, &pending, DEFAULT_MAX_CONCURRENT_REQUESTS),
+            vec![(3, FetchKind::Load)]
+        );
+
+        app.set_work_item_loading(3);
+        assert!(work_items_to_fetch(&app, &pending, DEFAULT_MAX_CONCURRENT_REQUESTS).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_error_then_refresh_loads_the_work_item() {
+        let client = MockWorkItemProvider::new()
+            .with_work_item(1, "Login page")
+            .with_error(2, "Service unavailable");
+        let mut app = App::new(
+            vec![
+                local_branch("feature/1", Some(1)),
+                local_branch("feature/2", Some(2)),
+            ],
+            vec![],
+        );
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        trigger_work_item_fetch(&mut app, &client, &tx, &mut fetches);
+        for _ in 0..2 {
+            let result = rx.recv().await.expect("fetch result");
+            apply_fetch_result(&mut app, result, &mut fetches);
+        }
+
+        assert!(fetches.work_items.keys().next().is_none());
+        assert!(matches!(
+            app.get_work_item_status(1),
+            WorkItemStatus::Loaded(_)
+        ));
+        match app.get_work_item_status(2) {
+            WorkItemStatus::Error(error) => assert_eq!(error, "Service unavailable"),
+            _ => panic!("expected a fetch error"),
+        }
+
+        trigger_work_item_fetch(&mut app, &client, &tx, &mut fetches);
+        assert_eq!(client.requests().len(), 2);
+
+        client.set_work_item(2, "Back online");
+        cancel_work_item_fetch(&mut app, &mut fetches, 2);
+        app.reset_work_item(2);
+        trigger_work_item_fetch(&mut app, &client, &tx, &mut fetches);
+        let result = rx.recv().await.expect("refresh result");
+        apply_fetch_result(&mut app, result, &mut fetches);
+
+        match app.get_work_item_status(2) {
+            WorkItemStatus::Loaded(work_item) => assert_eq!(work_item.title, "Back online"),
+            _ => panic!("expected the refreshed work item"),
+        }
+        assert_eq!(client.requests().iter().filter(|&&id| id == 2).count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_navigating_away_cancels_fetches_that_are_no_longer_wanted() {
+        let branches = (1..=8)
+            .map(|id| local_branch(&format!("feature/{id}"), Some(id)))
+            .collect();
+        let mut app = App::new(branches, vec![]);
+        app.set_client_ready(vec![work_item(2, "Cached")]);
+        for id in [1, 3, 4] {
+            app.set_work_item_loading(id);
+        }
+        let mut fetches = pending(&[
+            (1, FetchKind::Load),
+            (2, FetchKind::Revalidate),
+            (3, FetchKind::Load),
+            (4, FetchKind::Load),
+        ]);
+
+        for _ in 0..5 {
+            app.update(Msg::NextBranch);
+        }
+        cancel_superseded_fetches(&mut app, &mut fetches);
+
+        assert_eq!(fetches.work_items.keys().collect::<Vec<_>>(), [4]);
+        assert!(matches!(
+            app.get_work_item_status(1),
+            WorkItemStatus::NotFetched
+        ));
+        assert!(app.is_work_item_stale(2));
+        assert!(matches!(
+            app.get_work_item_status(4),
+            WorkItemStatus::Loading
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pull_requests_are_fetched_for_the_selected_work_item_only() {
+        let pull_request = PullRequest {
+            id: 7,
+            title: "Login page".to_string(),
+            status: "active".to_string(),
+            is_draft: false,
+            source_branch: "feature/1".to_string(),
+            target_branch: "main".to_string(),
+            created_by: None,
+            project_id: None,
+            repository_id: None,
+            last_merge_source_commit: None,
+            reviewers: vec![],
+            url: None,
+        };
+        let client = MockWorkItemProvider::new()
+            .with_work_item(1, "Login page")
+            .with_work_item(2, "Signup")
+            .with_pull_requests(1, vec![pull_request]);
+        let mut app = App::new(
+            vec![
+                local_branch("feature/1", Some(1)),
+                local_branch("feature/2", Some(2)),
+            ],
+            vec![],
+        );
+        app.set_work_item_loaded(1, work_item(1, "Login page"));
+        app.set_work_item_loaded(2, work_item(2, "Signup"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        trigger_pull_request_fetch(&mut app, &client, &tx, &mut fetches);
+        assert!(matches!(
+            app.get_pull_requests(1),
+            Some(PullRequestsStatus::Loading)
+        ));
+        let result = rx.recv().await.expect("pull request result");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        match app.get_pull_requests(1) {
+            Some(PullRequestsStatus::Loaded(prs)) => assert_eq!(prs[0].id, 7),
+            _ => panic!("expected the linked pull requests"),
+        }
+        assert!(app.work_item_needing_pull_requests().is_none());
+        assert!(app.get_pull_requests(2).is_none());
+
+        app.update(Msg::NextBranch);
+        trigger_pull_request_fetch(&mut app, &client, &tx, &mut fetches);
+        assert_eq!(fetches.pull_requests.keys().collect::<Vec<_>>(), [2]);
+        app.update(Msg::PreviousBranch);
+        cancel_superseded_fetches(&mut app, &mut fetches);
+
+        assert!(fetches.pull_requests.keys().next().is_none());
+        assert!(app.get_pull_requests(2).is_none());
+        assert!(app.get_pull_requests(1).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_comments_are_fetched_once_the_section_is_shown() {
+        let comment = Comment {
+            id: 3,
+            author: Some("Ada Lovelace".to_string()),
+            created_at: Some(1_792_056_600),
+            text: "Ready for review".to_string(),
+            format: FieldFormat::Markdown,
+        };
+        let client = MockWorkItemProvider::new()
+            .with_work_item(1, "Login page")
+            .with_comments(1, vec![comment]);
+        let mut app = App::new(vec![local_branch("feature/1", Some(1))], vec![]);
+        app.set_work_item_loaded(1, work_item(1, "Login page"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        trigger_comment_fetch(&mut app, &client, &tx, &mut fetches);
+        assert!(app.get_comments(1).is_none());
+        assert!(fetches.comments.keys().next().is_none());
+
+        app.update(Msg::ToggleComments);
+        trigger_comment_fetch(&mut app, &client, &tx, &mut fetches);
+        assert!(matches!(app.get_comments(1), Some(CommentsStatus::Loading)));
+        let result = rx.recv().await.expect("comment result");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        match app.get_comments(1) {
+            Some(CommentsStatus::Loaded(comments)) => assert_eq!(comments[0].id, 3),
+            _ => panic!("expected the comments"),
+        }
+
+        // Hiding and showing the section again reuses them
+        app.update(Msg::ToggleComments);
+        app.update(Msg::ToggleComments);
+        assert!(app.work_item_needing_comments().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hierarchy_steps_into_related_work_items_and_back() {
+        let client = MockWorkItemProvider::new()
+            .with_work_item(1, "Checkout")
+            .with_work_item(2, "Payment form")
+            .with_work_item(3, "Receipt mail")
+            .with_children(1, vec![2, 3]);
+        let mut app = App::new(vec![local_branch("feature/1", Some(1))], vec![]);
+        let mut checkout = work_item(1, "Checkout");
+        checkout
+            .fields
+            .insert("System.Parent".to_string(), "9".to_string());
+        app.set_work_item_loaded(1, checkout);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        trigger_children_fetch(&mut app, &client, &tx, &mut fetches);
+        assert!(matches!(app.get_children(1), Some(ChildrenStatus::Loading)));
+        let result = rx.recv().await.expect("children result");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        assert_eq!(app.related_work_items(1), [9, 2, 3]);
+
+        // Into the first child, shown as fetched with the others
+        app.update(Msg::OpenHierarchy);
+        app.update(Msg::HierarchyNext);
+        app.update(Msg::EnterRelatedWorkItem);
+        assert_eq!(app.selected_work_item_id(), Some(2));
+        assert!(matches!(
+            app.get_work_item_status(2),
+            WorkItemStatus::Loaded(_)
+        ));
+        assert_eq!(app.work_item_needing_children(), Some(2));
+
+        // Back to the branch's work item, with the child still highlighted
+        app.update(Msg::HierarchyBack);
+        assert_eq!(app.selected_work_item_id(), Some(1));
+        assert_eq!(app.selected_related_work_item(), Some(2));
+
+        // Into the parent, which is fetched like a selected work item
+        app.update(Msg::HierarchyPrevious);
+        app.update(Msg::EnterRelatedWorkItem);
+        assert_eq!(app.selected_work_item_id(), Some(9));
+        assert_eq!(app.unfetched_work_item_ids().first(), Some(&9));
+
+        app.update(Msg::HierarchyBack);
+        app.update(Msg::HierarchyBack);
+        assert!(app.hierarchy().is_none());
+        assert_eq!(app.selected_work_item_id(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_completing_a_pull_request_reloads_it_and_its_work_item() {
+        let pull_request = PullRequest {
+            id: 7,
+            title: "Login page".to_string(),
+            status: "active".to_string(),
+            is_draft: false,
+            source_branch: "feature/1".to_string(),
+            target_branch: "main".to_string(),
+            created_by: None,
+            project_id: None,
+            repository_id: None,
+            last_merge_source_commit: None,
+            reviewers: vec![],
+            url: None,
+        };
+        let client = MockWorkItemProvider::new()
+            .with_work_item(1, "Login page")
+            .with_pull_requests(1, vec![pull_request.clone()]);
+        let mut app = App::new(vec![local_branch("feature/1", Some(1))], vec![]);
+        app.set_work_item_loaded(1, work_item(1, "Login page"));
+        app.set_pull_requests(1, Ok(vec![pull_request.clone()]));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+        let options = CompletionOptions {
+            merge_strategy: MergeStrategy::Squash,
+            delete_source_branch: true,
+            complete_work_items: true,
+        };
+
+        complete_pull_request(&client, &tx, 1, pull_request.clone(), options);
+        let result = rx.recv().await.expect("completion result");
+        apply_fetch_result(&mut app, result, &mut fetches);
+
+        assert!(app.get_pull_requests(1).is_none());
+        assert!(matches!(
+            app.get_work_item_status(1),
+            WorkItemStatus::NotFetched
+        ));
+        assert_eq!(
+            app.get_status_message()
+                .map(|message| message.text.as_str()),
+            Some("Completed PR !7")
+        );
+
+        let unknown = PullRequest {
+            id: 8,
+            ..pull_request
+        };
+        complete_pull_request(&client, &tx, 1, unknown, options);
+        let result = rx.recv().await.expect("completion result");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        assert!(matches!(app.mode(), AppMode::ErrorPopup(_)));
+    }
+
+    #[tokio::test]
+    async fn test_state_menu_lists_server_transitions_and_applies_the_choice() {
+        let transition = |state: &str, category: &str| StateTransition {
+            state: state.to_string(),
+            category: category.to_string(),
+        };
+        let client = MockWorkItemProvider::new()
+            .with_work_item(1, "Login page")
+            .with_transitions(
+                1,
+                vec![
+                    transition("Resolved", "Resolved"),
+                    transition("Closed", "Completed"),
+                ],
+            );
+        let mut app = App::new(vec![local_branch("feature/1", Some(1))], vec![]);
+        app.set_work_item_loaded(1, work_item(1, "Login page"));
+        app.record_viewed_work_item();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        app.update(Msg::OpenStateMenu(1));
+        fetch_state_transitions(&client, &tx, 1);
+        let result = rx.recv().await.expect("transitions result");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        app.update(Msg::StateMenuPrevious);
+        let (work_item_id, picked) = app.selected_state_transition().expect("loaded");
+        assert_eq!((work_item_id, picked.state.as_str()), (1, "Closed"));
+
+        set_work_item_state(&client, &tx, 1, "Closed".to_string());
+        let result = rx.recv().await.expect("state change result");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        match app.get_work_item_status(1) {
+            WorkItemStatus::Loaded(work_item) => {
+                assert!(matches!(work_item.state, WorkItemState::Closed))
+            }
+            _ => panic!("expected the updated work item"),
+        }
+        assert_eq!(
+            app.get_status_message()
+                .map(|message| message.text.as_str()),
+            Some("#1 is now Closed")
+        );
+
+        let summary = app.session_log().summary_json(chrono::Utc::now(), 0, &[]);
+        assert_eq!(summary["work_items_viewed"][0]["title"], "Login page");
+        assert_eq!(summary["state_changes"][0]["from"], "Active");
+        assert_eq!(summary["state_changes"][0]["to"], "Closed");
+    }
+
+    #[tokio::test]
+    async fn test_activity_polls_only_while_open_and_when_due() {
+        let client = MockWorkItemProvider::new()
+            .with_work_item(1, "Login page")
+            .with_work_item(2, "Signup")
+            .with_work_item(3, "Reassigned to me")
+            .with_assigned(3);
+        let mut app = App::new(
+            vec![
+                local_branch("feature/1", Some(1)),
+                local_branch("feature/2", None),
+            ],
+            vec![],
+        );
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        trigger_activity_poll(&mut app, &client, &tx);
+        assert!(!app.activity().loading);
+
+        app.update(Msg::OpenActivity);
+        trigger_activity_poll(&mut app, &client, &tx);
+        assert!(app.activity().loading);
+        let result = rx.recv().await.expect("activity result");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        let ids: Vec<u32> = app
+            .activity()
+            .entries
+            .iter()
+            .map(|entry| entry.work_item.id)
+            .collect();
+        assert_eq!(ids, [3, 1]);
+
+        trigger_activity_poll(&mut app, &client, &tx);
+        assert!(!app.activity().loading);
+        assert!(
+            app.activity_polls_at()
+                .is_some_and(|at| at > Instant::now())
+        );
+
+        app.update(Msg::RefreshActivity);
+        trigger_activity_poll(&mut app, &client, &tx);
+        assert!(app.activity().loading);
+    }
+
+    #[tokio::test]
+    async fn test_pull_request_list_is_fetched_when_the_tab_opens() {
+        let pull_request = |id: u32| PullRequest {
+            id,
+            title: format!("PR {id}"),
+            status: "active".to_string(),
+            is_draft: false,
+            source_branch: format!("feature/{id}"),
+            target_branch: "main".to_string(),
+            created_by: None,
+            project_id: None,
+            repository_id: None,
+            last_merge_source_commit: None,
+            reviewers: vec![],
+            url: None,
+        };
+        let client = MockWorkItemProvider::new()
+            .with_active_pull_requests(vec![pull_request(3), pull_request(5)]);
+        let repository = RepositoryRef {
+            project: "Shop".to_string(),
+            repository: "web".to_string(),
+        };
+        let mut app =
+            App::new(vec![local_branch("feature/1", None)], vec![]).with_repository(Ok(repository));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        trigger_pull_request_list_fetch(&mut app, &client, &tx);
+        assert!(!app.pull_request_list().loading);
+
+        app.update(Msg::OpenPullRequestList);
+        trigger_pull_request_list_fetch(&mut app, &client, &tx);
+        assert!(app.pull_request_list().loading);
+        let result = rx.recv().await.expect("pull request list");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        app.update(Msg::PullRequestListNext);
+        assert_eq!(app.selected_listed_pull_request().map(|pr| pr.id), Some(5));
+
+        trigger_pull_request_list_fetch(&mut app, &client, &tx);
+        assert!(!app.pull_request_list().loading);
+
+        // Listing again keeps the highlight on the same pull request
+        let client = client.with_active_pull_requests(vec![
+            pull_request(2),
+            pull_request(3),
+            pull_request(5),
+        ]);
+        app.update(Msg::RefreshPullRequestList);
+        trigger_pull_request_list_fetch(&mut app, &client, &tx);
+        let result = rx.recv().await.expect("pull request list");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        assert_eq!(app.pull_request_list().entries.len(), 3);
+        assert_eq!(app.selected_listed_pull_request().map(|pr| pr.id), Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_work_item_list_shows_the_highlighted_work_item() {
+        let client = MockWorkItemProvider::new()
+            .with_work_item(1, "Login page")
+            .with_work_item(3, "Signup")
+            .with_work_item(4, "Checkout")
+            .with_assigned(3)
+            .with_assigned(4)
+            .with_assigned(9);
+        let mut app = App::new(vec![local_branch("feature/1", Some(1))], vec![]);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        trigger_work_item_list_fetch(&mut app, &client, &tx);
+        assert!(!app.work_item_list().loading);
+
+        app.update(Msg::OpenWorkItemList);
+        trigger_work_item_list_fetch(&mut app, &client, &tx);
+        assert!(app.work_item_list().loading);
+        let result = rx.recv().await.expect("work item list");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        let ids: Vec<u32> = app
+            .work_item_list()
+            .entries
+            .iter()
+            .map(|wi| wi.id)
+            .collect();
+        assert_eq!(ids, [3, 4], "work items that fail to load are left out");
+
+        // The listed copy is shown without fetching the work item again
+        app.update(Msg::WorkItemListNext);
+        assert_eq!(app.selected_work_item_id(), Some(4));
+        assert!(matches!(
+            app.get_work_item_status(4),
+            WorkItemStatus::Loaded(_)
+        ));
+        trigger_work_item_fetch(&mut app, &client, &tx, &mut fetches);
+        assert!(fetches.work_items.keys().all(|id| id != 4));
+
+        app.cancel_mode();
+        assert_eq!(app.selected_work_item_id(), Some(1));
+        trigger_work_item_list_fetch(&mut app, &client, &tx);
+        assert!(!app.work_item_list().loading);
+    }
+
+    #[test]
+    fn test_state_change_updates_the_listed_work_item() {
+        let mut app = App::new(vec![local_branch("feature/1", Some(1))], vec![]);
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+        app.update(Msg::OpenWorkItemList);
+        app.update(Msg::SetWorkItemList(Ok(vec![work_item(3, "Signup")])));
+
+        let mut resolved = work_item(3, "Signup");
+        resolved.state = WorkItemState::Resolved;
+        apply_fetch_result(
+            &mut app,
+            FetchResult::StateChanged {
+                work_item_id: 3,
+                result: Ok(resolved),
+            },
+            &mut fetches,
+        );
+
+        assert!(matches!(
+            app.work_item_list().entries[0].state,
+            WorkItemState::Resolved
+        ));
+    }
+
+    #[test]
+    fn test_pull_request_list_without_a_repository_shows_why() {
+        let client = MockWorkItemProvider::new();
+        let mut app = App::new(vec![local_branch("feature/1", None)], vec![]).with_repository(Err(
+            "Remote URL 'git@github.com:o/r' does not look like an Azure Repos repository"
+                .to_string(),
+        ));
+        let (tx, _rx) = mpsc::unbounded_channel();
+
+        app.update(Msg::OpenPullRequestList);
+        trigger_pull_request_list_fetch(&mut app, &client, &tx);
+        assert!(!app.pull_request_list().loading);
+        assert!(
+            app.pull_request_list()
+                .error
+                .as_deref()
+                .is_some_and(|error| error.contains("Azure Repos"))
+        );
+        assert!(!app.pull_request_list_due());
+    }
+
+    #[tokio::test]
+    async fn test_local_work_items_are_prefetched_in_one_batch() {
+        let client = MockWorkItemProvider::new()
+            .with_work_item(1, "Login page")
+            .with_work_item(3, "Signup");
+        let mut remote = local_branch("origin/feature/4", Some(4));
+        remote.scope = BranchScope::Remote;
+        let mut app = App::new(
+            vec![
+                local_branch("feature/1", Some(1)),
+                local_branch("feature/2", Some(2)),
+                local_branch("feature/3", Some(3)),
+                local_branch("bugfix/1", Some(1)),
+                remote,
+            ],
+            vec![],
+        );
+        app.set_client_ready(vec![work_item(3, "Cached")]);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        trigger_work_item_prefetch(&mut app, &client, &tx, &mut fetches);
+        assert!(matches!(
+            app.get_work_item_status(1),
+            WorkItemStatus::Loading
+        ));
+        assert!(matches!(
+            app.get_work_item_status(3),
+            WorkItemStatus::Loaded(_)
+        ));
+        // Nothing is left to fetch one at a time
+        trigger_work_item_fetch(&mut app, &client, &tx, &mut fetches);
+        assert!(fetches.work_items.keys().next().is_none());
+
+        let result = rx.recv().await.expect("batch result");
+        apply_fetch_result(&mut app, result, &mut fetches);
+        assert_eq!(client.requests(), [1, 2, 3]);
+        assert!(fetches.batch.is_empty());
+        assert!(matches!(
+            app.get_work_item_status(1),
+            WorkItemStatus::Loaded(work_item) if work_item.title == "Login page"
+        ));
+        assert!(matches!(
+            app.get_work_item_status(2),
+            WorkItemStatus::Error(_)
+        ));
+        assert!(!app.is_work_item_stale(3));
+    }
+
+    #[test]
+    fn test_cached_work_items_are_revalidated_after_loads() {
+        let branches = (1..=3)
+            .map(|id| local_branch(&format!("feature/{id}"), Some(id)))
+            .collect();
+        let mut app = App::new(branches, vec![]);
+        app.set_client_ready(vec![work_item(1, "Cached"), work_item(3, "Cached")]);
+
+        assert_eq!(
+            work_items_to_fetch(&app, &HashSet::new(), DEFAULT_MAX_CONCURRENT_REQUESTS),
+            vec![
+                (2, FetchKind::Load),
+                (1, FetchKind::Revalidate),
+                (3, FetchKind::Revalidate)
+            ]
+        );
+        assert!(app.fresh_work_items().is_empty());
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+        tx.send(FetchResult::Success {
+            id: 1,
+            work_item: work_item(1, "Fresh"),
+        })
+        .expect("send should succeed");
+        tx.send(FetchResult::RevalidationError {
+            id: 3,
+            error: "offline".to_string(),
+        })
+        .expect("send should succeed");
+        process_fetch_results(&mut rx, &mut app, &mut fetches);
+
+        assert!(!app.is_work_item_stale(1));
+        assert!(app.is_work_item_stale(3));
+        assert!(app.stale_work_item_ids().is_empty());
+        match app.get_work_item_status(3) {
+            WorkItemStatus::Loaded(work_item) => assert_eq!(work_item.title, "Cached"),
+            _ => panic!("expected the cached work item to stay"),
+        }
+        let fresh = app.fresh_work_items();
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].title, "Fresh");
+    }
+
+    fn pending(work_items: &[(u32, FetchKind)]) -> Fetches {
+        let mut fetches = Fetches::new(DEFAULT_MAX_CONCURRENT_REQUESTS);
+        for &(id, kind) in work_items {
+            fetches.work_items.spawn(id, kind, std::future::pending());
+        }
+        fetches
+    }
+
+    fn branch_status() -> BranchStatus {
+        BranchStatus {
+            remote_status: RemoteStatus::UpToDate,
+            last_commit_sha: None,
+            last_commit_author: None,
+            last_commit_time: None,
+        }
+    }
+
+    fn work_item(id: u32, title: &str) -> WorkItem {
+        WorkItem {
+            id,
+            title: title.to_string(),
+            work_item_type: WorkItemType::Task,
+            state: WorkItemState::Active,
+            assigned_to: None,
+            url: None,
+            tags: vec![],
+            rich_text_fields: vec![],
+            fields: BTreeMap::new(),
+        }
+    }
+
+    fn local_branch(name: &str, work_item_id: Option<u32>) -> BranchInfo {
+        BranchInfo {
+            key: format!("refs/heads/{name}"),
+            display_name: name.to_string(),
+            branch_name: name.to_string(),
+            remote_name: None,
+            scope: BranchScope::Local,
+            work_item_id,
+            is_current: false,
+            is_protected: false,
+            is_stale: false,
+            origin_upstream: None,
+        }
+    }
+
     fn remote_branch(is_stale: bool) -> BranchInfo {
         BranchInfo {
             key: "refs/remotes/origin/feature/1".to_string(),
@@ -331,6 +2114,7 @@ mod tests {
             is_current: false,
             is_protected: false,
             is_stale,
+            origin_upstream: None,
         }
     }
 }
@@ -0,0 +1,289 @@
+//! Work items from earlier TUI sessions, so the details panel has something
+//! to show before the first request returns.
+//!
+//! Items fetched in a session are written to the cache directory on exit,
+//! keyed by organization. On startup they are shown right away and fetched
+//! again in the background; an entry not refreshed for
+//! `[cache].max_age_days` is dropped, and so are the least recently viewed
+//! beyond `[cache].work_items` per organization.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+use crate::azure_devops::WorkItem;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedWorkItem {
+    /// Unix timestamp of the fetch
+    fetched_at: i64,
+    /// `System.Rev` when fetched, for telling whether revalidation changed it
+    revision: Option<u32>,
+    /// Rank in viewing order across sessions; higher was viewed more recently
+    #[serde(default)]
+    last_viewed: u64,
+    work_item: WorkItem,
+}
+
+/// Cached work items by organization URL, then by ID.
+type Cache = BTreeMap<String, BTreeMap<u32, CachedWorkItem>>;
+
+fn cache_path() -> Option<PathBuf> {
+    let proj_dirs = ProjectDirs::from("", "", "cazdo")?;
+    Some(proj_dirs.cache_dir().join("work-items.json"))
+}
+
+fn read_cache() -> Cache {
+    cache_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn cache_key(organization_url: &str) -> String {
+    crate::redact::redact(organization_url.trim_end_matches('/')).into_owned()
+}
+
+/// The cached work items of `organization_url` fetched in the last
+/// `max_age_days` days, least recently viewed first.
+pub(super) fn load(organization_url: &str, max_age_days: usize) -> Vec<WorkItem> {
+    let oldest = oldest_kept(Utc::now().timestamp(), max_age_days);
+    let mut items: Vec<CachedWorkItem> = read_cache()
+        .remove(&cache_key(organization_url))
+        .map(|items| {
+            items
+                .into_values()
+                .filter(|cached| cached.fetched_at >= oldest)
+                .collect()
+        })
+        .unwrap_or_default();
+    items.sort_by_key(|cached| cached.last_viewed);
+    items.into_iter().map(|cached| cached.work_item).collect()
+}
+
+/// Add the work items fetched this session, least recently viewed first, to
+/// the cache, keeping at most `capacity` for the organization. Failures are ignored since the cache
+/// only saves a round trip.
+pub(super) fn save(
+    organization_url: &str,
+    work_items: Vec<WorkItem>,
+    capacity: usize,
+    max_age_days: usize,
+) {
+    let mut cache = read_cache();
+    let key = cache_key(organization_url);
+    merge(
+        &mut cache,
+        &key,
+        work_items,
+        Utc::now().timestamp(),
+        max_age_days,
+    );
+    truncate(&mut cache, &key, capacity);
+
+    let Some(path) = cache_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(content) = serde_json::to_string(&cache) {
+        let _ = fs::write(path, content);
+    }
+}
+
+/// Delete the cache file, returning its path if there was one.
+pub fn clear() -> Result<Option<PathBuf>> {
+    let path = cache_path().context("Failed to determine cache directory")?;
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(Some(path)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error)
+            .with_context(|| format!("Failed to delete the cache file: {}", path.display())),
+    }
+}
+
+/// Unix timestamp of the oldest fetch kept at `now`. A huge
+/// `max_age_days` keeps everything rather than overflowing.
+fn oldest_kept(now: i64, max_age_days: usize) -> i64 {
+    let max_age = i64::try_from(max_age_days)
+        .unwrap_or(i64::MAX)
+        .saturating_mul(SECONDS_PER_DAY);
+    now.saturating_sub(max_age)
+}
+
+/// Store `work_items` under `key` as fetched at `now` and viewed after every
+/// cached item, in their order, and drop entries of every organization older
+/// than `max_age_days`.
+fn merge(cache: &mut Cache, key: &str, work_items: Vec<WorkItem>, now: i64, max_age_days: usize) {
+    let items = cache.entry(key.to_string()).or_default();
+    let mut last_viewed = items.values().map(|cached| cached.last_viewed).max();
+    for work_item in work_items {
+        let viewed = last_viewed.map_or(0, |last| last + 1);
+        last_viewed = Some(viewed);
+        items.insert(
+            work_item.id,
+            CachedWorkItem {
+                fetched_at: now,
+                revision: work_item.revision(),
+                last_viewed: viewed,
+                work_item,
+            },
+        );
+    }
+
+    let oldest = oldest_kept(now, max_age_days);
+    for items in cache.values_mut() {
+        items.retain(|_, cached| cached.fetched_at >= oldest);
+    }
+    cache.retain(|_, items| !items.is_empty());
+}
+
+/// Drop the least recently viewed work items of `key` beyond `capacity`.
+fn truncate(cache: &mut Cache, key: &str, capacity: usize) {
+    let Some(items) = cache.get_mut(key) else {
+        return;
+    };
+    if items.len() <= capacity {
+        return;
+    }
+    let mut viewed: Vec<(u64, u32)> = items
+        .iter()
+        .map(|(id, cached)| (cached.last_viewed, *id))
+        .collect();
+    viewed.sort_unstable_by(|a, b| b.cmp(a));
+    for (_, id) in &viewed[capacity..] {
+        items.remove(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure_devops::{WorkItemState, WorkItemType};
+
+    const MAX_AGE_DAYS: usize = 10;
+
+    fn work_item(id: u32, title: &str) -> WorkItem {
+        WorkItem {
+            id,
+            title: title.to_string(),
+            work_item_type: WorkItemType::Task,
+            state: WorkItemState::Active,
+            assigned_to: None,
+            url: None,
+            tags: vec![],
+            rich_text_fields: vec![],
+            fields: BTreeMap::from([("System.Rev".to_string(), "3".to_string())]),
+        }
+    }
+
+    #[test]
+    fn merge_replaces_items_and_drops_old_entries() {
+        let day = 20_000 * SECONDS_PER_DAY;
+        let mut cache = Cache::new();
+        merge(
+            &mut cache,
+            "https://dev.azure.com/a",
+            vec![work_item(1, "Old"), work_item(2, "Kept")],
+            day,
+            MAX_AGE_DAYS,
+        );
+        merge(
+            &mut cache,
+            "https://dev.azure.com/b",
+            vec![work_item(9, "B")],
+            day,
+            MAX_AGE_DAYS,
+        );
+
+        let later = day + (MAX_AGE_DAYS as i64 - 1) * SECONDS_PER_DAY;
+        merge(
+            &mut cache,
+            "https://dev.azure.com/a",
+            vec![work_item(1, "New")],
+            later,
+            MAX_AGE_DAYS,
+        );
+        let items = &cache["https://dev.azure.com/a"];
+        assert_eq!(items[&1].work_item.title, "New");
+        assert_eq!(items[&1].revision, Some(3));
+        assert_eq!(items[&2].work_item.title, "Kept");
+
+        merge(
+            &mut cache,
+            "https://dev.azure.com/a",
+            vec![],
+            day + (MAX_AGE_DAYS as i64 + 1) * SECONDS_PER_DAY,
+            MAX_AGE_DAYS,
+        );
+        assert_eq!(
+            cache["https://dev.azure.com/a"].keys().collect::<Vec<_>>(),
+            [&1]
+        );
+        assert!(!cache.contains_key("https://dev.azure.com/b"));
+    }
+
+    #[test]
+    fn truncate_keeps_the_most_recently_viewed_items() {
+        let mut cache = Cache::new();
+        merge(
+            &mut cache,
+            "org",
+            vec![work_item(3, "Viewed first"), work_item(1, "Viewed later")],
+            10,
+            MAX_AGE_DAYS,
+        );
+        merge(
+            &mut cache,
+            "org",
+            vec![work_item(2, "Viewed last")],
+            10,
+            MAX_AGE_DAYS,
+        );
+        merge(
+            &mut cache,
+            "other",
+            vec![work_item(4, "Other")],
+            0,
+            MAX_AGE_DAYS,
+        );
+
+        truncate(&mut cache, "org", 2);
+
+        assert_eq!(cache["org"].keys().collect::<Vec<_>>(), [&1, &2]);
+        assert_eq!(cache["other"].len(), 1);
+    }
+
+    #[test]
+    fn huge_max_age_keeps_everything_without_overflowing() {
+        assert_eq!(oldest_kept(1_000, usize::MAX), 1_000 - i64::MAX);
+        assert_eq!(oldest_kept(-1_000, usize::MAX), i64::MIN);
+        assert_eq!(oldest_kept(SECONDS_PER_DAY, 1), 0);
+    }
+
+    #[test]
+    fn cached_items_round_trip_through_json() {
+        let mut cache = Cache::new();
+        merge(
+            &mut cache,
+            "org",
+            vec![work_item(7, "Cached")],
+            0,
+            MAX_AGE_DAYS,
+        );
+
+        let content = serde_json::to_string(&cache).unwrap();
+        let restored: Cache = serde_json::from_str(&content).unwrap();
+        assert_eq!(restored["org"][&7].work_item.title, "Cached");
+        assert_eq!(restored["org"][&7].work_item.revision(), Some(3));
+    }
+}
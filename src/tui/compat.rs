@@ -0,0 +1,78 @@
+//! Terminal capability detection. The legacy Windows console (conhost, as
+//! opened by cmd.exe or PowerShell outside Windows Terminal) draws emoji at
+//! the wrong width and has no dim or strikethrough text, so the TUI falls
+//! back to ASCII icons and drops those modifiers there.
+
+use ratatui::buffer::Buffer;
+use ratatui::style::Modifier;
+
+/// Text modifiers the legacy Windows console cannot draw
+const LEGACY_CONSOLE_UNSUPPORTED: Modifier = Modifier::DIM.union(Modifier::CROSSED_OUT);
+
+/// Variables set by terminals that replace the legacy console on Windows:
+/// Windows Terminal, VS Code and other `TERM_PROGRAM` terminals, ConEmu, and
+/// mintty (Git Bash, MSYS2, Cygwin).
+const MODERN_TERMINAL_VARS: &[&str] = &["WT_SESSION", "TERM_PROGRAM", "ConEmuANSI", "TERM"];
+
+/// Whether cazdo runs in the legacy Windows console.
+pub fn is_legacy_windows_console() -> bool {
+    is_legacy_console(cfg!(windows), |name| std::env::var_os(name).is_some())
+}
+
+fn is_legacy_console(is_windows: bool, is_set: impl Fn(&str) -> bool) -> bool {
+    is_windows && !MODERN_TERMINAL_VARS.iter().any(|name| is_set(name))
+}
+
+/// Text modifiers the current terminal cannot draw.
+pub fn unsupported_modifiers() -> Modifier {
+    if is_legacy_windows_console() {
+        LEGACY_CONSOLE_UNSUPPORTED
+    } else {
+        Modifier::empty()
+    }
+}
+
+/// Remove `unsupported` modifiers from every cell of `buffer`.
+pub fn strip_modifiers(buffer: &mut Buffer, unsupported: Modifier) {
+    if unsupported.is_empty() {
+        return;
+    }
+    for cell in &mut buffer.content {
+        cell.modifier.remove(unsupported);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn only_windows_without_a_modern_terminal_is_legacy() {
+        assert!(is_legacy_console(true, |_| false));
+        assert!(!is_legacy_console(true, |name| name == "WT_SESSION"));
+        assert!(!is_legacy_console(true, |name| name == "TERM"));
+        assert!(!is_legacy_console(false, |_| false));
+    }
+
+    #[test]
+    fn strip_modifiers_keeps_supported_ones() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buffer.set_string(
+            0,
+            0,
+            "abc",
+            Style::new().add_modifier(Modifier::BOLD | Modifier::DIM | Modifier::CROSSED_OUT),
+        );
+
+        strip_modifiers(&mut buffer, LEGACY_CONSOLE_UNSUPPORTED);
+
+        assert!(
+            buffer
+                .content
+                .iter()
+                .all(|cell| cell.modifier == Modifier::BOLD)
+        );
+    }
+}
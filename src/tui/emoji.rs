@@ -0,0 +1,166 @@
+//! ASCII fallbacks for emoji.
+//!
+//! Some terminal fonts draw emoji at the wrong width, which shifts the rest of
+//! the line. When emoji are disabled, icons use their ASCII variants and
+//! rendered field text has emoji replaced through [`ascii_fallback`].
+
+use std::borrow::Cow;
+
+use ratatui::text::{Line, Span};
+
+use super::compat::is_legacy_windows_console;
+use crate::config::EmojiMode;
+
+/// Known emoji and the ASCII text that replaces them.
+const FALLBACKS: &[(char, &str)] = &[
+    ('✅', "[x]"),
+    ('✔', "[x]"),
+    ('☑', "[x]"),
+    ('❌', "[ ]"),
+    ('✖', "x"),
+    ('⚠', "!"),
+    ('❗', "!"),
+    ('❓', "?"),
+    ('⭐', "*"),
+    ('🔥', "*"),
+    ('🐞', "[bug]"),
+    ('🐛', "[bug]"),
+    ('👍', "+1"),
+    ('👎', "-1"),
+    ('🙂', ":)"),
+    ('😀', ":D"),
+    ('😃', ":D"),
+    ('😄', ":D"),
+    ('😉', ";)"),
+    ('🙁', ":("),
+    ('😢', ":'("),
+    ('➡', "->"),
+    ('⬅', "<-"),
+    ('⬆', "^"),
+    ('⬇', "v"),
+];
+
+/// Replacement for emoji without a known ASCII equivalent.
+const UNKNOWN_FALLBACK: &str = "*";
+
+/// Whether emoji should be shown for `mode` in the current terminal.
+pub fn emoji_enabled(mode: EmojiMode) -> bool {
+    emoji_enabled_for(
+        mode,
+        std::env::var("TERM").ok().as_deref(),
+        is_legacy_windows_console(),
+    )
+}
+
+/// `auto` falls back to ASCII on the Linux console, dumb terminals, and the
+/// legacy Windows console.
+fn emoji_enabled_for(mode: EmojiMode, term: Option<&str>, legacy_console: bool) -> bool {
+    match mode {
+        EmojiMode::Always => true,
+        EmojiMode::Never => false,
+        EmojiMode::Auto => {
+            let limited_term = matches!(term, Some("linux" | "dumb"));
+            !limited_term && !legacy_console
+        }
+    }
+}
+
+/// Replace emoji in `text` with ASCII, dropping emoji modifiers and joiners.
+pub fn ascii_fallback(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_emoji_char) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if is_emoji_modifier(c) {
+            continue;
+        }
+
+        if is_emoji(c) {
+            let fallback = FALLBACKS
+                .iter()
+                .find(|(emoji, _)| *emoji == c)
+                .map_or(UNKNOWN_FALLBACK, |(_, ascii)| ascii);
+            result.push_str(fallback);
+        } else {
+            result.push(c);
+        }
+    }
+
+    Cow::Owned(result)
+}
+
+/// Apply [`ascii_fallback`] to every span of rendered lines.
+pub fn ascii_lines(lines: Vec<Line<'static>>) -> Vec<Line<'static>> {
+    lines
+        .into_iter()
+        .map(|line| {
+            let spans: Vec<Span<'static>> = line
+                .spans
+                .into_iter()
+                .map(|span| match ascii_fallback(&span.content) {
+                    Cow::Borrowed(_) => span,
+                    Cow::Owned(text) => Span::styled(text, span.style),
+                })
+                .collect();
+            Line::from(spans).style(line.style)
+        })
+        .collect()
+}
+
+fn is_emoji_char(c: char) -> bool {
+    is_emoji(c) || is_emoji_modifier(c)
+}
+
+fn is_emoji(c: char) -> bool {
+    FALLBACKS.iter().any(|(emoji, _)| *emoji == c)
+        || matches!(c as u32, 0x1F000..=0x1FAFF | 0x2600..=0x27BF)
+}
+
+/// Variation selectors, zero-width joiners, skin tones, and tag characters.
+fn is_emoji_modifier(c: char) -> bool {
+    matches!(c as u32, 0xFE0E | 0xFE0F | 0x200D | 0x1F3FB..=0x1F3FF | 0xE0020..=0xE007F)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_modes_ignore_the_terminal() {
+        assert!(emoji_enabled_for(EmojiMode::Always, Some("linux"), true));
+        assert!(!emoji_enabled_for(
+            EmojiMode::Never,
+            Some("xterm-256color"),
+            false
+        ));
+    }
+
+    #[test]
+    fn auto_disables_emoji_on_limited_terminals() {
+        assert!(emoji_enabled_for(
+            EmojiMode::Auto,
+            Some("xterm-256color"),
+            false
+        ));
+        assert!(!emoji_enabled_for(EmojiMode::Auto, Some("linux"), false));
+        assert!(!emoji_enabled_for(EmojiMode::Auto, None, true));
+        assert!(emoji_enabled_for(EmojiMode::Auto, None, false));
+    }
+
+    #[test]
+    fn ascii_fallback_replaces_known_emoji_and_drops_modifiers() {
+        assert_eq!(ascii_fallback("done ✔️ 👍🏽"), "done [x] +1");
+    }
+
+    #[test]
+    fn ascii_fallback_uses_placeholder_for_unknown_emoji() {
+        assert_eq!(ascii_fallback("ship it 🚀"), "ship it *");
+    }
+
+    #[test]
+    fn ascii_fallback_borrows_plain_text() {
+        assert!(matches!(ascii_fallback("plain — text"), Cow::Borrowed(_)));
+    }
+}
@@ -1,100 +1,375 @@
-use std::collections::HashSet;
 use std::io;
+use std::path::Path;
+use std::time::Instant;
 
 use anyhow::Result;
+use chrono::Utc;
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{DisableMouseCapture, EnableMouseCapture, EventStream},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use futures_util::StreamExt;
 use ratatui::{Terminal, backend::CrosstermBackend};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
 use super::app::{App, DetailsMetrics, Msg};
-use super::ui;
+use super::shutdown::ShutdownSignals;
 use super::{
     actions::{
-        execute_checkout_branch, execute_delete_branch, execute_prune_branch,
-        open_current_work_item,
+        execute_checkout_branch, execute_delete_branch, execute_delete_marked,
+        execute_prune_branch, execute_undo_delete, open_current_pull_request,
+        open_current_work_item, open_listed_pull_request, open_work_item,
     },
     background::{
-        FetchResult, fetch_branch_status_if_needed, process_fetch_results,
-        trigger_remote_freshness_check, trigger_work_item_fetch,
+        FetchResult, Fetches, SelectionDebounce, apply_fetch_result, cancel_superseded_fetches,
+        cancel_work_item_fetch, complete_pull_request, fetch_branch_status_if_needed,
+        fetch_state_transitions, fetch_work_item_json, process_fetch_results, set_work_item_state,
+        start_git_fetch, trigger_activity_poll, trigger_branch_status_prefetch,
+        trigger_change_check, trigger_children_fetch, trigger_comment_fetch,
+        trigger_pull_request_fetch, trigger_pull_request_list_fetch,
+        trigger_remote_freshness_check, trigger_work_item_fetch, trigger_work_item_list_fetch,
+        trigger_work_item_prefetch,
     },
-    input::{Command, handle_input},
+    input::{Command, handle_event},
+};
+use super::{cache, session, ui};
+use crate::azure_devops::{
+    AzureDevOpsClient, WorkItem, WorkItemProvider, requests_sent, work_item_client,
 };
-use crate::azure_devops::{AzureDevOpsClient, work_item_client};
 use crate::git::GitRepo;
+use crate::i18n::{Text, t, tf};
+use crate::restore;
+
+/// The Azure DevOps client and the work items cached for its organization.
+struct Connection<P> {
+    client: P,
+    organization_url: Option<String>,
+    cached_work_items: Vec<WorkItem>,
+}
 
-pub async fn run_app(mut app: App, git_repo: GitRepo) -> Result<()> {
+/// Load the config and PAT and read the work item cache, leaving out work
+/// items older than `max_age_days`. Runs on a blocking thread so the branch
+/// list shows without waiting for it.
+fn connect(max_age_days: usize) -> Result<Connection<AzureDevOpsClient>> {
     let client = work_item_client()?;
+    let organization_url = client.organization_url().map(str::to_string);
+    let cached_work_items = organization_url
+        .as_deref()
+        .map(|organization_url| cache::load(organization_url, max_age_days))
+        .unwrap_or_default();
+    Ok(Connection {
+        client,
+        organization_url,
+        cached_work_items,
+    })
+}
+
+pub async fn run_app(
+    mut app: App,
+    git_repo: GitRepo,
+    session_summary: Option<&Path>,
+) -> Result<()> {
+    let max_age_days = app.cache_max_age_days();
+    let connecting = tokio::task::spawn_blocking(move || connect(max_age_days));
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    // Scrolling with the wheel is a convenience; a console that rejects
+    // mouse capture still gets the TUI.
+    let mouse_capture = execute!(stdout, EnableMouseCapture).is_ok();
+    if !mouse_capture {
+        tracing::warn!("mouse capture unavailable");
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let (tx, rx) = mpsc::unbounded_channel::<FetchResult>();
-    let result = run_loop(&mut terminal, &mut app, client, tx, rx, &git_repo).await;
+    trigger_branch_status_prefetch(&app, &git_repo, &tx);
+    if app.fetch_on_start() {
+        start_git_fetch(&mut app, &git_repo, &tx, true);
+    }
+    let result = run_loop(&mut terminal, &mut app, connecting, tx, rx, &git_repo).await;
 
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    if mouse_capture {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
     if !app.deleted_branches().is_empty() {
-        println!("\nDeleted branches this session:");
+        println!("\n{}", t(Text::DeletedThisSession));
         for db in app.deleted_branches() {
+            let name = match &db.remote {
+                Some(remote) => tf(
+                    Text::DeletedAlongWith,
+                    &[("branch", &db.name), ("remote", remote)],
+                ),
+                None => db.name.clone(),
+            };
             match &db.restore_hint {
-                Some(hint) => println!("  • {} - restore: {}", db.name, hint),
-                None => println!("  • {}", db.name),
+                Some(hint) => println!("  • {name} - {}", tf(Text::Restore, &[("hint", hint)])),
+                None => println!("  • {name}"),
             }
         }
+        save_deleted_branches(&app, &git_repo);
     }
+    print_session_summary(&app, session_summary);
 
     result
 }
 
-async fn run_loop(
+/// Print what the session did, and write it as JSON to `path` if given.
+fn print_session_summary(app: &App, path: Option<&Path>) {
+    let ended_at = Utc::now();
+    let requests_sent = requests_sent();
+    println!();
+    for line in app.session_log().summary_lines(ended_at, requests_sent) {
+        println!("{line}");
+    }
+
+    let Some(path) = path else {
+        return;
+    };
+    let deleted: Vec<&str> = app
+        .deleted_branches()
+        .iter()
+        .flat_map(|db| [Some(&db.name), db.remote.as_ref()])
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    let summary = app
+        .session_log()
+        .summary_json(ended_at, requests_sent, &deleted);
+    match session::write_summary(path, &summary) {
+        Ok(()) => println!(
+            "\n{}",
+            tf(
+                Text::SessionSummarySaved,
+                &[("path", &path.display().to_string())]
+            )
+        ),
+        Err(error) => eprintln!(
+            "\n{}",
+            tf(
+                Text::SessionSummaryFailed,
+                &[("error", &format!("{error:#}"))]
+            )
+        ),
+    }
+}
+
+/// Record the local branches deleted this session for `cazdo restore`.
+fn save_deleted_branches(app: &App, git_repo: &GitRepo) {
+    let branches: Vec<restore::DeletedBranch> = app
+        .deleted_branches()
+        .iter()
+        .filter_map(|db| {
+            Some(restore::DeletedBranch {
+                name: db.name.clone(),
+                commit_sha: db.commit_sha.clone()?,
+            })
+        })
+        .collect();
+    if branches.is_empty() {
+        return;
+    }
+    match git_repo
+        .repo_dir()
+        .and_then(|repo_dir| restore::write_session(&repo_dir, &branches))
+    {
+        Ok(path) => println!(
+            "\n{}",
+            tf(
+                Text::RestoreScriptSaved,
+                &[("path", &path.display().to_string())]
+            )
+        ),
+        Err(error) => eprintln!(
+            "\n{}",
+            tf(
+                Text::RestoreScriptFailed,
+                &[("error", &format!("{error:#}"))]
+            )
+        ),
+    }
+}
+
+/// Wait for whichever comes first: terminal input, a background result, the
+/// client becoming ready, a signal to quit, the status message expiring, or
+/// the selection settling for a debounced work item fetch. Between events the
+/// loop starts background work and redraws if needed; with none of these
+/// pending, an idle TUI does not wake up at all. Work items are fetched once
+/// `connecting` has produced a client, and saved to the cache on quit.
+async fn run_loop<P: WorkItemProvider>(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
-    client: AzureDevOpsClient,
+    mut connecting: JoinHandle<Result<Connection<P>>>,
     tx: mpsc::UnboundedSender<FetchResult>,
     mut rx: mpsc::UnboundedReceiver<FetchResult>,
     git_repo: &GitRepo,
 ) -> Result<()> {
-    let mut pending_fetches: HashSet<u32> = HashSet::new();
+    let mut connection: Option<Connection<P>> = None;
+    let mut connected = false;
+    let mut fetches = Fetches::new(app.max_concurrent_requests());
+    let mut debounce = SelectionDebounce::new(app);
+    let mut events = EventStream::new();
+    let mut signals = ShutdownSignals::new()?;
 
     loop {
         app.clear_expired_status();
-        process_fetch_results(&mut rx, app, &mut pending_fetches);
-        trigger_work_item_fetch(app, &client, &tx, &mut pending_fetches);
+        app.clear_expired_toasts();
+        cancel_superseded_fetches(app, &mut fetches);
+        let settled = debounce.is_settled(app, Instant::now());
+        if settled {
+            app.record_viewed_work_item();
+        }
+        if settled && let Some(connection) = &connection {
+            trigger_work_item_fetch(app, &connection.client, &tx, &mut fetches);
+            trigger_pull_request_fetch(app, &connection.client, &tx, &mut fetches);
+            trigger_comment_fetch(app, &connection.client, &tx, &mut fetches);
+            trigger_children_fetch(app, &connection.client, &tx, &mut fetches);
+        }
+        if let Some(connection) = &connection {
+            trigger_activity_poll(app, &connection.client, &tx);
+            trigger_pull_request_list_fetch(app, &connection.client, &tx);
+            trigger_work_item_list_fetch(app, &connection.client, &tx);
+            trigger_change_check(app, &connection.client, &tx);
+        }
         trigger_remote_freshness_check(app, git_repo, &tx);
-        fetch_branch_status_if_needed(app, git_repo);
-
-        let mut metrics = DetailsMetrics::default();
-        terminal.draw(|frame| metrics = ui::render(frame, app))?;
-        app.update(Msg::SetDetailsMetrics(metrics));
-
-        if let Some(action) = handle_input(app)? {
-            match action {
-                Command::Delete(branch) => execute_delete_branch(app, git_repo, &branch),
-                Command::Prune(branch) => execute_prune_branch(app, git_repo, &branch),
-                Command::Refresh(wi_id) => {
-                    pending_fetches.remove(&wi_id);
-                    app.reset_work_item(wi_id);
-                }
-                Command::OpenWorkItem => open_current_work_item(app),
-                Command::Checkout(branch) => execute_checkout_branch(app, git_repo, &branch),
-            }
+        fetch_branch_status_if_needed(app, git_repo, &tx, &mut fetches);
+
+        if app.take_needs_redraw() {
+            let mut metrics = DetailsMetrics::default();
+            terminal.draw(|frame| metrics = ui::render(frame, app))?;
+            app.update(Msg::SetDetailsMetrics(metrics));
         }
 
         if app.should_quit() {
+            fetches.cancel_all();
+            if let Some(Connection {
+                organization_url: Some(organization_url),
+                ..
+            }) = &connection
+            {
+                cache::save(
+                    organization_url,
+                    app.fresh_work_items(),
+                    app.work_item_capacity(),
+                    app.cache_max_age_days(),
+                );
+            }
             return Ok(());
         }
+
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else {
+                    app.update(Msg::Quit);
+                    continue;
+                };
+                if let Some(action) = handle_event(app, event?) {
+                    match action {
+                        Command::Delete(branch) => {
+                            execute_delete_branch(app, git_repo, &branch, false)
+                        }
+                        Command::DeleteWithRemote(branch) => {
+                            execute_delete_branch(app, git_repo, &branch, true)
+                        }
+                        Command::Prune(branch) => execute_prune_branch(app, git_repo, &branch),
+                        Command::UndoDelete => execute_undo_delete(app, git_repo),
+                        Command::DeleteMarked => execute_delete_marked(app, git_repo),
+                        Command::Refresh(wi_id) => {
+                            cancel_work_item_fetch(app, &mut fetches, wi_id);
+                            app.reset_work_item(wi_id);
+                        }
+                        Command::OpenWorkItem => open_current_work_item(app),
+                        Command::OpenPullRequest => open_current_pull_request(app),
+                        Command::OpenListedPullRequest => open_listed_pull_request(app),
+                        Command::OpenParent(wi_id) | Command::OpenActivityItem(wi_id) => {
+                            open_work_item(app, wi_id)
+                        }
+                        Command::Checkout(branch) => execute_checkout_branch(app, git_repo, &branch),
+                        Command::Fetch => start_git_fetch(app, git_repo, &tx, false),
+                        Command::FetchPrune => start_git_fetch(app, git_repo, &tx, true),
+                        Command::CheckMergedBranches => app.set_merged_branches(
+                            git_repo
+                                .merged_branches()
+                                .map_err(|error| format!("{error:#}")),
+                        ),
+                        Command::InspectJson(wi_id) => match &connection {
+                            Some(connection) => {
+                                fetch_work_item_json(&connection.client, &tx, wi_id)
+                            }
+                            None => app.set_inspector_json(
+                                wi_id,
+                                Err(t(Text::NotConnected).to_string()),
+                            ),
+                        },
+                        Command::StateTransitions(wi_id) => match &connection {
+                            Some(connection) => {
+                                fetch_state_transitions(&connection.client, &tx, wi_id)
+                            }
+                            None => app.set_state_transitions(
+                                wi_id,
+                                Err(t(Text::NotConnected).to_string()),
+                            ),
+                        },
+                        Command::SetState { work_item_id, state } => {
+                            if let Some(connection) = &connection {
+                                set_work_item_state(&connection.client, &tx, work_item_id, state);
+                            }
+                        }
+                        Command::CompletePullRequest {
+                            work_item_id,
+                            pull_request,
+                            options,
+                        } => {
+                            if let Some(connection) = &connection {
+                                complete_pull_request(
+                                    &connection.client,
+                                    &tx,
+                                    work_item_id,
+                                    pull_request,
+                                    options,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+            joined = &mut connecting, if !connected => {
+                connected = true;
+                match joined.map_err(anyhow::Error::from).and_then(|result| result) {
+                    Ok(mut ready) => {
+                        app.set_client_ready(std::mem::take(&mut ready.cached_work_items));
+                        trigger_work_item_prefetch(app, &ready.client, &tx, &mut fetches);
+                        connection = Some(ready);
+                    }
+                    Err(error) => app.set_client_unavailable(format!("{error:#}")),
+                }
+            }
+            Some(result) = rx.recv() => {
+                apply_fetch_result(app, result, &mut fetches);
+                process_fetch_results(&mut rx, app, &mut fetches);
+            }
+            _ = signals.recv() => app.update(Msg::Quit),
+            _ = sleep_until_some(app.status_expires_at()) => {}
+            _ = sleep_until_some(app.activity_polls_at()), if connection.is_some() => {}
+            _ = sleep_until_some(app.change_check_at()), if connection.is_some() => {}
+            _ = sleep_until_some(app.toasts_expire_at()) => {}
+            _ = tokio::time::sleep_until(debounce.settles_at().into()), if !settled => {}
+        }
+    }
+}
+
+/// Sleep until `deadline`, or forever without one.
+async fn sleep_until_some(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
     }
 }
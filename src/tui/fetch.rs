@@ -0,0 +1,223 @@
+//! Background requests in flight. A [`FetchManager`] keeps at most one
+//! request per key, holds the concurrency limit the caller schedules
+//! against, aborts requests that are no longer needed, and retries network
+//! failures per its [`RetryPolicy`]. Work items and the pull requests linked
+//! to them each get a manager, both keyed by work item ID.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::task::AbortHandle;
+
+use crate::azure_devops::is_transient_error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FetchKind {
+    /// Something with nothing to show yet
+    Load,
+    /// Something cached, shown until it is fetched again
+    Revalidate,
+}
+
+/// How often a failed request is made again, and which failures qualify.
+#[derive(Clone, Copy)]
+pub(super) struct RetryPolicy {
+    /// Attempts in total, including the first
+    attempts: u32,
+    /// Wait before the second attempt, doubled before each one after it
+    delay: Duration,
+    is_transient: fn(&anyhow::Error) -> bool,
+}
+
+impl RetryPolicy {
+    /// Retry network failures and timeouts twice. An error response is the
+    /// server's answer and is not retried.
+    pub(super) const NETWORK: Self = Self {
+        attempts: 3,
+        delay: Duration::from_millis(500),
+        is_transient: is_transient_error,
+    };
+
+    /// Make `request` until it succeeds, fails with an error that is not
+    /// transient, or runs out of attempts.
+    pub(super) async fn run<T, F, Fut>(self, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        let mut delay = self.delay;
+        loop {
+            match request().await {
+                Err(error) if attempt < self.attempts && (self.is_transient)(&error) => {
+                    tracing::debug!(attempt, error = %error, "retrying request");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// A request in flight, aborted when it is no longer needed.
+struct PendingFetch {
+    kind: FetchKind,
+    handle: AbortHandle,
+}
+
+/// Requests in flight, by key.
+pub(super) struct FetchManager<K> {
+    limit: usize,
+    retry: RetryPolicy,
+    fetches: HashMap<K, PendingFetch>,
+}
+
+impl<K: Copy + Eq + Hash> FetchManager<K> {
+    pub(super) fn new(limit: usize, retry: RetryPolicy) -> Self {
+        Self {
+            limit,
+            retry,
+            fetches: HashMap::new(),
+        }
+    }
+
+    /// How many requests to keep in flight when fetching ahead
+    pub(super) fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub(super) fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+
+    pub(super) fn keys(&self) -> impl Iterator<Item = K> + '_ {
+        self.fetches.keys().copied()
+    }
+
+    /// Run `task` in the background as the request for `key`. Returns false
+    /// without starting it when `key` is already being fetched.
+    pub(super) fn spawn(
+        &mut self,
+        key: K,
+        kind: FetchKind,
+        task: impl Future<Output = ()> + Send + 'static,
+    ) -> bool {
+        if self.fetches.contains_key(&key) {
+            return false;
+        }
+        let handle = tokio::spawn(task).abort_handle();
+        self.fetches.insert(key, PendingFetch { kind, handle });
+        true
+    }
+
+    /// Forget a request whose result has arrived.
+    pub(super) fn finish(&mut self, key: &K) {
+        self.fetches.remove(key);
+    }
+
+    /// Abort the request for `key`, returning its kind if one was in flight.
+    pub(super) fn cancel(&mut self, key: &K) -> Option<FetchKind> {
+        let fetch = self.fetches.remove(key)?;
+        fetch.handle.abort();
+        Some(fetch.kind)
+    }
+
+    /// Abort every request whose key `keep` rejects, returning those aborted.
+    pub(super) fn cancel_unless(&mut self, keep: impl Fn(&K) -> bool) -> Vec<(K, FetchKind)> {
+        let superseded: Vec<K> = self.keys().filter(|key| !keep(key)).collect();
+        superseded
+            .into_iter()
+            .filter_map(|key| Some((key, self.cancel(&key)?)))
+            .collect()
+    }
+
+    /// Abort every request, e.g. on quit.
+    pub(super) fn cancel_all(&mut self) {
+        for (_, fetch) in self.fetches.drain() {
+            fetch.handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::sync::oneshot;
+
+    const RETRY: RetryPolicy = RetryPolicy {
+        attempts: 3,
+        delay: Duration::ZERO,
+        is_transient: |error| error.to_string() == "timed out",
+    };
+
+    #[tokio::test]
+    async fn retry_policy_retries_only_transient_errors() {
+        let calls = AtomicU32::new(0);
+        let result = RETRY
+            .run(|| async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err(anyhow!("timed out")),
+                    _ => Ok("loaded"),
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), "loaded");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = RETRY
+            .run(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(anyhow!("timed out"))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = RETRY
+            .run(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(anyhow!("Work Item #1 not found"))
+            })
+            .await;
+        assert_eq!(result.unwrap_err().to_string(), "Work Item #1 not found");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn fetch_manager_dedups_and_aborts_cancelled_requests() {
+        let mut fetches = FetchManager::new(2, RETRY);
+        let mut receivers = Vec::new();
+        for key in [1, 2, 3] {
+            let (tx, rx) = oneshot::channel::<()>();
+            assert!(fetches.spawn(key, FetchKind::Load, async move {
+                std::future::pending::<()>().await;
+                drop(tx);
+            }));
+            receivers.push(rx);
+        }
+        assert!(!fetches.spawn(1, FetchKind::Revalidate, async {}));
+
+        assert_eq!(
+            fetches.cancel_unless(|&key| key != 2),
+            [(2, FetchKind::Load)]
+        );
+        assert_eq!(fetches.cancel(&2), None);
+        fetches.finish(&3);
+        assert_eq!(fetches.keys().collect::<Vec<_>>(), [1]);
+
+        let [first, second, third] = receivers.try_into().unwrap();
+        assert!(second.await.is_err());
+        fetches.cancel_all();
+        assert!(first.await.is_err());
+        assert!(fetches.keys().next().is_none());
+        drop(third);
+    }
+}
@@ -1,12 +1,22 @@
 //! HTML to ratatui renderer
 //!
 //! Converts HTML content from Azure DevOps work items into styled ratatui Lines.
+//! The HTML is first parsed into a node tree (see [`tree`]), which is then walked
+//! to emit styled spans.
+
+mod markdown;
+mod tree;
+
+use std::borrow::Cow;
 
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
 
+pub use markdown::html_to_markdown;
+use tree::{Element, Node};
+
 /// Context for tracking list state
 #[derive(Clone)]
 enum ListType {
@@ -33,8 +43,8 @@ impl TextStyle {
     }
 }
 
-/// Parser state for HTML rendering
-struct HtmlParser {
+/// Renderer state for walking the HTML tree
+struct HtmlRenderer {
     /// Stack of active style modifiers (bold, italic)
     style_stack: Vec<TextStyle>,
     /// Stack of active lists for nesting
@@ -59,9 +69,11 @@ struct HtmlParser {
     current_line_width: usize,
     /// Indent prefix for current context
     indent: String,
+    /// Width of `indent`, in characters
+    indent_width: usize,
 }
 
-impl HtmlParser {
+impl HtmlRenderer {
     fn new(max_width: usize) -> Self {
         Self {
             style_stack: Vec::new(),
@@ -76,6 +88,7 @@ impl HtmlParser {
             max_width,
             current_line_width: 0,
             indent: String::new(),
+            indent_width: 0,
         }
     }
 
@@ -145,10 +158,9 @@ impl HtmlParser {
         }
 
         // Handle word wrapping
+        let effective_max = self.max_width.saturating_sub(self.indent_width);
         for word in text.split_inclusive(char::is_whitespace) {
             let word_width = word.chars().count();
-            let indent_width = self.indent.chars().count();
-            let effective_max = self.max_width.saturating_sub(indent_width);
 
             // Check if we need to wrap
             if self.current_line_width + word_width > effective_max && self.current_line_width > 0 {
@@ -163,13 +175,12 @@ impl HtmlParser {
     /// Update indent based on list stack depth
     fn update_indent(&mut self) {
         self.indent = "  ".repeat(self.list_stack.len());
+        self.indent_width = self.indent.len();
     }
 
     /// Handle opening tag
-    fn handle_open_tag(&mut self, tag: &str) {
-        let tag_lower = tag.to_lowercase();
-
-        match tag_lower.as_str() {
+    fn handle_open_tag(&mut self, element: &Element) {
+        match element.name.as_str() {
             // Block elements that create line breaks
             "br" => {
                 self.flush_line();
@@ -212,6 +223,14 @@ impl HtmlParser {
                 self.style_stack.push(TextStyle::CrossedOut);
                 self.current_style = self.compute_style();
             }
+            // Word paste expresses strikethrough as an inline style.
+            "span" if has_line_through(&element.attributes) => {
+                self.flush_text();
+                self.style_stack.push(TextStyle::CrossedOut);
+                self.current_style = self.compute_style();
+            }
+            "sup" => self.add_text("^"),
+            "sub" => self.add_text("_"),
 
             // Links
             "a" => {
@@ -275,7 +294,7 @@ impl HtmlParser {
             // Code
             "code" | "pre" => {
                 self.flush_text();
-                if tag_lower == "pre" {
+                if element.name == "pre" {
                     self.in_pre = true;
                 }
                 self.current_style = self.compute_style().fg(Color::Yellow);
@@ -286,10 +305,8 @@ impl HtmlParser {
     }
 
     /// Handle closing tag
-    fn handle_close_tag(&mut self, tag: &str) {
-        let tag_lower = tag.to_lowercase();
-
-        match tag_lower.as_str() {
+    fn handle_close_tag(&mut self, element: &Element) {
+        match element.name.as_str() {
             // Block elements
             "p" | "div" | "h4" | "h5" | "h6" => {
                 self.flush_line();
@@ -322,6 +339,11 @@ impl HtmlParser {
                 self.pop_style(TextStyle::CrossedOut);
                 self.current_style = self.compute_style();
             }
+            "span" if has_line_through(&element.attributes) => {
+                self.flush_text();
+                self.pop_style(TextStyle::CrossedOut);
+                self.current_style = self.compute_style();
+            }
 
             // Links
             "a" => {
@@ -348,7 +370,7 @@ impl HtmlParser {
             // Code
             "code" | "pre" => {
                 self.flush_text();
-                if tag_lower == "pre" {
+                if element.name == "pre" {
                     self.in_pre = false;
                 }
                 self.current_style = self.compute_style();
@@ -358,50 +380,9 @@ impl HtmlParser {
         }
     }
 
-    /// Parse and render HTML to Lines
-    fn parse(mut self, html: &str) -> Vec<Line<'static>> {
-        let mut chars = html.chars().peekable();
-        let mut in_tag = false;
-        let mut tag_content = String::new();
-
-        while let Some(c) = chars.next() {
-            if c == '<' {
-                // Flush any pending text before tag
-                if !in_tag {
-                    in_tag = true;
-                    tag_content.clear();
-                }
-            } else if c == '>' && in_tag {
-                in_tag = false;
-                self.process_tag(&tag_content);
-                tag_content.clear();
-            } else if in_tag {
-                tag_content.push(c);
-            } else {
-                // Regular text content
-                let mut text = String::new();
-                text.push(c);
-
-                // Collect consecutive text
-                while let Some(&next_c) = chars.peek() {
-                    if next_c == '<' {
-                        break;
-                    }
-                    text.push(chars.next().unwrap());
-                }
-
-                // Normalize whitespace unless in pre block
-                let normalized = if self.in_pre {
-                    text
-                } else {
-                    normalize_whitespace(&text)
-                };
-
-                if !normalized.is_empty() {
-                    self.add_text(&normalized);
-                }
-            }
-        }
+    /// Render parsed HTML nodes to Lines
+    fn render(mut self, nodes: &[Node]) -> Vec<Line<'static>> {
+        self.render_nodes(nodes);
 
         // Flush any remaining content
         self.flush_line();
@@ -419,22 +400,31 @@ impl HtmlParser {
         self.lines
     }
 
-    /// Process a tag string (without < >)
-    fn process_tag(&mut self, tag_content: &str) {
-        let tag_content = tag_content.trim();
+    fn render_nodes(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            match node {
+                Node::Text(text) => self.render_text(text),
+                Node::Element(element) => {
+                    self.handle_open_tag(element);
+                    if !element.is_void() {
+                        self.render_nodes(&element.children);
+                        self.handle_close_tag(element);
+                    }
+                }
+            }
+        }
+    }
 
-        if let Some(rest) = tag_content.strip_prefix('/') {
-            // Closing tag
-            let tag_name = rest.split_whitespace().next().unwrap_or("");
-            self.handle_close_tag(tag_name);
-        } else if let Some(rest) = tag_content.strip_suffix('/') {
-            // Self-closing tag
-            let tag_name = rest.split_whitespace().next().unwrap_or("");
-            self.handle_open_tag(tag_name);
+    fn render_text(&mut self, text: &str) {
+        // Normalize whitespace unless in pre block
+        let normalized = if self.in_pre {
+            Cow::Borrowed(text)
         } else {
-            // Opening tag
-            let tag_name = tag_content.split_whitespace().next().unwrap_or("");
-            self.handle_open_tag(tag_name);
+            normalize_whitespace(text)
+        };
+
+        if !normalized.is_empty() {
+            self.add_text(&normalized);
         }
     }
 
@@ -449,28 +439,76 @@ impl HtmlParser {
     }
 }
 
-/// Decode common HTML entities
-fn decode_html_entities(s: &str) -> String {
-    s.replace("&nbsp;", " ")
-        .replace("&amp;", "&")
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
-        .replace("&quot;", "\"")
-        .replace("&#39;", "'")
-        .replace("&apos;", "'")
-        .replace("&#x27;", "'")
-        .replace("&mdash;", "—")
-        .replace("&ndash;", "–")
-        .replace("&hellip;", "…")
-        .replace("&bull;", "•")
-        .replace("&copy;", "©")
-        .replace("&reg;", "®")
-        .replace("&trade;", "™")
+/// Whether a tag's `style` attribute requests `text-decoration: line-through`
+fn has_line_through(attributes: &str) -> bool {
+    let attributes = attributes.to_ascii_lowercase();
+    let Some(start) = attributes.find("style=") else {
+        return false;
+    };
+
+    attributes[start + "style=".len()..]
+        .split(';')
+        .filter_map(|declaration| declaration.split_once(':'))
+        .any(|(property, value)| {
+            property
+                .trim_matches(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+                .starts_with("text-decoration")
+                && value.contains("line-through")
+        })
 }
 
-/// Normalize whitespace in text content
-fn normalize_whitespace(s: &str) -> String {
-    let mut result = String::new();
+/// Common HTML entities and the text they stand for
+const ENTITIES: &[(&str, &str)] = &[
+    ("&nbsp;", " "),
+    ("&amp;", "&"),
+    ("&lt;", "<"),
+    ("&gt;", ">"),
+    ("&quot;", "\""),
+    ("&#39;", "'"),
+    ("&apos;", "'"),
+    ("&#x27;", "'"),
+    ("&mdash;", "—"),
+    ("&ndash;", "–"),
+    ("&hellip;", "…"),
+    ("&bull;", "•"),
+    ("&copy;", "©"),
+    ("&reg;", "®"),
+    ("&trade;", "™"),
+];
+
+/// Decode common HTML entities in one pass, borrowing text without any
+fn decode_html_entities(s: &str) -> Cow<'_, str> {
+    if !s.contains('&') {
+        return Cow::Borrowed(s);
+    }
+
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match ENTITIES.iter().find(|(entity, _)| rest.starts_with(entity)) {
+            Some((entity, text)) => {
+                decoded.push_str(text);
+                rest = &rest[entity.len()..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    Cow::Owned(decoded)
+}
+
+/// Normalize whitespace in text content, borrowing text that needs none
+fn normalize_whitespace(s: &str) -> Cow<'_, str> {
+    if !s.contains("  ") && !s.chars().any(|c| c != ' ' && c.is_whitespace()) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut result = String::with_capacity(s.len());
     let mut last_was_space = false;
 
     for c in s.chars() {
@@ -485,7 +523,7 @@ fn normalize_whitespace(s: &str) -> String {
         }
     }
 
-    result
+    Cow::Owned(result)
 }
 
 /// Render HTML content to styled ratatui Lines
@@ -497,8 +535,7 @@ fn normalize_whitespace(s: &str) -> String {
 /// # Returns
 /// Vector of styled Lines ready for ratatui Paragraph
 pub fn render_html(html: &str, max_width: usize) -> Vec<Line<'static>> {
-    let parser = HtmlParser::new(max_width);
-    parser.parse(html)
+    HtmlRenderer::new(max_width).render(&tree::parse(html))
 }
 
 #[cfg(test)]
@@ -576,6 +613,15 @@ mod tests {
     fn test_html_entities() {
         let decoded = decode_html_entities("Hello&nbsp;&amp;&nbsp;world");
         assert_eq!(decoded, "Hello & world");
+        // Decoded text is not decoded again.
+        assert_eq!(
+            decode_html_entities("&amp;lt; &unknown; &"),
+            "&lt; &unknown; &"
+        );
+        assert!(matches!(
+            decode_html_entities("plain"),
+            Cow::Borrowed("plain")
+        ));
     }
 
     #[test]
@@ -652,4 +698,63 @@ mod tests {
                 .contains(Modifier::UNDERLINED)
         );
     }
+
+    #[test]
+    fn test_superscript_and_subscript_use_caret_and_underscore() {
+        let lines = render_html("x<sup>2</sup> + H<sub>2</sub>O", 80);
+        let content: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+
+        assert_eq!(content, "x^2 + H_2O");
+    }
+
+    #[test]
+    fn test_line_through_span_is_crossed_out() {
+        let lines = render_html(
+            r#"keep <span style="color: red; text-decoration: line-through">gone</span> kept"#,
+            80,
+        );
+        let gone = lines[0]
+            .spans
+            .iter()
+            .find(|s| s.content.contains("gone"))
+            .expect("gone span");
+        let kept = lines[0]
+            .spans
+            .iter()
+            .find(|s| s.content.contains("kept"))
+            .expect("kept span");
+
+        assert!(gone.style.add_modifier.contains(Modifier::CROSSED_OUT));
+        assert!(!kept.style.add_modifier.contains(Modifier::CROSSED_OUT));
+    }
+
+    #[test]
+    fn test_plain_span_does_not_close_outer_strikethrough() {
+        let lines = render_html("<s><span>a</span>b</s>", 80);
+
+        assert!(
+            lines[0]
+                .spans
+                .iter()
+                .all(|s| s.style.add_modifier.contains(Modifier::CROSSED_OUT))
+        );
+    }
+
+    #[test]
+    fn test_interleaved_tags_keep_inner_style_after_outer_close() {
+        let lines = render_html("<b><i>x</b>y</i>z", 80);
+        let style_of = |needle: &str| {
+            lines[0]
+                .spans
+                .iter()
+                .find(|s| s.content == needle)
+                .unwrap_or_else(|| panic!("no span {needle:?}"))
+                .style
+                .add_modifier
+        };
+
+        assert!(style_of("x").contains(Modifier::BOLD | Modifier::ITALIC));
+        assert_eq!(style_of("y"), Modifier::ITALIC);
+        assert_eq!(style_of("z"), Modifier::empty());
+    }
 }
@@ -0,0 +1,267 @@
+//! HTML to Markdown conversion for exporting work item fields.
+//!
+//! Walks the same [`Node`] tree as the terminal renderer and emits CommonMark:
+//! headings, emphasis, links, lists, code, and images map directly; tables and
+//! unknown elements fall back to their text content.
+
+use super::tree::{Element, Node};
+use super::{decode_html_entities, has_line_through, normalize_whitespace};
+
+/// Convert HTML to Markdown.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut writer = MarkdownWriter::default();
+    writer.write_nodes(&super::tree::parse(html));
+    writer.finish()
+}
+
+#[derive(Default)]
+struct MarkdownWriter {
+    out: String,
+    /// Open lists; `Some(n)` is an ordered list at item `n`.
+    list_stack: Vec<Option<usize>>,
+    in_pre: bool,
+}
+
+impl MarkdownWriter {
+    fn write_nodes(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            match node {
+                Node::Text(text) => self.write_text(text),
+                Node::Element(element) => self.write_element(element),
+            }
+        }
+    }
+
+    fn write_text(&mut self, text: &str) {
+        let text = decode_html_entities(text);
+        if self.in_pre {
+            self.out.push_str(&text);
+            return;
+        }
+
+        let text = normalize_whitespace(&text);
+        let text = if self.at_line_start() {
+            text.trim_start()
+        } else {
+            &text
+        };
+        self.out.push_str(text);
+    }
+
+    fn write_element(&mut self, element: &Element) {
+        match element.name.as_str() {
+            "br" => self.out.push_str("  \n"),
+            "p" | "div" => self.block(|w| w.write_nodes(&element.children)),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = element.name[1..].parse().unwrap_or(1);
+                self.block(|w| {
+                    w.out.push_str(&"#".repeat(level));
+                    w.out.push(' ');
+                    w.write_nodes(&element.children);
+                });
+            }
+            "b" | "strong" => self.wrap("**", &element.children),
+            "i" | "em" => self.wrap("*", &element.children),
+            "s" | "strike" | "del" => self.wrap("~~", &element.children),
+            "span" if has_line_through(&element.attributes) => self.wrap("~~", &element.children),
+            "code" if !self.in_pre => self.wrap("`", &element.children),
+            "sup" => {
+                self.out.push('^');
+                self.write_nodes(&element.children);
+            }
+            "sub" => {
+                self.out.push('_');
+                self.write_nodes(&element.children);
+            }
+            "a" => match attribute(&element.attributes, "href") {
+                Some(href) => {
+                    self.out.push('[');
+                    self.write_nodes(&element.children);
+                    self.out.push_str(&format!("]({href})"));
+                }
+                None => self.write_nodes(&element.children),
+            },
+            "img" => {
+                let alt = attribute(&element.attributes, "alt").unwrap_or_default();
+                let src = attribute(&element.attributes, "src").unwrap_or_default();
+                self.out.push_str(&format!("![{alt}]({src})"));
+            }
+            "pre" => self.block(|w| {
+                w.out.push_str("```\n");
+                w.in_pre = true;
+                w.write_nodes(&element.children);
+                w.in_pre = false;
+                w.newline();
+                w.out.push_str("```");
+            }),
+            "ul" | "ol" => {
+                let list = (element.name == "ol").then_some(0);
+                if self.list_stack.is_empty() {
+                    self.blank_line();
+                }
+                self.list_stack.push(list);
+                self.write_nodes(&element.children);
+                self.list_stack.pop();
+                if self.list_stack.is_empty() {
+                    self.blank_line();
+                }
+            }
+            "li" => {
+                self.newline();
+                let depth = self.list_stack.len().saturating_sub(1);
+                let marker = match self.list_stack.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{n}. ")
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.out.push_str(&"  ".repeat(depth));
+                self.out.push_str(&marker);
+                self.write_nodes(&element.children);
+            }
+            "tr" => {
+                self.newline();
+                self.write_nodes(&element.children);
+            }
+            "td" | "th" => {
+                if !self.at_line_start() {
+                    self.out.push_str(" | ");
+                }
+                self.write_nodes(&element.children);
+            }
+            _ => self.write_nodes(&element.children),
+        }
+    }
+
+    fn wrap(&mut self, marker: &str, children: &[Node]) {
+        self.out.push_str(marker);
+        self.write_nodes(children);
+        self.out.push_str(marker);
+    }
+
+    /// Write a block separated from its surroundings by blank lines; inside
+    /// list items, blocks only start a new line.
+    fn block(&mut self, write: impl FnOnce(&mut Self)) {
+        if self.list_stack.is_empty() {
+            self.blank_line();
+            write(self);
+            self.blank_line();
+        } else {
+            write(self);
+        }
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n')
+    }
+
+    fn newline(&mut self) {
+        if !self.at_line_start() {
+            self.out.push('\n');
+        }
+    }
+
+    fn blank_line(&mut self) {
+        self.newline();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut lines: Vec<&str> = Vec::new();
+        for line in self.out.lines() {
+            // Keep the two trailing spaces of a hard line break.
+            let line = if line.ends_with("  ") && !line.trim().is_empty() {
+                line
+            } else {
+                line.trim_end()
+            };
+            if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+                continue;
+            }
+            lines.push(line);
+        }
+
+        lines.join("\n").trim_end().to_string()
+    }
+}
+
+/// Value of attribute `name` in raw attribute text, quoted or not.
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let lower = attributes.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(found) = lower[search..].find(name) {
+        let start = search + found;
+        search = start + name.len();
+
+        let preceded_by_name_char = lower[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-');
+        let rest = lower[search..].trim_start();
+        if preceded_by_name_char || !rest.starts_with('=') {
+            continue;
+        }
+
+        let value_start = attributes.len() - rest.len() + 1;
+        let value = attributes[value_start..].trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or(""),
+            _ => value.split_whitespace().next().unwrap_or(""),
+        };
+        return Some(decode_html_entities(value).into_owned());
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_inline_formatting_and_links() {
+        assert_eq!(
+            html_to_markdown(
+                r#"<p>Use <b>bold</b>, <em>italic</em>, <code>code</code> and <a href="https://example.com">a link</a>.</p>"#
+            ),
+            "Use **bold**, *italic*, `code` and [a link](https://example.com)."
+        );
+    }
+
+    #[test]
+    fn separates_headings_and_paragraphs_with_blank_lines() {
+        assert_eq!(
+            html_to_markdown("<h2>Steps</h2><p>First</p><div>Second<br>line</div>"),
+            "## Steps\n\nFirst\n\nSecond  \nline"
+        );
+    }
+
+    #[test]
+    fn converts_nested_lists() {
+        assert_eq!(
+            html_to_markdown(
+                "<p>Todo</p><ul><li>one<ol><li>a</li><li>b</li></ol></li><li>two</li></ul>"
+            ),
+            "Todo\n\n- one\n  1. a\n  2. b\n- two"
+        );
+    }
+
+    #[test]
+    fn keeps_preformatted_text_in_a_fence() {
+        assert_eq!(
+            html_to_markdown("<pre>fn main() {\n    run();\n}</pre>"),
+            "```\nfn main() {\n    run();\n}\n```"
+        );
+    }
+
+    #[test]
+    fn decodes_entities_and_reads_unquoted_attributes() {
+        assert_eq!(
+            html_to_markdown("<img alt='diagram' src=img.png> a &amp; b"),
+            "![diagram](img.png) a & b"
+        );
+    }
+}
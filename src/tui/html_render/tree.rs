@@ -0,0 +1,314 @@
+//! Minimal HTML tree builder for the renderer.
+//!
+//! Tokenizes tags and text and nests them into [`Node`]s. Unmatched closing tags
+//! are ignored, and closing an element that still has inline formatting open
+//! inside it closes that formatting too, then reopens it after the element, so
+//! interleaved markup like `<b><i>x</b>y</i>` keeps `y` italic.
+
+/// A parsed HTML node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+/// An HTML element with a lowercased tag name and its raw attribute text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Element {
+    pub name: String,
+    pub attributes: String,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    fn new(name: &str, attributes: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            attributes: attributes.to_string(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn is_void(&self) -> bool {
+        is_void(&self.name)
+    }
+}
+
+/// Elements that never have content or a closing tag.
+fn is_void(name: &str) -> bool {
+    matches!(
+        name,
+        "br" | "img"
+            | "hr"
+            | "input"
+            | "meta"
+            | "link"
+            | "col"
+            | "area"
+            | "base"
+            | "wbr"
+            | "source"
+            | "embed"
+            | "param"
+            | "track"
+    )
+}
+
+/// Inline formatting elements that are reopened after an interleaved close.
+fn is_formatting(name: &str) -> bool {
+    matches!(
+        name,
+        "b" | "strong"
+            | "i"
+            | "em"
+            | "u"
+            | "s"
+            | "strike"
+            | "del"
+            | "span"
+            | "a"
+            | "code"
+            | "sup"
+            | "sub"
+    )
+}
+
+/// Parse HTML into a list of top-level nodes.
+pub(super) fn parse(html: &str) -> Vec<Node> {
+    let mut builder = TreeBuilder::default();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        match rest.find('<') {
+            Some(0) => match rest.find('>') {
+                Some(end) => {
+                    builder.tag(&rest[1..end]);
+                    rest = &rest[end + 1..];
+                }
+                // An unterminated tag is dropped, like the text scanner did.
+                None => rest = "",
+            },
+            Some(start) => {
+                builder.text(&rest[..start]);
+                rest = &rest[start..];
+            }
+            None => {
+                builder.text(rest);
+                rest = "";
+            }
+        }
+    }
+
+    builder.finish()
+}
+
+struct TreeBuilder {
+    /// Open elements; the bottom entry is a synthetic root.
+    stack: Vec<Element>,
+}
+
+impl Default for TreeBuilder {
+    fn default() -> Self {
+        Self {
+            stack: vec![Element::new("", "")],
+        }
+    }
+}
+
+impl TreeBuilder {
+    fn tag(&mut self, content: &str) {
+        let content = content.trim();
+
+        // Comments, doctypes, and processing instructions carry no content.
+        if content.starts_with('!') || content.starts_with('?') {
+            return;
+        }
+
+        if let Some(rest) = content.strip_prefix('/') {
+            let name = rest.split_whitespace().next().unwrap_or("");
+            self.close(&name.to_ascii_lowercase());
+            return;
+        }
+
+        let self_closing = content.ends_with('/');
+        let content = content.trim_end_matches('/');
+        let (name, attributes) = content
+            .split_once(char::is_whitespace)
+            .unwrap_or((content, ""));
+        let name = name.to_ascii_lowercase();
+        if name.is_empty() {
+            return;
+        }
+
+        let element = Element::new(&name, attributes.trim());
+        if self_closing || is_void(&name) {
+            self.append(Node::Element(element));
+        } else {
+            self.stack.push(element);
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        if let Some(Node::Text(last)) = self.current().children.last_mut() {
+            last.push_str(text);
+        } else {
+            self.append(Node::Text(text.to_string()));
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        let Some(target) = self.stack.iter().skip(1).rposition(|e| e.name == name) else {
+            return;
+        };
+        let target = target + 1;
+
+        let mut reopen = Vec::new();
+        while self.stack.len() > target + 1 {
+            let element = self.pop_into_parent();
+            if is_formatting(&element.0) {
+                reopen.push(element);
+            }
+        }
+        self.pop_into_parent();
+
+        for (name, attributes) in reopen.into_iter().rev() {
+            self.stack.push(Element::new(&name, &attributes));
+        }
+    }
+
+    /// Pop the innermost open element, attach it to its parent, and return its
+    /// name and attributes.
+    fn pop_into_parent(&mut self) -> (String, String) {
+        let element = self.stack.pop().expect("non-root element to pop");
+        let identity = (element.name.clone(), element.attributes.clone());
+        self.append(Node::Element(element));
+        identity
+    }
+
+    fn append(&mut self, node: Node) {
+        self.current().children.push(node);
+    }
+
+    fn current(&mut self) -> &mut Element {
+        self.stack.last_mut().expect("root element is always open")
+    }
+
+    fn finish(mut self) -> Vec<Node> {
+        while self.stack.len() > 1 {
+            self.pop_into_parent();
+        }
+
+        self.stack
+            .pop()
+            .map(|root| root.children)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &str) -> Node {
+        Node::Text(value.to_string())
+    }
+
+    fn element(name: &str, children: Vec<Node>) -> Node {
+        Node::Element(Element {
+            name: name.to_string(),
+            attributes: String::new(),
+            children,
+        })
+    }
+
+    #[test]
+    fn nests_well_formed_markup() {
+        assert_eq!(
+            parse("<p>a <b>b</b></p>"),
+            vec![element(
+                "p",
+                vec![text("a "), element("b", vec![text("b")])]
+            )]
+        );
+    }
+
+    #[test]
+    fn lowercases_names_and_keeps_attributes() {
+        let nodes = parse(r#"<A HREF="x">y</A>"#);
+
+        match &nodes[0] {
+            Node::Element(e) => {
+                assert_eq!(e.name, "a");
+                assert_eq!(e.attributes, r#"HREF="x""#);
+                assert_eq!(e.children, vec![text("y")]);
+            }
+            Node::Text(_) => panic!("expected element"),
+        }
+    }
+
+    #[test]
+    fn interleaved_close_reopens_inner_formatting() {
+        assert_eq!(
+            parse("<b><i>x</b>y</i>"),
+            vec![
+                element("b", vec![element("i", vec![text("x")])]),
+                element("i", vec![text("y")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn unmatched_closing_tag_is_ignored() {
+        assert_eq!(
+            parse("a</b>c"),
+            vec![text("ac")],
+            "text around a stray close tag merges"
+        );
+    }
+
+    #[test]
+    fn void_and_self_closing_elements_have_no_children() {
+        assert_eq!(
+            parse("a<br>b<img src=\"x\"/>c"),
+            vec![
+                text("a"),
+                element("br", vec![]),
+                text("b"),
+                Node::Element(Element {
+                    name: "img".to_string(),
+                    attributes: "src=\"x\"".to_string(),
+                    children: vec![],
+                }),
+                text("c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn unclosed_elements_are_closed_at_end_of_input() {
+        assert_eq!(
+            parse("<ul><li>one"),
+            vec![element("ul", vec![element("li", vec![text("one")])])]
+        );
+    }
+
+    #[test]
+    fn comments_are_dropped() {
+        assert_eq!(parse("a<!-- note -->b"), vec![text("ab")]);
+    }
+
+    #[test]
+    fn closing_a_block_does_not_reopen_blocks_inside_it() {
+        assert_eq!(
+            parse("<div><p>x</div>y"),
+            vec![
+                element("div", vec![element("p", vec![text("x")])]),
+                text("y")
+            ]
+        );
+    }
+}
@@ -1,29 +1,60 @@
-use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
 
 use super::app::{App, AppMode, BranchInfo, Msg};
+use super::keymap::Action;
 use super::theme::{scroll, timing};
+use crate::azure_devops::{CompletionOptions, PullRequest};
 
 pub(super) enum Command {
     Delete(BranchInfo),
+    /// Delete a local branch and the branch it tracks on origin
+    DeleteWithRemote(BranchInfo),
     Prune(BranchInfo),
+    /// Restore the local branch deleted last this session
+    UndoDelete,
+    /// Delete or prune every marked branch
+    DeleteMarked,
     Refresh(u32),
     OpenWorkItem,
+    OpenPullRequest,
+    /// Open the parent work item clicked in the details panel
+    OpenParent(u32),
     Checkout(BranchInfo),
+    Fetch,
+    /// Fetch origin with `--prune`
+    FetchPrune,
+    /// Fetch the raw JSON of a work item for the inspector
+    InspectJson(u32),
+    /// Fetch the states a work item can move to for the state menu
+    StateTransitions(u32),
+    /// Open the work item highlighted in the activity tab
+    OpenActivityItem(u32),
+    /// Open the pull request highlighted in the pull request tab
+    OpenListedPullRequest,
+    /// Move a work item to a new state
+    SetState {
+        work_item_id: u32,
+        state: String,
+    },
+    /// Check which local branches are merged for the statistics popup
+    CheckMergedBranches,
+    /// Complete a pull request of a work item
+    CompletePullRequest {
+        work_item_id: u32,
+        pull_request: PullRequest,
+        options: CompletionOptions,
+    },
 }
 
-pub(super) fn handle_input(app: &mut App) -> Result<Option<Command>> {
-    if !event::poll(timing::POLL_INTERVAL)? {
-        return Ok(None);
-    }
-
-    match event::read()? {
-        Event::Key(key) if key.kind == KeyEventKind::Press => Ok(handle_key_event(app, key)),
-        Event::Mouse(mouse_event) => {
-            handle_mouse_event(app, mouse_event);
-            Ok(None)
-        }
-        _ => Ok(None),
+pub(super) fn handle_event(app: &mut App, event: Event) -> Option<Command> {
+    // Any event can change what is shown, a resize included.
+    app.request_redraw();
+    match event {
+        Event::Key(key) if key.kind == KeyEventKind::Press => handle_key_event(app, key),
+        Event::Mouse(mouse_event) => handle_mouse_event(app, mouse_event),
+        _ => None,
     }
 }
 
@@ -38,81 +69,87 @@ fn handle_key_event(app: &mut App, key: KeyEvent) -> Option<Command> {
             let branch_key = branch_key.clone();
             handle_confirm_delete_key(app, key, &branch_key)
         }
-        AppMode::ErrorPopup(_) => {
+        AppMode::ConfirmDeleteMarked => handle_confirm_delete_marked_key(app, key),
+        AppMode::ErrorPopup(_) | AppMode::DeleteResults(_) => {
             handle_error_popup_key(app, key);
             None
         }
+        AppMode::Inspect(_) => {
+            handle_inspector_key(app, key);
+            None
+        }
+        AppMode::ChangeState(_) => handle_state_menu_key(app, key),
+        AppMode::Activity => handle_activity_key(app, key),
+        AppMode::PullRequests => handle_pull_request_list_key(app, key),
+        AppMode::WorkItemList => handle_work_item_list_key(app, key),
+        AppMode::CompletePullRequest(_) => handle_completion_form_key(app, key),
+        AppMode::BranchStats(_) => {
+            handle_branch_stats_key(app, key);
+            None
+        }
+        AppMode::Hierarchy(_) => handle_hierarchy_key(app, key),
     }
 }
 
 fn handle_normal_mode_key(app: &mut App, key: KeyEvent) -> Option<Command> {
-    match key.code {
-        KeyCode::Esc => {
-            if app.has_active_filter() {
+    let action = app.keymap().action(&key)?;
+    match action {
+        Action::Back => {
+            if app.has_marked_branches() {
+                app.update(Msg::ClearMarks);
+            } else if app.has_active_filter() {
                 app.update(Msg::ClearFilter);
             } else {
                 app.update(Msg::Quit);
             }
             None
         }
-        KeyCode::Char('q') => {
+        Action::Quit => {
             app.update(Msg::Quit);
             None
         }
-        KeyCode::Char('c') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
-            app.update(Msg::Quit);
+        Action::Next => {
+            app.update(Msg::NextBranch);
             None
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if key.modifiers.contains(event::KeyModifiers::SHIFT) {
-                app.update(Msg::ScrollDown(scroll::LINE_SCROLL_AMOUNT));
-            } else {
-                app.update(Msg::NextBranch);
-            }
+        Action::Previous => {
+            app.update(Msg::PreviousBranch);
             None
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if key.modifiers.contains(event::KeyModifiers::SHIFT) {
-                app.update(Msg::ScrollUp(scroll::LINE_SCROLL_AMOUNT));
-            } else {
-                app.update(Msg::PreviousBranch);
-            }
-            None
-        }
-        KeyCode::PageDown => {
-            app.update(Msg::ScrollDown(
-                app.visible_height() / scroll::PAGE_SCROLL_DIVISOR,
-            ));
+        Action::ScrollDown => {
+            app.update(Msg::ScrollDown(scroll::LINE_SCROLL_AMOUNT));
             None
         }
-        KeyCode::PageUp => {
-            app.update(Msg::ScrollUp(
-                app.visible_height() / scroll::PAGE_SCROLL_DIVISOR,
-            ));
+        Action::ScrollUp => {
+            app.update(Msg::ScrollUp(scroll::LINE_SCROLL_AMOUNT));
             None
         }
-        KeyCode::Char('d') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+        Action::PageDown => {
             app.update(Msg::ScrollDown(
                 app.visible_height() / scroll::PAGE_SCROLL_DIVISOR,
             ));
             None
         }
-        KeyCode::Char('u') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+        Action::PageUp => {
             app.update(Msg::ScrollUp(
                 app.visible_height() / scroll::PAGE_SCROLL_DIVISOR,
             ));
             None
         }
-        KeyCode::Char('d') => {
-            if let Err(error) = app.can_delete_selected() {
+        Action::Delete => {
+            if app.has_marked_branches() {
+                app.update(Msg::EnterDeleteMarkedConfirmMode);
+            } else if let Err(error) = app.can_delete_selected() {
                 app.set_status_message(error, true, timing::STATUS_DURATION_SECS);
             } else {
                 app.enter_confirm_mode();
             }
             None
         }
-        KeyCode::Char('D') => {
-            if let Err(error) = app.can_delete_selected() {
+        Action::DeleteNow => {
+            if app.has_marked_branches() {
+                Some(Command::DeleteMarked)
+            } else if let Err(error) = app.can_delete_selected() {
                 app.set_status_message(error, true, timing::STATUS_DURATION_SECS);
                 None
             } else if app.selected_branch().is_some_and(|branch| branch.is_stale) {
@@ -121,22 +158,83 @@ fn handle_normal_mode_key(app: &mut App, key: KeyEvent) -> Option<Command> {
                 app.selected_branch().cloned().map(Command::Delete)
             }
         }
-        KeyCode::Char('o') => Some(Command::OpenWorkItem),
-        KeyCode::Enter => app.selected_branch().cloned().map(Command::Checkout),
-        KeyCode::Char('t') => {
+        Action::Open => Some(Command::OpenWorkItem),
+        Action::OpenPullRequest => Some(Command::OpenPullRequest),
+        Action::UndoDelete => Some(Command::UndoDelete),
+        Action::ToggleMark => {
+            if let Err(error) = app.can_delete_selected() {
+                app.set_status_message(error, true, timing::STATUS_DURATION_SECS);
+            } else {
+                app.update(Msg::ToggleMark);
+            }
+            None
+        }
+        Action::MarkRange => {
+            app.update(Msg::MarkRange);
+            None
+        }
+        Action::Checkout => app.selected_branch().cloned().map(Command::Checkout),
+        Action::ToggleView => {
             app.update(Msg::ToggleView);
             None
         }
-        KeyCode::Char('/') => {
+        Action::Filter => {
             app.update(Msg::StartFilter);
             None
         }
-        KeyCode::Char('r') => app.selected_work_item_id().map(Command::Refresh),
-        KeyCode::Char('p') => {
+        Action::Refresh => app.selected_work_item_id().map(Command::Refresh),
+        Action::ToggleProtected => {
             app.update(Msg::ToggleShowProtected);
             None
         }
-        _ => None,
+        Action::SortByPriority => {
+            app.update(Msg::ToggleSortByPriority);
+            None
+        }
+        Action::Fetch => Some(Command::Fetch),
+        Action::FetchPrune => Some(Command::FetchPrune),
+        Action::InspectJson => {
+            let wi_id = app.selected_work_item_id()?;
+            app.update(Msg::OpenInspector(wi_id));
+            Some(Command::InspectJson(wi_id))
+        }
+        Action::ChangeState => {
+            let wi_id = app.selected_work_item_id()?;
+            app.update(Msg::OpenStateMenu(wi_id));
+            Some(Command::StateTransitions(wi_id))
+        }
+        Action::Activity => {
+            app.update(Msg::OpenActivity);
+            None
+        }
+        Action::PullRequests => {
+            app.update(Msg::OpenPullRequestList);
+            None
+        }
+        Action::WorkItems => {
+            app.update(Msg::OpenWorkItemList);
+            None
+        }
+        Action::ToggleComments => {
+            app.update(Msg::ToggleComments);
+            None
+        }
+        Action::Hierarchy => {
+            app.update(Msg::OpenHierarchy);
+            None
+        }
+        Action::CompletePullRequest => {
+            app.update(Msg::OpenCompletionForm);
+            None
+        }
+        Action::JumpToChange => {
+            app.update(Msg::JumpToToast);
+            None
+        }
+        Action::BranchStats => {
+            app.update(Msg::OpenBranchStats);
+            Some(Command::CheckMergedBranches)
+        }
     }
 }
 
@@ -176,6 +274,12 @@ fn handle_filter_input_key(app: &mut App, key: KeyEvent) -> Option<Command> {
 
 fn handle_confirm_delete_key(app: &mut App, key: KeyEvent, branch_key: &str) -> Option<Command> {
     match key.code {
+        KeyCode::Char('Y') => {
+            app.confirm_delete_remote()?;
+            let branch = app.branch_by_key(branch_key)?.clone();
+            app.cancel_mode();
+            Some(Command::DeleteWithRemote(branch))
+        }
         KeyCode::Char('y') | KeyCode::Enter => {
             let branch = app.branch_by_key(branch_key)?.clone();
             let action = if branch.is_stale {
@@ -194,6 +298,20 @@ fn handle_confirm_delete_key(app: &mut App, key: KeyEvent, branch_key: &str) ->
     }
 }
 
+fn handle_confirm_delete_marked_key(app: &mut App, key: KeyEvent) -> Option<Command> {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Enter => {
+            app.cancel_mode();
+            Some(Command::DeleteMarked)
+        }
+        KeyCode::Char('n') | KeyCode::Esc | KeyCode::Char('q') => {
+            app.cancel_mode();
+            None
+        }
+        _ => None,
+    }
+}
+
 fn handle_error_popup_key(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => app.cancel_mode(),
@@ -201,16 +319,197 @@ fn handle_error_popup_key(app: &mut App, key: KeyEvent) {
     }
 }
 
-fn handle_mouse_event(app: &mut App, mouse_event: MouseEvent) {
+/// Fixed keys of the state menu: move the highlight, pick the highlighted
+/// state, or close.
+fn handle_state_menu_key(app: &mut App, key: KeyEvent) -> Option<Command> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => app.update(Msg::StateMenuNext),
+        KeyCode::Char('k') | KeyCode::Up => app.update(Msg::StateMenuPrevious),
+        KeyCode::Enter => {
+            let (work_item_id, transition) = app.selected_state_transition()?;
+            let command = Command::SetState {
+                work_item_id,
+                state: transition.state.clone(),
+            };
+            app.cancel_mode();
+            return Some(command);
+        }
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_mode(),
+        _ => {}
+    }
+    None
+}
+
+/// Fixed keys of the completion form: move between rows, change the
+/// highlighted option, complete the pull request, or close.
+fn handle_completion_form_key(app: &mut App, key: KeyEvent) -> Option<Command> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => app.update(Msg::CompletionFormNext),
+        KeyCode::Char('k') | KeyCode::Up => app.update(Msg::CompletionFormPrevious),
+        KeyCode::Char('l') | KeyCode::Right | KeyCode::Char(' ') => {
+            app.update(Msg::ChangeCompletionOption(1))
+        }
+        KeyCode::Char('h') | KeyCode::Left => app.update(Msg::ChangeCompletionOption(-1)),
+        KeyCode::Enter => {
+            let form = app.completion_form()?;
+            let command = Command::CompletePullRequest {
+                work_item_id: form.work_item_id,
+                pull_request: form.pull_request.clone(),
+                options: form.options,
+            };
+            app.cancel_mode();
+            return Some(command);
+        }
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_mode(),
+        _ => {}
+    }
+    None
+}
+
+/// Fixed keys of the activity tab: move the highlight, open its work item,
+/// poll now, or go back to the branches.
+fn handle_activity_key(app: &mut App, key: KeyEvent) -> Option<Command> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => app.update(Msg::ActivityNext),
+        KeyCode::Char('k') | KeyCode::Up => app.update(Msg::ActivityPrevious),
+        KeyCode::Char('o') | KeyCode::Enter => {
+            return app
+                .selected_activity_work_item()
+                .map(Command::OpenActivityItem);
+        }
+        KeyCode::Char('r') => app.update(Msg::RefreshActivity),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('a') => app.cancel_mode(),
+        _ => {}
+    }
+    None
+}
+
+/// Fixed keys of the pull request tab: move the highlight, open its pull
+/// request, list again, or go back to the branches.
+fn handle_pull_request_list_key(app: &mut App, key: KeyEvent) -> Option<Command> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => app.update(Msg::PullRequestListNext),
+        KeyCode::Char('k') | KeyCode::Up => app.update(Msg::PullRequestListPrevious),
+        KeyCode::Char('o') | KeyCode::Enter => {
+            app.selected_listed_pull_request()?;
+            return Some(Command::OpenListedPullRequest);
+        }
+        KeyCode::Char('r') => app.update(Msg::RefreshPullRequestList),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Tab => app.cancel_mode(),
+        _ => {}
+    }
+    None
+}
+
+/// Fixed keys of the work item list: move the highlight, scroll its
+/// details, open it in the browser, query again, and close.
+fn handle_work_item_list_key(app: &mut App, key: KeyEvent) -> Option<Command> {
+    let page = app.visible_height() / scroll::PAGE_SCROLL_DIVISOR;
+    let ctrl = key.modifiers.contains(event::KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Char('d') if ctrl => app.update(Msg::ScrollDown(page)),
+        KeyCode::Char('u') if ctrl => app.update(Msg::ScrollUp(page)),
+        KeyCode::Char('j') | KeyCode::Down => app.update(Msg::WorkItemListNext),
+        KeyCode::Char('k') | KeyCode::Up => app.update(Msg::WorkItemListPrevious),
+        KeyCode::Char('J') => app.update(Msg::ScrollDown(scroll::LINE_SCROLL_AMOUNT)),
+        KeyCode::Char('K') => app.update(Msg::ScrollUp(scroll::LINE_SCROLL_AMOUNT)),
+        KeyCode::PageDown => app.update(Msg::ScrollDown(page)),
+        KeyCode::PageUp => app.update(Msg::ScrollUp(page)),
+        KeyCode::Char('o') | KeyCode::Enter => {
+            app.selected_listed_work_item()?;
+            return Some(Command::OpenWorkItem);
+        }
+        KeyCode::Char('r') => app.update(Msg::RefreshWorkItemList),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('w') => app.cancel_mode(),
+        _ => {}
+    }
+    None
+}
+
+/// Fixed keys of the hierarchy: move the highlight, show the highlighted
+/// work item or go back to the one before, scroll the details, open or
+/// refresh the work item shown, and close.
+fn handle_hierarchy_key(app: &mut App, key: KeyEvent) -> Option<Command> {
+    let page = app.visible_height() / scroll::PAGE_SCROLL_DIVISOR;
+    let ctrl = key.modifiers.contains(event::KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Char('d') if ctrl => app.update(Msg::ScrollDown(page)),
+        KeyCode::Char('u') if ctrl => app.update(Msg::ScrollUp(page)),
+        KeyCode::Char('j') | KeyCode::Down => app.update(Msg::HierarchyNext),
+        KeyCode::Char('k') | KeyCode::Up => app.update(Msg::HierarchyPrevious),
+        KeyCode::Char('J') => app.update(Msg::ScrollDown(scroll::LINE_SCROLL_AMOUNT)),
+        KeyCode::Char('K') => app.update(Msg::ScrollUp(scroll::LINE_SCROLL_AMOUNT)),
+        KeyCode::PageDown => app.update(Msg::ScrollDown(page)),
+        KeyCode::PageUp => app.update(Msg::ScrollUp(page)),
+        KeyCode::Enter => app.update(Msg::EnterRelatedWorkItem),
+        KeyCode::Backspace => app.update(Msg::HierarchyBack),
+        KeyCode::Char('o') => return Some(Command::OpenWorkItem),
+        KeyCode::Char('r') => return app.selected_work_item_id().map(Command::Refresh),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') => app.cancel_mode(),
+        _ => {}
+    }
+    None
+}
+
+/// Fixed keys of the statistics popup: close.
+fn handle_branch_stats_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('b') => {
+            app.cancel_mode()
+        }
+        _ => {}
+    }
+}
+
+/// Fixed keys of the JSON inspector: scroll by line, half page, or to either
+/// end, and close.
+fn handle_inspector_key(app: &mut App, key: KeyEvent) {
+    let page = usize::from((app.inspector_height() / scroll::PAGE_SCROLL_DIVISOR).max(1));
+    let ctrl = key.modifiers.contains(event::KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('i') => {
+            app.cancel_mode()
+        }
+        KeyCode::Char('d') if ctrl => app.update(Msg::InspectorScrollDown(page)),
+        KeyCode::Char('u') if ctrl => app.update(Msg::InspectorScrollUp(page)),
+        KeyCode::Char('j') | KeyCode::Down => app.update(Msg::InspectorScrollDown(1)),
+        KeyCode::Char('k') | KeyCode::Up => app.update(Msg::InspectorScrollUp(1)),
+        KeyCode::PageDown | KeyCode::Char(' ') => app.update(Msg::InspectorScrollDown(page)),
+        KeyCode::PageUp => app.update(Msg::InspectorScrollUp(page)),
+        KeyCode::Char('g') | KeyCode::Home => app.update(Msg::InspectorScrollUp(usize::MAX)),
+        KeyCode::Char('G') | KeyCode::End => app.update(Msg::InspectorScrollDown(usize::MAX)),
+        _ => {}
+    }
+}
+
+fn handle_mouse_event(app: &mut App, mouse_event: MouseEvent) -> Option<Command> {
+    if app.inspector().is_some() {
+        match mouse_event.kind {
+            MouseEventKind::ScrollDown => app.update(Msg::InspectorScrollDown(usize::from(
+                scroll::LINE_SCROLL_AMOUNT,
+            ))),
+            MouseEventKind::ScrollUp => app.update(Msg::InspectorScrollUp(usize::from(
+                scroll::LINE_SCROLL_AMOUNT,
+            ))),
+            _ => {}
+        }
+        return None;
+    }
     if !app.is_normal_mode() || app.is_editing_filter() {
-        return;
+        return None;
     }
 
     match mouse_event.kind {
         MouseEventKind::ScrollDown => app.update(Msg::ScrollDown(scroll::LINE_SCROLL_AMOUNT)),
         MouseEventKind::ScrollUp => app.update(Msg::ScrollUp(scroll::LINE_SCROLL_AMOUNT)),
+        MouseEventKind::Down(MouseButton::Left) => {
+            return app
+                .parent_link_at(mouse_event.column, mouse_event.row)
+                .map(Command::OpenParent);
+        }
         _ => {}
     }
+    None
 }
 
 #[cfg(test)]
@@ -218,8 +517,16 @@ mod tests {
     use std::collections::HashSet;
 
     use super::*;
-    use crate::git::BranchScope;
-    use crate::tui::app::{App, BranchInfo, BranchView};
+    use crate::azure_devops::{
+        ActivityEntry, MergeStrategy, PullRequest, StateTransition, WorkItem, WorkItemState,
+        WorkItemType,
+    };
+    use crate::git::{BranchScope, FixtureGitRepo, GitRepo};
+    use crate::tui::app::{
+        App, BranchInfo, BranchView, DetailsMetrics, InspectorContent, ParentLink,
+    };
+    use crossterm::event::KeyModifiers;
+    use ratatui::layout::Rect;
 
     #[test]
     fn test_confirm_delete_derives_prune_from_current_branch_state() {
@@ -292,6 +599,7 @@ mod tests {
                     is_current: false,
                     is_protected: false,
                     is_stale: false,
+                    origin_upstream: None,
                 },
                 BranchInfo {
                     key: "refs/heads/feature/beta-login".to_string(),
@@ -303,6 +611,7 @@ mod tests {
                     is_current: false,
                     is_protected: false,
                     is_stale: false,
+                    origin_upstream: None,
                 },
                 BranchInfo {
                     key: "refs/heads/chore/docs".to_string(),
@@ -314,6 +623,7 @@ mod tests {
                     is_current: false,
                     is_protected: false,
                     is_stale: false,
+                    origin_upstream: None,
                 },
             ],
             vec![],
@@ -367,6 +677,7 @@ mod tests {
                 is_current: true,
                 is_protected: true,
                 is_stale: false,
+                origin_upstream: None,
             }],
             vec![],
         );
@@ -396,6 +707,371 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_clicking_the_parent_line_opens_the_parent() {
+        let mut app = App::new(vec![remote_branch(false)], vec![]);
+        app.update(Msg::SetDetailsMetrics(DetailsMetrics {
+            parent_link: Some(ParentLink {
+                work_item_id: 7,
+                area: Rect::new(40, 5, 30, 1),
+            }),
+            ..DetailsMetrics::default()
+        }));
+        let click = |column, row| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        assert!(matches!(
+            handle_mouse_event(&mut app, click(45, 5)),
+            Some(Command::OpenParent(7))
+        ));
+        assert!(handle_mouse_event(&mut app, click(45, 6)).is_none());
+    }
+
+    #[test]
+    fn test_inspector_opens_on_the_selected_work_item_and_scrolls() {
+        let mut branch = remote_branch(false);
+        branch.work_item_id = Some(7);
+        let mut app = App::new(vec![branch], vec![]);
+        app.update(Msg::ToggleView);
+        let press = |app: &mut App, code| handle_key_event(app, KeyEvent::from(code));
+
+        assert!(matches!(
+            press(&mut app, KeyCode::Char('i')),
+            Some(Command::InspectJson(7))
+        ));
+        app.set_inspector_json(8, Ok("{}".to_string()));
+        assert!(matches!(
+            app.inspector().map(|inspector| &inspector.content),
+            Some(InspectorContent::Loading)
+        ));
+
+        app.set_inspector_json(7, Ok("{\n  \"id\": 7,\n  \"rev\": 2\n}".to_string()));
+        app.update(Msg::SetDetailsMetrics(DetailsMetrics {
+            inspector_height: 2,
+            ..DetailsMetrics::default()
+        }));
+        let scroll = |app: &App| app.inspector().map(|inspector| inspector.scroll);
+        press(&mut app, KeyCode::Char('G'));
+        assert_eq!(scroll(&app), Some(2));
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(scroll(&app), Some(2));
+        press(&mut app, KeyCode::Char('k'));
+        assert_eq!(scroll(&app), Some(1));
+
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.inspector().is_none());
+        assert!(!app.should_quit());
+    }
+
+    #[test]
+    fn test_state_menu_picks_the_highlighted_transition() {
+        let mut branch = remote_branch(false);
+        branch.work_item_id = Some(7);
+        let mut app = App::new(vec![branch], vec![]);
+        app.update(Msg::ToggleView);
+        let press = |app: &mut App, code| handle_key_event(app, KeyEvent::from(code));
+
+        assert!(matches!(
+            press(&mut app, KeyCode::Char('S')),
+            Some(Command::StateTransitions(7))
+        ));
+        assert!(press(&mut app, KeyCode::Enter).is_none());
+        app.set_state_transitions(
+            7,
+            Ok(["Active", "Closed"]
+                .map(|state| StateTransition {
+                    state: state.to_string(),
+                    category: "InProgress".to_string(),
+                })
+                .to_vec()),
+        );
+
+        press(&mut app, KeyCode::Char('j'));
+        match press(&mut app, KeyCode::Enter) {
+            Some(Command::SetState {
+                work_item_id,
+                state,
+            }) => assert_eq!((work_item_id, state.as_str()), (7, "Closed")),
+            _ => panic!("expected a state change"),
+        }
+        assert!(app.state_menu().is_none());
+    }
+
+    #[test]
+    fn test_completion_form_completes_the_selected_branchs_pull_request() {
+        let mut branch = remote_branch(false);
+        branch.work_item_id = Some(7);
+        let mut app = App::new(vec![branch], vec![]);
+        app.update(Msg::ToggleView);
+        let press = |app: &mut App, code| handle_key_event(app, KeyEvent::from(code));
+        let pull_request = |id: u32, status: &str, source_branch: &str| PullRequest {
+            id,
+            title: format!("PR {id}"),
+            status: status.to_string(),
+            is_draft: false,
+            source_branch: source_branch.to_string(),
+            target_branch: "main".to_string(),
+            created_by: None,
+            project_id: None,
+            repository_id: None,
+            last_merge_source_commit: None,
+            reviewers: vec![],
+            url: None,
+        };
+
+        app.set_work_item_loaded(
+            7,
+            WorkItem {
+                id: 7,
+                title: "Checkout".to_string(),
+                work_item_type: WorkItemType::Task,
+                state: WorkItemState::Active,
+                assigned_to: None,
+                url: None,
+                tags: vec![],
+                rich_text_fields: vec![],
+                fields: Default::default(),
+            },
+        );
+        app.set_pull_requests(7, Ok(vec![pull_request(30, "completed", "feature/1")]));
+        assert!(press(&mut app, KeyCode::Char('C')).is_none());
+        assert!(app.completion_form().is_none());
+        assert!(
+            app.get_status_message()
+                .is_some_and(|message| message.is_error)
+        );
+
+        app.set_pull_requests(
+            7,
+            Ok(vec![
+                pull_request(31, "active", "feature/other"),
+                pull_request(32, "active", "feature/1"),
+            ]),
+        );
+        press(&mut app, KeyCode::Char('C'));
+        assert_eq!(
+            app.completion_form().map(|form| form.pull_request.id),
+            Some(32)
+        );
+        press(&mut app, KeyCode::Char('l'));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char(' '));
+        match press(&mut app, KeyCode::Enter) {
+            Some(Command::CompletePullRequest {
+                work_item_id,
+                pull_request,
+                options,
+            }) => {
+                assert_eq!((work_item_id, pull_request.id), (7, 32));
+                assert_eq!(
+                    options,
+                    CompletionOptions {
+                        merge_strategy: MergeStrategy::Squash,
+                        delete_source_branch: true,
+                        complete_work_items: true,
+                    }
+                );
+            }
+            _ => panic!("expected a pull request completion"),
+        }
+        assert!(app.completion_form().is_none());
+    }
+
+    #[test]
+    fn test_activity_tab_opens_the_highlighted_work_item() {
+        let mut app = App::new(vec![remote_branch(false)], vec![]);
+        let press = |app: &mut App, code| handle_key_event(app, KeyEvent::from(code));
+
+        assert!(press(&mut app, KeyCode::Char('a')).is_none());
+        assert!(app.is_activity_open());
+        assert!(press(&mut app, KeyCode::Enter).is_none());
+
+        let entries = [4, 9]
+            .map(|id| ActivityEntry {
+                work_item: WorkItem {
+                    id,
+                    title: "Changed".to_string(),
+                    work_item_type: WorkItemType::Task,
+                    state: WorkItemState::Active,
+                    assigned_to: None,
+                    url: None,
+                    tags: vec![],
+                    rich_text_fields: vec![],
+                    fields: Default::default(),
+                },
+                changes: vec![],
+            })
+            .to_vec();
+        app.set_activity(Ok(entries));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('j'));
+        assert!(matches!(
+            press(&mut app, KeyCode::Char('o')),
+            Some(Command::OpenActivityItem(9))
+        ));
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.is_activity_open());
+        assert!(!app.should_quit());
+    }
+
+    #[test]
+    fn test_tab_switches_between_branches_and_pull_requests() {
+        let mut app = App::new(vec![remote_branch(false)], vec![]);
+        let press = |app: &mut App, code| handle_key_event(app, KeyEvent::from(code));
+
+        assert!(press(&mut app, KeyCode::Tab).is_none());
+        assert!(app.is_pull_request_list_open());
+        assert!(app.pull_request_list_due());
+        assert!(press(&mut app, KeyCode::Enter).is_none());
+
+        let pull_requests = [12, 15]
+            .map(|id| PullRequest {
+                id,
+                title: format!("PR {id}"),
+                status: "active".to_string(),
+                is_draft: false,
+                source_branch: format!("feature/{id}"),
+                target_branch: "main".to_string(),
+                created_by: None,
+                project_id: None,
+                repository_id: None,
+                last_merge_source_commit: None,
+                reviewers: vec![],
+                url: Some(format!(
+                    "https://dev.azure.com/org/p/_git/r/pullrequest/{id}"
+                )),
+            })
+            .to_vec();
+        app.set_pull_request_list(Ok(pull_requests));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('j'));
+        assert!(matches!(
+            press(&mut app, KeyCode::Enter),
+            Some(Command::OpenListedPullRequest)
+        ));
+        assert_eq!(app.selected_listed_pull_request().map(|pr| pr.id), Some(15));
+
+        press(&mut app, KeyCode::Tab);
+        assert!(app.is_normal_mode());
+        press(&mut app, KeyCode::Tab);
+        assert!(app.pull_request_list_due());
+        assert_eq!(app.pull_request_list().entries.len(), 2);
+    }
+
+    #[test]
+    fn test_jump_to_change_selects_the_toasted_work_items_branch() {
+        let mut branch = remote_branch(false);
+        branch.work_item_id = Some(7);
+        let mut app = App::new(vec![branch], vec![]);
+        let revision = |rev: u32, state: &str| WorkItem {
+            id: 7,
+            title: "Checkout".to_string(),
+            work_item_type: WorkItemType::Task,
+            state: state.parse().unwrap(),
+            assigned_to: None,
+            url: None,
+            tags: vec![],
+            rich_text_fields: vec![],
+            fields: [
+                ("System.Rev".to_string(), rev.to_string()),
+                ("System.ChangedBy".to_string(), "Alice".to_string()),
+            ]
+            .into(),
+        };
+        app.set_work_item_loaded(7, revision(3, "Active"));
+
+        app.update(Msg::ChangesChecked(vec![revision(3, "Active")]));
+        assert_eq!(app.toasts().count(), 0);
+        app.update(Msg::ChangesChecked(vec![revision(4, "Resolved")]));
+        let texts: Vec<&str> = app.toasts().map(|toast| toast.text.as_str()).collect();
+        assert_eq!(texts, ["#7 moved to Resolved by Alice"]);
+
+        assert!(handle_key_event(&mut app, KeyEvent::from(KeyCode::Char('n'))).is_none());
+        assert_eq!(app.active_view(), BranchView::Remote);
+        assert_eq!(app.selected_work_item_id(), Some(7));
+        assert_eq!(app.toasts().count(), 0);
+    }
+
+    #[test]
+    fn test_branch_stats_opens_with_a_merged_branch_check_and_closes() {
+        let mut branch = remote_branch(false);
+        branch.key = "refs/heads/feature/1".to_string();
+        branch.display_name = "feature/1".to_string();
+        branch.remote_name = None;
+        branch.scope = BranchScope::Local;
+        let mut app = App::new(vec![branch], vec![]);
+        let git_repo = GitRepo::fixture(
+            FixtureGitRepo::new()
+                .with_local_branch("main", true)
+                .with_local_branch("feature/1", false)
+                .with_merged_branch("feature/1"),
+        );
+
+        let command = handle_key_event(&mut app, KeyEvent::from(KeyCode::Char('b')));
+        assert!(matches!(command, Some(Command::CheckMergedBranches)));
+        assert_eq!(app.branch_stats(0).unwrap().cleanup.merged, None);
+
+        app.set_merged_branches(
+            git_repo
+                .merged_branches()
+                .map_err(|error| error.to_string()),
+        );
+        let stats = app.branch_stats(0).unwrap();
+        assert_eq!(stats.local_branches, 1);
+        assert_eq!(stats.cleanup.merged, Some(1));
+        assert_eq!(stats.cleanup.total, 1);
+
+        handle_key_event(&mut app, KeyEvent::from(KeyCode::Esc));
+        assert!(app.is_normal_mode());
+        assert!(app.branch_stats(0).is_none());
+    }
+
+    #[test]
+    fn test_hierarchy_keys_step_into_a_child_and_back() {
+        let mut branch = remote_branch(false);
+        branch.work_item_id = Some(7);
+        let mut app = App::new(vec![branch], vec![]);
+        app.update(Msg::ToggleView);
+        let child = WorkItem {
+            id: 12,
+            title: "Payment form".to_string(),
+            work_item_type: WorkItemType::Task,
+            state: WorkItemState::New,
+            assigned_to: None,
+            url: None,
+            tags: vec![],
+            rich_text_fields: vec![],
+            fields: Default::default(),
+        };
+        app.set_work_item_loaded(
+            7,
+            WorkItem {
+                id: 7,
+                ..child.clone()
+            },
+        );
+        app.set_children(7, Ok(vec![child]));
+        let press = |app: &mut App, code| handle_key_event(app, KeyEvent::from(code));
+
+        press(&mut app, KeyCode::Char('h'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.selected_work_item_id(), Some(12));
+        assert!(matches!(
+            press(&mut app, KeyCode::Char('r')),
+            Some(Command::Refresh(12))
+        ));
+
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.selected_work_item_id(), Some(7));
+        press(&mut app, KeyCode::Esc);
+        assert!(app.is_normal_mode());
+    }
+
     fn remote_branch(is_stale: bool) -> BranchInfo {
         BranchInfo {
             key: "refs/remotes/origin/feature/1".to_string(),
@@ -407,6 +1083,7 @@ mod tests {
             is_current: false,
             is_protected: false,
             is_stale,
+            origin_upstream: None,
         }
     }
 }
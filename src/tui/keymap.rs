@@ -0,0 +1,456 @@
+//! Key bindings for the branch list, with overrides from `[keys]`.
+//!
+//! Each action has default chords; an entry in `[keys]` replaces the chords of
+//! one action. The merged map is checked before the TUI starts, so a typo or
+//! a key bound to two actions fails with an explanation instead of one of the
+//! actions silently winning.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use anyhow::{Result, bail};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::KeyBinding;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Back,
+    Next,
+    Previous,
+    ScrollDown,
+    ScrollUp,
+    PageDown,
+    PageUp,
+    Delete,
+    DeleteNow,
+    UndoDelete,
+    ToggleMark,
+    MarkRange,
+    Open,
+    OpenPullRequest,
+    Checkout,
+    ToggleView,
+    Filter,
+    Refresh,
+    ToggleProtected,
+    SortByPriority,
+    Fetch,
+    FetchPrune,
+    InspectJson,
+    ChangeState,
+    CompletePullRequest,
+    Activity,
+    PullRequests,
+    WorkItems,
+    ToggleComments,
+    Hierarchy,
+    JumpToChange,
+    BranchStats,
+}
+
+/// Config name and default chords of each action. The first chord is the one
+/// shown in the footer.
+const ACTIONS: &[(Action, &str, &[&str])] = &[
+    (Action::Quit, "quit", &["q", "ctrl-c"]),
+    (Action::Back, "back", &["esc"]),
+    (Action::Next, "next", &["j", "down"]),
+    (Action::Previous, "previous", &["k", "up"]),
+    (
+        Action::ScrollDown,
+        "scroll_down",
+        &["shift-j", "shift-down"],
+    ),
+    (Action::ScrollUp, "scroll_up", &["shift-k", "shift-up"]),
+    (Action::PageDown, "page_down", &["pagedown", "ctrl-d"]),
+    (Action::PageUp, "page_up", &["pageup", "ctrl-u"]),
+    (Action::Delete, "delete", &["d"]),
+    (Action::DeleteNow, "delete_now", &["shift-d"]),
+    (Action::UndoDelete, "undo_delete", &["u"]),
+    (Action::ToggleMark, "toggle_mark", &["space"]),
+    (Action::MarkRange, "mark_range", &["shift-v"]),
+    (Action::Open, "open", &["o"]),
+    (Action::OpenPullRequest, "open_pull_request", &["shift-o"]),
+    (Action::Checkout, "checkout", &["enter"]),
+    (Action::ToggleView, "toggle_view", &["t"]),
+    (Action::Filter, "filter", &["/"]),
+    (Action::Refresh, "refresh", &["r"]),
+    (Action::ToggleProtected, "toggle_protected", &["p"]),
+    (Action::SortByPriority, "sort_priority", &["s"]),
+    (Action::Fetch, "fetch", &["f"]),
+    (Action::FetchPrune, "fetch_prune", &["shift-f"]),
+    (Action::InspectJson, "inspect_json", &["i"]),
+    (Action::ChangeState, "change_state", &["shift-s"]),
+    (
+        Action::CompletePullRequest,
+        "complete_pull_request",
+        &["shift-c"],
+    ),
+    (Action::Activity, "activity", &["a"]),
+    (Action::PullRequests, "pull_requests", &["tab"]),
+    (Action::WorkItems, "work_items", &["w"]),
+    (Action::ToggleComments, "toggle_comments", &["c"]),
+    (Action::Hierarchy, "hierarchy", &["h"]),
+    (Action::JumpToChange, "jump_to_change", &["n"]),
+    (Action::BranchStats, "branch_stats", &["b"]),
+];
+
+const NAMED_KEYS: &[(&str, KeyCode)] = &[
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("space", KeyCode::Char(' ')),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+];
+
+/// A key with modifiers, e.g. `ctrl-d`. Shifted letters are stored as the
+/// uppercase letter, which is what terminals report for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, mut modifiers: KeyModifiers) -> Self {
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) && !c.is_whitespace() => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    /// Parse `[ctrl-][alt-][shift-]<key>`, where `<key>` is a single
+    /// character, a named key like `enter` or `pagedown`, or `f1`–`f12`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text.trim();
+        if rest.is_empty() {
+            bail!("empty key");
+        }
+        while let Some((prefix, key)) = rest.split_once('-') {
+            let modifier = match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ if rest.chars().count() == 1 => break,
+                _ => bail!("unknown modifier '{prefix}' (use ctrl, alt, or shift)"),
+            };
+            if key.is_empty() {
+                bail!("missing key after '{prefix}-'");
+            }
+            modifiers.insert(modifier);
+            rest = key;
+        }
+
+        let code = if rest.chars().count() == 1 {
+            KeyCode::Char(rest.chars().next().unwrap_or_default())
+        } else {
+            let name = rest.to_ascii_lowercase();
+            match NAMED_KEYS.iter().find(|(known, _)| *known == name) {
+                Some((_, code)) => *code,
+                None => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => bail!(
+                        "unknown key '{rest}' (use a single character, f1-f12, or one of: {})",
+                        NAMED_KEYS
+                            .iter()
+                            .map(|(name, _)| *name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                },
+            }
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+
+    fn from_event(key: &KeyEvent) -> Self {
+        Self::new(
+            key.code,
+            key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT),
+        )
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            f.write_str("shift-")?;
+        }
+        match self.code {
+            KeyCode::F(n) => write!(f, "f{n}"),
+            code => match NAMED_KEYS.iter().find(|(_, known)| *known == code) {
+                Some((name, _)) => f.write_str(name),
+                None => match code {
+                    KeyCode::Char(c) => write!(f, "{c}"),
+                    code => write!(f, "{code:?}"),
+                },
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<KeyChord, Action>,
+    labels: HashMap<Action, String>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new()).expect("default key bindings are valid")
+    }
+}
+
+impl Keymap {
+    /// The default bindings with `[keys]` applied, or an error listing the
+    /// keys that ended up bound to more than one action.
+    pub fn from_config(keys: &BTreeMap<String, KeyBinding>) -> Result<Self> {
+        if let Some(unknown) = keys
+            .keys()
+            .find(|name| !ACTIONS.iter().any(|(_, known, _)| known == name))
+        {
+            bail!(
+                "Unknown action '{}' in [keys]. Actions: {}",
+                unknown,
+                ACTIONS
+                    .iter()
+                    .map(|(_, name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+
+        let mut chords_by_key: Vec<(KeyChord, Vec<(&str, bool)>)> = Vec::new();
+        let mut labels = HashMap::new();
+        for (action, name, defaults) in ACTIONS {
+            let configured = keys.get(*name);
+            let chords: Vec<&str> = match configured {
+                Some(binding) => binding.chords().iter().map(String::as_str).collect(),
+                None => defaults.to_vec(),
+            };
+            for (index, text) in chords.iter().enumerate() {
+                let chord = KeyChord::parse(text).map_err(|error| {
+                    anyhow::anyhow!("Invalid key '{text}' for [keys].{name}: {error}")
+                })?;
+                if index == 0 {
+                    labels.insert(*action, chord.to_string());
+                }
+                match chords_by_key.iter_mut().find(|(known, _)| *known == chord) {
+                    Some((_, actions)) => {
+                        if !actions.iter().any(|(known, _)| known == name) {
+                            actions.push((name, configured.is_none()));
+                        }
+                    }
+                    None => chords_by_key.push((chord, vec![(name, configured.is_none())])),
+                }
+            }
+        }
+
+        let conflicts: Vec<_> = chords_by_key
+            .iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .collect();
+        if !conflicts.is_empty() {
+            bail!("{}", conflict_table(&conflicts));
+        }
+
+        let bindings: HashMap<KeyChord, Action> = chords_by_key
+            .into_iter()
+            .map(|(chord, actions)| (chord, action_named(actions[0].0)))
+            .collect();
+        if !bindings
+            .values()
+            .any(|action| matches!(action, Action::Quit | Action::Back))
+        {
+            bail!("[keys] leaves no key to quit; bind at least one key to 'quit' or 'back'");
+        }
+
+        Ok(Self { bindings, labels })
+    }
+
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings.get(&KeyChord::from_event(key)).copied()
+    }
+
+    /// The first chord of `action` for key hints, or `None` if it is unbound.
+    pub fn label(&self, action: Action) -> Option<&str> {
+        self.labels.get(&action).map(String::as_str)
+    }
+}
+
+fn action_named(name: &str) -> Action {
+    ACTIONS
+        .iter()
+        .find(|(_, known, _)| *known == name)
+        .map(|(action, _, _)| *action)
+        .expect("action names come from ACTIONS")
+}
+
+fn conflict_table(conflicts: &[&(KeyChord, Vec<(&str, bool)>)]) -> String {
+    let rows: Vec<(String, String)> = conflicts
+        .iter()
+        .map(|(chord, actions)| {
+            let actions = actions
+                .iter()
+                .map(|(name, is_default)| {
+                    if *is_default {
+                        format!("{name} (default)")
+                    } else {
+                        name.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            (chord.to_string(), actions)
+        })
+        .collect();
+    let width = rows
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("KEY".len());
+
+    let mut table = String::from("Conflicting key bindings in [keys]:\n\n");
+    table.push_str(&format!("  {:width$}  ACTIONS\n", "KEY"));
+    for (key, actions) in rows {
+        table.push_str(&format!("  {key:width$}  {actions}\n"));
+    }
+    table.push_str(
+        "\nBind each key to one action; set an action listed as (default) in [keys] to move it.",
+    );
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(entries: &[(&str, &[&str])]) -> BTreeMap<String, KeyBinding> {
+        entries
+            .iter()
+            .map(|(name, chords)| {
+                (
+                    name.to_string(),
+                    KeyBinding::Many(chords.iter().map(|c| c.to_string()).collect()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_chords_and_normalizes_shifted_letters() {
+        assert_eq!(
+            KeyChord::parse("ctrl-d").unwrap(),
+            KeyChord::new(KeyCode::Char('d'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            KeyChord::parse("shift-d").unwrap(),
+            KeyChord::parse("D").unwrap()
+        );
+        assert_eq!(KeyChord::parse("-").unwrap().code, KeyCode::Char('-'));
+        assert_eq!(KeyChord::parse("F5").unwrap().code, KeyCode::F(5));
+        assert_eq!(
+            KeyChord::parse("alt-pagedown").unwrap().to_string(),
+            "alt-pagedown"
+        );
+
+        assert!(KeyChord::parse("ctlr-d").is_err());
+        assert!(KeyChord::parse("ctrl-").is_err());
+        assert!(KeyChord::parse("enterr").is_err());
+        assert!(KeyChord::parse("f13").is_err());
+    }
+
+    #[test]
+    fn matches_events_with_exact_modifiers() {
+        let keymap = Keymap::default();
+
+        assert_eq!(
+            keymap.action(&KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE)),
+            Some(Action::Delete)
+        );
+        assert_eq!(
+            keymap.action(&KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            Some(Action::PageDown)
+        );
+        assert_eq!(
+            keymap.action(&KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT)),
+            Some(Action::DeleteNow)
+        );
+        assert_eq!(
+            keymap.action(&KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT)),
+            Some(Action::ScrollDown)
+        );
+    }
+
+    #[test]
+    fn config_replaces_default_chords_of_an_action() {
+        let keymap = Keymap::from_config(&keys(&[("open", &["x", "ctrl-o"])])).unwrap();
+
+        assert_eq!(
+            keymap.action(&KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)),
+            Some(Action::Open)
+        );
+        assert_eq!(
+            keymap.action(&KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(keymap.label(Action::Open), Some("x"));
+    }
+
+    #[test]
+    fn reports_conflicts_as_a_table() {
+        let error = Keymap::from_config(&keys(&[("open", &["d"]), ("filter", &["ctrl-d"])]))
+            .expect_err("conflicting keys");
+
+        assert_eq!(
+            error.to_string(),
+            "Conflicting key bindings in [keys]:\n\n  \
+             KEY     ACTIONS\n  \
+             ctrl-d  page_down (default), filter\n  \
+             d       delete (default), open\n\n\
+             Bind each key to one action; set an action listed as (default) in [keys] to move it."
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_actions_invalid_chords_and_no_way_to_quit() {
+        let error = Keymap::from_config(&keys(&[("opne", &["x"])])).expect_err("typo");
+        assert!(
+            error
+                .to_string()
+                .starts_with("Unknown action 'opne' in [keys]")
+        );
+
+        let error = Keymap::from_config(&keys(&[("open", &["ctrl-"])])).expect_err("syntax");
+        assert_eq!(
+            error.to_string(),
+            "Invalid key 'ctrl-' for [keys].open: missing key after 'ctrl-'"
+        );
+
+        assert!(Keymap::from_config(&keys(&[("quit", &[]), ("back", &[])])).is_err());
+    }
+}
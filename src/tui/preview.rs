@@ -0,0 +1,184 @@
+//! `cazdo theme preview`: the TUI drawn once with sample data and printed as
+//! ANSI text, followed by a swatch of every theme style.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use crossterm::Command;
+use crossterm::style::{Attribute, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Span;
+
+use super::app::{App, BranchInfo};
+use super::theme;
+use super::ui;
+use crate::azure_devops::{FieldFormat, RichTextField, WorkItem, WorkItemState, WorkItemType};
+use crate::git::{BranchScope, BranchStatus, RemoteStatus};
+
+const ATTRIBUTES: &[(Modifier, Attribute)] = &[
+    (Modifier::BOLD, Attribute::Bold),
+    (Modifier::DIM, Attribute::Dim),
+    (Modifier::ITALIC, Attribute::Italic),
+    (Modifier::UNDERLINED, Attribute::Underlined),
+    (Modifier::SLOW_BLINK, Attribute::SlowBlink),
+    (Modifier::RAPID_BLINK, Attribute::RapidBlink),
+    (Modifier::REVERSED, Attribute::Reverse),
+    (Modifier::HIDDEN, Attribute::Hidden),
+    (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+];
+
+/// The sample screen at `width` x `height` plus the style swatch, with ANSI
+/// styling when `color` is set.
+pub fn render_preview(width: u16, height: u16, color: bool) -> Result<String> {
+    let app = sample_app();
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|frame| {
+        ui::render(frame, &app);
+    })?;
+
+    let mut out = buffer_text(terminal.backend().buffer(), color);
+    out.push('\n');
+    for (name, style) in theme::current().entries() {
+        let _ = write!(out, "  {name:<20}");
+        push_styled(&mut out, "The quick brown fox", style, color);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn sample_app() -> App {
+    let branch = |name: &str, work_item_id: Option<u32>, is_current: bool| BranchInfo {
+        key: format!("refs/heads/{name}"),
+        display_name: name.to_string(),
+        branch_name: name.to_string(),
+        remote_name: None,
+        scope: BranchScope::Local,
+        work_item_id,
+        is_current,
+        is_protected: false,
+        is_stale: false,
+        origin_upstream: None,
+    };
+    let mut app = App::new(
+        vec![
+            branch("feature/101-login-timeout", Some(101), true),
+            branch("bugfix/102-missing-avatar", Some(102), false),
+            branch("chore/update-dependencies", None, false),
+        ],
+        vec![],
+    )
+    .with_emoji(false);
+
+    app.set_work_item_loaded(
+        101,
+        WorkItem {
+            id: 101,
+            title: "Session times out during login".to_string(),
+            work_item_type: WorkItemType::Bug,
+            state: WorkItemState::Active,
+            assigned_to: Some("Ada Lovelace".to_string()),
+            url: None,
+            tags: vec!["auth".to_string(), "web".to_string()],
+            rich_text_fields: vec![RichTextField {
+                name: "Repro Steps".to_string(),
+                value:
+                    "1. Open the login page\n2. Wait two minutes\n3. Submit **valid** credentials"
+                        .to_string(),
+                format: FieldFormat::Markdown,
+            }],
+            fields: BTreeMap::new(),
+        },
+    );
+    app.set_branch_status(
+        "refs/heads/feature/101-login-timeout".to_string(),
+        BranchStatus {
+            remote_status: RemoteStatus::Ahead(2),
+            last_commit_sha: Some("3f2c9a1".to_string()),
+            last_commit_author: Some("Ada Lovelace".to_string()),
+            last_commit_time: None,
+        },
+    );
+    app
+}
+
+fn buffer_text(buffer: &Buffer, color: bool) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut style = None;
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            skip = Span::raw(cell.symbol()).width().saturating_sub(1);
+            if color && style != Some(cell.style()) {
+                style = Some(cell.style());
+                push_sgr(&mut out, cell.style());
+            }
+            out.push_str(cell.symbol());
+        }
+        if color {
+            push_reset(&mut out);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn push_styled(out: &mut String, text: &str, style: Style, color: bool) {
+    if color {
+        push_sgr(out, style);
+        out.push_str(text);
+        push_reset(out);
+    } else {
+        out.push_str(text);
+    }
+}
+
+fn push_sgr(out: &mut String, style: Style) {
+    push_reset(out);
+    if let Some(fg) = style.fg {
+        let _ = SetForegroundColor(fg.into()).write_ansi(out);
+    }
+    if let Some(bg) = style.bg {
+        let _ = SetBackgroundColor(bg.into()).write_ansi(out);
+    }
+    for (modifier, attribute) in ATTRIBUTES {
+        if style.add_modifier.contains(*modifier) {
+            let _ = SetAttribute(*attribute).write_ansi(out);
+        }
+    }
+}
+
+fn push_reset(out: &mut String) {
+    let _ = SetAttribute(Attribute::Reset).write_ansi(out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_preview_shows_sample_screen_and_every_style() {
+        let preview = render_preview(100, 20, false).unwrap();
+
+        assert!(preview.contains("feature/101-login-timeout"));
+        assert!(preview.contains("Session times out during login"));
+        assert!(preview.contains("  ui.selected         The quick brown fox"));
+        assert!(!preview.contains('\x1b'));
+    }
+
+    #[test]
+    fn colored_preview_emits_styles() {
+        let preview = render_preview(100, 20, true).unwrap();
+
+        assert!(preview.contains("\x1b[38;5;6m") || preview.contains("\x1b[36m"));
+    }
+}
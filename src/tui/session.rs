@@ -0,0 +1,225 @@
+//! What a TUI session did, summarized on quit: the branches checked out, the
+//! work items viewed, the state changes made, and the requests sent, for
+//! reconstructing a timesheet or debugging a session. `--session-summary`
+//! also writes the summary to a file as JSON.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+
+use crate::i18n::{Text, t, tf};
+use crate::timestamp::format_clock;
+
+/// Events of a session, each in the order it happened.
+#[derive(Debug, Clone)]
+pub struct SessionLog {
+    started_at: DateTime<Utc>,
+    checkouts: Vec<Checkout>,
+    /// Each work item once, when it was first viewed
+    viewed: Vec<ViewedWorkItem>,
+    state_changes: Vec<StateChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkout {
+    pub branch: String,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewedWorkItem {
+    pub id: u32,
+    pub title: String,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChange {
+    pub work_item_id: u32,
+    /// The state before, if the work item was loaded
+    pub from: Option<String>,
+    pub to: String,
+    pub at: DateTime<Utc>,
+}
+
+impl SessionLog {
+    pub fn new(started_at: DateTime<Utc>) -> Self {
+        Self {
+            started_at,
+            checkouts: Vec::new(),
+            viewed: Vec::new(),
+            state_changes: Vec::new(),
+        }
+    }
+
+    pub fn record_checkout(&mut self, branch: String, at: DateTime<Utc>) {
+        self.checkouts.push(Checkout { branch, at });
+    }
+
+    pub fn has_viewed(&self, id: u32) -> bool {
+        self.viewed.iter().any(|viewed| viewed.id == id)
+    }
+
+    /// Record the first view of work item `id`; later ones are ignored.
+    pub fn record_view(&mut self, id: u32, title: String, at: DateTime<Utc>) {
+        if !self.has_viewed(id) {
+            self.viewed.push(ViewedWorkItem { id, title, at });
+        }
+    }
+
+    pub fn record_state_change(&mut self, change: StateChange) {
+        self.state_changes.push(change);
+    }
+
+    /// The summary printed on quit, one line each, ending at `ended_at`
+    /// after `requests_sent` requests.
+    pub fn summary_lines(&self, ended_at: DateTime<Utc>, requests_sent: usize) -> Vec<String> {
+        let mut lines = vec![tf(
+            Text::SessionSummary,
+            &[
+                ("start", &format_clock(self.started_at)),
+                ("end", &format_clock(ended_at)),
+                ("duration", &format_duration(ended_at - self.started_at)),
+            ],
+        )];
+        let mut section = |heading: Text, entries: Vec<String>| {
+            if !entries.is_empty() {
+                lines.push(format!("  {}", t(heading)));
+                lines.extend(entries.into_iter().map(|entry| format!("    {entry}")));
+            }
+        };
+        section(
+            Text::SessionCheckedOut,
+            self.checkouts
+                .iter()
+                .map(|checkout| format!("{} {}", format_clock(checkout.at), checkout.branch))
+                .collect(),
+        );
+        section(
+            Text::SessionViewed,
+            self.viewed
+                .iter()
+                .map(|viewed| {
+                    format!(
+                        "{} #{} {}",
+                        format_clock(viewed.at),
+                        viewed.id,
+                        viewed.title
+                    )
+                })
+                .collect(),
+        );
+        section(
+            Text::SessionStateChanges,
+            self.state_changes
+                .iter()
+                .map(|change| {
+                    let states = match &change.from {
+                        Some(from) => format!("{from} -> {}", change.to),
+                        None => change.to.clone(),
+                    };
+                    format!(
+                        "{} #{} {states}",
+                        format_clock(change.at),
+                        change.work_item_id
+                    )
+                })
+                .collect(),
+        );
+        lines.push(format!(
+            "  {}",
+            tf(
+                Text::SessionRequests,
+                &[("count", &requests_sent.to_string())]
+            )
+        ));
+        lines
+    }
+
+    /// The summary as JSON, with the branches deleted in the session.
+    pub fn summary_json(
+        &self,
+        ended_at: DateTime<Utc>,
+        requests_sent: usize,
+        deleted_branches: &[&str],
+    ) -> Value {
+        json!({
+            "started_at": self.started_at.to_rfc3339(),
+            "ended_at": ended_at.to_rfc3339(),
+            "branches_checked_out": self.checkouts.iter().map(|checkout| json!({
+                "branch": checkout.branch,
+                "at": checkout.at.to_rfc3339(),
+            })).collect::<Vec<_>>(),
+            "work_items_viewed": self.viewed.iter().map(|viewed| json!({
+                "id": viewed.id,
+                "title": viewed.title,
+                "first_viewed_at": viewed.at.to_rfc3339(),
+            })).collect::<Vec<_>>(),
+            "state_changes": self.state_changes.iter().map(|change| json!({
+                "id": change.work_item_id,
+                "from": change.from,
+                "to": change.to,
+                "at": change.at.to_rfc3339(),
+            })).collect::<Vec<_>>(),
+            "branches_deleted": deleted_branches,
+            "api_requests": requests_sent,
+        })
+    }
+}
+
+/// Write `summary` to `path` as pretty-printed JSON.
+pub fn write_summary(path: &Path, summary: &Value) -> Result<()> {
+    let content = serde_json::to_string_pretty(summary).context("Failed to serialize summary")?;
+    fs::write(path, content + "\n").with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// `1h 05m`, or `12m` under an hour.
+fn format_duration(duration: chrono::TimeDelta) -> String {
+    let minutes = duration.num_minutes().max(0);
+    match minutes / 60 {
+        0 => format!("{minutes}m"),
+        hours => format!("{hours}h {:02}m", minutes % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn summary_lists_the_session_events_in_order() {
+        let at = |minute: u32| Utc.with_ymd_and_hms(2026, 10, 15, 9, minute, 0).unwrap();
+        let mut log = SessionLog::new(at(0));
+        log.record_view(1234, "Login page".to_string(), at(2));
+        log.record_checkout("feature/1234-login".to_string(), at(5));
+        log.record_view(1234, "Login page".to_string(), at(30));
+        log.record_state_change(StateChange {
+            work_item_id: 1234,
+            from: Some("Active".to_string()),
+            to: "Resolved".to_string(),
+            at: at(40),
+        });
+
+        let lines = log.summary_lines(at(0) + chrono::TimeDelta::minutes(65), 37);
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].ends_with("(1h 05m):"));
+        assert!(lines[2].ends_with(" feature/1234-login"));
+        assert!(lines[4].ends_with(" #1234 Login page"));
+        assert!(lines[6].ends_with(" #1234 Active -> Resolved"));
+        assert_eq!(lines[7], "  API requests: 37");
+
+        let summary = log.summary_json(at(50), 37, &["feature/old"]);
+        assert_eq!(summary["work_items_viewed"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            summary["work_items_viewed"][0]["first_viewed_at"],
+            "2026-10-15T09:02:00+00:00"
+        );
+        assert_eq!(summary["state_changes"][0]["to"], "Resolved");
+        assert_eq!(summary["branches_deleted"][0], "feature/old");
+        assert_eq!(summary["api_requests"], 37);
+    }
+}
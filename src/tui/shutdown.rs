@@ -0,0 +1,63 @@
+//! Signals that ask the TUI to stop, so it quits through the normal path:
+//! the terminal is restored and the deleted-branches summary still prints.
+//!
+//! In raw mode Ctrl+C arrives as a key press; SIGINT only comes from outside,
+//! e.g. `kill -INT`. Session managers such as tmux send SIGTERM or SIGHUP.
+
+use std::io;
+
+#[cfg(unix)]
+use tokio::signal::unix::{Signal, SignalKind, signal};
+#[cfg(windows)]
+use tokio::signal::windows::{CtrlC, CtrlClose, ctrl_c, ctrl_close};
+
+#[cfg(unix)]
+pub(super) struct ShutdownSignals {
+    interrupt: Signal,
+    terminate: Signal,
+    hangup: Signal,
+}
+
+#[cfg(unix)]
+impl ShutdownSignals {
+    pub(super) fn new() -> io::Result<Self> {
+        Ok(Self {
+            interrupt: signal(SignalKind::interrupt())?,
+            terminate: signal(SignalKind::terminate())?,
+            hangup: signal(SignalKind::hangup())?,
+        })
+    }
+
+    /// Wait for SIGINT, SIGTERM, or SIGHUP.
+    pub(super) async fn recv(&mut self) {
+        tokio::select! {
+            _ = self.interrupt.recv() => tracing::info!("SIGINT received"),
+            _ = self.terminate.recv() => tracing::info!("SIGTERM received"),
+            _ = self.hangup.recv() => tracing::info!("SIGHUP received"),
+        }
+    }
+}
+
+#[cfg(windows)]
+pub(super) struct ShutdownSignals {
+    ctrl_c: CtrlC,
+    ctrl_close: CtrlClose,
+}
+
+#[cfg(windows)]
+impl ShutdownSignals {
+    pub(super) fn new() -> io::Result<Self> {
+        Ok(Self {
+            ctrl_c: ctrl_c()?,
+            ctrl_close: ctrl_close()?,
+        })
+    }
+
+    /// Wait for Ctrl+C or the console window closing.
+    pub(super) async fn recv(&mut self) {
+        tokio::select! {
+            _ = self.ctrl_c.recv() => tracing::info!("Ctrl+C received"),
+            _ = self.ctrl_close.recv() => tracing::info!("console close received"),
+        }
+    }
+}
@@ -1,31 +1,80 @@
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::Style;
+use std::sync::OnceLock;
 use std::time::Duration;
 
+mod file;
+
+pub use file::{Theme, load_theme_file};
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Use `theme` for the rest of the process. Only the first call has an effect.
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+pub(super) fn current() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
 pub mod styles {
     use super::*;
 
-    pub const ACCENT: Style = Style::new().fg(Color::Cyan);
-    pub const MUTED: Style = Style::new().fg(Color::DarkGray);
-    pub const TEXT: Style = Style::new().fg(Color::White);
-    pub const ERROR: Style = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
-    pub const SUCCESS: Style = Style::new().fg(Color::Green);
-    pub const WARNING: Style = Style::new().fg(Color::Yellow);
+    pub fn accent() -> Style {
+        current().accent
+    }
+
+    pub fn muted() -> Style {
+        current().muted
+    }
+
+    pub fn text() -> Style {
+        current().text
+    }
+
+    pub fn error() -> Style {
+        current().error
+    }
+
+    pub fn success() -> Style {
+        current().success
+    }
+
+    pub fn warning() -> Style {
+        current().warning
+    }
 }
 
 pub mod ui {
     use super::*;
 
-    pub const BORDER: Style = Style::new().fg(Color::Cyan);
-    pub const BORDER_ERROR: Style = Style::new().fg(Color::Red);
-    pub const TITLE: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-    pub const TITLE_ERROR: Style = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
-    pub const SELECTED: Style = Style::new().fg(Color::White).bg(Color::DarkGray);
+    pub fn border() -> Style {
+        current().border
+    }
+
+    pub fn border_error() -> Style {
+        current().border_error
+    }
+
+    pub fn title() -> Style {
+        current().title
+    }
+
+    pub fn title_error() -> Style {
+        current().title_error
+    }
+
+    pub fn selected() -> Style {
+        current().selected
+    }
 }
 
 pub mod branch {
     use super::*;
 
-    pub const CURRENT: Style = Style::new().fg(Color::Green).add_modifier(Modifier::BOLD);
+    pub fn current() -> Style {
+        super::current().branch_current
+    }
 }
 
 /// Layout constants
@@ -36,16 +85,30 @@ pub mod layout {
     pub const BRANCH_INFO_HEIGHT: u16 = 5;
     /// Popup size (width%, height%)
     pub const POPUP_SIZE: (u16, u16) = (60, 12);
+    /// Width of a change toast
+    pub const TOAST_WIDTH: u16 = 48;
+    /// Width of the branch statistics popup
+    pub const STATS_WIDTH: u16 = 64;
 }
 
 /// Timing constants
 pub mod timing {
     use super::Duration;
 
-    /// Polling interval for event loop
-    pub const POLL_INTERVAL: Duration = Duration::from_millis(50);
+    /// How long the selection rests before its work items are fetched
+    pub const FETCH_DEBOUNCE: Duration = Duration::from_millis(150);
     /// Status message duration (seconds)
     pub const STATUS_DURATION_SECS: u64 = 4;
+    /// Days of changes the activity tab lists
+    pub const ACTIVITY_DAYS: u32 = 7;
+    /// How often the activity tab polls while it is open
+    pub const ACTIVITY_POLL_INTERVAL: Duration = Duration::from_secs(120);
+    /// How often loaded work items are fetched again to look for changes
+    pub const CHANGE_CHECK_INTERVAL: Duration = Duration::from_secs(120);
+    /// How long a change toast shows
+    pub const TOAST_DURATION: Duration = Duration::from_secs(10);
+    /// Toasts shown at once, newest first
+    pub const MAX_TOASTS: usize = 3;
 }
 
 /// Scroll constants
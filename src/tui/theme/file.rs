@@ -0,0 +1,278 @@
+//! Theme files, set with `[display].theme`.
+//!
+//! A theme file is TOML with one table per group of styles in
+//! [`crate::tui::theme`], and one entry per style:
+//!
+//! ```toml
+//! [styles]
+//! accent = { fg = "#268bd2" }
+//! error = { fg = "red", modifiers = ["bold"] }
+//!
+//! [ui]
+//! selected = { fg = "white", bg = "#073642" }
+//! ```
+//!
+//! Colors are names (`cyan`, `lightred`, `darkgray`), `#rrggbb`, or a 256-color
+//! index. Styles the file leaves out keep their defaults; a style the file
+//! sets is exactly what it lists. Unknown tables, styles, and fields are
+//! errors, so a typo does not silently fall back to the default.
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result, bail};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub(super) accent: Style,
+    pub(super) muted: Style,
+    pub(super) text: Style,
+    pub(super) error: Style,
+    pub(super) success: Style,
+    pub(super) warning: Style,
+    pub(super) border: Style,
+    pub(super) border_error: Style,
+    pub(super) title: Style,
+    pub(super) title_error: Style,
+    pub(super) selected: Style,
+    pub(super) branch_current: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            accent: Style::new().fg(Color::Cyan),
+            muted: Style::new().fg(Color::DarkGray),
+            text: Style::new().fg(Color::White),
+            error: Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            success: Style::new().fg(Color::Green),
+            warning: Style::new().fg(Color::Yellow),
+            border: Style::new().fg(Color::Cyan),
+            border_error: Style::new().fg(Color::Red),
+            title: Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            title_error: Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            selected: Style::new().fg(Color::White).bg(Color::DarkGray),
+            branch_current: Style::new().fg(Color::Green).add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+impl Theme {
+    /// Each style with its name in a theme file, in schema order.
+    pub fn entries(&self) -> [(&'static str, Style); 12] {
+        [
+            ("styles.accent", self.accent),
+            ("styles.muted", self.muted),
+            ("styles.text", self.text),
+            ("styles.error", self.error),
+            ("styles.success", self.success),
+            ("styles.warning", self.warning),
+            ("ui.border", self.border),
+            ("ui.border_error", self.border_error),
+            ("ui.title", self.title),
+            ("ui.title_error", self.title_error),
+            ("ui.selected", self.selected),
+            ("branch.current", self.branch_current),
+        ]
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    styles: StylesTable,
+    ui: UiTable,
+    branch: BranchTable,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StylesTable {
+    accent: Option<StyleSpec>,
+    muted: Option<StyleSpec>,
+    text: Option<StyleSpec>,
+    error: Option<StyleSpec>,
+    success: Option<StyleSpec>,
+    warning: Option<StyleSpec>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct UiTable {
+    border: Option<StyleSpec>,
+    border_error: Option<StyleSpec>,
+    title: Option<StyleSpec>,
+    title_error: Option<StyleSpec>,
+    selected: Option<StyleSpec>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct BranchTable {
+    current: Option<StyleSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleSpec {
+    fg: Option<String>,
+    bg: Option<String>,
+    #[serde(default)]
+    modifiers: Vec<String>,
+}
+
+impl StyleSpec {
+    fn to_style(&self) -> Result<Style> {
+        let mut style = Style::new();
+        if let Some(fg) = &self.fg {
+            style = style.fg(parse_color(fg)?);
+        }
+        if let Some(bg) = &self.bg {
+            style = style.bg(parse_color(bg)?);
+        }
+        for modifier in &self.modifiers {
+            style = style.add_modifier(parse_modifier(modifier)?);
+        }
+        Ok(style)
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color> {
+    Color::from_str(value).map_err(|_| {
+        anyhow::anyhow!("unknown color '{value}' (use a name like cyan, #rrggbb, or 0-255)")
+    })
+}
+
+const MODIFIERS: &[(&str, Modifier)] = &[
+    ("bold", Modifier::BOLD),
+    ("dim", Modifier::DIM),
+    ("italic", Modifier::ITALIC),
+    ("underlined", Modifier::UNDERLINED),
+    ("slow_blink", Modifier::SLOW_BLINK),
+    ("rapid_blink", Modifier::RAPID_BLINK),
+    ("reversed", Modifier::REVERSED),
+    ("hidden", Modifier::HIDDEN),
+    ("crossed_out", Modifier::CROSSED_OUT),
+];
+
+fn parse_modifier(value: &str) -> Result<Modifier> {
+    match MODIFIERS.iter().find(|(name, _)| *name == value) {
+        Some((_, modifier)) => Ok(*modifier),
+        None => bail!(
+            "unknown modifier '{}' (use one of: {})",
+            value,
+            MODIFIERS
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn parse_theme(content: &str) -> Result<Theme> {
+    let file: ThemeFile = toml::from_str(content)?;
+    let mut theme = Theme::default();
+
+    let overrides = [
+        ("styles.accent", file.styles.accent, &mut theme.accent),
+        ("styles.muted", file.styles.muted, &mut theme.muted),
+        ("styles.text", file.styles.text, &mut theme.text),
+        ("styles.error", file.styles.error, &mut theme.error),
+        ("styles.success", file.styles.success, &mut theme.success),
+        ("styles.warning", file.styles.warning, &mut theme.warning),
+        ("ui.border", file.ui.border, &mut theme.border),
+        (
+            "ui.border_error",
+            file.ui.border_error,
+            &mut theme.border_error,
+        ),
+        ("ui.title", file.ui.title, &mut theme.title),
+        (
+            "ui.title_error",
+            file.ui.title_error,
+            &mut theme.title_error,
+        ),
+        ("ui.selected", file.ui.selected, &mut theme.selected),
+        (
+            "branch.current",
+            file.branch.current,
+            &mut theme.branch_current,
+        ),
+    ];
+    for (name, spec, style) in overrides {
+        if let Some(spec) = spec {
+            *style = spec.to_style().with_context(|| format!("Invalid {name}"))?;
+        }
+    }
+
+    Ok(theme)
+}
+
+/// Read and validate the theme file at `path`.
+pub fn load_theme_file(path: &Path) -> Result<Theme> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
+    parse_theme(&content).with_context(|| format!("Failed to load theme file: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_listed_styles_and_keeps_the_rest() {
+        let theme = parse_theme(
+            r##"
+[styles]
+accent = { fg = "#268bd2" }
+
+[ui]
+selected = { fg = "white", bg = "8", modifiers = ["bold", "italic"] }
+"##,
+        )
+        .unwrap();
+
+        assert_eq!(theme.accent, Style::new().fg(Color::Rgb(0x26, 0x8b, 0xd2)));
+        assert_eq!(
+            theme.selected,
+            Style::new()
+                .fg(Color::White)
+                .bg(Color::Indexed(8))
+                .add_modifier(Modifier::BOLD | Modifier::ITALIC)
+        );
+        assert_eq!(theme.muted, Theme::default().muted);
+    }
+
+    #[test]
+    fn rejects_unknown_styles_colors_and_modifiers() {
+        assert!(parse_theme("[styles]\naccnet = { fg = \"red\" }").is_err());
+        assert!(parse_theme("[styles]\naccent = { color = \"red\" }").is_err());
+
+        let error = parse_theme("[ui]\nborder = { fg = \"redish\" }").expect_err("bad color");
+        assert_eq!(
+            format!("{error:#}"),
+            "Invalid ui.border: unknown color 'redish' (use a name like cyan, #rrggbb, or 0-255)"
+        );
+
+        let error =
+            parse_theme("[branch]\ncurrent = { modifiers = [\"blink\"] }").expect_err("bad mod");
+        assert!(format!("{error:#}").starts_with("Invalid branch.current: unknown modifier"));
+    }
+
+    #[test]
+    fn bundled_solarized_theme_sets_every_style() {
+        let theme = parse_theme(include_str!("../../../docs/themes/solarized-dark.toml")).unwrap();
+
+        assert!(
+            theme
+                .entries()
+                .iter()
+                .zip(Theme::default().entries())
+                .all(|((_, style), (_, default))| *style != default)
+        );
+    }
+}
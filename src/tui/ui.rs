@@ -1,18 +1,28 @@
 //! UI rendering module - orchestrates all UI components
 
+mod activity;
 mod branch_info;
 mod branches;
 mod details;
 mod footer;
 mod helpers;
+mod inspector;
 mod popup;
+mod pull_requests;
+mod stats;
+mod toasts;
+mod work_items;
+
+pub use helpers::wrap_text;
 
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout},
+    widgets::Clear,
 };
 
 use super::app::{App, AppMode, DetailsMetrics};
+use super::compat::strip_modifiers;
 use super::theme;
 
 /// Main render function - orchestrates all UI components
@@ -41,11 +51,31 @@ pub fn render(frame: &mut Frame, app: &App) -> DetailsMetrics {
         ])
         .split(chunks[1]);
 
-    branches::render_branches(frame, app, chunks[0]);
-    let metrics = details::render_details(frame, app, right_chunks[0]);
-    branch_info::render_branch_info(frame, app, right_chunks[1]);
+    // The work item list replaces the branch list, and the details of the
+    // highlighted work item take the whole right panel
+    let mut metrics = if app.is_work_item_list_open() {
+        work_items::render_work_items(frame, app, chunks[0]);
+        details::render_details(frame, app, chunks[1])
+    } else {
+        branches::render_branches(frame, app, chunks[0]);
+        let metrics = details::render_details(frame, app, right_chunks[0]);
+        branch_info::render_branch_info(frame, app, right_chunks[1]);
+        metrics
+    };
     footer::render_footer(frame, app, main_chunks[1]);
 
+    // The activity and pull request tabs cover the panels; they are still
+    // laid out so the details metrics stay current.
+    if app.is_activity_open() {
+        frame.render_widget(Clear, main_chunks[0]);
+        activity::render_activity(frame, app, main_chunks[0]);
+    } else if app.is_pull_request_list_open() {
+        frame.render_widget(Clear, main_chunks[0]);
+        pull_requests::render_pull_requests(frame, app, main_chunks[0]);
+    }
+
+    toasts::render_toasts(frame, app, main_chunks[0]);
+
     // Render popup if needed
     if let Some(branch) = app.confirm_delete_branch() {
         popup::render_confirm_popup(
@@ -53,10 +83,92 @@ pub fn render(frame: &mut Frame, app: &App) -> DetailsMetrics {
             &branch.display_name,
             branch.scope.is_remote(),
             app.confirm_delete_is_prune(),
+            app.confirm_delete_remote()
+                .map(|remote| remote.display_name.as_str()),
+            app.plain(),
         );
+    } else if matches!(app.mode(), AppMode::ConfirmDeleteMarked) {
+        let names: Vec<&str> = app
+            .marked_branches()
+            .iter()
+            .map(|branch| branch.display_name.as_str())
+            .collect();
+        popup::render_confirm_marked_popup(frame, &names, app.plain());
+    } else if let Some(outcomes) = app.delete_outcomes() {
+        popup::render_delete_results_popup(frame, outcomes, app.plain());
     } else if let AppMode::ErrorPopup(message) = app.mode() {
-        popup::render_error_popup(frame, message);
+        popup::render_error_popup(frame, message, app.plain());
+    } else if let AppMode::Inspect(inspector) = app.mode() {
+        metrics.inspector_height = inspector::render_inspector(frame, inspector, app.plain());
+    } else if let Some(menu) = app.state_menu() {
+        popup::render_state_menu(frame, menu, app.plain());
+    } else if let Some(form) = app.completion_form() {
+        popup::render_completion_form(frame, form, app.plain());
+    } else if app.merged_branches().is_some() {
+        stats::render_branch_stats(frame, app);
     }
 
+    strip_modifiers(frame.buffer_mut(), app.unsupported_modifiers());
+
     metrics
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::BranchScope;
+    use crate::tui::app::{BranchInfo, Msg};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn branch(name: &str, is_current: bool, is_protected: bool) -> BranchInfo {
+        BranchInfo {
+            key: format!("refs/heads/{name}"),
+            display_name: name.to_string(),
+            branch_name: name.to_string(),
+            remote_name: None,
+            scope: BranchScope::Local,
+            work_item_id: None,
+            is_current,
+            is_protected,
+            is_stale: false,
+            origin_upstream: None,
+        }
+    }
+
+    fn rendered_text(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).expect("terminal");
+        terminal
+            .draw(|frame| {
+                render(frame, app);
+            })
+            .expect("draw");
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn plain_mode_uses_text_labels_instead_of_box_drawing() {
+        let branches = vec![
+            branch("main", false, true),
+            branch("feature/1", true, false),
+        ];
+        let mut app = App::new(branches, vec![]).with_plain(true);
+        app.update(Msg::ToggleShowProtected);
+        app.set_status_message("git lookup failed".to_string(), true, 5);
+
+        let text = rendered_text(&app);
+        assert!(text.contains("SELECTED CURRENT feature/1"), "got: {text:?}");
+        assert!(text.contains("main PROTECTED"), "got: {text:?}");
+        assert!(text.contains("ERROR: git lookup failed"), "got: {text:?}");
+        assert!(
+            !text.contains(|c: char| ('\u{2500}'..='\u{257F}').contains(&c) || c == '►'),
+            "box drawing in plain mode: {text:?}"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::azure_devops::{ActivityEntry, FieldChange};
+use crate::i18n::{Text, t, tf};
+use crate::timestamp::format_timestamp;
+use crate::tui::app::{App, ClientState};
+use crate::tui::theme;
+
+use super::helpers::{panel_block, separator};
+
+/// Render the activity tab in place of the panels
+pub fn render_activity(frame: &mut Frame, app: &App, area: Rect) {
+    let activity = app.activity();
+    let plain = app.plain();
+
+    let mut title = vec![Span::styled(
+        format!(
+            " {} ",
+            tf(
+                Text::ActivityTitle,
+                &[("days", &theme::timing::ACTIVITY_DAYS.to_string())]
+            )
+        ),
+        theme::ui::title(),
+    )];
+    if activity.loading && !activity.entries.is_empty() {
+        title.push(Span::styled(
+            format!("{} ", t(Text::ActivityRefreshing)),
+            theme::styles::warning(),
+        ));
+    }
+    let block = panel_block(plain)
+        .border_style(theme::ui::border())
+        .title(Line::from(title))
+        .title_bottom(
+            Line::from(Span::styled(
+                format!(
+                    " j/k {}  o {}  r {}  esc {} ",
+                    t(Text::FooterNavigate),
+                    t(Text::FooterOpen),
+                    t(Text::FooterRefresh),
+                    t(Text::FooterClose)
+                ),
+                theme::styles::muted(),
+            ))
+            .right_aligned(),
+        );
+
+    if activity.entries.is_empty() {
+        let message = match (app.client_state(), &activity.error) {
+            (ClientState::Unavailable(error), _) => Span::styled(
+                format!("  {}: {error}", t(Text::NotConnected)),
+                theme::styles::error(),
+            ),
+            (_, Some(error)) => Span::styled(
+                format!("  {}", tf(Text::ActivityError, &[("error", error)])),
+                theme::styles::error(),
+            ),
+            (ClientState::Ready, None) if !activity.loading => Span::styled(
+                format!(
+                    "  {}",
+                    tf(
+                        Text::ActivityEmpty,
+                        &[("days", &theme::timing::ACTIVITY_DAYS.to_string())]
+                    )
+                ),
+                theme::styles::muted(),
+            ),
+            _ => Span::styled(
+                format!("  {}", t(Text::ActivityLoading)),
+                theme::styles::warning(),
+            ),
+        };
+        let content = vec![Line::from(""), Line::from(message)];
+        frame.render_widget(Paragraph::new(content).block(block), area);
+        return;
+    }
+
+    let mut items: Vec<ListItem> = Vec::new();
+    if let Some(error) = &activity.error {
+        items.push(ListItem::new(Line::from(Span::styled(
+            tf(Text::ActivityError, &[("error", error)]),
+            theme::styles::error(),
+        ))));
+    }
+    let offset = items.len();
+    items.extend(activity.entries.iter().enumerate().map(|(index, entry)| {
+        let marker = if plain && index == activity.selected {
+            format!("{} ", t(Text::SelectedLabel))
+        } else {
+            String::new()
+        };
+        ListItem::new(vec![headline(entry, marker), change_line(entry, plain)])
+    }));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(theme::ui::selected().add_modifier(Modifier::BOLD))
+        .highlight_symbol(if plain { "" } else { "\u{25BA} " });
+    let mut state = ListState::default();
+    state.select(Some(offset + activity.selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// When the work item changed, its ID, and its title.
+fn headline(entry: &ActivityEntry, marker: String) -> Line<'_> {
+    let work_item = &entry.work_item;
+    let when = work_item
+        .changed_at()
+        .and_then(format_timestamp)
+        .unwrap_or_default();
+    Line::from(vec![
+        Span::raw(marker),
+        Span::styled(when, theme::styles::muted()),
+        Span::raw("  "),
+        Span::styled(format!("#{}", work_item.id), theme::styles::accent()),
+        Span::raw(" "),
+        Span::styled(work_item.title.as_str(), theme::styles::text()),
+    ])
+}
+
+/// What the last change did, and who made it.
+fn change_line(entry: &ActivityEntry, plain: bool) -> Line<'static> {
+    let summary = if entry.is_new() {
+        t(Text::ActivityCreated).to_string()
+    } else if entry.changes.is_empty() {
+        t(Text::ActivityOtherFields).to_string()
+    } else {
+        entry
+            .changes
+            .iter()
+            .map(|change| change_text(change, plain))
+            .collect::<Vec<_>>()
+            .join(separator(plain, " · "))
+    };
+    let mut spans = vec![
+        Span::raw("    "),
+        Span::styled(summary, theme::styles::text()),
+    ];
+    if let Some(name) = entry.work_item.changed_by() {
+        spans.push(Span::styled(
+            format!(" {}", tf(Text::By, &[("name", name)])),
+            theme::styles::muted(),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// `State: Active → Resolved`, with `(none)` for an empty value.
+fn change_text(change: &FieldChange, plain: bool) -> String {
+    let value = |value: &Option<String>| {
+        value
+            .clone()
+            .unwrap_or_else(|| t(Text::ActivityNone).to_string())
+    };
+    let arrow = if plain { "->" } else { "→" };
+    format!(
+        "{}: {} {arrow} {}",
+        change.field,
+        value(&change.old),
+        value(&change.new)
+    )
+}
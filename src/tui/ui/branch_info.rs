@@ -3,48 +3,55 @@ use ratatui::{
     layout::Rect,
     style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::Paragraph,
 };
 
 use crate::git::BranchScope;
+use crate::i18n::{Text, t, tf};
 use crate::tui::app::App;
 use crate::tui::theme;
 
-use super::helpers::{format_relative_time, format_remote_status};
+use super::helpers::{
+    error_text, format_relative_time, format_remote_status, panel_block, separator,
+};
+
+fn label_span(label: Text) -> Span<'static> {
+    Span::styled(format!("  {}", t(label)), theme::styles::muted())
+}
 
 fn remote_freshness_line(app: &App) -> Option<Line<'static>> {
     if app.remote_freshness_is_checking() {
         return Some(Line::from(vec![
-            Span::styled("  Origin: ", theme::styles::MUTED),
-            Span::styled("Checking origin...", theme::styles::MUTED),
+            label_span(Text::OriginLabel),
+            Span::styled(t(Text::CheckingOrigin), theme::styles::muted()),
         ]));
     }
 
     app.remote_freshness_error().map(|error| {
         Line::from(vec![
-            Span::styled("  Origin: ", theme::styles::MUTED),
-            Span::styled(error.to_string(), theme::styles::ERROR),
+            label_span(Text::OriginLabel),
+            Span::styled(error_text(app.plain(), error), theme::styles::error()),
         ])
     })
 }
 
-fn local_branch_lines(status: &crate::git::BranchStatus) -> Vec<Line<'static>> {
+fn local_branch_lines(status: &crate::git::BranchStatus, plain: bool) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
-    let (remote_text, remote_color) = format_remote_status(&status.remote_status);
+    let (remote_text, remote_color) = format_remote_status(&status.remote_status, plain);
 
     if let (Some(author), Some(time)) = (&status.last_commit_author, status.last_commit_time) {
         let relative_time = format_relative_time(time);
         lines.push(Line::from(vec![
-            Span::styled("  Remote: ", theme::styles::MUTED),
+            label_span(Text::RemoteLabel),
             Span::styled(remote_text, Style::default().fg(remote_color)),
-            Span::styled("  │  ", theme::styles::MUTED),
-            Span::styled(author.clone(), theme::styles::TEXT),
-            Span::styled(", ", theme::styles::MUTED),
-            Span::styled(relative_time, theme::styles::MUTED),
+            Span::styled(separator(plain, "  │  "), theme::styles::muted()),
+            Span::styled(author.clone(), theme::styles::text()),
+            Span::styled(", ", theme::styles::muted()),
+            Span::styled(relative_time, theme::styles::muted()),
         ]));
     } else {
         lines.push(Line::from(vec![
-            Span::styled("  Remote: ", theme::styles::MUTED),
+            label_span(Text::RemoteLabel),
             Span::styled(remote_text, Style::default().fg(remote_color)),
         ]));
     }
@@ -60,17 +67,14 @@ fn remote_branch_lines(
     let mut lines = Vec::new();
     let remote_name = branch.remote_name.as_deref().unwrap_or("origin");
     lines.push(Line::from(vec![
-        Span::styled("  Source: ", theme::styles::MUTED),
-        Span::styled(remote_name.to_string(), theme::styles::TEXT),
+        label_span(Text::SourceLabel),
+        Span::styled(remote_name.to_string(), theme::styles::text()),
     ]));
 
     if branch.is_stale {
         lines.push(Line::from(vec![
-            Span::styled("  Stale: ", theme::styles::MUTED),
-            Span::styled(
-                "Missing on origin; cached remote-tracking ref may be stale",
-                theme::styles::ERROR,
-            ),
+            label_span(Text::StaleLabel),
+            Span::styled(t(Text::StaleOnOrigin), theme::styles::error()),
         ]));
     } else if let Some(line) = remote_freshness_line(app) {
         lines.push(line);
@@ -79,10 +83,10 @@ fn remote_branch_lines(
     if let (Some(author), Some(time)) = (&status.last_commit_author, status.last_commit_time) {
         let relative_time = format_relative_time(time);
         lines.push(Line::from(vec![
-            Span::styled("  Last commit: ", theme::styles::MUTED),
-            Span::styled(author.clone(), theme::styles::TEXT),
-            Span::styled(", ", theme::styles::MUTED),
-            Span::styled(relative_time, theme::styles::MUTED),
+            label_span(Text::LastCommitLabel),
+            Span::styled(author.clone(), theme::styles::text()),
+            Span::styled(", ", theme::styles::muted()),
+            Span::styled(relative_time, theme::styles::muted()),
         ]));
     }
 
@@ -91,12 +95,11 @@ fn remote_branch_lines(
 
 /// Render the branch info panel (bottom-right)
 pub fn render_branch_info(frame: &mut Frame, app: &App, area: Rect) {
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(theme::ui::BORDER)
+    let block = panel_block(app.plain())
+        .border_style(theme::ui::border())
         .title(Line::from(vec![Span::styled(
-            " Branch Info ",
-            theme::ui::TITLE,
+            format!(" {} ", t(Text::BranchInfoTitle)),
+            theme::ui::title(),
         )]));
 
     let inner = block.inner(area);
@@ -107,13 +110,13 @@ pub fn render_branch_info(frame: &mut Frame, app: &App, area: Rect) {
     if let Some(branch) = app.selected_branch() {
         lines.push(Line::from(vec![
             Span::styled("  ", Style::default()),
-            Span::styled(&branch.display_name, theme::branch::CURRENT),
+            Span::styled(&branch.display_name, theme::branch::current()),
         ]));
 
         if let Some(status) = app.get_branch_status(&branch.key) {
             match branch.scope {
                 BranchScope::Local => {
-                    lines.extend(local_branch_lines(status));
+                    lines.extend(local_branch_lines(status, app.plain()));
                 }
                 BranchScope::Remote => {
                     lines.extend(remote_branch_lines(app, branch, status));
@@ -121,13 +124,13 @@ pub fn render_branch_info(frame: &mut Frame, app: &App, area: Rect) {
             }
         } else if let Some(error) = app.get_branch_status_error(&branch.key) {
             lines.push(Line::from(vec![Span::styled(
-                format!("  Error: {error}"),
-                theme::styles::ERROR,
+                format!("  {}", tf(Text::Error, &[("error", error)])),
+                theme::styles::error(),
             )]));
         } else {
             lines.push(Line::from(vec![Span::styled(
-                "  Loading...",
-                theme::styles::MUTED,
+                format!("  {}", t(Text::Loading)),
+                theme::styles::muted(),
             )]));
         }
     }
@@ -153,12 +156,14 @@ mod tests {
             is_current: false,
             is_protected: false,
             is_stale: stale,
+            origin_upstream: None,
         }
     }
 
     fn remote_status() -> BranchStatus {
         BranchStatus {
             remote_status: RemoteStatus::RemoteTracking,
+            last_commit_sha: None,
             last_commit_author: Some("Alice".to_string()),
             last_commit_time: Some(123),
         }
@@ -167,6 +172,7 @@ mod tests {
     fn local_status() -> BranchStatus {
         BranchStatus {
             remote_status: RemoteStatus::UpToDate,
+            last_commit_sha: None,
             last_commit_author: Some("Bob".to_string()),
             last_commit_time: Some(456),
         }
@@ -181,7 +187,7 @@ mod tests {
 
         assert_eq!(line.spans[0].content.as_ref(), "  Origin: ");
         assert_eq!(line.spans[1].content.as_ref(), "Checking origin...");
-        assert_eq!(line.spans[1].style, theme::styles::MUTED);
+        assert_eq!(line.spans[1].style, theme::styles::muted());
     }
 
     #[test]
@@ -193,7 +199,7 @@ mod tests {
 
         assert_eq!(line.spans[0].content.as_ref(), "  Origin: ");
         assert_eq!(line.spans[1].content.as_ref(), "Network timeout");
-        assert_eq!(line.spans[1].style, theme::styles::ERROR);
+        assert_eq!(line.spans[1].style, theme::styles::error());
     }
 
     #[test]
@@ -232,7 +238,7 @@ mod tests {
     fn test_local_branch_lines_include_remote_and_commit_metadata() {
         let status = local_status();
 
-        let lines = local_branch_lines(&status);
+        let lines = local_branch_lines(&status, false);
 
         assert_eq!(lines.len(), 1);
         let combined = lines[0]
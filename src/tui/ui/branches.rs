@@ -3,54 +3,69 @@ use ratatui::{
     layout::{Alignment, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{List, ListItem, ListState, Paragraph},
 };
 
-use crate::tui::app::App;
+use crate::azure_devops::urgency_color;
+use crate::i18n::{Text, t, tf};
+use crate::tui::app::{App, BranchView};
 use crate::tui::theme;
 
+use super::helpers::panel_block;
+
 /// Render the branch list panel
 pub fn render_branches(frame: &mut Frame, app: &App, area: Rect) {
     let visible = app.visible_branches();
     let filter = app.effective_branch_filter().trim();
-    let title = if filter.is_empty() {
-        format!(" Branches ({}) ", app.active_view().label())
+    let sort = if app.is_sorted_by_priority() {
+        t(Text::SortedByPriority)
+    } else {
+        ""
+    };
+    let view = format!("{}{}", app.active_view().label(), sort);
+    let title = tf(Text::BranchesTitle, &[("view", &view)]);
+    let mut title = if filter.is_empty() {
+        format!(" {} ", title)
     } else {
-        format!(" Branches ({}) / {} ", app.active_view().label(), filter)
+        format!(" {} / {} ", title, filter)
     };
+    let marked = app.marked_branches().len();
+    if marked > 0 {
+        title.push_str(&format!(
+            "· {} ",
+            tf(Text::BranchesMarked, &[("count", &marked.to_string())])
+        ));
+    }
 
+    let plain = app.plain();
     if visible.is_empty() {
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(theme::ui::BORDER)
+        let block = panel_block(plain)
+            .border_style(theme::ui::border())
             .title(Line::from(vec![Span::styled(
                 title.clone(),
-                theme::ui::TITLE,
+                theme::ui::title(),
             )]));
 
         let inner_area = block.inner(area);
         frame.render_widget(block, area);
 
+        let branches = match app.active_view() {
+            BranchView::Local => t(Text::LocalBranches),
+            BranchView::Remote => t(Text::RemoteBranches),
+        };
         let empty_text = if !filter.is_empty() {
-            format!(
-                "No {} branches match \"{}\".",
-                app.active_view().label().to_lowercase(),
-                filter
+            tf(
+                Text::NoBranchesMatch,
+                &[("branches", branches), ("filter", filter)],
             )
         } else if app.has_hidden_branches_in_active_view() {
-            format!(
-                "No {} branches shown. Press p to show protected branches.",
-                app.active_view().label().to_lowercase()
-            )
+            tf(Text::NoBranchesShown, &[("branches", branches)])
         } else {
-            format!(
-                "No {} branches found.",
-                app.active_view().label().to_lowercase()
-            )
+            tf(Text::NoBranchesFound, &[("branches", branches)])
         };
 
         let empty_msg = Paragraph::new(empty_text)
-            .style(theme::styles::MUTED)
+            .style(theme::styles::muted())
             .alignment(Alignment::Center);
 
         let msg_height = 1;
@@ -68,14 +83,33 @@ pub fn render_branches(frame: &mut Frame, app: &App, area: Rect) {
 
     let items: Vec<ListItem> = visible
         .iter()
-        .map(|branch| {
-            let prefix = if branch.is_current { "* " } else { "  " };
+        .enumerate()
+        .map(|(index, branch)| {
+            // Plain mode names what the highlight, `*`, and symbols show
+            let prefix = if plain {
+                let selected = index == app.selected_index();
+                [
+                    (selected, Text::SelectedLabel),
+                    (branch.is_current, Text::CurrentLabel),
+                    (app.is_marked(branch), Text::MarkedLabel),
+                ]
+                .into_iter()
+                .filter(|(shown, _)| *shown)
+                .map(|(_, label)| format!("{} ", t(label)))
+                .collect()
+            } else if branch.is_current {
+                "* ".to_string()
+            } else if app.is_marked(branch) {
+                "+ ".to_string()
+            } else {
+                "  ".to_string()
+            };
 
             // Show lock for protected branches (when visible)
-            let protected_indicator = if branch.is_protected {
-                " \u{1F512}"
-            } else {
-                ""
+            let protected_indicator = match (branch.is_protected, plain) {
+                (false, _) => String::new(),
+                (true, false) => " \u{1F512}".to_string(),
+                (true, true) => format!(" {}", t(Text::ProtectedLabel)),
             };
 
             let wi_suffix = match branch.work_item_id {
@@ -83,33 +117,56 @@ pub fn render_branches(frame: &mut Frame, app: &App, area: Rect) {
                 None => String::new(),
             };
 
-            let stale_indicator = if branch.is_stale { " ⚠" } else { "" };
+            let stale_indicator = match (branch.is_stale, plain) {
+                (false, _) => String::new(),
+                (true, false) => " ⚠".to_string(),
+                (true, true) => format!(" {}", t(Text::StaleBadge)),
+            };
 
             let style = if branch.is_current {
-                theme::branch::CURRENT
+                theme::branch::current()
             } else if branch.is_protected || branch.is_stale {
-                theme::styles::MUTED
+                theme::styles::muted()
             } else {
                 Style::default()
             };
 
-            ListItem::new(format!(
-                "{}{}{}{}{}",
-                prefix, branch.display_name, protected_indicator, wi_suffix, stale_indicator
-            ))
-            .style(style)
+            let mut spans = vec![Span::raw(format!(
+                "{}{}{}",
+                prefix, branch.display_name, protected_indicator
+            ))];
+            // An overdue work item shows in red, with a label in plain mode
+            if app.branch_is_overdue(branch) {
+                spans.push(Span::styled(wi_suffix, theme::styles::error()));
+                if plain {
+                    spans.push(Span::styled(
+                        format!(" {}", t(Text::OverdueLabel)),
+                        theme::styles::error(),
+                    ));
+                }
+            } else {
+                spans.push(Span::raw(wi_suffix));
+            }
+            if let Some(priority) = app.branch_priority(branch) {
+                spans.push(Span::styled(
+                    format!(" P{}", priority),
+                    Style::default().fg(urgency_color(priority)),
+                ));
+            }
+            spans.push(Span::raw(stale_indicator));
+
+            ListItem::new(Line::from(spans)).style(style)
         })
         .collect();
 
     let list = List::new(items)
         .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(theme::ui::BORDER)
-                .title(Line::from(vec![Span::styled(title, theme::ui::TITLE)])),
+            panel_block(plain)
+                .border_style(theme::ui::border())
+                .title(Line::from(vec![Span::styled(title, theme::ui::title())])),
         )
-        .highlight_style(theme::ui::SELECTED.add_modifier(Modifier::BOLD))
-        .highlight_symbol("\u{25BA} ");
+        .highlight_style(theme::ui::selected().add_modifier(Modifier::BOLD))
+        .highlight_symbol(if plain { "" } else { "\u{25BA} " });
 
     let mut state = ListState::default();
     state.select(Some(app.selected_index()));
@@ -117,6 +174,8 @@ pub fn render_branches(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_stateful_widget(list, area, &mut state);
 
     // Render scrollbar inside the borders to match details view
-    let inner_area = Block::default().borders(Borders::ALL).inner(area);
-    super::helpers::render_scrollbar(frame, inner_area, visible.len(), state.offset());
+    if !plain {
+        let inner_area = panel_block(plain).inner(area);
+        super::helpers::render_scrollbar(frame, inner_area, visible.len(), state.offset());
+    }
 }
@@ -1,116 +1,180 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
 use ratatui::{
     Frame,
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Clear, Paragraph},
 };
 
-use crate::azure_devops::FieldFormat;
-use crate::tui::app::{App, DetailsMetrics, WorkItemStatus};
+use crate::azure_devops::{DetailField, FieldFormat, PullRequest, WorkItem, urgency_color};
+use crate::i18n::{Text, t, tf};
+use crate::timestamp::format_timestamp;
+use crate::tui::app::{
+    App, ChildrenStatus, ClientState, CommentsStatus, DetailsMetrics, ParentLink,
+    PullRequestsStatus, RenderedFields, WorkItemStatus,
+};
+use crate::tui::emoji::ascii_lines;
 use crate::tui::html_render::render_html;
+use crate::tui::keymap::Action;
 use crate::tui::markdown_render::render_markdown;
 use crate::tui::theme;
 
-use super::helpers::append_wrapped_text;
+use super::helpers::{
+    append_wrapped_text, error_text, format_relative_time, panel_block, pull_request_status,
+    separator,
+};
 
 /// Render the work item details panel
 pub fn render_details(frame: &mut Frame, app: &App, area: Rect) -> DetailsMetrics {
     let work_item_id = app.selected_work_item_id();
 
-    // Calculate inner area first to determine visible height
-    let inner = Block::default().borders(Borders::ALL).inner(area);
+    // Calculate inner area first to determine visible height; without
+    // borders, the title still takes the top row
+    let plain = app.plain();
+    let inner = panel_block(plain).title("").inner(area);
     let visible_height = inner.height;
 
     // Build scroll info for bottom border (only if scrollable).
     // This intentionally reads the content height measured on the previous frame
     // (`app.content_height()`); the freshly measured height is returned below and
     // applied after the draw, preserving the prior one-frame indicator lag.
-    let scroll_title = if app.content_height() > visible_height {
-        Line::from(vec![
-            Span::styled(
-                format!(
-                    " {}/{} ",
-                    app.scroll_offset() + 1,
-                    app.content_height().saturating_sub(visible_height) + 1
-                ),
-                theme::styles::MUTED,
-            ),
-            Span::styled("─", theme::styles::ACCENT),
-        ])
-    } else {
-        Line::default()
-    };
+    let scroll_position = (app.content_height() > visible_height).then(|| {
+        format!(
+            "{}/{}",
+            app.scroll_offset() + 1,
+            app.content_height().saturating_sub(visible_height) + 1
+        )
+    });
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(theme::ui::BORDER)
-        .title(Line::from(vec![Span::styled(
-            " Work Item Details ",
-            theme::ui::TITLE,
-        )]))
-        .title_bottom(scroll_title.right_aligned());
+    let mut title = format!(" {} ", t(Text::DetailsTitle));
+    // The way from the selected work item to the one stepped into
+    if let Some(view) = app.hierarchy().filter(|view| view.trail.len() > 1) {
+        let arrow = if plain { " > " } else { " › " };
+        let trail: Vec<String> = view.trail.iter().map(|id| format!("#{id}")).collect();
+        title.push_str(&format!("{} ", trail.join(arrow)));
+    }
+    let mut block = panel_block(plain).border_style(theme::ui::border());
+    match scroll_position {
+        // Plain mode has no bottom border to put the position on
+        Some(position) if plain => title.push_str(&format!("({position}) ")),
+        Some(position) => {
+            block = block.title_bottom(
+                Line::from(vec![
+                    Span::styled(format!(" {position} "), theme::styles::muted()),
+                    Span::styled("─", theme::styles::accent()),
+                ])
+                .right_aligned(),
+            );
+        }
+        None => {}
+    }
+    let block = block.title(Line::from(vec![Span::styled(title, theme::ui::title())]));
 
     frame.render_widget(block, area);
 
     // Clear the inner area before rendering new content
     frame.render_widget(Clear, inner);
 
-    let content_height = match work_item_id {
+    let (content_height, rendered_fields, parent_link) = match work_item_id {
         Some(wi_id) => render_work_item_details(frame, app, inner, wi_id),
         None => {
             let lines = vec![
                 Line::from(""),
                 Line::from(Span::styled(
-                    "  No work item linked to this branch",
-                    theme::styles::MUTED.add_modifier(Modifier::ITALIC),
+                    format!("  {}", t(Text::NoWorkItemLinked)),
+                    theme::styles::muted().add_modifier(Modifier::ITALIC),
                 )),
             ];
 
             let content_height = lines.len() as u16;
             let text = Paragraph::new(lines);
             frame.render_widget(text, inner);
-            content_height
+            (content_height, None, None)
         }
     };
 
     DetailsMetrics {
         content_height,
         visible_height,
+        rendered_fields,
+        parent_link,
+        ..DetailsMetrics::default()
     }
 }
 
-/// Render the work item details content
-fn render_work_item_details(frame: &mut Frame, app: &App, area: Rect, wi_id: u32) -> u16 {
+/// Render the work item details content, returning its height, the rich
+/// text fields if they were rendered anew rather than reused, and where the
+/// parent line was drawn.
+fn render_work_item_details(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    wi_id: u32,
+) -> (u16, Option<RenderedFields>, Option<ParentLink>) {
     let status = app.get_work_item_status(wi_id);
     let max_width = area.width.saturating_sub(4) as usize;
+    let mut fields: Option<Arc<[Line<'static>]>> = None;
+    let mut rendered_fields = None;
+    let mut comment_lines = Vec::new();
+    // Index in `content` and ID of the parent line
+    let mut parent_line = None;
 
     let content: Vec<Line> = match status {
-        WorkItemStatus::NotFetched | WorkItemStatus::Loading => {
-            vec![
+        WorkItemStatus::NotFetched | WorkItemStatus::Loading => match app.client_state() {
+            ClientState::Connecting => vec![
                 Line::from(""),
                 Line::from(Span::styled(
-                    "  Loading work item...",
-                    theme::styles::WARNING,
+                    format!("  {}", t(Text::Connecting)),
+                    theme::styles::warning(),
                 )),
-            ]
-        }
+            ],
+            ClientState::Ready => vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!("  {}", t(Text::LoadingWorkItem)),
+                    theme::styles::warning(),
+                )),
+            ],
+            ClientState::Unavailable(error) => {
+                let mut lines = vec![
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        format!("  {}", t(Text::NotConfigured)),
+                        theme::styles::warning().add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(""),
+                ];
+                append_wrapped_text(&mut lines, error, max_width, theme::styles::muted());
+                lines.push(Line::from(""));
+                append_wrapped_text(
+                    &mut lines,
+                    t(Text::RunConfigInit),
+                    max_width,
+                    theme::styles::muted(),
+                );
+                lines
+            }
+        },
         WorkItemStatus::Error(err) => {
             let mut lines = vec![Line::from("")];
             append_wrapped_text(
                 &mut lines,
-                &format!("Error: {}", err),
+                &tf(Text::Error, &[("error", err)]),
                 max_width,
                 Style::default().fg(Color::Red),
             );
             lines
         }
         WorkItemStatus::Loaded(wi) => {
-            let type_icon = wi.work_item_type.icon();
+            let type_icon = if app.emoji() {
+                wi.work_item_type.icon()
+            } else {
+                wi.work_item_type.ascii_icon()
+            };
             let type_name = wi.work_item_type.display_name();
-            let state_icon = wi.state.icon();
-            let state_name = wi.state.display_name();
-            let state_color = wi.state.color();
 
             // ID and Type
             let mut lines = vec![
@@ -119,38 +183,22 @@ fn render_work_item_details(frame: &mut Frame, app: &App, area: Rect, wi_id: u32
                     Span::styled("  ", Style::default()),
                     Span::styled(
                         format!("#{} ", wi.id),
-                        theme::styles::ACCENT.add_modifier(Modifier::BOLD),
+                        theme::styles::accent().add_modifier(Modifier::BOLD),
                     ),
                     Span::raw(format!("{} {}", type_icon, type_name)),
                 ]),
             ];
 
-            // Metadata line: State • Assigned To • Tags (directly under ID/Type)
-            let mut meta_spans = vec![
-                Span::styled("  ", Style::default()),
-                Span::styled(
-                    format!("{} {}", state_icon, state_name),
-                    Style::default().fg(state_color),
-                ),
-            ];
-
-            // Add assigned to if present
-            if let Some(ref assigned) = wi.assigned_to {
-                meta_spans.push(Span::styled("  •  ", theme::styles::MUTED));
-                meta_spans.push(Span::styled(assigned.clone(), theme::styles::TEXT));
+            // Metadata line: [display].fields (directly under ID/Type), with
+            // full area and iteration paths when they fit
+            let mut meta_line = metadata_line(app, wi, true);
+            if meta_line.width() > area.width as usize {
+                meta_line = metadata_line(app, wi, false);
             }
-
-            // Add tags if present
-            if !wi.tags.is_empty() {
-                meta_spans.push(Span::styled("  •  ", theme::styles::MUTED));
-                meta_spans.push(Span::styled(
-                    wi.tags.join(", "),
-                    Style::default().fg(Color::Magenta),
-                ));
+            if meta_line.spans.len() > 1 {
+                lines.push(meta_line);
             }
 
-            lines.push(Line::from(meta_spans));
-
             // Blank line before title
             lines.push(Line::from(""));
 
@@ -158,63 +206,547 @@ fn render_work_item_details(frame: &mut Frame, app: &App, area: Rect, wi_id: u32
                 &mut lines,
                 &wi.title,
                 max_width,
-                theme::styles::TEXT
+                theme::styles::text()
                     .add_modifier(Modifier::BOLD)
                     .add_modifier(Modifier::UNDERLINED),
             );
 
-            // All rich text fields (Description, Acceptance Criteria, etc.)
-            for field in &wi.rich_text_fields {
-                lines.push(Line::from(""));
-                lines.push(Line::from(vec![Span::styled(
-                    format!("  {}:", field.name),
-                    theme::styles::MUTED,
-                )]));
-
-                let field_width = max_width.saturating_sub(4);
-                let rendered = match field.format {
-                    FieldFormat::Html => render_html(&field.value, field_width),
-                    FieldFormat::Markdown => render_markdown(&field.value, field_width),
-                };
-                for rendered_line in rendered {
-                    // Add indent to each line
-                    let mut indented_spans = vec![Span::raw("    ")];
-                    indented_spans.extend(rendered_line.spans);
-                    lines.push(Line::from(indented_spans));
-                }
+            if let Some(history) = history_text(wi, app.plain()) {
+                append_wrapped_text(&mut lines, &history, max_width, theme::styles::muted());
             }
 
+            parent_line = append_hierarchy_lines(&mut lines, app, wi, max_width);
+
+            append_pull_request_lines(&mut lines, app, wi.id, max_width);
+
+            // All rich text fields (Description, Acceptance Criteria, etc.),
+            // rendered once per work item and width
+            fields = Some(match app.rendered_fields(wi.id, area.width) {
+                Some(lines) => Arc::clone(lines),
+                None => {
+                    let lines: Arc<[Line<'static>]> =
+                        rich_text_field_lines(app, wi, max_width).into();
+                    rendered_fields = Some(RenderedFields {
+                        work_item_id: wi.id,
+                        width: area.width,
+                        lines: Arc::clone(&lines),
+                    });
+                    lines
+                }
+            });
+            append_comment_lines(&mut comment_lines, app, wi.id, max_width);
+
             lines
         }
     };
+    let field_lines = fields.as_deref().unwrap_or_default();
 
     // Content height for scroll bounds (returned to the update loop).
-    let content_height = content.len() as u16;
+    let content_height = (content.len() + field_lines.len() + comment_lines.len()) as u16;
 
-    // Apply scroll offset
-    let paragraph = Paragraph::new(content).scroll((app.scroll_offset(), 0));
+    // Only the lines in view are handed to the paragraph, so a long
+    // description costs nothing while scrolled past.
+    let visible: Vec<Line> = content
+        .into_iter()
+        .chain(field_lines.iter().cloned())
+        .chain(comment_lines)
+        .skip(app.scroll_offset() as usize)
+        .take(area.height as usize)
+        .collect();
+    let paragraph = Paragraph::new(visible);
 
     frame.render_widget(paragraph, area);
 
+    let parent_link = parent_line.and_then(|(index, work_item_id)| {
+        let row = u16::try_from(index)
+            .ok()?
+            .checked_sub(app.scroll_offset())?;
+        (row < area.height).then_some(ParentLink {
+            work_item_id,
+            area: Rect::new(area.x, area.y + row, area.width, 1),
+        })
+    });
+
     // Render scrollbar
-    super::helpers::render_scrollbar(
-        frame,
-        area,
-        content_height as usize,
-        app.scroll_offset() as usize,
-    );
-
-    content_height
+    if !app.plain() {
+        super::helpers::render_scrollbar(
+            frame,
+            area,
+            content_height as usize,
+            app.scroll_offset() as usize,
+        );
+    }
+
+    (content_height, rendered_fields, parent_link)
+}
+
+/// The parent and children of `wi` under a heading, nothing when it has
+/// neither; the related work item to show next is highlighted while
+/// stepping through the hierarchy. Returns the index in `lines` and ID of
+/// the parent line.
+fn append_hierarchy_lines<'a>(
+    lines: &mut Vec<Line<'a>>,
+    app: &'a App,
+    wi: &WorkItem,
+    max_width: usize,
+) -> Option<(usize, u32)> {
+    let parent_id = wi.parent_id();
+    let children = match app.get_children(wi.id) {
+        Some(ChildrenStatus::Loaded(children)) if children.is_empty() => None,
+        children => children,
+    };
+    if parent_id.is_none() && children.is_none() {
+        return None;
+    }
+    let highlighted = app
+        .hierarchy()
+        .filter(|view| view.trail.last() == Some(&wi.id))
+        .map(|view| view.selected);
+    let heading = match (highlighted, app.keymap().label(Action::Hierarchy)) {
+        (Some(_), _) => t(Text::HierarchyKeys).to_string(),
+        (None, Some(key)) => tf(Text::HierarchyCollapsed, &[("key", key)]),
+        (None, None) => t(Text::HierarchyHeading).to_string(),
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {heading}"),
+        theme::styles::muted(),
+    )));
+
+    let mut related = 0;
+    let mut parent_line = None;
+    if let Some(parent_id) = parent_id {
+        parent_line = Some((lines.len(), parent_id));
+        let parent = match app.get_work_item_status(parent_id) {
+            WorkItemStatus::Loaded(parent) => RelatedStatus::Loaded(parent),
+            WorkItemStatus::NotFetched | WorkItemStatus::Loading => RelatedStatus::Loading,
+            WorkItemStatus::Error(_) => RelatedStatus::Failed,
+        };
+        lines.push(related_line(
+            app,
+            Text::ParentLabel,
+            parent_id,
+            parent,
+            highlighted == Some(related),
+        ));
+        related += 1;
+    }
+    match children {
+        None => {}
+        Some(ChildrenStatus::Loading) => lines.push(Line::from(Span::styled(
+            format!("    {}", t(Text::LoadingChildren)),
+            theme::styles::warning(),
+        ))),
+        Some(ChildrenStatus::Error(error)) => append_wrapped_text(
+            lines,
+            &error_text(app.plain(), tf(Text::ChildrenError, &[("error", error)])),
+            max_width,
+            theme::styles::error(),
+        ),
+        Some(ChildrenStatus::Loaded(children)) => {
+            for child in children {
+                // A child fetched again since, e.g. after a state change
+                let child = match app.get_work_item_status(child.id) {
+                    WorkItemStatus::Loaded(work_item) => work_item,
+                    _ => child,
+                };
+                lines.push(related_line(
+                    app,
+                    Text::ChildLabel,
+                    child.id,
+                    RelatedStatus::Loaded(child),
+                    highlighted == Some(related),
+                ));
+                related += 1;
+            }
+        }
+    }
+    parent_line
+}
+
+/// What is known of a related work item
+enum RelatedStatus<'a> {
+    Loaded(&'a WorkItem),
+    Loading,
+    Failed,
+}
+
+/// `Parent: #1234 Checkout flow redesign (Active)`, or only the ID until the
+/// work item has loaded, marked when `highlighted`.
+fn related_line<'a>(
+    app: &App,
+    label: Text,
+    id: u32,
+    status: RelatedStatus<'a>,
+    highlighted: bool,
+) -> Line<'a> {
+    let marker = match (highlighted, app.plain()) {
+        (false, _) => "    ".to_string(),
+        (true, true) => format!("    {} ", t(Text::SelectedLabel)),
+        (true, false) => "  \u{25BA} ".to_string(),
+    };
+    let mut spans = vec![
+        Span::raw(marker),
+        Span::styled(t(label), theme::styles::muted()),
+        Span::styled(
+            format!("#{id}"),
+            theme::styles::accent().add_modifier(Modifier::UNDERLINED),
+        ),
+    ];
+    match status {
+        RelatedStatus::Loaded(work_item) => {
+            spans.push(Span::styled(
+                format!(" {}", work_item.title),
+                theme::styles::text(),
+            ));
+            spans.push(Span::styled(
+                format!(" ({})", work_item.state.display_name()),
+                Style::default().fg(work_item.state.color()),
+            ));
+        }
+        RelatedStatus::Loading => {
+            spans.push(Span::styled(
+                format!(" {}", t(Text::LoadingLower)),
+                theme::styles::muted(),
+            ));
+        }
+        RelatedStatus::Failed => {}
+    }
+    let line = Line::from(spans);
+    if highlighted {
+        line.style(theme::ui::selected().add_modifier(Modifier::BOLD))
+    } else {
+        line
+    }
+}
+
+/// The `[display].fields` of `wi` on one line, after an indent. Area and
+/// iteration paths are shortened to their last node unless `full_paths`.
+fn metadata_line<'a>(app: &App, wi: &'a WorkItem, full_paths: bool) -> Line<'a> {
+    let mut spans = vec![Span::styled("  ", Style::default())];
+    for field in app.detail_fields() {
+        let field_spans = match field {
+            DetailField::State => {
+                let icon = if app.emoji() {
+                    wi.state.icon()
+                } else {
+                    wi.state.ascii_icon()
+                };
+                let mut state_spans = vec![Span::styled(
+                    format!("{} {}", icon, wi.state.display_name()),
+                    Style::default().fg(wi.state.color()),
+                )];
+                if let Some(estimate) = wi.estimate(app.estimate_field()) {
+                    state_spans.push(Span::raw(" "));
+                    state_spans.push(Span::styled(
+                        format!("[{estimate}]"),
+                        theme::styles::accent(),
+                    ));
+                }
+                state_spans
+            }
+            DetailField::AssignedTo => match &wi.assigned_to {
+                Some(assigned) => vec![Span::styled(assigned.as_str(), theme::styles::text())],
+                None => continue,
+            },
+            DetailField::Tags if wi.tags.is_empty() => continue,
+            DetailField::Tags => vec![Span::styled(
+                wi.tags.join(", "),
+                Style::default().fg(Color::Magenta),
+            )],
+            DetailField::Priority => match wi.priority() {
+                Some(priority) => vec![
+                    Span::styled(t(Text::PriorityLabel), theme::styles::muted()),
+                    Span::styled(
+                        priority.to_string(),
+                        Style::default()
+                            .fg(urgency_color(priority))
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ],
+                None => continue,
+            },
+            DetailField::Severity => match (wi.severity(), wi.severity_rank()) {
+                (Some(severity), rank) => vec![
+                    Span::styled(t(Text::SeverityLabel), theme::styles::muted()),
+                    Span::styled(
+                        severity,
+                        rank.map_or_else(theme::styles::text, |rank| {
+                            Style::default().fg(urgency_color(rank))
+                        }),
+                    ),
+                ],
+                (None, _) => continue,
+            },
+            DetailField::Iteration => match wi.iteration_path() {
+                Some(path) => path_spans("Iteration", path, full_paths),
+                None => continue,
+            },
+            DetailField::Area => match wi.area_path() {
+                Some(path) => path_spans("Area", path, full_paths),
+                None => continue,
+            },
+            DetailField::Field { reference, label } => match wi.fields.get(reference) {
+                Some(value) => vec![
+                    Span::styled(format!("{label}: "), theme::styles::muted()),
+                    Span::styled(field_value_text(value), theme::styles::text()),
+                ],
+                None => continue,
+            },
+            DetailField::Deadline { reference, label } => match wi.fields.get(reference) {
+                Some(value) => {
+                    let mut deadline_spans =
+                        vec![Span::styled(format!("{label}: "), theme::styles::muted())];
+                    if wi.is_past_deadline(reference, Utc::now()) {
+                        deadline_spans.push(Span::styled(
+                            field_value_text(value),
+                            theme::styles::error().add_modifier(Modifier::BOLD),
+                        ));
+                        if app.plain() {
+                            deadline_spans.push(Span::raw(format!(" {}", t(Text::OverdueLabel))));
+                        }
+                    } else {
+                        deadline_spans
+                            .push(Span::styled(field_value_text(value), theme::styles::text()));
+                    }
+                    deadline_spans
+                }
+                None => continue,
+            },
+        };
+        if spans.len() > 1 {
+            spans.push(Span::styled(
+                separator(app.plain(), "  •  "),
+                theme::styles::muted(),
+            ));
+        }
+        spans.extend(field_spans);
+    }
+    Line::from(spans)
+}
+
+/// `label: path`, with only the last node of the path (`Sprint 3` for
+/// `Web\Release 2\Sprint 3`) unless `full_path`.
+fn path_spans<'a>(label: &str, path: &'a str, full_path: bool) -> Vec<Span<'a>> {
+    let path = if full_path {
+        path
+    } else {
+        path.rsplit('\\').next().unwrap_or(path)
+    };
+    vec![
+        Span::styled(format!("{label}: "), theme::styles::muted()),
+        Span::styled(path, theme::styles::text()),
+    ]
+}
+
+/// The pull requests linked to work item `wi_id`, under a heading; nothing
+/// before they are fetched or when there are none.
+fn append_pull_request_lines(lines: &mut Vec<Line>, app: &App, wi_id: u32, max_width: usize) {
+    let status = match app.get_pull_requests(wi_id) {
+        None => return,
+        Some(PullRequestsStatus::Loaded(prs)) if prs.is_empty() => return,
+        Some(status) => status,
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {}", t(Text::PullRequestsHeading)),
+        theme::styles::muted(),
+    )));
+    match status {
+        PullRequestsStatus::Loading => lines.push(Line::from(Span::styled(
+            format!("    {}", t(Text::LoadingPullRequests)),
+            theme::styles::warning(),
+        ))),
+        PullRequestsStatus::Error(error) => append_wrapped_text(
+            lines,
+            &error_text(
+                app.plain(),
+                tf(Text::PullRequestsError, &[("error", error)]),
+            ),
+            max_width,
+            theme::styles::error(),
+        ),
+        PullRequestsStatus::Loaded(prs) => {
+            for pr in prs {
+                lines.push(pull_request_line(pr, app.plain()));
+            }
+        }
+    }
+}
+
+/// `!42 active → main  Login page  (2 approved, 1 waiting)`
+fn pull_request_line(pr: &PullRequest, plain: bool) -> Line<'static> {
+    let (status, style) = pull_request_status(pr);
+    let arrow = if plain { "->" } else { "→" };
+    Line::from(vec![
+        Span::raw("    "),
+        Span::styled(
+            format!("!{} ", pr.id),
+            theme::styles::accent().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(status.to_string(), style),
+        Span::styled(
+            format!(" {arrow} {}", pr.target_branch),
+            theme::styles::muted(),
+        ),
+        Span::raw("  "),
+        Span::styled(pr.title.clone(), theme::styles::text()),
+        Span::styled(format!("  ({})", pr.vote_summary()), theme::styles::muted()),
+    ])
+}
+
+/// The rich text fields of `wi`, each under its name and indented.
+fn rich_text_field_lines(app: &App, wi: &WorkItem, max_width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for field in &wi.rich_text_fields {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            format!("  {}:", field.name),
+            theme::styles::muted(),
+        )]));
+
+        append_rich_text(&mut lines, app, &field.value, field.format, max_width);
+    }
+    lines
+}
+
+/// `text` rendered from its format and indented under a heading.
+fn append_rich_text(
+    lines: &mut Vec<Line<'static>>,
+    app: &App,
+    text: &str,
+    format: FieldFormat,
+    max_width: usize,
+) {
+    let width = max_width.saturating_sub(4);
+    let mut rendered = match format {
+        FieldFormat::Html => render_html(text, width),
+        FieldFormat::Markdown => render_markdown(text, width),
+    };
+    if !app.emoji() {
+        rendered = ascii_lines(rendered);
+    }
+    for rendered_line in rendered {
+        // Add indent to each line
+        let mut indented_spans = vec![Span::raw("    ")];
+        indented_spans.extend(rendered_line.spans);
+        lines.push(Line::from(indented_spans));
+    }
+}
+
+/// The comments section of work item `wi_id`: only its heading, naming the
+/// key that shows it, until the section is expanded; then each comment,
+/// newest first, under its author and when it was posted.
+fn append_comment_lines(lines: &mut Vec<Line<'static>>, app: &App, wi_id: u32, max_width: usize) {
+    let expanded = app.comments_expanded();
+    let status = app.get_comments(wi_id).filter(|_| expanded);
+    let marker = match (app.plain(), expanded) {
+        (true, _) => "",
+        (false, true) => "\u{25BE} ",
+        (false, false) => "\u{25B8} ",
+    };
+    let heading = match (status, app.keymap().label(Action::ToggleComments)) {
+        (_, Some(key)) if !expanded => tf(Text::CommentsCollapsed, &[("key", key)]),
+        (Some(CommentsStatus::Loaded(comments)), _) => tf(
+            Text::CommentsHeading,
+            &[("count", &comments.len().to_string())],
+        ),
+        _ => t(Text::CommentsTitle).to_string(),
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {marker}{heading}"),
+        theme::styles::muted(),
+    )));
+
+    match status {
+        None if !expanded => {}
+        None | Some(CommentsStatus::Loading) => lines.push(Line::from(Span::styled(
+            format!("    {}", t(Text::LoadingComments)),
+            theme::styles::warning(),
+        ))),
+        Some(CommentsStatus::Error(error)) => append_wrapped_text(
+            lines,
+            &error_text(app.plain(), tf(Text::CommentsError, &[("error", error)])),
+            max_width,
+            theme::styles::error(),
+        ),
+        Some(CommentsStatus::Loaded(comments)) if comments.is_empty() => {
+            lines.push(Line::from(Span::styled(
+                format!("    {}", t(Text::NoComments)),
+                theme::styles::muted().add_modifier(Modifier::ITALIC),
+            )));
+        }
+        Some(CommentsStatus::Loaded(comments)) => {
+            for comment in comments {
+                let mut byline = vec![
+                    Span::raw("    "),
+                    Span::styled(
+                        comment
+                            .author
+                            .clone()
+                            .unwrap_or_else(|| t(Text::Unknown).to_string()),
+                        theme::styles::accent().add_modifier(Modifier::BOLD),
+                    ),
+                ];
+                if let Some(created_at) = comment.created_at {
+                    byline.push(Span::styled(
+                        format!(
+                            "{}{}",
+                            separator(app.plain(), " \u{b7} "),
+                            format_relative_time(created_at)
+                        ),
+                        theme::styles::muted(),
+                    ));
+                }
+                lines.push(Line::from(byline));
+                append_rich_text(lines, app, &comment.text, comment.format, max_width);
+            }
+        }
+    }
+}
+
+/// When and by whom `wi` was created and last changed, e.g. `created 3 weeks
+/// ago by Ada · updated 2 days ago by Grace`, so a work item left alone for
+/// months stands out. Times follow the `[display]` timestamp settings.
+fn history_text(wi: &WorkItem, plain: bool) -> Option<String> {
+    let event = |verb: Text, at: Option<i64>, by: Option<&str>| {
+        let mut text = tf(verb, &[("time", &format_timestamp(at?)?)]);
+        if let Some(by) = by {
+            text.push(' ');
+            text.push_str(&tf(Text::By, &[("name", by)]));
+        }
+        Some(text)
+    };
+    let events: Vec<String> = [
+        event(Text::Created, wi.created_at(), wi.created_by()),
+        event(Text::Updated, wi.changed_at(), wi.changed_by()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    (!events.is_empty()).then(|| events.join(separator(plain, " · ")))
+}
+
+/// A field value for the metadata line; date fields follow the `[display]`
+/// timestamp settings.
+fn field_value_text(value: &str) -> String {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .and_then(|time| format_timestamp(time.timestamp()))
+        .unwrap_or_else(|| value.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::azure_devops::{RichTextField, WorkItem, WorkItemState, WorkItemType};
+    use crate::azure_devops::{Comment, RichTextField, WorkItem, WorkItemState, WorkItemType};
+    use crate::config::EstimateField;
     use crate::git::BranchScope;
     use crate::tui::app::{BranchInfo, Msg};
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
+    use std::collections::BTreeMap;
 
     fn work_item_with(fields: Vec<RichTextField>) -> WorkItem {
         WorkItem {
@@ -226,6 +758,7 @@ mod tests {
             url: None,
             tags: vec![],
             rich_text_fields: fields,
+            fields: BTreeMap::new(),
         }
     }
 
@@ -240,6 +773,7 @@ mod tests {
             is_current: false,
             is_protected: false,
             is_stale: false,
+            origin_upstream: None,
         }
     }
 
@@ -260,6 +794,32 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn comments_section_shows_its_key_until_expanded() {
+        let mut app = App::new(vec![branch_linked_to(204)], vec![]);
+        app.set_work_item_loaded(204, work_item_with(vec![]));
+        assert!(rendered_text(&app).contains("Comments (c to show)"));
+
+        app.update(Msg::ToggleComments);
+        assert!(rendered_text(&app).contains("Loading comments..."));
+        app.set_comments(
+            204,
+            Ok(vec![Comment {
+                id: 1,
+                author: Some("Grace Hopper".to_string()),
+                created_at: None,
+                text: "<div>Fixed in <b>!42</b></div>".to_string(),
+                format: FieldFormat::Html,
+            }]),
+        );
+
+        let text = rendered_text(&app);
+        assert!(text.contains("Comments (1)"), "got: {text:?}");
+        assert!(text.contains("Grace Hopper"), "got: {text:?}");
+        assert!(text.contains("Fixed in !42"), "got: {text:?}");
+        assert!(!text.contains("<b>"), "raw HTML leaked: {text:?}");
+    }
+
     #[test]
     fn markdown_field_is_rendered_not_shown_as_raw_source() {
         let mut app = App::new(vec![branch_linked_to(204)], vec![]);
@@ -307,4 +867,392 @@ mod tests {
         assert!(!text.contains("_markdownish_"), "markdown leaked: {text:?}");
         assert!(!text.contains("<b>"), "html leaked: {text:?}");
     }
+
+    #[test]
+    fn unconfigured_client_explains_why_work_items_are_missing() {
+        let mut app = App::new(vec![branch_linked_to(204)], vec![]);
+        assert!(rendered_text(&app).contains("Connecting to Azure DevOps..."));
+
+        app.set_client_unavailable("Config file not found".to_string());
+        let text = rendered_text(&app);
+
+        assert!(
+            text.contains("Azure DevOps is not configured"),
+            "got: {text:?}"
+        );
+        assert!(text.contains("Config file not found"), "got: {text:?}");
+        assert!(text.contains("cazdo config init"), "got: {text:?}");
+    }
+
+    #[test]
+    fn ascii_mode_replaces_icons_and_field_emoji() {
+        let mut app = App::new(vec![branch_linked_to(204)], vec![]).with_emoji(false);
+        app.update(Msg::SetWorkItemLoaded {
+            id: 204,
+            work_item: work_item_with(vec![RichTextField {
+                name: "Description".to_string(),
+                value: "<p>shipped ✅</p>".to_string(),
+                format: FieldFormat::Html,
+            }]),
+        });
+
+        let text = rendered_text(&app);
+
+        assert!(text.contains("[P] Product Backlog Item"), "got: {text:?}");
+        assert!(text.contains("[ ] New"), "got: {text:?}");
+        assert!(text.contains("shipped [x]"), "got: {text:?}");
+        assert!(!text.contains('✅'), "emoji leaked: {text:?}");
+    }
+
+    #[test]
+    fn detail_fields_set_metadata_order_and_custom_fields() {
+        let fields = ["Iteration", "Custom.ReleaseTrain", "State", "Priority"]
+            .iter()
+            .map(|name| DetailField::parse(name).unwrap())
+            .collect();
+        let mut app = App::new(vec![branch_linked_to(204)], vec![]).with_detail_fields(fields);
+        let mut work_item = work_item_with(vec![]);
+        work_item.tags = vec!["hidden-tag".to_string()];
+        work_item.fields = BTreeMap::from([
+            (
+                "System.IterationPath".to_string(),
+                "Web\\Sprint 3".to_string(),
+            ),
+            ("Custom.ReleaseTrain".to_string(), "R7".to_string()),
+        ]);
+        app.update(Msg::SetWorkItemLoaded { id: 204, work_item });
+
+        let text = rendered_text(&app);
+
+        assert!(
+            text.contains("Iteration: Web\\Sprint 3  •  ReleaseTrain: R7  •  🆕"),
+            "got: {text:?}"
+        );
+        assert!(!text.contains("hidden-tag"), "tags not listed: {text:?}");
+        assert!(!text.contains("Priority"), "unset field shown: {text:?}");
+    }
+
+    #[test]
+    fn area_and_iteration_paths_are_shortened_when_the_line_does_not_fit() {
+        let mut app = App::new(vec![branch_linked_to(204)], vec![]);
+        let mut work_item = work_item_with(vec![]);
+        work_item.fields = BTreeMap::from([
+            (
+                "System.IterationPath".to_string(),
+                "Web\\Release 2\\Sprint 3".to_string(),
+            ),
+            ("System.AreaPath".to_string(), "Web\\Checkout".to_string()),
+        ]);
+        app.update(Msg::SetWorkItemLoaded { id: 204, work_item });
+
+        let text = rendered_text(&app);
+        assert!(
+            text.contains("Iteration: Web\\Release 2\\Sprint 3  •  Area: Web\\Checkout"),
+            "got: {text:?}"
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 30)).expect("terminal");
+        terminal
+            .draw(|frame| {
+                render_details(frame, &app, frame.area());
+            })
+            .expect("draw");
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(
+            text.contains("Iteration: Sprint 3  •  Area: Checkout"),
+            "got: {text:?}"
+        );
+    }
+
+    #[test]
+    fn estimate_badge_follows_the_state() {
+        let work_item = || {
+            let mut work_item = work_item_with(vec![]);
+            work_item.fields = BTreeMap::from([
+                (
+                    "Microsoft.VSTS.Scheduling.Effort".to_string(),
+                    "8".to_string(),
+                ),
+                (
+                    "Microsoft.VSTS.Scheduling.OriginalEstimate".to_string(),
+                    "2.5".to_string(),
+                ),
+            ]);
+            work_item
+        };
+        let mut app = App::new(vec![branch_linked_to(204)], vec![]);
+        app.update(Msg::SetWorkItemLoaded {
+            id: 204,
+            work_item: work_item(),
+        });
+        let text = rendered_text(&app);
+        assert!(text.contains("New [effort 8]"), "got: {text:?}");
+
+        let mut app = App::new(vec![branch_linked_to(204)], vec![])
+            .with_estimate_field(EstimateField::OriginalEstimate);
+        app.update(Msg::SetWorkItemLoaded {
+            id: 204,
+            work_item: work_item(),
+        });
+        let text = rendered_text(&app);
+        assert!(text.contains("New [est 2.5h]"), "got: {text:?}");
+
+        let mut app = App::new(vec![branch_linked_to(204)], vec![])
+            .with_estimate_field(EstimateField::StoryPoints);
+        app.update(Msg::SetWorkItemLoaded {
+            id: 204,
+            work_item: work_item(),
+        });
+        assert!(!rendered_text(&app).contains('['));
+    }
+
+    #[test]
+    fn priority_and_severity_are_colored_by_rank() {
+        let mut work_item = work_item_with(vec![]);
+        work_item.fields = BTreeMap::from([
+            (
+                "Microsoft.VSTS.Common.Priority".to_string(),
+                "1".to_string(),
+            ),
+            (
+                "Microsoft.VSTS.Common.Severity".to_string(),
+                "3 - Medium".to_string(),
+            ),
+        ]);
+        let app = App::new(vec![branch_linked_to(204)], vec![]);
+
+        let line = metadata_line(&app, &work_item, true);
+        let color = |content: &str| {
+            line.spans
+                .iter()
+                .find(|span| span.content == content)
+                .and_then(|span| span.style.fg)
+        };
+        assert_eq!(color("1"), Some(Color::Red));
+        assert_eq!(color("3 - Medium"), Some(Color::Blue));
+        let text: String = line
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert!(text.contains("Priority: 1"), "got: {text:?}");
+    }
+
+    #[test]
+    fn past_target_dates_are_highlighted_until_the_item_is_closed() {
+        let mut work_item = work_item_with(vec![]);
+        work_item.state = WorkItemState::Active;
+        work_item.fields = BTreeMap::from([
+            (
+                "Microsoft.VSTS.Scheduling.TargetDate".to_string(),
+                "2020-01-31T00:00:00Z".to_string(),
+            ),
+            (
+                "Microsoft.VSTS.Scheduling.DueDate".to_string(),
+                "2999-01-31T00:00:00Z".to_string(),
+            ),
+        ]);
+        let app = App::new(vec![branch_linked_to(204)], vec![]);
+        let highlighted = |work_item: &WorkItem| {
+            let line = metadata_line(&app, work_item, true);
+            line.spans
+                .windows(2)
+                .filter(|pair| pair[1].style == theme::styles::error().add_modifier(Modifier::BOLD))
+                .map(|pair| pair[0].content.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(work_item.is_overdue(Utc::now()));
+        assert_eq!(highlighted(&work_item), ["Target: "]);
+
+        work_item.state = WorkItemState::Done;
+        assert!(!work_item.is_overdue(Utc::now()));
+        assert!(highlighted(&work_item).is_empty());
+    }
+
+    #[test]
+    fn history_line_shows_when_and_by_whom_under_the_title() {
+        let mut work_item = work_item_with(vec![]);
+        work_item.fields = BTreeMap::from([
+            (
+                "System.CreatedDate".to_string(),
+                "2020-03-01T09:00:00Z".to_string(),
+            ),
+            ("System.CreatedBy".to_string(), "Grace Hopper".to_string()),
+            (
+                "System.ChangedDate".to_string(),
+                "2020-03-02T09:00:00.123Z".to_string(),
+            ),
+        ]);
+        let text = history_text(&work_item, false).unwrap();
+        assert!(text.starts_with("created "), "got: {text:?}");
+        assert!(
+            text.contains(" ago by Grace Hopper · updated "),
+            "got: {text:?}"
+        );
+        assert!(text.ends_with(" ago"), "got: {text:?}");
+
+        work_item.fields.clear();
+        assert_eq!(history_text(&work_item, false), None);
+    }
+
+    #[test]
+    fn parent_line_summarizes_the_parent_and_is_clickable_while_in_view() {
+        let mut work_item = work_item_with(vec![]);
+        work_item.fields = BTreeMap::from([("System.Parent".to_string(), "7".to_string())]);
+        let mut app = App::new(vec![branch_linked_to(204)], vec![]);
+        app.update(Msg::SetWorkItemLoaded { id: 204, work_item });
+        assert!(rendered_text(&app).contains("Parent: #7 loading..."));
+
+        let mut parent = work_item_with(vec![]);
+        parent.id = 7;
+        parent.title = "Checkout flow redesign".to_string();
+        parent.state = WorkItemState::Active;
+        app.update(Msg::SetWorkItemLoaded {
+            id: 7,
+            work_item: parent,
+        });
+        let text = rendered_text(&app);
+        assert!(
+            text.contains("Parent: #7 Checkout flow redesign (Active)"),
+            "got: {text:?}"
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).expect("terminal");
+        let mut metrics = DetailsMetrics::default();
+        terminal
+            .draw(|frame| metrics = render_details(frame, &app, frame.area()))
+            .expect("draw");
+        let link = metrics.parent_link.expect("parent line in view");
+        assert_eq!(link.work_item_id, 7);
+        let row = link.area.y;
+        app.update(Msg::SetDetailsMetrics(metrics));
+        assert_eq!(app.parent_link_at(10, row), Some(7));
+        assert_eq!(app.parent_link_at(10, row + 1), None);
+    }
+
+    #[test]
+    fn hierarchy_lists_children_and_the_way_stepped_into() {
+        let mut app = App::new(vec![branch_linked_to(204)], vec![]);
+        app.set_work_item_loaded(204, work_item_with(vec![]));
+        assert!(!rendered_text(&app).contains("Hierarchy"));
+
+        app.set_children_loading(204);
+        assert!(rendered_text(&app).contains("Loading child work items..."));
+        let mut child = work_item_with(vec![]);
+        child.id = 310;
+        child.title = "Payment form".to_string();
+        child.state = WorkItemState::Closed;
+        app.set_children(204, Ok(vec![child]));
+        let text = rendered_text(&app);
+        assert!(
+            text.contains("Hierarchy (h to step through):"),
+            "got: {text:?}"
+        );
+        assert!(
+            text.contains("Child: #310 Payment form (Closed)"),
+            "got: {text:?}"
+        );
+
+        app.update(Msg::OpenHierarchy);
+        let text = rendered_text(&app);
+        assert!(text.contains("► Child: #310"), "got: {text:?}");
+        app.update(Msg::EnterRelatedWorkItem);
+        let text = rendered_text(&app);
+        assert!(
+            text.contains("Work Item Details #204 › #310"),
+            "got: {text:?}"
+        );
+        assert!(text.contains("Payment form"), "got: {text:?}");
+    }
+
+    #[test]
+    fn linked_pull_requests_are_listed_with_their_votes() {
+        let mut app = App::new(vec![branch_linked_to(204)], vec![]);
+        app.update(Msg::SetWorkItemLoaded {
+            id: 204,
+            work_item: work_item_with(vec![]),
+        });
+        assert!(!rendered_text(&app).contains("Pull Requests"));
+
+        app.set_pull_requests_loading(204);
+        assert!(rendered_text(&app).contains("Loading pull requests..."));
+
+        app.set_pull_requests(
+            204,
+            Ok(vec![PullRequest {
+                id: 31,
+                title: "Sample change".to_string(),
+                status: "active".to_string(),
+                is_draft: false,
+                source_branch: "feature/wi".to_string(),
+                target_branch: "main".to_string(),
+                created_by: None,
+                project_id: None,
+                repository_id: None,
+                last_merge_source_commit: None,
+                reviewers: vec![],
+                url: None,
+            }]),
+        );
+        let text = rendered_text(&app);
+        assert!(text.contains("Pull Requests:"), "got: {text:?}");
+        assert!(
+            text.contains("!31 active → main  Sample change  (no reviewers)"),
+            "got: {text:?}"
+        );
+
+        app.set_pull_requests(204, Err("HTTP 401".to_string()));
+        assert!(rendered_text(&app).contains("Could not load pull requests: HTTP 401"));
+    }
+
+    #[test]
+    fn rich_text_fields_are_rendered_once_until_the_work_item_changes() {
+        let description = |value: &str| RichTextField {
+            name: "Description".to_string(),
+            value: value.to_string(),
+            format: FieldFormat::Html,
+        };
+        let paragraphs: String = (1..=40).map(|n| format!("<p>line {n}</p>")).collect();
+        let mut app = App::new(vec![branch_linked_to(204)], vec![]);
+        app.update(Msg::SetWorkItemLoaded {
+            id: 204,
+            work_item: work_item_with(vec![description(&paragraphs)]),
+        });
+
+        let draw = |app: &App| {
+            let mut terminal = Terminal::new(TestBackend::new(80, 30)).expect("terminal");
+            let mut metrics = DetailsMetrics::default();
+            terminal
+                .draw(|frame| metrics = render_details(frame, app, frame.area()))
+                .expect("draw");
+            metrics
+        };
+        let metrics = draw(&app);
+        assert!(metrics.rendered_fields.is_some());
+        assert!(metrics.content_height > 40);
+        app.update(Msg::SetDetailsMetrics(metrics));
+
+        assert!(draw(&app).rendered_fields.is_none());
+        app.update(Msg::ScrollDown(20));
+        let text = rendered_text(&app);
+        assert!(text.contains("line 40"), "got: {text:?}");
+        assert!(!text.contains("line 1 "), "scrolled past: {text:?}");
+        app.update(Msg::ScrollUp(20));
+
+        app.update(Msg::SetWorkItemLoaded {
+            id: 204,
+            work_item: work_item_with(vec![description("<p>updated</p>")]),
+        });
+        let metrics = draw(&app);
+        assert!(metrics.rendered_fields.is_some());
+        app.update(Msg::SetDetailsMetrics(metrics));
+        assert!(rendered_text(&app).contains("updated"));
+    }
 }
@@ -6,9 +6,13 @@ use ratatui::{
     widgets::Paragraph,
 };
 
-use crate::tui::app::{App, BranchView, StatusMessage};
+use crate::i18n::{Text, t, tf};
+use crate::tui::app::{App, BranchView, GitFetchState, StatusMessage};
+use crate::tui::keymap::{Action, Keymap};
 use crate::tui::theme;
 
+use super::helpers::error_text;
+
 enum FooterVariant<'a> {
     FilterInput,
     Status(&'a StatusMessage),
@@ -18,8 +22,8 @@ enum FooterVariant<'a> {
 /// Render the footer bar with status messages or key hints
 pub fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
     match footer_variant(app) {
-        FooterVariant::FilterInput => render_filter_footer(frame, area),
-        FooterVariant::Status(msg) => render_status_footer(frame, area, msg),
+        FooterVariant::FilterInput => render_filter_footer(frame, app, area),
+        FooterVariant::Status(msg) => render_status_footer(frame, area, msg, app.plain()),
         FooterVariant::Normal => render_normal_footer(frame, app, area),
     }
 }
@@ -34,87 +38,189 @@ fn footer_variant(app: &App) -> FooterVariant<'_> {
     }
 }
 
-fn render_filter_footer(frame: &mut Frame, area: Rect) {
-    let help_text = Line::from(vec![
-        key_span(" type "),
-        label_span("filter  "),
+fn render_filter_footer(frame: &mut Frame, app: &App, area: Rect) {
+    render_footer_line(
+        frame,
+        area,
+        Line::from(filter_footer_spans(app.filter_input())),
+        theme::styles::muted(),
+    );
+}
+
+/// The filter being typed, then the keys that edit it.
+fn filter_footer_spans(draft: &str) -> Vec<Span<'static>> {
+    vec![
+        key_span(" /"),
+        Span::styled(format!("{draft}  "), theme::styles::text()),
+        key_span(format!("{} ", t(Text::FooterType))),
+        label_span(format!("{}  ", t(Text::FooterFilter))),
         key_span("backspace"),
-        label_span(" delete  "),
+        label_span(format!(" {}  ", t(Text::FooterDelete))),
         key_span("ctrl+u"),
-        label_span(" clear  "),
+        label_span(format!(" {}  ", t(Text::FooterClear))),
         key_span("enter"),
-        label_span(" apply  "),
+        label_span(format!(" {}  ", t(Text::FooterApply))),
         key_span("esc"),
-        label_span(" cancel"),
-    ]);
-
-    render_footer_line(frame, area, help_text, theme::styles::MUTED);
+        label_span(format!(" {}", t(Text::FooterCancel))),
+    ]
 }
 
-fn render_status_footer(frame: &mut Frame, area: Rect, msg: &StatusMessage) {
-    let style = if msg.is_error {
-        theme::styles::ERROR
+fn render_status_footer(frame: &mut Frame, area: Rect, msg: &StatusMessage, plain: bool) {
+    let (text, style) = if msg.is_error {
+        (error_text(plain, &msg.text), theme::styles::error())
     } else {
-        theme::styles::SUCCESS.add_modifier(Modifier::BOLD)
+        (
+            msg.text.clone(),
+            theme::styles::success().add_modifier(Modifier::BOLD),
+        )
     };
 
-    let paragraph = Paragraph::new(Line::from(vec![Span::styled(&msg.text, style)]));
+    let paragraph = Paragraph::new(Line::from(vec![Span::styled(text, style)]));
     frame.render_widget(paragraph, area);
 }
 
 fn render_normal_footer(frame: &mut Frame, app: &App, area: Rect) {
     let spans = normal_footer_spans(app);
 
-    render_footer_line(frame, area, Line::from(spans), theme::styles::MUTED);
+    render_footer_line(frame, area, Line::from(spans), theme::styles::muted());
 }
 
 fn normal_footer_spans(app: &App) -> Vec<Span<'static>> {
     let toggle_label = match app.active_view() {
-        BranchView::Local => "remote",
-        BranchView::Remote => "local",
+        BranchView::Local => t(Text::FooterToggleRemote),
+        BranchView::Remote => t(Text::FooterToggleLocal),
     };
 
+    let keymap = app.keymap();
     let mut spans = Vec::new();
     spans.push(label_span(" "));
-    push_hint(&mut spans, "j/k", "navigate");
-    push_hint(&mut spans, "/", "filter");
-    push_hint(&mut spans, "t", format!("toggle {}", toggle_label));
-    push_hint(&mut spans, "o", "open");
-    push_hint(&mut spans, "pg↑↓", "scroll");
-    push_hint(&mut spans, "d", "delete");
+    push_action_hint(
+        &mut spans,
+        key_pair(keymap, Action::Next, Action::Previous),
+        t(Text::FooterNavigate),
+    );
+    push_action_hint(
+        &mut spans,
+        key_pair(keymap, Action::Filter, None),
+        t(Text::FooterFilter),
+    );
+    push_action_hint(
+        &mut spans,
+        key_pair(keymap, Action::ToggleView, None),
+        toggle_label,
+    );
+    push_action_hint(
+        &mut spans,
+        key_pair(keymap, Action::Open, None),
+        t(Text::FooterOpen),
+    );
+    // Arrows are read out as symbols; plain mode names the keys
+    let scroll_keys = key_pair(keymap, Action::PageUp, Action::PageDown).map(|keys| {
+        if app.plain() && keys == "pg↑↓" {
+            "pageup/pagedown".to_string()
+        } else {
+            keys
+        }
+    });
+    push_action_hint(&mut spans, scroll_keys, t(Text::FooterScroll));
+    push_action_hint(
+        &mut spans,
+        key_pair(keymap, Action::Delete, None),
+        t(Text::FooterDelete),
+    );
     if app.current_branch_has_work_item() {
-        push_hint(&mut spans, "r", "refresh");
+        push_action_hint(
+            &mut spans,
+            key_pair(keymap, Action::Refresh, None),
+            t(Text::FooterRefresh),
+        );
+    }
+    push_action_hint(
+        &mut spans,
+        key_pair(keymap, Action::ToggleProtected, None),
+        t(Text::FooterProtected),
+    );
+    let active_filter =
+        Some(app.effective_branch_filter().trim()).filter(|_| app.has_active_filter());
+    spans.extend(normal_footer_tail(keymap, active_filter));
+    if let GitFetchState::Running(progress) = app.git_fetch() {
+        let label = match progress {
+            Some(progress) => tf(
+                Text::FooterFetchProgress,
+                &[
+                    ("phase", &progress.phase.to_lowercase()),
+                    ("percent", &progress.percent.to_string()),
+                ],
+            ),
+            None => t(Text::FooterFetching).to_string(),
+        };
+        spans.push(Span::styled(label, theme::styles::accent()));
+        spans.push(label_span("  "));
+    }
+    if let Some(version) = app.update_hint() {
+        spans.push(Span::styled(
+            tf(Text::FooterUpdateAvailable, &[("version", version)]),
+            theme::styles::warning(),
+        ));
     }
-    push_hint(&mut spans, "p", "protected");
-    spans.extend(normal_footer_tail(app.has_active_filter()));
 
     spans
 }
 
-fn normal_footer_tail(has_active_filter: bool) -> Vec<Span<'static>> {
+/// Quitting, or clearing the active filter, named so it stays visible.
+fn normal_footer_tail(keymap: &Keymap, active_filter: Option<&str>) -> Vec<Span<'static>> {
     let mut spans = Vec::new();
-    if has_active_filter {
-        push_hint(&mut spans, "esc", "clear filter");
-        push_hint(&mut spans, "q", "quit");
+    if let Some(filter) = active_filter {
+        push_action_hint(
+            &mut spans,
+            key_pair(keymap, Action::Back, None),
+            tf(Text::FooterClearFilter, &[("filter", filter)]),
+        );
+        push_action_hint(
+            &mut spans,
+            key_pair(keymap, Action::Quit, None),
+            t(Text::FooterQuit),
+        );
     } else {
-        push_hint(&mut spans, "q/esc", "quit");
+        push_action_hint(
+            &mut spans,
+            key_pair(keymap, Action::Quit, Action::Back),
+            t(Text::FooterQuit),
+        );
     }
     spans
 }
 
-fn push_hint(spans: &mut Vec<Span<'static>>, key: &'static str, label: impl Into<String>) {
+/// The footer keys of one action or a pair like `j/k`, skipping unbound ones.
+fn key_pair(keymap: &Keymap, first: Action, second: impl Into<Option<Action>>) -> Option<String> {
+    let second = second.into().and_then(|action| keymap.label(action));
+    match (keymap.label(first), second) {
+        (Some("pageup"), Some("pagedown")) => Some("pg↑↓".to_string()),
+        (Some(first), Some(second)) => Some(format!("{first}/{second}")),
+        (Some(key), None) | (None, Some(key)) => Some(key.to_string()),
+        (None, None) => None,
+    }
+}
+
+fn push_action_hint(spans: &mut Vec<Span<'static>>, key: Option<String>, label: impl Into<String>) {
+    if let Some(key) = key {
+        push_hint(spans, key, label);
+    }
+}
+
+fn push_hint(spans: &mut Vec<Span<'static>>, key: impl Into<String>, label: impl Into<String>) {
     spans.push(key_span(key));
     spans.push(label_span(" "));
     spans.push(label_span(label));
     spans.push(label_span("  "));
 }
 
-fn key_span(key: &'static str) -> Span<'static> {
-    Span::styled(key, theme::styles::ACCENT)
+fn key_span(key: impl Into<String>) -> Span<'static> {
+    Span::styled(key.into(), theme::styles::accent())
 }
 
 fn label_span(label: impl Into<String>) -> Span<'static> {
-    Span::styled(label.into(), theme::styles::MUTED)
+    Span::styled(label.into(), theme::styles::muted())
 }
 
 fn render_footer_line(frame: &mut Frame, area: Rect, line: Line<'static>, style: Style) {
@@ -126,7 +232,8 @@ fn render_footer_line(frame: &mut Frame, area: Rect, line: Line<'static>, style:
 mod tests {
     use super::*;
 
-    use crate::git::BranchScope;
+    use crate::config::KeyBinding;
+    use crate::git::{BranchScope, FetchProgress};
     use crate::tui::app::{BranchInfo, Msg};
 
     #[test]
@@ -152,14 +259,25 @@ mod tests {
     #[test]
     fn test_normal_footer_tail_with_active_filter() {
         assert_eq!(
-            spans_text(&normal_footer_tail(true)),
-            "esc clear filter  q quit  "
+            spans_text(&normal_footer_tail(&Keymap::default(), Some("login p:1"))),
+            "esc clear filter \"login p:1\"  q quit  "
+        );
+    }
+
+    #[test]
+    fn test_filter_footer_shows_the_filter_being_typed() {
+        assert!(
+            spans_text(&filter_footer_spans("feat 12"))
+                .starts_with(" /feat 12  type filter  backspace delete")
         );
     }
 
     #[test]
     fn test_normal_footer_tail_without_active_filter() {
-        assert_eq!(spans_text(&normal_footer_tail(false)), "q/esc quit  ");
+        assert_eq!(
+            spans_text(&normal_footer_tail(&Keymap::default(), None)),
+            "q/esc quit  "
+        );
     }
 
     #[test]
@@ -186,6 +304,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normal_footer_shows_git_fetch_progress() {
+        let mut app = test_app(None);
+        assert!(app.start_git_fetch());
+        assert!(!app.start_git_fetch());
+        assert!(spans_text(&normal_footer_spans(&app)).ends_with("fetching origin...  "));
+
+        app.set_git_fetch_progress(FetchProgress {
+            phase: "Receiving objects".to_string(),
+            percent: 45,
+        });
+        assert!(
+            spans_text(&normal_footer_spans(&app))
+                .ends_with("fetching origin: receiving objects 45%  ")
+        );
+
+        app.finish_git_fetch();
+        assert!(!spans_text(&normal_footer_spans(&app)).contains("fetching"));
+    }
+
+    #[test]
+    fn test_normal_footer_shows_configured_keys() {
+        let keys = [
+            ("open".to_string(), KeyBinding::One("x".to_string())),
+            ("toggle_protected".to_string(), KeyBinding::Many(vec![])),
+        ];
+        let keymap = Keymap::from_config(&keys.into_iter().collect()).unwrap();
+        let app = test_app(None).with_keymap(keymap);
+
+        assert_eq!(
+            spans_text(&normal_footer_spans(&app)),
+            " j/k navigate  / filter  t toggle remote  x open  pg↑↓ scroll  d delete  q/esc quit  "
+        );
+    }
+
+    #[test]
+    fn test_normal_footer_ends_with_update_hint() {
+        let app = test_app(None).with_update_hint(Some("1.4.0".to_string()));
+
+        assert!(
+            spans_text(&normal_footer_spans(&app)).ends_with("q/esc quit  cazdo 1.4.0 available")
+        );
+    }
+
     fn spans_text(spans: &[Span<'static>]) -> String {
         spans
             .iter()
@@ -205,6 +367,7 @@ mod tests {
                 is_current: false,
                 is_protected: false,
                 is_stale: false,
+                origin_upstream: None,
             }],
             vec![],
         )
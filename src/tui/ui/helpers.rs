@@ -1,14 +1,42 @@
-use chrono::{TimeZone, Utc};
-use chrono_humanize::HumanTime;
 use ratatui::{
     Frame,
     layout::Rect,
     style::Style,
     text::{Line, Span},
-    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState},
+    widgets::{Block, Borders, Scrollbar, ScrollbarOrientation, ScrollbarState},
 };
 
+use crate::azure_devops::PullRequest;
 use crate::git::RemoteStatus;
+use crate::i18n::{Text, t, tf};
+use crate::timestamp::format_timestamp;
+use crate::tui::theme;
+
+/// A panel frame: borders, or in plain mode only the title line, so screen
+/// readers do not read out box-drawing characters.
+pub fn panel_block(plain: bool) -> Block<'static> {
+    if plain {
+        Block::default()
+    } else {
+        Block::default().borders(Borders::ALL)
+    }
+}
+
+/// `symbol` between items on one line, or a semicolon in plain mode.
+pub fn separator(plain: bool, symbol: &'static str) -> &'static str {
+    if plain { "; " } else { symbol }
+}
+
+/// `text` of an error, labeled in plain mode where its color alone would
+/// not tell.
+pub fn error_text(plain: bool, text: impl Into<String>) -> String {
+    let text = text.into();
+    if plain {
+        format!("{} {}", t(Text::ErrorLabel), text)
+    } else {
+        text
+    }
+}
 
 /// Helper to render a consistent scrollbar
 pub fn render_scrollbar(
@@ -71,30 +99,49 @@ pub fn wrap_text(s: &str, max_width: usize) -> Vec<String> {
     lines
 }
 
-/// Format relative time from Unix timestamp
+/// Format a Unix timestamp as set in `[display]` (relative by default)
 pub fn format_relative_time(timestamp: i64) -> String {
-    match Utc.timestamp_opt(timestamp, 0) {
-        chrono::LocalResult::Single(dt) => HumanTime::from(dt).to_string(),
-        _ => "unknown".to_string(),
+    format_timestamp(timestamp).unwrap_or_else(|| t(Text::Unknown).to_string())
+}
+
+/// The status label of a pull request and its style: draft, active, or
+/// completed.
+pub fn pull_request_status(pr: &PullRequest) -> (&str, Style) {
+    match pr.status.as_str() {
+        _ if pr.is_draft => (t(Text::PullRequestDraft), theme::styles::muted()),
+        "active" => (t(Text::PullRequestActive), theme::styles::success()),
+        "completed" => (t(Text::PullRequestCompleted), theme::styles::accent()),
+        status => (status, theme::styles::muted()),
     }
 }
 
-/// Format remote status for display
-pub fn format_remote_status(status: &RemoteStatus) -> (String, ratatui::style::Color) {
+/// Format remote status for display; plain mode spells out the arrows.
+pub fn format_remote_status(status: &RemoteStatus, plain: bool) -> (String, ratatui::style::Color) {
     use ratatui::style::Color;
 
+    let count = |text: Text, n: &usize| tf(text, &[("count", &n.to_string())]);
     match status {
-        RemoteStatus::LocalOnly => ("local only".to_string(), Color::DarkGray),
+        RemoteStatus::Ahead(n) if plain => (count(Text::Ahead, n), Color::Yellow),
+        RemoteStatus::Behind(n) if plain => (count(Text::Behind, n), Color::Yellow),
+        RemoteStatus::Diverged { ahead, behind } if plain => (
+            format!(
+                "{}, {}",
+                count(Text::Ahead, ahead),
+                count(Text::Behind, behind)
+            ),
+            Color::Yellow,
+        ),
+        RemoteStatus::LocalOnly => (t(Text::LocalOnly).to_string(), Color::DarkGray),
         RemoteStatus::RemoteTracking => {
             unreachable!("remote-tracking branches are rendered separately")
         }
-        RemoteStatus::UpToDate => ("up to date".to_string(), Color::Green),
+        RemoteStatus::UpToDate => (t(Text::UpToDate).to_string(), Color::Green),
         RemoteStatus::Ahead(n) => (format!("↑{}", n), Color::Yellow),
         RemoteStatus::Behind(n) => (format!("↓{}", n), Color::Yellow),
         RemoteStatus::Diverged { ahead, behind } => {
             (format!("↑{} ↓{}", ahead, behind), Color::Yellow)
         }
-        RemoteStatus::Gone => ("remote gone".to_string(), Color::Red),
+        RemoteStatus::Gone => (t(Text::RemoteGone).to_string(), Color::Red),
     }
 }
 
@@ -105,6 +152,6 @@ mod tests {
     #[test]
     #[should_panic(expected = "remote-tracking branches are rendered separately")]
     fn test_format_remote_status_rejects_remote_tracking() {
-        let _ = format_remote_status(&RemoteStatus::RemoteTracking);
+        let _ = format_remote_status(&RemoteStatus::RemoteTracking, false);
     }
 }
@@ -0,0 +1,173 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+};
+
+use crate::i18n::{Text, t, tf};
+use crate::tui::app::{InspectorContent, JsonInspector};
+use crate::tui::theme;
+
+use super::helpers::panel_block;
+
+/// Share of the screen the inspector covers, in percent
+const INSPECTOR_SIZE_PERCENT: u16 = 90;
+
+/// Render the JSON inspector over the panels, returning how many lines it
+/// shows at once.
+pub fn render_inspector(frame: &mut Frame, inspector: &JsonInspector, plain: bool) -> u16 {
+    let area = inspector_rect(frame.area());
+    let lines: &[String] = match &inspector.content {
+        InspectorContent::Loaded(lines) => lines,
+        _ => &[],
+    };
+    let block = panel_block(plain)
+        .border_style(theme::ui::border())
+        .title(Line::from(Span::styled(
+            format!(
+                " {} ",
+                tf(
+                    Text::InspectorTitle,
+                    &[("id", &inspector.work_item_id.to_string())]
+                )
+            ),
+            theme::ui::title(),
+        )))
+        .title_bottom(
+            Line::from(Span::styled(
+                format!(
+                    " j/k {}  g/G {}  esc {} ",
+                    t(Text::FooterScroll),
+                    t(Text::FooterTopBottom),
+                    t(Text::FooterClose)
+                ),
+                theme::styles::muted(),
+            ))
+            .right_aligned(),
+        );
+    let inner = block.inner(area);
+
+    let content: Vec<Line> = match &inspector.content {
+        InspectorContent::Loading => vec![Line::from(Span::styled(
+            t(Text::InspectorLoading),
+            theme::styles::warning(),
+        ))],
+        InspectorContent::Error(error) => vec![Line::from(Span::styled(
+            tf(Text::InspectorError, &[("error", error)]),
+            theme::styles::error(),
+        ))],
+        InspectorContent::Loaded(_) => lines
+            .iter()
+            .skip(inspector.scroll)
+            .take(inner.height as usize)
+            .map(|line| highlight_json_line(line))
+            .collect(),
+    };
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(content).block(block), area);
+    if !plain {
+        super::helpers::render_scrollbar(frame, inner, lines.len(), inspector.scroll);
+    }
+    inner.height
+}
+
+fn inspector_rect(r: Rect) -> Rect {
+    let width = r.width * INSPECTOR_SIZE_PERCENT / 100;
+    let height = r.height * INSPECTOR_SIZE_PERCENT / 100;
+    Rect::new(
+        r.x + (r.width - width) / 2,
+        r.y + (r.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Color one line of pretty-printed JSON: keys, strings, other values, and
+/// punctuation each get a style.
+fn highlight_json_line(line: &str) -> Line<'_> {
+    let mut spans = Vec::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let (len, style) = match c {
+            '"' => {
+                let len = string_len(rest);
+                let is_key = rest[len..].trim_start().starts_with(':');
+                let style = if is_key {
+                    theme::styles::accent()
+                } else {
+                    theme::styles::success()
+                };
+                (len, style)
+            }
+            c if c.is_whitespace() => (
+                rest.find(|c: char| !c.is_whitespace())
+                    .unwrap_or(rest.len()),
+                Style::default(),
+            ),
+            '{' | '}' | '[' | ']' | ',' | ':' => (1, theme::styles::muted()),
+            _ => (
+                rest.find(|c: char| {
+                    c.is_whitespace() || matches!(c, '{' | '}' | '[' | ']' | ',' | ':' | '"')
+                })
+                .unwrap_or(rest.len()),
+                theme::styles::warning(),
+            ),
+        };
+        spans.push(Span::styled(&rest[..len], style));
+        rest = &rest[len..];
+    }
+    Line::from(spans)
+}
+
+/// Length of the string literal `text` starts with, quotes included, or all
+/// of `text` if it is not closed.
+fn string_len(text: &str) -> usize {
+    let mut escaped = false;
+    for (index, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return index + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styled(line: &Line) -> Vec<(String, Style)> {
+        line.spans
+            .iter()
+            .filter(|span| !span.content.trim().is_empty())
+            .map(|span| (span.content.to_string(), span.style))
+            .collect()
+    }
+
+    #[test]
+    fn keys_strings_and_values_are_styled_apart() {
+        let line = highlight_json_line(r#"    "System.Title": "Say \"hi\": now","#);
+        assert_eq!(
+            styled(&line),
+            [
+                ("\"System.Title\"".to_string(), theme::styles::accent()),
+                (":".to_string(), theme::styles::muted()),
+                (r#""Say \"hi\": now""#.to_string(), theme::styles::success()),
+                (",".to_string(), theme::styles::muted()),
+            ]
+        );
+
+        let line = highlight_json_line(r#"  "rev": 12, "ok": true"#);
+        let values: Vec<_> = styled(&line)
+            .into_iter()
+            .filter(|(_, style)| *style == theme::styles::warning())
+            .map(|(text, _)| text)
+            .collect();
+        assert_eq!(values, ["12", "true"]);
+    }
+}
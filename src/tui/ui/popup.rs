@@ -1,85 +1,359 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+    widgets::{Clear, Padding, Paragraph, Wrap},
 };
 
+use crate::azure_devops::{MergeStrategy, WorkItemState};
+use crate::i18n::{Text, t, tf};
+use crate::tui::app::{CompletionForm, DeleteOutcome, StateMenu, StateMenuContent};
 use crate::tui::theme;
 
-/// Render the delete or prune confirmation popup
-pub fn render_confirm_popup(frame: &mut Frame, branch_name: &str, is_remote: bool, is_prune: bool) {
-    let content = if is_prune {
-        vec![
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("Prune stale tracking ref for "),
-                Span::styled(branch_name, theme::branch::CURRENT),
-                Span::raw("?"),
-            ]),
-            Line::from(Span::raw("(branch no longer exists on origin)")),
-            Line::from(""),
-            make_key_hint(&["y"], "confirm"),
-            make_key_hint(&["n", "Esc"], "cancel"),
-        ]
+use super::helpers::panel_block;
+
+/// Render the delete or prune confirmation popup. A local branch with a
+/// branch on origin, `remote`, offers to delete both.
+pub fn render_confirm_popup(
+    frame: &mut Frame,
+    branch_name: &str,
+    is_remote: bool,
+    is_prune: bool,
+    remote: Option<&str>,
+    plain: bool,
+) {
+    let question = if is_prune {
+        Text::ConfirmPrune
+    } else if is_remote {
+        Text::ConfirmDeleteRemote
     } else {
-        let branch_kind = if is_remote { "remote branch" } else { "branch" };
-        vec![
-            Line::from(""),
-            Line::from(vec![
-                Span::raw(format!("Are you sure you want to delete {} ", branch_kind)),
-                Span::styled(branch_name, theme::branch::CURRENT),
-                Span::raw("?"),
-            ]),
-            Line::from(""),
-            make_key_hint(&["y"], "confirm"),
-            make_key_hint(&["n", "Esc"], "cancel"),
-        ]
+        Text::ConfirmDelete
     };
+    let mut content = vec![
+        Line::from(""),
+        styled_placeholder(
+            t(question),
+            "{branch}",
+            vec![Span::styled(branch_name, theme::branch::current())],
+        ),
+    ];
+    if is_prune {
+        content.push(Line::from(Span::raw(t(Text::PruneReason))));
+    }
+    content.push(Line::from(""));
+    match remote {
+        Some(remote) => content.extend([
+            make_key_hint(&["y"], Text::HintDeleteLocalOnly),
+            styled_placeholder(
+                // The key is styled in after the remote is filled in
+                &tf(
+                    Text::HintDeleteWithRemote,
+                    &[("keys", "{keys}"), ("remote", remote)],
+                ),
+                "{keys}",
+                vec![Span::styled("Y", theme::ui::title())],
+            ),
+        ]),
+        None => content.push(make_key_hint(&["y"], Text::HintConfirm)),
+    }
+    content.push(make_key_hint(&["n", "Esc"], Text::HintCancel));
 
     let title = if is_prune {
-        " Prune Stale Branch "
+        t(Text::PruneTitle)
     } else {
-        " Delete Branch "
+        t(Text::DeleteTitle)
     };
 
     let area = centered_rect(frame.area());
-    render_popup_impl(frame, title, content, area);
+    render_popup_impl(frame, &format!(" {title} "), content, area, plain);
+}
+
+/// Render the confirmation of deleting the marked branches, naming them.
+pub fn render_confirm_marked_popup(frame: &mut Frame, branches: &[&str], plain: bool) {
+    let mut content = vec![
+        Line::from(""),
+        Line::from(tf(
+            Text::ConfirmDeleteMarked,
+            &[("count", &branches.len().to_string())],
+        )),
+    ];
+    content.extend(
+        branches
+            .iter()
+            .map(|name| Line::from(Span::styled(*name, theme::branch::current()))),
+    );
+    content.push(Line::from(""));
+    content.push(make_key_hint(&["y"], Text::HintConfirm));
+    content.push(make_key_hint(&["n", "Esc"], Text::HintCancel));
+
+    let area = fitted_rect(frame.area(), content.len());
+    render_popup_impl(
+        frame,
+        &format!(" {} ", t(Text::DeleteMarkedTitle)),
+        content,
+        area,
+        plain,
+    );
+}
+
+/// Render how deleting each marked branch went.
+pub fn render_delete_results_popup(frame: &mut Frame, outcomes: &[DeleteOutcome], plain: bool) {
+    let mut content = vec![Line::from("")];
+    content.extend(outcomes.iter().map(|outcome| {
+        let (mark, style) = match (outcome.is_error, plain) {
+            (false, false) => ("\u{2713} ", theme::styles::success()),
+            (true, false) => ("\u{2717} ", theme::styles::error()),
+            (false, true) => ("OK: ", theme::styles::success()),
+            (true, true) => ("ERROR: ", theme::styles::error()),
+        };
+        Line::from(Span::styled(format!("{mark}{}", outcome.message), style))
+    }));
+    content.push(Line::from(""));
+    content.push(make_key_hint(&["Enter", "Esc"], Text::HintDismiss));
+
+    let deleted = outcomes.iter().filter(|outcome| !outcome.is_error).count();
+    let title = tf(
+        Text::DeleteResultsTitle,
+        &[
+            ("deleted", &deleted.to_string()),
+            ("count", &outcomes.len().to_string()),
+        ],
+    );
+    let area = fitted_rect(frame.area(), content.len());
+    render_popup_impl(frame, &format!(" {title} "), content, area, plain);
 }
 
 /// Render an error popup with the given message
-pub fn render_error_popup(frame: &mut Frame, message: &str) {
+pub fn render_error_popup(frame: &mut Frame, message: &str, plain: bool) {
     let content = vec![
         Line::from(""),
-        Line::from(Span::styled(message, theme::styles::ERROR)),
+        Line::from(Span::styled(message, theme::styles::error())),
+        Line::from(""),
+        make_key_hint(&["Enter", "Esc"], Text::HintDismiss),
+    ];
+
+    let area = centered_rect(frame.area());
+    render_popup_impl(
+        frame,
+        &format!(" {} ", t(Text::ErrorTitle)),
+        content,
+        area,
+        plain,
+    );
+}
+
+/// Render the states a work item can move to, the highlighted one marked
+pub fn render_state_menu(frame: &mut Frame, menu: &StateMenu, plain: bool) {
+    let content: Vec<Line> = match &menu.content {
+        StateMenuContent::Loading => vec![Line::from(Span::styled(
+            t(Text::StateMenuLoading),
+            theme::styles::warning(),
+        ))],
+        StateMenuContent::Error(error) => vec![Line::from(Span::styled(
+            tf(Text::StateMenuError, &[("error", error)]),
+            theme::styles::error(),
+        ))],
+        StateMenuContent::Loaded(transitions) if transitions.is_empty() => {
+            vec![Line::from(Span::styled(
+                t(Text::StateMenuEmpty),
+                theme::styles::muted(),
+            ))]
+        }
+        StateMenuContent::Loaded(transitions) => transitions
+            .iter()
+            .enumerate()
+            .map(|(index, transition)| {
+                let selected = index == menu.selected;
+                let marker = match (selected, plain) {
+                    (false, false) => "  ".to_string(),
+                    (true, false) => "\u{25BA} ".to_string(),
+                    (false, true) => String::new(),
+                    (true, true) => format!("{} ", t(Text::SelectedLabel)),
+                };
+                let state: WorkItemState = transition.state.parse().unwrap();
+                let mut state_style = Style::default().fg(state.color());
+                if selected {
+                    state_style = state_style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+                }
+                Line::from(vec![
+                    Span::styled(marker, theme::ui::title()),
+                    Span::styled(transition.state.as_str(), state_style),
+                    Span::styled(
+                        format!(" ({})", category_label(&transition.category)),
+                        theme::styles::muted(),
+                    ),
+                ])
+            })
+            .collect(),
+    };
+
+    let block = panel_block(plain)
+        .border_style(theme::ui::border())
+        .padding(Padding::horizontal(1))
+        .title(Line::from(Span::styled(
+            format!(
+                " {} ",
+                tf(
+                    Text::StateMenuTitle,
+                    &[("id", &menu.work_item_id.to_string())]
+                )
+            ),
+            theme::ui::title(),
+        )))
+        .title_bottom(
+            Line::from(Span::styled(
+                format!(
+                    " j/k {}  enter {}  esc {} ",
+                    t(Text::FooterNavigate),
+                    t(Text::FooterSelect),
+                    t(Text::FooterClose)
+                ),
+                theme::styles::muted(),
+            ))
+            .right_aligned(),
+        );
+
+    let area = centered_rect(frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(content).block(block), area);
+}
+
+/// Render the options for completing a pull request
+pub fn render_completion_form(frame: &mut Frame, form: &CompletionForm, plain: bool) {
+    let options = &form.options;
+    let checkbox = |checked: bool| if checked { "[x]" } else { "[ ]" };
+    let rows = [
+        format!(
+            "{}: < {} >",
+            t(Text::MergeStrategyLabel),
+            merge_strategy_label(options.merge_strategy)
+        ),
+        format!(
+            "{} {}",
+            checkbox(options.delete_source_branch),
+            t(Text::DeleteSourceBranch)
+        ),
+        format!(
+            "{} {}",
+            checkbox(options.complete_work_items),
+            t(Text::CompleteWorkItems)
+        ),
+    ];
+
+    let mut content = vec![
+        Line::from(Span::styled(
+            form.pull_request.title.as_str(),
+            theme::styles::text().add_modifier(Modifier::BOLD),
+        )),
         Line::from(""),
-        make_key_hint(&["Enter", "Esc"], "Dismiss"),
     ];
+    content.extend(rows.into_iter().enumerate().map(|(index, row)| {
+        let selected = index == form.selected;
+        let marker = match (selected, plain) {
+            (false, false) => "  ".to_string(),
+            (true, false) => "\u{25BA} ".to_string(),
+            (false, true) => String::new(),
+            (true, true) => format!("{} ", t(Text::SelectedLabel)),
+        };
+        let style = if selected {
+            theme::ui::selected().add_modifier(Modifier::BOLD)
+        } else {
+            theme::styles::text()
+        };
+        Line::from(vec![
+            Span::styled(marker, theme::ui::title()),
+            Span::styled(row, style),
+        ])
+    }));
+
+    let block = panel_block(plain)
+        .border_style(theme::ui::border())
+        .padding(Padding::horizontal(1))
+        .title(Line::from(Span::styled(
+            format!(
+                " {} ",
+                tf(
+                    Text::CompletionTitle,
+                    &[("id", &form.pull_request.id.to_string())]
+                )
+            ),
+            theme::ui::title(),
+        )))
+        .title_bottom(
+            Line::from(Span::styled(
+                format!(
+                    " j/k {}  h/l {}  enter {}  esc {} ",
+                    t(Text::FooterNavigate),
+                    t(Text::FooterChange),
+                    t(Text::FooterComplete),
+                    t(Text::FooterCancel)
+                ),
+                theme::styles::muted(),
+            ))
+            .right_aligned(),
+        );
 
     let area = centered_rect(frame.area());
-    render_popup_impl(frame, " Error ", content, area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(content)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
 }
 
-fn make_key_hint<'a>(keys: &[&'a str], action: &str) -> Line<'a> {
-    let mut spans = vec![Span::raw("Press ")];
+fn merge_strategy_label(strategy: MergeStrategy) -> &'static str {
+    match strategy {
+        MergeStrategy::NoFastForward => t(Text::MergeNoFastForward),
+        MergeStrategy::Squash => t(Text::MergeSquash),
+        MergeStrategy::Rebase => t(Text::MergeRebase),
+        MergeStrategy::RebaseMerge => t(Text::MergeRebaseMerge),
+    }
+}
+
+/// The name of a state category, or the category as the server sent it
+/// when it is not one of the five Azure DevOps defines.
+fn category_label(category: &str) -> &str {
+    match category {
+        "Proposed" => t(Text::CategoryProposed),
+        "InProgress" => t(Text::CategoryInProgress),
+        "Resolved" => t(Text::CategoryResolved),
+        "Completed" => t(Text::CategoryCompleted),
+        "Removed" => t(Text::CategoryRemoved),
+        _ => category,
+    }
+}
+
+fn make_key_hint<'a>(keys: &[&'a str], hint: Text) -> Line<'a> {
+    let mut key_spans = Vec::new();
     for (i, &key) in keys.iter().enumerate() {
-        spans.push(Span::styled(key, theme::ui::TITLE));
+        key_spans.push(Span::styled(key, theme::ui::title()));
         if i < keys.len() - 1 {
-            spans.push(Span::raw(" or "));
+            key_spans.push(Span::raw(t(Text::KeysOr)));
         }
     }
-    spans.push(Span::raw(format!(" to {}.", action)));
-    Line::from(spans)
+    styled_placeholder(t(hint), "{keys}", key_spans)
 }
 
-fn render_popup_impl(frame: &mut Frame, title: &str, content: Vec<Line>, area: Rect) {
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(theme::ui::BORDER_ERROR)
+/// `template` as a line with `spans` in place of `placeholder`, so the
+/// translation decides where the styled part goes.
+fn styled_placeholder<'a>(template: &str, placeholder: &str, spans: Vec<Span<'a>>) -> Line<'a> {
+    let (before, after) = template.split_once(placeholder).unwrap_or((template, ""));
+    let mut line = vec![Span::raw(before.to_string())];
+    line.extend(spans);
+    line.push(Span::raw(after.to_string()));
+    Line::from(line)
+}
+
+fn render_popup_impl(frame: &mut Frame, title: &str, content: Vec<Line>, area: Rect, plain: bool) {
+    let block = panel_block(plain)
+        .border_style(theme::ui::border_error())
         .padding(Padding::horizontal(1))
         .title(Line::from(vec![Span::styled(
             title,
-            theme::ui::TITLE_ERROR,
+            theme::ui::title_error(),
         )]));
 
     let paragraph = Paragraph::new(content)
@@ -91,6 +365,21 @@ fn render_popup_impl(frame: &mut Frame, title: &str, content: Vec<Line>, area: R
     frame.render_widget(paragraph, area);
 }
 
+/// The popup rect, grown to fit `lines` of content where the screen allows.
+fn fitted_rect(r: Rect, lines: usize) -> Rect {
+    let (popup_width, popup_height) = theme::layout::POPUP_SIZE;
+    let height = popup_height
+        .max(u16::try_from(lines + 2).unwrap_or(u16::MAX))
+        .min(r.height.saturating_sub(3));
+    let width = popup_width.min(r.width.saturating_sub(2));
+    Rect::new(
+        r.x + r.width.saturating_sub(width) / 2,
+        r.y + r.height.saturating_sub(height) / 2,
+        width,
+        height,
+    )
+}
+
 /// Get the popup rect
 fn centered_rect(r: Rect) -> Rect {
     let (popup_width, popup_height) = theme::layout::POPUP_SIZE;
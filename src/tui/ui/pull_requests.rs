@@ -0,0 +1,182 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::azure_devops::{PullRequest, Reviewer, Vote};
+use crate::i18n::{Text, t, tf};
+use crate::tui::app::{App, ClientState};
+use crate::tui::theme;
+
+use super::helpers::{panel_block, pull_request_status, separator};
+
+/// Render the pull request tab in place of the panels
+pub fn render_pull_requests(frame: &mut Frame, app: &App, area: Rect) {
+    let list = app.pull_request_list();
+    let plain = app.plain();
+
+    let heading = match app.repository() {
+        Ok(repository) => tf(
+            Text::PullRequestListTitle,
+            &[("repository", &repository.repository)],
+        ),
+        Err(_) => t(Text::PullRequestListHeading).to_string(),
+    };
+    let mut title = vec![Span::styled(format!(" {heading} "), theme::ui::title())];
+    if list.loading && !list.entries.is_empty() {
+        title.push(Span::styled(
+            format!("{} ", t(Text::ActivityRefreshing)),
+            theme::styles::warning(),
+        ));
+    }
+    let block = panel_block(plain)
+        .border_style(theme::ui::border())
+        .title(Line::from(title))
+        .title_bottom(
+            Line::from(Span::styled(
+                format!(
+                    " j/k {}  o {}  r {}  tab {} ",
+                    t(Text::FooterNavigate),
+                    t(Text::FooterOpen),
+                    t(Text::FooterRefresh),
+                    t(Text::FooterBranches)
+                ),
+                theme::styles::muted(),
+            ))
+            .right_aligned(),
+        );
+
+    if list.entries.is_empty() {
+        let message = match (app.client_state(), &list.error) {
+            (ClientState::Unavailable(error), _) => Span::styled(
+                format!("  {}: {error}", t(Text::NotConnected)),
+                theme::styles::error(),
+            ),
+            (_, Some(error)) => Span::styled(
+                format!("  {}", tf(Text::PullRequestListError, &[("error", error)])),
+                theme::styles::error(),
+            ),
+            (ClientState::Ready, None) if !list.loading => Span::styled(
+                format!("  {}", t(Text::PullRequestListEmpty)),
+                theme::styles::muted(),
+            ),
+            _ => Span::styled(
+                format!("  {}", t(Text::LoadingPullRequests)),
+                theme::styles::warning(),
+            ),
+        };
+        let content = vec![Line::from(""), Line::from(message)];
+        frame.render_widget(Paragraph::new(content).block(block), area);
+        return;
+    }
+
+    let mut items: Vec<ListItem> = Vec::new();
+    if let Some(error) = &list.error {
+        items.push(ListItem::new(Line::from(Span::styled(
+            tf(Text::PullRequestListError, &[("error", error)]),
+            theme::styles::error(),
+        ))));
+    }
+    let offset = items.len();
+    items.extend(list.entries.iter().enumerate().map(|(index, pr)| {
+        let marker = if plain && index == list.selected {
+            format!("{} ", t(Text::SelectedLabel))
+        } else {
+            String::new()
+        };
+        ListItem::new(vec![
+            headline(pr, marker),
+            branch_line(pr, plain),
+            reviewer_line(pr, plain),
+        ])
+    }));
+
+    let list_widget = List::new(items)
+        .block(block)
+        .highlight_style(theme::ui::selected().add_modifier(Modifier::BOLD))
+        .highlight_symbol(if plain { "" } else { "\u{25BA} " });
+    let mut state = ListState::default();
+    state.select(Some(offset + list.selected));
+    frame.render_stateful_widget(list_widget, area, &mut state);
+}
+
+/// The pull request's ID, status, and title.
+fn headline(pr: &PullRequest, marker: String) -> Line<'_> {
+    let (status, style) = pull_request_status(pr);
+    Line::from(vec![
+        Span::raw(marker),
+        Span::styled(
+            format!("!{} ", pr.id),
+            theme::styles::accent().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(status, style),
+        Span::raw("  "),
+        Span::styled(pr.title.as_str(), theme::styles::text()),
+    ])
+}
+
+/// Which branch merges into which, and who created the pull request.
+fn branch_line(pr: &PullRequest, plain: bool) -> Line<'static> {
+    let arrow = if plain { "->" } else { "→" };
+    let mut spans = vec![
+        Span::raw("    "),
+        Span::styled(
+            format!("{} {arrow} {}", pr.source_branch, pr.target_branch),
+            theme::styles::text(),
+        ),
+    ];
+    if let Some(name) = &pr.created_by {
+        spans.push(Span::styled(
+            format!(" {}", tf(Text::By, &[("name", name)])),
+            theme::styles::muted(),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Each reviewer with their vote, colored by it.
+fn reviewer_line(pr: &PullRequest, plain: bool) -> Line<'static> {
+    let mut spans = vec![Span::raw("    ")];
+    if pr.reviewers.is_empty() {
+        spans.push(Span::styled(
+            t(Text::PullRequestNoReviewers),
+            theme::styles::muted(),
+        ));
+        return Line::from(spans);
+    }
+    for (index, reviewer) in pr.reviewers.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::styled(
+                separator(plain, " · "),
+                theme::styles::muted(),
+            ));
+        }
+        spans.extend(reviewer_spans(reviewer));
+    }
+    Line::from(spans)
+}
+
+/// `Ana (required): approved`
+fn reviewer_spans(reviewer: &Reviewer) -> [Span<'static>; 2] {
+    let name = if reviewer.is_required {
+        format!("{} ({})", reviewer.name, t(Text::ReviewerRequired))
+    } else {
+        reviewer.name.clone()
+    };
+    let (vote, style) = match reviewer.vote {
+        Vote::Approved => (Text::VoteApproved, theme::styles::success()),
+        Vote::ApprovedWithSuggestions => {
+            (Text::VoteApprovedWithSuggestions, theme::styles::success())
+        }
+        Vote::Pending => (Text::VotePending, theme::styles::muted()),
+        Vote::WaitingForAuthor => (Text::VoteWaitingForAuthor, theme::styles::warning()),
+        Vote::Rejected => (Text::VoteRejected, theme::styles::error()),
+    };
+    [
+        Span::styled(format!("{name}: "), theme::styles::text()),
+        Span::styled(t(vote), style),
+    ]
+}
@@ -0,0 +1,151 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Clear, Padding, Paragraph},
+};
+
+use crate::i18n::{Text, t, tf};
+use crate::tui::app::{App, BranchStats, MergedBranches};
+use crate::tui::theme;
+
+use super::helpers::panel_block;
+
+/// Render the branch statistics popup: each group of counts as a bar chart
+/// whose bars are scaled to the number of local branches.
+pub fn render_branch_stats(frame: &mut Frame, app: &App) {
+    let (Some(stats), Some(merged)) = (
+        app.branch_stats(chrono::Utc::now().timestamp()),
+        app.merged_branches(),
+    ) else {
+        return;
+    };
+    let plain = app.plain();
+
+    let cleanup = &stats.cleanup;
+    let mut cleanup_rows = Vec::new();
+    if let Some(count) = cleanup.merged {
+        cleanup_rows.push((t(Text::StatsMerged).to_string(), count));
+    }
+    cleanup_rows.push((t(Text::RemoteGone).to_string(), cleanup.gone));
+    cleanup_rows.push((t(Text::StatsCleanupTotal).to_string(), cleanup.total));
+    let sections = [
+        (Text::StatsByState, &stats.by_state),
+        (Text::StatsByAge, &stats.by_age),
+        (Text::StatsByRemote, &stats.by_remote),
+        (Text::StatsCleanup, &cleanup_rows),
+    ];
+
+    let frame_area = frame.area();
+    let width = theme::layout::STATS_WIDTH.min(frame_area.width.saturating_sub(2));
+    // Two columns of border and two of padding
+    let chart = Chart::new(&stats, &sections, width.saturating_sub(4), plain);
+    let mut content = Vec::new();
+    for (index, (heading, rows)) in sections.iter().enumerate() {
+        if index > 0 {
+            content.push(Line::from(""));
+        }
+        content.push(Line::from(Span::styled(
+            t(*heading),
+            theme::styles::text().add_modifier(Modifier::BOLD),
+        )));
+        content.extend(rows.iter().map(|(label, count)| chart.row(label, *count)));
+    }
+    match merged {
+        MergedBranches::Checking => content.push(Line::from(Span::styled(
+            format!("  {}", t(Text::StatsCheckingMerged)),
+            theme::styles::warning(),
+        ))),
+        MergedBranches::Failed(error) => content.push(Line::from(Span::styled(
+            format!("  {}", tf(Text::StatsMergedFailed, &[("error", error)])),
+            theme::styles::error(),
+        ))),
+        MergedBranches::Checked(_) => {}
+    }
+
+    let block = panel_block(plain)
+        .border_style(theme::ui::border())
+        .padding(Padding::horizontal(1))
+        .title(Line::from(Span::styled(
+            format!(
+                " {} ",
+                tf(
+                    Text::StatsTitle,
+                    &[("count", &stats.local_branches.to_string())]
+                )
+            ),
+            theme::ui::title(),
+        )))
+        .title_bottom(
+            Line::from(Span::styled(
+                format!(" esc {} ", t(Text::FooterClose)),
+                theme::styles::muted(),
+            ))
+            .right_aligned(),
+        );
+
+    let height = (content.len() as u16 + 2).min(frame_area.height.saturating_sub(2));
+    let area = Rect::new(
+        frame_area.x + frame_area.width.saturating_sub(width) / 2,
+        frame_area.y + frame_area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(content).block(block), area);
+}
+
+/// Column widths shared by all sections, so the bars line up.
+struct Chart {
+    label_width: usize,
+    count_width: usize,
+    bar_width: usize,
+    total: usize,
+    plain: bool,
+}
+
+impl Chart {
+    fn new(
+        stats: &BranchStats,
+        sections: &[(Text, &Vec<(String, usize)>)],
+        width: u16,
+        plain: bool,
+    ) -> Self {
+        let rows = || sections.iter().flat_map(|(_, rows)| rows.iter());
+        let label_width = rows()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let count_width = stats.local_branches.to_string().len();
+        let bar_width = usize::from(width).saturating_sub(label_width + count_width + 4);
+        Self {
+            label_width,
+            count_width,
+            bar_width,
+            total: stats.local_branches,
+            plain,
+        }
+    }
+
+    /// `  label  ████ count`, with any nonzero count getting at least one cell.
+    fn row(&self, label: &str, count: usize) -> Line<'static> {
+        let cells = if count == 0 || self.total == 0 {
+            0
+        } else {
+            (count * self.bar_width / self.total).max(1)
+        };
+        let bar = if self.plain { "#" } else { "\u{2588}" }.repeat(cells);
+        Line::from(vec![
+            Span::styled(
+                format!("  {label:<width$} ", width = self.label_width),
+                theme::styles::text(),
+            ),
+            Span::styled(bar, theme::styles::accent()),
+            Span::styled(
+                format!(" {count:>width$}", width = self.count_width),
+                theme::styles::muted(),
+            ),
+        ])
+    }
+}
@@ -0,0 +1,51 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Wrap},
+};
+
+use crate::i18n::{Text, t};
+use crate::tui::app::App;
+use crate::tui::keymap::Action;
+use crate::tui::theme;
+
+use super::helpers::panel_block;
+
+/// Toasts stack down from the top right corner of `area`, newest first.
+pub fn render_toasts(frame: &mut Frame, app: &App, area: Rect) {
+    let plain = app.plain();
+    let width = area.width.min(theme::layout::TOAST_WIDTH);
+    // Plain mode has no borders, only the text line
+    let height = if plain { 1 } else { 3 };
+    let jump = app.keymap().label(Action::JumpToChange);
+
+    for (index, toast) in app.toasts().enumerate() {
+        let y = area.y + 1 + index as u16 * height;
+        if y + height > area.bottom() {
+            break;
+        }
+        let toast_area = Rect::new(area.right() - width, y, width, height);
+        let mut block = panel_block(plain).border_style(theme::styles::accent());
+        if index == 0
+            && !plain
+            && let Some(jump) = jump
+        {
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {jump} {} ", t(Text::ToastJump)),
+                    theme::styles::muted(),
+                ))
+                .right_aligned(),
+            );
+        }
+        let text = Span::styled(toast.text.as_str(), theme::styles::text());
+        frame.render_widget(Clear, toast_area);
+        frame.render_widget(
+            Paragraph::new(Line::from(text))
+                .block(block)
+                .wrap(Wrap { trim: true }),
+            toast_area,
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::azure_devops::WorkItem;
+use crate::i18n::{Text, t, tf};
+use crate::tui::app::{App, ClientState};
+use crate::tui::theme;
+
+use super::helpers::panel_block;
+
+/// Render the work item list in place of the branch list
+pub fn render_work_items(frame: &mut Frame, app: &App, area: Rect) {
+    let list = app.work_item_list();
+    let plain = app.plain();
+
+    let mut title = vec![Span::styled(
+        format!(" {} ", t(Text::WorkItemListTitle)),
+        theme::ui::title(),
+    )];
+    if list.loading && !list.entries.is_empty() {
+        title.push(Span::styled(
+            format!("{} ", t(Text::ActivityRefreshing)),
+            theme::styles::warning(),
+        ));
+    }
+    let block = panel_block(plain)
+        .border_style(theme::ui::border())
+        .title(Line::from(title))
+        .title_bottom(
+            Line::from(Span::styled(
+                format!(
+                    " j/k {}  o {}  r {}  esc {} ",
+                    t(Text::FooterNavigate),
+                    t(Text::FooterOpen),
+                    t(Text::FooterRefresh),
+                    t(Text::FooterBranches)
+                ),
+                theme::styles::muted(),
+            ))
+            .right_aligned(),
+        );
+
+    if list.entries.is_empty() {
+        let message = match (app.client_state(), &list.error) {
+            (ClientState::Unavailable(error), _) => Span::styled(
+                format!("  {}: {error}", t(Text::NotConnected)),
+                theme::styles::error(),
+            ),
+            (_, Some(error)) => Span::styled(
+                format!("  {}", tf(Text::WorkItemListError, &[("error", error)])),
+                theme::styles::error(),
+            ),
+            (ClientState::Ready, None) if !list.loading => Span::styled(
+                format!("  {}", t(Text::WorkItemListEmpty)),
+                theme::styles::muted(),
+            ),
+            _ => Span::styled(
+                format!("  {}", t(Text::WorkItemListLoading)),
+                theme::styles::warning(),
+            ),
+        };
+        let content = vec![Line::from(""), Line::from(message)];
+        frame.render_widget(Paragraph::new(content).block(block), area);
+        return;
+    }
+
+    let mut items: Vec<ListItem> = Vec::new();
+    if let Some(error) = &list.error {
+        items.push(ListItem::new(Line::from(Span::styled(
+            tf(Text::WorkItemListError, &[("error", error)]),
+            theme::styles::error(),
+        ))));
+    }
+    let offset = items.len();
+    items.extend(list.entries.iter().enumerate().map(|(index, work_item)| {
+        let marker = if plain && index == list.selected {
+            format!("{} ", t(Text::SelectedLabel))
+        } else {
+            String::new()
+        };
+        ListItem::new(vec![
+            headline(work_item, marker),
+            state_line(work_item, app.emoji()),
+        ])
+    }));
+
+    let list_widget = List::new(items)
+        .block(block)
+        .highlight_style(theme::ui::selected().add_modifier(Modifier::BOLD))
+        .highlight_symbol(if plain { "" } else { "\u{25BA} " });
+    let mut state = ListState::default();
+    state.select(Some(offset + list.selected));
+    frame.render_stateful_widget(list_widget, area, &mut state);
+}
+
+/// The work item's ID and title.
+fn headline(work_item: &WorkItem, marker: String) -> Line<'_> {
+    Line::from(vec![
+        Span::raw(marker),
+        Span::styled(
+            format!("#{} ", work_item.id),
+            theme::styles::accent().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(work_item.title.as_str(), theme::styles::text()),
+    ])
+}
+
+/// The work item's type and state, colored by the state.
+fn state_line(work_item: &WorkItem, emoji: bool) -> Line<'static> {
+    let (type_icon, state_icon) = if emoji {
+        (work_item.work_item_type.icon(), work_item.state.icon())
+    } else {
+        (
+            work_item.work_item_type.ascii_icon(),
+            work_item.state.ascii_icon(),
+        )
+    };
+    Line::from(vec![
+        Span::raw("    "),
+        Span::styled(
+            format!("{type_icon} {}  ", work_item.work_item_type.display_name()),
+            theme::styles::muted(),
+        ),
+        Span::styled(
+            format!("{state_icon} {}", work_item.state.display_name()),
+            Style::default().fg(work_item.state.color()),
+        ),
+    ])
+}